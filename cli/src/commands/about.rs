@@ -0,0 +1,257 @@
+//! `mcmod about`: what to attach to a bug report.
+//!
+//! Gathers the mcmod version, OS and architecture, the Java and Gradle that
+//! builds would use, the active preferences and the project's mcmod.toml,
+//! and the dev runs' latest log and newest crash report. Values whose key
+//! looks like a secret (tokens, passwords, webhooks) or an email address are
+//! redacted, and the home directory is shortened to `~`, so the report can be
+//! posted publicly. It's printed as markdown, or bundled as a zip holding the
+//! report and the full files.
+
+use crate::commands::{build, wrapper};
+use crate::config::McmodConfig;
+use crate::error::{Result, ISSUE_URL};
+use crate::global_config;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Lines of each log shown in the markdown report; the zip has all of them.
+const LOG_TAIL: usize = 100;
+/// Key fragments whose values are redacted.
+const SECRET_KEYS: &[&str] = &["token", "secret", "password", "apikey", "api_key", "webhook", "email"];
+const REDACTED: &str = "\"<redacted>\"";
+/// File name for `--zip` without `--output`.
+const DEFAULT_ZIP: &str = "mcmod-debug-info.zip";
+
+/// A file bundled with the report: its name in the zip and its content.
+#[derive(Debug)]
+struct Attachment {
+    name: String,
+    content: String,
+}
+
+/// `mcmod about [--zip] [--output <file>]`. `dir` is the project, when the
+/// command runs inside one.
+pub fn run(dir: Option<&Path>, zip: bool, output: Option<&Path>) -> Result<()> {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok();
+    let (report, attachments) = collect(dir, &environment(dir));
+    let report = shorten_home(&report, home.as_deref());
+
+    if zip {
+        let out = output.map_or_else(|| PathBuf::from(DEFAULT_ZIP), Path::to_path_buf);
+        let attachments: Vec<(String, String)> = attachments
+            .into_iter()
+            .map(|a| (a.name, shorten_home(&a.content, home.as_deref())))
+            .collect();
+        let mut files: Vec<(&str, &[u8])> = vec![("report.md", report.as_bytes())];
+        files.extend(attachments.iter().map(|(name, content)| (name.as_str(), content.as_bytes())));
+        crate::util::write_binary(&out, &crate::jar::stored_zip(&files))?;
+        println!("{}", format!("  Wrote {}; attach it to your issue at {ISSUE_URL}", out.display()).green());
+    } else if let Some(out) = output {
+        crate::util::write_file(out, &report)?;
+        println!("{}", format!("  Wrote {}; paste it into your issue at {ISSUE_URL}", out.display()).green());
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
+
+/// Versions and settings outside the project, as (label, value) rows.
+fn environment(dir: Option<&Path>) -> Vec<(&'static str, String)> {
+    let java = match build::find_java() {
+        Ok((path, version)) => format!("{version} ({})", path.display()),
+        Err(_) => "not found".to_string(),
+    };
+    let wrapper = dir.and_then(wrapper::wrapper_version).unwrap_or_else(|| "none".to_string());
+    vec![
+        ("mcmod", env!("CARGO_PKG_VERSION").to_string()),
+        ("OS", format!("{} ({})", std::env::consts::OS, std::env::consts::ARCH)),
+        ("Java", java),
+        ("Gradle wrapper", wrapper),
+        ("Gradle on PATH", gradle_on_path().unwrap_or_else(|| "not found".to_string())),
+        ("Offline", if crate::util::is_offline() { "yes" } else { "no" }.to_string()),
+        ("Preferences profile", global_config::active_profile().unwrap_or_else(|_| "default".to_string())),
+    ]
+}
+
+/// The version `gradle --version` reports, e.g. `8.12`.
+fn gradle_on_path() -> Option<String> {
+    let gradle = if cfg!(windows) { "gradle.bat" } else { "gradle" };
+    let output = Command::new(gradle).arg("--version").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.lines().find_map(|line| line.strip_prefix("Gradle ")).map(|v| v.trim().to_string())
+}
+
+/// The markdown report and the files bundled with it.
+fn collect(dir: Option<&Path>, environment: &[(&str, String)]) -> (String, Vec<Attachment>) {
+    let mut report = String::from("# mcmod debug info\n\n| | |\n|---|---|\n");
+    for (label, value) in environment {
+        report.push_str(&format!("| {label} | {value} |\n"));
+    }
+    let mut attachments = Vec::new();
+
+    report.push_str("\n## Preferences\n\n");
+    match global_config::GlobalConfig::load().and_then(|c| c.export()) {
+        Ok(prefs) => {
+            let prefs = redact(&prefs);
+            report.push_str(&fenced("toml", &prefs));
+            attachments.push(Attachment { name: "config.toml".to_string(), content: prefs });
+        }
+        Err(e) => report.push_str(&format!("Couldn't load the preferences: {e}\n")),
+    }
+
+    report.push_str("\n## Project\n\n");
+    let Some(dir) = dir else {
+        report.push_str("Not run inside a project.\n");
+        return (report, attachments);
+    };
+    match std::fs::read_to_string(McmodConfig::config_path(dir)) {
+        Ok(content) => {
+            let content = redact(&content);
+            report.push_str(&fenced("toml", &content));
+            attachments.push(Attachment { name: "mcmod.toml".to_string(), content });
+        }
+        Err(e) => report.push_str(&format!("No readable mcmod.toml in {}: {e}\n", dir.display())),
+    }
+
+    report.push_str("\n## Logs\n\n");
+    let logs = logs(dir);
+    if logs.is_empty() {
+        report.push_str("No dev run logs or crash reports yet.\n");
+    }
+    for (rel, content) in logs {
+        let lines: Vec<&str> = content.lines().collect();
+        let tail = lines[lines.len().saturating_sub(LOG_TAIL)..].join("\n");
+        let shown = if lines.len() > LOG_TAIL { format!(" (last {LOG_TAIL} lines)") } else { String::new() };
+        report.push_str(&format!("### {rel}{shown}\n\n{}\n", fenced("", &tail)));
+        attachments.push(Attachment { name: rel, content });
+    }
+    (report, attachments)
+}
+
+/// `logs/latest.log` and the newest crash report of each dev run
+/// directory, as (path relative to the project, content).
+fn logs(dir: &Path) -> Vec<(String, String)> {
+    let run_dirs = McmodConfig::load(dir).map(|c| c.run_dirs()).unwrap_or_else(|_| vec!["run".to_string()]);
+    let mut logs = Vec::new();
+    for run in run_dirs {
+        let latest = format!("{run}/logs/latest.log");
+        let crash = newest_file(&dir.join(&run).join("crash-reports"))
+            .map(|name| format!("{run}/crash-reports/{name}"));
+        for rel in std::iter::once(latest).chain(crash) {
+            if let Ok(content) = std::fs::read_to_string(dir.join(&rel)) {
+                logs.push((rel, content));
+            }
+        }
+    }
+    logs
+}
+
+/// Name of the most recently modified file in `dir`.
+fn newest_file(dir: &Path) -> Option<String> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.file_name())))
+        .max()
+        .map(|(_, name)| name.to_string_lossy().into_owned())
+}
+
+/// `text` with the value of every `key = value` line whose key looks like a
+/// secret replaced.
+fn redact(text: &str) -> String {
+    let mut out: String = text
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _)) if is_secret(key) => format!("{} = {REDACTED}", key.trim_end()),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn is_secret(key: &str) -> bool {
+    let key = key.trim().trim_matches('"').to_ascii_lowercase();
+    !key.starts_with('#') && SECRET_KEYS.iter().any(|secret| key.contains(secret))
+}
+
+/// `text` with the home directory written as `~`.
+fn shorten_home(text: &str, home: Option<&str>) -> String {
+    match home {
+        Some(home) if home.len() > 1 => text.replace(home, "~"),
+        _ => text.to_string(),
+    }
+}
+
+/// `content` in a markdown code block.
+fn fenced(lang: &str, content: &str) -> String {
+    format!("```{lang}\n{}\n```\n", content.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jar::Jar;
+
+    #[test]
+    fn test_redact() {
+        let toml = "[defaults]\nauthor = \"Jane\"\nemail = \"jane@example.com\"\n# token = see docs\n\n[publish]\nmodrinth_token = \"mrp_abc\"\n";
+        assert_eq!(
+            redact(toml),
+            "[defaults]\nauthor = \"Jane\"\nemail = \"<redacted>\"\n# token = see docs\n\n[publish]\nmodrinth_token = \"<redacted>\"\n"
+        );
+        assert_eq!(shorten_home("/home/jane/mods/a", Some("/home/jane")), "~/mods/a");
+        assert_eq!(shorten_home("/a", Some("/")), "/a");
+    }
+
+    #[test]
+    fn test_report_and_zip() {
+        let dir = std::env::temp_dir().join(format!("mcmod_about_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("run/logs")).unwrap();
+        std::fs::create_dir_all(dir.join("run/crash-reports")).unwrap();
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        config.save(&dir).unwrap();
+        let log: Vec<String> = (1..=150).map(|n| format!("line {n}")).collect();
+        std::fs::write(dir.join("run/logs/latest.log"), log.join("\n")).unwrap();
+        std::fs::write(dir.join("run/crash-reports/crash-2026-01-01.txt"), "---- Minecraft Crash Report ----\n").unwrap();
+
+        let environment = [("mcmod", "1.0.0".to_string())];
+        let (report, attachments) = collect(Some(&dir), &environment);
+        assert!(report.starts_with("# mcmod debug info\n\n| | |\n|---|---|\n| mcmod | 1.0.0 |\n"));
+        assert!(report.contains("mod_id = \"testmod\""));
+        assert!(report.contains("### run/logs/latest.log (last 100 lines)\n\n```\nline 51\n"));
+        assert!(!report.contains("line 50\n"));
+        assert!(report.contains("### run/crash-reports/crash-2026-01-01.txt\n"));
+        let names: Vec<&str> = attachments.iter().map(|a| a.name.as_str()).collect();
+        assert!(names.contains(&"mcmod.toml") && names.contains(&"run/logs/latest.log"));
+
+        let out = dir.join("report.zip");
+        run(Some(&dir), true, Some(&out)).unwrap();
+        let zip = Jar::open(&out).unwrap();
+        assert!(zip.read_to_string("report.md").unwrap().unwrap().starts_with("# mcmod debug info"));
+        assert!(zip.read_to_string("run/logs/latest.log").unwrap().unwrap().starts_with("line 1\n"));
+
+        let (report, _) = collect(None, &environment);
+        assert!(report.contains("Not run inside a project."));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,8 +1,13 @@
-use crate::config::McmodConfig;
+use crate::commands::{add_dep, sync};
+use crate::config::{McmodConfig, VersionTarget};
 use crate::error::{McmodError, Result};
 use crate::gradle;
+use crate::lockfile::{Lockfile, BUILT_IN_SOURCE};
+use crate::sandbox;
 use crate::template::{self, render};
+use crate::conflict::write_generated;
 use crate::util::{derive_class_name, package_to_path, write_file};
+use crate::version_meta;
 use clap::ValueEnum;
 use colored::Colorize;
 use std::collections::HashMap;
@@ -13,45 +18,136 @@ use std::path::Path;
 pub enum Feature {
     Fabric,
     Neoforge,
+    Forge,
+    Quilt,
+    Sponge,
     Ci,
     Kotlin,
     Publishing,
     Testing,
+    UnitTests,
+    BuildInfo,
+    Paper,
+    Velocity,
+    Spigot,
+    Architectury,
+    /// A mod dependency: `mcmod add dep modrinth:<project>` or a Maven coordinate
+    Dep,
 }
 
 /// Dispatch an `add` subcommand.
 pub fn run(feature: &Feature, dir: &Path) -> Result<()> {
+    if !matches!(feature, Feature::Ci) && !McmodConfig::load(dir)?.uses_stonecutter() {
+        return Err(McmodError::Other(
+            "Pack and plugin projects only support `mcmod add ci`".to_string(),
+        ));
+    }
     match feature {
         Feature::Fabric => run_add_fabric(dir),
         Feature::Neoforge => run_add_neoforge(dir),
+        Feature::Forge => run_add_forge(dir),
+        Feature::Quilt => run_add_quilt(dir),
+        Feature::Sponge => run_add_sponge(dir),
         Feature::Ci => run_add_ci(dir),
         Feature::Kotlin => run_add_kotlin(dir),
         Feature::Publishing => run_add_publishing(dir),
         Feature::Testing => run_add_testing(dir),
+        Feature::UnitTests => run_add_unit_tests(dir),
+        Feature::BuildInfo => run_add_build_info(dir),
+        Feature::Paper => run_add_paper(dir),
+        Feature::Velocity => run_add_velocity(dir),
+        Feature::Spigot => run_add_spigot(dir),
+        Feature::Architectury => run_add_architectury(dir),
+        Feature::Dep => Err(McmodError::Other(
+            "mcmod add dep needs a dependency, e.g. `mcmod add dep modrinth:cloth-config`".to_string(),
+        )),
+    }
+}
+
+/// An `add` in progress. Each step is recorded in mcmod.lock once it's
+/// done, so when one fails (a read-only file, a missing settings.gradle.kts),
+/// running the same `add` again skips the finished steps and completes the
+/// rest instead of repeating them or refusing because mcmod.toml already
+/// has the feature.
+struct Operation<'a> {
+    dir: &'a Path,
+    /// `add <feature>`, its name in mcmod.lock.
+    name: String,
+    done: Vec<String>,
+}
+
+impl<'a> Operation<'a> {
+    /// Start `add <feature>`, or resume it when an earlier run didn't
+    /// finish. `enabled` is whether mcmod.toml already has the feature,
+    /// which is only an error when there's nothing to resume.
+    fn start(dir: &'a Path, feature: &str, enabled: bool) -> Result<Operation<'a>> {
+        let name = format!("add {feature}");
+        let mut lock = Lockfile::load(dir)?;
+        if let Some(pending) = lock.pending(&name) {
+            println!(
+                "{}",
+                format!("  Resuming an unfinished `mcmod {name}` ({} steps already done)", pending.done.len()).yellow()
+            );
+            return Ok(Operation { dir, name, done: pending.done.clone() });
+        }
+        if enabled {
+            return Err(McmodError::AlreadyEnabled(feature.to_string()));
+        }
+        lock.set_pending(&name, Vec::new());
+        lock.save(dir)?;
+        Ok(Operation { dir, name, done: Vec::new() })
+    }
+
+    /// Run `f` unless an earlier run finished `step`, and record it as done.
+    fn step(&mut self, step: &str, f: impl FnOnce() -> Result<()>) -> Result<()> {
+        if self.done.iter().any(|d| d == step) {
+            crate::util::progress(format!("  Skipped {step}: done by the unfinished run").dimmed());
+            return Ok(());
+        }
+        f()?;
+        self.done.push(step.to_string());
+        let mut lock = Lockfile::load(self.dir)?;
+        lock.set_pending(&self.name, self.done.clone());
+        lock.save(self.dir)
+    }
+
+    /// Every step is done: drop the record.
+    fn finish(self) -> Result<()> {
+        let mut lock = Lockfile::load(self.dir)?;
+        lock.finish_pending(&self.name);
+        lock.save(self.dir)
     }
 }
 
 fn run_add_fabric(dir: &Path) -> Result<()> {
     println!("{}", "\n  mcmod add fabric\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
-
-    if config.loaders.fabric {
-        return Err(McmodError::AlreadyEnabled("fabric".to_string()));
-    }
+    let mut op = Operation::start(dir, "fabric", config.loaders.fabric)?;
 
     let vars = build_vars_from_config(&config);
 
-    // Write fabric.mod.json into unified src/main/resources/
-    add_fabric_files(dir, &vars)?;
+    // Write fabric.mod.json into unified src/main/resources/, with any
+    // [fabric] fields mcmod.toml already has
+    op.step("loader metadata", || {
+        add_fabric_files(dir, &vars)?;
+        if !config.fabric.is_empty() {
+            let path = dir.join(crate::commands::sync::FABRIC_MOD_JSON);
+            let content = crate::fs::read_to_string(&path)?;
+            write_file(&path, &crate::commands::sync::apply_fabric(&content, &config.fabric)?)?;
+        }
+        Ok(())
+    })?;
 
     // Update settings.gradle.kts to add fabric loader
-    gradle::add_loader_to_settings_kts(dir, "fabric")?;
+    op.step("settings.gradle.kts", || gradle::add_loader_to_settings_kts(dir, "fabric"))?;
 
     // Regenerate unified source with both loaders
     config.loaders.fabric = true;
-    regenerate_unified_source(dir, &config)?;
+    op.step("unified source", || regenerate_unified_source(dir, &config))?;
+    op.step("run directory", || add_loader_run_dir(dir, &config, "fabric"))?;
 
     config.save(dir)?;
+    op.finish()?;
 
     println!("{}", "  Fabric loader added successfully!".bold().green());
     Ok(())
@@ -60,44 +156,325 @@ fn run_add_fabric(dir: &Path) -> Result<()> {
 fn run_add_neoforge(dir: &Path) -> Result<()> {
     println!("{}", "\n  mcmod add neoforge\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
-
-    if config.loaders.neoforge {
-        return Err(McmodError::AlreadyEnabled("neoforge".to_string()));
-    }
+    let mut op = Operation::start(dir, "neoforge", config.loaders.neoforge)?;
 
     let vars = build_vars_from_config(&config);
 
-    // Write neoforge.mods.toml into unified src/main/resources/
-    add_neoforge_files(dir, &vars)?;
+    // Write neoforge.mods.toml into unified src/main/resources/, with any
+    // [neoforge] fields mcmod.toml already has
+    op.step("loader metadata", || {
+        add_neoforge_files(dir, &vars)?;
+        if !config.neoforge.is_empty() {
+            let path = dir.join(crate::commands::sync::NEOFORGE_MODS_TOML);
+            let content = crate::fs::read_to_string(&path)?;
+            let synced = crate::commands::sync::apply_neoforge(&content, &config.neoforge, &config.mod_info.mod_id);
+            write_file(&path, &synced)?;
+        }
+        Ok(())
+    })?;
 
     // Update settings.gradle.kts to add neoforge loader
-    gradle::add_loader_to_settings_kts(dir, "neoforge")?;
+    op.step("settings.gradle.kts", || gradle::add_loader_to_settings_kts(dir, "neoforge"))?;
 
     // Regenerate unified source with both loaders
     config.loaders.neoforge = true;
-    regenerate_unified_source(dir, &config)?;
+    op.step("unified source", || regenerate_unified_source(dir, &config))?;
+    op.step("run directory", || add_loader_run_dir(dir, &config, "neoforge"))?;
 
     config.save(dir)?;
+    op.finish()?;
 
     println!("{}", "  NeoForge loader added successfully!".bold().green());
     Ok(())
 }
 
-fn run_add_ci(dir: &Path) -> Result<()> {
-    println!("{}", "\n  mcmod add ci\n".bold().cyan());
+fn run_add_forge(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add forge\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    if config.features.architectury && !config.loaders.forge {
+        return Err(McmodError::Other(
+            "Architectury API has no Forge builds for Minecraft 1.21+; Forge can't be added to an Architectury project".to_string(),
+        ));
+    }
+    let mut op = Operation::start(dir, "forge", config.loaders.forge)?;
+
+    // Pin a Forge version for every target before touching any files
+    pin_locked(&Lockfile::load(dir)?, &mut config.versions.targets, "forge", |t| &mut t.forge, forge_version_for)?;
+
+    let vars = build_vars_from_config(&config);
+
+    // Write mods.toml into unified src/main/resources/
+    op.step("loader metadata", || add_forge_files(dir, &vars))?;
+
+    // Update settings.gradle.kts to add forge loader
+    op.step("settings.gradle.kts", || gradle::add_loader_to_settings_kts(dir, "forge"))?;
+
+    // Record forge_version in each per-version properties file
+    op.step("dependency properties", || set_target_property(dir, &config, "forge_version", |t| &t.forge))?;
+
+    // Regenerate unified source with the Forge entrypoint
+    config.loaders.forge = true;
+    op.step("unified source", || regenerate_unified_source(dir, &config))?;
+    op.step("run directory", || add_loader_run_dir(dir, &config, "forge"))?;
+
+    config.save(dir)?;
+    lock_pins(dir, &config, "forge")?;
+    op.finish()?;
+
+    println!("{}", "  Forge loader added successfully!".bold().green());
+    Ok(())
+}
+
+fn run_add_quilt(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add quilt\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "quilt", config.loaders.quilt)?;
+
+    pin_locked(&Lockfile::load(dir)?, &mut config.versions.targets, "quilt_loader", |t| &mut t.quilt_loader, |_| {
+        Ok(crate::version_meta::QUILT_LOADER.to_string())
+    })?;
+
+    let vars = build_vars_from_config(&config);
+
+    // Write quilt.mod.json into unified src/main/resources/
+    op.step("loader metadata", || add_quilt_files(dir, &vars))?;
+
+    // Update settings.gradle.kts to add the quilt loader and Quilt's Maven
+    op.step("settings.gradle.kts", || {
+        gradle::add_loader_to_settings_kts(dir, "quilt")?;
+        gradle::add_plugin_repository_to_settings_kts(dir, gradle::QUILT_MAVEN)
+    })?;
+
+    // Record quilt_loader_version in each per-version properties file
+    op.step("dependency properties", || {
+        set_target_property(dir, &config, "quilt_loader_version", |t| &t.quilt_loader)
+    })?;
+
+    // Regenerate unified source so Quilt shares the Fabric entrypoint
+    config.loaders.quilt = true;
+    op.step("unified source", || regenerate_unified_source(dir, &config))?;
+    op.step("run directory", || add_loader_run_dir(dir, &config, "quilt"))?;
+
+    config.save(dir)?;
+    lock_pins(dir, &config, "quilt_loader")?;
+    op.finish()?;
+
+    println!("{}", "  Quilt loader added successfully!".bold().green());
+    Ok(())
+}
+
+fn run_add_sponge(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add sponge\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "sponge", config.loaders.sponge)?;
+
+    let vars = build_vars_from_config(&config);
+
+    // sponge/ subproject with SpongeGradle, sponge_plugins.json, and main class
+    op.step("sponge module", || crate::plugin_project::write_sponge_module(dir, &config, &vars))?;
+    op.step("settings.gradle.kts", || gradle::add_include_to_settings(dir, crate::plugin_project::SPONGE_MODULE_DIR))?;
+
+    config.loaders.sponge = true;
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Sponge plugin module added successfully!".bold().green());
+    println!("  Start a test server with ./gradlew :sponge:runServer");
+    Ok(())
+}
+
+/// The pinned Forge version for a Minecraft target, or an error if Forge
+/// never released for it.
+pub fn forge_version_for(minecraft: &str) -> Result<String> {
+    crate::version_meta::get_version_meta(minecraft)
+        .map(|m| m.forge)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            McmodError::Other(format!(
+                "Forge has no release for Minecraft {minecraft}; drop that target or use NeoForge"
+            ))
+        })
+}
+
+/// The pinned Architectury API version for a Minecraft target, or an error
+/// if mcmod knows no release for it.
+fn architectury_api_for(minecraft: &str) -> Result<String> {
+    crate::version_meta::get_version_meta(minecraft)
+        .map(|m| m.architectury_api.to_string())
+        .ok_or_else(|| {
+            McmodError::Other(format!("No known Architectury API release for Minecraft {minecraft}"))
+        })
+}
+
+/// Pin Architectury API for every target, or error if a target has no release.
+pub fn pin_architectury_api(targets: &mut [VersionTarget]) -> Result<()> {
+    for target in targets.iter_mut() {
+        target.architectury_api = architectury_api_for(&target.minecraft)?;
+    }
+    Ok(())
+}
+
+/// Pin `dependency` (its mcmod.lock name) for every target: the version
+/// mcmod.lock resolved for that target when there is one, so every checkout
+/// adding the loader gets the same pin, otherwise the one `pick` chooses.
+fn pin_locked(
+    lock: &Lockfile,
+    targets: &mut [VersionTarget],
+    dependency: &str,
+    field: fn(&mut VersionTarget) -> &mut String,
+    pick: impl Fn(&str) -> Result<String>,
+) -> Result<()> {
+    for target in targets.iter_mut() {
+        *field(target) = match lock.locked(&target.minecraft, dependency) {
+            Some(version) => version,
+            None => pick(&target.minecraft)?,
+        };
+    }
+    Ok(())
+}
+
+/// Record the pins `add` chose in mcmod.lock. `dependency` versions that
+/// weren't locked already came from the built-in table.
+fn lock_pins(dir: &Path, config: &McmodConfig, dependency: &str) -> Result<()> {
+    let mut lock = Lockfile::load(dir)?;
+    lock.record_targets(&config.versions.targets, |_, dep| {
+        (dep == dependency).then(|| BUILT_IN_SOURCE.to_string())
+    });
+    lock.save(dir)
+}
+
+/// Set `key` to each target's pin in its `versions/dependencies/<mc>.properties`.
+fn set_target_property(dir: &Path, config: &McmodConfig, key: &str, pin: fn(&VersionTarget) -> &String) -> Result<()> {
+    for target in &config.versions.targets {
+        let path = dir.join(format!("versions/dependencies/{}.properties", target.minecraft));
+        let content = crate::fs::read_to_string(&path).unwrap_or_default();
+        let updated = gradle::set_property_content(&content, key, pin(target));
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+    Ok(())
+}
+
+fn run_add_architectury(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add architectury\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
+    if config.loaders.forge && !config.features.architectury {
+        return Err(McmodError::Other(
+            "Architectury API has no Forge builds for Minecraft 1.21+; remove the forge loader first".to_string(),
+        ));
+    }
+    let mut op = Operation::start(dir, "architectury", config.features.architectury)?;
+
+    pin_locked(
+        &Lockfile::load(dir)?,
+        &mut config.versions.targets,
+        "architectury_api",
+        |t| &mut t.architectury_api,
+        architectury_api_for,
+    )?;
+    config.features.architectury = true;
+    let vars = build_vars_from_config(&config);
+    op.step("Architectury files", || add_architectury_files(dir, &config, &vars))?;
+
+    // Record architectury_api_version in each per-version properties file
+    op.step("dependency properties", || {
+        set_target_property(dir, &config, "architectury_api_version", |t| &t.architectury_api)
+    })?;
+
+    config.save(dir)?;
+    lock_pins(dir, &config, "architectury_api")?;
+    op.finish()?;
+
+    println!("{}", "  Architectury API added successfully!".bold().green());
+    println!(
+        "  {}",
+        format!(
+            "Loader-specific code goes in {}.platform.PlatformHelper",
+            config.mod_info.package
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+/// Append the Architectury API dependency to build.gradle.kts, declare it in
+/// the loader metadata, and write the PlatformHelper class (used by add).
+pub fn add_architectury_files(
+    dir: &Path,
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<()> {
+    let path = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    if !content.contains("dev.architectury:architectury") {
+        let mut updated = content.clone();
+        if !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(&render(template::SC_ARCHITECTURY_GRADLE, vars)?);
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+
+    let metadata = [
+        (config.loaders.fabric, "src/main/resources/fabric.mod.json", template::SC_FABRIC_MOD_JSON),
+        (config.loaders.neoforge, "src/main/resources/META-INF/neoforge.mods.toml", template::SC_NEOFORGE_MODS_TOML),
+        (config.loaders.quilt, "src/main/resources/quilt.mod.json", template::SC_QUILT_MOD_JSON),
+    ];
+    // Only metadata still as generated is re-rendered; edited files keep the
+    // user's changes and get a note instead
+    let mut plain = vars.clone();
+    plain.remove("architectury");
+    for (enabled, rel, tmpl) in metadata {
+        if !enabled {
+            continue;
+        }
+        let path = dir.join(rel);
+        let content = crate::fs::read_to_string(&path).unwrap_or_default();
+        let updated = template::render_optional(tmpl, vars)?;
+        if content == template::render_optional(tmpl, &plain)? {
+            crate::diff::apply_edit(&path, &content, &updated)?;
+        } else if content != updated {
+            println!(
+                "{}",
+                format!("  {rel} was edited; declare the \"architectury\" dependency there yourself.").yellow()
+            );
+        }
+    }
 
-    if config.features.ci {
-        return Err(McmodError::AlreadyEnabled("ci".to_string()));
+    let (helper, helper_path) = template::render_platform_helper(config, vars)?;
+    let helper_path = sandbox::resolve_output(dir, helper_path)?;
+    if !crate::fs::exists(&helper_path) {
+        write_generated(&helper_path, &helper)?;
     }
+    Ok(())
+}
+
+fn run_add_ci(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add ci\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "ci", config.features.ci)?;
 
     let vars = build_vars_from_config(&config);
 
-    add_ci_files(dir, &vars)?;
+    op.step("workflow", || {
+        if !config.uses_stonecutter() {
+            write_generated(
+                &dir.join(".github/workflows/build.yml"),
+                &template::render_standalone_ci(&config, &vars)?,
+            )
+        } else {
+            add_ci_files(dir, &vars)?;
+            if config.features.unit_tests {
+                add_ci_test_step(&dir.join(".github/workflows/build.yml"))?;
+            }
+            Ok(())
+        }
+    })?;
 
     // Update config
     config.features.ci = true;
     config.save(dir)?;
+    op.finish()?;
 
     println!("{}", "  CI workflow added successfully!".bold().green());
     Ok(())
@@ -106,56 +483,144 @@ fn run_add_ci(dir: &Path) -> Result<()> {
 fn run_add_kotlin(dir: &Path) -> Result<()> {
     println!("{}", "\n  mcmod add kotlin\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
-
-    if config.mod_info.language == "kotlin" {
-        return Err(McmodError::AlreadyEnabled("kotlin".to_string()));
-    }
+    let mut op = Operation::start(dir, "kotlin", config.mod_info.language == "kotlin")?;
 
     let vars = build_vars_from_config(&config);
     let package_path = package_to_path(&config.mod_info.package);
     let class_name = derive_class_name(&config.mod_info.mod_id);
 
-    // Delete Java source file (unified)
-    let java_path = dir.join(format!(
-        "src/main/java/{package_path}/{class_name}.java"
-    ));
-    if java_path.exists() {
-        std::fs::remove_file(&java_path)?;
-        cleanup_empty_dirs(&dir.join(format!("src/main/java/{package_path}")))?;
-    }
-
-    // Create Kotlin source file (unified)
-    let kt_path = dir.join(format!(
-        "src/main/kotlin/{package_path}/{class_name}.kt"
-    ));
-    write_file(&kt_path, &render(template::SC_UNIFIED_MOD_KT, &vars)?)?;
+    // Replace the Java source file (unified) with a Kotlin one
+    config.mod_info.language = "kotlin".to_string();
+    op.step("Kotlin source", || {
+        let java_path = dir.join(format!(
+            "src/main/java/{package_path}/{class_name}.java"
+        ));
+        if crate::fs::exists(&java_path) {
+            crate::fs::remove_file(&java_path)?;
+            cleanup_empty_dirs(&dir.join(format!("src/main/java/{package_path}")))?;
+        }
 
-    // Ensure mixin package-info.java stays in java tree
-    let mixin_path = dir.join(format!(
-        "src/main/java/{package_path}/mixin/package-info.java"
-    ));
-    if !mixin_path.exists() {
-        write_file(
-            &mixin_path,
-            &render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, &vars)?,
-        )?;
-    }
+        let (source, source_path) = template::render_unified_mod(&config, &vars)?;
+        write_generated(&sandbox::resolve_output(dir, source_path)?, &source)?;
+
+        // Ensure mixin package-info.java stays in java tree
+        let mixin_path = dir.join(format!(
+            "src/main/java/{package_path}/mixin/package-info.java"
+        ));
+        if !crate::fs::exists(&mixin_path) {
+            write_generated(
+                &mixin_path,
+                &render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, &vars)?,
+            )?;
+        }
+        Ok(())
+    })?;
 
-    // Update config
-    config.mod_info.language = "kotlin".to_string();
-    config.save(dir)?;
+    // The wiring adds dependencies and the Fabric adapter to mcmod.toml, so
+    // the config is saved in the same step
+    op.step("Kotlin build", || {
+        wire_kotlin_build(dir, &mut config)?;
+        config.save(dir)
+    })?;
+    op.finish()?;
 
     println!("{}", "  Kotlin migration completed successfully!".bold().green());
     Ok(())
 }
 
+/// Fabric's Maven, which hosts Fabric Language Kotlin.
+const FABRIC_MAVEN: &str = "https://maven.fabricmc.net/";
+const KOTLIN_FOR_FORGE_MAVEN: &str = "https://thedarkcolour.github.io/KotlinForForge/";
+
+/// Make the build compile and run Kotlin: the Kotlin Gradle plugin (versioned
+/// in settings.gradle.kts, or on the build.gradle.kts plugin line when
+/// settings has no `pluginManagement` block; applied in build.gradle.kts),
+/// Fabric Language Kotlin (Fabric and Quilt) or KotlinForForge for each
+/// loader's Kotlin runtime, and the Fabric and Quilt entrypoints loaded
+/// through the `kotlin` language adapter.
+fn wire_kotlin_build(dir: &Path, config: &mut McmodConfig) -> Result<()> {
+    let plugin = "kotlin(\"jvm\")";
+    let path = dir.join("settings.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    let build_version = match gradle::add_plugin_version_content(&content, plugin, version_meta::KOTLIN) {
+        Some(updated) => {
+            crate::diff::apply_edit(&path, &content, &updated)?;
+            None
+        }
+        None => Some(version_meta::KOTLIN),
+    };
+
+    let path = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = gradle::add_build_plugin_content(&content, plugin, build_version).ok_or_else(|| {
+        McmodError::Other(format!(
+            "build.gradle.kts has no plugins {{ }} block; add {plugin} version \"{}\" to it and run mcmod add kotlin again",
+            version_meta::KOTLIN
+        ))
+    })?;
+    crate::diff::apply_edit(&path, &content, &gradle::add_client_kotlin_src_content(&updated))?;
+
+    if config.loaders.fabric {
+        let coordinate = format!("net.fabricmc:fabric-language-kotlin:{}", version_meta::FABRIC_LANGUAGE_KOTLIN);
+        add_dep::add_maven(dir, config, &coordinate, "fabric", "fabric-language-kotlin", Some(FABRIC_MAVEN))?;
+
+        let (entrypoint, _, _) = config.mod_info.environment.fabric_entrypoint();
+        config.fabric.adapters.insert(entrypoint.to_string(), "kotlin".to_string());
+        let path = dir.join(sync::FABRIC_MOD_JSON);
+        let content = crate::fs::read_to_string(&path)?;
+        crate::diff::apply_edit(&path, &content, &sync::apply_fabric(&content, &config.fabric)?)?;
+    }
+    if config.loaders.neoforge {
+        let coordinate = format!("thedarkcolour:kotlinforforge-neoforge:{}", version_meta::KOTLIN_FOR_FORGE);
+        add_dep::add_maven(dir, config, &coordinate, "neoforge", "kotlinforforge", Some(KOTLIN_FOR_FORGE_MAVEN))?;
+    }
+    if config.loaders.forge {
+        let coordinate = format!("thedarkcolour:kotlinforforge:{}", version_meta::KOTLIN_FOR_FORGE);
+        add_dep::add_maven(dir, config, &coordinate, "forge", "kotlinforforge", Some(KOTLIN_FOR_FORGE_MAVEN))?;
+    }
+    if config.loaders.quilt {
+        // Quilt loads Fabric language adapters, so the quilt builds share fabric-language-kotlin
+        let coordinate = format!("net.fabricmc:fabric-language-kotlin:{}", version_meta::FABRIC_LANGUAGE_KOTLIN);
+        add_dep::add_maven(dir, config, &coordinate, "quilt", "fabric-language-kotlin", Some(FABRIC_MAVEN))?;
+
+        let (entrypoint, _, _) = config.mod_info.environment.fabric_entrypoint();
+        let path = dir.join(sync::QUILT_MOD_JSON);
+        let content = crate::fs::read_to_string(&path)?;
+        crate::diff::apply_edit(&path, &content, &quilt_kotlin_entrypoint(&content, entrypoint)?)?;
+    }
+    crate::util::progress("  Added the Kotlin Gradle plugin and Kotlin runtime dependencies".green());
+    Ok(())
+}
+
+/// quilt.mod.json with the `entrypoint` entries loaded through the Kotlin
+/// language adapter. Unchanged if they already are.
+fn quilt_kotlin_entrypoint(content: &str, entrypoint: &str) -> Result<String> {
+    let original: serde_json::Value = serde_json::from_str(content)?;
+    let mut json = original.clone();
+    let Some(entries) = json.pointer_mut(&format!("/quilt_loader/entrypoints/{entrypoint}")) else {
+        return Ok(content.to_string());
+    };
+    let kotlin = |value: &serde_json::Value| match value {
+        serde_json::Value::Object(object) => match object.get("value") {
+            Some(value) => serde_json::json!({ "adapter": "kotlin", "value": value }),
+            None => value.clone(),
+        },
+        other => serde_json::json!({ "adapter": "kotlin", "value": other }),
+    };
+    *entries = match &*entries {
+        serde_json::Value::Array(array) => array.iter().map(kotlin).collect(),
+        single => kotlin(single),
+    };
+    if json == original {
+        return Ok(content.to_string());
+    }
+    Ok(serde_json::to_string_pretty(&json)? + "\n")
+}
+
 fn run_add_publishing(dir: &Path) -> Result<()> {
     println!("{}", "\n  mcmod add publishing\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
-
-    if config.features.publishing {
-        return Err(McmodError::AlreadyEnabled("publishing".to_string()));
-    }
+    let mut op = Operation::start(dir, "publishing", config.features.publishing)?;
 
     let modrinth_id: String = dialoguer::Input::new()
         .with_prompt("  Modrinth project slug")
@@ -180,16 +645,20 @@ fn run_add_publishing(dir: &Path) -> Result<()> {
         vars.insert("curseforge_id".to_string(), id.clone());
     }
 
-    add_publishing_files(
-        dir,
-        &vars,
-        config.loaders.fabric,
-        config.loaders.neoforge,
-        curseforge_id.is_some(),
-    )?;
+    op.step("publishing files", || {
+        add_publishing_files(
+            dir,
+            &vars,
+            config.loaders.fabric,
+            config.loaders.neoforge,
+            config.loaders.forge,
+            config.loaders.quilt,
+            curseforge_id.is_some(),
+        )
+    })?;
 
     // Add version_type to gradle.properties if missing
-    gradle::set_gradle_property(dir, "version_type", "release")?;
+    op.step("gradle.properties", || gradle::set_gradle_property(dir, "version_type", "release"))?;
 
     // Update config
     config.features.publishing = true;
@@ -198,6 +667,7 @@ fn run_add_publishing(dir: &Path) -> Result<()> {
         curseforge_id,
     });
     config.save(dir)?;
+    op.finish()?;
 
     println!("{}", "  Publishing support added successfully!".bold().green());
     Ok(())
@@ -206,32 +676,162 @@ fn run_add_publishing(dir: &Path) -> Result<()> {
 fn run_add_testing(dir: &Path) -> Result<()> {
     println!("{}", "\n  mcmod add testing\n".bold().cyan());
     let mut config = McmodConfig::load(dir)?;
-
-    if config.features.testing {
-        return Err(McmodError::AlreadyEnabled("testing".to_string()));
-    }
+    let mut op = Operation::start(dir, "testing", config.features.testing)?;
 
     let vars = build_vars_from_config(&config);
 
-    add_testing_files(
-        dir,
-        &vars,
-        &config.mod_info.language,
-        config.loaders.fabric,
-        config.loaders.neoforge,
-    )?;
+    op.step("test files", || {
+        add_testing_files(
+            dir,
+            &vars,
+            &config.mod_info.language,
+            config.loaders.fabric,
+            config.loaders.neoforge,
+        )
+    })?;
 
     // Set testing_enabled in gradle.properties
-    gradle::set_gradle_property(dir, "testing_enabled", "true")?;
+    op.step("gradle.properties", || gradle::set_gradle_property(dir, "testing_enabled", "true"))?;
 
     // Update config
     config.features.testing = true;
     config.save(dir)?;
+    op.finish()?;
 
     println!("{}", "  Testing support added successfully!".bold().green());
     Ok(())
 }
 
+fn run_add_unit_tests(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add unit-tests\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "unit-tests", config.features.unit_tests)?;
+
+    config.features.unit_tests = true;
+    let vars = build_vars_from_config(&config);
+    op.step("JUnit setup", || {
+        add_unit_test_files(dir, &config, &vars)?;
+        println!("{}", "  Added JUnit 5 to build.gradle.kts".green());
+        Ok(())
+    })?;
+
+    // Run the tests in CI before building
+    let workflow = dir.join(".github/workflows/build.yml");
+    if config.features.ci && crate::fs::exists(&workflow) {
+        op.step("CI test step", || add_ci_test_step(&workflow))?;
+    }
+
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Unit tests added successfully!".bold().green());
+    println!("  {}", "Run them with `mcmod test` or ./gradlew test".dimmed());
+    Ok(())
+}
+
+/// Append the JUnit setup to build.gradle.kts and write an example test for
+/// the mod class unless one exists (used by add).
+pub fn add_unit_test_files(
+    dir: &Path,
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<()> {
+    let path = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    if !content.contains("useJUnitPlatform") {
+        let mut updated = content.clone();
+        if !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(&render(template::SC_UNIT_TESTS_GRADLE, vars)?);
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+
+    let (test_tmpl, ext, source_dir) = if config.mod_info.language == "kotlin" {
+        (template::TMPL_COMMON_UNIT_TEST_KT, "kt", "kotlin")
+    } else {
+        (template::TMPL_COMMON_UNIT_TEST_JAVA, "java", "java")
+    };
+    let test_path = dir.join(format!(
+        "src/test/{source_dir}/{}/{}Test.{ext}",
+        vars["package_path"], vars["class_name"]
+    ));
+    if !crate::fs::exists(&test_path) {
+        write_generated(&test_path, &render(test_tmpl, vars)?)?;
+        println!("{}", "  Created example unit test".green());
+    }
+    Ok(())
+}
+
+/// Insert a `./gradlew test` step ahead of the build in a CI workflow.
+fn add_ci_test_step(path: &Path) -> Result<()> {
+    let content = crate::fs::read_to_string(path)?;
+    if let Some(updated) = with_test_step(&content) {
+        crate::diff::apply_edit(path, &content, &updated)?;
+        println!("{}", "  Added a unit test step to .github/workflows/build.yml".green());
+    }
+    Ok(())
+}
+
+/// The workflow with a test step before its first `./gradlew` run, or `None`
+/// when it already runs the tests or has no Gradle step.
+fn with_test_step(workflow: &str) -> Option<String> {
+    if workflow.contains("./gradlew test") {
+        return None;
+    }
+    let line = workflow.lines().find(|l| l.contains("./gradlew"))?;
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let step = format!("{indent}- name: Run unit tests\n{indent}  run: chmod +x gradlew && ./gradlew test\n");
+    let at = workflow.find(line)?;
+    Some(format!("{}{step}{}", &workflow[..at], &workflow[at..]))
+}
+
+fn run_add_build_info(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add build-info\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "build-info", config.features.build_info)?;
+
+    let vars = build_vars_from_config(&config);
+    op.step("build.gradle.kts", || {
+        add_build_info_files(dir, &vars)?;
+        println!("{}", "  Added generateBuildConstants task to build.gradle.kts".green());
+        Ok(())
+    })?;
+
+    // Update config
+    config.features.build_info = true;
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Build info added successfully!".bold().green());
+    println!(
+        "  {}",
+        format!(
+            "Log it from your mod with {}.BuildConstants.VERSION / GIT_COMMIT / BUILD_DATE",
+            config.mod_info.package
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+/// Append the BuildConstants generation task to build.gradle.kts (used by add).
+/// No-op if the task is already present.
+pub fn add_build_info_files(dir: &Path, vars: &HashMap<String, String>) -> Result<()> {
+    let path = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    if content.contains("generateBuildConstants") {
+        return Ok(());
+    }
+    let mut updated = content.clone();
+    if !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(&render(template::SC_BUILD_INFO_GRADLE, vars)?);
+    crate::diff::apply_edit(&path, &content, &updated)?;
+    Ok(())
+}
+
 /// Create testing files (used by both init and add).
 pub fn add_testing_files(
     dir: &Path,
@@ -250,7 +850,7 @@ pub fn add_testing_files(
     };
 
     // Unit test in src/test/
-    write_file(
+    write_generated(
         &dir.join(format!(
             "src/test/{source_dir}/{package_path}/{class_name}Test.{ext}"
         )),
@@ -275,10 +875,10 @@ fn add_gametest_entrypoint(
     _language: &str,
 ) -> Result<()> {
     let path = dir.join("src/main/resources/fabric.mod.json");
-    if !path.exists() {
+    if !crate::fs::exists(&path) {
         return Ok(());
     }
-    let content = std::fs::read_to_string(&path)?;
+    let content = crate::fs::read_to_string(&path)?;
 
     // Parse as JSON, add the entrypoint, and re-serialize
     let mut json: serde_json::Value = serde_json::from_str(&content)?;
@@ -292,8 +892,77 @@ fn add_gametest_entrypoint(
     }
 
     // Write back with pretty formatting
-    let formatted = serde_json::to_string_pretty(&json)?;
-    std::fs::write(&path, formatted + "\n")?;
+    let formatted = serde_json::to_string_pretty(&json)? + "\n";
+    crate::diff::apply_edit(&path, &content, &formatted)?;
+    Ok(())
+}
+
+fn run_add_paper(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add paper\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "paper", config.features.paper)?;
+
+    let vars = build_vars_from_config(&config);
+
+    // paper/ subproject with paperweight-userdev, paper-plugin.yml, and main class
+    op.step("paper module", || crate::plugin_project::write_paper_module(dir, &config, &vars))?;
+
+    // Include the module and the Paper repository in settings.gradle.kts
+    op.step("settings.gradle.kts", || {
+        let path = dir.join("settings.gradle.kts");
+        let content = crate::fs::read_to_string(&path)?;
+        let updated = gradle::add_include_content(&content, crate::plugin_project::PAPER_MODULE_DIR);
+        let updated = gradle::add_plugin_repository_content(&updated, gradle::PAPER_MAVEN);
+        crate::diff::apply_edit(&path, &content, &updated)?;
+        Ok(())
+    })?;
+
+    config.features.paper = true;
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Paper plugin module added successfully!".bold().green());
+    println!("  Start a test server with ./gradlew :paper:runServer");
+    Ok(())
+}
+
+fn run_add_velocity(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add velocity\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "velocity", config.features.velocity)?;
+
+    let vars = build_vars_from_config(&config);
+
+    // velocity/ subproject with velocity-plugin.json and an @Plugin main class
+    op.step("velocity module", || crate::plugin_project::write_velocity_module(dir, &config, &vars))?;
+    op.step("settings.gradle.kts", || gradle::add_include_to_settings(dir, crate::plugin_project::VELOCITY_MODULE_DIR))?;
+
+    config.features.velocity = true;
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Velocity plugin module added successfully!".bold().green());
+    println!("  Start a test proxy with ./gradlew :velocity:runVelocity");
+    Ok(())
+}
+
+fn run_add_spigot(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod add spigot\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    let mut op = Operation::start(dir, "spigot", config.features.spigot)?;
+
+    let vars = build_vars_from_config(&config);
+
+    // spigot/ subproject with plugin.yml, a JavaPlugin main class, and the Spigot API
+    op.step("spigot module", || crate::plugin_project::write_spigot_module(dir, &config, &vars))?;
+    op.step("settings.gradle.kts", || gradle::add_include_to_settings(dir, crate::plugin_project::SPIGOT_MODULE_DIR))?;
+
+    config.features.spigot = true;
+    config.save(dir)?;
+    op.finish()?;
+
+    println!("{}", "  Spigot plugin module added successfully!".bold().green());
+    println!("  Build the plugin jar with ./gradlew :spigot:build");
     Ok(())
 }
 
@@ -303,6 +972,8 @@ pub fn add_publishing_files(
     vars: &HashMap<String, String>,
     has_fabric: bool,
     has_neoforge: bool,
+    has_forge: bool,
+    has_quilt: bool,
     has_curseforge: bool,
 ) -> Result<()> {
     // Render and strip conditional blocks from release.yml
@@ -312,13 +983,15 @@ pub fn add_publishing_files(
         &[
             ("fabric", has_fabric),
             ("neoforge", has_neoforge),
+            ("forge", has_forge),
+            ("quilt", has_quilt),
             ("curseforge", has_curseforge),
         ],
     );
-    write_file(&dir.join(".github/workflows/release.yml"), &stripped)?;
+    write_generated(&dir.join(".github/workflows/release.yml"), &stripped)?;
 
     // Starter changelog
-    write_file(
+    write_generated(
         &dir.join("changelogs/v1.0.0.md"),
         "Initial release.\n",
     )?;
@@ -329,10 +1002,13 @@ pub fn add_publishing_files(
         .get("description")
         .map(|s| s.as_str())
         .unwrap_or("A Minecraft mod");
-    write_file(
-        &dir.join("MODPAGE.md"),
-        &format!("# {mod_name}\n\n{description}\n"),
-    )?;
+    let mut modpage = format!("# {mod_name}\n\n{description}\n");
+    if let (Some(_), Some(homepage)) = (vars.get("github"), vars.get("homepage")) {
+        modpage.push_str(&format!(
+            "\n## Links\n\n- [Source code]({homepage})\n- [Issue tracker]({homepage}/issues)\n"
+        ));
+    }
+    write_generated(&dir.join("MODPAGE.md"), &modpage)?;
 
     Ok(())
 }
@@ -342,36 +1018,21 @@ pub fn add_fabric_files(
     dir: &Path,
     vars: &HashMap<String, String>,
 ) -> Result<()> {
-    let mod_id = vars.get("mod_id").unwrap();
-    let package_path = vars.get("package_path").unwrap();
-
     // fabric.mod.json in unified resources
-    write_file(
+    write_generated(
         &dir.join("src/main/resources/fabric.mod.json"),
-        &render(template::SC_FABRIC_MOD_JSON, vars)?,
+        &template::render_optional(template::SC_FABRIC_MOD_JSON, vars)?,
     )?;
 
-    // mixins.json (shared)
-    let mixins_path = dir.join(format!("src/main/resources/{mod_id}.mixins.json"));
-    if !mixins_path.exists() {
-        write_file(
-            &mixins_path,
-            &render(template::TMPL_FABRIC_MIXINS_JSON, vars)?,
-        )?;
-    }
-
-    // mixin package-info.java
-    let mixin_info_path = dir.join(format!(
-        "src/main/java/{package_path}/mixin/package-info.java"
-    ));
-    if !mixin_info_path.exists() {
-        write_file(
-            &mixin_info_path,
-            &render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, vars)?,
-        )?;
+    // Extra entrypoint classes named by fabric.mod.json (client, datagen, pre-launch)
+    for (source, path) in template::render_fabric_entrypoints(vars)? {
+        let path = sandbox::resolve_output(dir, path)?;
+        if !crate::fs::exists(&path) {
+            write_generated(&path, &source)?;
+        }
     }
 
-    Ok(())
+    add_shared_mixin_files(dir, vars)
 }
 
 /// Create neoforge resource metadata files (used by both init and add).
@@ -379,30 +1040,61 @@ pub fn add_neoforge_files(
     dir: &Path,
     vars: &HashMap<String, String>,
 ) -> Result<()> {
-    let mod_id = vars.get("mod_id").unwrap();
-    let package_path = vars.get("package_path").unwrap();
-
     // neoforge.mods.toml in unified resources
-    write_file(
+    write_generated(
         &dir.join("src/main/resources/META-INF/neoforge.mods.toml"),
-        &render(template::SC_NEOFORGE_MODS_TOML, vars)?,
+        &template::render_optional(template::SC_NEOFORGE_MODS_TOML, vars)?,
     )?;
 
-    // mixins.json (shared) — create if not present
-    let mixins_path = dir.join(format!("src/main/resources/{mod_id}.mixins.json"));
-    if !mixins_path.exists() {
-        write_file(
-            &mixins_path,
-            &render(template::TMPL_FABRIC_MIXINS_JSON, vars)?,
-        )?;
-    }
+    add_shared_mixin_files(dir, vars)
+}
+
+/// Create forge resource metadata files (used by both init and add).
+pub fn add_forge_files(
+    dir: &Path,
+    vars: &HashMap<String, String>,
+) -> Result<()> {
+    // mods.toml in unified resources
+    write_generated(
+        &dir.join("src/main/resources/META-INF/mods.toml"),
+        &template::render_optional(template::SC_FORGE_MODS_TOML, vars)?,
+    )?;
+
+    add_shared_mixin_files(dir, vars)
+}
+
+/// Create quilt resource metadata files (used by both init and add).
+pub fn add_quilt_files(
+    dir: &Path,
+    vars: &HashMap<String, String>,
+) -> Result<()> {
+    // quilt.mod.json in unified resources
+    write_generated(
+        &dir.join("src/main/resources/quilt.mod.json"),
+        &template::render_optional(template::SC_QUILT_MOD_JSON, vars)?,
+    )?;
+
+    add_shared_mixin_files(dir, vars)
+}
+
+/// Create the mixins.json and mixin package shared by all loaders. An existing
+/// mixins.json gains any missing fields and keeps its mixin lists; a field that
+/// differs from the template (such as a moved mixin package) is asked about.
+fn add_shared_mixin_files(dir: &Path, vars: &HashMap<String, String>) -> Result<()> {
+    let mod_id = vars.get("mod_id").unwrap();
+    let package_path = vars.get("package_path").unwrap();
+
+    crate::json_merge::merge_into_file(
+        &dir.join(format!("src/main/resources/{mod_id}.mixins.json")),
+        &render(template::TMPL_FABRIC_MIXINS_JSON, vars)?,
+        crate::json_merge::OnConflict::Ask,
+    )?;
 
-    // mixin package-info.java — create if not present
     let mixin_info_path = dir.join(format!(
         "src/main/java/{package_path}/mixin/package-info.java"
     ));
-    if !mixin_info_path.exists() {
-        write_file(
+    if !crate::fs::exists(&mixin_info_path) {
+        write_generated(
             &mixin_info_path,
             &render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, vars)?,
         )?;
@@ -413,7 +1105,7 @@ pub fn add_neoforge_files(
 
 /// Create CI files (used by both init and add).
 pub fn add_ci_files(dir: &Path, vars: &HashMap<String, String>) -> Result<()> {
-    write_file(
+    write_generated(
         &dir.join(".github/workflows/build.yml"),
         &render(template::TMPL_CI_BUILD_YML, vars)?,
     )?;
@@ -423,24 +1115,17 @@ pub fn add_ci_files(dir: &Path, vars: &HashMap<String, String>) -> Result<()> {
 /// Regenerate the unified source file after adding/removing a loader.
 fn regenerate_unified_source(dir: &Path, config: &McmodConfig) -> Result<()> {
     let vars = template::build_common_vars(config);
-    let package_path = package_to_path(&config.mod_info.package);
-    let class_name = derive_class_name(&config.mod_info.mod_id);
-
-    let (tmpl, ext, source_dir) = if config.mod_info.language == "kotlin" {
-        (template::SC_UNIFIED_MOD_KT, "kt", "kotlin")
-    } else {
-        (template::SC_UNIFIED_MOD_JAVA, "java", "java")
-    };
-
-    let source_path = dir.join(format!(
-        "src/main/{source_dir}/{package_path}/{class_name}.{ext}"
-    ));
-    write_file(&source_path, &render(tmpl, &vars)?)?;
+    let (source, source_path) = template::render_unified_mod(config, &vars)?;
+    write_generated(&sandbox::resolve_output(dir, source_path)?, &source)?;
     Ok(())
 }
 
 /// Remove a directory and its parents if they are empty.
 fn cleanup_empty_dirs(path: &Path) -> Result<()> {
+    // Previews never touch the real directories
+    if crate::fs::is_in_memory() {
+        return Ok(());
+    }
     let mut current = path.to_path_buf();
     while current.exists() {
         if std::fs::read_dir(&current)?.next().is_none() {
@@ -457,7 +1142,263 @@ fn cleanup_empty_dirs(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// With per-loader run directories, set up the new loader's `run/<loader>`.
+fn add_loader_run_dir(dir: &Path, config: &McmodConfig, loader: &str) -> Result<()> {
+    if config.features.run_dirs.is_shared() {
+        return Ok(());
+    }
+    let run = config.run_dir(loader);
+    let server = config.run_dirs().iter().any(|other| crate::fs::exists(&dir.join(other).join("eula.txt")));
+    crate::commands::init::write_dev_defaults(dir, config, &crate::global_config::GlobalConfig::load()?, &[run], server)
+}
+
 /// Build template variables from an existing config.
 fn build_vars_from_config(config: &McmodConfig) -> HashMap<String, String> {
     template::build_common_vars(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_add_loaders_to_project() {
+        let dir = Path::new("/memory/addmod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric"]))?;
+            run(&Feature::Neoforge, dir)?;
+            run(&Feature::Quilt, dir)?;
+            run(&Feature::Kotlin, dir)
+        });
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        assert!(tree.iter().any(|p| p == "src/main/resources/META-INF/neoforge.mods.toml"));
+        assert!(tree.iter().any(|p| p == "src/main/resources/quilt.mod.json"));
+        assert!(tree.iter().any(|p| p == "src/main/kotlin/com/example/testmod/TestmodMod.kt"));
+        assert!(!tree.iter().any(|p| p.ends_with("TestmodMod.java")));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("mc(\"1.21.4\", \"fabric\", \"neoforge\", \"quilt\")"));
+        assert!(settings.contains(gradle::QUILT_MAVEN));
+        let properties = fs.read_string(&dir.join("versions/dependencies/1.21.4.properties")).unwrap();
+        assert!(properties.contains("quilt_loader_version="));
+
+        let config = McmodConfig::load(dir);
+        assert!(config.is_err(), "the tree only exists inside in_memory");
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.loaders.neoforge && config.loaders.quilt);
+        assert_eq!(config.mod_info.language, "kotlin");
+        assert_eq!(config.fabric.adapters["main"], "kotlin");
+        assert!(config.dependencies.contains_key("fabric-language-kotlin"));
+        assert_eq!(config.dependencies["kotlinforforge-neoforge"].mod_ids["neoforge"], "kotlinforforge");
+
+        // Kotlin migration wires the plugin and each loader's Kotlin runtime
+        assert!(settings.contains("    plugins {\n        kotlin(\"jvm\") version \""));
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("    kotlin(\"jvm\")\n}"));
+        assert!(build.contains("modImplementation(\"net.fabricmc:fabric-language-kotlin:$it\")"));
+        assert!(build.contains("modImplementation(\"thedarkcolour:kotlinforforge-neoforge:$it\")"));
+        assert!(properties.contains("dep.fabric-language-kotlin.fabric="));
+        let fabric_json = fs.read_string(&dir.join(sync::FABRIC_MOD_JSON)).unwrap();
+        assert!(fabric_json.contains("\"adapter\": \"kotlin\""));
+        assert!(fabric_json.contains("\"fabric-language-kotlin\": \"*\""));
+        assert!(fs.read_string(&dir.join(sync::NEOFORGE_MODS_TOML)).unwrap().contains("modId = \"kotlinforforge\""));
+        assert!(properties.contains("dep.fabric-language-kotlin.quilt="));
+        let quilt: serde_json::Value =
+            serde_json::from_str(&fs.read_string(&dir.join(sync::QUILT_MOD_JSON)).unwrap()).unwrap();
+        assert_eq!(quilt["quilt_loader"]["entrypoints"]["main"]["adapter"], "kotlin");
+        assert_eq!(quilt["quilt_loader"]["entrypoints"]["main"]["value"], "${group}.TestmodMod");
+        let depends = quilt["quilt_loader"]["depends"].as_array().unwrap();
+        assert!(depends.iter().any(|d| d["id"] == "fabric-language-kotlin"));
+        let mod_ids = &config.dependencies["fabric-language-kotlin"].mod_ids;
+        assert_eq!(mod_ids["fabric"], "fabric-language-kotlin");
+        assert_eq!(mod_ids["quilt"], "fabric-language-kotlin");
+
+        assert!(matches!(
+            crate::fs::in_memory(|| {
+                init::run(init::tests::options(dir, &["fabric"]))?;
+                run(&Feature::Fabric, dir)
+            })
+            .0,
+            Err(McmodError::AlreadyEnabled(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_kotlin_forge() {
+        let dir = Path::new("/memory/addkotlinforge");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["forge"]))?;
+            run(&Feature::Kotlin, dir)
+        });
+        result.unwrap();
+
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert_eq!(config.dependencies["kotlinforforge"].mod_ids["forge"], "kotlinforforge");
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("thedarkcolour:kotlinforforge:$it"));
+        let properties = fs.read_string(&dir.join("versions/dependencies/1.21.4.properties")).unwrap();
+        assert!(properties.contains("dep.kotlinforforge.forge="));
+    }
+
+    #[test]
+    fn test_resume_failed_add() {
+        let dir = Path::new("/memory/resumemod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric"]))?;
+            // Without settings.gradle.kts the second step fails
+            let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts"))?;
+            crate::fs::remove_file(&dir.join("settings.gradle.kts"))?;
+            let failed = run(&Feature::Neoforge, dir);
+            assert!(failed.is_err());
+            let lock = Lockfile::load(dir)?;
+            assert_eq!(lock.pending("add neoforge").unwrap().done, ["loader metadata"]);
+            assert!(!McmodConfig::load(dir)?.loaders.neoforge);
+
+            // The metadata written by the failed run is kept as the user left it
+            let toml = dir.join(sync::NEOFORGE_MODS_TOML);
+            let edited = crate::fs::read_to_string(&toml)? + "# edited\n";
+            write_file(&toml, &edited)?;
+            write_file(&dir.join("settings.gradle.kts"), &settings)?;
+            run(&Feature::Neoforge, dir)?;
+            assert_eq!(crate::fs::read_to_string(&toml)?, edited);
+            assert!(Lockfile::load(dir)?.pending.is_empty());
+            run(&Feature::Neoforge, dir)
+        });
+        assert!(matches!(result, Err(McmodError::AlreadyEnabled(_))));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("mc(\"1.21.4\", \"fabric\", \"neoforge\")"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.loaders.neoforge);
+    }
+
+    #[test]
+    fn test_add_unit_tests() {
+        let dir = Path::new("/memory/unitmod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(&Feature::UnitTests, dir)
+        });
+        result.unwrap();
+
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("junit-bom:5.11.4"));
+        assert!(build.contains("fabric-loader-junit:${property(\"loader_version\")}"));
+        let test = fs.read_string(&dir.join("src/test/java/com/example/testmod/TestmodModTest.java")).unwrap();
+        assert!(test.contains("assertEquals(\"testmod\", TestmodMod.MOD_ID);"));
+        let workflow = fs.read_string(&dir.join(".github/workflows/build.yml")).unwrap();
+        assert!(workflow.contains("      - name: Run unit tests\n        run: chmod +x gradlew && ./gradlew test\n      - run: chmod +x gradlew && ./gradlew build"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.features.unit_tests);
+        assert_eq!(template::render_build_gradle(&config, &build_vars_from_config(&config)).unwrap(), build);
+        assert_eq!(with_test_step(&workflow), None);
+    }
+
+    #[test]
+    fn test_add_architectury() {
+        let dir = Path::new("/memory/archmod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(&Feature::Architectury, dir)
+        });
+        result.unwrap();
+
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("maven(\"https://maven.architectury.dev/\")"));
+        let properties = fs.read_string(&dir.join("versions/dependencies/1.21.4.properties")).unwrap();
+        assert!(properties.contains("architectury_api_version=15.0.3"));
+        let fabric_json = fs.read_string(&dir.join("src/main/resources/fabric.mod.json")).unwrap();
+        assert!(fabric_json.contains("\"architectury\": \"*\""));
+        let mods_toml = fs.read_string(&dir.join("src/main/resources/META-INF/neoforge.mods.toml")).unwrap();
+        assert!(mods_toml.contains("modId = \"architectury\""));
+        assert!(fs.tree(dir).iter().any(|p| p == "src/main/java/com/example/testmod/platform/PlatformHelper.java"));
+
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.features.architectury);
+        assert_eq!(config.versions.targets[0].architectury_api, "15.0.3");
+
+        // Architectury API has no Forge builds, in either order
+        let (result, _) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric"]))?;
+            run(&Feature::Architectury, dir)?;
+            run(&Feature::Forge, dir)
+        });
+        assert!(result.unwrap_err().to_string().contains("no Forge builds"));
+        let (result, _) = crate::fs::in_memory(|| {
+            let mut opts = init::tests::options(dir, &["fabric", "forge"]);
+            opts.architectury = Some(true);
+            init::run(opts)
+        });
+        assert!(result.unwrap_err().to_string().contains("no Forge builds"));
+    }
+
+    #[test]
+    fn test_add_sponge_module() {
+        let dir = Path::new("/memory/spongemod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["neoforge"]))?;
+            run(&Feature::Sponge, dir)
+        });
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        assert!(tree.iter().any(|p| p == "sponge/build.gradle.kts"));
+        assert!(tree.iter().any(|p| p == "sponge/src/main/resources/META-INF/sponge_plugins.json"));
+        assert!(tree.iter().any(|p| p == "sponge/src/main/java/com/example/testmod/sponge/TestmodPlugin.java"));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("include(\"sponge\")"));
+        assert!(settings.contains("mc(\"1.21.4\", \"neoforge\")"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.loaders.sponge);
+        assert_eq!(config.enabled_platforms(), vec!["neoforge"]);
+    }
+
+    #[test]
+    fn test_add_server_plugin_modules() {
+        let dir = Path::new("/memory/proxymod");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric"]))?;
+            run(&Feature::Velocity, dir)?;
+            run(&Feature::Paper, dir)?;
+            run(&Feature::Spigot, dir)
+        });
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        assert!(tree.iter().any(|p| p == "velocity/src/main/resources/velocity-plugin.json"));
+        assert!(tree.iter().any(|p| p == "velocity/src/main/java/com/example/testmod/velocity/TestmodPlugin.java"));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("include(\"velocity\")\n"));
+        assert!(settings.contains("include(\"paper\")\n"));
+        assert!(settings.contains("include(\"spigot\")\n"));
+        assert!(tree.iter().any(|p| p == "spigot/src/main/resources/plugin.yml"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.features.velocity && config.features.paper && config.features.spigot);
+    }
+
+    #[test]
+    fn test_add_paper_module() {
+        let dir = Path::new("/memory/hybrid");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric"]))?;
+            run(&Feature::Paper, dir)
+        });
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        assert!(tree.iter().any(|p| p == "paper/build.gradle.kts"));
+        assert!(tree.iter().any(|p| p == "paper/src/main/resources/paper-plugin.yml"));
+        assert!(tree.iter().any(|p| p == "paper/src/main/java/com/example/testmod/paper/TestmodPlugin.java"));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("include(\"paper\")"));
+        assert!(settings.contains(gradle::PAPER_MAVEN));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert!(config.features.paper);
+    }
+}
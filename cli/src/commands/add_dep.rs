@@ -0,0 +1,415 @@
+use crate::commands::publish::MODRINTH_API;
+use crate::commands::sync::{FABRIC_MOD_JSON, NEOFORGE_MODS_TOML, QUILT_MOD_JSON};
+use crate::config::{DependencySource, McmodConfig, ModDependency};
+use crate::error::{Context, McmodError, Result};
+use crate::gradle;
+use crate::jar::Jar;
+use crate::util::{http_download, http_get, sha1_file};
+use colored::Colorize;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const MODRINTH_MAVEN: &str = "https://api.modrinth.com/maven";
+
+/// Loaders whose builds and metadata `mcmod add dep` wires up.
+const LOADERS: [&str; 2] = ["fabric", "neoforge"];
+
+/// What `mcmod add dep` was asked to add.
+#[derive(Debug, PartialEq)]
+enum Spec {
+    /// `modrinth:<slug>`, optionally pinned with `@<version number>`.
+    Modrinth { slug: String, pin: Option<String> },
+    /// `group:artifact:version`, the same version for every build.
+    Maven { group: String, artifact: String, version: String },
+}
+
+impl Spec {
+    fn parse(spec: &str) -> Result<Spec> {
+        if let Some(project) = spec.strip_prefix("modrinth:") {
+            let (slug, pin) = match project.split_once('@') {
+                Some((slug, pin)) => (slug, Some(pin.to_string())),
+                None => (project, None),
+            };
+            if slug.is_empty() || !slug.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+                return Err(McmodError::Other(format!("Invalid Modrinth project '{slug}'")));
+            }
+            return Ok(Spec::Modrinth { slug: slug.to_string(), pin });
+        }
+        match spec.split(':').collect::<Vec<_>>()[..] {
+            [group, artifact, version] if [group, artifact, version].iter().all(|p| !p.is_empty()) => Ok(Spec::Maven {
+                group: group.to_string(),
+                artifact: artifact.to_string(),
+                version: version.to_string(),
+            }),
+            _ => Err(McmodError::Other(format!(
+                "Can't read '{spec}': use modrinth:<project>[@version] or a Maven group:artifact:version"
+            ))),
+        }
+    }
+
+    /// The key the dependency is recorded under.
+    fn name(&self) -> &str {
+        match self {
+            Spec::Modrinth { slug, .. } => slug,
+            Spec::Maven { artifact, .. } => artifact,
+        }
+    }
+}
+
+/// A version from Modrinth's `/project/{slug}/version` list.
+#[derive(Debug, Deserialize)]
+struct ModrinthVersion {
+    id: String,
+    version_number: String,
+    version_type: String,
+    files: Vec<ModrinthFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModrinthFile {
+    url: String,
+    primary: bool,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+}
+
+/// `mcmod add dep <spec>`: resolve the dependency for each Fabric and NeoForge
+/// build, record its versions in versions/dependencies/<mc>.properties as
+/// `dep.<name>.<loader>`, add it to build.gradle.kts and both loaders'
+/// metadata, and remember it in mcmod.toml for later upgrades. Running it
+/// again re-resolves the versions.
+pub fn run(dir: &Path, spec: &str, mod_id: Option<&str>, repository: Option<&str>) -> Result<()> {
+    let mut config = McmodConfig::load(dir)?;
+    let spec = Spec::parse(spec)?;
+    let name = spec.name().to_string();
+    let loaders: Vec<&str> = LOADERS.into_iter().filter(|l| loader_enabled(&config, l)).collect();
+    if loaders.is_empty() {
+        return Err(McmodError::Other("mcmod add dep needs the fabric or neoforge loader".to_string()));
+    }
+    if config.loaders.forge || config.loaders.quilt {
+        crate::progress::warning("Only the fabric and neoforge builds get the dependency; add it to forge or quilt by hand");
+    }
+
+    println!("{}", format!("\n  Adding {name}\n").bold().cyan());
+    // (minecraft, loader, version)
+    let mut versions: Vec<(String, &str, String)> = Vec::new();
+    let mut mod_ids = BTreeMap::new();
+    for loader in &loaders {
+        for (i, target) in config.versions.targets.iter().enumerate() {
+            let version = match &spec {
+                Spec::Maven { version, .. } => version.clone(),
+                Spec::Modrinth { slug, pin } => {
+                    let found = modrinth_version(slug, loader, &target.minecraft, pin.as_deref())?;
+                    println!("  {:<20} {}", format!("{}-{loader}", target.minecraft), found.version_number.cyan());
+                    // Read the mod id from the newest target's jar
+                    if mod_id.is_none() && i + 1 == config.versions.targets.len() {
+                        if let Some(id) = jar_mod_id(&found, loader)? {
+                            mod_ids.insert(loader.to_string(), id);
+                        }
+                    }
+                    // Version ids are unique; numbers may be shared between loaders
+                    found.id
+                }
+            };
+            versions.push((target.minecraft.clone(), loader, version));
+        }
+        let id = match mod_id {
+            Some(id) => id.to_string(),
+            None => mod_ids.get(*loader).cloned().unwrap_or_else(|| default_mod_id(&name, loader)),
+        };
+        mod_ids.insert(loader.to_string(), id);
+    }
+
+    let (source, artifact, repository) = match &spec {
+        Spec::Modrinth { slug, .. } => (DependencySource::Modrinth, slug.clone(), None),
+        Spec::Maven { group, artifact, .. } => {
+            (DependencySource::Maven, format!("{group}:{artifact}"), repository.map(str::to_string))
+        }
+    };
+    write(dir, &mut config, &name, ModDependency { source, artifact, repository, mod_ids }, &versions)?;
+    config.save(dir)?;
+
+    println!("{}", format!("\n  Added {name} to the build, the loader metadata and mcmod.toml").green());
+    Ok(())
+}
+
+/// Add a Maven dependency to one loader's builds, at the same version for
+/// every target, and record it in `config` (which the caller saves).
+pub fn add_maven(
+    dir: &Path,
+    config: &mut McmodConfig,
+    coordinate: &str,
+    loader: &str,
+    mod_id: &str,
+    repository: Option<&str>,
+) -> Result<()> {
+    let Spec::Maven { group, artifact, version } = Spec::parse(coordinate)? else {
+        return Err(McmodError::Other(format!("'{coordinate}' is not a Maven coordinate")));
+    };
+    let versions: Vec<(String, &str, String)> =
+        config.versions.targets.iter().map(|t| (t.minecraft.clone(), loader, version.clone())).collect();
+    let artifact_id = format!("{group}:{artifact}");
+    // Keep the ids of other loaders that already use the same artifact
+    let mut mod_ids = match config.dependencies.get(&artifact) {
+        Some(existing) if existing.artifact == artifact_id => existing.mod_ids.clone(),
+        _ => BTreeMap::new(),
+    };
+    mod_ids.insert(loader.to_string(), mod_id.to_string());
+    let dependency = ModDependency {
+        source: DependencySource::Maven,
+        artifact: artifact_id,
+        repository: repository.map(str::to_string),
+        mod_ids,
+    };
+    write(dir, config, &artifact, dependency, &versions)
+}
+
+/// Write a resolved dependency into the project: its `(minecraft, loader,
+/// version)`s into versions/dependencies, the build.gradle.kts block, the
+/// loader metadata for each of its `mod_ids`, and `config.dependencies`.
+fn write(
+    dir: &Path,
+    config: &mut McmodConfig,
+    name: &str,
+    dependency: ModDependency,
+    versions: &[(String, &str, String)],
+) -> Result<()> {
+    for (minecraft, loader, version) in versions {
+        let path = dir.join(format!("versions/dependencies/{minecraft}.properties"));
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))?;
+        let updated = gradle::set_property_content(&content, &format!("dep.{name}.{loader}"), version);
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+
+    let (coordinate, maven) = match dependency.source {
+        DependencySource::Modrinth => (format!("maven.modrinth:{}", dependency.artifact), Some(MODRINTH_MAVEN)),
+        DependencySource::Maven => (dependency.artifact.clone(), dependency.repository.as_deref()),
+    };
+    let build = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&build).context("Reading build.gradle.kts")?;
+    let updated = gradle::add_mod_dependency_content(&content, name, &coordinate, maven);
+    crate::diff::apply_edit(&build, &content, &updated)?;
+
+    if let Some(id) = dependency.mod_ids.get("fabric") {
+        let path = dir.join(FABRIC_MOD_JSON);
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {FABRIC_MOD_JSON}"))?;
+        let updated = add_fabric_depends(&content, id).with_context(|| format!("Updating {FABRIC_MOD_JSON}"))?;
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+    if let Some(id) = dependency.mod_ids.get("neoforge") {
+        let path = dir.join(NEOFORGE_MODS_TOML);
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {NEOFORGE_MODS_TOML}"))?;
+        crate::diff::apply_edit(&path, &content, &add_neoforge_dependency(&content, id))?;
+    }
+    if let Some(id) = dependency.mod_ids.get("quilt") {
+        let path = dir.join(QUILT_MOD_JSON);
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {QUILT_MOD_JSON}"))?;
+        let updated = add_quilt_depends(&content, id).with_context(|| format!("Updating {QUILT_MOD_JSON}"))?;
+        crate::diff::apply_edit(&path, &content, &updated)?;
+    }
+
+    config.dependencies.insert(name.to_string(), dependency);
+    Ok(())
+}
+
+fn loader_enabled(config: &McmodConfig, loader: &str) -> bool {
+    match loader {
+        "fabric" => config.loaders.fabric,
+        "neoforge" => config.loaders.neoforge,
+        _ => false,
+    }
+}
+
+/// NeoForge mod ids can't contain dashes; Fabric ones usually match the artifact.
+fn default_mod_id(name: &str, loader: &str) -> String {
+    if loader == "neoforge" { name.replace(['-', '.'], "_") } else { name.to_string() }
+}
+
+/// The newest version of `slug` for `loader` on `minecraft`, preferring
+/// releases, or the one numbered `pin`.
+fn modrinth_version(slug: &str, loader: &str, minecraft: &str, pin: Option<&str>) -> Result<ModrinthVersion> {
+    let url = format!(
+        "{MODRINTH_API}/project/{slug}/version?loaders=%5B%22{loader}%22%5D&game_versions=%5B%22{minecraft}%22%5D"
+    );
+    let body = http_get(&url).map_err(|e| match e.root() {
+        McmodError::Http(message) if message.contains("404") => {
+            McmodError::Other(format!("There is no Modrinth project '{slug}'"))
+        }
+        _ => e,
+    })?;
+    pick_version(serde_json::from_str(&body)?, pin).ok_or_else(|| {
+        let which = pin.map(|p| format!(" {p}")).unwrap_or_default();
+        McmodError::Other(format!("{slug}{which} has no {loader} build for Minecraft {minecraft} on Modrinth"))
+    })
+}
+
+fn pick_version(versions: Vec<ModrinthVersion>, pin: Option<&str>) -> Option<ModrinthVersion> {
+    let mut versions = versions.into_iter();
+    match pin {
+        Some(pin) => versions.find(|v| v.version_number == pin),
+        None => {
+            let all: Vec<ModrinthVersion> = versions.collect();
+            let release = all.iter().position(|v| v.version_type == "release").unwrap_or(0);
+            all.into_iter().nth(release)
+        }
+    }
+}
+
+/// Download the version's primary jar (checked against its SHA-1) and read
+/// the id its `loader` metadata declares.
+fn jar_mod_id(version: &ModrinthVersion, loader: &str) -> Result<Option<String>> {
+    let Some(file) = version.files.iter().find(|f| f.primary).or(version.files.first()) else {
+        return Ok(None);
+    };
+    let path = std::env::temp_dir().join(format!("mcmod-dep-{}.jar", version.id));
+    let _ = std::fs::remove_file(&path);
+    http_download(&file.url, &path, &mut |_, _| {})?;
+    if let Some(expected) = file.hashes.get("sha1") {
+        let actual = sha1_file(&path)?;
+        if actual != *expected {
+            let _ = std::fs::remove_file(&path);
+            return Err(McmodError::Other(format!("Checksum mismatch for {} (expected {expected}, got {actual})", file.url)));
+        }
+    }
+    let id = Jar::open(&path).and_then(|jar| crate::commands::dev::mod_id_for(&jar, loader));
+    let _ = std::fs::remove_file(&path);
+    id
+}
+
+/// fabric.mod.json with `id` in `depends` (any version). Unchanged if it's
+/// already there.
+fn add_fabric_depends(content: &str, id: &str) -> Result<String> {
+    let mut json: serde_json::Value = serde_json::from_str(content)?;
+    let Some(root) = json.as_object_mut() else {
+        return Err(McmodError::Other("fabric.mod.json is not a JSON object".to_string()));
+    };
+    let depends = root.entry("depends").or_insert_with(|| serde_json::json!({}));
+    let Some(depends) = depends.as_object_mut() else {
+        return Err(McmodError::Other("fabric.mod.json's depends is not an object".to_string()));
+    };
+    if depends.contains_key(id) {
+        return Ok(content.to_string());
+    }
+    depends.insert(id.to_string(), serde_json::Value::String("*".to_string()));
+    Ok(serde_json::to_string_pretty(&json)? + "\n")
+}
+
+/// quilt.mod.json with `id` in `quilt_loader.depends` (any version).
+/// Unchanged if it's already there.
+fn add_quilt_depends(content: &str, id: &str) -> Result<String> {
+    let mut json: serde_json::Value = serde_json::from_str(content)?;
+    let Some(loader) = json.get_mut("quilt_loader").and_then(|l| l.as_object_mut()) else {
+        return Err(McmodError::Other("quilt.mod.json has no quilt_loader object".to_string()));
+    };
+    let depends = loader.entry("depends").or_insert_with(|| serde_json::json!([]));
+    let Some(depends) = depends.as_array_mut() else {
+        return Err(McmodError::Other("quilt.mod.json's depends is not an array".to_string()));
+    };
+    let listed = depends.iter().any(|d| match d {
+        serde_json::Value::Object(object) => object.get("id").and_then(|i| i.as_str()) == Some(id),
+        other => other.as_str() == Some(id),
+    });
+    if listed {
+        return Ok(content.to_string());
+    }
+    depends.push(serde_json::json!({ "id": id, "versions": "*" }));
+    Ok(serde_json::to_string_pretty(&json)? + "\n")
+}
+
+/// neoforge.mods.toml with a required `[[dependencies.${id}]]` entry for `id`.
+/// Unchanged if one is already there.
+fn add_neoforge_dependency(content: &str, id: &str) -> String {
+    let line = format!("modId = \"{id}\"");
+    if content.lines().any(|l| l.trim() == line) {
+        return content.to_string();
+    }
+    format!(
+        "{}\n\n[[dependencies.${{id}}]]\n{line}\ntype = \"required\"\nversionRange = \"[0,)\"\nordering = \"NONE\"\nside = \"BOTH\"\n",
+        content.trim_end_matches('\n')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(
+            Spec::parse("modrinth:cloth-config").unwrap(),
+            Spec::Modrinth { slug: "cloth-config".to_string(), pin: None }
+        );
+        assert_eq!(
+            Spec::parse("modrinth:cloth-config@17.0.144+fabric").unwrap(),
+            Spec::Modrinth { slug: "cloth-config".to_string(), pin: Some("17.0.144+fabric".to_string()) }
+        );
+        let maven = Spec::parse("me.shedaniel.cloth:cloth-config-fabric:17.0.144").unwrap();
+        assert_eq!(maven.name(), "cloth-config-fabric");
+        assert!(Spec::parse("cloth-config").is_err());
+        assert!(Spec::parse("a::1").is_err());
+        assert!(Spec::parse("modrinth:").is_err());
+    }
+
+    #[test]
+    fn test_pick_version() {
+        let versions = || -> Vec<ModrinthVersion> {
+            serde_json::from_str(
+                r#"[
+                {"id": "b", "version_number": "2.0-beta", "version_type": "beta", "files": []},
+                {"id": "r", "version_number": "1.9", "version_type": "release",
+                 "files": [{"url": "https://cdn/x.jar", "primary": true, "hashes": {"sha1": "00"}}]}
+            ]"#,
+            )
+            .unwrap()
+        };
+        assert_eq!(pick_version(versions(), None).unwrap().id, "r");
+        assert_eq!(pick_version(versions(), Some("2.0-beta")).unwrap().id, "b");
+        assert!(pick_version(versions(), Some("3.0")).is_none());
+        assert!(pick_version(Vec::new(), None).is_none());
+    }
+
+    #[test]
+    fn test_run_maven() {
+        let dir = Path::new("/memory/adddep");
+        let (result, fs) = crate::fs::in_memory(|| {
+            crate::commands::init::run(crate::commands::init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(dir, "me.shedaniel.cloth:cloth-config:17.0.144", None, Some("https://maven.shedaniel.me/"))
+        });
+        result.unwrap();
+
+        let properties = fs.read_string(&dir.join("versions/dependencies/1.21.4.properties")).unwrap();
+        assert!(properties.contains("dep.cloth-config.fabric=17.0.144\ndep.cloth-config.neoforge=17.0.144"));
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("maven(\"https://maven.shedaniel.me/\")"));
+        assert!(build.contains("modImplementation(\"me.shedaniel.cloth:cloth-config:$it\")"));
+        assert!(fs.read_string(&dir.join(FABRIC_MOD_JSON)).unwrap().contains("\"cloth-config\": \"*\""));
+        assert!(fs.read_string(&dir.join(NEOFORGE_MODS_TOML)).unwrap().contains("modId = \"cloth_config\""));
+
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        let dep = &config.dependencies["cloth-config"];
+        assert_eq!(dep.source, DependencySource::Maven);
+        assert_eq!(dep.artifact, "me.shedaniel.cloth:cloth-config");
+        assert_eq!(dep.mod_ids["neoforge"], "cloth_config");
+    }
+
+    #[test]
+    fn test_metadata_edits() {
+        let fabric = "{\n  \"id\": \"${id}\",\n  \"depends\": {\n    \"fabricloader\": \"*\"\n  }\n}\n";
+        let added = add_fabric_depends(fabric, "cloth-config").unwrap();
+        assert_eq!(added, "{\n  \"id\": \"${id}\",\n  \"depends\": {\n    \"fabricloader\": \"*\",\n    \"cloth-config\": \"*\"\n  }\n}\n");
+        assert_eq!(add_fabric_depends(&added, "cloth-config").unwrap(), added);
+
+        let neoforge = "[[mods]]\nmodId = \"${id}\"\n";
+        let added = add_neoforge_dependency(neoforge, "cloth_config");
+        assert!(added.ends_with("\n\n[[dependencies.${id}]]\nmodId = \"cloth_config\"\ntype = \"required\"\nversionRange = \"[0,)\"\nordering = \"NONE\"\nside = \"BOTH\"\n"));
+        toml::from_str::<toml::Table>(&added.replace("${id}", "mymod")).unwrap();
+        assert_eq!(add_neoforge_dependency(&added, "cloth_config"), added);
+
+        let quilt = "{\n  \"quilt_loader\": {\n    \"depends\": [\n      \"quilt_loader\"\n    ]\n  }\n}\n";
+        let added = add_quilt_depends(quilt, "cloth-config").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&added).unwrap();
+        assert_eq!(json["quilt_loader"]["depends"][1], serde_json::json!({ "id": "cloth-config", "versions": "*" }));
+        assert_eq!(add_quilt_depends(&added, "cloth-config").unwrap(), added);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::config::{McmodConfig, VersionTarget, Versions, CONFIG_FILE};
+use crate::error::{Context, McmodError, Result};
+use crate::lockfile::Lockfile;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Directories loader metadata is looked for in: the project itself, then
+/// the subprojects of the usual multiloader layouts.
+const MODULES: &[&str] = &["", "fabric", "neoforge", "forge", "quilt", "common"];
+
+/// gradle.properties keys each value is read from, most specific first
+/// (mcmod's own, MultiLoader-Template, the NeoForge MDK, fabric-example-mod).
+const MOD_ID_KEYS: &[&str] = &["mod.id", "mod_id", "archives_base_name"];
+const MOD_NAME_KEYS: &[&str] = &["mod.name", "mod_name"];
+const GROUP_KEYS: &[&str] = &["mod.group", "group", "mod_group_id", "maven_group"];
+const AUTHOR_KEYS: &[&str] = &["mod.author", "mod_author", "mod_authors"];
+const DESCRIPTION_KEYS: &[&str] = &["mod.description", "mod_description", "description"];
+const MINECRAFT_KEYS: &[&str] = &["minecraft_version"];
+const FABRIC_LOADER_KEYS: &[&str] = &["fabric_loader_version", "loader_version"];
+const FABRIC_API_KEYS: &[&str] = &["fabric_api_version", "fabric_version"];
+const NEOFORGE_KEYS: &[&str] = &["neoforge_version", "neo_version"];
+const FORGE_KEYS: &[&str] = &["forge_version"];
+const QUILT_KEYS: &[&str] = &["quilt_loader_version"];
+
+/// What the project's files say about it. Each value is kept with the file
+/// it came from, for the summary.
+#[derive(Debug, Default)]
+struct Inferred {
+    mod_id: Option<(String, String)>,
+    mod_name: Option<(String, String)>,
+    package: Option<(String, String)>,
+    author: Option<(String, String)>,
+    description: Option<(String, String)>,
+    minecraft: Option<(String, String)>,
+    kotlin: bool,
+    fabric: bool,
+    neoforge: bool,
+    forge: bool,
+    quilt: bool,
+    fabric_loader: String,
+    fabric_api: String,
+    neoforge_version: String,
+    forge_version: String,
+    quilt_loader: String,
+}
+
+/// `mcmod adopt`: write mcmod.toml for a mod project mcmod didn't create,
+/// from its gradle.properties and loader metadata, so `add`, `upgrade` and
+/// `gen` can work on it.
+pub fn run(dir: &Path, force: bool, dry_run: bool) -> Result<()> {
+    println!("{}", "\n  mcmod adopt\n".bold().cyan());
+    if crate::fs::exists(&dir.join(CONFIG_FILE)) && !force {
+        return Err(McmodError::Other(format!(
+            "{} already has {CONFIG_FILE}; pass --force to replace it",
+            dir.display()
+        )));
+    }
+
+    let inferred = inspect(dir)?;
+    let config = build_config(dir, &inferred)?;
+    print_summary(&inferred, &config);
+
+    if dry_run {
+        println!("\n{}", format!("  Dry run: {CONFIG_FILE} was not written").yellow());
+        return Ok(());
+    }
+    config.save(dir)?;
+    // The versions in use become the locked ones, so `add` keeps them
+    let mut lock = Lockfile::load(dir)?;
+    lock.record_targets(&config.versions.targets, |_, _| None);
+    lock.save(dir)?;
+
+    println!("\n{}", format!("  Wrote {CONFIG_FILE} and mcmod.lock").bold().green());
+    println!(
+        "  mcmod expects a Stonecutter layout; run {} to see what it would generate differently.",
+        "mcmod diff --stat".cyan()
+    );
+    Ok(())
+}
+
+/// Read what can be inferred from gradle.properties and the fabric.mod.json,
+/// quilt.mod.json and (neoforge.)mods.toml of the project and its modules.
+fn inspect(dir: &Path) -> Result<Inferred> {
+    let mut inferred = Inferred::default();
+
+    let properties_path = dir.join("gradle.properties");
+    if crate::fs::exists(&properties_path) {
+        let content = crate::fs::read_to_string(&properties_path).context("Reading gradle.properties")?;
+        read_properties(&mut inferred, &parse_properties(&content), "gradle.properties");
+    }
+
+    for module in MODULES {
+        let base = dir.join(module).join("src/main");
+        let resources = base.join("resources");
+        let label = |file: &str| {
+            if module.is_empty() {
+                format!("src/main/resources/{file}")
+            } else {
+                format!("{module}/src/main/resources/{file}")
+            }
+        };
+        if crate::fs::exists(&base.join("kotlin")) {
+            inferred.kotlin = true;
+        }
+        if let Some(content) = read_optional(&resources.join("fabric.mod.json"))? {
+            inferred.fabric = true;
+            read_fabric_mod_json(&mut inferred, &content, &label("fabric.mod.json"))?;
+        }
+        if crate::fs::exists(&resources.join("quilt.mod.json")) {
+            inferred.quilt = true;
+        }
+        for (file, neoforge) in [("META-INF/neoforge.mods.toml", true), ("META-INF/mods.toml", false)] {
+            if let Some(content) = read_optional(&resources.join(file))? {
+                // NeoForge used mods.toml before 20.5
+                if neoforge || !inferred.neoforge_version.is_empty() || *module == "neoforge" {
+                    inferred.neoforge = true;
+                } else {
+                    inferred.forge = true;
+                }
+                read_mods_toml(&mut inferred, &content, &label(file))?;
+            }
+        }
+    }
+
+    inferred.fabric |= !inferred.fabric_loader.is_empty();
+    inferred.neoforge |= !inferred.neoforge_version.is_empty();
+    inferred.forge |= !inferred.forge_version.is_empty();
+    inferred.quilt |= !inferred.quilt_loader.is_empty();
+    Ok(inferred)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    if !crate::fs::exists(path) {
+        return Ok(None);
+    }
+    crate::fs::read_to_string(path)
+        .map(Some)
+        .with_context(|| format!("Reading {}", path.display()))
+}
+
+/// `key=value` (or `key: value`) lines of a .properties file.
+fn parse_properties(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| line.split_once(['=', ':']))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn read_properties(inferred: &mut Inferred, properties: &BTreeMap<String, String>, source: &str) {
+    let get = |keys: &[&str]| {
+        keys.iter()
+            .filter_map(|key| properties.get(*key))
+            .find(|value| is_literal(value))
+            .cloned()
+    };
+    let with_source = |value: Option<String>| value.map(|v| (v, source.to_string()));
+    inferred.mod_id = with_source(get(MOD_ID_KEYS));
+    inferred.mod_name = with_source(get(MOD_NAME_KEYS));
+    inferred.package = with_source(get(GROUP_KEYS));
+    inferred.author = with_source(get(AUTHOR_KEYS));
+    inferred.description = with_source(get(DESCRIPTION_KEYS));
+    inferred.minecraft = with_source(get(MINECRAFT_KEYS));
+    inferred.fabric_loader = get(FABRIC_LOADER_KEYS).unwrap_or_default();
+    inferred.fabric_api = get(FABRIC_API_KEYS).unwrap_or_default();
+    inferred.neoforge_version = get(NEOFORGE_KEYS).unwrap_or_default();
+    inferred.forge_version = get(FORGE_KEYS).unwrap_or_default();
+    inferred.quilt_loader = get(QUILT_KEYS).unwrap_or_default();
+}
+
+/// Values that are placeholders expanded at build time (`${mod_id}`) say
+/// nothing about the project.
+fn is_literal(value: &str) -> bool {
+    !value.is_empty() && !value.contains("${")
+}
+
+/// Fill `slot` with `value` unless gradle.properties already did.
+fn fill(slot: &mut Option<(String, String)>, value: Option<&str>, source: &str) {
+    if slot.is_none() {
+        if let Some(value) = value.filter(|v| is_literal(v)) {
+            *slot = Some((value.to_string(), source.to_string()));
+        }
+    }
+}
+
+fn read_fabric_mod_json(inferred: &mut Inferred, content: &str, source: &str) -> Result<()> {
+    let json: serde_json::Value = serde_json::from_str(content).context(format!("Parsing {source}"))?;
+    fill(&mut inferred.mod_id, json["id"].as_str(), source);
+    fill(&mut inferred.mod_name, json["name"].as_str(), source);
+    fill(&mut inferred.description, json["description"].as_str(), source);
+    let author = json["authors"].get(0).and_then(|a| a.as_str().or_else(|| a["name"].as_str()));
+    fill(&mut inferred.author, author, source);
+
+    // The main entrypoint's class is in the mod's package, or a loader
+    // subpackage of it in multiloader layouts
+    if let Some(main) = json["entrypoints"]["main"].get(0) {
+        if main["adapter"].as_str() == Some("kotlin") {
+            inferred.kotlin = true;
+        }
+        let class = main.as_str().or_else(|| main["value"].as_str());
+        let package = class.and_then(|c| c.rsplit_once('.')).map(|(package, _)| package);
+        let package = package.map(|p| {
+            ["fabric", "quilt"]
+                .iter()
+                .find_map(|loader| p.strip_suffix(&format!(".{loader}")))
+                .unwrap_or(p)
+        });
+        // An entrypoint package wins over a Maven group
+        if let Some(package) = package.filter(|p| is_literal(p)) {
+            inferred.package = Some((package.to_string(), source.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn read_mods_toml(inferred: &mut Inferred, content: &str, source: &str) -> Result<()> {
+    let toml: toml::Value = toml::from_str(content).context(format!("Parsing {source}"))?;
+    let Some(entry) = toml.get("mods").and_then(|m| m.get(0)) else {
+        return Ok(());
+    };
+    let get = |key: &str| entry.get(key).and_then(|v| v.as_str());
+    fill(&mut inferred.mod_id, get("modId"), source);
+    fill(&mut inferred.mod_name, get("displayName"), source);
+    fill(&mut inferred.description, get("description").map(str::trim), source);
+    fill(&mut inferred.author, get("authors"), source);
+    Ok(())
+}
+
+/// The mcmod.toml for what was inferred. The mod id and Minecraft version
+/// are required; other fields fall back to defaults derived from them.
+fn build_config(dir: &Path, inferred: &Inferred) -> Result<McmodConfig> {
+    let value = |slot: &Option<(String, String)>| slot.as_ref().map(|(v, _)| v.clone());
+    let mod_id = value(&inferred.mod_id).ok_or_else(|| {
+        McmodError::Other(
+            "Couldn't find the mod id: expected mod_id in gradle.properties, or a fabric.mod.json or mods.toml"
+                .to_string(),
+        )
+    })?;
+    crate::util::validate_mod_id(&mod_id)?;
+    let minecraft = value(&inferred.minecraft).ok_or_else(|| {
+        McmodError::Other("Couldn't find minecraft_version in gradle.properties".to_string())
+    })?;
+    if !(inferred.fabric || inferred.neoforge || inferred.forge || inferred.quilt) {
+        return Err(McmodError::Other(
+            "Couldn't tell which loaders the project targets: no loader metadata or loader versions found".to_string(),
+        ));
+    }
+
+    let author = value(&inferred.author).unwrap_or_default();
+    let package = value(&inferred.package)
+        .unwrap_or_else(|| crate::util::default_package(&author, None, &mod_id));
+    crate::util::validate_package(&package)?;
+
+    // Versions the project doesn't pin come from mcmod's table, so upgrade
+    // has something to compare against
+    let defaults = crate::version_meta::targets_to_ranges(&[minecraft.as_str()]).pop();
+    let pick = |own: &str, default: Option<&String>| {
+        if own.is_empty() { default.cloned().unwrap_or_default() } else { own.to_string() }
+    };
+    let target = VersionTarget {
+        minecraft: minecraft.clone(),
+        max_minecraft: minecraft.clone(),
+        fabric_loader: pick(&inferred.fabric_loader, defaults.as_ref().map(|d| &d.fabric_loader)),
+        fabric_api: pick(&inferred.fabric_api, defaults.as_ref().map(|d| &d.fabric_api)),
+        neoforge: pick(&inferred.neoforge_version, defaults.as_ref().map(|d| &d.neoforge)),
+        forge: inferred.forge_version.clone(),
+        quilt_loader: inferred.quilt_loader.clone(),
+        architectury_api: String::new(),
+    };
+
+    let mut config = McmodConfig::new(
+        mod_id.clone(),
+        value(&inferred.mod_name).unwrap_or_else(|| mod_id.clone()),
+        package,
+        author,
+        value(&inferred.description).unwrap_or_default(),
+        if inferred.kotlin { "kotlin" } else { "java" }.to_string(),
+        inferred.fabric,
+        inferred.neoforge,
+        crate::fs::exists(&dir.join(".github/workflows")),
+        false,
+        None,
+        Versions { targets: vec![target], ..Versions::default() },
+    );
+    config.loaders.forge = inferred.forge;
+    config.loaders.quilt = inferred.quilt;
+    Ok(config)
+}
+
+fn print_summary(inferred: &Inferred, config: &McmodConfig) {
+    let info = &config.mod_info;
+    let fields = [
+        ("Mod ID", &info.mod_id, &inferred.mod_id),
+        ("Name", &info.mod_name, &inferred.mod_name),
+        ("Package", &info.package, &inferred.package),
+        ("Author", &info.author, &inferred.author),
+        ("Description", &info.description, &inferred.description),
+        ("Minecraft", &config.versions.targets[0].minecraft, &inferred.minecraft),
+    ];
+    for (label, value, found) in fields {
+        let source = match found {
+            Some((_, source)) => format!("from {source}").dimmed(),
+            None => "default".yellow(),
+        };
+        println!("  {:<12} {value}  {source}", format!("{label}:"));
+    }
+    let loaders: Vec<&str> = [
+        ("fabric", config.loaders.fabric),
+        ("neoforge", config.loaders.neoforge),
+        ("forge", config.loaders.forge),
+        ("quilt", config.loaders.quilt),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect();
+    println!("  {:<12} {}", "Loaders:", loaders.join(", "));
+    println!("  {:<12} {}", "Language:", info.language);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_properties() {
+        let props = parse_properties("# comment\nmod_id=examplemod\nminecraft_version = 1.21.1\n\nfoo: bar\n");
+        assert_eq!(props["mod_id"], "examplemod");
+        assert_eq!(props["minecraft_version"], "1.21.1");
+        assert_eq!(props["foo"], "bar");
+        assert!(!props.contains_key("# comment"));
+    }
+
+    #[test]
+    fn test_adopt_multiloader_project() {
+        let dir = Path::new("/project");
+        let (result, fs) = crate::fs::in_memory(|| -> Result<()> {
+            let write = |path: &str, content: &str| crate::util::write_file(&dir.join(path), content);
+            write(
+                "gradle.properties",
+                "group=com.example.examplemod\nmod_id=examplemod\nmod_name=Example Mod\nmod_author=Alex\n\
+                 minecraft_version=1.21.1\nfabric_loader_version=0.16.5\nneoforge_version=21.1.66\n",
+            )?;
+            write(
+                "fabric/src/main/resources/fabric.mod.json",
+                r#"{"id": "${mod_id}", "description": "Adds examples",
+                    "entrypoints": {"main": ["com.example.examplemod.fabric.ExampleModFabric"]}}"#,
+            )?;
+            write(
+                "neoforge/src/main/resources/META-INF/neoforge.mods.toml",
+                "[[mods]]\nmodId=\"${mod_id}\"\n",
+            )?;
+            run(dir, false, false)?;
+
+            let config = McmodConfig::load(dir)?;
+            assert_eq!(config.mod_info.mod_id, "examplemod");
+            assert_eq!(config.mod_info.mod_name, "Example Mod");
+            assert_eq!(config.mod_info.package, "com.example.examplemod");
+            assert_eq!(config.mod_info.author, "Alex");
+            assert_eq!(config.mod_info.description, "Adds examples");
+            assert!(config.loaders.fabric && config.loaders.neoforge && !config.loaders.forge);
+            let target = &config.versions.targets[0];
+            assert_eq!(target.fabric_loader, "0.16.5");
+            assert_eq!(target.neoforge, "21.1.66");
+            assert_eq!(Lockfile::load(dir)?.locked("1.21.1", "neoforge").as_deref(), Some("21.1.66"));
+
+            // An existing mcmod.toml is only replaced with --force
+            assert!(run(dir, false, false).is_err());
+            run(dir, true, true)
+        });
+        result.unwrap();
+        assert!(fs.read_string(&dir.join(CONFIG_FILE)).is_some());
+    }
+
+    #[test]
+    fn test_adopt_needs_minecraft_version() {
+        let dir = Path::new("/project");
+        let (result, _) = crate::fs::in_memory(|| {
+            crate::util::write_file(&dir.join("gradle.properties"), "mod_id=examplemod\nloader_version=0.16.5\n")?;
+            run(dir, false, false)
+        });
+        assert!(result.unwrap_err().to_string().contains("minecraft_version"));
+    }
+}
@@ -0,0 +1,278 @@
+use crate::config::McmodConfig;
+use crate::content::{self, ContentManifest, MANIFEST_FILE};
+use crate::error::{McmodError, Result};
+use crate::json_merge::Json;
+use crate::lockfile::{Lockfile, Stub};
+use colored::Colorize;
+use std::path::Path;
+
+/// Side of the placeholder texture, in pixels.
+const STUB_SIZE: usize = 16;
+/// Side of one checker square; 8 gives the vanilla missing-texture look.
+const CHECKER: usize = 8;
+const MAGENTA: [u8; 3] = [0xf8, 0x00, 0xf8];
+const BLACK: [u8; 3] = [0x00, 0x00, 0x00];
+
+/// Create placeholder models and textures for content.toml entries that don't
+/// have them yet, so the game runs before the real art exists. With `clean`,
+/// delete the stubs recorded in mcmod.lock instead.
+pub fn run(dir: &Path, clean: bool) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    if clean {
+        return clean_stubs(dir);
+    }
+    let Some(manifest) = ContentManifest::load(dir)? else {
+        return Err(McmodError::Other(format!(
+            "No {MANIFEST_FILE} in {}; list your items and blocks there first",
+            dir.display()
+        )));
+    };
+
+    let created = stub(dir, &config, &manifest)?;
+    if created.is_empty() {
+        println!("{}", "  Every model and texture already exists".green());
+        return Ok(());
+    }
+    for rel in &created {
+        crate::util::progress(format!("  Stubbed {rel}").green());
+    }
+    println!(
+        "\n  {}",
+        format!("{} placeholder(s) recorded in mcmod.lock; `mcmod assets stub --clean-stubs` removes them", created.len())
+            .dimmed()
+    );
+    Ok(())
+}
+
+/// Write the missing models, then a checker texture for every texture in this
+/// mod's namespace that a model references but doesn't exist. Returns the
+/// created paths relative to the project.
+fn stub(dir: &Path, config: &McmodConfig, manifest: &ContentManifest) -> Result<Vec<String>> {
+    let assets = content::assets_dir(dir, config);
+    let mut files: Vec<(String, Vec<u8>)> = content::model_files(config, manifest)
+        .into_iter()
+        .map(|(rel, content)| (rel, content.into_bytes()))
+        .collect();
+    let png = checker_png();
+    let mut textures = referenced_textures(&assets, &config.mod_info.mod_id, &files);
+    for (atlas, entries) in manifest.sprite_groups() {
+        textures.extend(entries.iter().map(|e| e.texture_path(atlas)));
+    }
+    for texture in textures {
+        let rel = format!("textures/{texture}.png");
+        if !files.iter().any(|(r, _)| *r == rel) {
+            files.push((rel, png.clone()));
+        }
+    }
+
+    write_stubs(dir, &assets, files)
+}
+
+/// Write a checker placeholder for `texture` (`item/ruby`, in the mod's
+/// namespace) unless it exists, recorded in mcmod.lock like the stubs above.
+/// Returns the created path relative to the project.
+pub fn stub_texture(dir: &Path, config: &McmodConfig, texture: &str) -> Result<Option<String>> {
+    let assets = content::assets_dir(dir, config);
+    let files = vec![(format!("textures/{texture}.png"), checker_png())];
+    Ok(write_stubs(dir, &assets, files)?.pop())
+}
+
+/// Write each (path relative to `assets`, content) that doesn't exist yet
+/// and record it as a stub. Returns the created paths relative to `dir`.
+fn write_stubs(dir: &Path, assets: &Path, files: Vec<(String, Vec<u8>)>) -> Result<Vec<String>> {
+    let mut lock = Lockfile::load(dir)?;
+    let mut created = Vec::new();
+    for (rel, bytes) in files {
+        let path = assets.join(&rel);
+        if crate::fs::exists(&path) {
+            continue;
+        }
+        crate::util::write_binary(&path, &bytes)?;
+        let rel = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        lock.stubs.retain(|s| s.path != rel);
+        lock.stubs.push(Stub { path: rel.clone(), sha256: crate::util::sha256_bytes(&bytes) });
+        created.push(rel);
+    }
+    if !created.is_empty() {
+        lock.save(dir)?;
+    }
+    Ok(created)
+}
+
+/// Texture paths (`item/ruby`) in `mod_id`'s namespace referenced by the
+/// models on disk or about to be written, without a PNG yet.
+fn referenced_textures(assets: &Path, mod_id: &str, pending: &[(String, Vec<u8>)]) -> Vec<String> {
+    let mut models: Vec<String> = content::json_files(&assets.join("models"))
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect();
+    models.extend(
+        pending
+            .iter()
+            .filter(|(rel, _)| rel.starts_with("models/"))
+            .map(|(_, bytes)| String::from_utf8_lossy(bytes).into_owned()),
+    );
+
+    let mut textures = Vec::new();
+    for model in models {
+        let Ok(Json::Object(entries)) = Json::parse(&model) else { continue };
+        let Some((_, Json::Object(refs))) = entries.into_iter().find(|(k, _)| k == "textures") else {
+            continue;
+        };
+        for (_, texture) in refs {
+            let Json::String(texture) = texture else { continue };
+            let Some(texture) = texture.strip_prefix(&format!("{mod_id}:")) else { continue };
+            if !assets.join(format!("textures/{texture}.png")).exists() && !textures.iter().any(|t| t == texture) {
+                textures.push(texture.to_string());
+            }
+        }
+    }
+    textures
+}
+
+/// Delete the stubs recorded in mcmod.lock, keeping any that were edited.
+fn clean_stubs(dir: &Path) -> Result<()> {
+    let mut lock = Lockfile::load(dir)?;
+    if lock.stubs.is_empty() {
+        println!("{}", "  No stubs recorded in mcmod.lock".green());
+        return Ok(());
+    }
+    let (removed, kept) = remove_stubs(dir, &mut lock)?;
+    lock.save(dir)?;
+    for rel in &removed {
+        crate::util::progress(format!("  Removed {rel}").green());
+    }
+    for rel in &kept {
+        println!("  {}", format!("Kept {rel}: it was changed after stubbing").yellow());
+    }
+    Ok(())
+}
+
+/// Remove unchanged stubs and forget all recorded ones. Returns the removed
+/// and the kept (edited) paths.
+fn remove_stubs(dir: &Path, lock: &mut Lockfile) -> Result<(Vec<String>, Vec<String>)> {
+    let mut removed = Vec::new();
+    let mut kept = Vec::new();
+    for stub in lock.stubs.drain(..) {
+        let path = dir.join(&stub.path);
+        if !path.exists() {
+            continue;
+        }
+        if crate::util::sha256_file(&path)? == stub.sha256 {
+            crate::fs::remove_file(&path)?;
+            removed.push(stub.path);
+        } else {
+            kept.push(stub.path);
+        }
+    }
+    Ok((removed, kept))
+}
+
+/// A 16x16 magenta and black checkerboard PNG: uncompressed RGB, so it needs
+/// no image library.
+fn checker_png() -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(STUB_SIZE * (1 + STUB_SIZE * 3));
+    for y in 0..STUB_SIZE {
+        pixels.push(0); // filter: none
+        for x in 0..STUB_SIZE {
+            let color = if (x / CHECKER + y / CHECKER).is_multiple_of(2) { MAGENTA } else { BLACK };
+            pixels.extend(color);
+        }
+    }
+
+    let mut ihdr = Vec::new();
+    ihdr.extend((STUB_SIZE as u32).to_be_bytes());
+    ihdr.extend((STUB_SIZE as u32).to_be_bytes());
+    ihdr.extend([8, 2, 0, 0, 0]); // 8-bit RGB, deflate, no filter, no interlace
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut png, b"IHDR", &ihdr);
+    png_chunk(&mut png, b"IDAT", &zlib_stored(&pixels));
+    png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crate::util::crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wrap `data` (under 64 KiB) in a zlib stream made of one stored block.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let len = data.len() as u16;
+    let mut out = vec![0x78, 0x01, 0x01];
+    out.extend(len.to_le_bytes());
+    out.extend((!len).to_le_bytes());
+    out.extend(data);
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checker_png() {
+        let png = checker_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(crate::util::crc32(b"IEND"), 0xae42_6082);
+        assert!(png.ends_with(&[0xae, 0x42, 0x60, 0x82]));
+        assert_eq!(&png[16..24], &[0, 0, 0, 16, 0, 0, 0, 16]);
+    }
+
+    #[test]
+    fn test_stub_and_clean() {
+        let dir = std::env::temp_dir().join(format!("mcmod_assets_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        config.save(&dir).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), "[[items]]\nid = \"ruby\"\n\n[[blocks]]\nid = \"ruby_block\"\n").unwrap();
+        let assets = content::assets_dir(&dir, &config);
+        std::fs::create_dir_all(assets.join("textures/item")).unwrap();
+        std::fs::write(assets.join("textures/item/ruby.png"), "art").unwrap();
+
+        let manifest = ContentManifest::load(&dir).unwrap().unwrap();
+        let created = stub(&dir, &config, &manifest).unwrap();
+        let rel = "src/main/resources/assets/testmod";
+        assert!(created.contains(&format!("{rel}/models/item/ruby.json")));
+        assert!(created.contains(&format!("{rel}/textures/block/ruby_block.png")));
+        assert!(!created.contains(&format!("{rel}/textures/item/ruby.png")), "real art is kept");
+        assert!(content::validate(&dir, &config).is_empty());
+        assert!(stub(&dir, &config, &manifest).unwrap().is_empty());
+
+        std::fs::write(assets.join("models/block/ruby_block.json"), "{}").unwrap();
+        let mut lock = Lockfile::load(&dir).unwrap();
+        assert_eq!(lock.stubs.len(), created.len());
+        let (removed, kept) = remove_stubs(&dir, &mut lock).unwrap();
+        assert_eq!(kept, [format!("{rel}/models/block/ruby_block.json")]);
+        assert_eq!(removed.len(), created.len() - 1);
+        assert!(!assets.join("textures/block/ruby_block.png").exists());
+        assert!(assets.join("textures/item/ruby.png").exists());
+        assert!(lock.stubs.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
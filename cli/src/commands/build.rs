@@ -0,0 +1,280 @@
+use crate::commands::{doctor, report};
+use crate::config::McmodConfig;
+use crate::error::{McmodError, Result};
+use colored::{ColoredString, Colorize};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Java release every supported Minecraft version (1.20.5+) needs.
+const REQUIRED_JAVA: u32 = 21;
+
+/// `mcmod build`: run the Gradle wrapper with the task for the project (or
+/// one loader), stream its output, then list and check the jars it produced.
+pub fn run(dir: &Path, loader: Option<&str>) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts")).unwrap_or_default();
+    let tasks = gradle_tasks(&config, &settings, loader)?;
+    let gradlew = gradle_wrapper(dir)?;
+    let (java, version) = check_java()?;
+
+    println!("{}", "\n  mcmod build\n".bold().cyan());
+    println!("  {}", format!("Java {version} ({})", java.display()).dimmed());
+    println!("  {}\n", format!("./gradlew {}", tasks.join(" ")).dimmed());
+    let status = gradle(dir, &gradlew, &tasks)?;
+    if !status.success() {
+        return Err(McmodError::Other(match status.code() {
+            Some(code) => format!("Gradle build failed (exit code {code})"),
+            None => "Gradle build was interrupted".to_string(),
+        }));
+    }
+
+    if !config.uses_stonecutter() {
+        println!("\n  {}", "Artifacts".bold());
+        for path in files_in(&dir.join("build/libs")) {
+            println!("    {}", path.strip_prefix(dir).unwrap_or(&path).display());
+        }
+        return Ok(());
+    }
+    let jars: Vec<_> = report::find_jars(dir)
+        .into_iter()
+        .filter(|jar| loader.is_none() || jar.loader.as_deref() == loader)
+        .collect();
+    println!("\n  {}", "Jars".bold());
+    let failed = report::report(dir, &config, &jars)?;
+    println!();
+    if failed > 0 {
+        return Err(McmodError::Other(format!("{failed} jar(s) have packaging problems")));
+    }
+    Ok(())
+}
+
+/// The Gradle tasks to run: `chiseledBuild` for every target, or each
+/// `:<mc>-<loader>:build` listed in settings.gradle.kts for one loader.
+pub fn gradle_tasks(config: &McmodConfig, settings: &str, loader: Option<&str>) -> Result<Vec<String>> {
+    let Some(loader) = loader else {
+        let task = if config.uses_stonecutter() { "chiseledBuild" } else { "build" };
+        return Ok(vec![task.to_string()]);
+    };
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "--loader only applies to mods and libraries (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    Ok(loader_targets(config, settings, loader)?
+        .into_iter()
+        .map(|mc| format!(":{mc}-{loader}:build"))
+        .collect())
+}
+
+/// The Minecraft versions settings.gradle.kts builds `loader` for; errors when
+/// the loader isn't enabled or has no `mc(...)` line.
+pub fn loader_targets(config: &McmodConfig, settings: &str, loader: &str) -> Result<Vec<String>> {
+    let enabled = config.enabled_platforms();
+    if !enabled.contains(&loader) {
+        return Err(McmodError::Other(format!(
+            "{loader} isn't enabled in this project (enabled: {})",
+            enabled.join(", ")
+        )));
+    }
+    let targets: Vec<String> = doctor::parse_mc_entries(settings)
+        .into_iter()
+        .filter(|(_, loaders)| loaders.iter().any(|l| l == loader))
+        .map(|(mc, _)| mc)
+        .collect();
+    if targets.is_empty() {
+        return Err(McmodError::Other(format!(
+            "settings.gradle.kts has no mc(...) line with {loader}; run `mcmod doctor --fix`"
+        )));
+    }
+    Ok(targets)
+}
+
+/// The project's Gradle wrapper script.
+pub fn gradle_wrapper(dir: &Path) -> Result<PathBuf> {
+    let gradlew = dir.join(if cfg!(windows) { "gradlew.bat" } else { "gradlew" });
+    if !gradlew.exists() {
+        return Err(McmodError::Other(format!(
+            "No Gradle wrapper in {}; run `mcmod doctor --fix` to restore it",
+            dir.display()
+        )));
+    }
+    Ok(gradlew)
+}
+
+/// Find a JDK new enough for Minecraft, with an install hint when there isn't one.
+pub fn check_java() -> Result<(PathBuf, u32)> {
+    let (java, version) = find_java()?;
+    if version < REQUIRED_JAVA {
+        return Err(McmodError::Other(format!(
+            "Found Java {version} ({}), but Minecraft needs Java {REQUIRED_JAVA}; install JDK {REQUIRED_JAVA} (https://adoptium.net) and point JAVA_HOME at it",
+            java.display()
+        )));
+    }
+    Ok((java, version))
+}
+
+/// The `java` Gradle will use (JAVA_HOME first, then PATH) and its major version.
+pub fn find_java() -> Result<(PathBuf, u32)> {
+    let java = match std::env::var_os("JAVA_HOME") {
+        Some(home) => Path::new(&home).join("bin").join(if cfg!(windows) { "java.exe" } else { "java" }),
+        None => PathBuf::from("java"),
+    };
+    let missing = || {
+        McmodError::Other(format!(
+            "No JDK found ({} could not be run); install JDK {REQUIRED_JAVA} (https://adoptium.net) and put it on PATH or set JAVA_HOME",
+            java.display()
+        ))
+    };
+    let output = Command::new(&java).arg("-version").output().map_err(|_| missing())?;
+    // `java -version` prints to stderr
+    let text = String::from_utf8_lossy(&output.stderr);
+    let version = parse_java_version(&text).ok_or_else(missing)?;
+    Ok((java, version))
+}
+
+/// Major version from `java -version` output: `version "21.0.2"` → 21,
+/// `version "1.8.0_392"` → 8.
+fn parse_java_version(output: &str) -> Option<u32> {
+    let start = output.find("version \"")? + "version \"".len();
+    let version = output[start..].split('"').next()?;
+    let mut parts = version.split(['.', '_', '-', '+']);
+    match parts.next()?.parse().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+/// Run the wrapper, echoing stdout and stderr line by line as they arrive.
+pub fn gradle(dir: &Path, gradlew: &Path, tasks: &[String]) -> Result<std::process::ExitStatus> {
+    gradle_captured(dir, gradlew, tasks).map(|(status, _)| status)
+}
+
+/// [`gradle`], also returning the output lines (stdout, then stderr) so a
+/// failure can be explained. In offline mode Gradle gets `--offline` too, so
+/// it resolves plugins and dependencies from its cache only.
+pub fn gradle_captured(dir: &Path, gradlew: &Path, tasks: &[String]) -> Result<(std::process::ExitStatus, Vec<String>)> {
+    let mut command = Command::new(gradlew);
+    command.args(tasks);
+    if crate::util::is_offline() {
+        command.arg("--offline");
+    }
+    let mut child = command
+        .arg("--console=plain")
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stderr = child.stderr.take().map(|stderr| {
+        std::thread::spawn(move || {
+            let mut lines = Vec::new();
+            for line in BufReader::new(stderr).lines().map_while(std::result::Result::ok) {
+                eprintln!("  {}", style_line(&line));
+                lines.push(line);
+            }
+            lines
+        })
+    });
+    let mut lines = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(std::result::Result::ok) {
+            println!("  {}", style_line(&line));
+            lines.push(line);
+        }
+    }
+    if let Some(handle) = stderr {
+        lines.extend(handle.join().unwrap_or_default());
+    }
+    Ok((child.wait()?, lines))
+}
+
+/// Gradle output in the CLI's colors: task headers dimmed, warnings yellow,
+/// failures red, and the final result bold.
+fn style_line(line: &str) -> ColoredString {
+    let lower = line.to_lowercase();
+    if line.starts_with("BUILD SUCCESSFUL") {
+        line.bold().green()
+    } else if line.starts_with("BUILD FAILED") || line.starts_with("FAILURE:") {
+        line.bold().red()
+    } else if line.ends_with(" FAILED") {
+        line.red()
+    } else if line.starts_with("> Task") {
+        line.dimmed()
+    } else if lower.contains("error:") || line.starts_with("e: ") || line.starts_with("* What went wrong") {
+        line.red()
+    } else if lower.contains("warning:") || line.starts_with("w: ") {
+        line.yellow()
+    } else {
+        line.normal()
+    }
+}
+
+/// Files directly in `dir`, sorted.
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProjectType;
+
+    fn config() -> McmodConfig {
+        McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_gradle_tasks() {
+        let settings = "mc(\"1.21.1\", \"fabric\", \"neoforge\")\nmc(\"1.21.4\", \"fabric\")\n";
+        let mut config = config();
+        assert_eq!(gradle_tasks(&config, settings, None).unwrap(), ["chiseledBuild"]);
+        assert_eq!(
+            gradle_tasks(&config, settings, Some("fabric")).unwrap(),
+            [":1.21.1-fabric:build", ":1.21.4-fabric:build"]
+        );
+        assert_eq!(gradle_tasks(&config, settings, Some("neoforge")).unwrap(), [":1.21.1-neoforge:build"]);
+        let err = gradle_tasks(&config, settings, Some("forge")).unwrap_err();
+        assert_eq!(err.to_string(), "forge isn't enabled in this project (enabled: fabric, neoforge)");
+
+        config.mod_info.project_type = ProjectType::Datapack;
+        assert_eq!(gradle_tasks(&config, "", None).unwrap(), ["build"]);
+        assert!(gradle_tasks(&config, "", Some("fabric")).is_err());
+    }
+
+    #[test]
+    fn test_parse_java_version() {
+        assert_eq!(parse_java_version("openjdk version \"21.0.2\" 2024-01-16\nOpenJDK Runtime"), Some(21));
+        assert_eq!(parse_java_version("java version \"1.8.0_392\""), Some(8));
+        assert_eq!(parse_java_version("openjdk version \"17\" 2021-09-14"), Some(17));
+        assert_eq!(parse_java_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_style_line() {
+        colored::control::set_override(true);
+        assert_eq!(style_line("BUILD SUCCESSFUL in 3s"), "BUILD SUCCESSFUL in 3s".bold().green());
+        assert_eq!(style_line("> Task :1.21.4-fabric:compileJava"), "> Task :1.21.4-fabric:compileJava".dimmed());
+        assert_eq!(style_line("Foo.java:3: error: ';' expected"), "Foo.java:3: error: ';' expected".red());
+        assert_eq!(style_line("plain"), "plain".normal());
+        colored::control::unset_override();
+    }
+}
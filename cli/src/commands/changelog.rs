@@ -0,0 +1,246 @@
+use crate::commands::{publish, release};
+use crate::error::{McmodError, Result};
+use colored::Colorize;
+use std::path::Path;
+
+pub const CHANGELOG: &str = "CHANGELOG.md";
+const UNRELEASED: &str = "## [Unreleased]";
+
+/// Keep a Changelog's kinds of change, each a `### ` heading in a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChangeType {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl ChangeType {
+    fn heading(self) -> &'static str {
+        match self {
+            ChangeType::Added => "### Added",
+            ChangeType::Changed => "### Changed",
+            ChangeType::Deprecated => "### Deprecated",
+            ChangeType::Removed => "### Removed",
+            ChangeType::Fixed => "### Fixed",
+            ChangeType::Security => "### Security",
+        }
+    }
+}
+
+/// `mcmod changelog add`: note a change under `[Unreleased]` in
+/// CHANGELOG.md (created if missing), below its `kind` heading when given.
+pub fn run_add(dir: &Path, entry: &str, kind: Option<ChangeType>) -> Result<()> {
+    let entry = entry.trim().trim_start_matches("- ").trim();
+    if entry.is_empty() {
+        return Err(McmodError::Other("The changelog entry is empty".to_string()));
+    }
+    let path = dir.join(CHANGELOG);
+    let existing = crate::fs::read_to_string(&path).ok();
+    crate::util::write_file(&path, &add_entry(existing.as_deref(), entry, kind))?;
+    let under = kind.map(|k| format!(" ({})", k.heading().trim_start_matches("### "))).unwrap_or_default();
+    crate::util::progress(format!("  Added to [Unreleased]{under} in {CHANGELOG}").green());
+    Ok(())
+}
+
+/// `mcmod changelog release`: turn `[Unreleased]` into the `version`
+/// section, without the rest of `mcmod release`.
+pub fn run_release(dir: &Path, version: &str) -> Result<()> {
+    let version = version.trim_start_matches('v');
+    if version.is_empty() {
+        return Err(McmodError::Other("No version given".to_string()));
+    }
+    write_release(dir, version)
+}
+
+/// Write the `version` section of CHANGELOG.md (see [`release_changelog`]),
+/// listing the commits since the last tag when `[Unreleased]` is empty.
+pub fn write_release(dir: &Path, version: &str) -> Result<()> {
+    let path = dir.join(CHANGELOG);
+    let existing = crate::fs::read_to_string(&path).ok();
+    let commits = if dir.join(".git").exists() { release::commits_since_tag(dir) } else { Vec::new() };
+    let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+    let date = &crate::lockfile::timestamp(secs)[..10];
+    let updated = release_changelog(existing.as_deref(), version, date, &commits);
+    if existing.as_deref() == Some(updated.as_str()) {
+        crate::util::progress(format!("  {CHANGELOG} already has {version}").green());
+        return Ok(());
+    }
+    match existing {
+        Some(content) => {
+            crate::diff::apply_edit(&path, &content, &updated)?;
+        }
+        None => crate::util::write_file(&path, &updated)?,
+    }
+    crate::util::progress(format!("  Added {version} to {CHANGELOG}").green());
+    Ok(())
+}
+
+/// The notes under `[Unreleased]`, if there are any.
+pub fn unreleased(changelog: &str) -> Option<String> {
+    publish::changelog_section(changelog, "Unreleased")
+}
+
+/// The changelog with `- entry` added to its `[Unreleased]` section (added
+/// above the newest release if missing): at the end of the `kind` subsection,
+/// which is created if needed, or with the notes before any subsection.
+fn add_entry(existing: Option<&str>, entry: &str, kind: Option<ChangeType>) -> String {
+    let content = existing.unwrap_or("# Changelog\n");
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let start = match lines.iter().position(|l| l.trim().eq_ignore_ascii_case(UNRELEASED)) {
+        Some(i) => i,
+        None => {
+            let at = lines.iter().position(|l| l.starts_with("## ")).unwrap_or(lines.len());
+            lines.splice(at..at, [UNRELEASED.to_string(), String::new()]);
+            at
+        }
+    };
+    let end = lines[start + 1..].iter().position(|l| l.starts_with("## ")).map_or(lines.len(), |e| start + 1 + e);
+    let subsection = |from: usize| lines[from + 1..end].iter().position(|l| l.starts_with("### ")).map_or(end, |e| from + 1 + e);
+    let bullet = format!("- {entry}");
+
+    match kind {
+        Some(kind) => match lines[start + 1..end].iter().position(|l| l.trim().eq_ignore_ascii_case(kind.heading())) {
+            Some(i) => {
+                let heading = start + 1 + i;
+                let at = last_content(&lines, heading, subsection(heading)) + 1;
+                lines.insert(at, bullet);
+            }
+            None => {
+                let at = last_content(&lines, start, end) + 1;
+                lines.splice(at..at, [String::new(), kind.heading().to_string(), bullet]);
+            }
+        },
+        None => {
+            let last = last_content(&lines, start, subsection(start));
+            if last == start {
+                lines.splice(start + 1..start + 1, [String::new(), bullet]);
+            } else {
+                lines.insert(last + 1, bullet);
+            }
+        }
+    }
+    tidy(lines)
+}
+
+/// The last non-blank line in `from..to`; `from` itself when all are blank.
+fn last_content(lines: &[String], from: usize, to: usize) -> usize {
+    (from..to).rev().find(|&i| !lines[i].trim().is_empty()).unwrap_or(from)
+}
+
+/// One blank line before each heading and none repeated or at the end.
+fn tidy(lines: Vec<String>) -> String {
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    for line in lines {
+        let blank = line.trim().is_empty();
+        let previous_blank = out.last().is_none_or(|l| l.trim().is_empty());
+        if blank && previous_blank {
+            continue;
+        }
+        if line.starts_with("## ") && !previous_blank {
+            out.push(String::new());
+        }
+        out.push(line);
+    }
+    while out.last().is_some_and(|l| l.trim().is_empty()) {
+        out.pop();
+    }
+    out.join("\n") + "\n"
+}
+
+/// The changelog with a `## [version] - date` section (Keep a Changelog
+/// style). An `## [Unreleased]` section becomes the release and a new empty
+/// one goes above it; otherwise the section lists `commits`. A changelog
+/// that already has the version is returned unchanged.
+fn release_changelog(existing: Option<&str>, version: &str, date: &str, commits: &[String]) -> String {
+    let content = existing.unwrap_or("# Changelog\n");
+    if publish::changelog_section(content, version).is_some() {
+        return content.to_string();
+    }
+    let heading = format!("## [{version}] - {date}");
+    let notes: String = if commits.is_empty() {
+        "- Release\n".to_string()
+    } else {
+        commits.iter().map(|c| format!("- {c}\n")).collect()
+    };
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    match lines.iter().position(|l| l.trim().eq_ignore_ascii_case(UNRELEASED)) {
+        Some(i) => {
+            let end = lines[i + 1..].iter().position(|l| l.starts_with("## ")).map_or(lines.len(), |e| i + 1 + e);
+            let empty = lines[i + 1..end].iter().all(|l| l.trim().is_empty());
+            lines[i] = heading;
+            if empty {
+                lines.splice(i + 1..end, ["".to_string(), notes.trim_end().to_string(), "".to_string()]);
+            }
+            lines.splice(i..i, [UNRELEASED.to_string(), String::new()]);
+        }
+        None => {
+            let at = lines.iter().position(|l| l.starts_with("## ")).unwrap_or(lines.len());
+            let mut section = vec![heading, String::new(), notes.trim_end().to_string(), String::new()];
+            if at == lines.len() && lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                section.insert(0, String::new());
+            }
+            lines.splice(at..at, section);
+        }
+    }
+    let mut out = lines.join("\n");
+    while out.ends_with("\n\n") || out.ends_with('\n') {
+        out.pop();
+    }
+    out + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_changelog() {
+        let unreleased = "# Changelog\n\n## [Unreleased]\n\n- Added widgets\n\n## [1.0.0] - 2026-01-02\n\n- First\n";
+        let released = release_changelog(Some(unreleased), "1.1.0", "2026-10-17", &[]);
+        assert_eq!(
+            released,
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2026-10-17\n\n- Added widgets\n\n## [1.0.0] - 2026-01-02\n\n- First\n"
+        );
+        assert_eq!(publish::changelog_section(&released, "1.1.0").as_deref(), Some("- Added widgets"));
+        assert_eq!(release_changelog(Some(&released), "1.1.0", "2026-10-18", &[]), released);
+
+        let commits = vec!["Fix crash on join".to_string()];
+        assert_eq!(
+            release_changelog(None, "1.0.1", "2026-10-17", &commits),
+            "# Changelog\n\n## [1.0.1] - 2026-10-17\n\n- Fix crash on join\n"
+        );
+        let empty = "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2026-01-02\n\n- First\n";
+        assert!(release_changelog(Some(empty), "1.0.1", "2026-10-17", &commits)
+            .starts_with("# Changelog\n\n## [Unreleased]\n\n## [1.0.1] - 2026-10-17\n\n- Fix crash on join\n\n## [1.0.0]"));
+    }
+
+    #[test]
+    fn test_add_entry() {
+        let created = add_entry(None, "Ruby ore", Some(ChangeType::Added));
+        assert_eq!(created, "# Changelog\n\n## [Unreleased]\n\n### Added\n- Ruby ore\n");
+        let two = add_entry(Some(&created), "Ruby tools", Some(ChangeType::Added));
+        assert_eq!(two, "# Changelog\n\n## [Unreleased]\n\n### Added\n- Ruby ore\n- Ruby tools\n");
+        let fixed = add_entry(Some(&two), "Crash on load", Some(ChangeType::Fixed));
+        assert_eq!(
+            fixed,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- Ruby ore\n- Ruby tools\n\n### Fixed\n- Crash on load\n"
+        );
+        // Notes without a kind go before the subsections
+        let plain = add_entry(Some(&fixed), "Works on 1.21.4", None);
+        assert!(plain.starts_with("# Changelog\n\n## [Unreleased]\n\n- Works on 1.21.4\n\n### Added\n"));
+        let notes = unreleased(&plain).unwrap();
+        assert!(notes.starts_with("- Works on 1.21.4") && notes.ends_with("- Crash on load"));
+
+        // [Unreleased] is added above the newest release
+        let released = "# Changelog\n\n## [1.0.0] - 2026-01-02\n\n- First\n";
+        assert_eq!(
+            add_entry(Some(released), "Second", None),
+            "# Changelog\n\n## [Unreleased]\n\n- Second\n\n## [1.0.0] - 2026-01-02\n\n- First\n"
+        );
+        assert_eq!(unreleased(released), None);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::config::{CompatEntry, CompatStatus, McmodConfig};
+use crate::error::{McmodError, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// The compatibility table generated from `[compat]` in mcmod.toml.
+pub const COMPAT_FILE: &str = "COMPATIBILITY.md";
+
+/// Fields passed to `mcmod compat add`; unset ones keep the recorded value.
+#[derive(Debug, Default)]
+pub struct CompatUpdate {
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub minecraft: Option<String>,
+    pub loader: Option<String>,
+    pub status: Option<CompatStatus>,
+    pub notes: Option<String>,
+}
+
+impl CompatUpdate {
+    /// Apply to the recorded entry, or make a new one (which needs a version).
+    fn apply(self, mod_id: &str, existing: Option<CompatEntry>) -> Result<CompatEntry> {
+        let mut entry = match existing {
+            Some(entry) => entry,
+            None => CompatEntry {
+                name: None,
+                version: self.version.clone().ok_or_else(|| {
+                    McmodError::Other(format!("{mod_id} isn't in [compat] yet; pass --version"))
+                })?,
+                minecraft: None,
+                loader: None,
+                status: CompatStatus::Works,
+                notes: None,
+            },
+        };
+        if let Some(version) = self.version {
+            entry.version = version;
+        }
+        entry.name = self.name.or(entry.name);
+        entry.minecraft = self.minecraft.or(entry.minecraft);
+        entry.loader = self.loader.or(entry.loader);
+        entry.status = self.status.unwrap_or(entry.status);
+        entry.notes = self.notes.or(entry.notes);
+        Ok(entry)
+    }
+}
+
+/// `mcmod compat add`: record a tested-against mod in mcmod.toml (or update
+/// its recorded fields) and regenerate COMPATIBILITY.md.
+pub fn run_add(dir: &Path, mod_id: &str, fields: CompatUpdate) -> Result<()> {
+    let mut config = McmodConfig::load(dir)?;
+    let existing = config.compat.remove(mod_id);
+    let replaced = existing.is_some();
+    config.compat.insert(mod_id.to_string(), fields.apply(mod_id, existing)?);
+    config.save(dir)?;
+    update(dir, &config)?;
+    let verb = if replaced { "Updated" } else { "Added" };
+    println!("{}", format!("  {verb} {mod_id} in [compat]; {COMPAT_FILE} regenerated").green());
+    Ok(())
+}
+
+/// `mcmod compat remove`: drop a mod from `[compat]` and the table.
+pub fn run_remove(dir: &Path, mod_id: &str) -> Result<()> {
+    let mut config = McmodConfig::load(dir)?;
+    if config.compat.remove(mod_id).is_none() {
+        return Err(McmodError::Other(format!("{mod_id} isn't listed in [compat]")));
+    }
+    config.save(dir)?;
+    update(dir, &config)?;
+    println!("{}", format!("  Removed {mod_id} from [compat]; {COMPAT_FILE} regenerated").green());
+    Ok(())
+}
+
+/// `mcmod compat generate`: write COMPATIBILITY.md, or with `check` fail when
+/// it doesn't match mcmod.toml (for CI).
+pub fn run_generate(dir: &Path, check: bool) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    if check {
+        let current = crate::fs::read_to_string(&dir.join(COMPAT_FILE)).unwrap_or_default();
+        if current != render(&config) {
+            return Err(McmodError::Other(format!(
+                "{COMPAT_FILE} is out of date with [compat] in mcmod.toml; run `mcmod compat generate`"
+            )));
+        }
+        println!("{}", format!("  {COMPAT_FILE} is up to date").green());
+        return Ok(());
+    }
+    if update(dir, &config)? {
+        println!("{}", format!("  Wrote {COMPAT_FILE}").green());
+    } else {
+        println!("{}", format!("  {COMPAT_FILE} is already up to date").green());
+    }
+    Ok(())
+}
+
+/// Rewrite COMPATIBILITY.md when it differs from `[compat]`. Returns whether
+/// the file changed.
+pub fn update(dir: &Path, config: &McmodConfig) -> Result<bool> {
+    let path = dir.join(COMPAT_FILE);
+    let content = render(config);
+    if crate::fs::read_to_string(&path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    crate::util::write_file(&path, &content)?;
+    Ok(true)
+}
+
+/// The Markdown table, one row per `[compat]` entry in mod id order.
+pub fn render(config: &McmodConfig) -> String {
+    let mut out = format!(
+        "# Compatibility\n\n\
+         Mods {} has been tested alongside. Generated from the `[compat]` section of\n\
+         mcmod.toml by `mcmod compat generate`; edit that instead of this file.\n\n",
+        config.mod_info.mod_name
+    );
+    if config.compat.is_empty() {
+        out.push_str("No mods have been recorded yet. Add one with `mcmod compat add <mod-id> --version <version>`.\n");
+        return out;
+    }
+    let all_loaders = config.enabled_platforms().join(", ");
+    out.push_str("| Mod | Version | Minecraft | Loader | Status | Notes |\n");
+    out.push_str("|-----|---------|-----------|--------|--------|-------|\n");
+    for (mod_id, entry) in &config.compat {
+        let name = match &entry.name {
+            Some(name) => format!("{} (`{mod_id}`)", cell(name)),
+            None => format!("`{mod_id}`"),
+        };
+        out.push_str(&format!(
+            "| {name} | {} | {} | {} | {} | {} |\n",
+            cell(&entry.version),
+            entry.minecraft.as_deref().map(cell).unwrap_or_else(|| "-".to_string()),
+            entry.loader.as_deref().map(cell).unwrap_or_else(|| all_loaders.clone()),
+            entry.status.label(),
+            entry.notes.as_deref().map(cell).unwrap_or_default(),
+        ));
+    }
+    out
+}
+
+/// Text safe inside a table cell: pipes escaped, line breaks flattened.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        assert!(render(&config).contains("No mods have been recorded yet"));
+
+        config.compat.insert(
+            "sodium".to_string(),
+            CompatEntry {
+                name: Some("Sodium".to_string()),
+                version: "0.6.5".to_string(),
+                minecraft: Some("1.21.4".to_string()),
+                loader: Some("fabric".to_string()),
+                status: CompatStatus::Issues,
+                notes: Some("Fog | sky colors differ".to_string()),
+            },
+        );
+        config.compat.insert(
+            "jei".to_string(),
+            CompatEntry {
+                name: None,
+                version: "19.21.0".to_string(),
+                minecraft: None,
+                loader: None,
+                status: CompatStatus::Works,
+                notes: None,
+            },
+        );
+        let table = render(&config);
+        let rows: Vec<&str> = table.lines().filter(|l| l.starts_with("| ")).collect();
+        assert_eq!(
+            rows,
+            [
+                "| Mod | Version | Minecraft | Loader | Status | Notes |",
+                "| `jei` | 19.21.0 | - | fabric, neoforge | ✅ Works |  |",
+                "| Sodium (`sodium`) | 0.6.5 | 1.21.4 | fabric | ⚠️ Issues | Fog \\| sky colors differ |",
+            ]
+        );
+
+        let fields = CompatUpdate { status: Some(CompatStatus::Works), ..Default::default() };
+        let sodium = fields.apply("sodium", config.compat.remove("sodium")).unwrap();
+        assert_eq!((sodium.version.as_str(), sodium.status), ("0.6.5", CompatStatus::Works));
+        assert_eq!(sodium.notes.as_deref(), Some("Fog | sky colors differ"));
+        assert!(CompatUpdate::default().apply("lithium", None).is_err());
+        config.compat.insert("sodium".to_string(), sodium);
+
+        let parsed: McmodConfig = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.compat, config.compat);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::error::Result;
+use crate::global_config::GlobalConfig;
+use crate::template;
+use crate::version_meta;
+use crate::versions;
+use clap::{Arg, Command, ValueEnum};
+
+/// Shells with a completion script.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Print the completion script for `shell`. The scripts are thin wrappers that
+/// call the hidden `mcmod __complete` command for candidates.
+pub fn run(shell: &Shell) -> Result<()> {
+    let script = match shell {
+        Shell::Bash => template::COMPLETION_BASH,
+        Shell::Zsh => template::COMPLETION_ZSH,
+        Shell::Fish => template::COMPLETION_FISH,
+        Shell::Powershell => template::COMPLETION_POWERSHELL,
+    };
+    print!("{script}");
+    Ok(())
+}
+
+/// Handle `mcmod __complete <words...>`: print one candidate per line for the
+/// last word, given the words typed before it (starting with the program name).
+pub fn run_complete(mut cli: Command, words: &[String]) -> Result<()> {
+    // Building adds the generated --help/--version flags and `help` subcommand
+    cli.build();
+    for candidate in candidates(&cli, words) {
+        println!("{candidate}");
+    }
+    Ok(())
+}
+
+fn candidates(root: &Command, words: &[String]) -> Vec<String> {
+    let (current, preceding) = match words.split_last() {
+        // PowerShell passes `""` for an empty word
+        Some((last, rest)) => (
+            if last == "\"\"" { "" } else { last.as_str() },
+            rest.get(1..).unwrap_or(&[]),
+        ),
+        None => ("", &[][..]),
+    };
+
+    // Walk the typed words to find the active subcommand and any flag awaiting a value
+    let mut cmd = root;
+    let mut pending: Option<&Arg> = None;
+    let mut positional_index = 0;
+    for word in preceding {
+        if pending.take().is_some() {
+            continue;
+        }
+        if let Some(long) = word.strip_prefix("--") {
+            if !long.contains('=') {
+                pending = find_long(cmd, long).filter(|a| takes_value(a));
+            }
+        } else if let Some(short) = word.strip_prefix('-').and_then(|s| s.chars().next()) {
+            pending = cmd
+                .get_arguments()
+                .find(|a| a.get_short() == Some(short))
+                .filter(|a| takes_value(a));
+        } else if let Some(sub) = find_subcommand(cmd, word) {
+            cmd = sub;
+            positional_index = 0;
+        } else {
+            positional_index += 1;
+        }
+    }
+
+    let mut out = if let Some(arg) = pending {
+        value_candidates(arg)
+    } else if let Some((flag, _)) = current.strip_prefix("--").and_then(|f| f.split_once('=')) {
+        find_long(cmd, flag)
+            .map(value_candidates)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v| format!("--{flag}={v}"))
+            .collect()
+    } else if current.starts_with('-') {
+        cmd.get_arguments()
+            .filter(|a| !a.is_hide_set())
+            .filter_map(|a| a.get_long())
+            .map(|l| format!("--{l}"))
+            .collect()
+    } else {
+        let mut names: Vec<String> = cmd
+            .get_subcommands()
+            .filter(|s| !s.is_hide_set())
+            .map(|s| s.get_name().to_string())
+            .collect();
+        if let Some(arg) = cmd.get_positionals().nth(positional_index) {
+            names.extend(value_candidates(arg));
+        }
+        names
+    };
+
+    out.retain(|c| c.starts_with(current));
+    out
+}
+
+fn find_long<'a>(cmd: &'a Command, long: &str) -> Option<&'a Arg> {
+    cmd.get_arguments().find(|a| {
+        a.get_long() == Some(long)
+            || a.get_all_aliases()
+                .is_some_and(|aliases| aliases.contains(&long))
+    })
+}
+
+fn find_subcommand<'a>(cmd: &'a Command, name: &str) -> Option<&'a Command> {
+    cmd.get_subcommands()
+        .find(|s| s.get_name() == name || s.get_all_aliases().any(|a| a == name))
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    arg.get_action().takes_values()
+}
+
+/// Values for an argument. Free-form arguments with a known value set are
+/// listed here; everything else falls back to clap's possible values
+/// (value enums such as `add`'s features, and booleans).
+fn value_candidates(arg: &Arg) -> Vec<String> {
+    match arg.get_id().as_str() {
+        // The release list `mcmod versions` cached, or the pinned versions before that
+        "minecraft_versions" | "mc_version" => versions::cached_minecraft_releases().unwrap_or_else(|| {
+            version_meta::supported_versions()
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        }),
+        "loaders" => vec!["fabric".to_string(), "neoforge".to_string(), "forge".to_string(), "quilt".to_string(), "sponge".to_string()],
+        "language" => vec!["java".to_string(), "kotlin".to_string()],
+        "key" | "assignments" => GlobalConfig::default()
+            .list()
+            .into_iter()
+            .map(|(_, key, _)| key)
+            .collect(),
+        _ => arg
+            .get_possible_values()
+            .into_iter()
+            .filter(|v| !v.is_hide_set())
+            .map(|v| v.get_name().to_string())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn complete(line: &str) -> Vec<String> {
+        let mut words: Vec<String> = line.split(' ').map(str::to_string).collect();
+        words.insert(0, "mcmod".to_string());
+        let mut cli = crate::Cli::command();
+        cli.build();
+        candidates(&cli, &words)
+    }
+
+    #[test]
+    fn test_complete_subcommands() {
+        assert_eq!(complete("a"), vec!["add", "about", "adopt", "assets"]);
+        assert_eq!(complete("ad"), vec!["add", "adopt"]);
+        let all = complete("");
+        assert!(all.contains(&"init".to_string()));
+        assert!(!all.iter().any(|c| c == "__complete"), "hidden commands are not offered");
+    }
+
+    #[test]
+    fn test_complete_features() {
+        let features = complete("add ");
+        assert!(features.contains(&"build-info".to_string()));
+        assert_eq!(complete("add ne"), vec!["neoforge"]);
+    }
+
+    #[test]
+    fn test_complete_minecraft_versions() {
+        let versions = complete("init --minecraft 1.21");
+        assert!(versions.contains(&"1.21.1".to_string()));
+        assert!(versions.iter().all(|v| v.starts_with("1.21")));
+        assert!(complete("init --minecraft=1.21.1")
+            .contains(&"--minecraft=1.21.1".to_string()));
+        let versions = complete("upgrade --mc-version 1.21");
+        assert!(versions.contains(&"1.21.1".to_string()));
+        assert!(versions.iter().all(|v| v.starts_with("1.21")));
+    }
+
+    #[test]
+    fn test_complete_flags_and_values() {
+        assert_eq!(complete("init --lang"), vec!["--language"]);
+        assert_eq!(complete("init --he"), vec!["--help"]);
+        assert_eq!(complete("init --language k"), vec!["kotlin"]);
+        assert_eq!(complete("init --ci "), vec!["true", "false"]);
+        assert!(complete("config set ").contains(&"author".to_string()));
+        // A flag's value doesn't count as a positional
+        assert!(complete("init --mod-id foo --").contains(&"--loader".to_string()));
+    }
+}
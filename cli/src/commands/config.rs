@@ -1,14 +1,69 @@
-use crate::error::Result;
-use crate::global_config::{self, GlobalConfig};
+use crate::error::{McmodError, Result};
+use crate::global_config::{self, ConfigSection, GlobalConfig, ImportMode};
 use colored::Colorize;
+use clap::ValueEnum;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
 
-pub fn run_set(key: &str, value: &str) -> Result<()> {
-    let mut config = GlobalConfig::load()?;
-    config.set(key, value)?;
-    println!(
-        "{}",
-        format!("  Set {key} = {value}").green()
-    );
+/// A location `mcmod config path` resolves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PathKind {
+    /// The global preferences file (config.toml)
+    Config,
+    /// The directory holding config.toml
+    ConfigDir,
+    /// Downloaded and generated files that can be safely deleted
+    Cache,
+    /// Where user template packs are kept
+    Templates,
+    /// The project containing the current directory (nearest mcmod.toml)
+    Project,
+}
+
+impl PathKind {
+    fn name(&self) -> &'static str {
+        match self {
+            PathKind::Config => "config",
+            PathKind::ConfigDir => "config-dir",
+            PathKind::Cache => "cache",
+            PathKind::Templates => "templates",
+            PathKind::Project => "project",
+        }
+    }
+
+    fn resolve(&self) -> Result<PathBuf> {
+        match self {
+            PathKind::Config => global_config::global_config_file(),
+            PathKind::ConfigDir => global_config::global_config_dir(),
+            PathKind::Cache => global_config::cache_dir(),
+            PathKind::Templates => global_config::templates_dir(),
+            PathKind::Project => crate::config::find_project_root(&std::env::current_dir()?),
+        }
+    }
+}
+
+/// Set preferences from `key value`, one or more `key=value` arguments, or
+/// `-` to read `key=value` lines from stdin. Nothing is saved unless every
+/// assignment is valid.
+pub fn run_set(args: &[String]) -> Result<()> {
+    let assignments = match args {
+        [key, value] if !key.contains('=') => vec![(key.clone(), value.clone())],
+        [dash] if dash == "-" => {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            global_config::parse_assignments(content.lines())?
+        }
+        _ => global_config::parse_assignments(args.iter().map(String::as_str))?,
+    };
+    if assignments.is_empty() {
+        return Err(McmodError::Other("Nothing to set: pass key=value pairs or - for stdin".to_string()));
+    }
+
+    let config = GlobalConfig::load()?.apply_all(&assignments)?;
+    config.save()?;
+    for (key, value) in &assignments {
+        println!("{}", format!("  Set {key} = {value}").green());
+    }
     Ok(())
 }
 
@@ -21,6 +76,213 @@ pub fn run_get(key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Clear a preference back to not set.
+pub fn run_unset(key: &str) -> Result<()> {
+    let mut config = GlobalConfig::load()?;
+    config.unset(key)?;
+    config.save()?;
+    println!("{}", format!("  Unset {key}").green());
+    Ok(())
+}
+
+/// Restore the defaults of one section, or of the whole file, then list the
+/// keys that changed.
+pub fn run_reset(section: Option<ConfigSection>) -> Result<()> {
+    let config = GlobalConfig::load()?;
+    let mut reset = config.clone();
+    reset.reset(section);
+    reset.save()?;
+
+    let changed = print_changes(&config, &reset);
+    let scope = match section {
+        Some(section) => {
+            let name = section.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+            format!("[{name}]")
+        }
+        None => "all preferences".to_string(),
+    };
+    if changed == 0 {
+        println!("{}", format!("  {scope} already at defaults").green());
+    } else {
+        println!("{}", format!("  Reset {scope}, {changed} changed").green());
+    }
+    Ok(())
+}
+
+/// `mcmod config edit`: open a copy of config.toml in `$VISUAL` or `$EDITOR`,
+/// and install it once it parses with every key valid. Invalid edits are
+/// reported and can be edited again; config.toml is only replaced by a valid
+/// file.
+pub fn run_edit() -> Result<()> {
+    let editor = editor_command(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok());
+    let path = global_config::global_config_file()?;
+    let Some((program, args)) = editor.split_first() else {
+        return Err(McmodError::Other(format!(
+            "Set $VISUAL or $EDITOR to your editor, or edit {} directly",
+            path.display()
+        )));
+    };
+
+    let original = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        GlobalConfig::default().export()?
+    };
+    let draft = path.with_extension("edit.toml");
+    crate::util::write_file(&draft, &original)?;
+
+    loop {
+        let status = std::process::Command::new(program)
+            .args(args)
+            .arg(&draft)
+            .status()
+            .map_err(|e| McmodError::Other(format!("Could not start editor '{program}': {e}")))?;
+        if !status.success() {
+            return Err(McmodError::Other(format!(
+                "Editor exited with {status}; config.toml was not changed (your edits are in {})",
+                draft.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(&draft)?;
+        match GlobalConfig::parse_strict(&content) {
+            Ok(_) => {
+                std::fs::remove_file(&draft)?;
+                if content == original {
+                    println!("{}", "  No changes".dimmed());
+                } else {
+                    crate::util::write_file(&path, &content)?;
+                    println!("{}", format!("  Saved {}", path.display()).green());
+                }
+                return Ok(());
+            }
+            Err(e) => {
+                println!("{}", format!("  {e}").red());
+                let again = std::io::stdin().is_terminal()
+                    && dialoguer::Confirm::new()
+                        .with_prompt("  Edit again?")
+                        .default(true)
+                        .interact()
+                        .map_err(|e| McmodError::Other(e.to_string()))?;
+                if !again {
+                    return Err(McmodError::Other(format!(
+                        "config.toml was not changed; your edits are in {}",
+                        draft.display()
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// The editor to run and its arguments, from `$VISUAL` then `$EDITOR`
+/// (e.g. `code --wait`); empty when neither is set.
+fn editor_command(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(|command| command.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .find(|parts| !parts.is_empty())
+        .unwrap_or_default()
+}
+
+/// `mcmod config profile list`: every profile, the active one starred.
+pub fn run_profile_list() -> Result<()> {
+    let active = global_config::active_profile()?;
+    for name in global_config::profiles()? {
+        let path = global_config::profile_file(&name)?;
+        if name == active {
+            println!("  {} {:<16} {}", "*".green(), name.bold(), path.display().to_string().dimmed());
+        } else {
+            println!("    {:<16} {}", name, path.display().to_string().dimmed());
+        }
+    }
+    Ok(())
+}
+
+/// `mcmod config profile create`: a new profile with the default
+/// preferences, or a copy of the profile `from`.
+pub fn run_profile_create(name: &str, from: Option<&str>) -> Result<()> {
+    let path = global_config::create_profile(name, from)?;
+    println!("{}", format!("  Created config profile '{name}' ({})", path.display()).green());
+    println!(
+        "  {}",
+        format!("Use it with `mcmod config profile switch {name}`, or for one run with --config-profile {name}").dimmed()
+    );
+    Ok(())
+}
+
+/// `mcmod config profile switch`: use `name` whenever no profile is selected
+/// for the run.
+pub fn run_profile_switch(name: &str) -> Result<()> {
+    global_config::switch_profile(name)?;
+    println!("{}", format!("  Switched to config profile '{name}'").green());
+    if let Ok(selected) = std::env::var(global_config::PROFILE_ENV) {
+        if !selected.is_empty() && selected != name {
+            crate::progress::warning(format!(
+                "{} is set to '{selected}', which still takes precedence in this shell",
+                global_config::PROFILE_ENV
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Print the preferences as TOML, e.g. `mcmod config export > prefs.toml`.
+pub fn run_export() -> Result<()> {
+    let config = GlobalConfig::load()?;
+    print!("{}", config.export()?);
+    Ok(())
+}
+
+/// Import preferences from a file exported by `config export` (`-` reads
+/// stdin), then list the keys that changed.
+pub fn run_import(file: &Path, mode: ImportMode) -> Result<()> {
+    let content = if file == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(file)?
+    };
+
+    let config = GlobalConfig::load()?;
+    let imported = config.import(&content, mode)?;
+    imported.save()?;
+
+    let changed = print_changes(&config, &imported);
+    if changed == 0 {
+        println!("{}", "  Preferences already up to date".green());
+        return Ok(());
+    }
+    println!(
+        "{}",
+        format!("  Imported preferences, {changed} changed").green()
+    );
+    Ok(())
+}
+
+/// Print `key: old -> new` for each preference that differs, and return how
+/// many did.
+fn print_changes(old: &GlobalConfig, new: &GlobalConfig) -> usize {
+    let (old, new) = (old.list(), new.list());
+    let mut changed = 0;
+    for (_, key, value) in &new {
+        let before = old.iter().find(|(_, k, _)| k == key).map_or("(not set)", |(_, _, v)| v.as_str());
+        if before != value {
+            println!("  {:<22} {} -> {}", format!("{key}:"), before.dimmed(), value);
+            changed += 1;
+        }
+    }
+    for (_, key, value) in &old {
+        if !new.iter().any(|(_, k, _)| k == key) {
+            println!("  {:<22} {} -> (not set)", format!("{key}:"), value.dimmed());
+            changed += 1;
+        }
+    }
+    changed
+}
+
 pub fn run_list() -> Result<()> {
     let config = GlobalConfig::load()?;
     let dir = global_config::global_config_dir()?;
@@ -45,3 +307,64 @@ pub fn run_list() -> Result<()> {
     println!();
     Ok(())
 }
+
+/// `mcmod config path`: print one location as a bare path, or all of them as
+/// `name<TAB>path` lines (leaving out a project when there is none). `json`
+/// prints an object instead, with null for an unresolved location.
+pub fn run_path(kind: Option<PathKind>, json: bool) -> Result<()> {
+    let paths = match kind {
+        Some(kind) => vec![(kind, Some(kind.resolve()?))],
+        None => PathKind::value_variants().iter().map(|kind| (*kind, kind.resolve().ok())).collect(),
+    };
+    print!("{}", render_paths(&paths, json, kind.is_some()));
+    Ok(())
+}
+
+fn render_paths(paths: &[(PathKind, Option<PathBuf>)], json: bool, single: bool) -> String {
+    if json {
+        let object: serde_json::Map<String, serde_json::Value> = paths
+            .iter()
+            .map(|(kind, path)| (kind.name().to_string(), serde_json::json!(path)))
+            .collect();
+        return format!("{}\n", serde_json::to_string_pretty(&object).unwrap_or_default());
+    }
+    paths
+        .iter()
+        .filter_map(|(kind, path)| {
+            let path = path.as_ref()?.display();
+            Some(if single { format!("{path}\n") } else { format!("{}\t{path}\n", kind.name()) })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_paths() {
+        let paths = [
+            (PathKind::Config, Some(PathBuf::from("/home/a/.config/mcmod/config.toml"))),
+            (PathKind::Cache, Some(PathBuf::from("/home/a/.cache/mcmod"))),
+            (PathKind::Project, None),
+        ];
+        assert_eq!(
+            render_paths(&paths, false, false),
+            "config\t/home/a/.config/mcmod/config.toml\ncache\t/home/a/.cache/mcmod\n"
+        );
+        assert_eq!(render_paths(&paths[1..2], false, true), "/home/a/.cache/mcmod\n");
+
+        let json: serde_json::Value = serde_json::from_str(&render_paths(&paths, true, false)).unwrap();
+        assert_eq!(json["cache"], "/home/a/.cache/mcmod");
+        assert!(json["project"].is_null());
+    }
+
+    #[test]
+    fn test_editor_command() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(editor_command(some("code --wait"), some("vim")), ["code", "--wait"]);
+        assert_eq!(editor_command(None, some("vim")), ["vim"]);
+        assert_eq!(editor_command(some("  "), some("nano")), ["nano"]);
+        assert!(editor_command(None, None).is_empty());
+    }
+}
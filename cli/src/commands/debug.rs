@@ -0,0 +1,296 @@
+use crate::config::{McmodConfig, Publishing, Versions};
+use crate::error::{McmodError, Result};
+use crate::fs::FileSystem;
+use crate::global_config::GlobalConfig;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+const LANGUAGES: &[&str] = &["java", "kotlin"];
+const LOADERS: &[&str] = &["fabric", "neoforge", "forge", "quilt", "sponge"];
+const FEATURES: &[&str] = &["ci", "server", "publishing", "testing", "build-info", "architectury"];
+
+/// Every combination renders the same mod identity and Minecraft target, so
+/// a manifest change always means a template or generator change.
+const MINECRAFT: &str = "1.21.1";
+
+/// Where generated projects live inside the in-memory tree.
+const ROOT: &str = "/render-all/testmod";
+
+/// One language/loader/feature combination `init` can produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combo {
+    pub language: &'static str,
+    pub loaders: Vec<&'static str>,
+    pub features: Vec<&'static str>,
+}
+
+impl Combo {
+    /// e.g. `kotlin/fabric+quilt/ci+testing`; `none` when no feature is on.
+    pub fn name(&self) -> String {
+        let features = if self.features.is_empty() {
+            "none".to_string()
+        } else {
+            self.features.join("+")
+        };
+        format!("{}/{}/{features}", self.language, self.loaders.join("+"))
+    }
+
+    /// Whether every comma-separated term names this combo's language, one of
+    /// its loaders, or one of its features.
+    fn matches(&self, filter: &str) -> bool {
+        filter
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .all(|term| {
+                self.language == term || self.loaders.contains(&term) || self.features.contains(&term)
+            })
+    }
+
+    fn has(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+
+    fn config(&self) -> Result<McmodConfig> {
+        let loaders: Vec<String> = self.loaders.iter().map(|l| l.to_string()).collect();
+        let mut targets = crate::commands::init::version_targets(&[MINECRAFT.to_string()], &loaders)?;
+        if self.has("architectury") {
+            crate::commands::add::pin_architectury_api(&mut targets)?;
+        }
+        let publishing = self.has("publishing").then(|| Publishing {
+            modrinth_id: "testmod".to_string(),
+            curseforge_id: None,
+        });
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "A test mod".to_string(),
+            self.language.to_string(),
+            self.loaders.contains(&"fabric"),
+            self.loaders.contains(&"neoforge"),
+            self.has("ci"),
+            self.has("testing"),
+            publishing,
+            Versions {
+                targets,
+                architectury_plugin: None,
+                architectury_loom: None,
+            },
+        );
+        config.loaders.forge = self.loaders.contains(&"forge");
+        config.loaders.quilt = self.loaders.contains(&"quilt");
+        config.loaders.sponge = self.loaders.contains(&"sponge");
+        config.features.build_info = self.has("build-info");
+        config.features.architectury = self.has("architectury");
+        Ok(config)
+    }
+}
+
+/// All combinations: each language, every set of loaders with at least one
+/// mod loader (Sponge is a plugin module beside the mod), and every set of
+/// features. Architectury API has no Forge builds, so `init` refuses that pair.
+pub fn combos() -> Vec<Combo> {
+    let mut combos = Vec::new();
+    for &language in LANGUAGES {
+        for loader_mask in 1..(1u32 << LOADERS.len()) {
+            let loaders = pick(LOADERS, loader_mask);
+            if loaders == ["sponge"] {
+                continue;
+            }
+            for feature_mask in 0..(1u32 << FEATURES.len()) {
+                let features = pick(FEATURES, feature_mask);
+                if loaders.contains(&"forge") && features.contains(&"architectury") {
+                    continue;
+                }
+                combos.push(Combo {
+                    language,
+                    loaders: loaders.clone(),
+                    features,
+                });
+            }
+        }
+    }
+    combos
+}
+
+fn pick(items: &[&'static str], mask: u32) -> Vec<&'static str> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, item)| *item)
+        .collect()
+}
+
+/// A generated file: its path in the project, content hash, and Unix mode if
+/// one was set.
+pub struct Entry {
+    pub path: String,
+    pub hash: String,
+    pub mode: Option<u32>,
+    pub content: Vec<u8>,
+}
+
+impl Entry {
+    fn manifest_line(&self) -> String {
+        match self.mode {
+            Some(mode) => format!("{}  {} ({mode:o})", self.hash, self.path),
+            None => format!("{}  {}", self.hash, self.path),
+        }
+    }
+}
+
+/// Render a combination the way `init` writes it, entirely in memory, and
+/// return its files in path order.
+pub fn render(combo: &Combo) -> Result<Vec<Entry>> {
+    let root = Path::new(ROOT);
+    let config = combo.config()?;
+    let (result, fs) = crate::fs::in_memory(|| {
+        crate::commands::init::write_mod_project(root, &config, &GlobalConfig::default(), combo.has("server"))
+    });
+    result?;
+
+    fs.tree(root)
+        .into_iter()
+        .map(|path| {
+            let full = root.join(&path);
+            let content = fs.read(&full)?;
+            Ok(Entry {
+                hash: short_hash(&content),
+                mode: fs.mode(&full),
+                path,
+                content,
+            })
+        })
+        .collect()
+}
+
+fn short_hash(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    digest.iter().take(8).map(|b| format!("{b:02x}")).collect()
+}
+
+/// One line per combination: its name, a hash over its whole manifest, and
+/// its file count. This is what the snapshot test checks in.
+fn summary_line(combo: &Combo, entries: &[Entry]) -> String {
+    let manifest: String = entries.iter().map(|e| e.manifest_line() + "\n").collect();
+    format!(
+        "{}  {}  {} files",
+        combo.name(),
+        short_hash(manifest.as_bytes()),
+        entries.len()
+    )
+}
+
+/// `mcmod debug render-all`: print the manifest of every combination (or the
+/// ones matching `only`), and optionally write the rendered trees to `out`.
+pub fn run_render_all(only: Option<&str>, summary_only: bool, out: Option<&Path>) -> Result<()> {
+    let combos: Vec<Combo> = combos()
+        .into_iter()
+        .filter(|c| only.is_none_or(|f| c.matches(f)))
+        .collect();
+    if combos.is_empty() {
+        return Err(McmodError::Other(format!(
+            "No combination matches '{}'. Terms: {}, {}, {}",
+            only.unwrap_or_default(),
+            LANGUAGES.join(", "),
+            LOADERS.join(", "),
+            FEATURES.join(", ")
+        )));
+    }
+
+    crate::util::set_quiet(true);
+    for combo in &combos {
+        let entries = render(combo)?;
+        println!("{}", summary_line(combo, &entries));
+        if !summary_only {
+            for entry in &entries {
+                println!("  {}", entry.manifest_line());
+            }
+        }
+        if let Some(out) = out {
+            let dir = out.join(combo.name().replace('/', "-"));
+            for entry in &entries {
+                crate::fs::write(&dir.join(&entry.path), &entry.content, entry.mode)?;
+            }
+        }
+    }
+    crate::util::set_quiet(false);
+
+    eprintln!("  {} combination(s) rendered", combos.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = include_str!("../../../test/snapshots/render-all.txt");
+
+    /// Summary lines for every combination, as stored in the snapshot file.
+    fn summary() -> Result<String> {
+        let mut out = String::new();
+        for combo in combos() {
+            let entries = render(&combo)?;
+            out.push_str(&summary_line(&combo, &entries));
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    #[test]
+    fn test_combos_cover_matrix() {
+        let combos = combos();
+        // 16 loader sets include Forge and skip Architectury
+        assert_eq!(combos.len(), 2 * (16 * 32 + 14 * 64));
+        assert_eq!(combos[0].name(), "java/fabric/none");
+        assert!(combos.iter().any(|c| c.name() == "kotlin/fabric+neoforge+forge+quilt+sponge/ci+server+publishing+testing+build-info"));
+        assert!(combos.iter().any(|c| c.name() == "java/fabric+neoforge+quilt/architectury"));
+        assert!(!combos.iter().any(|c| c.loaders.contains(&"forge") && c.has("architectury")));
+        assert!(combos[1].matches("java, fabric,ci"));
+        assert!(!combos[1].matches("kotlin"));
+    }
+
+    /// Renders every combination and compares against the checked-in
+    /// manifest. After an intended template change, regenerate it with
+    /// `MCMOD_UPDATE_SNAPSHOTS=1 cargo test render_all` and review the diff;
+    /// `mcmod debug render-all --only <terms>` shows the per-file hashes.
+    #[test]
+    fn test_render_all_snapshot() {
+        let actual = summary().unwrap();
+        if std::env::var_os("MCMOD_UPDATE_SNAPSHOTS").is_some() {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/snapshots/render-all.txt");
+            std::fs::write(path, &actual).unwrap();
+            return;
+        }
+        let changed: Vec<&str> = actual
+            .lines()
+            .zip(SNAPSHOT.lines())
+            .filter(|(a, e)| a != e)
+            .map(|(a, _)| a.split_whitespace().next().unwrap_or_default())
+            .collect();
+        assert!(
+            changed.is_empty() && actual.lines().count() == SNAPSHOT.lines().count(),
+            "generated trees changed for {} combination(s), first: {:?}",
+            changed.len(),
+            changed.first()
+        );
+    }
+
+    #[test]
+    fn test_render_server_and_modes() {
+        let combo = Combo {
+            language: "java",
+            loaders: vec!["fabric"],
+            features: vec!["server"],
+        };
+        let entries = render(&combo).unwrap();
+        assert!(entries.iter().any(|e| e.path == "run/eula.txt"));
+        assert!(!entries.iter().any(|e| e.path.starts_with(".github/")));
+        let gradlew = entries.iter().find(|e| e.path == "gradlew").unwrap();
+        assert_eq!(gradlew.mode, Some(0o755));
+        assert!(gradlew.manifest_line().ends_with("gradlew (755)"));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::config::{LinkKind, McmodConfig, ProjectLink};
+use crate::diff;
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// `mcmod dep link <sibling>`: make the project in `dir` depend on the sibling
+/// project through a Gradle composite build, and record the link in both
+/// mcmod.toml files.
+pub fn run_link(dir: &Path, sibling: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod dep link\n".bold().cyan());
+
+    let dir = dir.canonicalize()?;
+    let sibling_dir = resolve_sibling(&dir, sibling)?;
+    if sibling_dir == dir {
+        return Err(McmodError::Other("A project can't be linked to itself".to_string()));
+    }
+
+    let mut config = McmodConfig::load(&dir)?;
+    let mut library = McmodConfig::load(&sibling_dir)?;
+    for (project, path) in [(&config, &dir), (&library, &sibling_dir)] {
+        if !project.uses_stonecutter() {
+            return Err(McmodError::Other(format!(
+                "{} is a {} project; only mod and library projects can be linked",
+                path.display(),
+                project.mod_info.project_type.as_str()
+            )));
+        }
+    }
+
+    let lib_id = library.mod_info.mod_id.clone();
+    if config.links.iter().any(|l| l.mod_id == lib_id) {
+        return Err(McmodError::AlreadyEnabled(format!("link to {lib_id}")));
+    }
+    if !library.is_library() {
+        println!(
+            "{}",
+            format!("  Note: {lib_id} is a regular mod, not a library (`init --type library`).").yellow()
+        );
+    }
+
+    let projects = stonecutter_projects(&library);
+    for missing in stonecutter_projects(&config)
+        .iter()
+        .filter(|p| !projects.contains(p))
+    {
+        crate::progress::warning(format!("{lib_id} doesn't build {missing}; that version won't resolve the dependency."));
+    }
+
+    let rel_path = crate::util::relative_path(&dir, &sibling_dir);
+    let group = &library.mod_info.package;
+
+    let settings_path = dir.join("settings.gradle.kts");
+    let settings = std::fs::read_to_string(&settings_path)?;
+    let updated = gradle::add_include_build_content(&settings, &rel_path, group, &lib_id, &projects);
+    diff::apply_edit(&settings_path, &settings, &updated)?;
+
+    let build_path = dir.join("build.gradle.kts");
+    let build = std::fs::read_to_string(&build_path)?;
+    let updated = gradle::add_linked_dependency_content(&build, group, &lib_id);
+    diff::apply_edit(&build_path, &build, &updated)?;
+
+    config.links.push(ProjectLink {
+        mod_id: lib_id.clone(),
+        path: rel_path.clone(),
+        kind: LinkKind::Dependency,
+    });
+    config.save(&dir)?;
+
+    let mod_id = config.mod_info.mod_id.clone();
+    library.links.retain(|l| l.mod_id != mod_id);
+    library.links.push(ProjectLink {
+        mod_id: mod_id.clone(),
+        path: crate::util::relative_path(&sibling_dir, &dir),
+        kind: LinkKind::Dependent,
+    });
+    library.save(&sibling_dir)?;
+
+    println!("{}", format!("  Linked {mod_id} -> {lib_id} ({rel_path})").bold().green());
+    println!();
+    println!("  Declare the runtime dependency in your metadata as well:");
+    if config.loaders.fabric {
+        println!("    fabric.mod.json       \"depends\": {{ \"{lib_id}\": \"*\" }}");
+    }
+    if config.loaders.neoforge {
+        println!("    neoforge.mods.toml    [[dependencies.${{id}}]] modId = \"{lib_id}\"");
+    }
+    if config.loaders.forge {
+        println!("    mods.toml             [[dependencies.${{id}}]] modId = \"{lib_id}\"");
+    }
+    if config.loaders.quilt {
+        println!("    quilt.mod.json        \"depends\": [{{ \"id\": \"{lib_id}\" }}]");
+    }
+    Ok(())
+}
+
+/// Find the sibling project: a path relative to the current directory, or
+/// else relative to the project's parent directory (`mcmod dep link mylib`).
+fn resolve_sibling(dir: &Path, sibling: &Path) -> Result<PathBuf> {
+    let candidates = [
+        sibling.to_path_buf(),
+        dir.parent().map(|p| p.join(sibling)).unwrap_or_default(),
+    ];
+    candidates
+        .iter()
+        .find(|c| McmodConfig::config_path(c).is_file())
+        .map(|c| c.canonicalize())
+        .transpose()?
+        .ok_or_else(|| {
+            McmodError::Other(format!(
+                "No mcmod project found at '{}' (looked for mcmod.toml)",
+                sibling.display()
+            ))
+        })
+}
+
+/// Stonecutter project names (`<minecraft>-<loader>`) a project builds.
+fn stonecutter_projects(config: &McmodConfig) -> Vec<String> {
+    let loaders = config.enabled_platforms();
+    config
+        .versions
+        .targets
+        .iter()
+        .flat_map(|t| loaders.iter().map(move |l| format!("{}-{l}", t.minecraft)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProjectType, Versions};
+
+    fn write_project(dir: &Path, mod_id: &str, project_type: ProjectType) {
+        std::fs::create_dir_all(dir).unwrap();
+        let mut config = McmodConfig::new(
+            mod_id.to_string(),
+            mod_id.to_string(),
+            format!("com.example.{mod_id}"),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Versions::default(),
+        );
+        config.mod_info.project_type = project_type;
+        config.save(dir).unwrap();
+        std::fs::write(dir.join("settings.gradle.kts"), "rootProject.name = \"x\"\n").unwrap();
+        std::fs::write(dir.join("build.gradle.kts"), "plugins {\n}\n").unwrap();
+    }
+
+    #[test]
+    fn test_run_link() {
+        let root = std::env::temp_dir().join(format!("mcmod_dep_link_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root.join("content"), "content", ProjectType::Mod);
+        write_project(&root.join("lib"), "lib", ProjectType::Library);
+
+        run_link(&root.join("content"), Path::new("lib")).unwrap();
+
+        let settings = std::fs::read_to_string(root.join("content/settings.gradle.kts")).unwrap();
+        assert!(settings.contains("includeBuild(\"../lib\")"));
+        assert!(settings.contains("listOf(\"1.21.4-fabric\")"));
+        let build = std::fs::read_to_string(root.join("content/build.gradle.kts")).unwrap();
+        assert!(build.contains("com.example.lib:lib-${stonecutter.current.project}"));
+
+        let content = McmodConfig::load(&root.join("content")).unwrap();
+        assert_eq!(content.links[0].mod_id, "lib");
+        assert_eq!(content.links[0].path, "../lib");
+        assert_eq!(content.links[0].kind, LinkKind::Dependency);
+        let lib = McmodConfig::load(&root.join("lib")).unwrap();
+        assert_eq!(lib.links[0].mod_id, "content");
+        assert_eq!(lib.links[0].kind, LinkKind::Dependent);
+
+        assert!(run_link(&root.join("content"), Path::new("lib")).is_err(), "already linked");
+        assert!(run_link(&root.join("content"), Path::new("content")).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,663 @@
+use crate::commands::doctor::LOADER_METADATA;
+use crate::commands::{compat, init};
+use crate::config::{CompatEntry, CompatStatus, McmodConfig, RunDirs};
+use crate::error::{Context, McmodError, Result};
+use crate::global_config::GlobalConfig;
+use crate::jar::Jar;
+use crate::template;
+use crate::version_meta::compare_versions;
+use colored::Colorize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A mod packaged in a jar, from its loader metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JarMod {
+    pub id: String,
+    pub name: Option<String>,
+    pub version: String,
+    pub loader: &'static str,
+    /// Required dependencies, `minecraft` included.
+    pub depends: Vec<Dependency>,
+}
+
+/// A required dependency and the versions it accepts, as the metadata wrote
+/// them (Fabric predicates, alternatives joined with `||`, or a Maven range).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub id: String,
+    pub versions: Option<String>,
+}
+
+/// Ids every dev run provides on each loader, besides this mod and the jars in
+/// its mods folder.
+const PROVIDED: &[(&str, &[&str])] = &[
+    ("fabric", &["minecraft", "java", "fabricloader", "fabric-api", "fabric"]),
+    ("quilt", &["minecraft", "java", "fabricloader", "fabric-api", "fabric", "quilt_loader", "quilted_fabric_api"]),
+    ("neoforge", &["minecraft", "neoforge"]),
+    ("forge", &["minecraft", "forge"]),
+];
+
+/// Where a loader's dev client and server load extra mods from.
+pub fn mods_dir(config: &McmodConfig, loader: &str) -> String {
+    format!("{}/mods", config.run_dir(loader))
+}
+
+/// `mcmod dev run-dirs <layout>`: switch between one shared `run/` and
+/// `run/<loader>/` per loader. Updates `[features] run_dirs`, adds or removes
+/// the Stonecraft `runDirectory` block in build.gradle.kts, and fills the new
+/// directories with the dev defaults. Existing run directories are left alone.
+pub fn run_run_dirs(dir: &Path, layout: RunDirs) -> Result<()> {
+    println!("{}", "\n  mcmod dev run-dirs\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "Run directories belong to mod and library projects (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    if config.features.run_dirs == layout {
+        println!("{}", "  Run directories already use that layout".green());
+        return Ok(());
+    }
+    let previous = config.run_dirs();
+
+    let path = dir.join("build.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    let fragment = template::render(template::SC_RUN_DIRS_GRADLE, &template::build_common_vars(&config))?;
+    let updated = match layout {
+        RunDirs::PerLoader => {
+            let mut updated = content.clone();
+            if !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            updated.push_str(&fragment);
+            updated
+        }
+        RunDirs::Shared => {
+            if !content.contains(&fragment) {
+                return Err(McmodError::Other(
+                    "build.gradle.kts no longer has the per-loader runDirectory block mcmod added; remove it by hand, then rerun"
+                        .to_string(),
+                ));
+            }
+            content.replacen(&fragment, "", 1)
+        }
+    };
+    crate::diff::apply_edit(&path, &content, &updated)?;
+    crate::util::progress("  Updated runDirectory in build.gradle.kts".green());
+
+    config.features.run_dirs = layout;
+    let fresh: Vec<String> = config
+        .run_dirs()
+        .into_iter()
+        .filter(|run| !crate::fs::exists(&dir.join(run).join("options.txt")))
+        .collect();
+    // The EULA was accepted at init if the old run directories have server files
+    let server = previous.iter().any(|run| crate::fs::exists(&dir.join(run).join("eula.txt")));
+    init::write_dev_defaults(dir, &config, &GlobalConfig::load()?, &fresh, server)?;
+    config.save(dir)?;
+
+    println!("{}", format!("  Dev runs now use {}", config.run_dirs().join(", ")).bold().green());
+    println!(
+        "  {}",
+        format!("Worlds and mods in {} weren't moved; copy over what you still need", previous.join(", ")).dimmed()
+    );
+    Ok(())
+}
+
+/// `mcmod dev mods add <jar>`: copy a mod jar into its loader's run mods
+/// folder for dev runs and record it in `[compat]` (status untested) so
+/// COMPATIBILITY.md lists it.
+pub fn run_mods_add(dir: &Path, jar_path: &Path) -> Result<()> {
+    let mut config = McmodConfig::load(dir)?;
+    let bytes = crate::fs::read(jar_path).with_context(|| format!("Reading {}", jar_path.display()))?;
+    let jar = Jar::from_bytes(bytes.clone()).with_context(|| format!("Opening {}", jar_path.display()))?;
+    let Some(found) = read_mod(&jar)? else {
+        return Err(McmodError::Other(format!(
+            "{} has no fabric.mod.json, quilt.mod.json or mods.toml; is it a mod jar?",
+            jar_path.display()
+        )));
+    };
+    if found.id == config.mod_info.mod_id {
+        return Err(McmodError::Other(format!(
+            "{} is a build of this mod; dev runs already load it",
+            jar_path.display()
+        )));
+    }
+    if !config.enabled_platforms().contains(&found.loader) {
+        crate::progress::warning(format!("{} is a {} mod, which this project doesn't build for", found.id, found.loader));
+    }
+
+    let file_name = jar_path
+        .file_name()
+        .ok_or_else(|| McmodError::Other(format!("{} is not a file", jar_path.display())))?;
+    let mods_dir = mods_dir(&config, found.loader);
+    crate::util::write_binary(&dir.join(&mods_dir).join(file_name), &bytes)?;
+    crate::util::progress(format!("  Copied {} to {mods_dir}/", file_name.to_string_lossy()).green());
+
+    record(&mut config, found);
+    config.save(dir)?;
+    compat::update(dir, &config)?;
+    println!(
+        "  {}",
+        format!("Recorded in [compat] and {}; mark the result with `mcmod compat add`", compat::COMPAT_FILE).dimmed()
+    );
+    Ok(())
+}
+
+/// `mcmod dev mods check`: read every jar in the run mods folders and report,
+/// per loader, duplicate mod ids, mods built for another Minecraft version,
+/// and required dependencies nothing provides, before the loader fails to
+/// boot with a less readable error.
+pub fn run_mods_check(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod dev mods check\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    let minecraft = config.versions.targets.first().map(|t| t.minecraft.clone()).unwrap_or_default();
+    let mut problems = 0;
+    for loader in config.enabled_platforms() {
+        let mods_dir = mods_dir(&config, loader);
+        let mut jars = Vec::new();
+        let Ok(entries) = std::fs::read_dir(dir.join(&mods_dir)) else {
+            println!("  {} {loader:<9} {}", "-".dimmed(), format!("no {mods_dir}/").dimmed());
+            continue;
+        };
+        let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "jar")).collect();
+        paths.sort();
+        for path in paths {
+            let file = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            let parsed = crate::fs::read(&path)
+                .map_err(McmodError::from)
+                .and_then(Jar::from_bytes)
+                .and_then(|jar| read_mod(&jar));
+            match parsed {
+                Ok(Some(found)) => jars.push((file, found)),
+                Ok(None) => println!("  {}", format!("{mods_dir}/{file}: no mod metadata, skipped").yellow()),
+                Err(e) => println!("  {}", format!("{mods_dir}/{file}: {e}").yellow()),
+            }
+        }
+        let found = check_mods(&config, loader, &minecraft, &jars);
+        if found.is_empty() {
+            println!("  {} {loader:<9} {} mod(s) in {mods_dir}/", "✓".green(), loaded(loader, &jars).count());
+            continue;
+        }
+        println!("  {} {loader:<9} {}", "✗".red(), format!("{mods_dir}/").dimmed());
+        for problem in &found {
+            println!("      {}", problem.red());
+        }
+        problems += found.len();
+    }
+    println!();
+    if problems > 0 {
+        return Err(McmodError::Other(format!("{problems} problem(s) with the dev run mods")));
+    }
+    Ok(())
+}
+
+/// The jars `loader` loads: its own, plus Fabric mods on Quilt.
+fn loaded<'a>(loader: &'a str, jars: &'a [(String, JarMod)]) -> impl Iterator<Item = &'a (String, JarMod)> {
+    jars.iter().filter(move |(_, m)| m.loader == loader || (loader == "quilt" && m.loader == "fabric"))
+}
+
+/// Problems with the mods `loader` would load from `jars` on `minecraft`.
+fn check_mods(config: &McmodConfig, loader: &str, minecraft: &str, jars: &[(String, JarMod)]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut by_id: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (file, found) in loaded(loader, jars) {
+        by_id.entry(found.id.as_str()).or_default().push(file);
+    }
+    for (id, files) in &by_id {
+        if *id == config.mod_info.mod_id {
+            problems.push(format!("{} is a build of this mod, which dev runs already load", files.join(", ")));
+        } else if files.len() > 1 {
+            problems.push(format!("Duplicate mod id {id}: {}", files.join(", ")));
+        }
+    }
+
+    let provided: BTreeSet<&str> = PROVIDED
+        .iter()
+        .find(|(l, _)| *l == loader)
+        .map(|(_, ids)| ids.to_vec())
+        .unwrap_or_default()
+        .into_iter()
+        .chain([config.mod_info.mod_id.as_str()])
+        .chain(config.features.architectury.then_some("architectury"))
+        .chain(by_id.keys().copied())
+        .collect();
+    // Fabric API modules (fabric-networking-api-v1, ...) come with fabric-api
+    let is_provided = |id: &str| {
+        provided.contains(id) || (matches!(loader, "fabric" | "quilt") && id.starts_with("fabric-"))
+    };
+
+    let mut missing: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (file, found) in loaded(loader, jars) {
+        for dep in &found.depends {
+            if dep.id == "minecraft" {
+                let Some(range) = &dep.versions else { continue };
+                if accepts(range, minecraft) == Some(false) {
+                    problems.push(format!("{file} ({}) needs Minecraft {range}, but dev runs use {minecraft}", found.id));
+                }
+            } else if !is_provided(&dep.id) {
+                missing.entry(dep.id.as_str()).or_default().push(found.id.as_str());
+            }
+        }
+    }
+    // Name each missing id with the chain of mods that end up needing it
+    for (id, needed_by) in &missing {
+        let mut chains = Vec::new();
+        for mod_id in needed_by {
+            chains.push(dependents_chain(loader, jars, mod_id));
+        }
+        problems.push(format!("Missing dependency {id}, required by {}", chains.join("; ")));
+    }
+    problems
+}
+
+/// `a ← b ← c`: `mod_id` and the mods that require it, transitively.
+fn dependents_chain(loader: &str, jars: &[(String, JarMod)], mod_id: &str) -> String {
+    let mut chain = vec![mod_id.to_string()];
+    let mut current = mod_id.to_string();
+    while let Some((_, dependent)) =
+        loaded(loader, jars).find(|(_, m)| !chain.contains(&m.id) && m.depends.iter().any(|d| d.id == current))
+    {
+        chain.push(dependent.id.clone());
+        current = dependent.id.clone();
+    }
+    chain.join(" ← ")
+}
+
+/// Add or refresh the `[compat]` entry for a dev mod. A new version of a known
+/// mod starts over as untested; the same version keeps its recorded result.
+fn record(config: &mut McmodConfig, found: JarMod) {
+    let minecraft = config.versions.targets.first().map(|t| t.minecraft.clone());
+    if let Some(existing) = config.compat.get(&found.id) {
+        if existing.version == found.version {
+            return;
+        }
+    }
+    config.compat.insert(
+        found.id,
+        CompatEntry {
+            name: found.name,
+            version: found.version,
+            minecraft,
+            loader: Some(found.loader.to_string()),
+            status: CompatStatus::Untested,
+            notes: None,
+        },
+    );
+}
+
+/// The mod a jar declares, from the first loader metadata file it contains.
+pub fn read_mod(jar: &Jar) -> Result<Option<JarMod>> {
+    for (loader, file) in LOADER_METADATA {
+        let Some(content) = jar.read_to_string(file)? else { continue };
+        let parsed = if file.ends_with(".toml") {
+            toml_mod(&content, || manifest_version(jar))
+        } else {
+            json_mod(file, &content)
+        };
+        return Ok(parsed.map(|(id, name, version)| {
+            let depends = if file.ends_with(".toml") { toml_depends(&content, &id) } else { json_depends(file, &content) };
+            JarMod { id, name, version, loader, depends }
+        }));
+    }
+    Ok(None)
+}
+
+/// The id a jar's `loader` metadata declares, for jars that carry metadata
+/// for several loaders.
+pub fn mod_id_for(jar: &Jar, loader: &str) -> Result<Option<String>> {
+    let Some((_, file)) = LOADER_METADATA.iter().find(|(l, _)| *l == loader) else { return Ok(None) };
+    let Some(content) = jar.read_to_string(file)? else { return Ok(None) };
+    let parsed = if file.ends_with(".toml") { toml_mod(&content, || None) } else { json_mod(file, &content) };
+    Ok(parsed.map(|(id, _, _)| id))
+}
+
+/// `depends` of fabric.mod.json (id to a version predicate or a list of
+/// alternatives) or quilt.mod.json (ids or `{id, versions, optional}` objects).
+fn json_depends(file: &str, content: &str) -> Vec<Dependency> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let versions = |value: &serde_json::Value| -> Option<String> {
+        let joined = match value {
+            serde_json::Value::String(v) => v.clone(),
+            serde_json::Value::Array(alternatives) => {
+                alternatives.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(" || ")
+            }
+            _ => return None,
+        };
+        (!joined.is_empty() && joined != "*").then_some(joined)
+    };
+    if file == "quilt.mod.json" {
+        let Some(depends) = json["quilt_loader"]["depends"].as_array() else {
+            return Vec::new();
+        };
+        return depends
+            .iter()
+            .filter(|d| !d["optional"].as_bool().unwrap_or(false))
+            .filter_map(|d| match d {
+                serde_json::Value::String(id) => Some(Dependency { id: id.clone(), versions: None }),
+                _ => Some(Dependency { id: d["id"].as_str()?.to_string(), versions: versions(&d["versions"]) }),
+            })
+            .collect();
+    }
+    let Some(depends) = json["depends"].as_object() else {
+        return Vec::new();
+    };
+    let mut found: Vec<Dependency> =
+        depends.iter().map(|(id, value)| Dependency { id: id.clone(), versions: versions(value) }).collect();
+    // By id, so reports don't depend on how the jar's author ordered them
+    found.sort_by(|a, b| a.id.cmp(&b.id));
+    found
+}
+
+/// `[[dependencies.<mod_id>]]` entries of a mods.toml that are required
+/// (`type = "required"` on NeoForge, `mandatory = true` on Forge).
+fn toml_depends(content: &str, mod_id: &str) -> Vec<Dependency> {
+    let Ok(table) = toml::from_str::<toml::Table>(content) else {
+        return Vec::new();
+    };
+    let Some(entries) = table.get("dependencies").and_then(|d| d.get(mod_id)).and_then(|d| d.as_array()) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.get("type").and_then(|t| t.as_str()).is_some_and(|t| t.eq_ignore_ascii_case("required"))
+                || entry.get("mandatory").and_then(|m| m.as_bool()) == Some(true)
+        })
+        .filter_map(|entry| {
+            Some(Dependency {
+                id: entry.get("modId")?.as_str()?.to_string(),
+                versions: entry.get("versionRange").and_then(|v| v.as_str()).map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Whether `version` satisfies `range`, or None when the range can't be read.
+/// Takes Maven ranges (`[1.21.4,1.21.5)`) and Fabric predicates (`>=1.21.2
+/// <1.21.6`, `~1.21.4`, `1.21.x`, alternatives joined with `||`).
+fn accepts(range: &str, version: &str) -> Option<bool> {
+    let range = range.trim();
+    if range.starts_with('[') || range.starts_with('(') {
+        return maven_accepts(range, version);
+    }
+    let mut any = false;
+    for alternative in range.split("||") {
+        let mut all = true;
+        for predicate in alternative.split_whitespace() {
+            all &= fabric_accepts(predicate, version)?;
+        }
+        any |= all;
+    }
+    Some(any)
+}
+
+fn maven_accepts(range: &str, version: &str) -> Option<bool> {
+    let inclusive_low = range.starts_with('[');
+    let inclusive_high = range.ends_with(']');
+    let inner = range.get(1..range.len() - 1)?;
+    let Some((low, high)) = inner.split_once(',') else {
+        return Some(compare_versions(version, inner.trim()).is_eq());
+    };
+    let (low, high) = (low.trim(), high.trim());
+    let above = low.is_empty() || {
+        let ord = compare_versions(version, low);
+        ord.is_gt() || (inclusive_low && ord.is_eq())
+    };
+    let below = high.is_empty() || {
+        let ord = compare_versions(version, high);
+        ord.is_lt() || (inclusive_high && ord.is_eq())
+    };
+    Some(above && below)
+}
+
+fn fabric_accepts(predicate: &str, version: &str) -> Option<bool> {
+    if predicate == "*" {
+        return Some(true);
+    }
+    if let Some(prefix) = predicate.strip_suffix(".x").or_else(|| predicate.strip_suffix(".*")) {
+        return Some(version == prefix || version.starts_with(&format!("{prefix}.")));
+    }
+    for (op, test) in [
+        (">=", (|o| o != Ordering::Less) as fn(Ordering) -> bool),
+        ("<=", |o| o != Ordering::Greater),
+        (">", |o| o == Ordering::Greater),
+        ("<", |o| o == Ordering::Less),
+        ("=", |o| o == Ordering::Equal),
+    ] {
+        if let Some(bound) = predicate.strip_prefix(op) {
+            return Some(test(compare_versions(version, bound)));
+        }
+    }
+    if let Some(bound) = predicate.strip_prefix('~').or_else(|| predicate.strip_prefix('^')) {
+        // Same major.minor (~) or major (^), at or above the bound
+        let parts = if predicate.starts_with('~') { 2 } else { 1 };
+        let prefix: Vec<&str> = bound.split('.').take(parts).collect();
+        let same = version.split('.').take(parts).eq(prefix.iter().copied());
+        return Some(same && compare_versions(version, bound).is_ge());
+    }
+    Some(compare_versions(version, predicate).is_eq())
+}
+
+/// Id, name and version from fabric.mod.json or quilt.mod.json.
+fn json_mod(file: &str, content: &str) -> Option<(String, Option<String>, String)> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let (info, name) = if file == "quilt.mod.json" {
+        (&json["quilt_loader"], &json["quilt_loader"]["metadata"]["name"])
+    } else {
+        (&json, &json["name"])
+    };
+    Some((
+        info["id"].as_str()?.to_string(),
+        name.as_str().map(str::to_string),
+        info["version"].as_str()?.to_string(),
+    ))
+}
+
+/// Id, name and version of the first `[[mods]]` entry in a mods.toml. Forge
+/// jars usually say `version = "${file.jarVersion}"`, filled in from the
+/// manifest's Implementation-Version.
+fn toml_mod(
+    content: &str,
+    jar_version: impl FnOnce() -> Option<String>,
+) -> Option<(String, Option<String>, String)> {
+    let table: toml::Table = toml::from_str(content).ok()?;
+    let first = table.get("mods")?.as_array()?.first()?;
+    let id = first.get("modId")?.as_str()?.to_string();
+    let name = first.get("displayName").and_then(|n| n.as_str()).map(str::to_string);
+    let version = match first.get("version").and_then(|v| v.as_str()) {
+        Some("${file.jarVersion}") | None => jar_version().unwrap_or_else(|| "unknown".to_string()),
+        Some(version) => version.to_string(),
+    };
+    Some((id, name, version))
+}
+
+fn manifest_version(jar: &Jar) -> Option<String> {
+    let manifest = jar.read_to_string("META-INF/MANIFEST.MF").ok()??;
+    manifest
+        .lines()
+        .find_map(|l| l.strip_prefix("Implementation-Version:"))
+        .map(|v| v.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jar::stored_zip;
+
+    #[test]
+    fn test_read_mod() {
+        let fabric = Jar::from_bytes(stored_zip(&[(
+            "fabric.mod.json",
+            br#"{"id": "sodium", "name": "Sodium", "version": "0.6.5+mc1.21.4", "depends": {"minecraft": ["1.21.4", "1.21.5"], "fabricloader": "*"}}"#,
+        )]))
+        .unwrap();
+        assert_eq!(
+            read_mod(&fabric).unwrap(),
+            Some(JarMod {
+                id: "sodium".to_string(),
+                name: Some("Sodium".to_string()),
+                version: "0.6.5+mc1.21.4".to_string(),
+                loader: "fabric",
+                depends: vec![
+                    Dependency { id: "fabricloader".to_string(), versions: None },
+                    Dependency { id: "minecraft".to_string(), versions: Some("1.21.4 || 1.21.5".to_string()) },
+                ],
+            })
+        );
+
+        let neoforge = Jar::from_bytes(stored_zip(&[
+            (
+                "META-INF/neoforge.mods.toml",
+                b"modLoader = \"javafml\"\n[[mods]]\nmodId = \"jei\"\nversion = \"${file.jarVersion}\"\n\
+                  [[dependencies.jei]]\nmodId = \"minecraft\"\ntype = \"required\"\nversionRange = \"[1.21.1,1.21.2)\"\n\
+                  [[dependencies.jei]]\nmodId = \"emi\"\ntype = \"optional\"\n",
+            ),
+            ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\r\nImplementation-Version: 19.21.0\r\n"),
+        ]))
+        .unwrap();
+        let jei = read_mod(&neoforge).unwrap().unwrap();
+        assert_eq!((jei.id.as_str(), jei.version.as_str(), jei.loader), ("jei", "19.21.0", "neoforge"));
+        assert_eq!(jei.name, None);
+        assert_eq!(jei.depends, [Dependency { id: "minecraft".to_string(), versions: Some("[1.21.1,1.21.2)".to_string()) }]);
+
+        let empty = Jar::from_bytes(stored_zip(&[("README.txt", b"hi")])).unwrap();
+        assert_eq!(read_mod(&empty).unwrap(), None);
+    }
+
+    #[test]
+    fn test_run_dirs() {
+        let dir = Path::new("/memory/rundirs");
+        let (result, fs) = crate::fs::in_memory(|| {
+            let mut opts = init::tests::options(dir, &["fabric", "neoforge"]);
+            opts.server = Some(true);
+            init::run(opts)?;
+            run_run_dirs(dir, RunDirs::PerLoader)?;
+            let per_loader = McmodConfig::load(dir)?;
+            let build = crate::fs::read_to_string(&dir.join("build.gradle.kts"))?;
+            run_run_dirs(dir, RunDirs::Shared)?;
+            Ok::<_, McmodError>((per_loader, build))
+        });
+        let (config, build) = result.unwrap();
+
+        assert!(build.contains("runDirectory = rootProject.layout.projectDirectory.dir(\"run/\""));
+        for loader in ["fabric", "neoforge"] {
+            let run = dir.join("run").join(loader);
+            assert!(fs.read_string(&run.join("options.txt")).unwrap().contains("file/dev-resources"));
+            assert!(fs.read_string(&run.join("eula.txt")).unwrap().contains("eula=true"));
+            assert!(fs.read_string(&run.join("world/datapacks/dev-defaults/pack.mcmeta")).is_some());
+        }
+        assert_eq!(config.features.run_dirs, RunDirs::PerLoader);
+        assert_eq!(mods_dir(&config, "neoforge"), "run/neoforge/mods");
+        assert_eq!(template::render_build_gradle(&config, &template::build_common_vars(&config)).unwrap(), build);
+
+        // Switching back drops the block mcmod added
+        assert!(!fs.read_string(&dir.join("build.gradle.kts")).unwrap().contains("runDirectory"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert_eq!(config.run_dirs(), ["run"]);
+    }
+
+    #[test]
+    fn test_record_keeps_tested_version() {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let sodium = |version: &str| JarMod {
+            id: "sodium".to_string(),
+            name: Some("Sodium".to_string()),
+            version: version.to_string(),
+            loader: "fabric",
+            depends: Vec::new(),
+        };
+        record(&mut config, sodium("0.6.5"));
+        config.compat.get_mut("sodium").unwrap().status = CompatStatus::Works;
+        record(&mut config, sodium("0.6.5"));
+        assert_eq!(config.compat["sodium"].status, CompatStatus::Works);
+        record(&mut config, sodium("0.6.6"));
+        assert_eq!(config.compat["sodium"].status, CompatStatus::Untested);
+        assert_eq!(config.compat["sodium"].version, "0.6.6");
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert_eq!(accepts("[1.21.1,1.21.2)", "1.21.1"), Some(true));
+        assert_eq!(accepts("[1.21.1,1.21.2)", "1.21.2"), Some(false));
+        assert_eq!(accepts("[1.21.4]", "1.21.4"), Some(true));
+        assert_eq!(accepts("[1.21,)", "1.21.4"), Some(true));
+        assert_eq!(accepts(">=1.21.2 <1.21.5", "1.21.4"), Some(true));
+        assert_eq!(accepts(">=1.21.2 <1.21.5", "1.21.5"), Some(false));
+        assert_eq!(accepts("1.21.1 || 1.21.4", "1.21.4"), Some(true));
+        assert_eq!(accepts("~1.21.2", "1.21.4"), Some(true));
+        assert_eq!(accepts("~1.21.2", "1.22"), Some(false));
+        assert_eq!(accepts("1.21.x", "1.21.4"), Some(true));
+        assert_eq!(accepts("1.20.x", "1.21.4"), Some(false));
+        assert_eq!(accepts("1.21.1", "1.21.4"), Some(false));
+    }
+
+    #[test]
+    fn test_check_mods() {
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let jar = |file: &str, id: &str, loader: &'static str, depends: &[(&str, Option<&str>)]| {
+            let depends = depends
+                .iter()
+                .map(|(id, versions)| Dependency { id: id.to_string(), versions: versions.map(str::to_string) })
+                .collect();
+            (file.to_string(), JarMod { id: id.to_string(), name: None, version: "1.0".to_string(), loader, depends })
+        };
+        let jars = [
+            jar("sodium-0.6.5.jar", "sodium", "fabric", &[("minecraft", Some("~1.21.4")), ("fabric-api-base", None)]),
+            jar("sodium-0.6.6.jar", "sodium", "fabric", &[]),
+            jar("iris.jar", "iris", "fabric", &[("sodium", None), ("minecraft", Some(">=1.21.4"))]),
+            jar("addon.jar", "iris_addon", "fabric", &[("iris", None), ("glsl_lib", None)]),
+            jar("testmod-1.0.jar", "testmod", "fabric", &[]),
+            jar("jei.jar", "jei", "neoforge", &[("minecraft", Some("[1.21.1,1.21.2)"))]),
+        ];
+
+        assert_eq!(
+            check_mods(&config, "fabric", "1.21.1", &jars),
+            [
+                "Duplicate mod id sodium: sodium-0.6.5.jar, sodium-0.6.6.jar",
+                "testmod-1.0.jar is a build of this mod, which dev runs already load",
+                "sodium-0.6.5.jar (sodium) needs Minecraft ~1.21.4, but dev runs use 1.21.1",
+                "iris.jar (iris) needs Minecraft >=1.21.4, but dev runs use 1.21.1",
+                "Missing dependency glsl_lib, required by iris_addon",
+            ]
+        );
+        assert!(check_mods(&config, "neoforge", "1.21.1", &jars).is_empty());
+
+        let jars = [jar("addon.jar", "iris_addon", "fabric", &[("iris", None)]), jars[2].clone()];
+        assert_eq!(
+            check_mods(&config, "fabric", "1.21.4", &jars),
+            ["Missing dependency sodium, required by iris ← iris_addon"]
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use crate::commands::build::check_java;
+use crate::commands::update::Progress;
+use crate::config::{McmodConfig, VersionTarget};
+use crate::error::{Context, McmodError, Result};
+use crate::global_config::GlobalConfig;
+use crate::gradle;
+use crate::util::{http_download, http_get, sha1_file, write_file};
+use crate::versions::{FABRIC_LOADER_META, MOJANG_VERSION_MANIFEST};
+use clap::ValueEnum;
+use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
+
+/// Where `mcmod dev server install` puts the server, relative to the project.
+pub const SERVER_DIR: &str = "run/server";
+/// What was installed (`minecraft`, `loader`, `neoforge_version`), read back by `dev server run`.
+const INSTALLED: &str = "mcmod-server.properties";
+const VANILLA_JAR: &str = "server.jar";
+const FABRIC_LAUNCHER: &str = "fabric-server-launch.jar";
+const NEOFORGE_RELEASES: &str = "https://maven.neoforged.net/releases/net/neoforged/neoforge";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ServerLoader {
+    /// Mojang's server jar, without mods
+    Vanilla,
+    /// The Fabric server launcher, with the project's Fabric Loader
+    Fabric,
+    /// The NeoForge server, set up by NeoForge's installer
+    Neoforge,
+}
+
+impl ServerLoader {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServerLoader::Vanilla => "vanilla",
+            ServerLoader::Fabric => "fabric",
+            ServerLoader::Neoforge => "neoforge",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<ServerLoader> {
+        [ServerLoader::Vanilla, ServerLoader::Fabric, ServerLoader::Neoforge]
+            .into_iter()
+            .find(|l| l.as_str() == s)
+    }
+}
+
+/// A file to fetch and, where the source publishes one, its SHA-1.
+#[derive(Debug, PartialEq)]
+struct Download {
+    url: String,
+    sha1: Option<String>,
+    size: Option<u64>,
+}
+
+/// `mcmod dev server install`: download the server for one of the project's
+/// Minecraft versions into run/server, so a server can be started without
+/// Gradle. Mojang's jar and NeoForge's installer are checked against their
+/// published SHA-1s; NeoForge's installer then sets up the server with Java.
+pub fn run_install(
+    dir: &Path,
+    loader: Option<ServerLoader>,
+    mc_version: Option<&str>,
+    accept_eula: bool,
+) -> Result<()> {
+    println!("{}", "\n  mcmod dev server install\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "Dev servers belong to mod and library projects (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let target = pick_target(&config, mc_version)?;
+    let loader = loader.unwrap_or_else(|| default_loader(&config));
+    let enabled = match loader {
+        ServerLoader::Vanilla => true,
+        ServerLoader::Fabric => config.loaders.fabric,
+        ServerLoader::Neoforge => config.loaders.neoforge,
+    };
+    if !enabled {
+        return Err(McmodError::Other(format!(
+            "The {} loader isn't enabled in this project; add it with `mcmod add {}`",
+            loader.as_str(),
+            loader.as_str()
+        )));
+    }
+
+    let server = dir.join(SERVER_DIR);
+    std::fs::create_dir_all(&server)?;
+    println!("  Minecraft {} ({}) into {SERVER_DIR}/", target.minecraft, loader.as_str());
+
+    match loader {
+        ServerLoader::Vanilla => install_vanilla(&server, &target.minecraft)?,
+        ServerLoader::Fabric => {
+            install_vanilla(&server, &target.minecraft)?;
+            install_fabric(&server, target)?;
+        }
+        ServerLoader::Neoforge => install_neoforge(&server, target)?,
+    }
+
+    let mut installed = format!("minecraft={}\nloader={}\n", target.minecraft, loader.as_str());
+    if loader == ServerLoader::Neoforge {
+        installed.push_str(&format!("neoforge_version={}\n", target.neoforge));
+    }
+    std::fs::write(server.join(INSTALLED), installed)?;
+
+    let eula = server.join("eula.txt");
+    if accept_eula {
+        std::fs::write(&eula, "# Accepted with mcmod dev server install\n# https://aka.ms/MinecraftEULA\neula=true\n")?;
+        if !server.join("server.properties").exists() {
+            std::fs::write(server.join("server.properties"), GlobalConfig::load()?.render_server_properties())?;
+        }
+    } else if !std::fs::read_to_string(&eula).is_ok_and(|e| e.contains("eula=true")) {
+        println!(
+            "{}",
+            "  The server stops on first start until eula.txt accepts the Minecraft EULA (--accept-eula)".yellow()
+        );
+    }
+
+    println!("\n  {}", "Server installed.".bold().green());
+    println!("  Start it with {}", "mcmod dev server run".green());
+    Ok(())
+}
+
+/// `mcmod dev server init`: prepare run/server for a dev server without
+/// downloading one. server.properties is rendered from the `[server]`
+/// preferences, the dev-defaults data pack goes into the server's world, and
+/// eula.txt is only written when `accept_eula` is given.
+pub fn run_init(dir: &Path, accept_eula: bool) -> Result<()> {
+    println!("{}", "\n  mcmod dev server init\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    let global = GlobalConfig::load()?;
+    let server = dir.join(SERVER_DIR);
+    prepare_server_dir(&server, &config, &global, accept_eula)?;
+
+    println!("\n  {}", "Server directory ready.".bold().green());
+    if !server.join(INSTALLED).exists() {
+        println!("  Download a server into it with {}", "mcmod dev server install".green());
+    }
+    Ok(())
+}
+
+/// Write server.properties, the dev data pack and (when accepted) eula.txt into `server`.
+fn prepare_server_dir(server: &Path, config: &McmodConfig, global: &GlobalConfig, accept_eula: bool) -> Result<()> {
+    write_file(&server.join("server.properties"), &global.render_server_properties())?;
+    let online = global.server.online_mode.unwrap_or(false);
+    crate::util::progress(format!("  Wrote {SERVER_DIR}/server.properties (online-mode={online})").green());
+
+    let mc = config.versions.targets.last().map(|t| t.minecraft.as_str()).unwrap_or("1.21.4");
+    crate::pack_format::write_dev_datapack(server, global, mc)?;
+    crate::util::progress(format!("  Wrote {SERVER_DIR}/world/datapacks/dev-defaults/").green());
+
+    let eula = server.join("eula.txt");
+    if accept_eula {
+        write_file(&eula, "# Accepted with mcmod dev server init\n# https://aka.ms/MinecraftEULA\neula=true\n")?;
+        crate::util::progress(format!("  Wrote {SERVER_DIR}/eula.txt (EULA accepted)").green());
+    } else if !crate::fs::read_to_string(&eula).is_ok_and(|e| e.contains("eula=true")) {
+        crate::progress::warning(
+            "The server stops on first start until eula.txt accepts the Minecraft EULA (--accept-eula)",
+        );
+    }
+    Ok(())
+}
+
+/// `mcmod dev server run`: copy the project's built jars for the installed
+/// version into run/server/mods and start the server in the foreground.
+pub fn run_server(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod dev server run\n".bold().cyan());
+    let server = dir.join(SERVER_DIR);
+    let installed = std::fs::read_to_string(server.join(INSTALLED)).map_err(|_| {
+        McmodError::Other(format!("No server in {SERVER_DIR}; run `mcmod dev server install` first"))
+    })?;
+    let property = |key: &str| gradle::get_property_content(&installed, key).unwrap_or_default();
+    let minecraft = property("minecraft");
+    let loader = ServerLoader::from_str(&property("loader"))
+        .ok_or_else(|| McmodError::Other(format!("{SERVER_DIR}/{INSTALLED} names no known loader")))?;
+
+    if loader != ServerLoader::Vanilla {
+        let jars: Vec<_> = crate::commands::report::find_jars(dir)
+            .into_iter()
+            .filter(|j| j.loader.as_deref() == Some(loader.as_str()) && j.minecraft.as_deref() == Some(minecraft.as_str()))
+            .collect();
+        if jars.is_empty() {
+            println!(
+                "{}",
+                format!("  No built {} jar for {minecraft}; run `mcmod build` to test the mod", loader.as_str()).yellow()
+            );
+        }
+        let mods = server.join("mods");
+        std::fs::create_dir_all(&mods)?;
+        for jar in jars {
+            let name = jar.path.file_name().unwrap_or_default();
+            std::fs::copy(&jar.path, mods.join(name)).with_context(|| format!("Copying {}", jar.path.display()))?;
+            println!("{}", format!("  Copied {} to {SERVER_DIR}/mods/", name.to_string_lossy()).green());
+        }
+    }
+
+    let (java, _) = check_java()?;
+    let args = launch_args(loader, &property("neoforge_version"), cfg!(windows));
+    println!("  {}\n", format!("{} {}", java.display(), args.join(" ")).dimmed());
+    let status = Command::new(&java)
+        .args(&args)
+        .current_dir(&server)
+        .status()
+        .map_err(|e| McmodError::Other(format!("Could not start {}: {e}", java.display())))?;
+    if !status.success() {
+        return Err(McmodError::Other(format!("The server exited with {status}")));
+    }
+    Ok(())
+}
+
+/// The target named by `--mc-version`, or the newest one.
+fn pick_target<'a>(config: &'a McmodConfig, mc_version: Option<&str>) -> Result<&'a VersionTarget> {
+    let targets = &config.versions.targets;
+    match mc_version {
+        Some(mc) => targets.iter().find(|t| t.minecraft == mc).ok_or_else(|| {
+            let known: Vec<&str> = targets.iter().map(|t| t.minecraft.as_str()).collect();
+            McmodError::Other(format!("{mc} is not one of this project's versions ({})", known.join(", ")))
+        }),
+        None => targets
+            .last()
+            .ok_or_else(|| McmodError::Other("mcmod.toml has no version targets".to_string())),
+    }
+}
+
+fn default_loader(config: &McmodConfig) -> ServerLoader {
+    if config.loaders.fabric {
+        ServerLoader::Fabric
+    } else if config.loaders.neoforge {
+        ServerLoader::Neoforge
+    } else {
+        ServerLoader::Vanilla
+    }
+}
+
+fn install_vanilla(server: &Path, minecraft: &str) -> Result<()> {
+    let version_url = version_url(&http_get(MOJANG_VERSION_MANIFEST)?, minecraft)?;
+    let download = server_download(&http_get(&version_url)?)?;
+    fetch(&download, &server.join(VANILLA_JAR))
+}
+
+/// The Fabric server launcher, pointed at the vanilla jar beside it. Fabric
+/// Meta publishes no checksum for it; the vanilla jar it loads is verified.
+fn install_fabric(server: &Path, target: &VersionTarget) -> Result<()> {
+    if target.fabric_loader.is_empty() {
+        return Err(McmodError::Other(format!("No Fabric Loader version is pinned for {}", target.minecraft)));
+    }
+    let installer = crate::versions::fetch_fabric_installer_version()?;
+    let download = Download {
+        url: format!("{FABRIC_LOADER_META}/{}/{}/{installer}/server/jar", target.minecraft, target.fabric_loader),
+        sha1: None,
+        size: None,
+    };
+    // Always fetched again: the launcher's name doesn't change with the loader version
+    let _ = std::fs::remove_file(server.join(FABRIC_LAUNCHER));
+    fetch(&download, &server.join(FABRIC_LAUNCHER))?;
+    std::fs::write(server.join("fabric-server-launcher.properties"), format!("serverJar={VANILLA_JAR}\n"))?;
+    Ok(())
+}
+
+/// Download NeoForge's installer (checked against Maven's `.sha1`) and run
+/// it with `--installServer`, which fetches and verifies the rest.
+fn install_neoforge(server: &Path, target: &VersionTarget) -> Result<()> {
+    let version = &target.neoforge;
+    if version.is_empty() {
+        return Err(McmodError::Other(format!("No NeoForge version is pinned for {}", target.minecraft)));
+    }
+    let url = format!("{NEOFORGE_RELEASES}/{version}/neoforge-{version}-installer.jar");
+    let sha1 = http_get(&format!("{url}.sha1"))?.split_whitespace().next().map(str::to_string);
+    let installer = server.join("neoforge-installer.jar");
+    let _ = std::fs::remove_file(&installer);
+    fetch(&Download { url, sha1, size: None }, &installer)?;
+
+    let (java, _) = check_java()?;
+    println!("  Running the NeoForge installer...");
+    let output = Command::new(&java)
+        .args(["-jar", "neoforge-installer.jar", "--installServer", "."])
+        .current_dir(server)
+        .output()
+        .map_err(|e| McmodError::Other(format!("Could not start {}: {e}", java.display())))?;
+    let _ = std::fs::remove_file(&installer);
+    if !output.status.success() {
+        let log = String::from_utf8_lossy(&output.stdout);
+        let tail: Vec<&str> = log.lines().rev().take(10).collect();
+        return Err(McmodError::Other(format!(
+            "The NeoForge installer failed ({}):\n{}",
+            output.status,
+            tail.into_iter().rev().collect::<Vec<_>>().join("\n")
+        )));
+    }
+    println!("{}", format!("  Installed NeoForge {version}").green());
+    Ok(())
+}
+
+/// Download `download` to `dest` through a `.part` file, checking its SHA-1
+/// when there is one. A `dest` that already has that SHA-1 is kept.
+fn fetch(download: &Download, dest: &Path) -> Result<()> {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy().to_string();
+    if let Some(expected) = &download.sha1 {
+        if dest.exists() && sha1_file(dest)? == *expected {
+            println!("{}", format!("  {name} is already downloaded").green());
+            return Ok(());
+        }
+    }
+
+    let partial = dest.with_file_name(format!("{name}.part"));
+    let mut progress = Progress::new(download.size);
+    let result = http_download(&download.url, &partial, &mut |done, total| progress.update(done, total));
+    progress.finish();
+    result?;
+
+    if let Some(expected) = &download.sha1 {
+        let actual = sha1_file(&partial)?;
+        if actual != *expected {
+            let _ = std::fs::remove_file(&partial);
+            return Err(McmodError::Other(format!(
+                "Checksum mismatch for {name} (expected {expected}, got {actual})"
+            )));
+        }
+    }
+    std::fs::rename(&partial, dest)?;
+    let verified = if download.sha1.is_some() { " (SHA-1 verified)" } else { "" };
+    println!("{}", format!("  Downloaded {name}{verified}").green());
+    Ok(())
+}
+
+/// The per-version JSON URL for `minecraft` in Mojang's version manifest.
+fn version_url(manifest: &str, minecraft: &str) -> Result<String> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest)?;
+    manifest["versions"]
+        .as_array()
+        .and_then(|versions| versions.iter().find(|v| v["id"] == minecraft))
+        .and_then(|v| v["url"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| McmodError::Other(format!("Minecraft {minecraft} is not in Mojang's version manifest")))
+}
+
+/// `downloads.server` of a Mojang version JSON.
+fn server_download(version: &str) -> Result<Download> {
+    let version: serde_json::Value = serde_json::from_str(version)?;
+    let server = &version["downloads"]["server"];
+    match (server["url"].as_str(), server["sha1"].as_str()) {
+        (Some(url), Some(sha1)) => Ok(Download {
+            url: url.to_string(),
+            sha1: Some(sha1.to_string()),
+            size: server["size"].as_u64(),
+        }),
+        _ => Err(McmodError::Other("Mojang publishes no server jar for this version".to_string())),
+    }
+}
+
+/// Java arguments that start the installed server, run from run/server.
+/// NeoForge's installer leaves an argument file per platform.
+fn launch_args(loader: ServerLoader, neoforge: &str, windows: bool) -> Vec<String> {
+    let mut args: Vec<String> = match loader {
+        ServerLoader::Vanilla => vec!["-jar".to_string(), VANILLA_JAR.to_string()],
+        ServerLoader::Fabric => vec!["-jar".to_string(), FABRIC_LAUNCHER.to_string()],
+        ServerLoader::Neoforge => {
+            let platform = if windows { "win_args.txt" } else { "unix_args.txt" };
+            vec![
+                "@user_jvm_args.txt".to_string(),
+                format!("@libraries/net/neoforged/neoforge/{neoforge}/{platform}"),
+            ]
+        }
+    };
+    args.push("nogui".to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare_server_dir() {
+        let config = McmodConfig::new(
+            "mymod".to_string(),
+            "My Mod".to_string(),
+            "com.example.mymod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let mut global = GlobalConfig::default();
+        global.server.spawn_protection = Some(0);
+        let server = Path::new("/project").join(SERVER_DIR);
+
+        let (result, fs) = crate::fs::in_memory(|| prepare_server_dir(&server, &config, &global, false));
+        result.unwrap();
+        let props = fs.read_string(&server.join("server.properties")).unwrap();
+        assert!(props.contains("\nspawn-protection=0\n"));
+        assert!(props.contains("\nonline-mode=false\n"));
+        assert!(fs.read_string(&server.join("world/datapacks/dev-defaults/pack.mcmeta")).is_some());
+        assert!(fs.read_string(&server.join("eula.txt")).is_none());
+
+        let (result, fs) = crate::fs::in_memory(|| prepare_server_dir(&server, &config, &global, true));
+        result.unwrap();
+        assert!(fs.read_string(&server.join("eula.txt")).unwrap().contains("eula=true"));
+    }
+
+    #[test]
+    fn test_mojang_metadata() {
+        let manifest = r#"{"versions": [
+            {"id": "1.21.5", "type": "release", "url": "https://piston-meta.mojang.com/v1/packages/aa/1.21.5.json"},
+            {"id": "1.21.4", "type": "release", "url": "https://piston-meta.mojang.com/v1/packages/bb/1.21.4.json"}
+        ]}"#;
+        assert_eq!(
+            version_url(manifest, "1.21.4").unwrap(),
+            "https://piston-meta.mojang.com/v1/packages/bb/1.21.4.json"
+        );
+        assert!(version_url(manifest, "1.20.1").is_err());
+
+        let version = r#"{"downloads": {
+            "client": {"sha1": "c0", "size": 1, "url": "https://piston-data.mojang.com/v1/objects/c0/client.jar"},
+            "server": {"sha1": "4707d00eb834b446575d89a61a11b5d548d8c001", "size": 57555044,
+                       "url": "https://piston-data.mojang.com/v1/objects/4707d00eb834b446575d89a61a11b5d548d8c001/server.jar"}
+        }}"#;
+        assert_eq!(
+            server_download(version).unwrap(),
+            Download {
+                url: "https://piston-data.mojang.com/v1/objects/4707d00eb834b446575d89a61a11b5d548d8c001/server.jar"
+                    .to_string(),
+                sha1: Some("4707d00eb834b446575d89a61a11b5d548d8c001".to_string()),
+                size: Some(57555044),
+            }
+        );
+        assert!(server_download(r#"{"downloads": {}}"#).is_err());
+    }
+
+    #[test]
+    fn test_launch_args() {
+        assert_eq!(launch_args(ServerLoader::Fabric, "", false), ["-jar", "fabric-server-launch.jar", "nogui"]);
+        assert_eq!(
+            launch_args(ServerLoader::Neoforge, "21.4.157", true),
+            ["@user_jvm_args.txt", "@libraries/net/neoforged/neoforge/21.4.157/win_args.txt", "nogui"]
+        );
+    }
+}
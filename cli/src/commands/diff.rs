@@ -0,0 +1,39 @@
+use crate::commands::init;
+use crate::config::{McmodConfig, ProjectType};
+use crate::error::Result;
+use crate::global_config::GlobalConfig;
+use colored::Colorize;
+use std::path::Path;
+
+/// `mcmod diff`: render every template for the project as mcmod.toml
+/// describes it, in memory, and show how the files on disk differ from a
+/// fresh generation: template updates, hand edits, and missing files.
+pub fn run(dir: &Path, stat: bool) -> Result<()> {
+    println!("{}", "\n  mcmod diff".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    let global = GlobalConfig::load().unwrap_or_default();
+
+    crate::util::set_quiet(true);
+    let result = crate::diff::preview(dir, stat, || regenerate(dir, &config, &global));
+    crate::util::set_quiet(false);
+    result
+}
+
+/// Write the whole project again, the way `init` would with these answers.
+fn regenerate(dir: &Path, config: &McmodConfig, global: &GlobalConfig) -> Result<()> {
+    if config.uses_stonecutter() {
+        // The run directory's eula.txt is how init's server answer shows
+        let run = config.run_dirs().first().cloned().unwrap_or_else(|| "run".to_string());
+        let server = dir.join(run).join("eula.txt").exists();
+        return init::write_mod_project(dir, config, global, server);
+    }
+    let vars = crate::template::build_common_vars(config);
+    if config.is_plugin() {
+        crate::plugin_project::write_project(dir, config, &vars)?;
+    } else {
+        let optifine = config.mod_info.project_type == ProjectType::Resourcepack
+            && dir.join("assets/minecraft/optifine").exists();
+        crate::pack_project::write_project(dir, config, &vars, optifine)?;
+    }
+    config.save(dir)
+}
@@ -0,0 +1,835 @@
+use crate::config::McmodConfig;
+use crate::diff;
+use crate::error::{McmodError, Result};
+use crate::file_policy::FilePolicy;
+use crate::gradle;
+use crate::sandbox;
+use crate::template::{self, render};
+use crate::util::{write_binary, write_file};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where an issue stands after the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    /// Can be fixed automatically with `--fix`.
+    Fixable,
+    /// Fixed during this run.
+    Fixed,
+    /// An automatic fix was attempted but failed.
+    Skipped,
+    /// Needs a human to decide.
+    Manual,
+}
+
+/// A safe, automatic remediation.
+#[derive(Debug)]
+enum Fix {
+    /// Recreate a missing managed file.
+    Write { path: PathBuf, content: Vec<u8> },
+    /// Add a loader to the `mc(...)` entries in settings.gradle.kts.
+    AddLoader(&'static str),
+    /// Restore the executable bit on a script.
+    MakeExecutable(PathBuf),
+    /// Add `include("<module>")` to settings.gradle.kts.
+    Include(&'static str),
+    /// Set a property in gradle.properties.
+    SetProperty { key: &'static str, value: String },
+    /// Drop a mixin class that doesn't exist from a mixins.json list.
+    RemoveMixin { path: PathBuf, class: String },
+}
+
+#[derive(Debug, Serialize)]
+struct Issue {
+    /// Stable identifier for scripts, e.g. `missing_file` or `settings_loader`.
+    id: &'static str,
+    message: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(skip)]
+    fix: Option<Fix>,
+}
+
+impl Issue {
+    fn fixable(id: &'static str, message: String, fix: Fix) -> Self {
+        Issue {
+            id,
+            message,
+            status: Status::Fixable,
+            detail: None,
+            fix: Some(fix),
+        }
+    }
+
+    fn manual(id: &'static str, message: String, hint: &str) -> Self {
+        Issue {
+            id,
+            message,
+            status: Status::Manual,
+            detail: Some(hint.to_string()),
+            fix: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    project: String,
+    issues: &'a [Issue],
+    summary: HashMap<Status, usize>,
+}
+
+/// Run the project health checks, optionally applying automatic fixes.
+pub fn run(dir: &Path, fix: bool, json: bool) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let mut issues = check(dir, &config)?;
+
+    if fix {
+        for issue in &mut issues {
+            if let Some(f) = issue.fix.take() {
+                match apply_fix(dir, &f, json) {
+                    Ok(()) => issue.status = Status::Fixed,
+                    Err(e) => {
+                        issue.status = Status::Skipped;
+                        issue.detail = Some(e.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    if json {
+        let mut summary = HashMap::new();
+        for issue in &issues {
+            *summary.entry(issue.status).or_insert(0) += 1;
+        }
+        let report = Report {
+            project: dir.display().to_string(),
+            issues: &issues,
+            summary,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&issues, fix);
+    }
+
+    let remaining = issues.iter().filter(|i| i.status != Status::Fixed).count();
+    if remaining > 0 {
+        return Err(McmodError::Other(format!(
+            "{remaining} problem(s) remain in {}",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Collect every problem with the project.
+fn check(dir: &Path, config: &McmodConfig) -> Result<Vec<Issue>> {
+    let mut issues = Vec::new();
+
+    for (path, content) in managed_files(dir, config)? {
+        if !path.exists() {
+            let rel = path.strip_prefix(dir).unwrap_or(&path).display().to_string();
+            issues.push(Issue::fixable(
+                "missing_file",
+                format!("{rel} is missing"),
+                Fix::Write { path, content },
+            ));
+        }
+    }
+
+    // Packs and plugins have no Stonecutter settings to drift from mcmod.toml
+    let settings_path = dir.join("settings.gradle.kts");
+    if let (true, Ok(settings)) = (config.uses_stonecutter(), std::fs::read_to_string(&settings_path)) {
+        check_settings(&settings, config, &mut issues);
+    }
+
+    if config.uses_stonecutter() {
+        check_enabled_platforms(dir, config, &mut issues);
+        check_mixins(dir, config, &mut issues);
+    }
+    check_leftovers(dir, config, &mut issues);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let gradlew = dir.join("gradlew");
+        if let Ok(meta) = std::fs::metadata(&gradlew) {
+            if meta.permissions().mode() & 0o111 == 0 {
+                issues.push(Issue::fixable(
+                    "gradlew_not_executable",
+                    "gradlew is not executable".to_string(),
+                    Fix::MakeExecutable(gradlew),
+                ));
+            }
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Compare the loaders and versions in settings.gradle.kts against mcmod.toml.
+fn check_settings(settings: &str, config: &McmodConfig, issues: &mut Vec<Issue>) {
+    let entries = parse_mc_entries(settings);
+    let enabled = config.enabled_platforms();
+
+    for loader in ["fabric", "neoforge", "forge", "quilt"] {
+        let in_settings = entries.iter().any(|(_, loaders)| loaders.iter().any(|l| l == loader));
+        let in_config = enabled.contains(&loader);
+        if in_config && !entries.iter().all(|(_, loaders)| loaders.iter().any(|l| l == loader)) {
+            issues.push(Issue::fixable(
+                "settings_loader",
+                format!("{loader} is enabled in mcmod.toml but missing from settings.gradle.kts"),
+                Fix::AddLoader(loader),
+            ));
+        } else if in_settings && !in_config {
+            issues.push(Issue::manual(
+                "settings_loader",
+                format!("settings.gradle.kts builds {loader} but mcmod.toml doesn't enable it"),
+                &format!(
+                    "Run `mcmod add {loader}` to enable it, or remove \"{loader}\" from the mc(...) lines"
+                ),
+            ));
+        }
+    }
+
+    let modules = [
+        (crate::plugin_project::PAPER_MODULE_DIR, config.features.paper),
+        (crate::plugin_project::SPONGE_MODULE_DIR, config.loaders.sponge),
+        (crate::plugin_project::VELOCITY_MODULE_DIR, config.features.velocity),
+        (crate::plugin_project::SPIGOT_MODULE_DIR, config.features.spigot),
+    ];
+    for (module, enabled) in modules {
+        let include = format!("include(\"{module}\")");
+        let included = settings.lines().any(|l| l.trim() == include);
+        if enabled && !included {
+            issues.push(Issue::fixable(
+                "settings_include",
+                format!("{module} is enabled in mcmod.toml but settings.gradle.kts doesn't include it"),
+                Fix::Include(module),
+            ));
+        } else if included && !enabled {
+            issues.push(Issue::manual(
+                "settings_include",
+                format!("settings.gradle.kts includes {module} but mcmod.toml doesn't enable it"),
+                &format!("Run `mcmod add {module}` to enable it, or remove {include}"),
+            ));
+        }
+    }
+
+    for target in &config.versions.targets {
+        if !entries.iter().any(|(mc, _)| mc == &target.minecraft) {
+            issues.push(Issue::manual(
+                "settings_version",
+                format!(
+                    "Minecraft {} is a target in mcmod.toml but not in settings.gradle.kts",
+                    target.minecraft
+                ),
+                "Add an mc(...) line for it or remove the target from mcmod.toml",
+            ));
+        }
+    }
+}
+
+/// Projects from the Architectury layout list their loaders in an
+/// `enabled_platforms` property; it has to match mcmod.toml.
+fn check_enabled_platforms(dir: &Path, config: &McmodConfig, issues: &mut Vec<Issue>) {
+    let Ok(properties) = std::fs::read_to_string(dir.join("gradle.properties")) else {
+        return;
+    };
+    let Some(current) = properties.lines().find_map(|l| l.trim().strip_prefix("enabled_platforms=")) else {
+        return;
+    };
+    let expected = config.enabled_platforms().join(",");
+    let mut listed: Vec<&str> = current.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    let mut wanted: Vec<&str> = config.enabled_platforms();
+    listed.sort_unstable();
+    wanted.sort_unstable();
+    if listed != wanted {
+        issues.push(Issue::fixable(
+            "enabled_platforms",
+            format!("gradle.properties has enabled_platforms={current} but mcmod.toml enables {expected}"),
+            Fix::SetProperty { key: "enabled_platforms", value: expected },
+        ));
+    }
+}
+
+/// Loader metadata files under `src/main/resources` (and at the root of a
+/// built jar), with the loader that reads each.
+pub const LOADER_METADATA: &[(&str, &str)] = &[
+    ("fabric", "fabric.mod.json"),
+    ("quilt", "quilt.mod.json"),
+    ("neoforge", "META-INF/neoforge.mods.toml"),
+    ("forge", "META-INF/mods.toml"),
+];
+
+/// Mixin configs named by the loader metadata must exist, and every class
+/// they list must have a source file.
+fn check_mixins(dir: &Path, config: &McmodConfig, issues: &mut Vec<Issue>) {
+    let resources = dir.join("src/main/resources");
+    let managed = format!("{}.mixins.json", config.mod_info.mod_id);
+    let mut configs: Vec<(String, &str)> = Vec::new();
+    for (_, metadata) in LOADER_METADATA {
+        let Ok(content) = std::fs::read_to_string(resources.join(metadata)) else { continue };
+        for name in mixin_configs(metadata, &content) {
+            if !configs.iter().any(|(n, _)| *n == name) {
+                configs.push((name, metadata));
+            }
+        }
+    }
+
+    for (name, metadata) in configs {
+        let path = resources.join(&name);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            // The managed mixins.json is already reported as a missing file
+            if name != managed {
+                issues.push(Issue::manual(
+                    "mixin_config",
+                    format!("{metadata} references {name}, which doesn't exist"),
+                    "Create the mixin config or remove it from the metadata",
+                ));
+            }
+            continue;
+        };
+        let classes = match mixin_classes(&content) {
+            Ok(classes) => classes,
+            Err(e) => {
+                issues.push(Issue::manual("mixin_config", format!("{name} is not valid JSON"), &e.to_string()));
+                continue;
+            }
+        };
+        for (listed, class) in classes {
+            if !mixin_class_exists(dir, &class) {
+                issues.push(Issue::fixable(
+                    "mixin_class",
+                    format!("{name} lists {class}, which has no source file"),
+                    Fix::RemoveMixin { path: path.clone(), class: listed },
+                ));
+            }
+        }
+    }
+}
+
+/// Mixin config names referenced by a loader metadata file: the `mixins`
+/// field of fabric.mod.json, `mixin` of quilt.mod.json (a string, strings, or
+/// `{"config": ...}` objects), or the `[[mixins]]` tables of a mods.toml.
+pub fn mixin_configs(metadata: &str, content: &str) -> Vec<String> {
+    if metadata.ends_with(".toml") {
+        let Ok(table) = toml::from_str::<toml::Table>(content) else {
+            return Vec::new();
+        };
+        return table
+            .get("mixins")
+            .and_then(|m| m.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|m| m.get("config")?.as_str().map(str::to_string))
+            .collect();
+    }
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let field = if metadata == "quilt.mod.json" { "mixin" } else { "mixins" };
+    let items = match &value[field] {
+        serde_json::Value::Array(items) => items.clone(),
+        other => vec![other.clone()],
+    };
+    items
+        .iter()
+        .filter_map(|item| item.as_str().or_else(|| item["config"].as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Classes listed in a mixin config's `mixins`, `client` and `server`
+/// arrays, as (entry as written, fully qualified name).
+pub fn mixin_classes(content: &str) -> Result<Vec<(String, String)>> {
+    use crate::json_merge::Json;
+    let Json::Object(entries) = Json::parse(content)? else {
+        return Ok(Vec::new());
+    };
+    let field = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let Some(Json::String(package)) = field("package") else {
+        return Ok(Vec::new());
+    };
+    let mut classes = Vec::new();
+    for list in ["mixins", "client", "server"] {
+        let Some(Json::Array(items)) = field(list) else { continue };
+        classes.extend(items.iter().filter_map(|c| match c {
+            Json::String(class) => Some((class.clone(), format!("{package}.{class}"))),
+            _ => None,
+        }));
+    }
+    Ok(classes)
+}
+
+fn mixin_class_exists(dir: &Path, class: &str) -> bool {
+    let rel = class.replace('.', "/");
+    ["main", "client"].iter().any(|set| {
+        dir.join(format!("src/{set}/java/{rel}.java")).exists()
+            || dir.join(format!("src/{set}/kotlin/{rel}.kt")).exists()
+    })
+}
+
+/// Directories never scanned for template leftovers: build output, VCS, IDE
+/// state, and the dev run directory.
+const SKIP_DIRS: &[&str] = &["build", ".gradle", ".git", ".idea", "run", "out", "node_modules"];
+/// File types mcmod generates from templates.
+const TEMPLATE_EXTENSIONS: &[&str] = &["kts", "gradle", "properties", "java", "kt", "json", "toml", "yml", "yaml", "mcmeta"];
+
+/// Unreplaced `{{placeholders}}` and `[VERSIONED]` markers in generated files.
+/// Placeholders whose values are known from mcmod.toml are fixable in place.
+fn check_leftovers(dir: &Path, config: &McmodConfig, issues: &mut Vec<Issue>) {
+    let vars = template::build_common_vars(config);
+    let mut files = Vec::new();
+    collect_template_files(dir, &mut files);
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let rel = path.strip_prefix(dir).unwrap_or(&path).display().to_string();
+
+        let placeholders = template::unresolved_placeholders(&content);
+        if !placeholders.is_empty() {
+            let names = placeholders.iter().map(|p| format!("{{{{{p}}}}}")).collect::<Vec<_>>().join(", ");
+            let message = format!("{rel} has unreplaced placeholders: {names}");
+            if placeholders.iter().all(|p| vars.contains_key(p)) {
+                let mut fixed = content.clone();
+                for name in &placeholders {
+                    fixed = fixed.replace(&format!("{{{{{name}}}}}"), &vars[name]);
+                }
+                issues.push(Issue::fixable(
+                    "placeholder",
+                    message,
+                    Fix::Write { path: path.clone(), content: fixed.into_bytes() },
+                ));
+            } else {
+                issues.push(Issue::manual("placeholder", message, "Replace them with real values by hand"));
+            }
+        }
+
+        let markers: Vec<String> = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("[VERSIONED]"))
+            .map(|(i, _)| (i + 1).to_string())
+            .collect();
+        if !markers.is_empty() {
+            issues.push(Issue::manual(
+                "versioned_marker",
+                format!("{rel} has [VERSIONED] markers on line(s) {}", markers.join(", ")),
+                "Move the per-version value into versions/dependencies/<mc>.properties or a Stonecutter comment, then delete the marker",
+            ));
+        }
+    }
+}
+
+fn collect_template_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if path.is_dir() {
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                collect_template_files(&path, files);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|e| TEMPLATE_EXTENSIONS.contains(&e.to_string_lossy().as_ref()))
+        {
+            files.push(path);
+        }
+    }
+}
+
+/// Parse `mc("1.21.1", "fabric", "neoforge")` lines into (version, loaders).
+pub fn parse_mc_entries(settings: &str) -> Vec<(String, Vec<String>)> {
+    settings
+        .lines()
+        .filter_map(|line| {
+            let args = line.trim().strip_prefix("mc(")?.strip_suffix(')')?;
+            let mut parts = args
+                .split(',')
+                .map(|p| p.trim().trim_matches('"').to_string());
+            let version = parts.next()?;
+            Some((version, parts.collect()))
+        })
+        .collect()
+}
+
+/// Files mcmod generates and can recreate from mcmod.toml, with their expected
+/// content. User-owned files (LICENSE, README, MODPAGE) are not included.
+fn managed_files(dir: &Path, config: &McmodConfig) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let vars = template::build_common_vars(config);
+    let files = if config.is_pack() {
+        standalone_files(crate::pack_project::managed_files(config, &vars)?)
+    } else if config.is_plugin() {
+        standalone_files(crate::plugin_project::managed_files(config, &vars)?)
+    } else {
+        mod_files(config, &vars)?
+    };
+    files
+        .into_iter()
+        .map(|(rel, content)| Ok((sandbox::resolve_output(dir, &rel)?, content)))
+        .collect()
+}
+
+/// Text files from a pack or plugin project, plus the wrapper jar they share.
+fn standalone_files(text_files: Vec<(String, String)>) -> Vec<(String, Vec<u8>)> {
+    let mut files: Vec<(String, Vec<u8>)> = text_files
+        .into_iter()
+        .map(|(rel, content)| (rel, content.into_bytes()))
+        .collect();
+    files.push((
+        "gradle/wrapper/gradle-wrapper.jar".to_string(),
+        template::GRADLE_WRAPPER_JAR.to_vec(),
+    ));
+    files
+}
+
+fn mod_files(config: &McmodConfig, vars: &HashMap<String, String>) -> Result<Vec<(String, Vec<u8>)>> {
+    let mod_id = &config.mod_info.mod_id;
+    let package_path = vars.get("package_path").cloned().unwrap_or_default();
+    let text = |s: String| s.into_bytes();
+
+    let mut files = vec![
+        ("stonecutter.gradle.kts".to_string(), text(render(template::SC_STONECUTTER_GRADLE, vars)?)),
+        ("settings.gradle.kts".to_string(), text(template::render_settings_gradle(config, vars)?)),
+        ("build.gradle.kts".to_string(), text(template::render_build_gradle(config, vars)?)),
+        ("gradle.properties".to_string(), text(render(template::SC_GRADLE_PROPERTIES, vars)?)),
+        (".gitignore".to_string(), crate::template_pack::resolve(template::TMPL_GITIGNORE).as_bytes().to_vec()),
+        ("gradlew".to_string(), template::GRADLEW.as_bytes().to_vec()),
+        ("gradlew.bat".to_string(), template::GRADLEW_BAT.as_bytes().to_vec()),
+        ("gradle/wrapper/gradle-wrapper.jar".to_string(), template::GRADLE_WRAPPER_JAR.to_vec()),
+        (
+            "gradle/wrapper/gradle-wrapper.properties".to_string(),
+            template::GRADLE_WRAPPER_PROPS.as_bytes().to_vec(),
+        ),
+        (
+            format!("src/main/resources/{mod_id}.mixins.json"),
+            text(render(template::TMPL_FABRIC_MIXINS_JSON, vars)?),
+        ),
+        (
+            format!("src/main/java/{package_path}/mixin/package-info.java"),
+            text(render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, vars)?),
+        ),
+    ];
+
+    let (source, source_path) = template::render_unified_mod(config, vars)?;
+    files.push((source_path, text(source)));
+
+    if config.loaders.fabric {
+        files.push((
+            "src/main/resources/fabric.mod.json".to_string(),
+            text(template::render_optional(template::SC_FABRIC_MOD_JSON, vars)?),
+        ));
+    }
+    if config.loaders.neoforge {
+        files.push((
+            "src/main/resources/META-INF/neoforge.mods.toml".to_string(),
+            text(template::render_optional(template::SC_NEOFORGE_MODS_TOML, vars)?),
+        ));
+    }
+    if config.loaders.forge {
+        files.push((
+            "src/main/resources/META-INF/mods.toml".to_string(),
+            text(template::render_optional(template::SC_FORGE_MODS_TOML, vars)?),
+        ));
+    }
+    if config.loaders.quilt {
+        files.push((
+            "src/main/resources/quilt.mod.json".to_string(),
+            text(template::render_optional(template::SC_QUILT_MOD_JSON, vars)?),
+        ));
+    }
+    for target in &config.versions.targets {
+        files.push((
+            format!("versions/dependencies/{}.properties", target.minecraft),
+            text(template::render_version_properties(target)?),
+        ));
+    }
+    if config.features.ci {
+        files.push((
+            ".github/workflows/build.yml".to_string(),
+            text(render(template::TMPL_CI_BUILD_YML, vars)?),
+        ));
+    }
+    if config.loaders.sponge {
+        for (rel, content) in crate::plugin_project::sponge_module_files(config, vars)? {
+            let path = format!("{}/{rel}", crate::plugin_project::SPONGE_MODULE_DIR);
+            files.push((path, text(content)));
+        }
+    }
+    if config.features.velocity {
+        for (rel, content) in crate::plugin_project::velocity_module_files(config, vars)? {
+            let path = format!("{}/{rel}", crate::plugin_project::VELOCITY_MODULE_DIR);
+            files.push((path, text(content)));
+        }
+    }
+    if config.features.spigot {
+        for (rel, content) in crate::plugin_project::spigot_module_files(config, vars)? {
+            let path = format!("{}/{rel}", crate::plugin_project::SPIGOT_MODULE_DIR);
+            files.push((path, text(content)));
+        }
+    }
+    if config.features.paper {
+        for (rel, content) in crate::plugin_project::paper_module_files(config, vars)? {
+            let path = format!("{}/{rel}", crate::plugin_project::PAPER_MODULE_DIR);
+            files.push((path, text(content)));
+        }
+    }
+
+    Ok(files)
+}
+
+/// Apply `fix`. In-place edits are shown as diffs to confirm, except when
+/// `quiet` (`--json`), where nothing may be printed or asked.
+fn apply_fix(dir: &Path, fix: &Fix, quiet: bool) -> Result<()> {
+    let edit = |path: &Path, old: &str, new: &str| -> Result<()> {
+        if quiet {
+            diff::apply_edit_quietly(path, old, new)?;
+        } else {
+            diff::apply_edit(path, old, new)?;
+        }
+        Ok(())
+    };
+    match fix {
+        Fix::Write { path, content } => match std::str::from_utf8(content) {
+            Ok(text) => write_file(path, text),
+            Err(_) => write_binary(path, content),
+        },
+        Fix::AddLoader(loader) => {
+            let path = dir.join("settings.gradle.kts");
+            let content = crate::fs::read_to_string(&path)?;
+            let mut updated = gradle::add_loader_to_settings_content(&content, loader);
+            if *loader == "quilt" {
+                updated = gradle::add_plugin_repository_content(&updated, gradle::QUILT_MAVEN);
+            }
+            edit(&path, &content, &updated)
+        }
+        Fix::Include(module) => {
+            let path = dir.join("settings.gradle.kts");
+            let content = crate::fs::read_to_string(&path)?;
+            edit(&path, &content, &gradle::add_include_content(&content, module))
+        }
+        Fix::SetProperty { key, value } => {
+            let path = dir.join("gradle.properties");
+            let content = crate::fs::read_to_string(&path)?;
+            edit(&path, &content, &gradle::set_property_content(&content, key, value))
+        }
+        Fix::RemoveMixin { path, class } => {
+            use crate::json_merge::Json;
+            let content = crate::fs::read_to_string(path)?;
+            let mut json = Json::parse(&content)?;
+            if let Json::Object(entries) = &mut json {
+                for (key, value) in entries.iter_mut() {
+                    if let (true, Json::Array(classes)) = (["mixins", "client", "server"].contains(&key.as_str()), value) {
+                        classes.retain(|c| *c != Json::String(class.clone()));
+                    }
+                }
+            }
+            edit(path, &content, &json.to_pretty_string()?)
+        }
+        Fix::MakeExecutable(path) => {
+            let content = std::fs::read(path)?;
+            let policy = FilePolicy::for_path(path).with_executable(true);
+            crate::util::write_binary_with(path, &content, &policy)
+        }
+    }
+}
+
+fn print_report(issues: &[Issue], fix: bool) {
+    println!("{}", "\n  mcmod doctor\n".bold().cyan());
+    if issues.is_empty() {
+        println!("{}", "  No problems found".green());
+        return;
+    }
+
+    for issue in issues {
+        let (marker, label) = match issue.status {
+            Status::Fixed => ("✓".green(), "fixed".green()),
+            Status::Fixable => ("!".yellow(), "fixable".yellow()),
+            Status::Skipped => ("-".dimmed(), "skipped".dimmed()),
+            Status::Manual => ("✗".red(), "manual".red()),
+        };
+        println!("  {marker} {} [{label}]", issue.message);
+        if let Some(detail) = &issue.detail {
+            println!("      {}", detail.dimmed());
+        }
+    }
+
+    let count = |s: Status| issues.iter().filter(|i| i.status == s).count();
+    println!();
+    println!(
+        "  {} fixed, {} fixable, {} skipped, {} need manual action",
+        count(Status::Fixed),
+        count(Status::Fixable),
+        count(Status::Skipped),
+        count(Status::Manual)
+    );
+    if !fix && count(Status::Fixable) > 0 {
+        println!("{}", "  Run `mcmod doctor --fix` to apply automatic fixes".cyan());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: &str = "stonecutter {\n    create(rootProject) {\n        mc(\"1.21.1\", \"fabric\")\n        mc(\"1.21.7\", \"fabric\")\n    }\n}\n";
+
+    fn config(fabric: bool, neoforge: bool) -> McmodConfig {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            fabric,
+            neoforge,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        config.versions.targets[0].minecraft = "1.21.1".to_string();
+        config
+    }
+
+    #[test]
+    fn test_parse_mc_entries() {
+        let entries = parse_mc_entries(SETTINGS);
+        assert_eq!(
+            entries,
+            vec![
+                ("1.21.1".to_string(), vec!["fabric".to_string()]),
+                ("1.21.7".to_string(), vec!["fabric".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_settings_loader_drift() {
+        let mut issues = Vec::new();
+        check_settings(SETTINGS, &config(true, true), &mut issues);
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(issues[0].fix, Some(Fix::AddLoader("neoforge"))));
+
+        let mut issues = Vec::new();
+        check_settings(SETTINGS, &config(false, true), &mut issues);
+        let manual: Vec<_> = issues.iter().filter(|i| i.status == Status::Manual).collect();
+        assert_eq!(manual.len(), 1, "fabric in settings but disabled in config");
+    }
+
+    #[test]
+    fn test_check_and_fix_missing_files() {
+        let dir = std::env::temp_dir().join(format!("mcmod_doctor_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = config(true, false);
+        config.save(&dir).unwrap();
+
+        let issues = check(&dir, &config).unwrap();
+        assert!(issues.iter().any(|i| i.message == "gradlew is missing"));
+        assert!(issues.iter().all(|i| i.status == Status::Fixable));
+
+        for issue in issues {
+            apply_fix(&dir, issue.fix.as_ref().unwrap(), false).unwrap();
+        }
+        assert!(check(&dir, &config).unwrap().is_empty());
+        assert!(dir.join("src/main/resources/fabric.mod.json").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_check_and_fix_project_drift() {
+        let dir = std::env::temp_dir().join(format!("mcmod_doctor_drift_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut config = config(true, false);
+        config.save(&dir).unwrap();
+        for issue in check(&dir, &config).unwrap() {
+            apply_fix(&dir, issue.fix.as_ref().unwrap(), false).unwrap();
+        }
+
+        config.features.paper = true;
+        std::fs::write(dir.join("gradle.properties"), "enabled_platforms=fabric,forge\n").unwrap();
+        let mixins = dir.join("src/main/resources/testmod.mixins.json");
+        let json = std::fs::read_to_string(&mixins).unwrap();
+        let json = json.replacen("\"mixins\": []", "\"mixins\": [\"GoneMixin\"]", 1);
+        std::fs::write(&mixins, json).unwrap();
+        std::fs::write(dir.join("notes.properties"), "id={{mod_id}}\nversion=1.0 # [VERSIONED]\n").unwrap();
+        std::fs::write(dir.join("extra.json"), "{\"x\": \"{{unknown}}\"}").unwrap();
+
+        let issues = check(&dir, &config).unwrap();
+        let ids: Vec<_> = issues.iter().map(|i| (i.id, i.status == Status::Fixable)).collect();
+        for expected in [
+            ("settings_include", true),
+            ("enabled_platforms", true),
+            ("mixin_class", true),
+            ("placeholder", true),
+            ("placeholder", false),
+            ("versioned_marker", false),
+        ] {
+            assert!(ids.contains(&expected), "{expected:?} not in {ids:?}");
+        }
+
+        for fix in issues.iter().filter_map(|i| i.fix.as_ref()) {
+            if !matches!(fix, Fix::Write { .. }) || matches!(fix, Fix::Write { path, .. } if path.ends_with("notes.properties")) {
+                apply_fix(&dir, fix, false).unwrap();
+            }
+        }
+        assert!(std::fs::read_to_string(dir.join("settings.gradle.kts")).unwrap().contains("include(\"paper\")"));
+        assert!(std::fs::read_to_string(dir.join("gradle.properties")).unwrap().contains("enabled_platforms=fabric\n"));
+        assert!(!std::fs::read_to_string(&mixins).unwrap().contains("GoneMixin"));
+        assert!(std::fs::read_to_string(dir.join("notes.properties")).unwrap().starts_with("id=testmod\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fix_json_output() {
+        // `run` prints to the process's stdout, so the test reruns itself in a
+        // child (selected by the variable) and parses what that child printed
+        const CHILD_DIR: &str = "MCMOD_DOCTOR_JSON_DIR";
+        if let Ok(dir) = std::env::var(CHILD_DIR) {
+            let _ = run(Path::new(&dir), true, true);
+            return;
+        }
+
+        let dir = std::env::temp_dir().join(format!("mcmod_doctor_json_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut config = config(true, false);
+        config.save(&dir).unwrap();
+        for issue in check(&dir, &config).unwrap() {
+            apply_fix(&dir, issue.fix.as_ref().unwrap(), true).unwrap();
+        }
+        config.features.paper = true;
+        config.save(&dir).unwrap();
+        std::fs::write(dir.join("gradle.properties"), "enabled_platforms=fabric,forge\n").unwrap();
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "commands::doctor::tests::test_fix_json_output", "--nocapture", "--quiet", "--test-threads=1"])
+            .env(CHILD_DIR, &dir)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        // Drop the test harness's own lines; everything else is doctor's
+        let printed: String = stdout
+            .lines()
+            .filter(|l| !l.is_empty() && *l != "running 1 test" && *l != "." && !l.starts_with("test result:"))
+            .map(|l| format!("{l}\n"))
+            .collect();
+        let report: serde_json::Value = serde_json::from_str(&printed).unwrap_or_else(|e| panic!("{e}:\n{printed}"));
+        let issues = report["issues"].as_array().unwrap();
+        assert!(issues.iter().any(|i| i["id"] == "settings_include" && i["status"] == "fixed"), "{printed}");
+        assert!(issues.iter().any(|i| i["id"] == "enabled_platforms" && i["status"] == "fixed"), "{printed}");
+        assert!(std::fs::read_to_string(dir.join("gradle.properties")).unwrap().contains("enabled_platforms=fabric\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
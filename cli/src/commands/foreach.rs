@@ -0,0 +1,194 @@
+use crate::config::McmodConfig;
+use crate::error::{McmodError, Result};
+use clap::Command;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Directories never searched for projects: build output and the dev run
+/// directory. Hidden directories (`.git`, `.gradle`) are skipped as well.
+const SKIP_DIRS: &[&str] = &["build", "run", "target", "node_modules", "out", "bin"];
+
+/// Outcome of running the command in one project.
+struct ProjectResult {
+    dir: PathBuf,
+    success: bool,
+    elapsed: Duration,
+}
+
+/// Run `mcmod <command...> --dir <project>` in every mcmod project under `root`.
+pub fn run(cli: &Command, root: &Path, command: &[String], fail_fast: bool) -> Result<()> {
+    validate_command(cli, command)?;
+
+    let projects = discover_projects(root)?;
+    if projects.is_empty() {
+        return Err(McmodError::Other(format!(
+            "No mcmod projects (mcmod.toml) found under {}",
+            root.display()
+        )));
+    }
+
+    let exe = std::env::current_exe()?;
+    let command_line = command.join(" ");
+    println!(
+        "{}",
+        format!("  Running `mcmod {command_line}` in {} projects", projects.len()).cyan()
+    );
+
+    let mut results = Vec::new();
+    for dir in projects {
+        println!();
+        println!("{}", format!("  ── {} ──", dir.display()).bold());
+
+        let start = Instant::now();
+        let status = std::process::Command::new(&exe)
+            .args(command)
+            .arg("--dir")
+            .arg(&dir)
+            .status()?;
+        let success = status.success();
+        results.push(ProjectResult {
+            dir,
+            success,
+            elapsed: start.elapsed(),
+        });
+
+        if !success && fail_fast {
+            break;
+        }
+    }
+
+    println!();
+    print_summary(root, &results);
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    if failed > 0 {
+        return Err(McmodError::Other(format!(
+            "{failed} of {} projects failed",
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Only subcommands that operate on a project (they take `--dir`) can be fanned out.
+fn validate_command(cli: &Command, command: &[String]) -> Result<()> {
+    let Some(name) = command.first() else {
+        return Err(McmodError::Other("No command given to run".to_string()));
+    };
+
+    let project_commands: Vec<&str> = cli
+        .get_subcommands()
+        .filter(|s| !s.is_hide_set() && s.get_name() != "init" && s.get_name() != "foreach")
+        .filter(|s| s.get_arguments().any(|a| a.get_id() == "dir"))
+        .map(|s| s.get_name())
+        .collect();
+
+    if !project_commands.contains(&name.as_str()) {
+        return Err(McmodError::Other(format!(
+            "`mcmod foreach` can't run '{name}'. Commands that work per project: {}",
+            project_commands.join(", ")
+        )));
+    }
+    if command.iter().any(|a| a == "--dir" || a.starts_with("--dir=")) {
+        return Err(McmodError::Other(
+            "Don't pass --dir to the command; foreach sets it for each project".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Find every directory under `root` containing mcmod.toml, sorted by path.
+/// Hidden directories and build output are skipped, and the search does not
+/// descend into a project once one is found.
+fn discover_projects(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        if McmodConfig::config_path(&dir).is_file() {
+            found.push(dir);
+            continue;
+        }
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // Unreadable directories are skipped rather than failing the whole run
+            Err(_) if dir != root => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            // file_type() doesn't follow symlinks, so linked directories aren't searched
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_ref()) {
+                stack.push(entry.path());
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+fn print_summary(root: &Path, results: &[ProjectResult]) {
+    let rows: Vec<(String, bool, String)> = results
+        .iter()
+        .map(|r| {
+            let name = r
+                .dir
+                .strip_prefix(root)
+                .ok()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(&r.dir)
+                .display()
+                .to_string();
+            (name, r.success, format!("{:.1}s", r.elapsed.as_secs_f64()))
+        })
+        .collect();
+    let width = rows.iter().map(|(n, _, _)| n.len()).max().unwrap_or(0).max(7);
+
+    println!("{}", "  Summary".bold());
+    println!("  {:<width$}  {:<6}  Time", "Project", "Result");
+    println!("  {}", "-".repeat(width + 16).dimmed());
+    for (name, success, time) in &rows {
+        let result = if *success { "ok".green() } else { "FAILED".red() };
+        println!("  {name:<width$}  {result:<6}  {time}");
+    }
+
+    let passed = results.iter().filter(|r| r.success).count();
+    println!();
+    println!("  {passed}/{} succeeded", results.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_discover_projects() {
+        let root = std::env::temp_dir().join(format!("mcmod_foreach_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for dir in ["alpha", "group/beta", "alpha/nested", ".hidden/gamma", "build/delta"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("mcmod.toml"), "").unwrap();
+        }
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+
+        let found = discover_projects(&root).unwrap();
+        assert_eq!(found, vec![root.join("alpha"), root.join("group/beta")]);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_validate_command() {
+        let cli = crate::Cli::command();
+        let args = |s: &str| s.split(' ').map(str::to_string).collect::<Vec<_>>();
+        assert!(validate_command(&cli, &args("add ci")).is_ok());
+        assert!(validate_command(&cli, &args("update")).is_err());
+        assert!(validate_command(&cli, &args("init")).is_err());
+        assert!(validate_command(&cli, &args("add ci --dir x")).is_err());
+        assert!(validate_command(&cli, &[]).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use super::{add_field, add_import, ClassName, Project};
+use crate::commands::assets;
+use crate::content::{self, ContentManifest, MANIFEST_FILE};
+use crate::error::{McmodError, Result};
+use crate::naming::{self, ContentKind, ContentName};
+use crate::template;
+use colored::Colorize;
+use std::path::Path;
+
+/// The class blocks and their block items are registered in, below the mod's package.
+const REGISTRY_CLASS: &str = "registry.ModBlocks";
+/// Subpackage of the mod's package that generated block classes go in.
+const BLOCK_PACKAGE: &str = "block";
+/// Block and item properties need the registry key from this release on.
+const PROPERTIES_ID_SINCE: &str = "1.21.2";
+/// ResourceLocation is called Identifier from this release on.
+const IDENTIFIER_SINCE: &str = "1.21.11";
+
+/// `mcmod gen block <name>`: write a `Block` subclass (`block.RubyBlock`),
+/// register it and its `BlockItem` in `registry.ModBlocks` (created, and
+/// called from the mod's `init()`, on first use), list it in content.toml,
+/// and generate its translation, blockstate and models, a loot table that
+/// drops the block, and a placeholder texture. `name` is a registry id
+/// (`ruby_block`) or a display name ("Block of Ruby"); `display` overrides
+/// the English name.
+pub fn run(dir: &Path, name: &str, display: Option<&str>) -> Result<()> {
+    println!("{}", format!("\n  mcmod gen block {name}\n").bold().cyan());
+    let project = Project::load(dir)?;
+    let (block, display) = block_name(&project, name, display)?;
+    let class = block_class(&project, &block)?;
+    project.write_class(&class, template::GEN_BLOCK_JAVA, template::GEN_BLOCK_KT, &[("gen_id", block.id.clone())])?;
+    add_block(&project, &block, display, &class)?;
+    println!("{}", format!("  {}:{} generated!", project.config.mod_info.mod_id, block.id).bold().green());
+    Ok(())
+}
+
+/// The block `name` (a registry id or a display name) stands for, and its
+/// English name when it differs from the id's. Fails when content.toml
+/// already lists it.
+pub(super) fn block_name<'a>(
+    project: &Project,
+    name: &'a str,
+    display: Option<&'a str>,
+) -> Result<(ContentName, Option<&'a str>)> {
+    let mod_id = &project.config.mod_info.mod_id;
+    let (block, display) = if naming::is_valid_id(name) {
+        (ContentName::from_id(name, mod_id, ContentKind::Block), display)
+    } else {
+        (ContentName::suggest(name, mod_id, ContentKind::Block)?, display.or(Some(name)))
+    };
+    if let Some(manifest) = ContentManifest::load(&project.dir)? {
+        if manifest.items.iter().chain(&manifest.blocks).any(|e| e.id == block.id) {
+            return Err(McmodError::Other(format!("'{}' is already listed in {MANIFEST_FILE}", block.id)));
+        }
+    }
+    Ok((block, display))
+}
+
+/// The block's class, `block.<ClassName>`.
+pub(super) fn block_class(project: &Project, block: &ContentName) -> Result<ClassName> {
+    ClassName::parse(&project.config.mod_info.package, &format!("{BLOCK_PACKAGE}.{}", block.class_name))
+}
+
+/// Everything `gen block` does once the block's class is written: register
+/// it, list it in content.toml, and generate its assets, loot table and
+/// texture.
+pub(super) fn add_block(project: &Project, block: &ContentName, display: Option<&str>, class: &ClassName) -> Result<()> {
+    let dir = &project.dir;
+    let mod_id = &project.config.mod_info.mod_id;
+    register_block(project, &block.id, class)?;
+    content::add_entry(dir, ContentKind::Block, &block.id, display)?;
+    crate::util::progress(format!("  Added {} to {MANIFEST_FILE}", block.id).green());
+    if let Some(manifest) = ContentManifest::load(dir)? {
+        for rel in content::generate(dir, &project.config, &manifest)? {
+            crate::util::progress(format!("  Generated assets/{mod_id}/{rel}").green());
+        }
+    }
+    if let Some(rel) = write_loot_table(project, &block.id)? {
+        crate::util::progress(format!("  Created {rel}").green());
+    }
+    if let Some(rel) = assets::stub_texture(dir, &project.config, &format!("block/{}", block.id))? {
+        crate::util::progress(format!("  Stubbed {rel}").green());
+    }
+    Ok(())
+}
+
+/// Add the block's field (and its class's import) to ModBlocks, creating the
+/// class first if needed.
+fn register_block(project: &Project, id: &str, block_class: &ClassName) -> Result<()> {
+    let class = ClassName::parse(&project.config.mod_info.package, REGISTRY_CLASS)?;
+    let kotlin = project.kotlin();
+    let field = field(id, &block_class.name, kotlin);
+    let path = project.source_path(&class);
+    let target = crate::sandbox::resolve_output(&project.dir, path.clone())?;
+    if !crate::fs::exists(&target) {
+        let extra = registry_vars(project, field, block_class);
+        project.write_class(&class, template::GEN_MOD_BLOCKS_JAVA, template::GEN_MOD_BLOCKS_KT, &extra)?;
+        project.register_init(&class)?;
+        return Ok(());
+    }
+
+    let content = crate::fs::read_to_string(&target)?;
+    if content.contains(&format!("register(\"{id}\"")) {
+        return Err(McmodError::Other(format!("{path} already registers '{id}'")));
+    }
+    let updated = add_field(&content, &field)
+        .and_then(|updated| add_import(&updated, &block_class.qualified(), kotlin))
+        .ok_or_else(|| {
+            McmodError::Other(format!(
+                "Couldn't find where {path} registers its blocks; add this line to it by hand:\n{}",
+                field.trim()
+            ))
+        })?;
+    crate::util::write_file(&target, &updated)?;
+    crate::util::progress(format!("  Registered {id} in {path}").green());
+    Ok(())
+}
+
+/// The ModBlocks field registering `id`, e.g.
+/// `RUBY_BLOCK = register("ruby_block", RubyBlock::new)`.
+fn field(id: &str, class: &str, kotlin: bool) -> String {
+    let name = id.to_ascii_uppercase();
+    if kotlin {
+        format!("    val {name}: Supplier<{class}> = register(\"{id}\", ::{class})")
+    } else {
+        format!("    public static final Supplier<{class}> {name} = register(\"{id}\", {class}::new);")
+    }
+}
+
+/// Template variables for a new ModBlocks: its first field and the import of
+/// that block's class, and the lines that depend on the Minecraft version.
+fn registry_vars(project: &Project, field: String, block_class: &ClassName) -> Vec<(&'static str, String)> {
+    let (semicolon, new) = if project.kotlin() { ("", "") } else { (";", "new ") };
+    vec![
+        ("gen_field", field),
+        ("gen_block_import", format!("import {}{semicolon}", block_class.qualified())),
+        ("gen_identifier_import", identifier_import(project)),
+        ("gen_block_key", registry_key(project, "BLOCK")),
+        ("gen_item_key", registry_key(project, "ITEM")),
+        (
+            "gen_block_properties",
+            project.version_switch(
+                PROPERTIES_ID_SINCE,
+                "        ",
+                &format!("return BlockBehaviour.Properties.of().setId(key).strength(1.5f, 6.0f){semicolon}"),
+                &format!("return BlockBehaviour.Properties.of().strength(1.5f, 6.0f){semicolon}"),
+            ),
+        ),
+        (
+            "gen_item_properties",
+            project.version_switch(
+                PROPERTIES_ID_SINCE,
+                "        ",
+                &format!("return {new}Item.Properties().setId(key).useBlockDescriptionPrefix(){semicolon}"),
+                &format!("return {new}Item.Properties(){semicolon}"),
+            ),
+        ),
+    ]
+}
+
+/// The import of `ResourceLocation`, or `Identifier` where it's called that.
+pub(super) fn identifier_import(project: &Project) -> String {
+    let semicolon = if project.kotlin() { "" } else { ";" };
+    project.version_switch(
+        IDENTIFIER_SINCE,
+        "",
+        &format!("import net.minecraft.resources.Identifier{semicolon}"),
+        &format!("import net.minecraft.resources.ResourceLocation{semicolon}"),
+    )
+}
+
+/// The body of a method returning the `Registries.<registry>` key for `id`
+/// in the mod's namespace.
+pub(super) fn registry_key(project: &Project, registry: &str) -> String {
+    let class_name = &project.vars["class_name"];
+    let semicolon = if project.kotlin() { "" } else { ";" };
+    let key = |id_class: &str| {
+        format!(
+            "return ResourceKey.create(Registries.{registry}, {id_class}.fromNamespaceAndPath({class_name}.MOD_ID, id)){semicolon}"
+        )
+    };
+    project.version_switch(IDENTIFIER_SINCE, "        ", &key("Identifier"), &key("ResourceLocation"))
+}
+
+/// Write `data/<mod_id>/loot_table/blocks/<id>.json`, dropping the block
+/// when it's mined and survives the explosion, unless it exists. Returns
+/// the created path relative to the project.
+fn write_loot_table(project: &Project, id: &str) -> Result<Option<String>> {
+    let mod_id = &project.config.mod_info.mod_id;
+    let rel = format!("src/main/resources/data/{mod_id}/loot_table/blocks/{id}.json");
+    let path = crate::sandbox::resolve_output(&project.dir, rel.clone())?;
+    if crate::fs::exists(&path) {
+        return Ok(None);
+    }
+    crate::util::write_file(&path, &loot_table(mod_id, id))?;
+    Ok(Some(rel))
+}
+
+fn loot_table(mod_id: &str, id: &str) -> String {
+    format!(
+        r#"{{
+  "type": "minecraft:block",
+  "pools": [
+    {{
+      "rolls": 1,
+      "entries": [
+        {{
+          "type": "minecraft:item",
+          "name": "{mod_id}:{id}"
+        }}
+      ],
+      "conditions": [
+        {{
+          "condition": "minecraft:survives_explosion"
+        }}
+      ]
+    }}
+  ],
+  "random_sequence": "{mod_id}:blocks/{id}"
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_gen_block() {
+        let dir = Path::new("/memory/genblock");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(dir, "ruby_block", None)?;
+            run(dir, "Ruby Ore", None)?;
+            run(dir, "ruby_block", None)
+        });
+        assert!(result.unwrap_err().to_string().contains("already listed"));
+
+        let java = dir.join("src/main/java/com/example/testmod");
+        let blocks = fs.read_string(&java.join("registry/ModBlocks.java")).unwrap();
+        assert!(blocks.starts_with(
+            "package com.example.testmod.registry;\n\nimport com.example.testmod.TestmodMod;\nimport com.example.testmod.block.RubyBlock;\nimport com.example.testmod.block.RubyOreBlock;\nimport java.util.function.Function;\n"
+        ));
+        assert!(blocks.contains(
+            "    public static final Supplier<RubyBlock> RUBY_BLOCK = register(\"ruby_block\", RubyBlock::new);\n    public static final Supplier<RubyOreBlock> RUBY_ORE = register(\"ruby_ore\", RubyOreBlock::new);\n"
+        ));
+        assert!(blocks.contains("        return BlockBehaviour.Properties.of().setId(key).strength(1.5f, 6.0f);\n"));
+        assert!(blocks.contains("        return new Item.Properties().setId(key).useBlockDescriptionPrefix();\n"));
+        assert!(blocks.contains("Registries.BLOCK, ResourceLocation.fromNamespaceAndPath(TestmodMod.MOD_ID, id));\n"));
+        assert!(!blocks.contains("{{") && !blocks.contains("elif forge"));
+        let block = fs.read_string(&java.join("block/RubyOreBlock.java")).unwrap();
+        assert!(block.starts_with("package com.example.testmod.block;\n"));
+        assert!(block.contains("public class RubyOreBlock extends Block {"));
+        assert!(block.contains("{@code testmod:ruby_ore}"));
+        let main = fs.read_string(&java.join("TestmodMod.java")).unwrap();
+        assert!(main.contains("import com.example.testmod.registry.ModBlocks;\n"));
+        assert!(main.contains("        ModBlocks.init();\n"));
+
+        assert_eq!(
+            fs.read_string(&dir.join(MANIFEST_FILE)).unwrap(),
+            "[[blocks]]\nid = \"ruby_block\"\n\n[[blocks]]\nid = \"ruby_ore\"\nname = \"Ruby Ore\"\n"
+        );
+        let assets = dir.join("src/main/resources/assets/testmod");
+        let lang = fs.read_string(&assets.join("lang/en_us.json")).unwrap();
+        assert!(lang.contains("\"block.testmod.ruby_block\": \"Ruby Block\""));
+        assert!(lang.contains("\"block.testmod.ruby_ore\": \"Ruby Ore\""));
+        assert!(fs.read_string(&assets.join("blockstates/ruby_ore.json")).unwrap().contains("testmod:block/ruby_ore"));
+        assert!(fs.read_string(&assets.join("models/block/ruby_ore.json")).is_some());
+        assert!(fs.read_string(&assets.join("models/item/ruby_ore.json")).is_some());
+        assert!(fs.read_string(&assets.join("textures/block/ruby_block.png")).is_some());
+        let loot = fs.read_string(&dir.join("src/main/resources/data/testmod/loot_table/blocks/ruby_ore.json")).unwrap();
+        assert!(loot.contains("\"name\": \"testmod:ruby_ore\""));
+    }
+
+    #[test]
+    fn test_gen_block_kotlin() {
+        let dir = Path::new("/memory/genblockkt");
+        let (result, fs) = crate::fs::in_memory(|| {
+            let mut options = init::tests::options(dir, &["neoforge"]);
+            options.language = Some("kotlin".to_string());
+            options.minecraft_versions = vec!["1.21.1".to_string()];
+            init::run(options)?;
+            run(dir, "ruby_block", Some("Block of Ruby"))
+        });
+        result.unwrap();
+        let kotlin = dir.join("src/main/kotlin/com/example/testmod");
+        let blocks = fs.read_string(&kotlin.join("registry/ModBlocks.kt")).unwrap();
+        assert!(blocks.contains("object ModBlocks {"));
+        assert!(blocks.contains("import com.example.testmod.block.RubyBlock\n"));
+        assert!(blocks.contains("    val RUBY_BLOCK: Supplier<RubyBlock> = register(\"ruby_block\", ::RubyBlock)\n"));
+        assert!(blocks.contains("        return Item.Properties()\n"));
+        assert!(!blocks.contains("setId"));
+        let block = fs.read_string(&kotlin.join("block/RubyBlock.kt")).unwrap();
+        assert!(block.contains("class RubyBlock(properties: BlockBehaviour.Properties) : Block(properties)"));
+        let lang = fs.read_string(&dir.join("src/main/resources/assets/testmod/lang/en_us.json")).unwrap();
+        assert!(lang.contains("\"block.testmod.ruby_block\": \"Block of Ruby\""));
+    }
+}
@@ -0,0 +1,467 @@
+use super::{add_field, add_import, add_init_call, block, ClassName, Project};
+use crate::error::{McmodError, Result};
+use crate::template;
+use colored::Colorize;
+use std::path::Path;
+
+/// The class block entity types are registered in, below the mod's package.
+const REGISTRY_CLASS: &str = "registry.ModBlockEntities";
+/// The client class that registers the block entities' renderers.
+const RENDERERS_CLASS: &str = "client.ModBlockEntityRenderers";
+/// Subpackages of the mod's package for block entity and renderer classes.
+const ENTITY_PACKAGE: &str = "block.entity";
+const CLIENT_PACKAGE: &str = "client";
+/// BlockEntityType takes its valid blocks as a set from this release on,
+/// instead of through its builder.
+const TYPE_CONSTRUCTOR_SINCE: &str = "1.21.2";
+/// Block entities save and load through ValueOutput/ValueInput instead of
+/// CompoundTag from this release on.
+const VALUE_IO_SINCE: &str = "1.21.6";
+/// Block entity renderers get the camera position from this release on...
+const RENDER_CAMERA_SINCE: &str = "1.21.5";
+/// ...and extract a render state and submit it from this one.
+const RENDER_STATE_SINCE: &str = "1.21.9";
+
+/// `mcmod gen block-entity <name>`: everything `gen block` does, with a
+/// block class that implements `EntityBlock`, plus its block entity
+/// (`block.entity.RubyBoxBlockEntity`) registered in
+/// `registry.ModBlockEntities`, and a renderer stub
+/// (`client.RubyBoxBlockEntityRenderer`) registered in
+/// `client.ModBlockEntityRenderers`: through the renderer event on NeoForge
+/// and Forge, and from the client entrypoint's `init()` on Fabric. Client
+/// classes go in the client source set when the project splits one off.
+pub fn run(dir: &Path, name: &str, display: Option<&str>) -> Result<()> {
+    println!("{}", format!("\n  mcmod gen block-entity {name}\n").bold().cyan());
+    let project = Project::load(dir)?;
+    let package = &project.config.mod_info.package;
+    let client_set = project.vars["client_source_set"].clone();
+    let (block, display) = block::block_name(&project, name, display)?;
+    let block_class = block::block_class(&project, &block)?;
+    let entity = ClassName::parse(package, &format!("{ENTITY_PACKAGE}.{}Entity", block_class.name))?;
+    let renderer = ClassName::parse(package, &format!("{CLIENT_PACKAGE}.{}EntityRenderer", block_class.name))?;
+    // Check every class up front so a clash doesn't leave half a block entity
+    for path in [
+        project.source_path(&block_class),
+        project.source_path(&entity),
+        project.source_path_in(&client_set, &renderer),
+    ] {
+        if crate::fs::exists(&crate::sandbox::resolve_output(dir, path.clone())?) {
+            return Err(McmodError::Other(format!("{path} already exists")));
+        }
+    }
+
+    let semicolon = if project.kotlin() { "" } else { ";" };
+    let entity_import = format!("import {}{semicolon}", entity.qualified());
+    project.write_class(
+        &block_class,
+        template::GEN_ENTITY_BLOCK_JAVA,
+        template::GEN_ENTITY_BLOCK_KT,
+        &[
+            ("gen_id", block.id.clone()),
+            ("gen_entity_import", entity_import.clone()),
+            ("gen_entity_class", entity.name.clone()),
+        ],
+    )?;
+    block::add_block(&project, &block, display, &block_class)?;
+    project.write_class(
+        &entity,
+        template::GEN_BLOCK_ENTITY_JAVA,
+        template::GEN_BLOCK_ENTITY_KT,
+        &[
+            ("gen_id", block.id.clone()),
+            ("gen_field_name", block.id.to_ascii_uppercase()),
+            ("gen_storage_imports", storage_imports(&project)),
+            ("gen_storage_methods", storage_methods(&project)),
+        ],
+    )?;
+    register_entity(&project, &block.id, &entity)?;
+    project.write_class_in(
+        &client_set,
+        &renderer,
+        template::GEN_BLOCK_ENTITY_RENDERER_JAVA,
+        template::GEN_BLOCK_ENTITY_RENDERER_KT,
+        &[
+            ("gen_id", block.id.clone()),
+            ("gen_entity_import", entity_import),
+            ("gen_entity_class", entity.name.clone()),
+            ("gen_renderer_imports", renderer_imports(&project)),
+            ("gen_renderer", renderer_class(&project, &renderer.name, &entity.name)),
+        ],
+    )?;
+    register_renderer(&project, &client_set, &block.id, &renderer)?;
+    println!("{}", format!("  {}:{} generated!", project.config.mod_info.mod_id, block.id).bold().green());
+    Ok(())
+}
+
+/// Add the block entity type's field (and the block entity's import) to
+/// ModBlockEntities, creating the class first if needed.
+fn register_entity(project: &Project, id: &str, entity: &ClassName) -> Result<()> {
+    let class = ClassName::parse(&project.config.mod_info.package, REGISTRY_CLASS)?;
+    let kotlin = project.kotlin();
+    let field = field(id, &entity.name, kotlin);
+    let path = project.source_path(&class);
+    let target = crate::sandbox::resolve_output(&project.dir, path.clone())?;
+    if !crate::fs::exists(&target) {
+        let semicolon = if kotlin { "" } else { ";" };
+        let extra = [
+            ("gen_field", field),
+            ("gen_entity_import", format!("import {}{semicolon}", entity.qualified())),
+            ("gen_identifier_import", block::identifier_import(project)),
+            ("gen_key", block::registry_key(project, "BLOCK_ENTITY_TYPE")),
+            ("gen_type", type_body(project)),
+        ];
+        project.write_class(&class, template::GEN_MOD_BLOCK_ENTITIES_JAVA, template::GEN_MOD_BLOCK_ENTITIES_KT, &extra)?;
+        project.register_init(&class)?;
+        return Ok(());
+    }
+
+    let content = crate::fs::read_to_string(&target)?;
+    if content.contains(&format!("register(\"{id}\"")) {
+        return Err(McmodError::Other(format!("{path} already registers '{id}'")));
+    }
+    let updated = add_field(&content, &field)
+        .and_then(|updated| add_import(&updated, &entity.qualified(), kotlin))
+        .ok_or_else(|| {
+            McmodError::Other(format!(
+                "Couldn't find where {path} registers its block entity types; add this line to it by hand:\n{}",
+                field.trim()
+            ))
+        })?;
+    crate::util::write_file(&target, &updated)?;
+    crate::util::progress(format!("  Registered {id} in {path}").green());
+    Ok(())
+}
+
+/// The ModBlockEntities field registering `id`, e.g.
+/// `RUBY_BOX = register("ruby_box", RubyBoxBlockEntity::new, ModBlocks.RUBY_BOX)`.
+fn field(id: &str, class: &str, kotlin: bool) -> String {
+    let name = id.to_ascii_uppercase();
+    if kotlin {
+        format!("    val {name}: Supplier<BlockEntityType<{class}>> = register(\"{id}\", ::{class}, ModBlocks.{name})")
+    } else {
+        format!(
+            "    public static final Supplier<BlockEntityType<{class}>> {name} = register(\"{id}\", {class}::new, ModBlocks.{name});"
+        )
+    }
+}
+
+/// The body of ModBlockEntities' `type()`, which builds a block entity type.
+fn type_body(project: &Project) -> String {
+    let (new, set, semicolon) = if project.kotlin() { ("", "setOf", "") } else { ("new ", "Set.of", ";") };
+    let diamond = if project.kotlin() { "" } else { "<>" };
+    project.version_switch(
+        TYPE_CONSTRUCTOR_SINCE,
+        "        ",
+        &format!("return {new}BlockEntityType{diamond}(factory, {set}(block)){semicolon}"),
+        &format!("return BlockEntityType.Builder.of(factory, block).build(null){semicolon}"),
+    )
+}
+
+/// The block entity's imports for saving and loading its fields.
+fn storage_imports(project: &Project) -> String {
+    let semicolon = if project.kotlin() { "" } else { ";" };
+    let imports = |classes: &[&str]| {
+        classes.iter().map(|class| format!("import {class}{semicolon}")).collect::<Vec<_>>().join("\n")
+    };
+    project.version_switch(
+        VALUE_IO_SINCE,
+        "",
+        &imports(&["net.minecraft.world.level.storage.ValueInput", "net.minecraft.world.level.storage.ValueOutput"]),
+        &imports(&["net.minecraft.core.HolderLookup", "net.minecraft.nbt.CompoundTag"]),
+    )
+}
+
+/// The block entity's `saveAdditional` and `loadAdditional` overrides.
+fn storage_methods(project: &Project) -> String {
+    let kotlin = project.kotlin();
+    let method = |name: &str, params: &str, args: &str| {
+        if kotlin {
+            format!("override fun {name}({params}) {{\n    super.{name}({args})\n}}")
+        } else {
+            format!("@Override\nprotected void {name}({params}) {{\n    super.{name}({args});\n}}")
+        }
+    };
+    let param = |name: &str, class: &str| if kotlin { format!("{name}: {class}") } else { format!("{class} {name}") };
+    let tag_params = format!("{}, {}", param("tag", "CompoundTag"), param("registries", "HolderLookup.Provider"));
+    let value_io = format!(
+        "{}\n\n{}",
+        method("saveAdditional", &param("output", "ValueOutput"), "output"),
+        method("loadAdditional", &param("input", "ValueInput"), "input")
+    );
+    let tag = format!(
+        "{}\n\n{}",
+        method("saveAdditional", &tag_params, "tag, registries"),
+        method("loadAdditional", &tag_params, "tag, registries")
+    );
+    project.version_switch(VALUE_IO_SINCE, "    ", &value_io, &tag)
+}
+
+/// The renderer's imports for the rendering API of each Minecraft version.
+fn renderer_imports(project: &Project) -> String {
+    let semicolon = if project.kotlin() { "" } else { ";" };
+    let imports = |classes: &[&str]| {
+        classes.iter().map(|class| format!("import {class}{semicolon}")).collect::<Vec<_>>().join("\n")
+    };
+    project.version_branches(
+        "",
+        &[
+            (
+                RENDER_STATE_SINCE,
+                &imports(&[
+                    "com.mojang.blaze3d.vertex.PoseStack",
+                    "net.minecraft.client.renderer.SubmitNodeCollector",
+                    "net.minecraft.client.renderer.blockentity.state.BlockEntityRenderState",
+                    "net.minecraft.client.renderer.state.CameraRenderState",
+                ]),
+            ),
+            (
+                RENDER_CAMERA_SINCE,
+                &imports(&[
+                    "com.mojang.blaze3d.vertex.PoseStack",
+                    "net.minecraft.client.renderer.MultiBufferSource",
+                    "net.minecraft.world.phys.Vec3",
+                ]),
+            ),
+        ],
+        &imports(&["com.mojang.blaze3d.vertex.PoseStack", "net.minecraft.client.renderer.MultiBufferSource"]),
+    )
+}
+
+/// The renderer class, for the rendering API of each Minecraft version.
+fn renderer_class(project: &Project, class: &str, entity: &str) -> String {
+    let buffer_params = if project.kotlin() {
+        "blockEntity: {E}, partialTick: Float, poseStack: PoseStack, bufferSource: MultiBufferSource, packedLight: Int, packedOverlay: Int"
+    } else {
+        "{E} blockEntity, float partialTick, PoseStack poseStack, MultiBufferSource bufferSource, int packedLight, int packedOverlay"
+    };
+    let camera_param = if project.kotlin() { ", cameraPosition: Vec3" } else { ", Vec3 cameraPosition" };
+    let render = |params: &str| {
+        let params = params.replace("{E}", entity);
+        if project.kotlin() {
+            format!(
+                "class {class}(context: BlockEntityRendererProvider.Context) : BlockEntityRenderer<{entity}> {{\n    \
+                 override fun render({params}) {{\n        // Draw the block entity here\n    }}\n}}"
+            )
+        } else {
+            format!(
+                "public class {class} implements BlockEntityRenderer<{entity}> {{\n    \
+                 public {class}(BlockEntityRendererProvider.Context context) {{\n    }}\n\n    \
+                 @Override\n    public void render({params}) {{\n        // Draw the block entity here\n    }}\n}}"
+            )
+        }
+    };
+    let submit = if project.kotlin() {
+        format!(
+            "class {class}(context: BlockEntityRendererProvider.Context) : BlockEntityRenderer<{entity}, BlockEntityRenderState> {{\n    \
+             override fun createRenderState() = BlockEntityRenderState()\n\n    \
+             override fun submit(state: BlockEntityRenderState, poseStack: PoseStack, collector: SubmitNodeCollector, camera: CameraRenderState) {{\n        \
+             // Draw the block entity here\n    }}\n}}"
+        )
+    } else {
+        format!(
+            "public class {class} implements BlockEntityRenderer<{entity}, BlockEntityRenderState> {{\n    \
+             public {class}(BlockEntityRendererProvider.Context context) {{\n    }}\n\n    \
+             @Override\n    public BlockEntityRenderState createRenderState() {{\n        return new BlockEntityRenderState();\n    }}\n\n    \
+             @Override\n    public void submit(BlockEntityRenderState state, PoseStack poseStack, SubmitNodeCollector collector, CameraRenderState camera) {{\n        \
+             // Draw the block entity here\n    }}\n}}"
+        )
+    };
+    project.version_branches(
+        "",
+        &[(RENDER_STATE_SINCE, &submit), (RENDER_CAMERA_SINCE, &render(&format!("{buffer_params}{camera_param}")))],
+        &render(buffer_params),
+    )
+}
+
+/// Register `renderer` for `id`'s block entities in ModBlockEntityRenderers,
+/// creating the class first if needed (and, on Fabric, calling its `init()`
+/// from the client entrypoint).
+fn register_renderer(project: &Project, client_set: &str, id: &str, renderer: &ClassName) -> Result<()> {
+    let class = ClassName::parse(&project.config.mod_info.package, RENDERERS_CLASS)?;
+    let kotlin = project.kotlin();
+    let name = id.to_ascii_uppercase();
+    let (provider, semicolon) =
+        if kotlin { (format!("::{}", renderer.name), "") } else { (format!("{}::new", renderer.name), ";") };
+    let event_line = format!("        event.registerBlockEntityRenderer(ModBlockEntities.{name}.get(), {provider}){semicolon}");
+    let fabric_line = format!("        BlockEntityRenderers.register(ModBlockEntities.{name}.get(), {provider}){semicolon}");
+    let path = project.source_path_in(client_set, &class);
+    let target = crate::sandbox::resolve_output(&project.dir, path.clone())?;
+    if !crate::fs::exists(&target) {
+        let extra = [("gen_event_registration", event_line), ("gen_registration", fabric_line)];
+        project.write_class_in(
+            client_set,
+            &class,
+            template::GEN_MOD_BLOCK_ENTITY_RENDERERS_JAVA,
+            template::GEN_MOD_BLOCK_ENTITY_RENDERERS_KT,
+            &extra,
+        )?;
+        return call_from_client_entrypoint(project, &class);
+    }
+
+    let content = crate::fs::read_to_string(&target)?;
+    let updated = add_line(&add_line(&content, &event_line), &fabric_line);
+    if !updated.contains(&format!("ModBlockEntities.{name}.get()")) {
+        return Err(McmodError::Other(format!(
+            "Couldn't find where {path} registers its renderers; register {} for ModBlockEntities.{name} by hand",
+            renderer.name
+        )));
+    }
+    crate::util::write_file(&target, &updated)?;
+    crate::util::progress(format!("  Registered {} in {path}", renderer.name).green());
+    Ok(())
+}
+
+/// `content` with `line` after each run of lines making the same call (the
+/// text before `(ModBlockEntities.`), so every loader's branch gets it.
+fn add_line(content: &str, line: &str) -> String {
+    let Some(call) = line.split_once("(ModBlockEntities.").map(|(call, _)| call.trim()) else {
+        return content.to_string();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut updated = Vec::new();
+    for (i, current) in lines.iter().enumerate() {
+        updated.push(*current);
+        let calls = |l: &str| l.trim().starts_with(&format!("{call}(ModBlockEntities."));
+        if calls(current) && !lines.get(i + 1).is_some_and(|next| calls(next)) {
+            updated.push(line);
+        }
+    }
+    updated.join("\n") + "\n"
+}
+
+/// On Fabric and Quilt, call `class`'s `init()` from the client
+/// entrypoint's `init()`, or say how to when the mod has none.
+fn call_from_client_entrypoint(project: &Project, class: &ClassName) -> Result<()> {
+    if !project.config.loaders.fabric && !project.config.loaders.quilt {
+        return Ok(());
+    }
+    let kotlin = project.kotlin();
+    let call = if kotlin { format!("{}.init()", class.name) } else { format!("{}.init();", class.name) };
+    let Some(client) = project.vars.get("client_entrypoint") else {
+        println!(
+            "{}",
+            format!("  This mod has no Fabric client entrypoint; call {call} from your ClientModInitializer").yellow()
+        );
+        return Ok(());
+    };
+    let entrypoint = ClassName::parse(&project.config.mod_info.package, client)?;
+    let path = project.source_path_in(&project.vars["client_source_set"], &entrypoint);
+    let target = crate::sandbox::resolve_output(&project.dir, path.clone())?;
+    let content = crate::fs::read_to_string(&target).unwrap_or_default();
+    match add_init_call(&content, &call, Some(&class.qualified()), kotlin) {
+        Some(updated) => {
+            if updated != content {
+                crate::util::write_file(&target, &updated)?;
+                crate::util::progress(format!("  Registered {} in {path}", class.name).green());
+            }
+        }
+        None => println!("{}", format!("  Couldn't find init() in {path}; call {call} from it").yellow()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_gen_block_entity() {
+        let dir = Path::new("/memory/genblockentity");
+        let (result, fs) = crate::fs::in_memory(|| {
+            let mut options = init::tests::options(dir, &["fabric", "neoforge"]);
+            options.fabric_entrypoints = Some(vec![crate::config::FabricEntrypoint::Client]);
+            init::run(options)?;
+            run(dir, "ruby_box", None)?;
+            run(dir, "Gem Safe", None)?;
+            run(dir, "ruby_box", None)
+        });
+        assert!(result.unwrap_err().to_string().contains("already listed"));
+
+        let java = dir.join("src/main/java/com/example/testmod");
+        let block = fs.read_string(&java.join("block/RubyBoxBlock.java")).unwrap();
+        assert!(block.contains("import com.example.testmod.block.entity.RubyBoxBlockEntity;\n"));
+        assert!(block.contains("public class RubyBoxBlock extends Block implements EntityBlock {"));
+        assert!(block.contains("        return new RubyBoxBlockEntity(pos, state);\n"));
+        let entity = fs.read_string(&java.join("block/entity/GemSafeBlockEntity.java")).unwrap();
+        assert!(entity.starts_with("package com.example.testmod.block.entity;\n"));
+        assert!(entity.contains("        super(ModBlockEntities.GEM_SAFE.get(), pos, state);\n"));
+        assert!(entity.contains("import net.minecraft.nbt.CompoundTag;\n"));
+        assert!(entity.contains(
+            "    protected void loadAdditional(CompoundTag tag, HolderLookup.Provider registries) {\n        super.loadAdditional(tag, registries);\n"
+        ));
+        assert!(!entity.contains("ValueInput"));
+
+        let registry = fs.read_string(&java.join("registry/ModBlockEntities.java")).unwrap();
+        assert!(registry.contains("import com.example.testmod.block.entity.GemSafeBlockEntity;\nimport com.example.testmod.block.entity.RubyBoxBlockEntity;\n"));
+        assert!(registry.contains(
+            "    public static final Supplier<BlockEntityType<RubyBoxBlockEntity>> RUBY_BOX = register(\"ruby_box\", RubyBoxBlockEntity::new, ModBlocks.RUBY_BOX);\n    public static final Supplier<BlockEntityType<GemSafeBlockEntity>> GEM_SAFE = register(\"gem_safe\", GemSafeBlockEntity::new, ModBlocks.GEM_SAFE);\n"
+        ));
+        assert!(registry.contains("        return new BlockEntityType<>(factory, Set.of(block));\n"));
+        assert!(!registry.contains("{{") && !registry.contains("elif forge"));
+        let blocks = fs.read_string(&java.join("registry/ModBlocks.java")).unwrap();
+        assert!(blocks.contains("RUBY_BOX = register(\"ruby_box\", RubyBoxBlock::new);"));
+        let main = fs.read_string(&java.join("TestmodMod.java")).unwrap();
+        assert!(main.contains("        ModBlocks.init();\n        ModBlockEntities.init();\n"));
+
+        let client = dir.join("src/main/java/com/example/testmod/client");
+        let renderer = fs.read_string(&client.join("GemSafeBlockEntityRenderer.java")).unwrap();
+        assert!(renderer.contains("import com.example.testmod.block.entity.GemSafeBlockEntity;\n"));
+        assert!(renderer.contains("public class GemSafeBlockEntityRenderer implements BlockEntityRenderer<GemSafeBlockEntity> {"));
+        assert!(renderer.contains("int packedLight, int packedOverlay) {\n        // Draw the block entity here\n"));
+        assert!(!renderer.contains("/*?"));
+        let renderers = fs.read_string(&client.join("ModBlockEntityRenderers.java")).unwrap();
+        assert!(renderers.contains(
+            "        event.registerBlockEntityRenderer(ModBlockEntities.RUBY_BOX.get(), RubyBoxBlockEntityRenderer::new);\n        event.registerBlockEntityRenderer(ModBlockEntities.GEM_SAFE.get(), GemSafeBlockEntityRenderer::new);\n"
+        ));
+        assert!(renderers.contains(
+            "        BlockEntityRenderers.register(ModBlockEntities.RUBY_BOX.get(), RubyBoxBlockEntityRenderer::new);\n        BlockEntityRenderers.register(ModBlockEntities.GEM_SAFE.get(), GemSafeBlockEntityRenderer::new);\n"
+        ));
+        assert!(renderers.contains("/*@Mod(value = TestmodMod.MOD_ID, dist = Dist.CLIENT)\n"));
+        let entrypoint = fs.read_string(&java.join("TestmodModClient.java")).unwrap();
+        assert!(entrypoint.contains("import com.example.testmod.client.ModBlockEntityRenderers;\n"));
+        assert!(entrypoint.contains("        ModBlockEntityRenderers.init();\n"));
+
+        assert!(fs.read_string(&dir.join("src/main/resources/data/testmod/loot_table/blocks/gem_safe.json")).is_some());
+        let lang = fs.read_string(&dir.join("src/main/resources/assets/testmod/lang/en_us.json")).unwrap();
+        assert!(lang.contains("\"block.testmod.gem_safe\": \"Gem Safe\""));
+    }
+
+    #[test]
+    fn test_gen_block_entity_versions() {
+        let dir = Path::new("/memory/genblockentitykt");
+        let (result, fs) = crate::fs::in_memory(|| {
+            let mut options = init::tests::options(dir, &["fabric"]);
+            options.language = Some("kotlin".to_string());
+            options.minecraft_versions = vec!["1.21.1".to_string(), "1.21.5".to_string(), "1.21.10".to_string()];
+            options.split_environment = Some(true);
+            init::run(options)?;
+            run(dir, "ruby_box", None)
+        });
+        result.unwrap();
+        let kotlin = dir.join("src/main/kotlin/com/example/testmod");
+        let entity = fs.read_string(&kotlin.join("block/entity/RubyBoxBlockEntity.kt")).unwrap();
+        assert!(entity.contains(
+            "class RubyBoxBlockEntity(pos: BlockPos, state: BlockState) : BlockEntity(ModBlockEntities.RUBY_BOX.get(), pos, state) {"
+        ));
+        assert!(entity.contains("    /*? if >=1.21.6 {*/\n    /*override fun saveAdditional(output: ValueOutput) {"));
+        assert!(entity.contains("    override fun loadAdditional(tag: CompoundTag, registries: HolderLookup.Provider) {\n"));
+        let registry = fs.read_string(&kotlin.join("registry/ModBlockEntities.kt")).unwrap();
+        assert!(registry.contains(
+            "    val RUBY_BOX: Supplier<BlockEntityType<RubyBoxBlockEntity>> = register(\"ruby_box\", ::RubyBoxBlockEntity, ModBlocks.RUBY_BOX)\n"
+        ));
+        assert!(registry.contains("        return BlockEntityType.Builder.of(factory, block).build(null)\n"));
+
+        let client = dir.join("src/client/kotlin/com/example/testmod/client");
+        let renderer = fs.read_string(&client.join("RubyBoxBlockEntityRenderer.kt")).unwrap();
+        assert!(renderer.contains("/*? if >=1.21.9 {*/\n/*class RubyBoxBlockEntityRenderer"));
+        assert!(renderer.contains("/*?} elif >=1.21.5 {*/\n/*class RubyBoxBlockEntityRenderer"));
+        assert!(renderer.contains("packedOverlay: Int, cameraPosition: Vec3) {"));
+        assert!(renderer.contains(
+            "/*?} else {*/\nclass RubyBoxBlockEntityRenderer(context: BlockEntityRendererProvider.Context) : BlockEntityRenderer<RubyBoxBlockEntity> {\n"
+        ));
+        let renderers = fs.read_string(&client.join("ModBlockEntityRenderers.kt")).unwrap();
+        assert!(renderers.contains("        BlockEntityRenderers.register(ModBlockEntities.RUBY_BOX.get(), ::RubyBoxBlockEntityRenderer)\n"));
+        assert!(fs.read_string(&kotlin.join("client/ModBlockEntityRenderers.kt")).is_none());
+    }
+}
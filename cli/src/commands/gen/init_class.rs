@@ -0,0 +1,46 @@
+use super::{ClassName, Project};
+use crate::error::Result;
+use crate::template;
+use colored::Colorize;
+use std::path::Path;
+
+/// `mcmod gen class <Name>`: a class with an `init()` hook that the mod's
+/// `init()` calls, for grouping setup code (commands, events, config).
+pub fn run(dir: &Path, name: &str) -> Result<()> {
+    println!("{}", format!("\n  mcmod gen class {name}\n").bold().cyan());
+    let project = Project::load(dir)?;
+    let class = ClassName::parse(&project.config.mod_info.package, name)?;
+    project.write_class(&class, template::GEN_INIT_CLASS_JAVA, template::GEN_INIT_CLASS_KT, &[])?;
+    project.register_init(&class)?;
+    println!("{}", format!("  {} generated!", class.name).bold().green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_gen_class() {
+        let dir = Path::new("/memory/genclass");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(dir, "event.ModEvents")?;
+            run(dir, "ModCommands")?;
+            run(dir, "ModCommands")
+        });
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+
+        let events = fs.read_string(&dir.join("src/main/java/com/example/testmod/event/ModEvents.java")).unwrap();
+        assert!(events.starts_with("package com.example.testmod.event;\n\nimport com.example.testmod.TestmodMod;\n"));
+        assert!(events.contains("TestmodMod.LOGGER.debug(\"Initializing ModEvents\");"));
+        let commands = fs.read_string(&dir.join("src/main/java/com/example/testmod/ModCommands.java")).unwrap();
+        assert!(!commands.contains("import"));
+
+        let main = fs.read_string(&dir.join("src/main/java/com/example/testmod/TestmodMod.java")).unwrap();
+        assert!(main.contains("import com.example.testmod.event.ModEvents;\n"));
+        assert!(main.contains("        ModEvents.init();\n        ModCommands.init();\n    }\n"));
+        assert!(!main.contains("import com.example.testmod.ModCommands;"));
+    }
+}
@@ -0,0 +1,184 @@
+use super::{add_field, ClassName, Project};
+use crate::commands::assets;
+use crate::content::{self, ContentManifest, MANIFEST_FILE};
+use crate::error::{McmodError, Result};
+use crate::naming::{self, ContentKind, ContentName};
+use crate::template;
+use colored::Colorize;
+use std::path::Path;
+
+/// The class items are registered in, below the mod's package.
+const REGISTRY_CLASS: &str = "registry.ModItems";
+/// Item.Properties needs the item's registry key from this release on.
+const ITEM_ID_SINCE: &str = "1.21.2";
+/// ResourceLocation is called Identifier from this release on.
+const IDENTIFIER_SINCE: &str = "1.21.11";
+
+/// `mcmod gen item <name>`: register a plain `Item` in `registry.ModItems`
+/// (created, and called from the mod's `init()`, on first use), list it in
+/// content.toml, and generate its translation, model and a placeholder
+/// texture. `name` is a registry id (`ruby`) or a display name
+/// ("Ruby Sword"); `display` overrides the English name.
+pub fn run(dir: &Path, name: &str, display: Option<&str>) -> Result<()> {
+    println!("{}", format!("\n  mcmod gen item {name}\n").bold().cyan());
+    let project = Project::load(dir)?;
+    let mod_id = &project.config.mod_info.mod_id;
+    let (item, display) = if naming::is_valid_id(name) {
+        (ContentName::from_id(name, mod_id, ContentKind::Item), display)
+    } else {
+        (ContentName::suggest(name, mod_id, ContentKind::Item)?, display.or(Some(name)))
+    };
+    if let Some(manifest) = ContentManifest::load(dir)? {
+        if manifest.items.iter().chain(&manifest.blocks).any(|e| e.id == item.id) {
+            return Err(McmodError::Other(format!("'{}' is already listed in {MANIFEST_FILE}", item.id)));
+        }
+    }
+
+    register_item(&project, &item.id)?;
+    content::add_entry(dir, ContentKind::Item, &item.id, display)?;
+    crate::util::progress(format!("  Added {} to {MANIFEST_FILE}", item.id).green());
+    if let Some(manifest) = ContentManifest::load(dir)? {
+        for rel in content::generate(dir, &project.config, &manifest)? {
+            crate::util::progress(format!("  Generated assets/{mod_id}/{rel}").green());
+        }
+    }
+    if let Some(rel) = assets::stub_texture(dir, &project.config, &format!("item/{}", item.id))? {
+        crate::util::progress(format!("  Stubbed {rel}").green());
+    }
+    println!("{}", format!("  {mod_id}:{} generated!", item.id).bold().green());
+    Ok(())
+}
+
+/// Add the item's field to ModItems, creating the class first if needed.
+fn register_item(project: &Project, id: &str) -> Result<()> {
+    let class = ClassName::parse(&project.config.mod_info.package, REGISTRY_CLASS)?;
+    let kotlin = project.kotlin();
+    let field = field(id, kotlin);
+    let path = project.source_path(&class);
+    let target = crate::sandbox::resolve_output(&project.dir, path.clone())?;
+    if !crate::fs::exists(&target) {
+        let extra = registry_vars(project, field);
+        project.write_class(&class, template::GEN_MOD_ITEMS_JAVA, template::GEN_MOD_ITEMS_KT, &extra)?;
+        project.register_init(&class)?;
+        return Ok(());
+    }
+
+    let content = crate::fs::read_to_string(&target)?;
+    if content.contains(&format!("register(\"{id}\"")) {
+        return Err(McmodError::Other(format!("{path} already registers '{id}'")));
+    }
+    let updated = add_field(&content, &field).ok_or_else(|| {
+        McmodError::Other(format!(
+            "Couldn't find where {path} registers its items; add this line to it by hand:\n{}",
+            field.trim()
+        ))
+    })?;
+    crate::util::write_file(&target, &updated)?;
+    crate::util::progress(format!("  Registered {id} in {path}").green());
+    Ok(())
+}
+
+/// The ModItems field registering `id`, e.g. `RUBY = register("ruby", Item::new)`.
+fn field(id: &str, kotlin: bool) -> String {
+    let name = id.to_ascii_uppercase();
+    if kotlin {
+        format!("    val {name}: Supplier<Item> = register(\"{id}\", ::Item)")
+    } else {
+        format!("    public static final Supplier<Item> {name} = register(\"{id}\", Item::new);")
+    }
+}
+
+/// Template variables for a new ModItems: its first field, and the lines
+/// that depend on the Minecraft version.
+fn registry_vars(project: &Project, field: String) -> Vec<(&'static str, String)> {
+    let class_name = &project.vars["class_name"];
+    let (semicolon, new) = if project.kotlin() { ("", "") } else { (";", "new ") };
+    let key = |id_class: &str| {
+        format!("return ResourceKey.create(Registries.ITEM, {id_class}.fromNamespaceAndPath({class_name}.MOD_ID, id)){semicolon}")
+    };
+    vec![
+        ("gen_field", field),
+        (
+            "gen_identifier_import",
+            project.version_switch(
+                IDENTIFIER_SINCE,
+                "",
+                &format!("import net.minecraft.resources.Identifier{semicolon}"),
+                &format!("import net.minecraft.resources.ResourceLocation{semicolon}"),
+            ),
+        ),
+        ("gen_item_key", project.version_switch(IDENTIFIER_SINCE, "        ", &key("Identifier"), &key("ResourceLocation"))),
+        (
+            "gen_item_properties",
+            project.version_switch(
+                ITEM_ID_SINCE,
+                "        ",
+                &format!("return {new}Item.Properties().setId(key){semicolon}"),
+                &format!("return {new}Item.Properties(){semicolon}"),
+            ),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_gen_item() {
+        let dir = Path::new("/memory/genitem");
+        let (result, fs) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            run(dir, "ruby", None)?;
+            run(dir, "Ruby Sword", None)?;
+            run(dir, "ruby", None)
+        });
+        assert!(result.unwrap_err().to_string().contains("already listed"));
+
+        let items = fs.read_string(&dir.join("src/main/java/com/example/testmod/registry/ModItems.java")).unwrap();
+        assert!(items.starts_with("package com.example.testmod.registry;\n\nimport com.example.testmod.TestmodMod;\n"));
+        assert!(items.contains(
+            "    public static final Supplier<Item> RUBY = register(\"ruby\", Item::new);\n    public static final Supplier<Item> RUBY_SWORD = register(\"ruby_sword\", Item::new);\n"
+        ));
+        assert!(items.contains("import net.minecraft.resources.ResourceLocation;\n"));
+        assert!(items.contains("        return new Item.Properties().setId(key);\n"));
+        assert!(!items.contains("{{") && !items.contains("elif forge"));
+        let main = fs.read_string(&dir.join("src/main/java/com/example/testmod/TestmodMod.java")).unwrap();
+        assert!(main.contains("import com.example.testmod.registry.ModItems;\n"));
+        assert!(main.contains("        ModItems.init();\n"));
+
+        assert_eq!(
+            fs.read_string(&dir.join(MANIFEST_FILE)).unwrap(),
+            "[[items]]\nid = \"ruby\"\n\n[[items]]\nid = \"ruby_sword\"\nname = \"Ruby Sword\"\n"
+        );
+        let assets = dir.join("src/main/resources/assets/testmod");
+        let lang = fs.read_string(&assets.join("lang/en_us.json")).unwrap();
+        assert!(lang.contains("\"item.testmod.ruby\": \"Ruby\""));
+        assert!(lang.contains("\"item.testmod.ruby_sword\": \"Ruby Sword\""));
+        assert!(fs.read_string(&assets.join("models/item/ruby.json")).unwrap().contains("testmod:item/ruby"));
+        assert!(fs.read_string(&assets.join("items/ruby.json")).is_some());
+        assert!(fs.read_string(&assets.join("textures/item/ruby_sword.png")).is_some());
+        assert!(fs.read_string(&dir.join("mcmod.lock")).unwrap().contains("textures/item/ruby.png"));
+    }
+
+    #[test]
+    fn test_gen_item_kotlin() {
+        let dir = Path::new("/memory/genitemkt");
+        let (result, fs) = crate::fs::in_memory(|| {
+            let mut options = init::tests::options(dir, &["neoforge"]);
+            options.language = Some("kotlin".to_string());
+            options.minecraft_versions = vec!["1.21.1".to_string()];
+            init::run(options)?;
+            run(dir, "ruby", Some("Polished Ruby"))
+        });
+        result.unwrap();
+        let items = fs.read_string(&dir.join("src/main/kotlin/com/example/testmod/registry/ModItems.kt")).unwrap();
+        assert!(items.contains("object ModItems {"));
+        assert!(items.contains("    val RUBY: Supplier<Item> = register(\"ruby\", ::Item)\n"));
+        assert!(items.contains("        return Item.Properties()\n"));
+        assert!(!items.contains("setId"));
+        let lang = fs.read_string(&dir.join("src/main/resources/assets/testmod/lang/en_us.json")).unwrap();
+        assert!(lang.contains("\"item.testmod.ruby\": \"Polished Ruby\""));
+    }
+}
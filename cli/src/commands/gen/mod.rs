@@ -0,0 +1,376 @@
+//! `mcmod gen <thing>`: scaffold code into an existing mod project.
+//!
+//! Generators share [`Project`]: it loads mcmod.toml, renders a Java or Kotlin
+//! template (whichever the project uses) with the common template variables
+//! and loader conditions, writes the class into the unified `src/main` source
+//! tree (or the split client source set, for client-only code), and wires the
+//! new code into the mod by adding a call to the main class's `init()`, which
+//! every loader's entrypoint runs.
+
+pub mod block;
+pub mod block_entity;
+pub mod init_class;
+pub mod item;
+
+use crate::config::{McmodConfig, ProjectType};
+use crate::error::{McmodError, Result};
+use crate::sandbox;
+use crate::template;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A mod project as generators see it.
+pub struct Project {
+    pub dir: PathBuf,
+    pub config: McmodConfig,
+    pub vars: HashMap<String, String>,
+}
+
+/// A class to generate: `Name` in the mod's package or `sub.pkg.Name` below it.
+#[derive(Debug, PartialEq)]
+pub struct ClassName {
+    /// Full package, e.g. `com.example.mymod.registry`.
+    pub package: String,
+    pub name: String,
+}
+
+impl ClassName {
+    /// Parse `Name` or `sub.pkg.Name` relative to `base` (the mod's package).
+    pub fn parse(base: &str, input: &str) -> Result<ClassName> {
+        let (sub, name) = match input.rsplit_once('.') {
+            Some((sub, name)) => (Some(sub), name),
+            None => (None, input),
+        };
+        let valid_name = name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(McmodError::Other(format!(
+                "Invalid class name '{name}': use PascalCase letters and digits, e.g. ModItems"
+            )));
+        }
+        let package = match sub {
+            Some(sub) => {
+                crate::util::validate_package(sub)?;
+                format!("{base}.{sub}")
+            }
+            None => base.to_string(),
+        };
+        Ok(ClassName { package, name: name.to_string() })
+    }
+
+    pub fn qualified(&self) -> String {
+        format!("{}.{}", self.package, self.name)
+    }
+}
+
+impl Project {
+    /// Load the project in `dir`. Generators need a regular mod: libraries
+    /// have no `init()` to register code in.
+    pub fn load(dir: &Path) -> Result<Project> {
+        let config = McmodConfig::load(dir)?;
+        if config.mod_info.project_type != ProjectType::Mod {
+            return Err(McmodError::Other(format!(
+                "mcmod gen scaffolds code into mods (this is a {} project)",
+                config.mod_info.project_type.as_str()
+            )));
+        }
+        let vars = template::build_common_vars(&config);
+        Ok(Project { dir: dir.to_path_buf(), config, vars })
+    }
+
+    pub fn kotlin(&self) -> bool {
+        self.config.mod_info.language == "kotlin"
+    }
+
+    /// Where `class` lives, relative to the project.
+    pub fn source_path(&self, class: &ClassName) -> String {
+        self.source_path_in("main", class)
+    }
+
+    /// Where `class` lives in `source_set` (`main`, or `client` for
+    /// client-only code in a split-environment project).
+    pub fn source_path_in(&self, source_set: &str, class: &ClassName) -> String {
+        let (source_dir, ext) = if self.kotlin() { ("kotlin", "kt") } else { ("java", "java") };
+        format!(
+            "src/{source_set}/{source_dir}/{}/{}.{ext}",
+            crate::util::package_to_path(&class.package),
+            class.name
+        )
+    }
+
+    /// Render the template for the project's language and write it as
+    /// `class`. Templates see the common variables plus `gen_class`,
+    /// `gen_package`, and `extra`; `{{#subpackage}}` blocks are kept when the
+    /// class is below the mod's package, and the loader and language
+    /// conditions apply as in the build templates. Existing files are never
+    /// overwritten.
+    pub fn write_class(
+        &self,
+        class: &ClassName,
+        java: &str,
+        kotlin: &str,
+        extra: &[(&str, String)],
+    ) -> Result<String> {
+        self.write_class_in("main", class, java, kotlin, extra)
+    }
+
+    /// [`Self::write_class`] into `source_set`.
+    pub fn write_class_in(
+        &self,
+        source_set: &str,
+        class: &ClassName,
+        java: &str,
+        kotlin: &str,
+        extra: &[(&str, String)],
+    ) -> Result<String> {
+        let path = self.source_path_in(source_set, class);
+        let target = sandbox::resolve_output(&self.dir, path.clone())?;
+        if crate::fs::exists(&target) {
+            return Err(McmodError::Other(format!("{path} already exists")));
+        }
+        let mut conditions = template::build_conditions(&self.config).to_vec();
+        conditions.push(("subpackage", class.package != self.config.mod_info.package));
+        let source = template::strip_conditional_blocks(if self.kotlin() { kotlin } else { java }, &conditions);
+
+        let mut vars = self.vars.clone();
+        vars.insert("gen_class".to_string(), class.name.clone());
+        vars.insert("gen_package".to_string(), class.package.clone());
+        for (key, value) in extra {
+            vars.insert(key.to_string(), value.clone());
+        }
+        crate::util::write_file(&target, &template::render(&source, &vars)?)?;
+        crate::util::progress(format!("  Created {path}").green());
+        Ok(path)
+    }
+
+    /// Lines for code that changed in Minecraft `since`: a Stonecutter
+    /// `/*? if >=since {*/` block choosing between `then` and `otherwise`,
+    /// with the branch for the active (first) target uncommented. When every
+    /// target is on one side of `since`, just that side's lines. Each line
+    /// is indented by `indent`.
+    pub fn version_switch(&self, since: &str, indent: &str, then: &str, otherwise: &str) -> String {
+        self.version_branches(indent, &[(since, then)], otherwise)
+    }
+
+    /// [`Self::version_switch`] for code that changed more than once:
+    /// `branches` are (since, code), newest first, and `oldest` is the code
+    /// before all of them. Branches no target uses are left out, so the
+    /// block is `if`/`elif`/`else` over the ones that remain.
+    pub fn version_branches(&self, indent: &str, branches: &[(&str, &str)], oldest: &str) -> String {
+        let used: Vec<usize> = self
+            .config
+            .versions
+            .targets
+            .iter()
+            .map(|t| {
+                branches
+                    .iter()
+                    .position(|(since, _)| crate::version_meta::compare_versions(&t.minecraft, since).is_ge())
+                    .unwrap_or(branches.len())
+            })
+            .collect();
+        let code = |i: usize| if i < branches.len() { branches[i].1 } else { oldest };
+        let lines = |code: &str| {
+            code.lines()
+                .map(|l| if l.is_empty() { String::new() } else { format!("{indent}{l}") })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let mut kept: Vec<usize> = used.clone();
+        kept.sort_unstable();
+        kept.dedup();
+        if kept.len() == 1 {
+            return lines(code(kept[0]));
+        }
+
+        let active = used[0];
+        let last = kept.len() - 1;
+        let mut out = String::new();
+        for (n, &i) in kept.iter().enumerate() {
+            let condition = match n {
+                0 => format!("{indent}/*? if >={} {{*/\n", branches[i].0),
+                n if n == last => "/*?} else {*/\n".to_string(),
+                _ => format!("/*?}} elif >={} {{*/\n", branches[i].0),
+            };
+            out.push_str(&condition);
+            if i == active {
+                out.push_str(&format!("{}\n{indent}", lines(code(i))));
+            } else {
+                out.push_str(&format!("{indent}/*{}\n{indent}*/", lines(code(i)).trim_start()));
+            }
+        }
+        out.push_str("/*?}*/");
+        out
+    }
+
+    /// Call `class`'s `init()` from the main class's `init()`, importing it
+    /// when it's in another package. Returns false (after printing what to
+    /// add by hand) when the main class no longer has a recognizable `init()`.
+    pub fn register_init(&self, class: &ClassName) -> Result<bool> {
+        let (_, main_path) = template::render_unified_mod(&self.config, &self.vars)?;
+        let path = self.dir.join(&main_path);
+        let content = crate::fs::read_to_string(&path).unwrap_or_default();
+        let call = if self.kotlin() { format!("{}.init()", class.name) } else { format!("{}.init();", class.name) };
+        let import = (class.package != self.config.mod_info.package).then(|| class.qualified());
+        match add_init_call(&content, &call, import.as_deref(), self.kotlin()) {
+            Some(updated) => {
+                if updated != content {
+                    crate::util::write_file(&path, &updated)?;
+                    crate::util::progress(format!("  Registered {} in {main_path}", class.name).green());
+                }
+                Ok(true)
+            }
+            None => {
+                println!(
+                    "{}",
+                    format!("  Couldn't find init() in {main_path}; call {call} from your mod's initialization").yellow()
+                );
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Add `call` as the last statement of the main class's `init()` (and
+/// `import` after the package line). Returns the content unchanged when the
+/// call is already there, or None without an `init()` to add it to.
+fn add_init_call(content: &str, call: &str, import: Option<&str>, kotlin: bool) -> Option<String> {
+    let signature = if kotlin { "fun init() {" } else { "public static void init() {" };
+    let open = content.find(signature)? + signature.len() - 1;
+    let close = matching_brace(content, open)?;
+    if content[open..close].lines().any(|line| line.trim() == call) {
+        return Some(content.to_string());
+    }
+
+    let line_start = content[..close].rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = content[line_start..close].chars().take_while(|c| c.is_whitespace()).collect();
+    let updated = format!("{}{indent}    {call}\n{}", &content[..line_start], &content[line_start..]);
+
+    match import {
+        Some(import) => add_import(&updated, import, kotlin),
+        None => Some(updated),
+    }
+}
+
+/// `content` with `import` in order among the imports right after the
+/// package line, unless it's already imported. None without a package line.
+pub fn add_import(content: &str, import: &str, kotlin: bool) -> Option<String> {
+    let statement = if kotlin { format!("import {import}") } else { format!("import {import};") };
+    if content.lines().any(|line| line.trim() == statement) {
+        return Some(content.to_string());
+    }
+    let package_end = content.find("package ").and_then(|p| content[p..].find('\n').map(|n| p + n + 1))?;
+    // After the blank line that follows the package declaration
+    let mut at = if content[package_end..].starts_with('\n') { package_end + 1 } else { package_end };
+    for line in content[at..].split_inclusive('\n') {
+        if !line.starts_with("import ") || line.trim_end() > statement.as_str() {
+            break;
+        }
+        at += line.len();
+    }
+    let mut updated = content.to_string();
+    updated.insert_str(at, &format!("{statement}\n"));
+    Some(updated)
+}
+
+/// `content` with `field` after the last `NAME = register("...` line of a
+/// registry class, or None when there isn't one to go by.
+pub fn add_field(content: &str, field: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let last = lines.iter().rposition(|line| line.contains(" = register(\""))?;
+    let mut updated: Vec<&str> = lines[..=last].to_vec();
+    updated.push(field);
+    updated.extend(&lines[last + 1..]);
+    Some(updated.join("\n") + "\n")
+}
+
+/// Index of the `}` closing the `{` at `open`.
+fn matching_brace(content: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in content[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_name() {
+        let base = "com.example.testmod";
+        assert_eq!(
+            ClassName::parse(base, "ModItems").unwrap(),
+            ClassName { package: base.to_string(), name: "ModItems".to_string() }
+        );
+        let nested = ClassName::parse(base, "registry.ModItems").unwrap();
+        assert_eq!(nested.qualified(), "com.example.testmod.registry.ModItems");
+        assert!(ClassName::parse(base, "modItems").is_err());
+        assert!(ClassName::parse(base, "Registry.ModItems").is_err());
+        assert!(ClassName::parse(base, "Mod-Items").is_err());
+    }
+
+    #[test]
+    fn test_version_switch() {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let mut older = config.versions.targets[0].clone();
+        older.minecraft = "1.21.1".to_string();
+        config.versions.targets[0].minecraft = "1.21.4".to_string();
+        let vars = template::build_common_vars(&config);
+        let mut project = Project { dir: PathBuf::from("/memory/switch"), config, vars };
+
+        assert_eq!(project.version_switch("1.21.2", "    ", "new();", "old();"), "    new();");
+        project.config.versions.targets.push(older);
+        assert_eq!(
+            project.version_switch("1.21.2", "    ", "new();", "old();"),
+            "    /*? if >=1.21.2 {*/\n    new();\n    /*?} else {*/\n    /*old();\n    *//*?}*/"
+        );
+        project.config.versions.targets.reverse();
+        assert_eq!(
+            project.version_switch("1.21.2", "", "new();", "old();"),
+            "/*? if >=1.21.2 {*/\n/*new();\n*//*?} else {*/\nold();\n/*?}*/"
+        );
+        project.config.versions.targets.truncate(1);
+        assert_eq!(project.version_switch("1.21.2", "", "new();", "old();"), "old();");
+    }
+
+    #[test]
+    fn test_add_init_call() {
+        let java = "package com.example.testmod;\n\nimport org.slf4j.Logger;\n\npublic class TestmodMod {\n    public static void init() {\n        LOGGER.info(\"Initializing\");\n        if (DEBUG) {\n            LOGGER.info(\"debug\");\n        }\n    }\n}\n";
+        let updated = add_init_call(java, "ModItems.init();", Some("com.example.testmod.registry.ModItems"), false).unwrap();
+        assert_eq!(
+            updated,
+            "package com.example.testmod;\n\nimport com.example.testmod.registry.ModItems;\nimport org.slf4j.Logger;\n\npublic class TestmodMod {\n    public static void init() {\n        LOGGER.info(\"Initializing\");\n        if (DEBUG) {\n            LOGGER.info(\"debug\");\n        }\n        ModItems.init();\n    }\n}\n"
+        );
+        assert_eq!(add_init_call(&updated, "ModItems.init();", Some("com.example.testmod.registry.ModItems"), false).unwrap(), updated);
+
+        let kotlin = "package com.example.testmod\n\nclass TestmodMod {\n    companion object {\n        fun init() {\n            LOGGER.info(\"Initializing\")\n        }\n    }\n}\n";
+        let updated = add_init_call(kotlin, "ModItems.init()", None, true).unwrap();
+        assert!(updated.contains("            LOGGER.info(\"Initializing\")\n            ModItems.init()\n        }\n"));
+        assert_eq!(add_init_call("class A {}", "ModItems.init();", None, false), None);
+    }
+}
@@ -0,0 +1,382 @@
+use crate::config::{LinkKind, McmodConfig};
+use crate::error::Result;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Output syntax for `mcmod graph`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Mermaid flowchart, renders inline on GitHub
+    Mermaid,
+    /// Graphviz DOT, for `dot -Tsvg`
+    Dot,
+}
+
+/// Platform dependencies every project declares; they'd only add noise.
+const IMPLICIT_DEPS: &[&str] = &["java", "minecraft"];
+
+struct Node {
+    id: String,
+    label: String,
+    /// Nodes sharing a cluster are drawn in one box (loaders, content).
+    cluster: Option<String>,
+}
+
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+    /// Dependencies are dashed; structure (versions, content) is solid.
+    dashed: bool,
+}
+
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn node(&mut self, id: &str, label: &str, cluster: Option<&str>) {
+        if !self.nodes.iter().any(|n| n.id == id) {
+            self.nodes.push(Node {
+                id: id.to_string(),
+                label: label.to_string(),
+                cluster: cluster.map(str::to_string),
+            });
+        }
+    }
+
+    fn edge(&mut self, from: &str, to: &str, label: Option<String>, dashed: bool) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label,
+            dashed,
+        });
+    }
+}
+
+/// Print (or write to `output`) a graph of the project's structure.
+pub fn run(dir: &Path, format: GraphFormat, output: Option<&Path>) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let graph = build_graph(dir, &config);
+    let text = match format {
+        GraphFormat::Mermaid => render_mermaid(&graph),
+        GraphFormat::Dot => render_dot(&graph, &config.mod_info.mod_id),
+    };
+    match output {
+        Some(path) => {
+            crate::util::write_file(path, &text)?;
+            println!("  Wrote {}", path.display());
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn build_graph(dir: &Path, config: &McmodConfig) -> Graph {
+    let mut graph = Graph::default();
+    let root = format!("mod:{}", config.mod_info.mod_id);
+    graph.node(
+        &root,
+        &format!(
+            "{} ({})",
+            config.mod_info.mod_name,
+            config.mod_info.project_type.as_str()
+        ),
+        None,
+    );
+
+    // Build targets: one Stonecutter project per version and loader
+    let loaders = config.enabled_platforms();
+    for target in &config.versions.targets {
+        if loaders.is_empty() {
+            let id = format!("mc:{}", target.minecraft);
+            graph.node(&id, &format!("Minecraft {}", target.minecraft), None);
+            graph.edge(&root, &id, None, false);
+        }
+        for loader in &loaders {
+            let name = format!("{}-{loader}", target.minecraft);
+            let id = format!("target:{name}");
+            graph.node(&id, &name, Some(loader));
+            graph.edge(&root, &id, None, false);
+        }
+    }
+
+    // Declared mod dependencies, labelled with the loaders that declare them
+    for (dep, declared_by) in declared_dependencies(dir) {
+        let id = format!("dep:{dep}");
+        graph.node(&id, &dep, None);
+        graph.edge(&root, &id, Some(declared_by.join(", ")), true);
+    }
+    for link in &config.links {
+        let id = format!("mod:{}", link.mod_id);
+        graph.node(&id, &format!("{} ({})", link.mod_id, link.path), None);
+        match link.kind {
+            LinkKind::Dependency => graph.edge(&root, &id, Some("link".to_string()), true),
+            LinkKind::Dependent => graph.edge(&id, &root, Some("link".to_string()), true),
+        }
+    }
+
+    // Generated content, counted by kind (textures, models, recipe, ...)
+    let resources = if config.uses_stonecutter() || config.is_plugin() {
+        dir.join("src/main/resources")
+    } else {
+        dir.to_path_buf()
+    };
+    for (kind, count) in content_counts(&resources, &config.mod_info.mod_id) {
+        let id = format!("content:{kind}");
+        graph.node(&id, &format!("{kind} ({count})"), Some("content"));
+        graph.edge(&root, &id, None, false);
+    }
+    graph
+}
+
+/// Mod IDs from fabric.mod.json `depends` and the `[[dependencies.*]]` of
+/// neoforge.mods.toml and Forge's mods.toml, each with the loaders declaring it.
+fn declared_dependencies(dir: &Path) -> BTreeMap<String, Vec<&'static str>> {
+    let mut deps: BTreeMap<String, Vec<&'static str>> = BTreeMap::new();
+    let resources = dir.join("src/main/resources");
+
+    if let Some(json) = std::fs::read_to_string(resources.join("fabric.mod.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+    {
+        if let Some(depends) = json["depends"].as_object() {
+            for id in depends.keys() {
+                deps.entry(id.clone()).or_default().push("fabric");
+            }
+        }
+    }
+
+    if let Some(json) = std::fs::read_to_string(resources.join("quilt.mod.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+    {
+        if let Some(depends) = json["quilt_loader"]["depends"].as_array() {
+            for id in depends.iter().filter_map(|d| d["id"].as_str()) {
+                deps.entry(id.to_string()).or_default().push("quilt");
+            }
+        }
+    }
+
+    // The files use ${...} placeholders in table names, so scan lines
+    // instead of parsing them as TOML
+    for (file, loader) in [
+        ("META-INF/neoforge.mods.toml", "neoforge"),
+        ("META-INF/mods.toml", "forge"),
+    ] {
+        let Ok(toml) = std::fs::read_to_string(resources.join(file)) else {
+            continue;
+        };
+        let mut in_dependency = false;
+        for line in toml.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_dependency = line.starts_with("[[dependencies.");
+            } else if let Some(value) = line.strip_prefix("modId").map(str::trim_start) {
+                if let (true, Some(id)) = (in_dependency, value.strip_prefix('=')) {
+                    let id = id.trim().trim_matches('"').to_string();
+                    deps.entry(id).or_default().push(loader);
+                }
+            }
+        }
+    }
+
+    deps.retain(|id, _| !IMPLICIT_DEPS.contains(&id.as_str()));
+    deps
+}
+
+/// Count files under `assets/<mod_id>/<kind>/` and `data/<mod_id>/<kind>/`.
+fn content_counts(resources: &Path, mod_id: &str) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for side in ["assets", "data"] {
+        let Ok(entries) = std::fs::read_dir(resources.join(side).join(mod_id)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                let count = count_files(&entry.path());
+                if count > 0 {
+                    let kind = entry.file_name().to_string_lossy().into_owned();
+                    *counts.entry(kind).or_insert(0) += count;
+                }
+            }
+        }
+    }
+    counts
+}
+
+fn count_files(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => count_files(&e.path()),
+            Ok(t) if t.is_file() => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Mermaid node IDs must be plain identifiers.
+fn mermaid_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn render_mermaid(graph: &Graph) -> String {
+    let node_line = |n: &Node| format!("{}[\"{}\"]", mermaid_id(&n.id), n.label.replace('"', "'"));
+    let mut out = String::from("graph LR\n");
+    for node in graph.nodes.iter().filter(|n| n.cluster.is_none()) {
+        out.push_str(&format!("  {}\n", node_line(node)));
+    }
+    for (cluster, nodes) in clusters(graph) {
+        out.push_str(&format!("  subgraph {cluster}\n"));
+        for node in nodes {
+            out.push_str(&format!("    {}\n", node_line(node)));
+        }
+        out.push_str("  end\n");
+    }
+    for edge in &graph.edges {
+        let (from, to) = (mermaid_id(&edge.from), mermaid_id(&edge.to));
+        let line = match (&edge.label, edge.dashed) {
+            (Some(label), true) => format!("{from} -. {label} .-> {to}"),
+            (None, true) => format!("{from} -.-> {to}"),
+            (Some(label), false) => format!("{from} -->|{label}| {to}"),
+            (None, false) => format!("{from} --> {to}"),
+        };
+        out.push_str(&format!("  {line}\n"));
+    }
+    out
+}
+
+fn render_dot(graph: &Graph, name: &str) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('"', "\\\""));
+    let mut out = format!("digraph {} {{\n  rankdir=LR;\n  node [shape=box];\n", quote(name));
+    for node in graph.nodes.iter().filter(|n| n.cluster.is_none()) {
+        out.push_str(&format!("  {} [label={}];\n", quote(&node.id), quote(&node.label)));
+    }
+    for (cluster, nodes) in clusters(graph) {
+        out.push_str(&format!(
+            "  subgraph {} {{\n    label={};\n",
+            quote(&format!("cluster_{cluster}")),
+            quote(cluster)
+        ));
+        for node in nodes {
+            out.push_str(&format!("    {} [label={}];\n", quote(&node.id), quote(&node.label)));
+        }
+        out.push_str("  }\n");
+    }
+    for edge in &graph.edges {
+        let mut attrs = Vec::new();
+        if let Some(label) = &edge.label {
+            attrs.push(format!("label={}", quote(label)));
+        }
+        if edge.dashed {
+            attrs.push("style=dashed".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        out.push_str(&format!("  {} -> {}{attrs};\n", quote(&edge.from), quote(&edge.to)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Clustered nodes grouped by cluster name, in first-seen order.
+fn clusters(graph: &Graph) -> Vec<(&str, Vec<&Node>)> {
+    let mut out: Vec<(&str, Vec<&Node>)> = Vec::new();
+    for node in &graph.nodes {
+        let Some(cluster) = node.cluster.as_deref() else {
+            continue;
+        };
+        match out.iter_mut().find(|(name, _)| *name == cluster) {
+            Some((_, nodes)) => nodes.push(node),
+            None => out.push((cluster, vec![node])),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Versions;
+
+    fn sample_project(name: &str) -> (std::path::PathBuf, McmodConfig) {
+        let dir = std::env::temp_dir().join(format!("mcmod_graph_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let resources = dir.join("src/main/resources");
+        std::fs::create_dir_all(resources.join("META-INF")).unwrap();
+        std::fs::write(
+            resources.join("fabric.mod.json"),
+            r#"{"depends": {"minecraft": "*", "fabric-api": "*", "java": ">=21"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            resources.join("META-INF/neoforge.mods.toml"),
+            "[[mods]]\nmodId = \"testmod\"\n\n[[dependencies.${id}]]\nmodId = \"neoforge\"\n",
+        )
+        .unwrap();
+        for file in ["models/item/a.json", "models/block/b.json", "textures/item/a.png"] {
+            let path = resources.join("assets/testmod").join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Versions::default(),
+        );
+        (dir, config)
+    }
+
+    #[test]
+    fn test_declared_dependencies_and_content() {
+        let (dir, _) = sample_project("deps");
+        let deps = declared_dependencies(&dir);
+        assert_eq!(deps.keys().collect::<Vec<_>>(), vec!["fabric-api", "neoforge"]);
+        assert_eq!(deps["neoforge"], vec!["neoforge"]);
+
+        let counts = content_counts(&dir.join("src/main/resources"), "testmod");
+        assert_eq!(counts["models"], 2);
+        assert_eq!(counts["textures"], 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let (dir, config) = sample_project("render");
+        let graph = build_graph(&dir, &config);
+
+        let mermaid = render_mermaid(&graph);
+        assert!(mermaid.starts_with("graph LR\n  mod_testmod[\"Test Mod (mod)\"]\n"));
+        assert!(mermaid.contains("  subgraph fabric\n    target_1_21_4_fabric[\"1.21.4-fabric\"]\n  end\n"));
+        assert!(mermaid.contains("  mod_testmod -. fabric .-> dep_fabric_api\n"));
+        assert!(mermaid.contains("    content_models[\"models (2)\"]\n"));
+
+        let dot = render_dot(&graph, "testmod");
+        assert!(dot.starts_with("digraph \"testmod\" {\n"));
+        assert!(dot.contains("  subgraph \"cluster_neoforge\" {\n"));
+        assert!(dot.contains("  \"mod:testmod\" -> \"dep:neoforge\" [label=\"neoforge\", style=dashed];\n"));
+        assert!(dot.ends_with("}\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
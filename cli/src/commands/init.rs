@@ -1,15 +1,22 @@
-use crate::config::{McmodConfig, VersionTarget, Versions};
-use crate::error::Result;
-use crate::template::{self, render, strip_conditional_blocks};
+use crate::config::{Environment, FabricEntrypoint, McmodConfig, ProjectType, VersionTarget, Versions};
+use crate::error::{Context, McmodError, Result};
+use crate::git_repo::{self, EnclosingRepo, NestedMode};
+use crate::lockfile::{Lockfile, BUILT_IN_SOURCE};
+use crate::preset::Preset;
+use crate::sandbox;
+use crate::template::{self, render};
+use crate::conflict::{write_generated, ConflictPolicy};
 use crate::util::{write_binary, write_file};
 use crate::version_meta;
 use colored::Colorize;
 use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
-/// Default server.properties for dev use.
+/// Default server.properties for dev use, before the `[server]` preferences
+/// are applied by `GlobalConfig::render_server_properties`.
 /// online-mode and enforce-secure-profile are disabled so unauthenticated dev clients can connect.
-const SERVER_PROPERTIES: &str = "\
+pub const SERVER_PROPERTIES: &str = "\
 #Minecraft server properties - generated by mcmod init
 accepts-transfers=false
 allow-flight=false
@@ -80,9 +87,13 @@ pub struct InitOptions {
     pub mod_name: Option<String>,
     pub package: Option<String>,
     pub author: Option<String>,
+    pub email: Option<String>,
+    pub github: Option<String>,
     pub description: Option<String>,
+    pub project_type: Option<ProjectType>,
     pub language: Option<String>,
     pub loaders: Vec<String>,
+    pub environment: Option<Environment>,
     pub minecraft_versions: Vec<String>,
     pub ci: Option<bool>,
     pub server: Option<bool>,
@@ -90,12 +101,33 @@ pub struct InitOptions {
     pub modrinth_id: Option<String>,
     pub curseforge_id: Option<String>,
     pub testing: Option<bool>,
+    pub build_info: Option<bool>,
+    pub architectury: Option<bool>,
+    pub split_environment: Option<bool>,
+    /// Extra Fabric entrypoints; None prompts when interactive.
+    pub fabric_entrypoints: Option<Vec<FabricEntrypoint>>,
+    pub optifine: Option<bool>,
+    #[allow(dead_code)]
     pub offline: bool,
     pub force: bool,
+    /// How to place the project inside an enclosing Git repository; None
+    /// asks when interactive.
+    pub nested: Option<NestedMode>,
+    /// Answers file filling the options left unset.
+    pub preset: Option<PathBuf>,
+    /// Write the final answers here as a preset.
+    pub save_preset: Option<PathBuf>,
 }
 
-pub fn run(opts: InitOptions) -> Result<()> {
+pub fn run(mut opts: InitOptions) -> Result<()> {
     println!("{}", "\n  mcmod init\n".bold().cyan());
+    if let Some(path) = opts.preset.take() {
+        opts = Preset::load(&path)?.apply(opts);
+        println!("{}", format!("  Using answers from {}\n", path.display()).dimmed());
+    }
+    if let Some(pack) = crate::template_pack::active() {
+        println!("{}", format!("  Using template pack '{}'\n", pack.info.name).dimmed());
+    }
 
     // Warn if target directory is non-empty
     if opts.dir.exists() && !opts.force {
@@ -103,14 +135,7 @@ pub fn run(opts: InitOptions) -> Result<()> {
             .map(|mut d| d.next().is_some())
             .unwrap_or(false);
         if has_files {
-            println!(
-                "{}",
-                format!(
-                    "  Warning: {} is not empty. Files may be overwritten.",
-                    opts.dir.display()
-                )
-                .yellow()
-            );
+            crate::progress::warning(format!("{} is not empty. Files may be overwritten.", opts.dir.display()));
             let proceed = prompt_confirm("  Continue?", false)?;
             if !proceed {
                 return Err(crate::error::McmodError::Other(
@@ -122,11 +147,17 @@ pub fn run(opts: InitOptions) -> Result<()> {
 
     let interactive = opts.mod_id.is_none();
 
+    let nested = check_enclosing_repo(&opts.dir, opts.nested, interactive)?;
+
     // Load global config for defaults (never blocks init)
     let global = crate::global_config::GlobalConfig::load().unwrap_or_default();
 
-    // Derive default mod ID from directory name
-    let default_mod_id = slugify_dir_name(&opts.dir);
+    // Derive default mod ID from the mod name when given, else the directory name
+    let default_mod_id = opts
+        .mod_name
+        .as_deref()
+        .and_then(crate::naming::registry_id)
+        .unwrap_or_else(|| slugify_dir_name(&opts.dir));
 
     // Gather inputs
     let mod_id = if let Some(id) = opts.mod_id {
@@ -139,10 +170,34 @@ pub fn run(opts: InitOptions) -> Result<()> {
     let mod_name = if let Some(name) = opts.mod_name {
         name
     } else {
-        let default = default_mod_name(&mod_id);
+        // A directory named in another script is likely the display name
+        // the id was transliterated from
+        let default = match opts.dir.file_name().and_then(|n| n.to_str()) {
+            Some(name) if !name.is_ascii() && slugify_dir_name(&opts.dir) == mod_id => name.to_string(),
+            _ => default_mod_name(&mod_id),
+        };
         prompt_input("Mod Name", &default)?
     };
 
+    let project_type = if let Some(t) = opts.project_type {
+        t
+    } else if interactive {
+        let types = ["mod", "library", "datapack", "resourcepack", "paper-plugin"];
+        match prompt_select("Project type", &types, 0)?.as_str() {
+            "library" => ProjectType::Library,
+            "datapack" => ProjectType::Datapack,
+            "resourcepack" => ProjectType::Resourcepack,
+            "paper-plugin" => ProjectType::PaperPlugin,
+            _ => ProjectType::Mod,
+        }
+    } else {
+        ProjectType::Mod
+    };
+    // Packs have no code: package, language and loaders don't apply.
+    // Plugins have code but no mod loaders.
+    let pack = matches!(project_type, ProjectType::Datapack | ProjectType::Resourcepack);
+    let standalone = pack || project_type == ProjectType::PaperPlugin;
+
     let author = if let Some(a) = opts.author {
         a
     } else {
@@ -150,14 +205,48 @@ pub fn run(opts: InitOptions) -> Result<()> {
         prompt_input("Author", default_author)?
     };
 
+    let email = if let Some(e) = opts.email {
+        Some(e)
+    } else if interactive {
+        let default_email = global.defaults.email.as_deref().unwrap_or("");
+        prompt_optional("Email (optional)", default_email)?
+    } else {
+        global.defaults.email.clone()
+    };
+    if let Some(ref e) = email {
+        crate::util::validate_email(e)?;
+    }
+
+    let github = if let Some(g) = opts.github {
+        Some(g)
+    } else if interactive {
+        let default_github = global.defaults.github.as_deref().unwrap_or("");
+        prompt_optional("GitHub username (optional, used for the package and links)", default_github)?
+    } else {
+        global.defaults.github.clone()
+    };
+    if let Some(ref g) = github {
+        crate::global_config::validate_github_username(g)?;
+    }
+
     let package = if let Some(pkg) = opts.package {
         pkg
     } else {
-        let author_slug = slugify_for_package(&author);
-        let default = format!("com.{author_slug}.{mod_id}");
-        prompt_input("Package", &default)?
+        let default = crate::util::default_package(&author, github.as_deref(), &mod_id);
+        if pack {
+            default
+        } else {
+            prompt_input("Package", &default)?
+        }
     };
     crate::util::validate_package(&package)?;
+    if !pack && crate::util::is_placeholder_package(&package) {
+        crate::progress::warning(format!(
+            "'{package}' uses the com.example placeholder domain. \
+             Consider a domain you control, e.g. io.github.<username>.{mod_id} \
+             (set a default with `mcmod config set github <username>`)."
+        ));
+    }
 
     let description = if let Some(d) = opts.description {
         d
@@ -167,7 +256,7 @@ pub fn run(opts: InitOptions) -> Result<()> {
 
     let language = if let Some(l) = opts.language {
         l
-    } else if interactive {
+    } else if interactive && !pack {
         let default_idx = match global.defaults.language.as_deref() {
             Some("kotlin") => 1,
             _ => 0,
@@ -182,19 +271,26 @@ pub fn run(opts: InitOptions) -> Result<()> {
             .to_string()
     };
 
-    let loaders = if !opts.loaders.is_empty() {
+    let loaders = if standalone {
+        Vec::new()
+    } else if !opts.loaders.is_empty() {
         opts.loaders
     } else if interactive {
-        prompt_multiselect("Loaders", &["fabric", "neoforge"])?
+        prompt_loaders()?
     } else {
         vec!["fabric".to_string(), "neoforge".to_string()]
     };
 
-    if loaders.is_empty() {
+    if loaders.is_empty() && !standalone {
         return Err(crate::error::McmodError::Other(
             "At least one loader must be selected".to_string(),
         ));
     }
+    if !standalone && loaders.iter().all(|l| l == "sponge") {
+        return Err(crate::error::McmodError::Other(
+            "Sponge is a plugin module built beside the mod; select a mod loader too".to_string(),
+        ));
+    }
 
     // Minecraft version targets
     let mc_targets: Vec<String> = if !opts.minecraft_versions.is_empty() {
@@ -226,8 +322,7 @@ pub fn run(opts: InitOptions) -> Result<()> {
         }
     }
 
-    let target_refs: Vec<&str> = mc_targets.iter().map(|s| s.as_str()).collect();
-    let version_targets: Vec<VersionTarget> = version_meta::targets_to_ranges(&target_refs);
+    let mut version_targets = version_targets(&mc_targets, &loaders)?;
 
     let ci = if let Some(c) = opts.ci {
         c
@@ -237,7 +332,64 @@ pub fn run(opts: InitOptions) -> Result<()> {
         true
     };
 
-    let server = if let Some(s) = opts.server {
+    if standalone {
+        let optifine = if let Some(o) = opts.optifine {
+            o
+        } else if interactive && project_type == ProjectType::Resourcepack {
+            prompt_confirm("Add OptiFine/CTM folders (optifine/ctm, optifine/cit)?", false)?
+        } else {
+            false
+        };
+        let mut config = McmodConfig::new(
+            mod_id,
+            mod_name,
+            package,
+            author,
+            description,
+            language,
+            false,
+            false,
+            ci,
+            false,
+            None,
+            Versions {
+                targets: version_targets,
+                architectury_plugin: None,
+                architectury_loom: None,
+            },
+        );
+        config.mod_info.project_type = project_type;
+        config.mod_info.email = email;
+        config.mod_info.github = github;
+        if let Some(path) = &opts.save_preset {
+            save_preset(path, Preset::from_answers(&config, &opts.dir, false, optifine))?;
+        }
+        write_standalone_project(&opts.dir, &config, optifine)?;
+        return finish_nested(&opts.dir, nested);
+    }
+
+    // Fabric and Quilt declare the side; NeoForge and Forge load everywhere
+    let fabric_like = loaders.iter().any(|l| l == "fabric" || l == "quilt");
+    let environment = if let Some(e) = opts.environment {
+        e
+    } else if interactive && fabric_like {
+        match prompt_select("Environment", &["both", "client", "server"], 0)?.as_str() {
+            "client" => Environment::Client,
+            "server" => Environment::Server,
+            _ => Environment::Both,
+        }
+    } else {
+        Environment::Both
+    };
+
+    let server = if environment == Environment::Client {
+        if opts.server == Some(true) {
+            return Err(crate::error::McmodError::Other(
+                "A client-only mod has no dedicated server to run; drop --server or use --environment both".to_string(),
+            ));
+        }
+        false
+    } else if let Some(s) = opts.server {
         s
     } else if interactive {
         let wants_server = prompt_confirm("Enable server-side support?", false)?;
@@ -311,6 +463,70 @@ pub fn run(opts: InitOptions) -> Result<()> {
         true
     };
 
+    let build_info = if let Some(b) = opts.build_info {
+        b
+    } else if interactive {
+        prompt_confirm("Generate BuildConstants class (version, git commit, build date)?", false)?
+    } else {
+        false
+    };
+
+    let has_forge = loaders.iter().any(|l| l == "forge");
+    let architectury = if let Some(a) = opts.architectury {
+        a
+    } else if interactive && !has_forge {
+        prompt_confirm("Depend on Architectury API (cross-loader events, networking, registries)?", false)?
+    } else {
+        false
+    };
+    if architectury {
+        if has_forge {
+            return Err(crate::error::McmodError::Other(
+                "Architectury API has no Forge builds for Minecraft 1.21+; drop forge or --architectury".to_string(),
+            ));
+        }
+        crate::commands::add::pin_architectury_api(&mut version_targets)?;
+    }
+
+    let has_fabric = loaders.iter().any(|l| l == "fabric");
+    let split_environment = if let Some(s) = opts.split_environment {
+        s
+    } else if interactive && has_fabric && environment == Environment::Both {
+        prompt_confirm("Split client code into src/client (Loom splitEnvironmentSourceSets)?", false)?
+    } else {
+        false
+    };
+    if split_environment && (!has_fabric || environment != Environment::Both) {
+        return Err(crate::error::McmodError::Other(
+            "--split-environment needs the fabric loader and --environment both".to_string(),
+        ));
+    }
+
+    let is_library = project_type == ProjectType::Library;
+    let mut fabric_entrypoints = if let Some(e) = opts.fabric_entrypoints.clone() {
+        e
+    } else if interactive && has_fabric && !is_library {
+        prompt_fabric_entrypoints(split_environment || environment != Environment::Both)?
+    } else {
+        Vec::new()
+    };
+    fabric_entrypoints.sort();
+    fabric_entrypoints.dedup();
+    if !fabric_entrypoints.is_empty() {
+        if !has_fabric || is_library {
+            return Err(crate::error::McmodError::Other(
+                "--entrypoint needs the fabric loader and a mod (libraries have no entrypoints)".to_string(),
+            ));
+        }
+        if fabric_entrypoints.contains(&FabricEntrypoint::Client) && environment != Environment::Both {
+            return Err(crate::error::McmodError::Other(format!(
+                "--environment {} already uses the {} entrypoint; drop --entrypoint client",
+                environment.as_str(),
+                environment.fabric_entrypoint().0,
+            )));
+        }
+    }
+
     // Build Versions config
     let versions = Versions {
         targets: version_targets,
@@ -318,8 +534,9 @@ pub fn run(opts: InitOptions) -> Result<()> {
         architectury_loom: None,
     };
 
-    let has_fabric = loaders.iter().any(|l| l == "fabric");
     let has_neoforge = loaders.iter().any(|l| l == "neoforge");
+    let has_quilt = loaders.iter().any(|l| l == "quilt");
+    let has_sponge = loaders.iter().any(|l| l == "sponge");
 
     let publishing_config = if publishing_enabled {
         Some(crate::config::Publishing {
@@ -334,7 +551,7 @@ pub fn run(opts: InitOptions) -> Result<()> {
     };
 
     // Build McmodConfig
-    let config = McmodConfig::new(
+    let mut config = McmodConfig::new(
         mod_id.clone(),
         mod_name.clone(),
         package.clone(),
@@ -348,89 +565,39 @@ pub fn run(opts: InitOptions) -> Result<()> {
         publishing_config,
         versions,
     );
+    config.features.build_info = build_info;
+    config.features.architectury = architectury;
+    config.loaders.forge = has_forge;
+    config.loaders.quilt = has_quilt;
+    config.loaders.sponge = has_sponge;
+    config.mod_info.project_type = project_type;
+    config.mod_info.environment = environment;
+    config.features.split_environment = split_environment;
+    config.features.fabric_entrypoints = fabric_entrypoints;
+    config.mod_info.email = email;
+    config.mod_info.github = github;
+
+    if let Some(path) = &opts.save_preset {
+        save_preset(path, Preset::from_answers(&config, &opts.dir, server, false))?;
+    }
+    crate::progress::versions_resolved(&config.versions.targets);
 
-    // Build template variables
-    let vars = template::build_common_vars(&config);
-
-    // Create project directory
     let project_dir = &opts.dir;
-    crate::util::ensure_dir(project_dir)?;
-
     println!(
         "{}",
         format!("  Creating project in {}", project_dir.display()).cyan()
     );
-
-    // Write Stonecutter project files
-    write_stonecutter_files(project_dir, &config, &vars)?;
-
-    // Write unified source (root src/) with preprocessor directives
-    write_unified_source(project_dir, &vars, &language)?;
-
-    // Write resource metadata files into src/main/resources/
-    write_resource_metadata(project_dir, &vars, has_fabric, has_neoforge)?;
-
-    // Per-version properties files
-    for target in &config.versions.targets {
-        let ver_vars = template::build_version_vars(target);
-        let content = render(template::SC_VERSION_GRADLE_PROPERTIES, &ver_vars)?;
-        write_file(
-            &project_dir.join(format!("versions/dependencies/{}.properties", target.minecraft)),
-            &content,
-        )?;
-        println!(
-            "{}",
-            format!("  Created versions/dependencies/{}.properties", target.minecraft).green()
-        );
-    }
-
-    // Copy global options.txt template into run/ (shared by both loaders)
-    match create_run_options(project_dir, &global) {
-        Ok(()) => println!("{}", "  Created run/options.txt".green()),
-        Err(e) => eprintln!(
-            "  {}",
-            format!("Warning: Could not create options.txt: {e}").yellow()
-        ),
-    }
-
-    // Write dev-defaults data pack using the first target MC version
-    let active_mc = config.versions.targets.first().map(|t| t.minecraft.as_str()).unwrap_or("1.21.4");
-    match crate::pack_format::write_dev_datapack(project_dir, &global, active_mc) {
-        Ok(()) => println!(
-            "{}",
-            "  Created run/world/datapacks/dev-defaults/".green()
-        ),
-        Err(e) => eprintln!(
-            "  {}",
-            format!("Warning: Could not create dev data pack: {e}").yellow()
-        ),
-    }
-
-    // Write server files if server support enabled
-    if server {
-        write_file(
-            &project_dir.join("run/eula.txt"),
-            "# Accepted during mcmod init\n# https://aka.ms/MinecraftEULA\neula=true\n",
-        )?;
-        write_file(
-            &project_dir.join("run/server.properties"),
-            SERVER_PROPERTIES,
-        )?;
-        println!("{}", "  Created run/eula.txt (EULA accepted)".green());
-        println!(
-            "{}",
-            "  Created run/server.properties (online-mode=false)".green()
-        );
-    }
-
-    // Write CI
-    if ci {
-        crate::commands::add::add_ci_files(project_dir, &vars)?;
-        println!("{}", "  Created .github/workflows/build.yml".green());
-    }
-
-    // Write mcmod.toml
-    config.save(project_dir)?;
+    crate::fs::batched(|| {
+        // The session records the generated files' hashes in mcmod.lock
+        crate::conflict::session(project_dir, ConflictPolicy::Overwrite, || {
+            write_mod_project(project_dir, &config, &global, server)?;
+
+            // mcmod.lock records where the pins came from, so `add` reuses them
+            let mut lock = Lockfile::default();
+            lock.record_targets(&config.versions.targets, |_, _| Some(BUILT_IN_SOURCE.to_string()));
+            lock.save(project_dir)
+        })
+    })?;
 
     // Print success
     let target_list = config
@@ -452,7 +619,17 @@ pub fn run(opts: InitOptions) -> Result<()> {
     println!("  {}", format!("  Mod ID:      {mod_id}").white());
     println!("  {}", format!("  Mod Name:    {mod_name}").white());
     println!("  {}", format!("  Package:     {package}").white());
+    if let Some(ref github) = config.mod_info.github {
+        println!("  {}", format!("  GitHub:      {github}").white());
+    }
+    println!(
+        "  {}",
+        format!("  Type:        {}", project_type.as_str()).white()
+    );
     println!("  {}", format!("  Language:    {language}").white());
+    if environment != Environment::Both {
+        println!("  {}", format!("  Environment: {}", environment.as_str()).white());
+    }
     println!(
         "  {}",
         format!("  Loaders:     {}", loaders.join(", ")).white()
@@ -463,156 +640,394 @@ pub fn run(opts: InitOptions) -> Result<()> {
     );
     println!("  {}", format!("  CI:          {ci}").white());
     println!("  {}", format!("  Testing:     {testing}").white());
+    println!("  {}", format!("  Build info:  {build_info}").white());
+    if architectury {
+        println!("  {}", "  Architectury: true".white());
+    }
     println!();
     println!("  {}", "  Next steps:".bold());
     println!("    cd {}", project_dir.display());
     println!("    ./gradlew chiseledBuild");
     println!();
 
+    finish_nested(project_dir, nested)
+}
+
+/// Warn about creating the project inside another Git repository (ignored
+/// by it, or below another Gradle build) and decide how to nest it: `mode`,
+/// else asked when `interactive` (no flags given) and stdin is a terminal,
+/// else as a subdirectory with just the warnings.
+fn check_enclosing_repo(
+    dir: &Path,
+    mode: Option<NestedMode>,
+    interactive: bool,
+) -> Result<Option<(EnclosingRepo, NestedMode)>> {
+    if crate::fs::is_in_memory() {
+        return Ok(None);
+    }
+    let Some(repo) = git_repo::enclosing_repo(dir) else {
+        return Ok(None);
+    };
+    crate::progress::warning(format!(
+        "{} is inside the Git repository at {}",
+        dir.display(),
+        repo.root.display()
+    ));
+    if repo.ignored {
+        crate::progress::warning(format!(
+            "That repository ignores {}/, so the project's files would never be committed there; \
+             give it its own repository, or un-ignore the path",
+            repo.relative
+        ));
+    }
+    if let Some(settings) = &repo.gradle_settings {
+        crate::progress::warning(format!(
+            "{} defines a Gradle build above the project; the project stays a separate build \
+             (use it from there with includeBuild(\"{}\"))",
+            settings.display(),
+            relative_path(settings.parent().unwrap_or(&repo.root), &repo.root.join(&repo.relative))
+        ));
+    }
+
+    let mode = match mode {
+        Some(mode) => mode,
+        None if interactive && std::io::stdin().is_terminal() => {
+            let default = if repo.ignored { 1 } else { 0 };
+            let choice = prompt_select(
+                "Create the project",
+                &[
+                    "as a subdirectory of that repository",
+                    "as its own repository, to add as a submodule",
+                    "abort",
+                ],
+                default,
+            )?;
+            match choice.as_str() {
+                "as a subdirectory of that repository" => NestedMode::Subdirectory,
+                "as its own repository, to add as a submodule" => NestedMode::Submodule,
+                _ => return Err(McmodError::Other("Aborted — nothing was written".to_string())),
+            }
+        }
+        None => NestedMode::Subdirectory,
+    };
+    println!();
+    Ok(Some((repo, mode)))
+}
+
+/// `dir` relative to `from`, both under the same root, with `/` separators.
+fn relative_path(from: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(from) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => dir.display().to_string(),
+    }
+}
+
+/// After writing a nested project: give a submodule its own repository and
+/// print how to register it with the parent.
+fn finish_nested(dir: &Path, nested: Option<(EnclosingRepo, NestedMode)>) -> Result<()> {
+    let Some((repo, mode)) = nested else {
+        return Ok(());
+    };
+    match mode {
+        NestedMode::Subdirectory if repo.ignored => crate::progress::warning(format!(
+            "{}/ is still ignored by the repository at {}; un-ignore it to commit the project",
+            repo.relative,
+            repo.root.display()
+        )),
+        NestedMode::Subdirectory => {}
+        NestedMode::Submodule => {
+            git_repo::init(dir)?;
+            println!("{}", format!("  Initialized a Git repository in {}", dir.display()).green());
+            println!("  {}", "  To add it to the enclosing repository as a submodule:".bold());
+            println!("    git -C {0} add -A && git -C {0} commit -m \"Initial commit\"", dir.display());
+            println!("    (push it to a remote)");
+            println!("    git -C {} submodule add <remote-url> {}", repo.root.display(), repo.relative);
+            println!();
+        }
+    }
     Ok(())
 }
 
 // --- File writing ---
 
+/// Version targets for the given Minecraft versions, with loader-specific
+/// versions filled in for the selected loaders.
+pub fn version_targets(mc_targets: &[String], loaders: &[String]) -> Result<Vec<VersionTarget>> {
+    let target_refs: Vec<&str> = mc_targets.iter().map(|s| s.as_str()).collect();
+    let mut targets = version_meta::targets_to_ranges(&target_refs);
+    if loaders.iter().any(|l| l == "forge") {
+        for target in targets.iter_mut() {
+            target.forge = crate::commands::add::forge_version_for(&target.minecraft)?;
+        }
+    }
+    if loaders.iter().any(|l| l == "quilt") {
+        for target in targets.iter_mut() {
+            target.quilt_loader = version_meta::QUILT_LOADER.to_string();
+        }
+    }
+    Ok(targets)
+}
+
+/// Write every file of a Stonecutter mod or library project, including its
+/// mcmod.toml. Used by `init` and by `debug render-all`.
+pub fn write_mod_project(
+    project_dir: &Path,
+    config: &McmodConfig,
+    global: &crate::global_config::GlobalConfig,
+    server: bool,
+) -> Result<()> {
+    // Build template variables
+    let vars = template::build_common_vars(config);
+
+    crate::util::ensure_dir(project_dir)?;
+
+    // Write Stonecutter project files
+    write_stonecutter_files(project_dir, config, &vars).context("Writing Stonecutter build files")?;
+
+    // Write unified source (root src/) with preprocessor directives
+    write_unified_source(project_dir, config, &vars).context("Writing mod sources")?;
+
+    // Write resource metadata files into src/main/resources/
+    write_resource_metadata(project_dir, &vars, config).context("Writing loader metadata")?;
+
+    // Per-version properties files
+    for target in &config.versions.targets {
+        let content = template::render_version_properties(target)?;
+        write_file(
+            &project_dir.join(format!("versions/dependencies/{}.properties", target.minecraft)),
+            &content,
+        )?;
+        crate::util::progress(
+            format!("  Created versions/dependencies/{}.properties", target.minecraft).green(),
+        );
+    }
+
+    for path in crate::commands::sync::write_gradle_overrides(project_dir, config)? {
+        crate::util::progress(format!("  Created {path}").green());
+    }
+
+    // Dev run directories: options, dev packs, and server files
+    write_dev_defaults(project_dir, config, global, &config.run_dirs(), server)?;
+
+    // Sponge plugin module beside the mod
+    if config.loaders.sponge {
+        crate::plugin_project::write_sponge_module(project_dir, config, &vars)
+            .context("Writing the sponge/ plugin module")?;
+        crate::util::progress("  Created sponge/ plugin module".green());
+    }
+
+    // Write CI
+    if config.features.ci {
+        crate::commands::add::add_ci_files(project_dir, &vars).context("Writing CI workflows")?;
+        crate::util::progress("  Created .github/workflows/build.yml".green());
+    }
+
+    // Write mcmod.toml
+    config.save(project_dir)?;
+
+    Ok(())
+}
+
+/// Write a pack or plugin project and its mcmod.toml, then print next steps.
+fn write_standalone_project(dir: &Path, config: &McmodConfig, optifine: bool) -> Result<()> {
+    let vars = template::build_common_vars(config);
+    crate::util::ensure_dir(dir)?;
+    println!(
+        "{}",
+        format!("  Creating project in {}", dir.display()).cyan()
+    );
+
+    crate::fs::batched(|| {
+        if config.is_plugin() {
+            crate::plugin_project::write_project(dir, config, &vars)?;
+        } else {
+            crate::pack_project::write_project(dir, config, &vars, optifine)?;
+        }
+        config.save(dir)
+    })?;
+
+    // Only report the files once the batch has been flushed to disk
+    if config.is_plugin() {
+        println!("{}", "  Created paper-plugin.yml and main class".green());
+    } else {
+        let content_dir = if config.is_datapack() { "data/" } else { "assets/" };
+        println!("{}", format!("  Created pack.mcmeta and {content_dir}").green());
+    }
+    if config.features.ci {
+        println!("{}", "  Created .github/workflows/build.yml".green());
+    }
+
+    let targets = &config.versions.targets;
+    let min = targets.first().map(|t| t.minecraft.as_str()).unwrap_or_default();
+    let max = targets.last().map(|t| t.max_minecraft.as_str()).unwrap_or_default();
+
+    println!("\n{}", "  Project created successfully!".bold().green());
+    println!();
+    println!("  {}", format!("  ID:          {}", config.mod_info.mod_id).white());
+    println!("  {}", format!("  Name:        {}", config.mod_info.mod_name).white());
+    println!(
+        "  {}",
+        format!("  Type:        {}", config.mod_info.project_type.as_str()).white()
+    );
+    println!("  {}", format!("  MC Range:    {min}-{max}").white());
+    println!("  {}", format!("  CI:          {}", config.features.ci).white());
+    println!();
+    println!("  {}", "  Next steps:".bold());
+    println!("    cd {}", dir.display());
+    println!("    ./gradlew build");
+    if config.is_plugin() {
+        println!("    ./gradlew runServer");
+    }
+    println!();
+    Ok(())
+}
+
 fn write_stonecutter_files(
     dir: &Path,
     config: &McmodConfig,
     vars: &HashMap<String, String>,
 ) -> Result<()> {
-    let has_fabric = config.loaders.fabric;
-    let has_neoforge = config.loaders.neoforge;
-    let is_kotlin = config.mod_info.language == "kotlin";
-
-    let conditions = &[
-        ("fabric", has_fabric),
-        ("neoforge", has_neoforge),
-        ("kotlin", is_kotlin),
-    ];
-
     // stonecutter.gradle.kts
-    write_file(
+    write_generated(
         &dir.join("stonecutter.gradle.kts"),
         &render(template::SC_STONECUTTER_GRADLE, vars)?,
     )?;
 
-    // settings.gradle.kts — strip conditional blocks first, then render
-    let settings = strip_conditional_blocks(template::SC_SETTINGS_GRADLE, conditions);
-    write_file(&dir.join("settings.gradle.kts"), &render(&settings, vars)?)?;
-
-    // build.gradle.kts — strip conditionals first (removes {{kotlin_version}} if not kotlin), then render
-    let build = strip_conditional_blocks(template::SC_BUILD_GRADLE, conditions);
-    write_file(&dir.join("build.gradle.kts"), &render(&build, vars)?)?;
+    // settings.gradle.kts and build.gradle.kts — loader/language blocks resolved per config
+    write_generated(
+        &dir.join("settings.gradle.kts"),
+        &template::render_settings_gradle(config, vars)?,
+    )?;
+    write_generated(
+        &dir.join("build.gradle.kts"),
+        &template::render_build_gradle(config, vars)?,
+    )?;
 
     // gradle.properties — shared props
-    write_file(
+    write_generated(
         &dir.join("gradle.properties"),
         &render(template::SC_GRADLE_PROPERTIES, vars)?,
     )?;
 
     // .gitignore
-    write_file(&dir.join(".gitignore"), template::TMPL_GITIGNORE)?;
+    write_generated(&dir.join(".gitignore"), crate::template_pack::resolve(template::TMPL_GITIGNORE))?;
 
     // LICENSE
-    write_file(&dir.join("LICENSE"), &render(template::TMPL_LICENSE, vars)?)?;
+    write_generated(&dir.join("LICENSE"), &render(template::TMPL_LICENSE, vars)?)?;
+
+    // README.md
+    write_generated(&dir.join("README.md"), &template::render_optional(template::TMPL_README, vars)?)?;
+    write_generated(&dir.join("docs/DEVELOPMENT.md"), &template::render_development_doc(config, vars)?)?;
 
     // Gradle wrapper
     write_binary(
         &dir.join("gradle/wrapper/gradle-wrapper.jar"),
         template::GRADLE_WRAPPER_JAR,
     )?;
-    write_file(
+    write_generated(
         &dir.join("gradle/wrapper/gradle-wrapper.properties"),
         template::GRADLE_WRAPPER_PROPS,
     )?;
-    write_binary(&dir.join("gradlew"), template::GRADLEW)?;
-    write_binary(&dir.join("gradlew.bat"), template::GRADLEW_BAT)?;
-
-    // Set gradlew as executable (Unix)
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(dir.join("gradlew"))?.permissions();
-        perms.set_mode(0o755);
-        std::fs::set_permissions(dir.join("gradlew"), perms)?;
-    }
+    // Line endings and the executable bit come from the file policy
+    write_generated(&dir.join("gradlew"), template::GRADLEW)?;
+    write_generated(&dir.join("gradlew.bat"), template::GRADLEW_BAT)?;
 
-    println!("{}", "  Created Stonecutter project files".green());
+    crate::util::progress("  Created Stonecutter project files".green());
     Ok(())
 }
 
 /// Write the unified mod source file with Stonecutter preprocessor directives.
 fn write_unified_source(
     dir: &Path,
+    config: &McmodConfig,
     vars: &HashMap<String, String>,
-    language: &str,
 ) -> Result<()> {
-    let package_path = vars.get("package_path").unwrap();
-    let class_name = vars.get("class_name").unwrap();
     let mod_id = vars.get("mod_id").unwrap();
 
-    let (template, ext, source_dir) = if language == "kotlin" {
-        (template::SC_UNIFIED_MOD_KT, "kt", "kotlin")
-    } else {
-        (template::SC_UNIFIED_MOD_JAVA, "java", "java")
-    };
+    let (source, source_path) = template::render_unified_mod(config, vars)?;
+    write_generated(&sandbox::resolve_output(dir, source_path)?, &source)?;
 
-    let source_path = dir.join(format!(
-        "src/main/{source_dir}/{package_path}/{class_name}.{ext}"
-    ));
-    write_file(&source_path, &render(template, vars)?)?;
+    if config.features.architectury {
+        let (helper, helper_path) = template::render_platform_helper(config, vars)?;
+        write_generated(&sandbox::resolve_output(dir, helper_path)?, &helper)?;
+    }
+
+    for (source, path) in template::render_fabric_entrypoints(vars)? {
+        write_generated(&sandbox::resolve_output(dir, path)?, &source)?;
+    }
 
     // assets/<mod_id>/icon.png.txt
-    write_file(
+    write_generated(
         &dir.join(format!(
             "src/main/resources/assets/{mod_id}/icon.png.txt"
         )),
         "Replace this file with your mod icon (icon.png)\n",
     )?;
 
-    println!("{}", "  Created unified source in src/".green());
+    crate::util::progress("  Created unified source in src/".green());
     Ok(())
 }
 
-/// Write resource metadata files (fabric.mod.json, neoforge.mods.toml, mixins.json)
-/// into the unified src/main/resources/ directory.
+/// Write resource metadata files (fabric.mod.json, neoforge.mods.toml, mods.toml,
+/// quilt.mod.json, mixins.json) into the unified src/main/resources/ directory.
 fn write_resource_metadata(
     dir: &Path,
     vars: &HashMap<String, String>,
-    has_fabric: bool,
-    has_neoforge: bool,
+    config: &McmodConfig,
 ) -> Result<()> {
     let package_path = vars.get("package_path").unwrap();
     let mod_id = vars.get("mod_id").unwrap();
 
-    if has_fabric {
-        write_file(
-            &dir.join("src/main/resources/fabric.mod.json"),
-            &render(template::SC_FABRIC_MOD_JSON, vars)?,
+    if config.loaders.fabric {
+        write_generated(
+            &dir.join(crate::commands::sync::FABRIC_MOD_JSON),
+            &crate::commands::sync::apply_fabric(
+                &template::render_optional(template::SC_FABRIC_MOD_JSON, vars)?,
+                &config.fabric,
+            )?,
         )?;
     }
 
-    if has_neoforge {
-        write_file(
-            &dir.join("src/main/resources/META-INF/neoforge.mods.toml"),
-            &render(template::SC_NEOFORGE_MODS_TOML, vars)?,
+    if config.loaders.neoforge {
+        write_generated(
+            &dir.join(crate::commands::sync::NEOFORGE_MODS_TOML),
+            &crate::commands::sync::apply_neoforge(
+                &template::render_optional(template::SC_NEOFORGE_MODS_TOML, vars)?,
+                &config.neoforge,
+                mod_id,
+            ),
+        )?;
+    }
+
+    if config.loaders.forge {
+        write_generated(
+            &dir.join("src/main/resources/META-INF/mods.toml"),
+            &template::render_optional(template::SC_FORGE_MODS_TOML, vars)?,
+        )?;
+    }
+
+    if config.loaders.quilt {
+        write_generated(
+            &dir.join(crate::commands::sync::QUILT_MOD_JSON),
+            &template::render_optional(template::SC_QUILT_MOD_JSON, vars)?,
         )?;
     }
 
     // Shared mixins JSON
-    write_file(
+    write_generated(
         &dir.join(format!("src/main/resources/{mod_id}.mixins.json")),
         &render(template::TMPL_FABRIC_MIXINS_JSON, vars)?,
     )?;
 
     // Mixin package-info.java (always in java source tree, even for kotlin)
-    write_file(
+    write_generated(
         &dir.join(format!(
             "src/main/java/{package_path}/mixin/package-info.java"
         )),
         &render(template::TMPL_FABRIC_MIXIN_PACKAGE_INFO, vars)?,
     )?;
 
-    println!("{}", "  Created resource metadata".green());
+    crate::util::progress("  Created resource metadata".green());
     Ok(())
 }
 
@@ -627,6 +1042,22 @@ fn prompt_input(prompt: &str, default: &str) -> Result<String> {
     Ok(result)
 }
 
+/// Prompt for an optional value; an empty answer yields None.
+fn prompt_optional(prompt: &str, default: &str) -> Result<Option<String>> {
+    let result = dialoguer::Input::<String>::new()
+        .with_prompt(format!("  {prompt}"))
+        .default(default.to_string())
+        .allow_empty(true)
+        .interact_text()
+        .map_err(|e| crate::error::McmodError::Other(e.to_string()))?;
+    let trimmed = result.trim();
+    Ok(if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    })
+}
+
 fn prompt_select(prompt: &str, items: &[&str], default: usize) -> Result<String> {
     let selection = dialoguer::Select::new()
         .with_prompt(format!("  {prompt}"))
@@ -648,6 +1079,35 @@ fn prompt_multiselect(prompt: &str, items: &[&str]) -> Result<Vec<String>> {
     Ok(selections.iter().map(|&i| items[i].to_string()).collect())
 }
 
+/// Loader checklist: Fabric and NeoForge are checked by default, Forge,
+/// Quilt, and the Sponge plugin module are opt-in.
+fn prompt_loaders() -> Result<Vec<String>> {
+    let items = ["fabric", "neoforge", "forge", "quilt", "sponge"];
+    let selections = dialoguer::MultiSelect::new()
+        .with_prompt("  Loaders")
+        .items(items)
+        .defaults(&[true, true, false, false, false])
+        .interact()
+        .map_err(|e| crate::error::McmodError::Other(e.to_string()))?;
+    Ok(selections.iter().map(|&i| items[i].to_string()).collect())
+}
+
+/// Extra Fabric entrypoint checklist, all unchecked. `skip_client` leaves out
+/// the client entrypoint when the project already has one.
+fn prompt_fabric_entrypoints(skip_client: bool) -> Result<Vec<FabricEntrypoint>> {
+    let choices: Vec<FabricEntrypoint> = FabricEntrypoint::ALL
+        .into_iter()
+        .filter(|e| !(skip_client && *e == FabricEntrypoint::Client))
+        .collect();
+    let items: Vec<&str> = choices.iter().map(|e| e.description()).collect();
+    let selections = dialoguer::MultiSelect::new()
+        .with_prompt("  Extra Fabric entrypoints")
+        .items(&items)
+        .interact()
+        .map_err(|e| crate::error::McmodError::Other(e.to_string()))?;
+    Ok(selections.iter().map(|&i| choices[i]).collect())
+}
+
 fn prompt_confirm(prompt: &str, default: bool) -> Result<bool> {
     let result = dialoguer::Confirm::new()
         .with_prompt(format!("  {prompt}"))
@@ -657,38 +1117,86 @@ fn prompt_confirm(prompt: &str, default: bool) -> Result<bool> {
     Ok(result)
 }
 
-fn create_run_options(
+/// Populate dev run directories (`run`, or `run/<loader>` with per-loader run
+/// dirs): the dev resource pack and the options.txt enabling it, the
+/// dev-defaults data pack, and with `server` an accepted eula.txt and
+/// server.properties. Used by init and `mcmod dev run-dirs`.
+pub fn write_dev_defaults(
     project_dir: &Path,
-    config: &crate::global_config::GlobalConfig,
+    config: &McmodConfig,
+    global: &crate::global_config::GlobalConfig,
+    run_dirs: &[String],
+    server: bool,
 ) -> Result<()> {
-    let run_dir = project_dir.join("run");
-    crate::util::ensure_dir(&run_dir)?;
-    crate::global_config::copy_options_to(&run_dir.join("options.txt"), config)
-}
+    let active_mc = config.versions.targets.first().map(|t| t.minecraft.as_str()).unwrap_or("1.21.4");
+    for run in run_dirs {
+        let run_dir = project_dir.join(run);
+
+        // Dev resource pack, enabled by the options.txt below
+        let dev_packs = match crate::pack_format::write_dev_resourcepack(&run_dir, active_mc) {
+            Ok(()) => {
+                crate::util::progress(
+                    format!("  Created {run}/resourcepacks/{}/", crate::pack_format::DEV_RESOURCE_PACK).green(),
+                );
+                vec![format!("file/{}", crate::pack_format::DEV_RESOURCE_PACK)]
+            }
+            Err(e) => {
+                crate::progress::warning(format!("Could not create dev resource pack: {e}"));
+                Vec::new()
+            }
+        };
 
-/// Converts an author name to a valid Java package segment (lowercase, alphanumeric).
-fn slugify_for_package(author: &str) -> String {
-    let slug: String = author
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .map(|c| c.to_ascii_lowercase())
-        .collect();
+        // Copy global options.txt template into the run directory
+        let dev_packs: Vec<&str> = dev_packs.iter().map(String::as_str).collect();
+        match create_run_options(&run_dir, global, &dev_packs) {
+            Ok(()) => crate::util::progress(format!("  Created {run}/options.txt").green()),
+            Err(e) => crate::progress::warning(format!("Could not create options.txt: {e}")),
+        }
 
-    if slug.is_empty() || slug.starts_with(|c: char| c.is_ascii_digit()) {
-        "example".to_string()
-    } else {
-        slug
+        // Write dev-defaults data pack using the first target MC version
+        match crate::pack_format::write_dev_datapack(&run_dir, global, active_mc) {
+            Ok(()) => crate::util::progress(format!("  Created {run}/world/datapacks/dev-defaults/").green()),
+            Err(e) => crate::progress::warning(format!("Could not create dev data pack: {e}")),
+        }
+
+        // Write server files if server support enabled
+        if server {
+            write_file(
+                &run_dir.join("eula.txt"),
+                "# Accepted during mcmod init\n# https://aka.ms/MinecraftEULA\neula=true\n",
+            )?;
+            write_file(&run_dir.join("server.properties"), &global.render_server_properties())?;
+            crate::util::progress(format!("  Created {run}/eula.txt (EULA accepted)").green());
+            crate::util::progress(format!("  Created {run}/server.properties (online-mode=false)").green());
+        }
     }
+    Ok(())
 }
 
-/// Converts a directory name to a valid mod ID (lowercase, underscores).
-fn slugify_dir_name(dir: &Path) -> String {
+fn create_run_options(
+    run_dir: &Path,
+    config: &crate::global_config::GlobalConfig,
+    dev_packs: &[&str],
+) -> Result<()> {
+    crate::util::ensure_dir(run_dir)?;
+    crate::global_config::copy_options_to(&run_dir.join("options.txt"), config, dev_packs)
+}
+
+fn save_preset(path: &Path, preset: Preset) -> Result<()> {
+    preset.save(path)?;
+    println!("{}", format!("  Saved these answers to {} (use with --preset)", path.display()).green());
+    Ok(())
+}
+
+/// Converts a directory name to a valid mod ID (lowercase, underscores),
+/// transliterating non-ASCII letters: "Crème" -> "creme".
+pub fn slugify_dir_name(dir: &Path) -> String {
     let name = dir
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("mymod");
 
-    let slug: String = name
+    let slug: String = crate::util::transliterate(name)
         .chars()
         .map(|c| {
             if c.is_ascii_alphanumeric() {
@@ -712,7 +1220,7 @@ fn slugify_dir_name(dir: &Path) -> String {
     }
 }
 
-fn default_mod_name(mod_id: &str) -> String {
+pub fn default_mod_name(mod_id: &str) -> String {
     mod_id
         .split('_')
         .filter(|s| !s.is_empty())
@@ -730,3 +1238,155 @@ fn default_mod_name(mod_id: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Fully specified options, so `run` never prompts.
+    pub(crate) fn options(dir: &Path, loaders: &[&str]) -> InitOptions {
+        InitOptions {
+            dir: dir.to_path_buf(),
+            mod_id: Some("testmod".to_string()),
+            mod_name: Some("Test Mod".to_string()),
+            package: Some("com.example.testmod".to_string()),
+            author: Some("Author".to_string()),
+            email: None,
+            github: None,
+            description: Some("A test mod".to_string()),
+            project_type: Some(ProjectType::Mod),
+            language: Some("java".to_string()),
+            loaders: loaders.iter().map(|l| l.to_string()).collect(),
+            environment: None,
+            minecraft_versions: vec!["1.21.4".to_string()],
+            ci: Some(true),
+            server: Some(false),
+            publishing: Some(false),
+            modrinth_id: None,
+            curseforge_id: None,
+            testing: Some(false),
+            build_info: Some(false),
+            architectury: Some(false),
+            split_environment: None,
+            fabric_entrypoints: Some(Vec::new()),
+            optifine: None,
+            offline: true,
+            force: true,
+            nested: None,
+            preset: None,
+            save_preset: None,
+        }
+    }
+
+    #[test]
+    fn test_slugify_dir_name() {
+        assert_eq!(slugify_dir_name(Path::new("/mods/My-Cool Mod")), "my_cool_mod");
+        assert_eq!(slugify_dir_name(Path::new("/mods/Crème Brûlée")), "creme_brulee");
+        assert_eq!(slugify_dir_name(Path::new("/mods/Волшебство")), "volshebstvo");
+        assert_eq!(slugify_dir_name(Path::new("/mods/魔法")), "mymod");
+        assert_eq!(slugify_dir_name(Path::new("/mods/2fast")), "mymod");
+    }
+
+    #[test]
+    fn test_init_project_tree() {
+        let dir = Path::new("/memory/testmod");
+        let (result, fs) = crate::fs::in_memory(|| run(options(dir, &["fabric", "neoforge"])));
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        for expected in [
+            "mcmod.toml",
+            "settings.gradle.kts",
+            "stonecutter.gradle.kts",
+            "build.gradle.kts",
+            "gradlew",
+            "gradle/wrapper/gradle-wrapper.jar",
+            ".github/workflows/build.yml",
+            "src/main/java/com/example/testmod/TestmodMod.java",
+            "src/main/resources/fabric.mod.json",
+            "src/main/resources/META-INF/neoforge.mods.toml",
+            "src/main/resources/testmod.mixins.json",
+            "versions/dependencies/1.21.4.properties",
+            "run/resourcepacks/dev-resources/pack.mcmeta",
+        ] {
+            assert!(tree.iter().any(|p| p == expected), "missing {expected} in {tree:?}");
+        }
+        assert!(!tree.iter().any(|p| p.ends_with("quilt.mod.json")));
+        assert_eq!(fs.mode(&dir.join("gradlew")), Some(0o755));
+
+        let settings = fs.read_string(&dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("mc(\"1.21.4\", \"fabric\", \"neoforge\")"));
+        let options = fs.read_string(&dir.join("run/options.txt")).unwrap();
+        assert!(options.contains("resourcePacks:[\"vanilla\",\"file/dev-resources\"]"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert_eq!(config.mod_info.mod_id, "testmod");
+        assert!(config.loaders.fabric && config.loaders.neoforge);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_init_client_environment() {
+        let dir = Path::new("/memory/clientmod");
+        let mut opts = options(dir, &["fabric"]);
+        opts.environment = Some(Environment::Client);
+        let (result, fs) = crate::fs::in_memory(|| run(opts));
+        result.unwrap();
+
+        let fabric_json = fs.read_string(&dir.join("src/main/resources/fabric.mod.json")).unwrap();
+        assert!(fabric_json.contains("\"environment\": \"client\""));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert_eq!(config.mod_info.environment, Environment::Client);
+
+        let mut opts = options(dir, &["fabric"]);
+        opts.environment = Some(Environment::Client);
+        opts.server = Some(true);
+        let (result, _) = crate::fs::in_memory(|| run(opts));
+        assert!(result.unwrap_err().to_string().contains("client-only"));
+    }
+
+    #[test]
+    fn test_init_split_environment() {
+        let dir = Path::new("/memory/splitmod");
+        let mut opts = options(dir, &["fabric", "neoforge"]);
+        opts.language = Some("kotlin".to_string());
+        opts.split_environment = Some(true);
+        let (result, fs) = crate::fs::in_memory(|| run(opts));
+        result.unwrap();
+
+        assert!(fs.tree(dir).iter().any(|p| p == "src/client/kotlin/com/example/testmod/TestmodModClient.kt"));
+        let build = fs.read_string(&dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("kotlin.srcDir(\"src/client/kotlin\")"));
+
+        let mut opts = options(dir, &["neoforge"]);
+        opts.split_environment = Some(true);
+        let (result, _) = crate::fs::in_memory(|| run(opts));
+        assert!(result.unwrap_err().to_string().contains("needs the fabric loader"));
+    }
+
+    #[test]
+    fn test_init_fabric_entrypoints() {
+        let dir = Path::new("/memory/entrymod");
+        let mut opts = options(dir, &["fabric"]);
+        opts.fabric_entrypoints = Some(vec![FabricEntrypoint::PreLaunch, FabricEntrypoint::Datagen]);
+        let (result, fs) = crate::fs::in_memory(|| run(opts));
+        result.unwrap();
+
+        let tree = fs.tree(dir);
+        assert!(tree.iter().any(|p| p == "src/main/java/com/example/testmod/TestmodModDataGenerator.java"));
+        assert!(tree.iter().any(|p| p == "src/main/java/com/example/testmod/TestmodModPreLaunch.java"));
+        assert!(!tree.iter().any(|p| p.ends_with("TestmodModClient.java")));
+        let fabric_json = fs.read_string(&dir.join("src/main/resources/fabric.mod.json")).unwrap();
+        assert!(fabric_json.contains("\"preLaunch\": [\"${group}.TestmodModPreLaunch\"]"));
+        let config: McmodConfig = toml::from_str(&fs.read_string(&dir.join("mcmod.toml")).unwrap()).unwrap();
+        assert_eq!(
+            config.features.fabric_entrypoints,
+            [FabricEntrypoint::Datagen, FabricEntrypoint::PreLaunch]
+        );
+
+        let mut opts = options(dir, &["fabric"]);
+        opts.environment = Some(Environment::Client);
+        opts.fabric_entrypoints = Some(vec![FabricEntrypoint::Client]);
+        let (result, _) = crate::fs::in_memory(|| run(opts));
+        assert!(result.unwrap_err().to_string().contains("drop --entrypoint client"));
+    }
+}
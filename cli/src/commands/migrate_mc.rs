@@ -0,0 +1,383 @@
+use crate::commands::upgrade::{self, Change, Dep};
+use crate::config::{McmodConfig, ProjectType};
+use crate::content::{self, ContentManifest};
+use crate::diff;
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use crate::lockfile::{Lockfile, BUILT_IN_SOURCE};
+use crate::version_meta::{self, compare_versions};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Data pack folders Minecraft 1.21 renamed to singular names, relative to
+/// `data/<namespace>/`.
+const DATA_RENAMES: &[(&str, &str)] = &[
+    ("advancements", "advancement"),
+    ("functions", "function"),
+    ("item_modifiers", "item_modifier"),
+    ("loot_tables", "loot_table"),
+    ("predicates", "predicate"),
+    ("recipes", "recipe"),
+    ("structures", "structure"),
+    ("tags/blocks", "tags/block"),
+    ("tags/entity_types", "tags/entity_type"),
+    ("tags/fluids", "tags/fluid"),
+    ("tags/functions", "tags/function"),
+    ("tags/game_events", "tags/game_event"),
+    ("tags/items", "tags/item"),
+];
+
+/// Mixin `compatibilityLevel` required since Minecraft 1.20.5 (Java 21).
+const MIXIN_LEVEL: &str = "JAVA_21";
+
+/// What a manual step touches, so packs only see the ones that apply to them.
+#[derive(Clone, Copy, PartialEq)]
+enum Area {
+    Code,
+    Data,
+    Assets,
+}
+
+/// Breaking changes that can't be migrated mechanically, as (first release
+/// affected, area, what to port). Reported when a migration crosses that release.
+const MANUAL_STEPS: &[(&str, Area, &str)] = &[
+    (
+        "1.21.2",
+        Area::Code,
+        "Items and blocks must set their registry key on the settings (`Item.Properties#setId`, `BlockBehaviour.Properties#setId`) before construction",
+    ),
+    (
+        "1.21.2",
+        Area::Data,
+        "Recipe ingredients are plain strings (`\"minecraft:stick\"`, `\"#minecraft:planks\"`) instead of `{\"item\": ...}` objects",
+    ),
+    (
+        "1.21.4",
+        Area::Assets,
+        "Every item needs an item model definition in `assets/<namespace>/items/<item>.json`",
+    ),
+    (
+        "1.21.6",
+        Area::Code,
+        "Entity and block entity data is saved and loaded through `ValueOutput`/`ValueInput` instead of `CompoundTag`",
+    ),
+    (
+        "1.21.6",
+        Area::Code,
+        "GUI rendering is deferred: `GuiGraphics` calls are submitted and drawn later, so custom screens may need updating",
+    ),
+    (
+        "1.21.9",
+        Area::Code,
+        "Entity and block entity renderers use render states and `SubmitNodeCollector` instead of drawing directly",
+    ),
+];
+
+/// What a migration did and what it left for the user.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub changed: Vec<String>,
+    pub manual: Vec<String>,
+}
+
+/// `mcmod migrate --to <version>`: move the newest target to `version`, then
+/// apply the mechanical migrations for generated files and list the manual ones.
+pub fn run(dir: &Path, version: &str) -> Result<()> {
+    println!("{}", format!("\n  mcmod migrate --to {version}\n").bold().cyan());
+
+    let mut config = McmodConfig::load(dir)?;
+    if version_meta::get_version_meta(version).is_none() {
+        return Err(McmodError::Other(format!(
+            "Unsupported Minecraft version: {version}. Supported: {}",
+            version_meta::supported_versions().join(", ")
+        )));
+    }
+    let from = config
+        .versions
+        .targets
+        .last()
+        .map(|t| t.minecraft.clone())
+        .ok_or_else(|| McmodError::Other("mcmod.toml has no version targets".to_string()))?;
+    if !compare_versions(version, &from).is_gt() {
+        return Err(McmodError::Other(format!(
+            "Already on Minecraft {from}; migrate only moves to newer versions"
+        )));
+    }
+
+    let mut report = Report::default();
+    retarget(dir, &mut config, &from, version, &mut report)?;
+
+    let resources = if config.is_pack() {
+        dir.to_path_buf()
+    } else {
+        dir.join("src/main/resources")
+    };
+    rename_data_folders(&resources, &mut report)?;
+    if config.uses_stonecutter() {
+        let mixins = resources.join(format!("{}.mixins.json", config.mod_info.mod_id));
+        update_mixin_level(&mixins, &mut report)?;
+        regenerate_content(dir, &config, version, &mut report)?;
+        refresh_dev_packs(dir, &config, &mut report)?;
+    }
+    let areas: &[Area] = match config.mod_info.project_type {
+        ProjectType::Mod | ProjectType::Library => &[Area::Code, Area::Data, Area::Assets],
+        ProjectType::Datapack => &[Area::Data],
+        ProjectType::Resourcepack => &[Area::Assets],
+        ProjectType::PaperPlugin => &[],
+    };
+    report.manual.extend(manual_steps(&from, version, areas));
+
+    println!();
+    if report.changed.is_empty() {
+        println!("{}", "  No generated files needed changes.".green());
+    } else {
+        println!("{}", "  Changed:".bold().green());
+        for line in &report.changed {
+            println!("    {} {line}", "✓".green());
+        }
+    }
+    if !report.manual.is_empty() {
+        println!();
+        println!("{}", "  Manual porting checklist:".bold().yellow());
+        for line in &report.manual {
+            println!("    {} {line}", "[ ]".yellow());
+        }
+    }
+    Ok(())
+}
+
+/// Move the newest target from `from` to `to` with the known-good dependency
+/// versions for `to`, and refresh the files that depend on it.
+fn retarget(dir: &Path, config: &mut McmodConfig, from: &str, to: &str, report: &mut Report) -> Result<()> {
+    if config.uses_stonecutter() {
+        let meta = version_meta::get_version_meta(to).expect("checked by caller");
+        let target = config.versions.targets.last().expect("checked by caller").clone();
+        let forge = if config.loaders.forge {
+            crate::commands::add::forge_version_for(to)?
+        } else {
+            String::new()
+        };
+        let mut changes = vec![Change {
+            target: from.to_string(),
+            dep: Dep::Minecraft,
+            from: from.to_string(),
+            to: to.to_string(),
+            source: BUILT_IN_SOURCE.to_string(),
+        }];
+        let deps = [
+            (Dep::FabricLoader, config.loaders.fabric, &target.fabric_loader, meta.fabric_loader),
+            (Dep::FabricApi, config.loaders.fabric || config.loaders.quilt, &target.fabric_api, meta.fabric_api),
+            (Dep::Neoforge, config.loaders.neoforge, &target.neoforge, meta.neoforge),
+            (Dep::Forge, config.loaders.forge, &target.forge, forge.as_str()),
+        ];
+        for (dep, enabled, current, new) in deps {
+            if enabled && current != new {
+                changes.push(Change {
+                    target: from.to_string(),
+                    dep,
+                    from: current.clone(),
+                    to: new.to_string(),
+                    source: BUILT_IN_SOURCE.to_string(),
+                });
+            }
+        }
+        let selected: Vec<&Change> = changes.iter().collect();
+        let mut lock = Lockfile::load(dir)?;
+        upgrade::apply(dir, config, &mut lock, &selected)?;
+        lock.save(dir)?;
+        report
+            .changed
+            .extend(changes.iter().map(|c| format!("{} {} → {}", c.dep.key(), c.from, c.to)));
+        return Ok(());
+    }
+
+    // Packs and plugins: the target drives pack.mcmeta or the Paper version
+    if let Some(target) = config.versions.targets.last_mut() {
+        target.minecraft = to.to_string();
+        if compare_versions(to, &target.max_minecraft).is_gt() {
+            target.max_minecraft = to.to_string();
+        }
+    }
+    report.changed.push(format!("minecraft {from} → {to}"));
+    let mut edits = Vec::new();
+    if config.is_pack() {
+        edits.push(("pack.mcmeta", crate::pack_project::pack_mcmeta(config)));
+    } else {
+        // Only the Paper version lines; the rest of these files is the user's
+        let props = std::fs::read_to_string(dir.join("gradle.properties")).unwrap_or_default();
+        let props = gradle::set_property_content(&props, "paper.minecraft", to);
+        let props = gradle::set_property_content(&props, "paper.version", &format!("{to}-R0.1-SNAPSHOT"));
+        edits.push(("gradle.properties", props));
+        let yml_path = "src/main/resources/paper-plugin.yml";
+        let yml = std::fs::read_to_string(dir.join(yml_path)).unwrap_or_default();
+        let yml = yml
+            .lines()
+            .map(|l| if l.starts_with("api-version:") { format!("api-version: '{to}'") } else { l.to_string() })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+        edits.push((yml_path, yml));
+    }
+    for (rel, content) in edits {
+        let path = dir.join(rel);
+        let old = std::fs::read_to_string(&path).unwrap_or_default();
+        if diff::apply_edit(&path, &old, &content)? {
+            report.changed.push(format!("{rel} updated for Minecraft {to}"));
+        }
+    }
+    config.save(dir)?;
+    Ok(())
+}
+
+/// Generated resources differ between releases (1.21.4 added item model
+/// definitions), so rerun the content.toml generators for the new target.
+/// Only missing files are created; existing ones are never touched.
+fn regenerate_content(dir: &Path, config: &McmodConfig, to: &str, report: &mut Report) -> Result<()> {
+    let Some(manifest) = ContentManifest::load(dir)? else {
+        return Ok(());
+    };
+    for rel in content::generate(dir, config, &manifest)? {
+        report
+            .changed
+            .push(format!("assets/{}/{rel} generated for Minecraft {to}", config.mod_info.mod_id));
+    }
+    Ok(())
+}
+
+/// Move the dev packs in each run directory to the pack format of the
+/// version dev runs use (the first target).
+fn refresh_dev_packs(dir: &Path, config: &McmodConfig, report: &mut Report) -> Result<()> {
+    let Some(active) = config.versions.targets.first() else {
+        return Ok(());
+    };
+    for run in config.run_dirs() {
+        for rel in crate::pack_format::update_dev_pack_formats(&dir.join(&run), &active.minecraft)? {
+            report.changed.push(format!("{run}/{rel}: pack format for {}", active.minecraft));
+        }
+    }
+    Ok(())
+}
+
+/// Rename pre-1.21 plural data folders in every namespace under `data/`.
+/// A folder is left alone (and reported) when the new name already exists.
+pub fn rename_data_folders(resources: &Path, report: &mut Report) -> Result<()> {
+    let Ok(namespaces) = std::fs::read_dir(resources.join("data")) else {
+        return Ok(());
+    };
+    let mut namespaces: Vec<PathBuf> = namespaces.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    namespaces.sort();
+
+    for ns in namespaces {
+        let ns_name = ns.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        for (old, new) in DATA_RENAMES {
+            let old_path = ns.join(old);
+            if !old_path.is_dir() {
+                continue;
+            }
+            let new_path = ns.join(new);
+            if new_path.exists() {
+                report.manual.push(format!(
+                    "data/{ns_name}/{old} and data/{ns_name}/{new} both exist; merge them into {new}/"
+                ));
+                continue;
+            }
+            std::fs::rename(&old_path, &new_path)?;
+            report.changed.push(format!("data/{ns_name}/{old} → data/{ns_name}/{new}"));
+        }
+    }
+    Ok(())
+}
+
+/// Raise the mixin config's `compatibilityLevel` to Java 21.
+fn update_mixin_level(path: &Path, report: &mut Report) -> Result<()> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let updated = mixin_level_content(&content);
+    let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    if diff::apply_edit(path, &content, &updated)? {
+        report.changed.push(format!("{name}: compatibilityLevel → {MIXIN_LEVEL}"));
+    }
+    Ok(())
+}
+
+/// Pure transform behind `update_mixin_level`: replaces an older `JAVA_*`
+/// level in place so the rest of the file keeps its formatting.
+fn mixin_level_content(content: &str) -> String {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return content.to_string();
+    };
+    let Some(level) = json["compatibilityLevel"].as_str() else {
+        return content.to_string();
+    };
+    let older = level
+        .strip_prefix("JAVA_")
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v < 21);
+    if !older {
+        return content.to_string();
+    }
+    content.replacen(&format!("\"{level}\""), &format!("\"{MIXIN_LEVEL}\""), 1)
+}
+
+/// Manual steps in `areas` for releases after `from` up to and including `to`.
+fn manual_steps(from: &str, to: &str, areas: &[Area]) -> Vec<String> {
+    MANUAL_STEPS
+        .iter()
+        .filter(|(since, area, _)| {
+            areas.contains(area) && compare_versions(since, from).is_gt() && compare_versions(since, to).is_le()
+        })
+        .map(|(since, _, step)| format!("{since}: {step}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_data_folders() {
+        let dir = std::env::temp_dir().join(format!("mcmod_migrate_data_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("data/mymod/recipes")).unwrap();
+        std::fs::write(dir.join("data/mymod/recipes/a.json"), "{}").unwrap();
+        std::fs::create_dir_all(dir.join("data/mymod/tags/items")).unwrap();
+        std::fs::create_dir_all(dir.join("data/minecraft/loot_tables")).unwrap();
+        std::fs::create_dir_all(dir.join("data/minecraft/loot_table")).unwrap();
+
+        let mut report = Report::default();
+        rename_data_folders(&dir, &mut report).unwrap();
+
+        assert!(dir.join("data/mymod/recipe/a.json").is_file());
+        assert!(dir.join("data/mymod/tags/item").is_dir());
+        assert!(!dir.join("data/mymod/recipes").exists());
+        assert_eq!(report.changed.len(), 2);
+        assert!(dir.join("data/minecraft/loot_tables").is_dir(), "never overwrite");
+        assert_eq!(report.manual.len(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_mixin_level_content() {
+        let old = "{\n  \"package\": \"a.mixin\",\n  \"compatibilityLevel\": \"JAVA_17\"\n}\n";
+        assert_eq!(
+            mixin_level_content(old),
+            "{\n  \"package\": \"a.mixin\",\n  \"compatibilityLevel\": \"JAVA_21\"\n}\n"
+        );
+        let current = "{ \"compatibilityLevel\": \"JAVA_21\" }";
+        assert_eq!(mixin_level_content(current), current);
+    }
+
+    #[test]
+    fn test_manual_steps_between_versions() {
+        let all = [Area::Code, Area::Data, Area::Assets];
+        let steps = manual_steps("1.21.1", "1.21.4", &all);
+        assert_eq!(steps.len(), 3);
+        assert!(steps.iter().all(|s| s.starts_with("1.21.2:") || s.starts_with("1.21.4:")));
+        assert!(manual_steps("1.21.4", "1.21.5", &all).is_empty());
+
+        let data = manual_steps("1.21.1", "1.21.11", &[Area::Data]);
+        assert_eq!(data.len(), 1);
+        assert!(data[0].contains("Recipe ingredients"));
+    }
+}
@@ -1,4 +1,40 @@
+pub mod about;
 pub mod add;
+pub mod add_dep;
+pub mod adopt;
+pub mod assets;
+pub mod build;
+pub mod changelog;
+pub mod compat;
+pub mod completions;
 pub mod config;
+pub mod debug;
+pub mod dep;
+pub mod dev;
+pub mod dev_server;
+pub mod diff;
+pub mod doctor;
+pub mod foreach;
+pub mod gen;
+pub mod graph;
 pub mod init;
+pub mod migrate_mc;
+pub mod name;
+pub mod outdated;
+pub mod offline;
+pub mod package;
+pub mod prune;
+pub mod publish;
+pub mod release;
+pub mod rename;
+pub mod report;
+pub mod status;
+pub mod sync;
+pub mod template;
+pub mod test;
 pub mod update;
+pub mod upgrade;
+pub mod verify;
+pub mod versions;
+pub mod watch;
+pub mod wrapper;
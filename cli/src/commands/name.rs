@@ -0,0 +1,35 @@
+use crate::config::{self, McmodConfig};
+use crate::error::Result;
+use crate::naming::{ContentKind, ContentName};
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// `mcmod name suggest "<display name>"`: print the registry id, translation
+/// key, class name and texture path the name maps to. The mod id comes from
+/// `--mod-id`, or else the project's mcmod.toml.
+pub fn run_suggest(display: &str, kind: ContentKind, mod_id: Option<String>, dir: Option<PathBuf>) -> Result<()> {
+    let mod_id = match mod_id {
+        Some(mod_id) => {
+            crate::util::validate_mod_id(&mod_id)?;
+            mod_id
+        }
+        None => {
+            let dir = config::resolve_project_dir(dir)?;
+            McmodConfig::load(&dir)?.mod_info.mod_id
+        }
+    };
+    let name = ContentName::suggest(display, &mod_id, kind)?;
+
+    println!();
+    println!("  {}", display.bold());
+    for (label, value) in [
+        ("Registry id", format!("{mod_id}:{}", name.id)),
+        ("Translation key", name.translation_key),
+        ("Class name", name.class_name),
+        ("Texture", name.texture),
+    ] {
+        println!("  {:<16} {}", label.dimmed(), value.cyan());
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use crate::error::{McmodError, Result};
+use crate::global_config::GlobalConfig;
+use crate::template;
+use crate::util;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that turns offline mode on, e.g. in CI images.
+pub const OFFLINE_ENV: &str = "MCMOD_OFFLINE";
+
+/// Commands that use the network, what they contact, and what they do offline.
+pub const NETWORK_TOUCHPOINTS: &[(&str, &str, &str)] = &[
+    ("mcmod upgrade", "Mojang, Fabric, NeoForge, Forge, and Quilt metadata", "uses the built-in version table"),
+    ("mcmod outdated", "Mojang, Fabric, NeoForge, Forge, and Quilt metadata", "compares against the built-in version table"),
+    ("mcmod versions", "Mojang, Fabric, and NeoForge metadata", "fails"),
+    ("mcmod update", "GitHub releases", "fails"),
+    ("mcmod dev server install", "Mojang, Fabric, and NeoForge downloads", "fails"),
+    ("mcmod add dep modrinth:", "Modrinth API", "fails (Maven coordinates work)"),
+    ("mcmod wrapper", "Gradle distributions and GitHub", "fails"),
+    ("mcmod build", "Gradle's plugin and dependency repositories", "runs Gradle with --offline"),
+    ("mcmod test", "Gradle's plugin and dependency repositories", "runs Gradle with --offline"),
+    ("mcmod verify", "Gradle's plugin and dependency repositories", "runs Gradle with --offline"),
+    ("mcmod publish", "Modrinth and CurseForge APIs", "fails"),
+    ("mcmod release", "Gradle's repositories, GitHub, Modrinth, and CurseForge APIs", "builds with Gradle --offline and stops at the GitHub and publish steps"),
+];
+
+/// Where offline mode was turned on, if it was: the `--offline` flag,
+/// `MCMOD_OFFLINE`, or the `offline` preference, in that order.
+pub fn offline_source(flag: bool) -> Option<&'static str> {
+    if flag {
+        return Some("--offline");
+    }
+    let env_on = std::env::var(OFFLINE_ENV)
+        .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"));
+    if env_on {
+        return Some(OFFLINE_ENV);
+    }
+    GlobalConfig::load()
+        .ok()
+        .and_then(|c| c.defaults.offline)
+        .filter(|on| *on)
+        .map(|_| "the offline preference")
+}
+
+/// `mcmod offline verify`: confirm offline mode is on, that the network gate
+/// refuses every kind of request, and report what still needs a connection
+/// (the Gradle distribution when it isn't cached).
+pub fn run_verify(source: Option<&str>) -> Result<()> {
+    println!("{}", "\n  mcmod offline verify\n".bold().cyan());
+    let mut problems = 0;
+
+    match source {
+        Some(source) => println!("  {} Offline mode is on (from {source})", "✓".green()),
+        None => {
+            println!(
+                "  {} Offline mode is off; pass --offline, set {OFFLINE_ENV}=1, or run `mcmod config set offline true`",
+                "✗".red()
+            );
+            problems += 1;
+        }
+    }
+
+    let failures = probe_gate();
+    if failures.is_empty() {
+        println!("  {} Every request type (GET, POST, download) is refused offline", "✓".green());
+    } else {
+        for failure in &failures {
+            println!("  {} {failure}", "✗".red());
+        }
+        problems += failures.len();
+    }
+
+    println!("\n  {}", "Commands that use the network:".bold());
+    for (command, contacts, offline) in NETWORK_TOUCHPOINTS {
+        println!("    {command:<25} {}", format!("{contacts}; offline it {offline}").dimmed());
+    }
+
+    println!("\n  {}", "Gradle:".bold());
+    println!(
+        "  {} Gradle wrapper jar is bundled ({} KB)",
+        "✓".green(),
+        template::GRADLE_WRAPPER_JAR.len() / 1024
+    );
+    let project = crate::config::find_project_root(&std::env::current_dir()?).ok();
+    let properties = project
+        .as_ref()
+        .and_then(|dir| crate::fs::read_to_string(&dir.join("gradle/wrapper/gradle-wrapper.properties")).ok())
+        .unwrap_or_else(|| template::GRADLE_WRAPPER_PROPS.to_string());
+    match distribution_name(&properties) {
+        Some(name) => match gradle_user_home().map(|home| home.join("wrapper/dists").join(&name)) {
+            Some(dist) if dist.is_dir() => {
+                println!("  {} {name} is cached in {}", "✓".green(), dist.display());
+            }
+            _ => println!(
+                "  {} {name} isn't in the Gradle cache; builds will download it unless distributionUrl points at a local mirror",
+                "⚠".yellow()
+            ),
+        },
+        None => println!("  {} No distributionUrl in gradle-wrapper.properties", "⚠".yellow()),
+    }
+    println!(
+        "  {}",
+        "Gradle resolves Loom, ModDevGradle, and Maven dependencies itself; run builds with --offline once the caches are warm".dimmed()
+    );
+
+    println!();
+    if problems > 0 {
+        return Err(McmodError::Other(format!("{problems} offline check(s) failed")));
+    }
+    Ok(())
+}
+
+/// Send one of each request type with offline mode forced on, and report any
+/// that got past the gate.
+fn probe_gate() -> Vec<String> {
+    let was_offline = util::is_offline();
+    util::set_offline(true);
+    let url = "https://offline-probe.invalid/";
+    let dest = std::env::temp_dir().join(format!("mcmod_offline_probe_{}", std::process::id()));
+    let results = [
+        ("GET", util::http_get(url).err()),
+        ("POST", util::http_post(url, &[], b"").err()),
+        ("Download", util::http_download(url, &dest, &mut |_, _| {}).err()),
+    ];
+    util::set_offline(was_offline);
+    results
+        .into_iter()
+        .filter(|(_, err)| !matches!(err.as_ref().map(|e| e.root()), Some(McmodError::Offline(_))))
+        .map(|(kind, _)| format!("{kind} requests aren't refused offline"))
+        .collect()
+}
+
+/// `gradle-9.4.1-bin` from the wrapper's distributionUrl.
+fn distribution_name(properties: &str) -> Option<String> {
+    let url = properties.lines().find_map(|line| line.trim().strip_prefix("distributionUrl="))?;
+    let file = url.rsplit('/').next()?;
+    Some(file.strip_suffix(".zip").unwrap_or(file).to_string())
+}
+
+/// `GRADLE_USER_HOME`, or `~/.gradle`.
+fn gradle_user_home() -> Option<PathBuf> {
+    if let Ok(home) = std::env::var("GRADLE_USER_HOME") {
+        return Some(PathBuf::from(home));
+    }
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?;
+    Some(Path::new(&home).join(".gradle"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_gate() {
+        assert!(probe_gate().is_empty());
+        assert!(!util::is_offline());
+        assert_eq!(distribution_name(template::GRADLE_WRAPPER_PROPS).as_deref(), Some("gradle-9.4.1-bin"));
+        assert_eq!(offline_source(true), Some("--offline"));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::commands::upgrade::{self, Change, Dep};
+use crate::config::{McmodConfig, VersionTarget};
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use crate::lockfile::Lockfile;
+use colored::Colorize;
+use std::path::Path;
+
+/// Accessor for one pinned version of a target.
+type Field = fn(&mut VersionTarget) -> &mut String;
+
+/// Per-version properties keys, by the target field they pin.
+const PROPERTY_KEYS: &[(&str, Field)] = &[
+    ("loader_version", |t| &mut t.fabric_loader),
+    ("fabric_version", |t| &mut t.fabric_api),
+    ("neoforge_version", |t| &mut t.neoforge),
+    ("forge_version", |t| &mut t.forge),
+    ("quilt_loader_version", |t| &mut t.quilt_loader),
+    ("architectury_api_version", |t| &mut t.architectury_api),
+];
+
+/// `mcmod outdated`: compare the pinned versions against the newest ones
+/// online (the versions `mcmod upgrade` would move to) and print the stale
+/// ones with the upgrade commands that apply them. Writes nothing.
+pub fn run(dir: &Path, offline: bool) -> Result<()> {
+    println!("{}", "\n  mcmod outdated\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "outdated only supports mod and library projects (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let lock = Lockfile::load(dir)?;
+    let targets = built_targets(dir, &config.versions.targets);
+
+    if offline {
+        println!("{}", "  Offline: comparing against the built-in version table.".yellow());
+    } else {
+        println!("{}", "  Checking for newer versions...".cyan());
+    }
+    let (latest_mc, _) = upgrade::latest_minecraft(offline);
+    let changes = upgrade::plan(&targets, &config.loaders, &latest_mc, |mc| upgrade::resolve(mc, offline));
+    if changes.is_empty() {
+        println!("{}", "  Everything is up to date.".green());
+        return Ok(());
+    }
+
+    print_table(&changes, &lock);
+    println!("\n  {}", "To update:".bold());
+    let newest = targets.last().map(|t| t.minecraft.as_str()).unwrap_or_default();
+    for (command, why) in suggestions(&changes, &lock, newest) {
+        println!("    {:<36} {}", command.green(), why.dimmed());
+    }
+    println!();
+    Ok(())
+}
+
+/// The project's targets as Gradle builds them: mcmod.toml's pins, overridden
+/// by the per-version properties files where those were edited by hand.
+fn built_targets(dir: &Path, targets: &[VersionTarget]) -> Vec<VersionTarget> {
+    targets
+        .iter()
+        .map(|target| {
+            let mut built = target.clone();
+            let path = dir.join(format!("versions/dependencies/{}.properties", target.minecraft));
+            if let Ok(properties) = crate::fs::read_to_string(&path) {
+                for (key, field) in PROPERTY_KEYS {
+                    if let Some(value) = gradle::get_property_content(&properties, key).filter(|v| !v.is_empty()) {
+                        *field(&mut built) = value;
+                    }
+                }
+            }
+            built
+        })
+        .collect()
+}
+
+fn print_table(changes: &[Change], lock: &Lockfile) {
+    let width = |f: fn(&Change) -> usize, title: &str| changes.iter().map(f).max().unwrap_or(0).max(title.len());
+    let target_width = width(|c| c.target.len(), "Target");
+    let dep_width = width(|c| c.dep.label().len(), "Dependency");
+    let from_width = width(|c| c.from.len(), "Current");
+    println!(
+        "\n  {}",
+        format!("{:<target_width$}  {:<dep_width$}  {:<from_width$}  Latest", "Target", "Dependency", "Current").bold()
+    );
+    for change in changes {
+        let held = if lock.hold(&change.target, change.dep.key()).is_some() { "  (held)".yellow() } else { "".normal() };
+        // Minecraft and the versions built for it are the disruptive bumps
+        let from = format!("{:<from_width$}", change.from);
+        let from = if change.dep.follows_minecraft() || change.dep == Dep::Minecraft { from.red() } else { from.yellow() };
+        println!(
+            "  {:<target_width$}  {:<dep_width$}  {from}  {}{held}",
+            change.target,
+            change.dep.label(),
+            change.to.green(),
+        );
+    }
+}
+
+/// `mcmod upgrade` invocations for the stale dependencies, with what each does.
+fn suggestions(changes: &[Change], lock: &Lockfile, newest: &str) -> Vec<(String, String)> {
+    let held = changes.iter().filter(|c| lock.hold(&c.target, c.dep.key()).is_some()).count();
+    let applied = changes.len() - held;
+    let mut out = Vec::new();
+    if applied > 0 {
+        let mut why = format!("apply {applied} update(s)");
+        if held > 0 {
+            why.push_str(&format!("; {held} held in mcmod.lock stay put"));
+        }
+        out.push(("mcmod upgrade".to_string(), why));
+    }
+    let minecraft = changes.iter().find(|c| c.dep == Dep::Minecraft);
+    if let Some(minecraft) = minecraft {
+        if changes.len() > 1 {
+            out.push((
+                format!("mcmod upgrade --mc-version {newest}"),
+                format!("update the loaders but stay on Minecraft {}", minecraft.from),
+            ));
+        }
+    }
+    if changes.len() > 1 || held > 0 {
+        out.push(("mcmod upgrade -i".to_string(), "choose updates one by one".to_string()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::{Hold, BUILT_IN_SOURCE};
+
+    fn change(target: &str, dep: Dep, from: &str, to: &str) -> Change {
+        Change {
+            target: target.to_string(),
+            dep,
+            from: from.to_string(),
+            to: to.to_string(),
+            source: BUILT_IN_SOURCE.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let changes = vec![
+            change("1.21.4", Dep::Minecraft, "1.21.4", "1.21.11"),
+            change("1.21.4", Dep::Neoforge, "21.4.157", "21.11.40-beta"),
+        ];
+        let mut lock = Lockfile::default();
+        let commands: Vec<String> = suggestions(&changes, &lock, "1.21.4").into_iter().map(|(c, _)| c).collect();
+        assert_eq!(commands, ["mcmod upgrade", "mcmod upgrade --mc-version 1.21.4", "mcmod upgrade -i"]);
+
+        lock.set_hold(Hold {
+            target: "1.21.4".to_string(),
+            dependency: "neoforge".to_string(),
+            version: "21.4.157".to_string(),
+            skipped: "21.11.40-beta".to_string(),
+        });
+        let single = &changes[1..];
+        let found = suggestions(single, &lock, "1.21.4");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "mcmod upgrade -i");
+    }
+
+    #[test]
+    fn test_built_targets_read_properties() {
+        let dir = std::path::PathBuf::from("/memory/outdated");
+        let target = VersionTarget {
+            minecraft: "1.21.4".to_string(),
+            max_minecraft: "1.21.4".to_string(),
+            fabric_loader: "0.18.5".to_string(),
+            fabric_api: "0.119.4+1.21.4".to_string(),
+            neoforge: "21.4.157".to_string(),
+            forge: String::new(),
+            quilt_loader: String::new(),
+            architectury_api: String::new(),
+        };
+        let (built, _) = crate::fs::in_memory(|| {
+            let properties = crate::template::render_version_properties(&target).unwrap();
+            let properties = gradle::set_property_content(&properties, "neoforge_version", "21.4.100");
+            crate::util::write_file(&dir.join("versions/dependencies/1.21.4.properties"), &properties).unwrap();
+            built_targets(&dir, std::slice::from_ref(&target))
+        });
+        assert_eq!(built[0].neoforge, "21.4.100");
+        assert_eq!(built[0].fabric_api, target.fabric_api);
+        assert!(built[0].forge.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::commands::report::{self, BuiltJar};
+use crate::config::McmodConfig;
+use crate::error::{Context, McmodError, Result};
+use crate::gradle;
+use crate::util::sha256_file;
+use colored::Colorize;
+use std::path::Path;
+
+/// Where `mcmod package` collects release artifacts, relative to the project.
+pub const DIST_DIR: &str = "dist";
+/// `sha256sum`-format checksums of the packaged jars.
+const CHECKSUMS: &str = "SHA256SUMS";
+
+/// `mcmod package`: build the project (unless `skip_build`), then copy each
+/// built mod jar into `dist/<version>/` as `<mod_id>-<version>+<mc>-<loader>.jar`
+/// with a SHA256SUMS file, ready to upload by hand. An existing
+/// `dist/<version>/` is only replaced with `force`.
+pub fn run(dir: &Path, loader: Option<&str>, skip_build: bool, force: bool) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "mcmod package collects mod and library jars (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let properties = crate::fs::read_to_string(&dir.join("gradle.properties"))?;
+    let version = gradle::get_property_content(&properties, "mod.version")
+        .ok_or_else(|| McmodError::Other("gradle.properties has no mod.version".to_string()))?;
+
+    let out = dir.join(DIST_DIR).join(&version);
+    let occupied = std::fs::read_dir(&out).is_ok_and(|mut entries| entries.next().is_some());
+    if occupied && !force {
+        return Err(McmodError::Other(format!(
+            "{DIST_DIR}/{version}/ already exists; bump mod.version or pass --force to replace it"
+        )));
+    }
+
+    if !skip_build {
+        crate::commands::build::run(dir, loader)?;
+    }
+
+    println!("{}", "\n  mcmod package\n".bold().cyan());
+    let jars: Vec<BuiltJar> = report::find_jars(dir)
+        .into_iter()
+        .filter(|jar| jar.minecraft.is_some() && (loader.is_none() || jar.loader.as_deref() == loader))
+        .collect();
+    if jars.is_empty() {
+        return Err(McmodError::Other(format!(
+            "No built jars under {}; run `mcmod build` first",
+            dir.join("versions/*/build/libs").display()
+        )));
+    }
+    let artifacts = artifacts(&config.mod_info.mod_id, &version, &jars)?;
+
+    if occupied {
+        std::fs::remove_dir_all(&out).with_context(|| format!("Clearing {}", out.display()))?;
+    }
+    std::fs::create_dir_all(&out).with_context(|| format!("Creating {}", out.display()))?;
+    let mut checksums = String::new();
+    for (jar, name) in &artifacts {
+        let dest = out.join(name);
+        std::fs::copy(&jar.path, &dest).with_context(|| format!("Copying {}", jar.path.display()))?;
+        checksums.push_str(&format!("{}  {name}\n", sha256_file(&dest)?));
+        let source = jar.path.strip_prefix(dir).unwrap_or(&jar.path);
+        println!("  {} {name} {}", "✓".green(), format!("({})", source.display()).dimmed());
+    }
+    crate::util::write_file(&out.join(CHECKSUMS), &checksums)?;
+
+    println!(
+        "\n  {}",
+        format!("{} jar(s) and {CHECKSUMS} in {DIST_DIR}/{version}/", artifacts.len()).bold().green()
+    );
+    Ok(())
+}
+
+/// Each jar to package with its normalized name, `<mod_id>-<version>+<mc>-<loader>.jar`.
+/// When a version directory holds several jars (Gradle leaves jars of
+/// earlier versions in build/libs), the one whose file name has `version`
+/// is taken; any other clash is an error.
+fn artifacts<'a>(mod_id: &str, version: &str, jars: &'a [BuiltJar]) -> Result<Vec<(&'a BuiltJar, String)>> {
+    let current = |jar: &BuiltJar| jar.path.file_name().is_some_and(|n| n.to_string_lossy().contains(version));
+    let mut artifacts: Vec<(&BuiltJar, String)> = Vec::new();
+    for jar in jars {
+        let minecraft = jar.minecraft.as_deref().unwrap_or_default();
+        let loader = jar.loader.as_deref().unwrap_or_default();
+        let name = format!("{mod_id}-{version}+{minecraft}-{loader}.jar");
+        let Some(i) = artifacts.iter().position(|(_, n)| *n == name) else {
+            artifacts.push((jar, name));
+            continue;
+        };
+        match (current(artifacts[i].0), current(jar)) {
+            (false, true) => artifacts[i].0 = jar,
+            (true, false) => {}
+            _ => {
+                return Err(McmodError::Other(format!(
+                    "Both {} and {} would be packaged as {name}; remove the stale jar and rebuild",
+                    artifacts[i].0.path.display(),
+                    jar.path.display()
+                )));
+            }
+        }
+    }
+    Ok(artifacts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn built(path: &str, minecraft: &str, loader: &str) -> BuiltJar {
+        BuiltJar {
+            path: PathBuf::from(path),
+            minecraft: Some(minecraft.to_string()),
+            loader: Some(loader.to_string()),
+        }
+    }
+
+    fn names(artifacts: &[(&BuiltJar, String)]) -> Vec<(String, String)> {
+        artifacts
+            .iter()
+            .map(|(jar, name)| (jar.path.file_name().unwrap().to_string_lossy().into_owned(), name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_artifacts() {
+        let jars = [
+            built("versions/1.21.1-fabric/build/libs/mymod-0.9.0.jar", "1.21.1", "fabric"),
+            built("versions/1.21.1-fabric/build/libs/mymod-1.0.0.jar", "1.21.1", "fabric"),
+            built("versions/1.21.1-neoforge/build/libs/mymod-neoforge-1.0.0+mc1.21.1.jar", "1.21.1", "neoforge"),
+        ];
+        assert_eq!(
+            names(&artifacts("mymod", "1.0.0", &jars).unwrap()),
+            [
+                ("mymod-1.0.0.jar".to_string(), "mymod-1.0.0+1.21.1-fabric.jar".to_string()),
+                ("mymod-neoforge-1.0.0+mc1.21.1.jar".to_string(), "mymod-1.0.0+1.21.1-neoforge.jar".to_string()),
+            ]
+        );
+
+        let ambiguous = [
+            built("versions/1.21.1-fabric/build/libs/mymod-1.0.0.jar", "1.21.1", "fabric"),
+            built("versions/1.21.1-fabric/build/libs/mymod-1.0.0-all.jar", "1.21.1", "fabric"),
+        ];
+        let err = artifacts("mymod", "1.0.0", &ambiguous).unwrap_err().to_string();
+        assert!(err.contains("mymod-1.0.0-all.jar") && err.contains("mymod-1.0.0+1.21.1-fabric.jar"));
+    }
+}
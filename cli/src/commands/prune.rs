@@ -0,0 +1,326 @@
+use crate::commands::doctor::LOADER_METADATA;
+use crate::config::McmodConfig;
+use crate::diff;
+use crate::error::{Context, McmodError, Result};
+use crate::lockfile::Lockfile;
+use crate::version_meta::compare_versions;
+use colored::Colorize;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// Directories under src/ that never hold Stonecutter sources.
+const SKIP_DIRS: &[&str] = &["build", "generated"];
+
+/// A Stonecutter condition that no longer depends on the Minecraft version
+/// once the pruned targets are gone.
+#[derive(Debug, PartialEq)]
+struct StaleCondition {
+    /// Path relative to the project.
+    path: String,
+    line: usize,
+    predicate: String,
+    /// What the predicate now is for every remaining target.
+    always: bool,
+}
+
+/// `mcmod prune versions <mc>...`: drop Minecraft versions from a Stonecutter
+/// project. Their `mc(...)` lines, `versions/<mc>-<loader>/` directories,
+/// `versions/dependencies/<mc>.properties`, lockfile entries and CI matrix
+/// entries are removed. Version checks in the sources that become always true
+/// or always false stop the prune unless `force` is given, so dead branches
+/// aren't left behind unnoticed.
+pub fn run_versions(dir: &Path, versions: &[String], force: bool) -> Result<()> {
+    println!("{}", "\n  mcmod prune versions\n".bold().cyan());
+    let mut config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "Only mod and library projects build several Minecraft versions (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let targets: Vec<String> = config.versions.targets.iter().map(|t| t.minecraft.clone()).collect();
+    if let Some(unknown) = versions.iter().find(|v| !targets.contains(v)) {
+        return Err(McmodError::Other(format!(
+            "{unknown} is not one of this project's versions ({})",
+            targets.join(", ")
+        )));
+    }
+    let remaining: Vec<String> = targets.iter().filter(|t| !versions.contains(t)).cloned().collect();
+    if remaining.is_empty() {
+        return Err(McmodError::Other("Can't prune every version; the project needs at least one target".to_string()));
+    }
+
+    let stonecutter = crate::fs::read_to_string(&dir.join("stonecutter.gradle.kts")).unwrap_or_default();
+    let next = format!("{}-{}", remaining[0], config.enabled_platforms().first().copied().unwrap_or("fabric"));
+    if let Some(active) = active_version(&stonecutter) {
+        if versions.iter().any(|v| active.starts_with(&format!("{v}-"))) {
+            return Err(McmodError::Other(format!(
+                "{active} is the active Stonecutter version, so the sources are written for it; \
+                 switch first with ./gradlew \"Set active project to {next}\""
+            )));
+        }
+    }
+
+    let stale = stale_conditions(dir, &targets, &remaining);
+    if !stale.is_empty() {
+        let lines: Vec<String> = stale
+            .iter()
+            .map(|s| format!("{}:{}: {} is always {} now", s.path, s.line, s.predicate, s.always))
+            .collect();
+        if !force {
+            return Err(McmodError::Other(format!(
+                "Version checks in the sources only matter for the versions being pruned:\n    {}\n  \
+                 Simplify those blocks first, or pass --force to prune anyway",
+                lines.join("\n    ")
+            )));
+        }
+        for line in &lines {
+            crate::progress::warning(line);
+        }
+    }
+
+    let settings_path = dir.join("settings.gradle.kts");
+    let settings = crate::fs::read_to_string(&settings_path).context("Reading settings.gradle.kts")?;
+    let fallback = active_version(&stonecutter).unwrap_or(next);
+    let mut updated = settings.clone();
+    for version in versions {
+        updated = crate::gradle::remove_mc_version_content(&updated, version, &fallback);
+    }
+    if diff::apply_edit(&settings_path, &settings, &updated)? {
+        println!("{}", "  Updated settings.gradle.kts".green());
+    }
+
+    for path in workflows(dir) {
+        let content = crate::fs::read_to_string(&path)?;
+        let mut updated = content.clone();
+        for version in versions {
+            updated = remove_from_yaml_lists(&updated, version);
+        }
+        let rel = path.strip_prefix(dir).unwrap_or(&path).display().to_string();
+        if diff::apply_edit(&path, &content, &updated)? {
+            println!("{}", format!("  Updated the matrix in {rel}").green());
+        }
+        if let Some(version) = versions.iter().find(|v| updated.contains(v.as_str())) {
+            crate::progress::warning(format!("{rel} still mentions {version}; check it by hand"));
+        }
+    }
+
+    for version in versions {
+        remove_version_files(dir, version)?;
+    }
+
+    config.versions.targets.retain(|t| !versions.contains(&t.minecraft));
+    config.save(dir)?;
+    if crate::fs::exists(&Lockfile::path(dir)) {
+        let mut lock = Lockfile::load(dir)?;
+        lock.resolved.retain(|r| !versions.contains(&r.target));
+        lock.holds.retain(|h| !versions.contains(&h.target));
+        lock.save(dir)?;
+    }
+
+    println!(
+        "\n  {}",
+        format!("Pruned {}; building {}", versions.join(", "), remaining.join(", ")).bold().green()
+    );
+    Ok(())
+}
+
+/// The node in `stonecutter active "1.21.1-fabric"`.
+fn active_version(stonecutter: &str) -> Option<String> {
+    stonecutter.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("stonecutter active \"")?;
+        Some(rest.split('"').next()?.to_string())
+    })
+}
+
+/// Remove `versions/<version>-<loader>/` for every loader and
+/// `versions/dependencies/<version>.properties`.
+fn remove_version_files(dir: &Path, version: &str) -> Result<()> {
+    let versions_dir = dir.join("versions");
+    for (loader, _) in LOADER_METADATA {
+        let node = versions_dir.join(format!("{version}-{loader}"));
+        if node.is_dir() {
+            std::fs::remove_dir_all(&node).with_context(|| format!("Removing {}", node.display()))?;
+            println!("{}", format!("  Removed versions/{version}-{loader}/").green());
+        }
+    }
+    let properties = versions_dir.join(format!("dependencies/{version}.properties"));
+    if crate::fs::exists(&properties) {
+        crate::fs::remove_file(&properties)?;
+        println!("{}", format!("  Removed versions/dependencies/{version}.properties").green());
+    }
+    Ok(())
+}
+
+/// GitHub Actions workflows in the project.
+fn workflows(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir.join(".github/workflows")) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "yml" || e == "yaml"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Drop `version` from YAML lists: `- 1.20.1` items (quoted or not) and
+/// entries of flow lists like `[1.20.1, "1.21.1"]`. Anything else that
+/// mentions the version is left alone.
+fn remove_from_yaml_lists(content: &str, version: &str) -> String {
+    let is_version = |item: &str| item.trim().trim_matches(|c| c == '"' || c == '\'') == version;
+    let mut out = String::new();
+    for line in content.lines() {
+        let item = line.trim_start().strip_prefix("- ").map(|i| i.split(" #").next().unwrap_or(i));
+        if item.is_some_and(is_version) {
+            continue;
+        }
+        let flow = line.find('[').zip(line.rfind(']')).filter(|(open, close)| open < close);
+        match flow {
+            Some((open, close)) if line[open + 1..close].split(',').any(is_version) => {
+                let items: Vec<&str> = line[open + 1..close]
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|i| !is_version(i))
+                    .collect();
+                out.push_str(&format!("{}[{}]{}", &line[..open], items.join(", "), &line[close + 1..]));
+            }
+            _ => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    if !content.ends_with('\n') {
+        out.pop();
+    }
+    out
+}
+
+/// Version predicates in Stonecutter comments under src/ (`//? if >=1.21.2`,
+/// `/*? if <1.21 {*/`) that differ between `before`'s targets but give the
+/// same answer for every target in `after`.
+fn stale_conditions(dir: &Path, before: &[String], after: &[String]) -> Vec<StaleCondition> {
+    let mut files = Vec::new();
+    collect_sources(&dir.join("src"), &mut files);
+    let mut stale = Vec::new();
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let rel = path.strip_prefix(dir).unwrap_or(&path).display().to_string();
+        for (i, line) in content.lines().enumerate() {
+            for predicate in version_predicates(line) {
+                let results = |targets: &[String]| -> Vec<bool> {
+                    targets.iter().filter_map(|t| eval_predicate(&predicate, t)).collect()
+                };
+                let (was, now) = (results(before), results(after));
+                let varied = was.iter().any(|r| *r != was[0]);
+                if varied && !now.is_empty() && now.iter().all(|r| *r == now[0]) {
+                    stale.push(StaleCondition { path: rel.clone(), line: i + 1, predicate, always: now[0] });
+                }
+            }
+        }
+    }
+    stale
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                collect_sources(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// The version predicates (`>=1.21.2`, `<1.21`, `=1.20.1`) in a line's
+/// Stonecutter comment, if it has one.
+fn version_predicates(line: &str) -> Vec<String> {
+    let Some(start) = line.find("/*?").or_else(|| line.find("//?")) else {
+        return Vec::new();
+    };
+    line[start + 3..]
+        .split(|c: char| c.is_whitespace() || matches!(c, '{' | '}' | '(' | ')' | '!' | '&' | '|' | '*' | '/'))
+        .filter(|token| {
+            let version = token.trim_start_matches(['>', '<', '=']);
+            version.contains('.')
+                && version.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether Minecraft `minecraft` satisfies `predicate`; None for a predicate
+/// this doesn't understand. A bare version means equality.
+fn eval_predicate(predicate: &str, minecraft: &str) -> Option<bool> {
+    let (op, version) = ["<=", ">=", "<", ">", "="]
+        .iter()
+        .find_map(|op| predicate.strip_prefix(op).map(|v| (*op, v)))
+        .unwrap_or(("=", predicate));
+    let ordering = compare_versions(minecraft, version);
+    Some(match op {
+        "<=" => ordering != Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        "<" => ordering == Ordering::Less,
+        ">" => ordering == Ordering::Greater,
+        _ => ordering == Ordering::Equal,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_predicates() {
+        assert_eq!(version_predicates("    //? if >=1.21.2 {"), [">=1.21.2"]);
+        assert_eq!(version_predicates("/*? if fabric && <1.21 {*/"), ["<1.21"]);
+        assert_eq!(version_predicates("/*?} else if =1.20.1 {*/"), ["=1.20.1"]);
+        assert!(version_predicates("/*? if neoforge {*/").is_empty());
+        assert!(version_predicates("String v = \">=1.21\";").is_empty());
+
+        assert_eq!(eval_predicate(">=1.21.2", "1.21.4"), Some(true));
+        assert_eq!(eval_predicate("<1.21", "1.21.1"), Some(false));
+        assert_eq!(eval_predicate("1.20.1", "1.20.1"), Some(true));
+    }
+
+    #[test]
+    fn test_stale_conditions() {
+        let dir = std::env::temp_dir().join(format!("mcmod-prune-{}", std::process::id()));
+        let source = dir.join("src/main/java/com/example/Mod.java");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(
+            &source,
+            "class Mod {\n    //? if >=1.21 {\n    int a;\n    //?} else\n    /*? if >=1.21.4 {*/\n    int b;\n    /*?}*/\n}\n",
+        )
+        .unwrap();
+        let before = ["1.20.1".to_string(), "1.21.1".to_string(), "1.21.4".to_string()];
+        let stale = stale_conditions(&dir, &before, &before[1..]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            stale,
+            [StaleCondition {
+                path: "src/main/java/com/example/Mod.java".to_string(),
+                line: 2,
+                predicate: ">=1.21".to_string(),
+                always: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_remove_from_yaml_lists() {
+        let workflow = "    strategy:\n      matrix:\n        minecraft: [1.20.1, \"1.21.1\"]\n        java:\n          - '1.20.1'\n          - 1.21.1 # newest\n";
+        assert_eq!(
+            remove_from_yaml_lists(workflow, "1.20.1"),
+            "    strategy:\n      matrix:\n        minecraft: [\"1.21.1\"]\n        java:\n          - 1.21.1 # newest\n"
+        );
+        assert_eq!(remove_from_yaml_lists(workflow, "1.19.4"), workflow);
+    }
+}
@@ -0,0 +1,613 @@
+use crate::commands::changelog;
+use crate::commands::report::{self, BuiltJar};
+use crate::config::{CurseforgePublish, McmodConfig, ModrinthPublish};
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use crate::version_meta;
+use colored::Colorize;
+use serde::Deserialize;
+use std::path::Path;
+
+pub const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+const CURSEFORGE_API: &str = "https://minecraft.curseforge.com/api";
+
+/// Modrinth project ids of the dependencies mcmod projects declare.
+const FABRIC_API_PROJECT: &str = "P7dR8mSH";
+const ARCHITECTURY_API_PROJECT: &str = "lhGA9TYQ";
+
+/// CurseForge slugs of the same dependencies.
+const FABRIC_API_SLUG: &str = "fabric-api";
+const ARCHITECTURY_API_SLUG: &str = "architectury-api";
+
+/// An entry of CurseForge's `/game/versions`: a Minecraft release, loader,
+/// Java version, etc., told apart by its type.
+#[derive(Debug, Deserialize)]
+struct GameVersion {
+    id: u64,
+    #[serde(rename = "gameVersionTypeID")]
+    game_version_type_id: u64,
+    name: String,
+}
+
+/// An entry of CurseForge's `/game/version-types`, e.g. `minecraft-1-21` or `modloader`.
+#[derive(Debug, Deserialize)]
+struct GameVersionType {
+    id: u64,
+    slug: String,
+}
+
+/// Release channel of an uploaded version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum VersionType {
+    Release,
+    Beta,
+    Alpha,
+}
+
+impl VersionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VersionType::Release => "release",
+            VersionType::Beta => "beta",
+            VersionType::Alpha => "alpha",
+        }
+    }
+
+    /// `version_type` from gradle.properties, else guessed from the version:
+    /// `-alpha` is alpha, `-beta`/`-rc`/`-pre` are beta.
+    fn detect(properties: &str, version: &str) -> VersionType {
+        match gradle::get_property_content(properties, "version_type").as_deref() {
+            Some("alpha") => return VersionType::Alpha,
+            Some("beta") => return VersionType::Beta,
+            Some("release") => return VersionType::Release,
+            _ => {}
+        }
+        let version = version.to_lowercase();
+        if version.contains("alpha") {
+            VersionType::Alpha
+        } else if ["beta", "rc", "pre"].iter().any(|tag| version.contains(tag)) {
+            VersionType::Beta
+        } else {
+            VersionType::Release
+        }
+    }
+}
+
+/// What every upload of one release shares.
+struct Release {
+    version: String,
+    version_type: VersionType,
+    changelog: String,
+}
+
+/// `mcmod publish modrinth`: upload each built jar (optionally one loader's)
+/// as a Modrinth version with its loader, game versions, changelog, and
+/// dependencies filled in. `dry_run` prints the payloads instead.
+pub fn run_modrinth(
+    dir: &Path,
+    dry_run: bool,
+    version_type: Option<VersionType>,
+    changelog: Option<&Path>,
+    loader: Option<&str>,
+) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let target = ModrinthPublish::from_config(&config).ok_or_else(|| {
+        McmodError::Other(
+            "No Modrinth project configured; add [publish.modrinth] with project_id = \"...\" to mcmod.toml"
+                .to_string(),
+        )
+    })?;
+    let release = release(dir, &config, version_type, changelog)?;
+    let jars = jars(dir, &config, loader)?;
+
+    println!("{}", "\n  mcmod publish modrinth\n".bold().cyan());
+    println!("  {:<14} {}", "Project:", target.project_id);
+    println!("  {:<14} {} ({})", "Version:", release.version, release.version_type.as_str());
+    println!();
+
+    let token = if dry_run {
+        String::new()
+    } else {
+        std::env::var(&target.token_env).map_err(|_| {
+            McmodError::Other(format!(
+                "Set {} to a Modrinth personal access token with the \"Create versions\" scope",
+                target.token_env
+            ))
+        })?
+    };
+
+    for jar in &jars {
+        let data = modrinth_version(&config, &target.project_id, &release, jar);
+        if dry_run {
+            print_payload(jar, &data)?;
+            continue;
+        }
+        let response = upload(
+            "Modrinth",
+            &format!("{MODRINTH_API}/version"),
+            ("Authorization", &token),
+            "data",
+            &data,
+            jar,
+        )?;
+        let id = response["id"].as_str().unwrap_or_default();
+        println!("    {}", format!("https://modrinth.com/mod/{}/version/{id}", target.project_id).dimmed());
+    }
+    Ok(())
+}
+
+/// `mcmod publish curseforge`: upload each built jar (optionally one loader's)
+/// as a file of the CurseForge project, with the game versions mapped to
+/// CurseForge's ids and Fabric API/Architectury as required relations.
+/// `dry_run` prints the metadata instead; without a token it shows game
+/// version names, since the id lookup needs one.
+pub fn run_curseforge(
+    dir: &Path,
+    dry_run: bool,
+    version_type: Option<VersionType>,
+    changelog: Option<&Path>,
+    loader: Option<&str>,
+) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let target = CurseforgePublish::from_config(&config).ok_or_else(|| {
+        McmodError::Other(
+            "No CurseForge project configured; add [publish.curseforge] with project_id = \"...\" to mcmod.toml"
+                .to_string(),
+        )
+    })?;
+    let release = release(dir, &config, version_type, changelog)?;
+    let jars = jars(dir, &config, loader)?;
+
+    println!("{}", "\n  mcmod publish curseforge\n".bold().cyan());
+    println!("  {:<14} {}", "Project:", target.project_id);
+    println!("  {:<14} {} ({})", "Version:", release.version, release.version_type.as_str());
+    println!();
+
+    let token = std::env::var(&target.token_env).ok();
+    if token.is_none() && !dry_run {
+        return Err(McmodError::Other(format!(
+            "Set {} to a CurseForge API token (https://www.curseforge.com/account/api-tokens)",
+            target.token_env
+        )));
+    }
+    let lookup = match &token {
+        Some(token) => Some(fetch_game_versions(token)?),
+        None => {
+            println!(
+                "  {}\n",
+                format!("{} isn't set, so game versions are shown by name instead of id", target.token_env).dimmed()
+            );
+            None
+        }
+    };
+
+    for jar in &jars {
+        let minecraft = game_versions(&config, jar.minecraft.as_deref().unwrap_or_default());
+        let loader = jar.loader.as_deref().unwrap_or_default();
+        let game_versions = match &lookup {
+            Some((types, versions)) => serde_json::json!(game_version_ids(types, versions, &minecraft, loader)?),
+            None => serde_json::json!(minecraft.iter().map(String::as_str).chain([loader_name(loader)]).collect::<Vec<_>>()),
+        };
+        let metadata = curseforge_metadata(&config, &release, jar, game_versions);
+        if dry_run {
+            print_payload(jar, &metadata)?;
+            continue;
+        }
+        let response = upload(
+            "CurseForge",
+            &format!("{CURSEFORGE_API}/projects/{}/upload-file", target.project_id),
+            ("X-Api-Token", token.as_deref().unwrap_or_default()),
+            "metadata",
+            &metadata,
+            jar,
+        )?;
+        println!("    {}", format!("File id {}", response["id"]).dimmed());
+    }
+    Ok(())
+}
+
+/// CurseForge's game version types and versions.
+fn fetch_game_versions(token: &str) -> Result<(Vec<GameVersionType>, Vec<GameVersion>)> {
+    let headers = [("X-Api-Token", token)];
+    let types = crate::util::http_get_with_headers(&format!("{CURSEFORGE_API}/game/version-types"), &headers)?;
+    let versions = crate::util::http_get_with_headers(&format!("{CURSEFORGE_API}/game/versions"), &headers)?;
+    Ok((serde_json::from_str(&types)?, serde_json::from_str(&versions)?))
+}
+
+/// CurseForge ids of the Minecraft releases (from the `minecraft-1-*` types)
+/// and the loader (from `modloader`) a jar is uploaded for.
+fn game_version_ids(
+    types: &[GameVersionType],
+    versions: &[GameVersion],
+    minecraft: &[String],
+    loader: &str,
+) -> Result<Vec<u64>> {
+    let type_ids = |matches: fn(&str) -> bool| -> Vec<u64> {
+        types.iter().filter(|t| matches(&t.slug)).map(|t| t.id).collect()
+    };
+    let minecraft_types = type_ids(|slug| slug.starts_with("minecraft-"));
+    let loader_types = type_ids(|slug| slug == "modloader");
+    let find = |name: &str, in_types: &[u64]| {
+        versions
+            .iter()
+            .find(|v| in_types.contains(&v.game_version_type_id) && v.name.eq_ignore_ascii_case(name))
+            .map(|v| v.id)
+    };
+
+    let mut ids = Vec::new();
+    for mc in minecraft {
+        ids.push(
+            find(mc, &minecraft_types)
+                .ok_or_else(|| McmodError::Other(format!("CurseForge has no game version for Minecraft {mc}")))?,
+        );
+    }
+    let name = loader_name(loader);
+    ids.push(find(name, &loader_types).ok_or_else(|| McmodError::Other(format!("CurseForge has no {name} loader version")))?);
+    Ok(ids)
+}
+
+/// The `metadata` part of a CurseForge upload-file request for one jar.
+fn curseforge_metadata(
+    config: &McmodConfig,
+    release: &Release,
+    jar: &BuiltJar,
+    game_versions: serde_json::Value,
+) -> serde_json::Value {
+    let mut relations = Vec::new();
+    if jar.loader.as_deref() == Some("fabric") {
+        relations.push(serde_json::json!({ "slug": FABRIC_API_SLUG, "type": "requiredDependency" }));
+    }
+    if config.features.architectury {
+        relations.push(serde_json::json!({ "slug": ARCHITECTURY_API_SLUG, "type": "requiredDependency" }));
+    }
+    let mut metadata = serde_json::json!({
+        "displayName": display_name(config, release, jar),
+        "changelog": release.changelog,
+        "changelogType": "markdown",
+        "gameVersions": game_versions,
+        "releaseType": release.version_type.as_str(),
+    });
+    if !relations.is_empty() {
+        metadata["relations"] = serde_json::json!({ "projects": relations });
+    }
+    metadata
+}
+
+fn print_payload(jar: &BuiltJar, payload: &serde_json::Value) -> Result<()> {
+    let file_name = jar.path.file_name().unwrap_or_default().to_string_lossy();
+    println!("  {}", format!("Would upload {file_name}:").bold());
+    for line in serde_json::to_string_pretty(payload)?.lines() {
+        println!("    {}", line.dimmed());
+    }
+    Ok(())
+}
+
+/// POST a jar with its JSON metadata (in the `field` part) as multipart form
+/// data. Returns the parsed response; anything but 200 is an error naming `site`.
+fn upload(
+    site: &str,
+    url: &str,
+    auth: (&str, &str),
+    field: &str,
+    metadata: &serde_json::Value,
+    jar: &BuiltJar,
+) -> Result<serde_json::Value> {
+    let file_name = jar.path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let bytes = crate::fs::read(&jar.path)?;
+    let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let boundary = format!("mcmod-{nanos:x}");
+    let body = multipart(
+        &boundary,
+        &[
+            Part { name: field, file_name: None, content_type: "application/json", bytes: metadata.to_string().as_bytes() },
+            Part { name: "file", file_name: Some(&file_name), content_type: "application/java-archive", bytes: &bytes },
+        ],
+    );
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+    let (status, response) = crate::util::http_post(url, &[auth, ("Content-Type", &content_type)], &body)?;
+    if status != 200 {
+        return Err(McmodError::Http(format!("{site} rejected {file_name} (HTTP {status}): {}", response.trim())));
+    }
+    crate::util::progress(format!("  Published {file_name}").green());
+    Ok(serde_json::from_str(&response).unwrap_or_default())
+}
+
+/// The mod version from gradle.properties, its channel, and the changelog.
+fn release(
+    dir: &Path,
+    config: &McmodConfig,
+    version_type: Option<VersionType>,
+    changelog: Option<&Path>,
+) -> Result<Release> {
+    let properties = crate::fs::read_to_string(&dir.join("gradle.properties"))?;
+    let version = gradle::get_property_content(&properties, "mod.version")
+        .ok_or_else(|| McmodError::Other("gradle.properties has no mod.version".to_string()))?;
+    let changelog = match changelog {
+        Some(path) => crate::fs::read_to_string(path)?,
+        None => find_changelog(dir, &version)
+            .unwrap_or_else(|| format!("{} {version}", config.mod_info.mod_name)),
+    };
+    Ok(Release {
+        version_type: version_type.unwrap_or_else(|| VersionType::detect(&properties, &version)),
+        version,
+        changelog,
+    })
+}
+
+/// The built mod jars, optionally for one loader.
+fn jars(dir: &Path, config: &McmodConfig, loader: Option<&str>) -> Result<Vec<BuiltJar>> {
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "mcmod publish uploads mod and library jars (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let jars: Vec<BuiltJar> = report::find_jars(dir)
+        .into_iter()
+        .filter(|jar| jar.minecraft.is_some() && (loader.is_none() || jar.loader.as_deref() == loader))
+        .collect();
+    if jars.is_empty() {
+        return Err(McmodError::Other(format!(
+            "No built jars under {}; run `mcmod build` first",
+            dir.join("versions/*/build/libs").display()
+        )));
+    }
+    Ok(jars)
+}
+
+/// Release notes for `version`: `changelogs/v<version>.md` (what the release
+/// workflow uses), else that version's section of CHANGELOG.md, else its
+/// `[Unreleased]` notes (kept with `mcmod changelog add`).
+fn find_changelog(dir: &Path, version: &str) -> Option<String> {
+    for name in [format!("changelogs/v{version}.md"), format!("changelogs/{version}.md")] {
+        if let Ok(text) = crate::fs::read_to_string(&dir.join(name)) {
+            return Some(text.trim().to_string());
+        }
+    }
+    let content = crate::fs::read_to_string(&dir.join(changelog::CHANGELOG)).ok()?;
+    changelog_section(&content, version).or_else(|| changelog::unreleased(&content))
+}
+
+/// The body of the `## ` heading naming `version` (`## [1.2.0] - date` or
+/// `## 1.2.0`), up to the next `## ` heading.
+pub fn changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let names_version = |line: &str| {
+        let heading = line.trim_start_matches("## ").trim_start();
+        heading.starts_with(&format!("[{version}]"))
+            || heading.split_whitespace().next().is_some_and(|w| w.trim_start_matches('v') == version)
+    };
+    let mut lines = changelog.lines().skip_while(|l| !(l.starts_with("## ") && names_version(l)));
+    lines.next()?;
+    let body: Vec<&str> = lines.take_while(|l| !l.starts_with("## ")).collect();
+    let body = body.join("\n").trim().to_string();
+    (!body.is_empty()).then_some(body)
+}
+
+/// The Minecraft releases a jar supports: its target's range from mcmod.toml
+/// (`minecraft` up to `max_minecraft`).
+fn game_versions(config: &McmodConfig, minecraft: &str) -> Vec<String> {
+    let Some(target) = config.versions.targets.iter().find(|t| t.minecraft == minecraft) else {
+        return vec![minecraft.to_string()];
+    };
+    let in_range = |v: &str| {
+        version_meta::compare_versions(v, &target.minecraft).is_ge()
+            && version_meta::compare_versions(v, &target.max_minecraft).is_le()
+    };
+    let mut versions: Vec<String> = version_meta::supported_versions()
+        .into_iter()
+        .filter(|v| in_range(v))
+        .map(str::to_string)
+        .collect();
+    for bound in [&target.minecraft, &target.max_minecraft] {
+        if !versions.contains(bound) {
+            versions.push(bound.clone());
+        }
+    }
+    versions.sort_by(|a, b| version_meta::compare_versions(a, b));
+    versions
+}
+
+/// The `data` part of a Modrinth create-version request for one jar.
+fn modrinth_version(config: &McmodConfig, project_id: &str, release: &Release, jar: &BuiltJar) -> serde_json::Value {
+    let minecraft = jar.minecraft.as_deref().unwrap_or_default();
+    let loader = jar.loader.as_deref().unwrap_or_default();
+    let mut dependencies = Vec::new();
+    if loader == "fabric" {
+        dependencies.push(serde_json::json!({ "project_id": FABRIC_API_PROJECT, "dependency_type": "required" }));
+    }
+    if config.features.architectury {
+        dependencies.push(serde_json::json!({ "project_id": ARCHITECTURY_API_PROJECT, "dependency_type": "required" }));
+    }
+    serde_json::json!({
+        "project_id": project_id,
+        "name": display_name(config, release, jar),
+        "version_number": format!("{}+{minecraft}-{loader}", release.version),
+        "changelog": release.changelog,
+        "dependencies": dependencies,
+        "game_versions": game_versions(config, minecraft),
+        "version_type": release.version_type.as_str(),
+        "loaders": [loader],
+        "featured": false,
+        "file_parts": ["file"],
+        "primary_file": "file",
+    })
+}
+
+/// `Test Mod 1.2.0 (Fabric 1.21.1)`.
+fn display_name(config: &McmodConfig, release: &Release, jar: &BuiltJar) -> String {
+    format!(
+        "{} {} ({} {})",
+        config.mod_info.mod_name,
+        release.version,
+        loader_name(jar.loader.as_deref().unwrap_or_default()),
+        jar.minecraft.as_deref().unwrap_or_default()
+    )
+}
+
+fn loader_name(loader: &str) -> &str {
+    match loader {
+        "fabric" => "Fabric",
+        "quilt" => "Quilt",
+        "neoforge" => "NeoForge",
+        "forge" => "Forge",
+        other => other,
+    }
+}
+
+/// One field of a multipart/form-data body.
+struct Part<'a> {
+    name: &'a str,
+    file_name: Option<&'a str>,
+    content_type: &'a str,
+    bytes: &'a [u8],
+}
+
+fn multipart(boundary: &str, parts: &[Part]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for part in parts {
+        body.extend(format!("--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"", part.name).as_bytes());
+        if let Some(file_name) = part.file_name {
+            body.extend(format!("; filename=\"{file_name}\"").as_bytes());
+        }
+        body.extend(format!("\r\nContent-Type: {}\r\n\r\n", part.content_type).as_bytes());
+        body.extend(part.bytes);
+        body.extend(b"\r\n");
+    }
+    body.extend(format!("--{boundary}--\r\n").as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VersionTarget;
+    use std::path::PathBuf;
+
+    fn config() -> McmodConfig {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        config.versions.targets.push(VersionTarget {
+            minecraft: "1.21.1".to_string(),
+            max_minecraft: "1.21.3".to_string(),
+            fabric_loader: String::new(),
+            fabric_api: String::new(),
+            neoforge: String::new(),
+            forge: String::new(),
+            quilt_loader: String::new(),
+            architectury_api: String::new(),
+        });
+        config
+    }
+
+    #[test]
+    fn test_modrinth_version() {
+        let config = config();
+        let release = Release {
+            version: "1.2.0".to_string(),
+            version_type: VersionType::Beta,
+            changelog: "- Fixed things".to_string(),
+        };
+        let jar = BuiltJar {
+            path: PathBuf::from("versions/1.21.1-fabric/build/libs/testmod-1.2.0+1.21.1-fabric.jar"),
+            minecraft: Some("1.21.1".to_string()),
+            loader: Some("fabric".to_string()),
+        };
+        let data = modrinth_version(&config, "AABBCCDD", &release, &jar);
+        assert_eq!(data["name"], "Test Mod 1.2.0 (Fabric 1.21.1)");
+        assert_eq!(data["version_number"], "1.2.0+1.21.1-fabric");
+        assert_eq!(data["game_versions"], serde_json::json!(["1.21.1", "1.21.2", "1.21.3"]));
+        assert_eq!(data["loaders"], serde_json::json!(["fabric"]));
+        assert_eq!(data["version_type"], "beta");
+        assert_eq!(data["dependencies"][0]["project_id"], FABRIC_API_PROJECT);
+
+        let jar = BuiltJar { loader: Some("neoforge".to_string()), ..jar };
+        let data = modrinth_version(&config, "AABBCCDD", &release, &jar);
+        assert_eq!(data["dependencies"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_curseforge() {
+        let types: Vec<GameVersionType> = serde_json::from_str(
+            r#"[{"id": 77784, "name": "Minecraft 1.21", "slug": "minecraft-1-21"}, {"id": 68441, "name": "Modloader", "slug": "modloader"}, {"id": 2, "name": "Java", "slug": "java"}]"#,
+        )
+        .unwrap();
+        let versions: Vec<GameVersion> = serde_json::from_str(
+            r#"[
+                {"id": 11779, "gameVersionTypeID": 77784, "name": "1.21.1", "slug": "1-21-1"},
+                {"id": 11780, "gameVersionTypeID": 77784, "name": "1.21.2", "slug": "1-21-2"},
+                {"id": 7499, "gameVersionTypeID": 68441, "name": "Fabric", "slug": "fabric"},
+                {"id": 10150, "gameVersionTypeID": 68441, "name": "NeoForge", "slug": "neoforge"},
+                {"id": 1, "gameVersionTypeID": 2, "name": "1.21.1", "slug": "bogus"}
+            ]"#,
+        )
+        .unwrap();
+        let minecraft = vec!["1.21.1".to_string(), "1.21.2".to_string()];
+        assert_eq!(game_version_ids(&types, &versions, &minecraft, "neoforge").unwrap(), [11779, 11780, 10150]);
+        assert!(game_version_ids(&types, &versions, &["1.21.3".to_string()], "fabric").is_err());
+        assert!(game_version_ids(&types, &versions, &minecraft, "quilt").is_err());
+
+        let config = config();
+        let release = Release {
+            version: "1.2.0".to_string(),
+            version_type: VersionType::Release,
+            changelog: "- Fixed things".to_string(),
+        };
+        let jar = BuiltJar {
+            path: PathBuf::from("versions/1.21.1-fabric/build/libs/testmod-1.2.0.jar"),
+            minecraft: Some("1.21.1".to_string()),
+            loader: Some("fabric".to_string()),
+        };
+        let metadata = curseforge_metadata(&config, &release, &jar, serde_json::json!([11779, 7499]));
+        assert_eq!(metadata["displayName"], "Test Mod 1.2.0 (Fabric 1.21.1)");
+        assert_eq!(metadata["releaseType"], "release");
+        assert_eq!(metadata["gameVersions"], serde_json::json!([11779, 7499]));
+        assert_eq!(
+            metadata["relations"],
+            serde_json::json!({ "projects": [{ "slug": "fabric-api", "type": "requiredDependency" }] })
+        );
+        let jar = BuiltJar { loader: Some("neoforge".to_string()), ..jar };
+        assert!(curseforge_metadata(&config, &release, &jar, serde_json::json!([])).get("relations").is_none());
+    }
+
+    #[test]
+    fn test_version_type_and_changelog() {
+        assert_eq!(VersionType::detect("", "1.0.0"), VersionType::Release);
+        assert_eq!(VersionType::detect("", "1.0.0-rc.1"), VersionType::Beta);
+        assert_eq!(VersionType::detect("", "2.0.0-alpha"), VersionType::Alpha);
+        assert_eq!(VersionType::detect("version_type=beta\n", "1.0.0"), VersionType::Beta);
+
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- WIP\n\n## [1.2.0] - 2026-05-01\n\n### Fixed\n- Crash on load\n\n## [1.1.0]\n- Old\n";
+        assert_eq!(changelog_section(changelog, "1.2.0").as_deref(), Some("### Fixed\n- Crash on load"));
+        assert_eq!(changelog_section("## v1.1.0\nNotes\n", "1.1.0").as_deref(), Some("Notes"));
+        assert_eq!(changelog_section(changelog, "1.2"), None);
+    }
+
+    #[test]
+    fn test_multipart() {
+        let body = multipart(
+            "XYZ",
+            &[
+                Part { name: "data", file_name: None, content_type: "application/json", bytes: b"{}" },
+                Part { name: "file", file_name: Some("a.jar"), content_type: "application/java-archive", bytes: b"PK" },
+            ],
+        );
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "--XYZ\r\nContent-Disposition: form-data; name=\"data\"\r\nContent-Type: application/json\r\n\r\n{}\r\n\
+             --XYZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.jar\"\r\nContent-Type: application/java-archive\r\n\r\nPK\r\n\
+             --XYZ--\r\n"
+        );
+    }
+}
@@ -0,0 +1,425 @@
+use crate::commands::changelog::{self, CHANGELOG};
+use crate::commands::publish::{self, VersionType};
+use crate::commands::{build, report};
+use crate::config::{CurseforgePublish, McmodConfig, ModrinthPublish};
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
+
+const GITHUB_API: &str = "https://api.github.com";
+
+/// Which part of `mod.version` a release increments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Bump {
+    Major,
+    Minor,
+    #[default]
+    Patch,
+}
+
+/// The steps of a release, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Bump,
+    Changelog,
+    Build,
+    Validate,
+    Tag,
+    Github,
+    Modrinth,
+    Curseforge,
+}
+
+impl Step {
+    fn title(&self) -> &'static str {
+        match self {
+            Step::Bump => "Bump version",
+            Step::Changelog => "Update changelog",
+            Step::Build => "Build all loaders",
+            Step::Validate => "Validate jars",
+            Step::Tag => "Commit and tag",
+            Step::Github => "GitHub release",
+            Step::Modrinth => "Publish to Modrinth",
+            Step::Curseforge => "Publish to CurseForge",
+        }
+    }
+
+    /// The `mcmod release` flag that skips the step.
+    fn flag(&self) -> &'static str {
+        match self {
+            Step::Bump => "--no-bump",
+            Step::Changelog => "--no-changelog",
+            Step::Build => "--no-build",
+            Step::Validate => "--no-validate",
+            Step::Tag => "--no-tag",
+            Step::Github => "--no-github",
+            Step::Modrinth => "--no-modrinth",
+            Step::Curseforge => "--no-curseforge",
+        }
+    }
+}
+
+/// `mcmod release` flags.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOptions {
+    pub bump: Bump,
+    /// Release exactly this version instead of bumping.
+    pub version: Option<String>,
+    pub version_type: Option<VersionType>,
+    pub no_bump: bool,
+    pub no_changelog: bool,
+    pub no_build: bool,
+    pub no_validate: bool,
+    pub no_tag: bool,
+    pub no_github: bool,
+    pub no_modrinth: bool,
+    pub no_curseforge: bool,
+    pub dry_run: bool,
+}
+
+/// One step of the plan, with what it will do or why it won't run.
+#[derive(Debug)]
+struct Planned {
+    step: Step,
+    detail: String,
+    skipped: Option<String>,
+}
+
+/// `mcmod release`: bump the version, update the changelog, build every
+/// loader, validate the jars, commit and tag, create the GitHub release, and
+/// publish to Modrinth and CurseForge. The whole plan is printed first;
+/// `--dry-run` stops there, with the file edits shown as a diff. A failed
+/// step stops the release and prints the command that resumes it.
+pub fn run(dir: &Path, opts: &ReleaseOptions) -> Result<()> {
+    println!("{}", "\n  mcmod release\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "mcmod release builds and publishes mod and library jars (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    let properties = crate::fs::read_to_string(&dir.join("gradle.properties"))?;
+    let current = gradle::get_property_content(&properties, "mod.version")
+        .ok_or_else(|| McmodError::Other("gradle.properties has no mod.version".to_string()))?;
+    let version = match (&opts.version, opts.no_bump) {
+        (Some(version), _) => version.clone(),
+        (None, true) => current.clone(),
+        (None, false) => bump(&current, opts.bump)?,
+    };
+
+    let plan = plan(dir, &config, opts, &current, &version);
+    println!("  {:<10} {}", "Version:", if version == current { version.clone() } else { format!("{current} → {version}") });
+    println!();
+    for (i, planned) in plan.iter().enumerate() {
+        match &planned.skipped {
+            None => println!("  {}. {:<22} {}", i + 1, planned.step.title().bold(), planned.detail.dimmed()),
+            Some(why) => println!("  {}. {:<22} {}", i + 1, planned.step.title().dimmed(), format!("skipped: {why}").dimmed()),
+        }
+    }
+    println!();
+
+    if opts.dry_run {
+        let runs = |step: Step| plan.iter().any(|p| p.step == step && p.skipped.is_none());
+        crate::util::set_quiet(true);
+        let result = crate::diff::preview(dir, false, || {
+            if runs(Step::Bump) {
+                write_version(dir, &version)?;
+            }
+            if runs(Step::Changelog) {
+                changelog::write_release(dir, &version)?;
+            }
+            Ok(())
+        });
+        crate::util::set_quiet(false);
+        result?;
+        println!("{}", "  Dry run: nothing was built, committed, tagged, or published.".dimmed());
+        return Ok(());
+    }
+
+    let mut done: Vec<Step> = Vec::new();
+    for planned in plan.iter().filter(|p| p.skipped.is_none()) {
+        println!("{}", format!("  ▸ {}", planned.step.title()).bold());
+        if let Err(e) = apply(dir, &config, opts, planned.step, &version) {
+            println!("\n  {}", format!("{} failed.", planned.step.title()).red().bold());
+            // Pinning the version covers the bump, done or not
+            let mut resume = vec!["mcmod release".to_string(), format!("--version {version}")];
+            resume.extend(
+                plan.iter()
+                    .filter(|p| p.step != Step::Bump && (p.skipped.is_some() || done.contains(&p.step)))
+                    .map(|p| p.step.flag().to_string()),
+            );
+            println!("  Fix the problem and continue with:\n    {}\n", resume.join(" ").green());
+            return Err(e);
+        }
+        done.push(planned.step);
+        println!();
+    }
+    println!("  {}", format!("Released {} {version}", config.mod_info.mod_name).bold().green());
+    Ok(())
+}
+
+/// Every step in order, each with what it will do or why it's skipped.
+fn plan(dir: &Path, config: &McmodConfig, opts: &ReleaseOptions, current: &str, version: &str) -> Vec<Planned> {
+    let git = dir.join(".git").exists();
+    let repo = git.then(|| github_repo(dir)).flatten();
+    let tag = format!("v{version}");
+    let entry = |step: Step, off: bool, missing: Option<&str>, detail: String| Planned {
+        step,
+        detail,
+        skipped: if off { Some(step.flag().to_string()) } else { missing.map(str::to_string) },
+    };
+    let modrinth = ModrinthPublish::from_config(config);
+    let curseforge = CurseforgePublish::from_config(config);
+    vec![
+        entry(
+            Step::Bump,
+            opts.no_bump && opts.version.is_none(),
+            (version == current).then_some("the version is unchanged"),
+            format!("mod.version {current} → {version} in gradle.properties"),
+        ),
+        entry(
+            Step::Changelog,
+            opts.no_changelog,
+            None,
+            format!("a [{version}] section in {CHANGELOG}, from [Unreleased] or the commits since the last tag"),
+        ),
+        entry(Step::Build, opts.no_build, None, "./gradlew chiseledBuild".to_string()),
+        entry(Step::Validate, opts.no_validate, None, "the `mcmod report jars` checks".to_string()),
+        entry(
+            Step::Tag,
+            opts.no_tag,
+            (!git).then_some("not a git repository"),
+            format!("commit gradle.properties and {CHANGELOG}, tag {tag}"),
+        ),
+        entry(
+            Step::Github,
+            opts.no_github,
+            repo.is_none().then_some("no GitHub remote named origin"),
+            format!("push, then create release {tag} on {} with the jars", repo.as_deref().unwrap_or_default()),
+        ),
+        entry(
+            Step::Modrinth,
+            opts.no_modrinth,
+            modrinth.is_none().then_some("no [publish.modrinth] in mcmod.toml"),
+            format!("project {}", modrinth.map(|m| m.project_id).unwrap_or_default()),
+        ),
+        entry(
+            Step::Curseforge,
+            opts.no_curseforge,
+            curseforge.is_none().then_some("no [publish.curseforge] in mcmod.toml"),
+            format!("project {}", curseforge.map(|c| c.project_id).unwrap_or_default()),
+        ),
+    ]
+}
+
+fn apply(dir: &Path, config: &McmodConfig, opts: &ReleaseOptions, step: Step, version: &str) -> Result<()> {
+    match step {
+        Step::Bump => write_version(dir, version),
+        Step::Changelog => changelog::write_release(dir, version),
+        Step::Build => {
+            let gradlew = build::gradle_wrapper(dir)?;
+            build::check_java()?;
+            let status = build::gradle(dir, &gradlew, &["chiseledBuild".to_string()])?;
+            if !status.success() {
+                return Err(McmodError::Other(format!("Gradle build failed ({status})")));
+            }
+            Ok(())
+        }
+        Step::Validate => {
+            let jars = report::find_jars(dir);
+            if jars.is_empty() {
+                return Err(McmodError::Other("No built jars to validate; run the build step first".to_string()));
+            }
+            let failed = report::report(dir, config, &jars)?;
+            if failed > 0 {
+                return Err(McmodError::Other(format!("{failed} jar(s) have packaging problems")));
+            }
+            Ok(())
+        }
+        Step::Tag => commit_and_tag(dir, config, version),
+        Step::Github => github_release(dir, config, version),
+        Step::Modrinth => publish::run_modrinth(dir, false, opts.version_type, None, None),
+        Step::Curseforge => publish::run_curseforge(dir, false, opts.version_type, None, None),
+    }
+}
+
+/// `version` with the `bump` part incremented and the lower parts reset. A
+/// pre-release suffix is dropped, finishing that pre-release.
+fn bump(version: &str, bump: Bump) -> Result<String> {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<u64> = core
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()
+        .filter(|p: &Vec<u64>| !p.is_empty() && p.len() <= 3)
+        .ok_or_else(|| {
+            McmodError::Other(format!("Can't bump mod.version {version}; pass --version with the new version"))
+        })?;
+    let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+    if core != version && bump == Bump::Patch {
+        return Ok(format!("{}.{}.{}", part(0), part(1), part(2)));
+    }
+    Ok(match bump {
+        Bump::Major => format!("{}.0.0", part(0) + 1),
+        Bump::Minor => format!("{}.{}.0", part(0), part(1) + 1),
+        Bump::Patch => format!("{}.{}.{}", part(0), part(1), part(2) + 1),
+    })
+}
+
+fn write_version(dir: &Path, version: &str) -> Result<()> {
+    let path = dir.join("gradle.properties");
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = gradle::set_property_content(&content, "mod.version", version);
+    crate::diff::apply_edit(&path, &content, &updated)?;
+    crate::util::progress(format!("  mod.version = {version}").green());
+    Ok(())
+}
+
+/// Subjects of the commits since the newest tag (all commits without one),
+/// leaving out earlier release commits.
+pub fn commits_since_tag(dir: &Path) -> Vec<String> {
+    let range = match git(dir, &["describe", "--tags", "--abbrev=0"]) {
+        Ok(tag) => format!("{}..HEAD", tag.trim()),
+        Err(_) => "HEAD".to_string(),
+    };
+    git(dir, &["log", "--pretty=format:%s", &range])
+        .unwrap_or_default()
+        .lines()
+        .filter(|s| !s.is_empty() && !s.starts_with("Release v"))
+        .map(str::to_string)
+        .collect()
+}
+
+fn commit_and_tag(dir: &Path, config: &McmodConfig, version: &str) -> Result<()> {
+    let tag = format!("v{version}");
+    if git(dir, &["rev-parse", "--verify", "--quiet", &format!("refs/tags/{tag}")]).is_ok() {
+        return Err(McmodError::Other(format!("Tag {tag} already exists")));
+    }
+    let files: Vec<&str> = ["gradle.properties", CHANGELOG].into_iter().filter(|f| dir.join(f).exists()).collect();
+    let mut add = vec!["add", "--"];
+    add.extend(&files);
+    git(dir, &add)?;
+    // `diff --cached --quiet` fails when something is staged
+    if git(dir, &["diff", "--cached", "--quiet"]).is_err() {
+        git(dir, &["commit", "-m", &format!("Release {tag}")])?;
+        println!("{}", format!("  Committed Release {tag}").green());
+    }
+    git(dir, &["tag", "-a", &tag, "-m", &format!("{} {version}", config.mod_info.mod_name)])?;
+    println!("{}", format!("  Tagged {tag}").green());
+    Ok(())
+}
+
+/// Push the branch and tag, create the GitHub release with the changelog
+/// section as notes, and attach the built jars.
+fn github_release(dir: &Path, config: &McmodConfig, version: &str) -> Result<()> {
+    let repo = github_repo(dir).ok_or_else(|| McmodError::Other("origin is not a GitHub repository".to_string()))?;
+    let token = std::env::var("GITHUB_TOKEN").or_else(|_| std::env::var("GH_TOKEN")).map_err(|_| {
+        McmodError::Other("Set GITHUB_TOKEN to a token that can create releases (contents: write)".to_string())
+    })?;
+    let tag = format!("v{version}");
+    git(dir, &["push", "origin", "HEAD"])?;
+    git(dir, &["push", "origin", &tag])?;
+    println!("{}", format!("  Pushed {tag} to {repo}").green());
+
+    let notes = crate::fs::read_to_string(&dir.join(CHANGELOG))
+        .ok()
+        .and_then(|c| publish::changelog_section(&c, version))
+        .unwrap_or_default();
+    let prerelease = version.contains('-');
+    let body = serde_json::json!({
+        "tag_name": tag,
+        "name": format!("{} {version}", config.mod_info.mod_name),
+        "body": notes,
+        "prerelease": prerelease,
+    });
+    let auth = format!("Bearer {token}");
+    let headers = [
+        ("Authorization", auth.as_str()),
+        ("Accept", "application/vnd.github+json"),
+        ("Content-Type", "application/json"),
+    ];
+    let (status, response) =
+        crate::util::http_post(&format!("{GITHUB_API}/repos/{repo}/releases"), &headers, body.to_string().as_bytes())?;
+    if status != 201 {
+        return Err(McmodError::Http(format!("GitHub rejected the release (HTTP {status}): {}", response.trim())));
+    }
+    let release: serde_json::Value = serde_json::from_str(&response)?;
+    let upload_url = release["upload_url"].as_str().unwrap_or_default();
+    let upload_url = upload_url.split('{').next().unwrap_or_default();
+
+    for jar in report::find_jars(dir) {
+        let name = jar.path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let bytes = crate::fs::read(&jar.path)?;
+        let headers = [
+            ("Authorization", auth.as_str()),
+            ("Accept", "application/vnd.github+json"),
+            ("Content-Type", "application/java-archive"),
+        ];
+        let (status, response) = crate::util::http_post(&format!("{upload_url}?name={name}"), &headers, &bytes)?;
+        if status != 201 {
+            return Err(McmodError::Http(format!("GitHub rejected {name} (HTTP {status}): {}", response.trim())));
+        }
+        println!("{}", format!("  Attached {name}").green());
+    }
+    if let Some(url) = release["html_url"].as_str() {
+        println!("    {}", url.dimmed());
+    }
+    Ok(())
+}
+
+/// `owner/repo` of the `origin` remote when it's on GitHub.
+fn github_repo(dir: &Path) -> Option<String> {
+    parse_github_remote(git(dir, &["remote", "get-url", "origin"]).ok()?.trim())
+}
+
+/// `owner/repo` from an HTTPS or SSH GitHub remote URL.
+fn parse_github_remote(url: &str) -> Option<String> {
+    let path = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("git@github.com:"))
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, repo) = path.split_once('/')?;
+    (!owner.is_empty() && !repo.is_empty() && !repo.contains('/')).then(|| format!("{owner}/{repo}"))
+}
+
+/// Run git in `dir`, returning stdout; a failing command is an error with
+/// git's message.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| McmodError::Other(format!("Could not run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(McmodError::Other(format!("git {} failed: {}", args.join(" "), stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump() {
+        assert_eq!(bump("1.2.3", Bump::Patch).unwrap(), "1.2.4");
+        assert_eq!(bump("1.2.3", Bump::Minor).unwrap(), "1.3.0");
+        assert_eq!(bump("1.2.3", Bump::Major).unwrap(), "2.0.0");
+        assert_eq!(bump("1.0", Bump::Patch).unwrap(), "1.0.1");
+        assert_eq!(bump("1.3.0-beta.2", Bump::Patch).unwrap(), "1.3.0");
+        assert_eq!(bump("1.3.0-beta.2", Bump::Minor).unwrap(), "1.4.0");
+        assert!(bump("snapshot", Bump::Patch).is_err());
+    }
+
+    #[test]
+    fn test_parse_github_remote() {
+        assert_eq!(parse_github_remote("https://github.com/jane/cool-mod.git").as_deref(), Some("jane/cool-mod"));
+        assert_eq!(parse_github_remote("git@github.com:jane/cool-mod.git").as_deref(), Some("jane/cool-mod"));
+        assert_eq!(parse_github_remote("https://gitlab.com/jane/cool-mod"), None);
+    }
+}
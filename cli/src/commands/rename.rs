@@ -0,0 +1,376 @@
+use crate::config::McmodConfig;
+use crate::error::{Context, McmodError, Result};
+use crate::util::to_pascal_case;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Directories never touched by a rename: build output, VCS and IDE state,
+/// and the dev run directory.
+const SKIP_DIRS: &[&str] = &["build", ".gradle", ".git", ".idea", "run", "out", "node_modules"];
+/// Files whose text is rewritten; everything else is only moved.
+const TEXT_EXTENSIONS: &[&str] = &[
+    "java", "kt", "kts", "gradle", "properties", "json", "toml", "yml", "yaml", "mcmeta", "md", "txt",
+];
+/// Stand-in for the new package while mod_id occurrences are replaced, so a
+/// package that contains the mod id (`com.example.mymod`) keeps its segment.
+const PACKAGE_SENTINEL: &str = "\u{0}mcmod-package\u{0}";
+
+/// One file of the rename: where it is, where it goes, and its new content.
+#[derive(Debug)]
+struct Step {
+    from: PathBuf,
+    to: PathBuf,
+    content: Vec<u8>,
+}
+
+impl Step {
+    fn moves(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+/// `mcmod rename`: change the mod id and/or Java package everywhere they
+/// appear. Every new file is written before any old one is removed, and a
+/// failed write restores the files already written.
+pub fn run(dir: &Path, mod_id: Option<&str>, package: Option<&str>, dry_run: bool) -> Result<()> {
+    let mut config = McmodConfig::load(dir)?;
+    let old_id = config.mod_info.mod_id.clone();
+    let old_package = config.mod_info.package.clone();
+    let new_id = mod_id.unwrap_or(&old_id).to_string();
+    let new_package = package.unwrap_or(&old_package).to_string();
+    crate::util::validate_mod_id(&new_id)?;
+    crate::util::validate_package(&new_package)?;
+    if new_id == old_id && new_package == old_package {
+        return Err(McmodError::Other(
+            "Nothing to rename: pass --mod-id and/or --package with a new value".to_string(),
+        ));
+    }
+
+    println!("{}", "\n  mcmod rename\n".bold().cyan());
+    if new_id != old_id {
+        println!("  mod id:  {old_id} → {new_id}");
+    }
+    if new_package != old_package {
+        println!("  package: {old_package} → {new_package}");
+    }
+    println!();
+
+    let renames = Renames {
+        old_id: &old_id,
+        new_id: &new_id,
+        old_package: &old_package,
+        new_package: &new_package,
+    };
+    let steps = plan(dir, &renames)?;
+    for step in &steps {
+        let from = step.from.strip_prefix(dir).unwrap_or(&step.from).display();
+        if step.moves() {
+            let to = step.to.strip_prefix(dir).unwrap_or(&step.to).display();
+            println!("  {} {from} → {to}", "~".cyan());
+        } else {
+            println!("  {} {from}", "~".cyan());
+        }
+    }
+    println!("  {} mcmod.toml", "~".cyan());
+    println!();
+    if dry_run {
+        println!("{}", "  Dry run — nothing was written.".yellow());
+        return Ok(());
+    }
+
+    config.mod_info.mod_id = new_id;
+    config.mod_info.package = new_package;
+    apply(dir, &steps, &config)?;
+    println!(
+        "{}",
+        format!("  Renamed across {} file(s). Run `./gradlew clean` before the next build.", steps.len())
+            .bold()
+            .green()
+    );
+    Ok(())
+}
+
+struct Renames<'a> {
+    old_id: &'a str,
+    new_id: &'a str,
+    old_package: &'a str,
+    new_package: &'a str,
+}
+
+impl Renames<'_> {
+    /// Rewrite package names, then mod ids as whole identifiers, then class
+    /// names derived from the mod id (`MymodMod`, `MymodClient`, `MymodPlugin`).
+    fn text(&self, content: &str) -> String {
+        let content = replace_identifier(content, self.old_package, self.new_package);
+        if self.old_id == self.new_id {
+            return content;
+        }
+        let shielded = content.replace(self.new_package, PACKAGE_SENTINEL);
+        let content =
+            replace_identifier(&shielded, self.old_id, self.new_id).replace(PACKAGE_SENTINEL, self.new_package);
+        replace_class_prefix(&content, &to_pascal_case(self.old_id), &to_pascal_case(self.new_id))
+    }
+
+    /// A source file named after a class derived from the mod id gets the new name.
+    fn file_name(&self, name: &str) -> String {
+        if self.old_id == self.new_id {
+            return name.to_string();
+        }
+        replace_class_prefix(name, &to_pascal_case(self.old_id), &to_pascal_case(self.new_id))
+    }
+
+    /// Where a project-relative path moves: sources follow the package
+    /// directory, and resources named after the mod id follow it.
+    fn path(&self, rel: &Path) -> PathBuf {
+        let parts: Vec<String> = rel.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+        let Some(src) = parts.iter().position(|p| p == "src") else {
+            return rel.to_path_buf();
+        };
+        // <module>/src/<set>/<root>/...
+        let (Some(root), rest) = (parts.get(src + 2), parts.get(src + 3..).unwrap_or_default()) else {
+            return rel.to_path_buf();
+        };
+        let mut rest: Vec<String> = rest.to_vec();
+        match root.as_str() {
+            "java" | "kotlin" => {
+                let old: Vec<&str> = self.old_package.split('.').collect();
+                if rest.len() > old.len() && rest.iter().zip(&old).all(|(a, b)| a == b) {
+                    let tail = rest.split_off(old.len());
+                    rest = self.new_package.split('.').map(str::to_string).chain(tail).collect();
+                }
+                if let Some(name) = rest.last_mut() {
+                    *name = self.file_name(name);
+                }
+            }
+            "resources" => {
+                if rest.len() > 1 && (rest[0] == "assets" || rest[0] == "data") && rest[1] == self.old_id {
+                    rest[1] = self.new_id.to_string();
+                } else if rest.len() == 1 {
+                    if let Some(suffix) = rest[0].strip_prefix(&format!("{}.", self.old_id)) {
+                        rest[0] = format!("{}.{suffix}", self.new_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+        parts[..src + 3].iter().cloned().chain(rest).collect()
+    }
+}
+
+/// Replace `old` where it stands as a whole identifier: not preceded or
+/// followed by a letter, digit or underscore. `old` may contain dots.
+fn replace_identifier(text: &str, old: &str, new: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(old) {
+        let before = rest[..i].chars().last().or_else(|| out.chars().last());
+        let after = rest[i + old.len()..].chars().next();
+        out.push_str(&rest[..i]);
+        if before.is_some_and(is_ident) || after.is_some_and(is_ident) {
+            out.push_str(old);
+        } else {
+            out.push_str(new);
+        }
+        rest = &rest[i + old.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Replace `old` where it starts a class name: not preceded by an identifier
+/// character, and followed by an uppercase letter or a non-identifier.
+fn replace_class_prefix(text: &str, old: &str, new: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(old) {
+        let before = rest[..i].chars().last().or_else(|| out.chars().last());
+        let after = rest[i + old.len()..].chars().next();
+        out.push_str(&rest[..i]);
+        let starts_class = !before.is_some_and(is_ident) && !after.is_some_and(|c| is_ident(c) && !c.is_ascii_uppercase());
+        out.push_str(if starts_class { new } else { old });
+        rest = &rest[i + old.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Every file that changes path or content, read fully into memory. Fails
+/// before anything is written when a destination already exists.
+fn plan(dir: &Path, renames: &Renames) -> Result<Vec<Step>> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files);
+    let mut steps = Vec::new();
+    for from in files {
+        let rel = from.strip_prefix(dir).unwrap_or(&from);
+        if rel == Path::new(crate::config::CONFIG_FILE) {
+            continue;
+        }
+        let to = dir.join(renames.path(rel));
+        let bytes = std::fs::read(&from).with_context(|| format!("Reading {}", from.display()))?;
+        let is_text = from
+            .extension()
+            .is_some_and(|e| TEXT_EXTENSIONS.contains(&e.to_string_lossy().as_ref()));
+        let content = match (is_text, String::from_utf8(bytes.clone())) {
+            (true, Ok(text)) => renames.text(&text).into_bytes(),
+            _ => bytes.clone(),
+        };
+        if to != from || content != bytes {
+            steps.push(Step { from, to, content });
+        }
+    }
+
+    for step in steps.iter().filter(|s| s.moves()) {
+        let replaced = steps.iter().any(|s| s.moves() && s.from == step.to);
+        if step.to.exists() && !replaced {
+            return Err(McmodError::Other(format!(
+                "Can't rename: {} already exists",
+                step.to.strip_prefix(dir).unwrap_or(&step.to).display()
+            )));
+        }
+    }
+    Ok(steps)
+}
+
+/// Write every step, then mcmod.toml, then remove the moved-away files. A
+/// failure while writing puts back everything written so far.
+fn apply(dir: &Path, steps: &[Step], config: &McmodConfig) -> Result<()> {
+    let mut written: Vec<(PathBuf, Option<Vec<u8>>)> = Vec::new();
+    let result = steps
+        .iter()
+        .try_for_each(|step| {
+            written.push((step.to.clone(), std::fs::read(&step.to).ok()));
+            crate::util::write_binary(&step.to, &step.content)
+        })
+        .and_then(|()| {
+            let path = dir.join(crate::config::CONFIG_FILE);
+            written.push((path.clone(), std::fs::read(&path).ok()));
+            config.save(dir)
+        });
+    if let Err(e) = result {
+        for (path, original) in written.iter().rev() {
+            let _ = match original {
+                Some(bytes) => crate::util::write_binary(path, bytes),
+                None => crate::fs::remove_file(path).map_err(McmodError::from),
+            };
+        }
+        return Err(e.context("Rename failed; the project was left unchanged"));
+    }
+
+    let targets: Vec<&PathBuf> = steps.iter().map(|s| &s.to).collect();
+    for step in steps.iter().filter(|s| s.moves() && !targets.contains(&&s.from)) {
+        crate::fs::remove_file(&step.from)?;
+        remove_empty_parents(dir, &step.from);
+    }
+    Ok(())
+}
+
+/// Remove directories left empty by moves, up to (not including) `dir`.
+fn remove_empty_parents(dir: &Path, path: &Path) {
+    let mut parent = path.parent();
+    while let Some(p) = parent {
+        if p == dir || std::fs::remove_dir(p).is_err() {
+            break;
+        }
+        parent = p.parent();
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                collect_files(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    fn renames<'a>(new_id: &'a str, new_package: &'a str) -> Renames<'a> {
+        Renames {
+            old_id: "testmod",
+            new_id,
+            old_package: "com.example.testmod",
+            new_package,
+        }
+    }
+
+    #[test]
+    fn test_rename_text_and_paths() {
+        let r = renames("gizmo", "com.example.testmod");
+        assert_eq!(
+            r.text("package com.example.testmod;\nMOD_ID = \"testmod\"; // testmod2, my_testmod\n\"item.testmod.ruby\""),
+            "package com.example.testmod;\nMOD_ID = \"gizmo\"; // testmod2, my_testmod\n\"item.gizmo.ruby\""
+        );
+        assert_eq!(
+            r.path(Path::new("src/main/resources/assets/testmod/lang/en_us.json")),
+            Path::new("src/main/resources/assets/gizmo/lang/en_us.json")
+        );
+        assert_eq!(
+            r.path(Path::new("src/main/resources/testmod.mixins.json")),
+            Path::new("src/main/resources/gizmo.mixins.json")
+        );
+        assert_eq!(
+            r.path(Path::new("src/client/java/com/example/testmod/TestmodClient.java")),
+            Path::new("src/client/java/com/example/testmod/GizmoClient.java")
+        );
+        assert_eq!(r.text("new TestmodMod(); TestmodsHelper x;"), "new GizmoMod(); TestmodsHelper x;");
+
+        let r = renames("testmod", "org.acme.gadget");
+        assert_eq!(r.text("import com.example.testmod.mixin.X;"), "import org.acme.gadget.mixin.X;");
+        assert_eq!(
+            r.path(Path::new("paper/src/main/java/com/example/testmod/paper/P.java")),
+            Path::new("paper/src/main/java/org/acme/gadget/paper/P.java")
+        );
+        assert_eq!(r.path(Path::new("README.md")), Path::new("README.md"));
+    }
+
+    #[test]
+    fn test_rename_project() {
+        let dir = std::env::temp_dir().join(format!("mcmod_rename_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        init::run(init::tests::options(&dir, &["fabric", "neoforge"])).unwrap();
+
+        run(&dir, Some("gizmo"), Some("org.acme.gizmo"), false).unwrap();
+        let config = McmodConfig::load(&dir).unwrap();
+        assert_eq!(config.mod_info.mod_id, "gizmo");
+        assert_eq!(config.mod_info.package, "org.acme.gizmo");
+        assert!(!dir.join("src/main/java/com").exists(), "old package directories are removed");
+        assert!(!dir.join("src/main/resources/testmod.mixins.json").exists());
+        let mixins = std::fs::read_to_string(dir.join("src/main/resources/gizmo.mixins.json")).unwrap();
+        assert!(mixins.contains("\"package\": \"org.acme.gizmo.mixin\""));
+        let mods_toml = std::fs::read_to_string(dir.join("src/main/resources/META-INF/neoforge.mods.toml")).unwrap();
+        assert!(mods_toml.contains("config = \"gizmo.mixins.json\""));
+        let properties = std::fs::read_to_string(dir.join("gradle.properties")).unwrap();
+        assert!(properties.contains("mod.id=gizmo\n") && properties.contains("mod.group=org.acme.gizmo\n"));
+        let main_class = std::fs::read_dir(dir.join("src/main/java/org/acme/gizmo"))
+            .unwrap()
+            .flatten()
+            .find(|e| e.path().extension().is_some_and(|x| x == "java"))
+            .unwrap();
+        let source = std::fs::read_to_string(main_class.path()).unwrap();
+        assert_eq!(main_class.file_name(), "GizmoMod.java");
+        assert!(source.starts_with("package org.acme.gizmo;"));
+        assert!(!source.contains("testmod"));
+
+        crate::commands::doctor::run(&dir, false, false).expect("the renamed project is consistent");
+
+        // A clashing destination stops the rename before anything changes
+        std::fs::write(dir.join("src/main/resources/other.mixins.json"), "{}").unwrap();
+        assert!(run(&dir, Some("other"), None, false).is_err());
+        assert_eq!(McmodConfig::load(&dir).unwrap().mod_info.mod_id, "gizmo");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
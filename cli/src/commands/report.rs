@@ -0,0 +1,279 @@
+use crate::commands::doctor::{self, LOADER_METADATA};
+use crate::config::McmodConfig;
+use crate::error::{McmodError, Result};
+use crate::jar::Jar;
+use crate::template;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Jar name suffixes that aren't the mod itself.
+const SECONDARY_JARS: &[&str] = &["-sources.jar", "-javadoc.jar", "-dev-shadow.jar", "-dev.jar"];
+
+/// Loaders whose production environment remaps mixin targets through a refmap.
+const REFMAP_LOADERS: &[&str] = &["fabric", "quilt", "forge"];
+
+/// A built jar and the Minecraft version and loader it was built for, from
+/// its `versions/<mc>-<loader>/` directory.
+#[derive(Debug, PartialEq)]
+pub struct BuiltJar {
+    pub path: PathBuf,
+    pub minecraft: Option<String>,
+    pub loader: Option<String>,
+}
+
+/// `mcmod report jars`: open every built jar and check what's packaged in it.
+pub fn run_jars(dir: &Path) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let jars = find_jars(dir);
+    if jars.is_empty() {
+        return Err(McmodError::Other(format!(
+            "No built jars under {}; run `./gradlew chiseledBuild` first",
+            dir.join("versions/*/build/libs").display()
+        )));
+    }
+    println!("{}", "\n  mcmod report jars\n".bold().cyan());
+    let failed = report(dir, &config, &jars)?;
+    println!();
+    if failed > 0 {
+        return Err(McmodError::Other(format!("{failed} jar(s) have packaging problems")));
+    }
+    println!("{}", format!("  {} jar(s) look correctly packaged", jars.len()).green());
+    Ok(())
+}
+
+/// Print each jar with its problems; returns how many jars had any.
+pub fn report(dir: &Path, config: &McmodConfig, jars: &[BuiltJar]) -> Result<usize> {
+    let mut failed = 0;
+    for built in jars {
+        let rel = built.path.strip_prefix(dir).unwrap_or(&built.path).display();
+        let problems = check_jar(&Jar::open(&built.path)?, built.loader.as_deref(), config)?;
+        if problems.is_empty() {
+            println!("  {} {rel}", "✓".green());
+            continue;
+        }
+        failed += 1;
+        println!("  {} {rel}", "✗".red());
+        for problem in problems {
+            println!("      {}", problem.yellow());
+        }
+    }
+    Ok(failed)
+}
+
+/// The mod jars under `versions/*/build/libs/`, skipping sources, javadoc and
+/// dev jars.
+pub fn find_jars(dir: &Path) -> Vec<BuiltJar> {
+    let Ok(versions) = std::fs::read_dir(dir.join("versions")) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<PathBuf> = versions.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    versions.sort();
+
+    let mut jars = Vec::new();
+    for version in versions {
+        let (minecraft, loader) = version
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.rsplit_once('-'))
+            .filter(|(_, l)| LOADER_METADATA.iter().any(|(name, _)| name == l))
+            .map(|(mc, loader)| (Some(mc.to_string()), Some(loader.to_string())))
+            .unwrap_or_default();
+        let Ok(libs) = std::fs::read_dir(version.join("build/libs")) else { continue };
+        let mut paths: Vec<PathBuf> = libs
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                let name = p.file_name().unwrap_or_default().to_string_lossy();
+                name.ends_with(".jar") && !SECONDARY_JARS.iter().any(|s| name.ends_with(s))
+            })
+            .collect();
+        paths.sort();
+        jars.extend(paths.into_iter().map(|path| BuiltJar {
+            path,
+            minecraft: minecraft.clone(),
+            loader: loader.clone(),
+        }));
+    }
+    jars
+}
+
+/// Problems with one jar: loader metadata that's missing, unexpanded, or for
+/// another mod id; mixin configs, their classes, or refmaps that weren't
+/// packaged; and the mod's own classes missing.
+pub fn check_jar(jar: &Jar, loader: Option<&str>, config: &McmodConfig) -> Result<Vec<String>> {
+    let mod_id = &config.mod_info.mod_id;
+    let mut problems = Vec::new();
+
+    let present: Vec<&str> = LOADER_METADATA
+        .iter()
+        .filter(|(_, file)| jar.contains(file))
+        .map(|(_, file)| *file)
+        .collect();
+    let metadata: Vec<&str> = match loader {
+        Some(loader) => {
+            let (_, file) = LOADER_METADATA.iter().find(|(l, _)| *l == loader).copied().unwrap_or_default();
+            if !jar.contains(file) {
+                problems.push(format!("{file} is missing; the {loader} loader won't see this mod"));
+            }
+            present.into_iter().filter(|f| *f == file).collect()
+        }
+        None if present.is_empty() => {
+            problems.push("no loader metadata (fabric.mod.json, mods.toml, ...) in the jar".to_string());
+            Vec::new()
+        }
+        None => present,
+    };
+
+    let mut mixin_configs = Vec::new();
+    for file in metadata {
+        let content = jar.read_to_string(file)?.unwrap_or_default();
+        if content.contains("${") {
+            problems.push(format!("{file} still has unexpanded ${{...}} properties; check processResources"));
+        }
+        if metadata_mod_id(file, &content).is_some_and(|id| id != *mod_id) {
+            problems.push(format!("{file} declares a mod id other than {mod_id}"));
+        }
+        for name in doctor::mixin_configs(file, &content) {
+            if !mixin_configs.contains(&name) {
+                mixin_configs.push(name);
+            }
+        }
+    }
+
+    for name in &mixin_configs {
+        let Some(content) = jar.read_to_string(name)? else {
+            problems.push(format!("{name} is referenced by the metadata but not packaged"));
+            continue;
+        };
+        let Ok(classes) = doctor::mixin_classes(&content) else {
+            problems.push(format!("{name} is not valid JSON"));
+            continue;
+        };
+        for (_, class) in &classes {
+            let entry = format!("{}.class", class.replace('.', "/"));
+            if !jar.contains(&entry) {
+                problems.push(format!("{name} lists {class}, but {entry} isn't in the jar"));
+            }
+        }
+        let refmap = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|v| v["refmap"].as_str().map(str::to_string));
+        match refmap {
+            Some(refmap) if !jar.contains(&refmap) => {
+                problems.push(format!("{name} points at refmap {refmap}, which isn't in the jar"));
+            }
+            None if !classes.is_empty() && loader.is_some_and(|l| REFMAP_LOADERS.contains(&l)) => {
+                problems.push(format!(
+                    "{name} has mixins but no refmap; they will fail to apply outside the dev environment"
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let vars = template::build_common_vars(config);
+    let package_dir = format!("{}/", vars["package_path"]);
+    if !jar.names().any(|n| n.starts_with(&package_dir) && n.ends_with(".class")) {
+        problems.push(format!("no classes under {package_dir}; the common sources weren't compiled into this jar"));
+    } else if !config.is_library() {
+        let main_class = format!("{package_dir}{}.class", vars["class_name"]);
+        if !jar.contains(&main_class) {
+            problems.push(format!("the mod's main class {main_class} isn't in the jar"));
+        }
+    }
+    Ok(problems)
+}
+
+/// The mod id a metadata file declares, when it can be read.
+fn metadata_mod_id(file: &str, content: &str) -> Option<String> {
+    if file.ends_with(".toml") {
+        let table: toml::Table = toml::from_str(content).ok()?;
+        let mods = table.get("mods")?.as_array()?;
+        return mods.first()?.get("modId")?.as_str().map(str::to_string);
+    }
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let id = if file == "quilt.mod.json" { &json["quilt_loader"]["id"] } else { &json["id"] };
+    id.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jar::stored_zip;
+
+    fn config() -> McmodConfig {
+        McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        )
+    }
+
+    const FABRIC_JSON: &[u8] = br#"{"id": "testmod", "mixins": ["testmod.mixins.json"]}"#;
+    const MIXINS: &[u8] = br#"{"package": "com.example.testmod.mixin", "refmap": "testmod-refmap.json", "mixins": ["TitleMixin"]}"#;
+
+    #[test]
+    fn test_check_good_jar() {
+        let jar = Jar::from_bytes(stored_zip(&[
+            ("fabric.mod.json", FABRIC_JSON),
+            ("testmod.mixins.json", MIXINS),
+            ("testmod-refmap.json", b"{}"),
+            ("com/example/testmod/TestmodMod.class", b""),
+            ("com/example/testmod/mixin/TitleMixin.class", b""),
+        ]))
+        .unwrap();
+        assert_eq!(check_jar(&jar, Some("fabric"), &config()).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_check_broken_jar() {
+        let jar = Jar::from_bytes(stored_zip(&[
+            ("fabric.mod.json", br#"{"id": "othermod", "version": "${version}", "mixins": ["testmod.mixins.json"]}"#),
+            ("testmod.mixins.json", br#"{"package": "com.example.testmod.mixin", "mixins": ["TitleMixin"]}"#),
+        ]))
+        .unwrap();
+        let problems = check_jar(&jar, Some("fabric"), &config()).unwrap();
+        assert_eq!(
+            problems,
+            [
+                "fabric.mod.json still has unexpanded ${...} properties; check processResources",
+                "fabric.mod.json declares a mod id other than testmod",
+                "testmod.mixins.json lists com.example.testmod.mixin.TitleMixin, but com/example/testmod/mixin/TitleMixin.class isn't in the jar",
+                "testmod.mixins.json has mixins but no refmap; they will fail to apply outside the dev environment",
+                "no classes under com/example/testmod/; the common sources weren't compiled into this jar",
+            ]
+        );
+
+        let jar = Jar::from_bytes(stored_zip(&[("com/example/testmod/TestmodMod.class", b"")])).unwrap();
+        let problems = check_jar(&jar, Some("neoforge"), &config()).unwrap();
+        assert_eq!(problems, ["META-INF/neoforge.mods.toml is missing; the neoforge loader won't see this mod"]);
+    }
+
+    #[test]
+    fn test_find_jars() {
+        let dir = std::env::temp_dir().join(format!("mcmod_report_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for file in [
+            "versions/1.21.4-fabric/build/libs/testmod-1.0.0+1.21.4-fabric.jar",
+            "versions/1.21.4-fabric/build/libs/testmod-1.0.0+1.21.4-fabric-sources.jar",
+            "versions/1.21.4-neoforge/build/libs/testmod-1.0.0+1.21.4-neoforge.jar",
+        ] {
+            std::fs::create_dir_all(dir.join(file).parent().unwrap()).unwrap();
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        let jars = find_jars(&dir);
+        let loaders: Vec<_> = jars.iter().map(|j| j.loader.as_deref()).collect();
+        assert_eq!(loaders, [Some("fabric"), Some("neoforge")]);
+        assert_eq!(jars[0].minecraft.as_deref(), Some("1.21.4"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
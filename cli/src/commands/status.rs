@@ -0,0 +1,245 @@
+use crate::config::{McmodConfig, VersionTarget};
+use crate::error::Result;
+use colored::Colorize;
+use std::path::Path;
+
+/// A loader or feature that owns files on disk, and whether mcmod.toml and
+/// the project tree agree about it.
+#[derive(Debug, PartialEq)]
+struct Module {
+    name: &'static str,
+    enabled: bool,
+    /// File whose presence shows the module exists, relative to the project.
+    marker: &'static str,
+    present: bool,
+}
+
+impl Module {
+    fn drifted(&self) -> bool {
+        self.enabled != self.present
+    }
+}
+
+/// Print a summary of the project: type, language, loaders, features, pinned
+/// versions per target, and drift between mcmod.toml and the files on disk.
+pub fn run(dir: &Path) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let modules = modules(dir, &config);
+    let drift = drift(dir, &config, &modules);
+
+    println!("{}", "\n  mcmod status\n".bold().cyan());
+    let info = &config.mod_info;
+    println!("  {:<12} {} ({})", "Project:", info.mod_name, info.mod_id);
+    println!("  {:<12} {}", "Type:", info.project_type.as_str());
+    if config.uses_stonecutter() {
+        println!("  {:<12} {}", "Language:", info.language);
+        let loaders = config.enabled_platforms();
+        let loaders = if loaders.is_empty() { "none".to_string() } else { loaders.join(", ") };
+        println!("  {:<12} {loaders}", "Loaders:");
+    }
+    let features = enabled_features(&config);
+    if !features.is_empty() {
+        println!("  {:<12} {}", "Features:", features.join(", "));
+    }
+    let ci = if config.features.ci { "enabled" } else { "disabled" };
+    println!("  {:<12} {ci}", "CI:");
+
+    println!("\n  {}", "Targets".bold());
+    for target in &config.versions.targets {
+        println!("    {:<16} {}", target_range(target), pinned_versions(target, &config).join(", "));
+    }
+
+    if !modules.is_empty() {
+        println!("\n  {}", "Modules".bold());
+        for module in &modules {
+            let state = match (module.enabled, module.present) {
+                (true, true) => "ok".green(),
+                (true, false) => "missing".red(),
+                (false, true) => "not enabled".yellow(),
+                (false, false) => continue,
+            };
+            println!("    {:<12} {:<12} {}", module.name, state, module.marker.dimmed());
+        }
+    }
+
+    println!();
+    if drift.is_empty() {
+        println!("{}", "  mcmod.toml matches the project".green());
+    } else {
+        println!("  {}", "Drift".bold().yellow());
+        for line in &drift {
+            println!("    {}", line.yellow());
+        }
+        println!("\n  {}", "Run `mcmod doctor` for fixes.".dimmed());
+    }
+    println!();
+    Ok(())
+}
+
+/// Loaders and features that generate their own files, with the file that
+/// shows each one is on disk.
+fn modules(dir: &Path, config: &McmodConfig) -> Vec<Module> {
+    let mut candidates = Vec::new();
+    if config.uses_stonecutter() {
+        candidates.extend([
+            ("fabric", config.loaders.fabric, "src/main/resources/fabric.mod.json"),
+            ("neoforge", config.loaders.neoforge, "src/main/resources/META-INF/neoforge.mods.toml"),
+            ("forge", config.loaders.forge, "src/main/resources/META-INF/mods.toml"),
+            ("quilt", config.loaders.quilt, "src/main/resources/quilt.mod.json"),
+            ("sponge", config.loaders.sponge, "sponge/build.gradle.kts"),
+            ("paper", config.features.paper, "paper/build.gradle.kts"),
+            ("velocity", config.features.velocity, "velocity/build.gradle.kts"),
+            ("spigot", config.features.spigot, "spigot/build.gradle.kts"),
+        ]);
+    }
+    candidates.push(("ci", config.features.ci, ".github/workflows/build.yml"));
+    candidates
+        .into_iter()
+        .map(|(name, enabled, marker)| Module {
+            name,
+            enabled,
+            marker,
+            present: crate::fs::exists(&dir.join(marker)),
+        })
+        .collect()
+}
+
+/// Human-readable differences between mcmod.toml and the project on disk.
+fn drift(dir: &Path, config: &McmodConfig, modules: &[Module]) -> Vec<String> {
+    let mut drift: Vec<String> = modules
+        .iter()
+        .filter(|m| m.drifted())
+        .map(|m| {
+            if m.enabled {
+                format!("{} is enabled in mcmod.toml but {} is missing", m.name, m.marker)
+            } else {
+                format!("{} exists but {} is not enabled in mcmod.toml", m.marker, m.name)
+            }
+        })
+        .collect();
+
+    if !config.uses_stonecutter() {
+        return drift;
+    }
+    let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts")).unwrap_or_default();
+    let entries = crate::commands::doctor::parse_mc_entries(&settings);
+    for loader in config.enabled_platforms() {
+        if entries.iter().any(|(_, loaders)| !loaders.iter().any(|l| l == loader)) {
+            drift.push(format!("{loader} is enabled but missing from mc(...) lines in settings.gradle.kts"));
+        }
+    }
+    for target in &config.versions.targets {
+        if !entries.iter().any(|(mc, _)| *mc == target.minecraft) {
+            drift.push(format!("Minecraft {} has no mc(...) line in settings.gradle.kts", target.minecraft));
+        }
+        let properties = format!("versions/dependencies/{}.properties", target.minecraft);
+        if !crate::fs::exists(&dir.join(&properties)) {
+            drift.push(format!("{properties} is missing"));
+        }
+    }
+    drift
+}
+
+fn enabled_features(config: &McmodConfig) -> Vec<&'static str> {
+    let f = &config.features;
+    [
+        ("publishing", f.publishing),
+        ("testing", f.testing),
+        ("unit-tests", f.unit_tests),
+        ("build-info", f.build_info),
+        ("architectury", f.architectury),
+        ("split-environment", f.split_environment),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect()
+}
+
+fn target_range(target: &VersionTarget) -> String {
+    if target.minecraft == target.max_minecraft {
+        target.minecraft.clone()
+    } else {
+        format!("{}-{}", target.minecraft, target.max_minecraft)
+    }
+}
+
+/// `name version` pairs for the dependencies this project's loaders use.
+fn pinned_versions(target: &VersionTarget, config: &McmodConfig) -> Vec<String> {
+    let loaders = &config.loaders;
+    let uses_fabric_api = loaders.fabric || loaders.quilt;
+    [
+        ("fabric-loader", loaders.fabric, &target.fabric_loader),
+        ("fabric-api", uses_fabric_api, &target.fabric_api),
+        ("neoforge", loaders.neoforge, &target.neoforge),
+        ("forge", loaders.forge, &target.forge),
+        ("quilt-loader", loaders.quilt, &target.quilt_loader),
+        ("architectury-api", config.features.architectury, &target.architectury_api),
+    ]
+    .into_iter()
+    .filter(|(_, used, version)| *used && config.uses_stonecutter() && !version.is_empty())
+    .map(|(name, _, version)| format!("{name} {version}"))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+
+    #[test]
+    fn test_status_reports_drift() {
+        let dir = Path::new("/memory/statusmod");
+        let (result, _) = crate::fs::in_memory(|| {
+            init::run(init::tests::options(dir, &["fabric", "neoforge"]))?;
+            let config = McmodConfig::load(dir)?;
+            let clean = drift(dir, &config, &modules(dir, &config));
+
+            crate::fs::remove_file(&dir.join("src/main/resources/fabric.mod.json"))?;
+            crate::util::write_file(&dir.join("paper/build.gradle.kts"), "")?;
+            let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts"))?;
+            crate::util::write_file(
+                &dir.join("settings.gradle.kts"),
+                &settings.replace(", \"neoforge\")", ")"),
+            )?;
+            let modules = modules(dir, &config);
+            Ok::<_, crate::error::McmodError>((clean, drift(dir, &config, &modules), modules))
+        });
+        let (clean, drifted, modules) = result.unwrap();
+        assert!(clean.is_empty(), "{clean:?}");
+        assert_eq!(
+            drifted,
+            [
+                "fabric is enabled in mcmod.toml but src/main/resources/fabric.mod.json is missing",
+                "paper/build.gradle.kts exists but paper is not enabled in mcmod.toml",
+                "neoforge is enabled but missing from mc(...) lines in settings.gradle.kts",
+            ]
+        );
+        assert!(modules.iter().any(|m| m.name == "ci" && m.enabled && m.present));
+    }
+
+    #[test]
+    fn test_pinned_versions_follow_loaders() {
+        let mut config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            false,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let target = config.versions.targets[0].clone();
+        let pins = pinned_versions(&target, &config);
+        assert_eq!(pins, [format!("neoforge {}", target.neoforge)]);
+
+        config.loaders.quilt = true;
+        let pins = pinned_versions(&target, &config);
+        assert!(pins.iter().any(|p| p.starts_with("fabric-api ")));
+        assert!(!pins.iter().any(|p| p.starts_with("quilt-loader")), "unpinned versions are omitted");
+    }
+}
@@ -0,0 +1,360 @@
+use crate::config::{FabricMetadata, McmodConfig, NeoForgeMetadata};
+use crate::error::{Context, McmodError, Result};
+use colored::Colorize;
+use std::path::Path;
+
+pub const NEOFORGE_MODS_TOML: &str = "src/main/resources/META-INF/neoforge.mods.toml";
+pub const FABRIC_MOD_JSON: &str = "src/main/resources/fabric.mod.json";
+pub const QUILT_MOD_JSON: &str = "src/main/resources/quilt.mod.json";
+
+/// `mcmod sync`: rewrite the loader metadata fields mcmod.toml manages, so
+/// they follow mcmod.toml instead of being edited by hand.
+pub fn run(dir: &Path) -> Result<()> {
+    println!("{}", "\n  mcmod sync\n".bold().cyan());
+    let config = McmodConfig::load(dir)?;
+    let mut updated = 0;
+
+    if config.loaders.neoforge {
+        let path = dir.join(NEOFORGE_MODS_TOML);
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {NEOFORGE_MODS_TOML}"))?;
+        let synced = apply_neoforge(&content, &config.neoforge, &config.mod_info.mod_id);
+        if crate::diff::apply_edit(&path, &content, &synced)? {
+            println!("{}", format!("  Updated {NEOFORGE_MODS_TOML}").green());
+            updated += 1;
+        }
+    } else if !config.neoforge.is_empty() {
+        crate::progress::warning("[neoforge] is set in mcmod.toml but the neoforge loader isn't enabled");
+    }
+
+    if config.loaders.fabric {
+        let path = dir.join(FABRIC_MOD_JSON);
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {FABRIC_MOD_JSON}"))?;
+        let synced = apply_fabric(&content, &config.fabric).with_context(|| format!("Updating {FABRIC_MOD_JSON}"))?;
+        if crate::diff::apply_edit(&path, &content, &synced)? {
+            println!("{}", format!("  Updated {FABRIC_MOD_JSON}").green());
+            updated += 1;
+        }
+    } else if !config.fabric.is_empty() {
+        crate::progress::warning("[fabric] is set in mcmod.toml but the fabric loader isn't enabled");
+    }
+
+    for (loader, enabled, properties) in config.gradle.loaders(&config.loaders) {
+        if !properties.is_empty() && !enabled {
+            crate::progress::warning(format!("[gradle.{loader}] is set in mcmod.toml but the {loader} loader isn't enabled"));
+        }
+    }
+    for path in write_gradle_overrides(dir, &config)? {
+        println!("{}", format!("  Updated {path}").green());
+        updated += 1;
+    }
+
+    if updated == 0 {
+        println!("{}", "  Loader metadata already matches mcmod.toml.".green());
+    }
+    Ok(())
+}
+
+/// Write `[gradle.<loader>]` into every enabled loader's Stonecutter version
+/// projects (`versions/<mc>-<loader>/gradle.properties`), replacing what an
+/// earlier run wrote. Returns the files that changed, relative to `dir`.
+pub fn write_gradle_overrides(dir: &Path, config: &McmodConfig) -> Result<Vec<String>> {
+    let mut changed = Vec::new();
+    for (loader, enabled, properties) in config.gradle.loaders(&config.loaders) {
+        if !enabled {
+            continue;
+        }
+        let marker = format!("# From [gradle.{loader}] in mcmod.toml");
+        let properties: Vec<(String, String)> = properties
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        for target in &config.versions.targets {
+            let rel = format!("versions/{}-{loader}/gradle.properties", target.minecraft);
+            let path = dir.join(&rel);
+            let exists = crate::fs::exists(&path);
+            if !exists && properties.is_empty() {
+                continue;
+            }
+            let content = if exists { crate::fs::read_to_string(&path)? } else { String::new() };
+            let synced = crate::gradle::set_managed_properties(&content, &marker, &properties);
+            if synced == content {
+                continue;
+            }
+            if synced.is_empty() {
+                crate::fs::remove_file(&path)?;
+            } else if exists {
+                crate::diff::apply_edit(&path, &content, &synced)?;
+            } else {
+                crate::util::write_file(&path, &synced)?;
+            }
+            changed.push(rel);
+        }
+    }
+    Ok(changed)
+}
+
+/// neoforge.mods.toml with the `[neoforge]` fields applied. Unset fields are
+/// removed (`logoFile` goes back to the mod's icon), so the result depends
+/// only on mcmod.toml; everything else in the file is left alone.
+pub fn apply_neoforge(content: &str, meta: &NeoForgeMetadata, mod_id: &str) -> String {
+    let quoted = |value: &str| toml::Value::String(value.to_string()).to_string();
+    let logo = meta.logo_file.clone().unwrap_or_else(|| format!("assets/{mod_id}/icon.png"));
+
+    let content = set_key(content, None, "clientSideOnly", meta.client_side_only.then(|| "true".to_string()));
+    let content = set_key(&content, Some("[[mods]]"), "logoFile", Some(quoted(&logo)));
+    let content = set_key(&content, Some("[[mods]]"), "credits", meta.credits.as_deref().map(quoted));
+    let content = set_key(
+        &content,
+        Some("[[mods]]"),
+        "displayTest",
+        meta.display_test.map(|d| quoted(d.as_str())),
+    );
+
+    let header = "[features.${id}]";
+    let mut lines = remove_table(&content, header);
+    if !meta.features.is_empty() {
+        lines.push(String::new());
+        lines.push(header.to_string());
+        lines.extend(meta.features.iter().map(|(key, value)| format!("{key} = {}", quoted(value))));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// fabric.mod.json with the `[fabric]` fields applied: `accessWidener` is
+/// set (or removed when unset), every entrypoint is written as an
+/// `{ "adapter", "value" }` object when its name has an adapter and as a plain
+/// class name otherwise, and the `[fabric.custom]` keys replace the same keys
+/// of `custom`. Other `custom` keys are kept, since the template adds its own.
+/// An unchanged file is returned as is, so its formatting survives.
+pub fn apply_fabric(content: &str, meta: &FabricMetadata) -> Result<String> {
+    let original: serde_json::Value = serde_json::from_str(content)?;
+    let mut json = original.clone();
+    let root = json
+        .as_object_mut()
+        .ok_or_else(|| McmodError::Other("fabric.mod.json is not a JSON object".to_string()))?;
+
+    match &meta.access_widener {
+        Some(path) => {
+            root.insert("accessWidener".to_string(), path.as_str().into());
+        }
+        None => {
+            root.shift_remove("accessWidener");
+        }
+    }
+
+    if let Some(entrypoints) = root.get_mut("entrypoints").and_then(|e| e.as_object_mut()) {
+        for (name, entries) in entrypoints.iter_mut() {
+            let Some(entries) = entries.as_array_mut() else { continue };
+            for entry in entries {
+                let value = match &*entry {
+                    serde_json::Value::Object(object) => match object.get("value") {
+                        Some(value) => value.clone(),
+                        None => continue,
+                    },
+                    other => other.clone(),
+                };
+                *entry = match meta.adapters.get(name) {
+                    Some(adapter) => serde_json::json!({ "adapter": adapter, "value": value }),
+                    None => value,
+                };
+            }
+        }
+    }
+
+    if !meta.custom.is_empty() {
+        let custom = root
+            .entry("custom")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        let custom = custom
+            .as_object_mut()
+            .ok_or_else(|| McmodError::Other("fabric.mod.json's custom is not an object".to_string()))?;
+        for (key, value) in &meta.custom {
+            custom.insert(key.clone(), serde_json::to_value(value)?);
+        }
+    }
+
+    if json == original {
+        return Ok(content.to_string());
+    }
+    Ok(serde_json::to_string_pretty(&json)? + "\n")
+}
+
+/// Whether `line` assigns `key`.
+fn is_key(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(key)
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+fn is_header(line: &str) -> bool {
+    line.trim_start().starts_with('[')
+}
+
+/// Set `key` in the first table headed `header` (`None` for the top level,
+/// before any table) to the TOML `value`, or remove it when `value` is None.
+/// A new key goes after the table's last assignment. Content without the
+/// table is returned as is.
+fn set_key(content: &str, header: Option<&str>, key: &str, value: Option<String>) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let start = match header {
+        None => 0,
+        Some(header) => match lines.iter().position(|l| l.trim() == header) {
+            Some(i) => i + 1,
+            None => return content.to_string(),
+        },
+    };
+    let end = lines[start..].iter().position(|l| is_header(l)).map_or(lines.len(), |i| start + i);
+
+    match (lines[start..end].iter().position(|l| is_key(l, key)), value) {
+        (Some(i), Some(value)) => lines[start + i] = format!("{key} = {value}"),
+        (Some(i), None) => {
+            lines.remove(start + i);
+        }
+        (None, Some(value)) => {
+            let last = lines[start..end].iter().rposition(|l| l.contains('=') && !l.trim_start().starts_with('#'));
+            let at = last.map_or(start, |i| start + i + 1);
+            lines.insert(at, format!("{key} = {value}"));
+        }
+        (None, None) => {}
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The lines of `content` without the table headed `header` (and the blank
+/// line before it).
+fn remove_table(content: &str, header: &str) -> Vec<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    if let Some(start) = lines.iter().position(|l| l.trim() == header) {
+        let end = lines[start + 1..].iter().position(|l| is_header(l)).map_or(lines.len(), |i| start + 1 + i);
+        let mut start = start;
+        while start > 0 && lines[start - 1].trim().is_empty() {
+            start -= 1;
+        }
+        // Keep the blank line that separated the table from the next one
+        let end = if end < lines.len() { end - 1 } else { end };
+        lines.drain(start..end.max(start));
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DisplayTest;
+    use crate::template;
+
+    fn generated_config() -> McmodConfig {
+        McmodConfig::new(
+            "mymod".to_string(),
+            "My Mod".to_string(),
+            "com.example.mymod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        )
+    }
+
+    fn generated() -> String {
+        let config = generated_config();
+        let vars = template::build_common_vars(&config);
+        template::render_optional(template::SC_NEOFORGE_MODS_TOML, &vars).unwrap()
+    }
+
+    #[test]
+    fn test_apply_neoforge() {
+        let original = generated();
+        assert_eq!(apply_neoforge(&original, &NeoForgeMetadata::default(), "mymod"), original);
+
+        let mut meta = NeoForgeMetadata {
+            display_test: Some(DisplayTest::IgnoreAllVersion),
+            client_side_only: true,
+            logo_file: Some("logo.png".to_string()),
+            credits: Some("Thanks to \"everyone\"".to_string()),
+            ..Default::default()
+        };
+        meta.features.insert("openGLVersion".to_string(), "[3.2,)".to_string());
+        let synced = apply_neoforge(&original, &meta, "mymod");
+        assert!(synced.starts_with("modLoader = \"javafml\"\nloaderVersion = \"[4,)\"\nlicense = \"MIT\"\nclientSideOnly = true\n"));
+        assert!(synced.contains("logoFile = \"logo.png\"\ncredits = 'Thanks to \"everyone\"'\ndisplayTest = \"IGNORE_ALL_VERSION\"\n"));
+        assert!(synced.ends_with("\n\n[features.${id}]\nopenGLVersion = \"[3.2,)\"\n"));
+        toml::from_str::<toml::Table>(&synced.replace("${id}", "mymod")).unwrap();
+
+        // Syncing again changes nothing, and clearing the section restores the file
+        assert_eq!(apply_neoforge(&synced, &meta, "mymod"), synced);
+        assert_eq!(apply_neoforge(&synced, &NeoForgeMetadata::default(), "mymod"), original);
+    }
+
+    #[test]
+    fn test_apply_fabric() {
+        let original = "{\n  \"id\": \"${id}\",\n  \"entrypoints\": {\n    \"client\": [\"${group}.Client\"],\n    \"main\": [\"${group}.Mod\"]\n  },\n  \"custom\": { \"modmenu\": { \"badges\": [\"library\"] } }\n}\n";
+        assert_eq!(apply_fabric(original, &FabricMetadata::default()).unwrap(), original);
+
+        let meta: FabricMetadata = toml::from_str(
+            "access_widener = \"mymod.accesswidener\"\n[adapters]\nmain = \"kotlin\"\n\
+             [custom.cardinal-components]\nlist = [\"mymod:mana\"]\n",
+        )
+        .unwrap();
+        let synced = apply_fabric(original, &meta).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&synced).unwrap();
+        assert_eq!(json["accessWidener"], "mymod.accesswidener");
+        assert_eq!(json["entrypoints"]["main"][0], serde_json::json!({ "adapter": "kotlin", "value": "${group}.Mod" }));
+        assert_eq!(json["entrypoints"]["client"][0], "${group}.Client");
+        assert_eq!(json["custom"]["modmenu"]["badges"][0], "library");
+        assert_eq!(json["custom"]["cardinal-components"]["list"][0], "mymod:mana");
+        // Keys keep their place; new ones go last
+        assert!(synced.find("\"id\"").unwrap() < synced.find("\"accessWidener\"").unwrap());
+
+        assert_eq!(apply_fabric(&synced, &meta).unwrap(), synced);
+        let cleared: serde_json::Value =
+            serde_json::from_str(&apply_fabric(&synced, &FabricMetadata::default()).unwrap()).unwrap();
+        assert!(cleared.get("accessWidener").is_none());
+        assert_eq!(cleared["entrypoints"]["main"][0], "${group}.Mod");
+    }
+
+    #[test]
+    fn test_write_gradle_overrides() {
+        let dir = Path::new("/project");
+        let mut config = generated_config();
+        config.gradle = toml::from_str("[fabric]\n\"fabric.loom.multiProjectOptimisation\" = true\n[neoforge]\nx = \"y\"\n").unwrap();
+        config.loaders.neoforge = false;
+
+        let (changed, fs) = crate::fs::in_memory(|| {
+            let first = write_gradle_overrides(dir, &config).unwrap();
+            assert!(write_gradle_overrides(dir, &config).unwrap().is_empty());
+            first
+        });
+        assert_eq!(changed, vec!["versions/1.21.4-fabric/gradle.properties"]);
+        assert_eq!(
+            fs.read_string(&dir.join(&changed[0])).unwrap(),
+            "# From [gradle.fabric] in mcmod.toml\nfabric.loom.multiProjectOptimisation=true\n"
+        );
+
+        config.gradle.fabric.clear();
+        let (_, fs) = crate::fs::in_memory(|| {
+            crate::util::write_file(&dir.join(&changed[0]), "# From [gradle.fabric] in mcmod.toml\na=b\n").unwrap();
+            write_gradle_overrides(dir, &config).unwrap()
+        });
+        assert!(fs.tree(dir).is_empty());
+    }
+
+    #[test]
+    fn test_remove_table_between_tables() {
+        let content = "a = 1\n\n[features.${id}]\nx = \"1\"\n\n[[dependencies.${id}]]\nmodId = \"x\"\n";
+        assert_eq!(remove_table(content, "[features.${id}]").join("\n"), "a = 1\n\n[[dependencies.${id}]]\nmodId = \"x\"");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::{McmodError, Result};
+use crate::global_config::{self, GlobalConfig};
+use crate::template_pack::{self, TemplatePack, BUILT_IN};
+use colored::Colorize;
+use std::path::Path;
+
+/// `mcmod template list`: the built-in templates and every installed pack,
+/// marking the one `mcmod init` uses by default.
+pub fn run_list() -> Result<()> {
+    let selected = GlobalConfig::load()?.defaults.template;
+    let marker = |name: Option<&str>| if selected.as_deref() == name { "*".green() } else { " ".normal() };
+    println!();
+    println!("  {} {:<20} {}", marker(None), BUILT_IN.bold(), "built-in templates".dimmed());
+    for pack in template_pack::installed()? {
+        let summary = format!("{} template(s) replaced", pack.overrides().count());
+        let details = match (&pack.info.description, &pack.info.version) {
+            (Some(description), Some(version)) => format!("{description} (v{version}; {summary})"),
+            (Some(description), None) => format!("{description} ({summary})"),
+            (None, Some(version)) => format!("v{version}; {summary}"),
+            (None, None) => summary,
+        };
+        println!("  {} {:<20} {}", marker(Some(&pack.info.name)), pack.info.name.bold(), details.dimmed());
+    }
+    if let Some(name) = &selected {
+        if template_pack::find(name).is_err() {
+            println!("\n  {}", format!("The default pack '{name}' isn't installed; init uses the built-in templates").yellow());
+        }
+    }
+    println!("\n  {}", format!("Packs are kept in {}", global_config::templates_dir()?.display()).dimmed());
+    println!();
+    Ok(())
+}
+
+/// `mcmod template install <path>`: copy a pack directory into the global
+/// config dir.
+pub fn run_install(source: &Path, name: Option<&str>, force: bool) -> Result<()> {
+    if !source.is_dir() {
+        return Err(McmodError::Other(format!("{} is not a directory", source.display())));
+    }
+    let pack = template_pack::install_into(&global_config::templates_dir()?, source, name, force)?;
+    println!(
+        "{}",
+        format!("  Installed template pack '{}' ({} template(s) replaced)", pack.info.name, pack.overrides().count()).green()
+    );
+    for path in pack.overrides() {
+        println!("    {path}");
+    }
+    if !pack.unused.is_empty() {
+        println!(
+            "{}",
+            format!("  These files don't match a built-in template and are ignored: {}", pack.unused.join(", ")).yellow()
+        );
+    }
+    println!("{}", format!("  Use it with `mcmod init --template {0}` or `mcmod template use {0}`", pack.info.name).dimmed());
+    Ok(())
+}
+
+/// `mcmod eject-templates <dir>`: write the built-in templates out as a pack
+/// to start customizing from.
+pub fn run_eject(dest: &Path, force: bool) -> Result<()> {
+    let pack = template_pack::eject_into(dest, force)?;
+    println!(
+        "{}",
+        format!("  Wrote {} templates to {}", pack.overrides().count(), dest.display()).green()
+    );
+    println!(
+        "{}",
+        "  Shell completions and the Gradle wrapper can't be replaced by a pack, so they were left out".dimmed()
+    );
+    println!("\n  Edit the files you want to change and delete the rest, then:");
+    println!("    {}", format!("mcmod template install {}", dest.display()).green());
+    println!("    {}", format!("mcmod init --template {}", pack.info.name).green());
+    Ok(())
+}
+
+/// `mcmod template remove <name>`: delete an installed pack, and stop using
+/// it by default.
+pub fn run_remove(name: &str) -> Result<()> {
+    let pack = template_pack::find(name)?;
+    std::fs::remove_dir_all(&pack.dir)?;
+    println!("{}", format!("  Removed template pack '{name}'").green());
+    let mut config = GlobalConfig::load()?;
+    if config.defaults.template.as_deref() == Some(name) {
+        config.defaults.template = None;
+        config.save()?;
+        println!("{}", "  mcmod init uses the built-in templates again".dimmed());
+    }
+    Ok(())
+}
+
+/// `mcmod template use <name>`: make a pack (or `default`, the built-in
+/// templates) what mcmod uses when no `--template` is given.
+pub fn run_use(name: &str) -> Result<()> {
+    let pack: Option<TemplatePack> = (name != BUILT_IN).then(|| template_pack::find(name)).transpose()?;
+    let mut config = GlobalConfig::load()?;
+    config.defaults.template = pack.as_ref().map(|p| p.info.name.clone());
+    config.save()?;
+    match pack {
+        Some(_) => println!("{}", format!("  Using template pack '{name}' by default").green()),
+        None => println!("{}", "  Using the built-in templates by default".green()),
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::commands::build;
+use crate::config::McmodConfig;
+use crate::error::{McmodError, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// The run task each loader's Gradle plugin creates for its GameTest server.
+const GAMETEST_TASKS: &[(&str, &str)] = &[
+    ("fabric", "runGameTest"),
+    ("quilt", "runGameTest"),
+    ("neoforge", "runGameTestServer"),
+    ("forge", "runGameTestServer"),
+];
+
+/// JUnit totals from the XML reports Gradle writes under `build/test-results/`.
+#[derive(Debug, Default, PartialEq)]
+struct TestCounts {
+    tests: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+/// `mcmod test`: run the unit tests and GameTests for every loader (or one),
+/// one Gradle invocation per loader, then summarize pass/fail per loader.
+/// With `class`, only that unit test class runs and GameTests are skipped.
+pub fn run(dir: &Path, loader: Option<&str>, class: Option<&str>) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "mcmod test runs mod and library tests (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    if !config.features.testing && !config.features.unit_tests {
+        return Err(McmodError::Other(
+            "Testing isn't set up; run `mcmod add unit-tests` or `mcmod add testing` first".to_string(),
+        ));
+    }
+    // GameTests need the `mcmod add testing` scaffolding and can't be narrowed to a class
+    let gametests = config.features.testing && class.is_none();
+    let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts")).unwrap_or_default();
+    let loaders = match loader {
+        Some(loader) => vec![loader],
+        None => config.enabled_platforms(),
+    };
+    let mut plan = Vec::new();
+    for loader in loaders {
+        plan.push((loader, test_tasks(&config, &settings, loader, class, gametests)?));
+    }
+    let gradlew = build::gradle_wrapper(dir)?;
+    let (java, version) = build::check_java()?;
+
+    println!("{}", "\n  mcmod test\n".bold().cyan());
+    println!("  {}", format!("Java {version} ({})", java.display()).dimmed());
+    if class.is_some() && config.features.testing {
+        println!("  {}", "GameTests are skipped when running a single class".dimmed());
+    }
+    let mut results = Vec::new();
+    for (loader, tasks) in plan {
+        println!("\n  {}", format!("Testing {loader}").bold());
+        println!("  {}\n", format!("./gradlew {}", tasks.join(" ")).dimmed());
+        let status = build::gradle(dir, &gradlew, &tasks)?;
+        results.push((loader, status.success(), counts(dir, loader)));
+    }
+
+    println!("\n  {}", "Summary".bold());
+    let mut failed = 0;
+    for (loader, success, counts) in &results {
+        let mut line = describe(counts);
+        if !success && counts.failed == 0 {
+            line.push_str("; Gradle failed (see the output above)");
+        }
+        if *success {
+            println!("  {} {loader:<9} {line}", "✓".green());
+        } else {
+            failed += 1;
+            println!("  {} {loader:<9} {}", "✗".red(), line.red());
+        }
+    }
+    println!();
+    if failed > 0 {
+        return Err(McmodError::Other(format!("Tests failed on {failed} loader(s)")));
+    }
+    Ok(())
+}
+
+/// Gradle arguments testing one loader on every target: `test` (narrowed by
+/// `--tests <class>`) and, with `gametests`, the loader's GameTest task.
+fn test_tasks(
+    config: &McmodConfig,
+    settings: &str,
+    loader: &str,
+    class: Option<&str>,
+    gametests: bool,
+) -> Result<Vec<String>> {
+    let mut tasks = Vec::new();
+    for mc in build::loader_targets(config, settings, loader)? {
+        tasks.push(format!(":{mc}-{loader}:test"));
+        if let Some(class) = class {
+            tasks.extend(["--tests".to_string(), class.to_string()]);
+        }
+        if let Some((_, gametest)) = GAMETEST_TASKS.iter().find(|(l, _)| *l == loader).filter(|_| gametests) {
+            tasks.push(format!(":{mc}-{loader}:{gametest}"));
+        }
+    }
+    Ok(tasks)
+}
+
+/// Sum the JUnit reports of every `versions/*-<loader>/` target.
+fn counts(dir: &Path, loader: &str) -> TestCounts {
+    let mut total = TestCounts::default();
+    let Ok(versions) = std::fs::read_dir(dir.join("versions")) else {
+        return total;
+    };
+    for version in versions.flatten() {
+        if !version.file_name().to_string_lossy().ends_with(&format!("-{loader}")) {
+            continue;
+        }
+        let Ok(reports) = std::fs::read_dir(version.path().join("build/test-results/test")) else {
+            continue;
+        };
+        for report in reports.flatten() {
+            let Ok(xml) = std::fs::read_to_string(report.path()) else { continue };
+            if let Some(counts) = parse_suite(&xml) {
+                total.tests += counts.tests;
+                total.failed += counts.failed;
+                total.skipped += counts.skipped;
+            }
+        }
+    }
+    total
+}
+
+/// Counts from a report's `<testsuite tests=".." failures=".." errors=".."
+/// skipped="..">` element.
+fn parse_suite(xml: &str) -> Option<TestCounts> {
+    let start = xml.find("<testsuite ")?;
+    let tag = &xml[start..start + xml[start..].find('>')?];
+    let attr = |name: &str| -> usize {
+        tag.split_once(&format!(" {name}=\""))
+            .and_then(|(_, rest)| rest.split('"').next())
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0)
+    };
+    Some(TestCounts {
+        tests: attr("tests"),
+        failed: attr("failures") + attr("errors"),
+        skipped: attr("skipped"),
+    })
+}
+
+/// `3 passed, 1 failed, 2 skipped`, leaving out the zero counts.
+fn describe(counts: &TestCounts) -> String {
+    if counts.tests == 0 {
+        return "no unit test reports".to_string();
+    }
+    let passed = counts.tests - counts.failed - counts.skipped;
+    let mut parts = vec![format!("{passed} passed")];
+    if counts.failed > 0 {
+        parts.push(format!("{} failed", counts.failed));
+    }
+    if counts.skipped > 0 {
+        parts.push(format!("{} skipped", counts.skipped));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_tasks() {
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let settings = "mc(\"1.21.1\", \"fabric\", \"neoforge\")\nmc(\"1.21.4\", \"fabric\")\n";
+        assert_eq!(
+            test_tasks(&config, settings, "fabric", None, true).unwrap(),
+            [":1.21.1-fabric:test", ":1.21.1-fabric:runGameTest", ":1.21.4-fabric:test", ":1.21.4-fabric:runGameTest"]
+        );
+        assert_eq!(
+            test_tasks(&config, settings, "neoforge", Some("com.example.testmod.TestmodModTest"), false).unwrap(),
+            [":1.21.1-neoforge:test", "--tests", "com.example.testmod.TestmodModTest"]
+        );
+        assert_eq!(test_tasks(&config, settings, "neoforge", None, false).unwrap(), [":1.21.1-neoforge:test"]);
+        assert!(test_tasks(&config, settings, "forge", None, true).is_err());
+    }
+
+    #[test]
+    fn test_parse_suite() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.example.testmod.TestmodModTest" tests="5" skipped="1" failures="1" errors="1" timestamp="2026-01-01T00:00:00">
+  <testcase name="loads()" classname="com.example.testmod.TestmodModTest" time="0.01"/>
+</testsuite>"#;
+        let counts = parse_suite(xml).unwrap();
+        assert_eq!(counts, TestCounts { tests: 5, failed: 2, skipped: 1 });
+        assert_eq!(describe(&counts), "2 passed, 2 failed, 1 skipped");
+        assert_eq!(describe(&TestCounts { tests: 3, ..Default::default() }), "3 passed");
+        assert!(parse_suite("<html/>").is_none());
+    }
+}
@@ -1,8 +1,14 @@
 use crate::error::{McmodError, Result};
+use crate::file_policy::FilePolicy;
 use crate::install;
-use crate::util::{http_get, http_get_bytes};
+use crate::util::{http_download, http_get, sha256_file};
 use colored::Colorize;
-use std::path::Path;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Download attempts before giving up; each retry resumes the partial file.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
 
 const GITHUB_RELEASES_URL: &str =
     "https://api.github.com/repos/jhughes-dev/Minecraft-Mod-Starter/releases/latest";
@@ -31,19 +37,54 @@ pub fn run() -> Result<()> {
         format!("  New version available: v{latest_version}").yellow()
     );
 
-    let asset_name = get_asset_name()?;
-    let download_url = extract_asset_url(&release, &latest_version, &asset_name)?;
+    let asset_name = select_asset_name(
+        &asset_names(&release)?,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        cfg!(target_env = "musl"),
+        &latest_version,
+    )?;
+    let asset = extract_asset(&release, &latest_version, &asset_name)?;
+
+    let target = install::install_path()?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let partial = download_path(&target);
 
     println!("{}", format!("  Downloading {asset_name}...").cyan());
-    let binary = http_get_bytes(&download_url)?;
+    download_asset(&asset, &partial)?;
+    verify_download(&asset, &partial)?;
 
-    let target = install::install_path()?;
-    install_binary(&target, &binary)?;
+    let binary = std::fs::read(&partial)?;
+    let outcome = install_binary(&target, &binary)?;
+    let _ = std::fs::remove_file(&partial);
 
-    println!(
-        "{}",
-        format!("  Updated mcmod: v{current_version} → v{latest_version}").green()
-    );
+    match outcome {
+        InstallOutcome::Installed => println!(
+            "{}",
+            format!("  Updated mcmod: v{current_version} → v{latest_version}").green()
+        ),
+        InstallOutcome::Staged(staged) => {
+            println!(
+                "{}",
+                format!("  Could not replace {} (the file is locked).", target.display()).yellow()
+            );
+            println!(
+                "{}",
+                "  This usually means antivirus is scanning it or another shell is running mcmod.".yellow()
+            );
+            println!(
+                "{}",
+                format!("  Staged v{latest_version} at {}", staged.display()).cyan()
+            );
+            println!(
+                "{}",
+                "  The update will finish automatically the next time you run mcmod.".cyan()
+            );
+            return Ok(());
+        }
+    }
 
     // If running from a different location, let the user know where the binary was installed
     if let Ok(current_exe) = std::env::current_exe() {
@@ -72,14 +113,7 @@ pub fn run() -> Result<()> {
     // Warn if the install directory isn't on PATH
     if let Ok(dir) = install::install_dir() {
         if !install::is_on_path(&dir) {
-            println!(
-                "{}",
-                format!(
-                    "  Warning: {} is not on your PATH",
-                    dir.display()
-                )
-                .yellow()
-            );
+            crate::progress::warning(format!("{} is not on your PATH", dir.display()));
         }
     }
 
@@ -103,11 +137,21 @@ fn extract_version(release: &serde_json::Value) -> Result<String> {
     Ok(version.to_string())
 }
 
-fn extract_asset_url(
+/// A release asset selected for download.
+#[derive(Debug, PartialEq)]
+struct ReleaseAsset {
+    url: String,
+    /// Size in bytes as reported by the release API.
+    size: Option<u64>,
+    /// Lowercase hex SHA-256, from the asset's `digest` field when GitHub provides one.
+    sha256: Option<String>,
+}
+
+fn extract_asset(
     release: &serde_json::Value,
     version: &str,
     asset_name: &str,
-) -> Result<String> {
+) -> Result<ReleaseAsset> {
     let assets = release
         .get("assets")
         .and_then(|v| v.as_array())
@@ -122,7 +166,16 @@ fn extract_asset_url(
                 .ok_or_else(|| {
                     McmodError::Other("No download URL for asset".to_string())
                 })?;
-            return Ok(url.to_string());
+            let sha256 = asset
+                .get("digest")
+                .and_then(|v| v.as_str())
+                .and_then(|d| d.strip_prefix("sha256:"))
+                .map(|d| d.to_ascii_lowercase());
+            return Ok(ReleaseAsset {
+                url: url.to_string(),
+                size: asset.get("size").and_then(|v| v.as_u64()),
+                sha256,
+            });
         }
     }
 
@@ -131,57 +184,270 @@ fn extract_asset_url(
     )))
 }
 
-fn get_asset_name() -> Result<String> {
-    let os = std::env::consts::OS;
-    let arch = std::env::consts::ARCH;
+/// Where the in-progress download is kept. It lives next to the install target
+/// so an interrupted update can resume on the next `mcmod update`.
+fn download_path(target: &Path) -> PathBuf {
+    let mut name = target
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "mcmod".into());
+    name.push(".download");
+    target.with_file_name(name)
+}
+
+/// Stream the asset to `partial`, retrying interrupted transfers from where
+/// they stopped.
+fn download_asset(asset: &ReleaseAsset, partial: &Path) -> Result<()> {
+    // A partial file larger than the asset can't be resumed
+    if let (Some(size), Ok(meta)) = (asset.size, std::fs::metadata(partial)) {
+        if meta.len() > size {
+            let _ = std::fs::remove_file(partial);
+        }
+    }
+
+    let mut attempt = 1;
+    loop {
+        let mut progress = Progress::new(asset.size);
+        let result = http_download(&asset.url, partial, &mut |done, total| {
+            progress.update(done, total)
+        });
+        progress.finish();
+
+        match result {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                println!(
+                    "{}",
+                    format!("  Download interrupted ({e}); resuming...").yellow()
+                );
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(McmodError::Other(format!(
+                    "Download failed after {MAX_DOWNLOAD_ATTEMPTS} attempts: {e}\n  \
+                     Run `mcmod update` again to resume from {}",
+                    partial.display()
+                )));
+            }
+        }
+    }
+}
 
-    let name = match (os, arch) {
-        ("linux", "x86_64") => "mcmod-linux-x86_64",
-        ("macos", "x86_64") => "mcmod-macos-x86_64",
-        ("macos", "aarch64") => "mcmod-macos-aarch64",
-        ("windows", "x86_64") => "mcmod-windows-x86_64.exe",
-        _ => {
+/// Check the downloaded file against the size and checksum published with the
+/// release; either is skipped when the release API doesn't report it. A
+/// mismatching file is deleted so the next attempt starts clean.
+fn verify_download(asset: &ReleaseAsset, partial: &Path) -> Result<()> {
+    let len = std::fs::metadata(partial)?.len();
+    if let Some(size) = asset.size {
+        if len != size {
+            let _ = std::fs::remove_file(partial);
             return Err(McmodError::Other(format!(
-                "Unsupported platform: {os}/{arch}"
+                "Downloaded {len} bytes but the release asset is {size} bytes"
             )));
         }
+    }
+
+    if let Some(expected) = &asset.sha256 {
+        let actual = sha256_file(partial)?;
+        if &actual != expected {
+            let _ = std::fs::remove_file(partial);
+            return Err(McmodError::Other(format!(
+                "Checksum mismatch for downloaded binary (expected {expected}, got {actual})"
+            )));
+        }
+        println!("{}", "  Checksum verified".green());
+    }
+
+    Ok(())
+}
+
+/// Minimal single-line progress bar written to stderr. Only drawn when stderr
+/// is a terminal so logs and CI output stay clean.
+pub(crate) struct Progress {
+    enabled: bool,
+    expected: Option<u64>,
+    last_draw: Option<Instant>,
+}
+
+impl Progress {
+    const WIDTH: usize = 30;
+
+    pub(crate) fn new(expected: Option<u64>) -> Self {
+        Progress {
+            enabled: std::io::stderr().is_terminal(),
+            expected,
+            last_draw: None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, done: u64, total: Option<u64>) {
+        if !self.enabled {
+            return;
+        }
+        let total = total.or(self.expected);
+        let due = self
+            .last_draw
+            .is_none_or(|t| t.elapsed() >= Duration::from_millis(100));
+        if !due && total != Some(done) {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+
+        let line = match total {
+            Some(total) if total > 0 => {
+                let filled = (done.min(total) * Self::WIDTH as u64 / total) as usize;
+                format!(
+                    "  [{}{}] {:>3}% {}/{}",
+                    "#".repeat(filled),
+                    "-".repeat(Self::WIDTH - filled),
+                    done.min(total) * 100 / total,
+                    format_bytes(done),
+                    format_bytes(total)
+                )
+            }
+            _ => format!("  {} downloaded", format_bytes(done)),
+        };
+        let mut err = std::io::stderr();
+        let _ = write!(err, "\r{line}");
+        let _ = err.flush();
+    }
+
+    pub(crate) fn finish(&mut self) {
+        if self.enabled && self.last_draw.is_some() {
+            eprintln!();
+        }
+    }
+}
+
+fn format_bytes(n: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    if n >= 1024 * 1024 {
+        format!("{:.1} MiB", n as f64 / MIB)
+    } else {
+        format!("{:.0} KiB", n as f64 / 1024.0)
+    }
+}
+
+fn asset_names(release: &serde_json::Value) -> Result<Vec<String>> {
+    let assets = release
+        .get("assets")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| McmodError::Other("No assets in release response".to_string()))?;
+    Ok(assets
+        .iter()
+        .filter_map(|a| a.get("name").and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Platform parsed from a release asset named `mcmod-<os>-<arch>[-musl][.exe]`.
+#[derive(Debug, PartialEq)]
+struct AssetPlatform {
+    os: String,
+    arch: String,
+    musl: bool,
+}
+
+fn parse_asset_name(name: &str) -> Option<AssetPlatform> {
+    let rest = name.strip_prefix("mcmod-")?;
+    let rest = rest.strip_suffix(".exe").unwrap_or(rest);
+    let mut parts = rest.split('-');
+    let os = parts.next()?;
+    let arch = match parts.next()? {
+        "amd64" | "x64" => "x86_64",
+        "arm64" => "aarch64",
+        other => other,
+    };
+    // Rejects sidecar files such as `mcmod-linux-x86_64.sha256`
+    if !arch.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let musl = match parts.next() {
+        None | Some("gnu") => false,
+        Some("musl") => true,
+        Some(_) => return None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(AssetPlatform {
+        os: os.to_string(),
+        arch: arch.to_string(),
+        musl,
+    })
+}
+
+/// Pick the release asset for this platform. A glibc Linux host falls back to a
+/// static musl build when no glibc build is published; the reverse doesn't work.
+fn select_asset_name(
+    names: &[String],
+    os: &str,
+    arch: &str,
+    musl: bool,
+    version: &str,
+) -> Result<String> {
+    let candidates: Vec<(&String, AssetPlatform)> = names
+        .iter()
+        .filter_map(|n| parse_asset_name(n).map(|p| (n, p)))
+        .filter(|(_, p)| p.os == os && p.arch == arch)
+        .collect();
+
+    let exact = candidates.iter().find(|(_, p)| p.musl == musl);
+    let fallback = candidates.iter().find(|(_, p)| !musl && p.musl);
+    if let Some((name, _)) = exact.or(fallback) {
+        return Ok((*name).clone());
+    }
+
+    let platform = if musl {
+        format!("{os}/{arch} (musl)")
+    } else {
+        format!("{os}/{arch}")
+    };
+    let available = if names.is_empty() {
+        "    (none)".to_string()
+    } else {
+        names
+            .iter()
+            .map(|n| format!("    {n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
     };
+    Err(McmodError::Other(format!(
+        "No mcmod build for {platform} in release v{version}.\n  Available assets:\n{available}\n  \
+         Build from source with: cargo install --git https://github.com/jhughes-dev/Minecraft-Mod-Starter mcmod"
+    )))
+}
 
-    Ok(name.to_string())
+/// Result of installing a downloaded binary.
+enum InstallOutcome {
+    /// The binary at the install path was replaced.
+    Installed,
+    /// The install path was locked; the new binary was staged at this path
+    /// and will be swapped in on the next launch.
+    Staged(PathBuf),
 }
 
-fn install_binary(target: &Path, new_binary: &[u8]) -> Result<()> {
+fn install_binary(target: &Path, new_binary: &[u8]) -> Result<InstallOutcome> {
     // Ensure the install directory exists
     if let Some(parent) = target.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
     if cfg!(unix) {
-        install_binary_unix(target, new_binary)
+        install_binary_unix(target, new_binary)?;
+        Ok(InstallOutcome::Installed)
     } else {
         install_binary_windows(target, new_binary)
     }
 }
 
 fn install_binary_unix(target: &Path, new_binary: &[u8]) -> Result<()> {
-    let temp_path = target.with_extension("new");
-
-    std::fs::write(&temp_path, new_binary)?;
-
-    // Set executable permission
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o755))?;
-    }
-
-    // Atomic rename
-    std::fs::rename(&temp_path, target)?;
-
-    Ok(())
+    // Temp file + rename keeps the running binary intact until the swap
+    let policy = FilePolicy::for_path(target).with_executable(true);
+    crate::util::write_binary_with(target, new_binary, &policy)
 }
 
-fn install_binary_windows(target: &Path, new_binary: &[u8]) -> Result<()> {
+fn install_binary_windows(target: &Path, new_binary: &[u8]) -> Result<InstallOutcome> {
     let old_path = target.with_extension("exe.old");
 
     // Clean up any leftover old file from a previous update
@@ -191,12 +457,20 @@ fn install_binary_windows(target: &Path, new_binary: &[u8]) -> Result<()> {
 
     if target.exists() {
         // Rename current exe out of the way
-        std::fs::rename(target, &old_path)?;
+        if let Err(e) = std::fs::rename(target, &old_path) {
+            if is_lock_error(&e) {
+                return stage_binary(target, new_binary);
+            }
+            return Err(e.into());
+        }
 
         // Write new binary
         if let Err(e) = std::fs::write(target, new_binary) {
             // Try to restore the old binary
             let _ = std::fs::rename(&old_path, target);
+            if is_lock_error(&e) {
+                return stage_binary(target, new_binary);
+            }
             return Err(e.into());
         }
 
@@ -207,6 +481,151 @@ fn install_binary_windows(target: &Path, new_binary: &[u8]) -> Result<()> {
         std::fs::write(target, new_binary)?;
     }
 
-    Ok(())
+    Ok(InstallOutcome::Installed)
+}
+
+/// Write the new binary next to the locked one so it can be swapped in later.
+fn stage_binary(target: &Path, new_binary: &[u8]) -> Result<InstallOutcome> {
+    let staged = install::staged_path(target);
+    crate::util::write_binary(&staged, new_binary)?;
+    Ok(InstallOutcome::Staged(staged))
+}
+
+/// Whether an IO error means the file is locked by another process.
+/// On Windows this is ERROR_ACCESS_DENIED (5), ERROR_SHARING_VIOLATION (32)
+/// or ERROR_LOCK_VIOLATION (33).
+fn is_lock_error(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::PermissionDenied
+        || matches!(e.raw_os_error(), Some(5) | Some(32) | Some(33))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release_json() -> serde_json::Value {
+        serde_json::json!({
+            "tag_name": "v1.2.3",
+            "assets": [{
+                "name": "mcmod-linux-x86_64",
+                "browser_download_url": "https://example.com/mcmod-linux-x86_64",
+                "size": 1234,
+                "digest": "sha256:ABCDEF"
+            }, {
+                "name": "mcmod-macos-aarch64",
+                "browser_download_url": "https://example.com/mcmod-macos-aarch64"
+            }]
+        })
+    }
+
+    #[test]
+    fn test_extract_asset_with_digest() {
+        let asset = extract_asset(&release_json(), "1.2.3", "mcmod-linux-x86_64").unwrap();
+        assert_eq!(
+            asset,
+            ReleaseAsset {
+                url: "https://example.com/mcmod-linux-x86_64".to_string(),
+                size: Some(1234),
+                sha256: Some("abcdef".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_asset_without_digest() {
+        let asset = extract_asset(&release_json(), "1.2.3", "mcmod-macos-aarch64").unwrap();
+        assert_eq!(asset.size, None);
+        assert_eq!(asset.sha256, None);
+        assert!(extract_asset(&release_json(), "1.2.3", "mcmod-freebsd").is_err());
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_asset_name() {
+        assert_eq!(
+            parse_asset_name("mcmod-windows-x86_64.exe"),
+            Some(AssetPlatform {
+                os: "windows".to_string(),
+                arch: "x86_64".to_string(),
+                musl: false
+            })
+        );
+        let musl = parse_asset_name("mcmod-linux-aarch64-musl").unwrap();
+        assert_eq!(musl.arch, "aarch64");
+        assert!(musl.musl);
+        assert_eq!(parse_asset_name("mcmod-macos-arm64").unwrap().arch, "aarch64");
+        assert_eq!(parse_asset_name("install.sh"), None);
+        assert_eq!(parse_asset_name("mcmod-linux-x86_64.sha256"), None);
+    }
+
+    #[test]
+    fn test_select_asset_name() {
+        let assets = names(&[
+            "mcmod-linux-x86_64",
+            "mcmod-linux-x86_64-musl",
+            "mcmod-linux-aarch64",
+            "mcmod-macos-aarch64",
+            "install.sh",
+        ]);
+        let pick = |os, arch, musl| select_asset_name(&assets, os, arch, musl, "1.0.0");
+        assert_eq!(pick("linux", "x86_64", false).unwrap(), "mcmod-linux-x86_64");
+        assert_eq!(pick("linux", "x86_64", true).unwrap(), "mcmod-linux-x86_64-musl");
+        assert_eq!(pick("linux", "aarch64", false).unwrap(), "mcmod-linux-aarch64");
+        assert_eq!(pick("macos", "aarch64", false).unwrap(), "mcmod-macos-aarch64");
+        // A musl host can't run the glibc aarch64 build
+        assert!(pick("linux", "aarch64", true).is_err());
+    }
+
+    #[test]
+    fn test_select_asset_name_musl_fallback() {
+        let assets = names(&["mcmod-linux-x86_64-musl"]);
+        assert_eq!(
+            select_asset_name(&assets, "linux", "x86_64", false, "1.0.0").unwrap(),
+            "mcmod-linux-x86_64-musl"
+        );
+    }
+
+    #[test]
+    fn test_select_asset_name_error_lists_assets() {
+        let assets = names(&["mcmod-linux-x86_64", "mcmod-windows-x86_64.exe"]);
+        let err = select_asset_name(&assets, "freebsd", "x86_64", false, "1.0.0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("freebsd/x86_64"));
+        assert!(err.contains("mcmod-linux-x86_64"));
+        assert!(err.contains("mcmod-windows-x86_64.exe"));
+    }
+
+    #[test]
+    fn test_download_path() {
+        let target = Path::new("/opt/bin/mcmod.exe");
+        assert_eq!(download_path(target), Path::new("/opt/bin/mcmod.exe.download"));
+    }
+
+    #[test]
+    fn test_verify_download_rejects_bad_size() {
+        let path = std::env::temp_dir().join(format!("mcmod_verify_{}", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let asset = ReleaseAsset {
+            url: String::new(),
+            size: Some(3),
+            sha256: Some(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string(),
+            ),
+        };
+        assert!(verify_download(&asset, &path).is_ok());
+
+        let bad = ReleaseAsset { size: Some(4), ..asset };
+        assert!(verify_download(&bad, &path).is_err());
+        assert!(!path.exists(), "mismatching download should be removed");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(2048), "2 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 512 * 1024), "3.5 MiB");
+    }
+}
@@ -0,0 +1,774 @@
+use crate::config::{Loaders, McmodConfig, VersionTarget};
+use crate::diff;
+use crate::error::{McmodError, Result};
+use crate::gradle;
+use crate::lockfile::{Hold, Lockfile, BUILT_IN_SOURCE};
+use crate::template;
+use crate::version_meta::{self, compare_versions};
+use crate::versions;
+use colored::Colorize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// A dependency pinned per Minecraft target in mcmod.toml.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dep {
+    Minecraft,
+    FabricLoader,
+    FabricApi,
+    Neoforge,
+    Forge,
+    QuiltLoader,
+    ArchitecturyApi,
+}
+
+impl Dep {
+    const ALL: [Dep; 7] = [
+        Dep::Minecraft,
+        Dep::FabricLoader,
+        Dep::FabricApi,
+        Dep::Neoforge,
+        Dep::Forge,
+        Dep::QuiltLoader,
+        Dep::ArchitecturyApi,
+    ];
+
+    /// Name used in mcmod.lock.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Dep::Minecraft => "minecraft",
+            Dep::FabricLoader => "fabric_loader",
+            Dep::FabricApi => "fabric_api",
+            Dep::Neoforge => "neoforge",
+            Dep::Forge => "forge",
+            Dep::QuiltLoader => "quilt_loader",
+            Dep::ArchitecturyApi => "architectury_api",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Dep> {
+        Dep::ALL.into_iter().find(|d| d.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Dep::Minecraft => "Minecraft",
+            Dep::FabricLoader => "Fabric Loader",
+            Dep::FabricApi => "Fabric API",
+            Dep::Neoforge => "NeoForge",
+            Dep::Forge => "Forge",
+            Dep::QuiltLoader => "Quilt Loader",
+            Dep::ArchitecturyApi => "Architectury API",
+        }
+    }
+
+    /// Whether versions are built for a specific Minecraft release and must
+    /// move with it. The loaders themselves work across releases.
+    pub fn follows_minecraft(&self) -> bool {
+        !matches!(self, Dep::Minecraft | Dep::FabricLoader | Dep::QuiltLoader)
+    }
+}
+
+/// Newest dependency versions available for one Minecraft version.
+#[derive(Debug, Clone)]
+pub struct Latest {
+    pub fabric_loader: String,
+    pub fabric_api: String,
+    pub neoforge: String,
+    /// Empty when Forge has no build for that Minecraft version.
+    pub forge: String,
+    pub quilt_loader: String,
+    /// Empty when mcmod knows no Architectury API release for it.
+    pub architectury_api: String,
+    /// Metadata URLs of the versions that were looked up online; the rest
+    /// came from the built-in table.
+    pub sources: Vec<(Dep, &'static str)>,
+}
+
+impl Latest {
+    fn source(&self, dep: Dep) -> String {
+        let url = self.sources.iter().find(|(d, _)| *d == dep).map(|(_, url)| *url);
+        url.unwrap_or(BUILT_IN_SOURCE).to_string()
+    }
+}
+
+/// One proposed version bump. `target` is the target's current Minecraft
+/// version; `source` is where `to` came from, for mcmod.lock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub target: String,
+    pub dep: Dep,
+    pub from: String,
+    pub to: String,
+    pub source: String,
+}
+
+impl Change {
+    fn describe(&self) -> String {
+        format!(
+            "{:<9} {:<14} {} → {}",
+            self.target,
+            self.dep.label(),
+            self.from,
+            self.to
+        )
+    }
+}
+
+/// `mcmod upgrade`: bump the newest target to the latest Minecraft release (or
+/// `mc_version`) and every target's loaders to their newest versions. With
+/// `interactive`, each bump can be declined; declined bumps are recorded as
+/// holds in mcmod.lock and skipped by later runs. With `locked`, nothing is
+/// looked up: the project moves to the versions mcmod.lock resolved, so every
+/// checkout builds against the same pins.
+pub fn run(
+    dir: &Path,
+    interactive: bool,
+    dry_run: bool,
+    offline: bool,
+    locked: bool,
+    mc_version: Option<&str>,
+) -> Result<()> {
+    println!("{}", "\n  mcmod upgrade\n".bold().cyan());
+
+    let mut config = McmodConfig::load(dir)?;
+    if !config.uses_stonecutter() {
+        return Err(McmodError::Other(format!(
+            "upgrade only supports mod and library projects (this is a {} project)",
+            config.mod_info.project_type.as_str()
+        )));
+    }
+    if interactive && !std::io::stdin().is_terminal() {
+        return Err(McmodError::Other(
+            "--interactive needs a terminal; run `mcmod upgrade` without it to apply all non-held updates"
+                .to_string(),
+        ));
+    }
+    let mut lock = Lockfile::load(dir)?;
+
+    let changes = if locked {
+        if lock.resolved.is_empty() {
+            return Err(McmodError::Other(
+                "mcmod.lock has no resolved versions yet; run `mcmod upgrade` without --locked".to_string(),
+            ));
+        }
+        locked_changes(&config.versions.targets, &lock)
+    } else {
+        if !offline {
+            println!("{}", "  Checking for newer versions...".cyan());
+        }
+        let (latest_mc, mc_source) = match mc_version {
+            Some(requested) => {
+                check_requested(&config.versions.targets, &config.loaders, requested, |mc| resolve(mc, offline))?;
+                (requested.to_string(), "--mc-version".to_string())
+            }
+            None => latest_minecraft(offline),
+        };
+        let mut changes = plan(
+            &config.versions.targets,
+            &config.loaders,
+            &latest_mc,
+            |mc| resolve(mc, offline),
+        );
+        for change in changes.iter_mut().filter(|c| c.dep == Dep::Minecraft) {
+            change.source = mc_source.clone();
+        }
+        changes
+    };
+    if changes.is_empty() {
+        // Lock pins that predate mcmod.lock, so the next `add` reuses them
+        if lock.resolved.is_empty() && !dry_run {
+            lock.record_targets(&config.versions.targets, |_, _| None);
+            lock.save(dir)?;
+        }
+        let message = if locked { "  mcmod.toml already matches mcmod.lock." } else { "  Everything is up to date." };
+        println!("{}", message.green());
+        return Ok(());
+    }
+
+    let accepted = if interactive {
+        select_changes(&changes, &lock)?
+    } else {
+        changes
+            .iter()
+            .map(|c| lock.hold(&c.target, c.dep.key()).is_none())
+            .collect()
+    };
+    let accepted = check_consistency(&changes, accepted);
+
+    for (change, &keep) in changes.iter().zip(&accepted) {
+        if keep {
+            println!("  {} {}", "+".green(), change.describe());
+        } else {
+            println!("  {} {} {}", "=".yellow(), change.describe(), "(held)".yellow());
+        }
+    }
+    println!();
+
+    if dry_run {
+        println!("{}", "  Dry run — nothing was written.".yellow());
+        return Ok(());
+    }
+
+    // Holds follow the user's answers; a plain run leaves them alone
+    if interactive {
+        for (change, &keep) in changes.iter().zip(&accepted) {
+            if keep {
+                lock.release(&change.target, change.dep.key());
+            } else {
+                lock.set_hold(Hold {
+                    target: change.target.clone(),
+                    dependency: change.dep.key().to_string(),
+                    version: change.from.clone(),
+                    skipped: change.to.clone(),
+                });
+            }
+        }
+    }
+
+    let selected: Vec<&Change> = changes
+        .iter()
+        .zip(&accepted)
+        .filter_map(|(c, &keep)| keep.then_some(c))
+        .collect();
+    apply(dir, &mut config, &mut lock, &selected)?;
+    crate::progress::versions_resolved(&config.versions.targets);
+
+    lock.save(dir)?;
+    println!(
+        "{}",
+        format!("  Applied {} of {} updates.", selected.len(), changes.len()).bold().green()
+    );
+    if !lock.holds.is_empty() {
+        println!(
+            "{}",
+            format!("  {} held back in mcmod.lock (run `mcmod upgrade -i` to revisit).", lock.holds.len())
+                .yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Work out the available bumps. Only the newest target moves to `latest_mc`;
+/// older targets exist to cover older releases and keep their version. A
+/// target's loader versions are resolved for the Minecraft version it will
+/// build against after the bump.
+pub fn plan(
+    targets: &[VersionTarget],
+    loaders: &Loaders,
+    latest_mc: &str,
+    resolve: impl Fn(&str) -> Option<Latest>,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (i, target) in targets.iter().enumerate() {
+        let mut mc = target.minecraft.clone();
+        let is_newest = i + 1 == targets.len();
+        // Forge projects can't move to a release Forge hasn't built for yet
+        let available = resolve(latest_mc).is_some_and(|l| !loaders.forge || !l.forge.is_empty());
+        if is_newest && available && compare_versions(latest_mc, &target.minecraft).is_gt() {
+            changes.push(Change {
+                target: target.minecraft.clone(),
+                dep: Dep::Minecraft,
+                from: target.minecraft.clone(),
+                to: latest_mc.to_string(),
+                source: BUILT_IN_SOURCE.to_string(),
+            });
+            mc = latest_mc.to_string();
+        }
+        let Some(latest) = resolve(&mc) else {
+            continue;
+        };
+        let mc_changed = mc != target.minecraft;
+
+        let mut deps = Vec::new();
+        if loaders.fabric {
+            deps.push((Dep::FabricLoader, &target.fabric_loader, latest.fabric_loader.clone()));
+        }
+        // Quilt runs Fabric API too
+        if loaders.fabric || loaders.quilt {
+            deps.push((Dep::FabricApi, &target.fabric_api, latest.fabric_api.clone()));
+        }
+        if loaders.neoforge {
+            deps.push((Dep::Neoforge, &target.neoforge, latest.neoforge.clone()));
+        }
+        if loaders.forge && !latest.forge.is_empty() {
+            deps.push((Dep::Forge, &target.forge, latest.forge.clone()));
+        }
+        if loaders.quilt {
+            deps.push((Dep::QuiltLoader, &target.quilt_loader, latest.quilt_loader.clone()));
+        }
+        // Only targets that pin Architectury API (the feature is on) track it
+        if !target.architectury_api.is_empty() && !latest.architectury_api.is_empty() {
+            deps.push((Dep::ArchitecturyApi, &target.architectury_api, latest.architectury_api.clone()));
+        }
+        for (dep, from, to) in deps {
+            // Versions built for another Minecraft release must move with it;
+            // otherwise only ever move forward
+            let wanted = if mc_changed && dep.follows_minecraft() {
+                *from != to
+            } else {
+                compare_versions(&to, from).is_gt()
+            };
+            if wanted {
+                changes.push(Change {
+                    target: target.minecraft.clone(),
+                    dep,
+                    from: from.clone(),
+                    source: latest.source(dep),
+                    to,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Moves from the pins in mcmod.toml to the versions mcmod.lock resolved for
+/// each target, for `--locked`.
+fn locked_changes(targets: &[VersionTarget], lock: &Lockfile) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for target in targets {
+        for entry in lock.resolved.iter().filter(|r| r.target == target.minecraft) {
+            let Some(dep) = Dep::from_key(&entry.dependency) else {
+                continue;
+            };
+            let from = match dep {
+                // Targets are matched by their Minecraft version
+                Dep::Minecraft => continue,
+                Dep::FabricLoader => &target.fabric_loader,
+                Dep::FabricApi => &target.fabric_api,
+                Dep::Neoforge => &target.neoforge,
+                Dep::Forge => &target.forge,
+                Dep::QuiltLoader => &target.quilt_loader,
+                Dep::ArchitecturyApi => &target.architectury_api,
+            };
+            if *from != entry.version {
+                changes.push(Change {
+                    target: target.minecraft.clone(),
+                    dep,
+                    from: from.clone(),
+                    to: entry.version.clone(),
+                    source: entry.source.clone(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Reject a `--mc-version` the plan would silently skip: one older than the
+/// newest target, or one with no loader versions for this project.
+fn check_requested(
+    targets: &[VersionTarget],
+    loaders: &Loaders,
+    requested: &str,
+    resolve: impl Fn(&str) -> Option<Latest>,
+) -> Result<()> {
+    if let Some(newest) = targets.last() {
+        if compare_versions(requested, &newest.minecraft).is_lt() {
+            return Err(McmodError::Other(format!(
+                "Minecraft {requested} is older than the newest target ({}); upgrade only moves forward",
+                newest.minecraft
+            )));
+        }
+    }
+    let Some(latest) = resolve(requested) else {
+        return Err(McmodError::Other(format!(
+            "No loader versions found for Minecraft {requested}; check the version number, or drop --offline"
+        )));
+    };
+    if loaders.forge && latest.forge.is_empty() {
+        return Err(McmodError::Other(format!(
+            "Forge has no build for Minecraft {requested} yet"
+        )));
+    }
+    Ok(())
+}
+
+/// Drop loader bumps that were resolved for a Minecraft bump that was
+/// declined, and warn when a held dependency stays on the old release.
+fn check_consistency(changes: &[Change], mut accepted: Vec<bool>) -> Vec<bool> {
+    for (i, mc) in changes.iter().enumerate().filter(|(_, c)| c.dep == Dep::Minecraft) {
+        let mc_accepted = accepted[i];
+        for (j, change) in changes.iter().enumerate() {
+            if change.target != mc.target || !change.dep.follows_minecraft() {
+                continue;
+            }
+            if !mc_accepted && accepted[j] {
+                println!(
+                    "{}",
+                    format!(
+                        "  Skipping {} {}: it is built for Minecraft {}, which was held back.",
+                        change.dep.label(),
+                        change.to,
+                        mc.to
+                    )
+                    .yellow()
+                );
+                accepted[j] = false;
+            } else if mc_accepted && !accepted[j] {
+                crate::progress::warning(format!(
+                    "{} {} is built for Minecraft {}, not {}; that loader may fail to build until it is upgraded.",
+                    change.dep.label(),
+                    change.from,
+                    mc.from,
+                    mc.to
+                ));
+            }
+        }
+    }
+    accepted
+}
+
+/// Checklist of all bumps; held ones start unchecked.
+fn select_changes(changes: &[Change], lock: &Lockfile) -> Result<Vec<bool>> {
+    let items: Vec<String> = changes.iter().map(Change::describe).collect();
+    let defaults: Vec<bool> = changes
+        .iter()
+        .map(|c| lock.hold(&c.target, c.dep.key()).is_none())
+        .collect();
+    let selections = dialoguer::MultiSelect::new()
+        .with_prompt("  Select updates to apply (unchecked ones are held back)")
+        .items(&items)
+        .defaults(&defaults)
+        .interact()
+        .map_err(|e| McmodError::Other(e.to_string()))?;
+    Ok((0..changes.len()).map(|i| selections.contains(&i)).collect())
+}
+
+/// Write the accepted bumps to mcmod.toml, the per-version properties files
+/// and settings.gradle.kts, and record the new versions in `lock`.
+pub fn apply(dir: &Path, config: &mut McmodConfig, lock: &mut Lockfile, changes: &[&Change]) -> Result<()> {
+    let mut renamed = Vec::new();
+    for target in config.versions.targets.iter_mut() {
+        let old_mc = target.minecraft.clone();
+        let mut touched = false;
+        for change in changes.iter().filter(|c| c.target == old_mc) {
+            touched = true;
+            match change.dep {
+                Dep::Minecraft => {
+                    target.minecraft = change.to.clone();
+                    if compare_versions(&change.to, &target.max_minecraft).is_gt() {
+                        target.max_minecraft = change.to.clone();
+                    }
+                }
+                Dep::FabricLoader => target.fabric_loader = change.to.clone(),
+                Dep::FabricApi => target.fabric_api = change.to.clone(),
+                Dep::Neoforge => target.neoforge = change.to.clone(),
+                Dep::Forge => target.forge = change.to.clone(),
+                Dep::QuiltLoader => target.quilt_loader = change.to.clone(),
+                Dep::ArchitecturyApi => target.architectury_api = change.to.clone(),
+            }
+        }
+        if !touched {
+            continue;
+        }
+
+        let properties = template::render_version_properties(target)?;
+        let path = dir.join(format!("versions/dependencies/{}.properties", target.minecraft));
+        crate::util::write_file(&path, &properties)?;
+        println!(
+            "{}",
+            format!("  Updated versions/dependencies/{}.properties", target.minecraft).green()
+        );
+        if target.minecraft != old_mc {
+            let old_path = dir.join(format!("versions/dependencies/{old_mc}.properties"));
+            if crate::fs::exists(&old_path) {
+                crate::fs::remove_file(&old_path)?;
+            }
+            renamed.push((old_mc, target.minecraft.clone()));
+        }
+    }
+
+    if !renamed.is_empty() {
+        let settings_path = dir.join("settings.gradle.kts");
+        let settings = crate::fs::read_to_string(&settings_path)?;
+        let updated = renamed.iter().fold(settings.clone(), |content, (old, new)| {
+            gradle::rename_mc_version_content(&content, old, new)
+        });
+        diff::apply_edit(&settings_path, &settings, &updated)?;
+        for (old, new) in &renamed {
+            lock.rename_target(old, new);
+        }
+    }
+
+    lock.record_targets(&config.versions.targets, |target, dependency| {
+        let old = renamed.iter().find(|(_, new)| new == target).map_or(target, |(old, _)| old.as_str());
+        changes
+            .iter()
+            .find(|c| c.target == old && c.dep.key() == dependency)
+            .map(|c| c.source.clone())
+    });
+    config.save(dir)?;
+    Ok(())
+}
+
+/// Latest stable Minecraft release, falling back to the newest one mcmod
+/// knows, and where it came from.
+pub fn latest_minecraft(offline: bool) -> (String, String) {
+    let known = version_meta::supported_versions()
+        .last()
+        .copied()
+        .unwrap_or("1.21.4")
+        .to_string();
+    let fetched = if offline { None } else { versions::fetch_minecraft_version().ok() };
+    match fetched {
+        Some(version) => (version, versions::FABRIC_GAME_META.to_string()),
+        None => (known, BUILT_IN_SOURCE.to_string()),
+    }
+}
+
+/// Newest loader versions for `mc`, online with the built-in table as fallback.
+pub fn resolve(mc: &str, offline: bool) -> Option<Latest> {
+    let known = version_meta::get_version_meta(mc).map(|m| Latest {
+        fabric_loader: m.fabric_loader.to_string(),
+        fabric_api: m.fabric_api.to_string(),
+        neoforge: m.neoforge.to_string(),
+        forge: m.forge.to_string(),
+        quilt_loader: version_meta::QUILT_LOADER.to_string(),
+        architectury_api: m.architectury_api.to_string(),
+        sources: Vec::new(),
+    });
+    if offline {
+        return known;
+    }
+    let mut sources = Vec::new();
+    let mut fetched = |dep: Dep, url: &'static str, result: Result<String>| {
+        let version = result.ok()?;
+        sources.push((dep, url));
+        Some(version)
+    };
+    let fabric_loader = fetched(Dep::FabricLoader, versions::FABRIC_LOADER_META, versions::fetch_fabric_loader_version())
+        .or_else(|| known.as_ref().map(|k| k.fabric_loader.clone()))?;
+    let fabric_api = fetched(Dep::FabricApi, versions::FABRIC_API_MAVEN, versions::fetch_fabric_api_version(mc))
+        .or_else(|| known.as_ref().map(|k| k.fabric_api.clone()))?;
+    let neoforge = fetched(Dep::Neoforge, versions::NEOFORGE_MAVEN, versions::fetch_neoforge_version(mc))
+        .or_else(|| known.as_ref().map(|k| k.neoforge.clone()))?;
+    let forge = fetched(Dep::Forge, versions::FORGE_MAVEN, versions::fetch_forge_version(mc))
+        .or_else(|| known.as_ref().map(|k| k.forge.clone()))
+        .unwrap_or_default();
+    let quilt_loader = fetched(Dep::QuiltLoader, versions::QUILT_LOADER_META, versions::fetch_quilt_loader_version())
+        .unwrap_or_else(|| version_meta::QUILT_LOADER.to_string());
+    // Architectury API comes from the built-in table only
+    let architectury_api = known.map(|k| k.architectury_api).unwrap_or_default();
+    Some(Latest {
+        fabric_loader,
+        fabric_api,
+        neoforge,
+        forge,
+        quilt_loader,
+        architectury_api,
+        sources,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offline(mc: &str) -> Option<Latest> {
+        resolve(mc, true)
+    }
+
+    fn target(mc: &str, fabric_loader: &str, fabric_api: &str, neoforge: &str) -> VersionTarget {
+        VersionTarget {
+            minecraft: mc.to_string(),
+            max_minecraft: mc.to_string(),
+            fabric_loader: fabric_loader.to_string(),
+            fabric_api: fabric_api.to_string(),
+            neoforge: neoforge.to_string(),
+            forge: String::new(),
+            quilt_loader: String::new(),
+            architectury_api: String::new(),
+        }
+    }
+
+    fn loaders(fabric: bool, neoforge: bool, forge: bool) -> Loaders {
+        Loaders {
+            fabric,
+            neoforge,
+            forge,
+            quilt: false,
+            sponge: false,
+        }
+    }
+
+    #[test]
+    fn test_plan_bumps_newest_target_only() {
+        let targets = vec![
+            target("1.21.1", "0.16.0", "0.116.9+1.21.1", "21.1.221"),
+            target("1.21.4", "0.18.5", "0.119.4+1.21.4", "21.4.157"),
+        ];
+        let changes = plan(&targets, &loaders(true, true, false), "1.21.11", offline);
+
+        let old: Vec<_> = changes.iter().filter(|c| c.target == "1.21.1").collect();
+        assert_eq!(old.len(), 1);
+        assert_eq!(old[0].dep, Dep::FabricLoader);
+        assert_eq!(old[0].to, "0.18.5");
+
+        let new: Vec<_> = changes.iter().filter(|c| c.target == "1.21.4").collect();
+        assert_eq!(new[0].dep, Dep::Minecraft);
+        assert_eq!(new[0].to, "1.21.11");
+        assert!(new.iter().any(|c| c.dep == Dep::FabricApi && c.to == "0.141.3+1.21.11"));
+        assert!(new.iter().any(|c| c.dep == Dep::Neoforge && c.to == "21.11.40-beta"));
+        assert!(!new.iter().any(|c| c.dep == Dep::FabricLoader), "loader is already current");
+    }
+
+    #[test]
+    fn test_plan_never_downgrades() {
+        let targets = vec![target("1.21.11", "0.19.0", "0.142.0+1.21.11", "21.11.50")];
+        assert!(plan(&targets, &loaders(true, true, false), "1.21.11", offline).is_empty());
+        assert!(plan(&targets, &loaders(true, true, false), "1.21.4", offline).is_empty());
+    }
+
+    #[test]
+    fn test_check_requested() {
+        let targets = vec![target("1.21.4", "0.16.0", "0.100.0+1.21.4", "21.4.1")];
+        assert!(check_requested(&targets, &loaders(true, true, false), "1.21.11", offline).is_ok());
+        assert!(check_requested(&targets, &loaders(true, true, false), "1.21.4", offline).is_ok());
+        let err = check_requested(&targets, &loaders(true, false, false), "1.21.1", offline).unwrap_err();
+        assert!(err.to_string().contains("older than the newest target (1.21.4)"));
+        assert!(check_requested(&targets, &loaders(true, false, false), "1.99", offline).is_err());
+
+        let changes = plan(&targets, &loaders(true, false, false), "1.21.5", offline);
+        assert_eq!(changes[0].to, "1.21.5");
+    }
+
+    #[test]
+    fn test_plan_skips_disabled_loaders() {
+        let targets = vec![target("1.21.4", "0.16.0", "0.100.0+1.21.4", "21.4.1")];
+        let changes = plan(&targets, &loaders(true, false, false), "1.21.4", offline);
+        assert!(changes.iter().all(|c| c.dep != Dep::Neoforge));
+        assert_eq!(changes.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_forge() {
+        let mut old = target("1.21.1", "0.18.5", "0.116.9+1.21.1", "21.1.221");
+        old.forge = "52.0.1".to_string();
+        let changes = plan(&[old], &loaders(false, false, true), "1.21.1", offline);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].dep, Dep::Forge);
+        assert_eq!(changes[0].to, "52.1.0");
+
+        // Forge never built 1.21.2, so a Forge project stays put
+        let mut newest = target("1.21.1", "0.18.5", "0.116.9+1.21.1", "21.1.221");
+        newest.forge = "52.1.0".to_string();
+        assert!(plan(&[newest], &loaders(false, false, true), "1.21.2", offline).is_empty());
+    }
+
+    #[test]
+    fn test_plan_quilt() {
+        let mut old = target("1.21.4", "0.18.5", "0.110.0+1.21.4", "21.4.157");
+        old.quilt_loader = "0.27.0".to_string();
+        let quilt = Loaders {
+            quilt: true,
+            ..loaders(false, false, false)
+        };
+        let changes = plan(&[old.clone()], &quilt, "1.21.4", offline);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().any(|c| c.dep == Dep::FabricApi && c.to == "0.119.4+1.21.4"));
+        assert!(changes.iter().any(|c| c.dep == Dep::QuiltLoader && c.to == version_meta::QUILT_LOADER));
+
+        // The loader isn't tied to Minecraft, so it isn't held with it
+        let changes = plan(&[old], &quilt, "1.21.11", offline);
+        let accepted: Vec<bool> = changes.iter().map(|c| c.dep != Dep::Minecraft).collect();
+        let accepted = check_consistency(&changes, accepted);
+        let kept: Vec<Dep> = changes.iter().zip(accepted).filter(|(_, a)| *a).map(|(c, _)| c.dep).collect();
+        assert_eq!(kept, vec![Dep::QuiltLoader]);
+    }
+
+    #[test]
+    fn test_plan_architectury_follows_minecraft() {
+        let mut old = target("1.21.4", "0.18.5", "0.119.4+1.21.4", "21.4.157");
+        assert!(!plan(&[old.clone()], &loaders(true, false, false), "1.21.11", offline)
+            .iter()
+            .any(|c| c.dep == Dep::ArchitecturyApi));
+
+        old.architectury_api = "15.0.3".to_string();
+        let changes = plan(&[old], &loaders(true, false, false), "1.21.11", offline);
+        assert!(changes.iter().any(|c| c.dep == Dep::ArchitecturyApi && c.to == "19.0.1"));
+    }
+
+    #[test]
+    fn test_check_consistency_drops_deps_of_held_minecraft() {
+        let targets = vec![target("1.21.4", "0.18.5", "0.119.4+1.21.4", "21.4.157")];
+        let changes = plan(&targets, &loaders(true, true, false), "1.21.11", offline);
+        assert_eq!(changes[0].dep, Dep::Minecraft);
+
+        let mut accepted = vec![true; changes.len()];
+        accepted[0] = false;
+        assert!(check_consistency(&changes, accepted).iter().all(|&a| !a));
+
+        // Accepting Minecraft while holding NeoForge is allowed (with a warning)
+        let accepted: Vec<bool> = changes.iter().map(|c| c.dep != Dep::Neoforge).collect();
+        assert_eq!(check_consistency(&changes, accepted.clone()), accepted);
+    }
+
+    #[test]
+    fn test_apply_renames_target() {
+        let dir = std::env::temp_dir().join(format!("mcmod_upgrade_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut config = McmodConfig::new(
+            "mymod".to_string(),
+            "My Mod".to_string(),
+            "com.example.mymod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            crate::config::Versions::default(),
+        );
+        config.versions.targets = vec![target("1.21.4", "0.18.5", "0.119.4+1.21.4", "21.4.157")];
+        std::fs::create_dir_all(dir.join("versions/dependencies")).unwrap();
+        std::fs::write(dir.join("versions/dependencies/1.21.4.properties"), "old\n").unwrap();
+        std::fs::write(
+            dir.join("settings.gradle.kts"),
+            "        mc(\"1.21.4\", \"fabric\", \"neoforge\")\n        vcsVersion = \"1.21.4-fabric\"\n",
+        )
+        .unwrap();
+
+        let mut lock = Lockfile::default();
+        lock.set_hold(Hold {
+            target: "1.21.4".to_string(),
+            dependency: "neoforge".to_string(),
+            version: "21.4.157".to_string(),
+            skipped: "21.11.40-beta".to_string(),
+        });
+        let changes: Vec<Change> = plan(&config.versions.targets, &config.loaders, "1.21.11", offline)
+            .into_iter()
+            .filter(|c| c.dep != Dep::Neoforge)
+            .collect();
+        let selected: Vec<&Change> = changes.iter().collect();
+        apply(&dir, &mut config, &mut lock, &selected).unwrap();
+
+        let saved = McmodConfig::load(&dir).unwrap();
+        assert_eq!(saved.versions.targets[0].minecraft, "1.21.11");
+        assert_eq!(saved.versions.targets[0].max_minecraft, "1.21.11");
+        assert_eq!(saved.versions.targets[0].neoforge, "21.4.157");
+        assert!(!dir.join("versions/dependencies/1.21.4.properties").exists());
+        let props = std::fs::read_to_string(dir.join("versions/dependencies/1.21.11.properties")).unwrap();
+        assert!(props.contains("fabric_version=0.141.3+1.21.11"));
+        assert!(props.contains("neoforge_version=21.4.157"));
+        let settings = std::fs::read_to_string(dir.join("settings.gradle.kts")).unwrap();
+        assert!(settings.contains("mc(\"1.21.11\""));
+        assert!(settings.contains("vcsVersion = \"1.21.11-fabric\""));
+        assert!(lock.hold("1.21.11", "neoforge").is_some());
+        assert_eq!(lock.locked("1.21.11", "fabric_api").as_deref(), Some("0.141.3+1.21.11"));
+        assert_eq!(lock.resolved("1.21.11", "fabric_api").unwrap().source, BUILT_IN_SOURCE);
+        assert_eq!(lock.resolved("1.21.11", "neoforge").unwrap().source, crate::lockfile::CONFIG_SOURCE);
+
+        // A checkout that still pins the old NeoForge moves to the locked one
+        let mut behind = saved.versions.targets.clone();
+        behind[0].neoforge = "21.4.100".to_string();
+        let changes = locked_changes(&behind, &lock);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].dep, changes[0].to.as_str()), (Dep::Neoforge, "21.4.157"));
+        assert!(locked_changes(&saved.versions.targets, &lock).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::commands::build;
+use crate::config::McmodConfig;
+use crate::content;
+use crate::error::{McmodError, Result};
+use colored::Colorize;
+use std::path::Path;
+
+/// Known Gradle failure output, and what to do about it in an mcmod project.
+/// Matched case-sensitively against each output line.
+const HINTS: &[(&str, &str)] = &[
+    (
+        "Plugin [id:",
+        "A Gradle plugin couldn't be found: `mcmod doctor --fix` restores the plugin repositories in settings.gradle.kts",
+    ),
+    (
+        "Could not resolve",
+        "A dependency couldn't be downloaded: check its version in versions/dependencies/<mc>.properties (`mcmod outdated` lists known-good ones)",
+    ),
+    (
+        "No cached version of",
+        "Gradle is offline and hasn't cached this dependency yet; run `mcmod verify` once with network access",
+    ),
+    (
+        "Could not get unknown property 'dep.",
+        "build.gradle.kts reads a dependency version that versions/dependencies/<mc>.properties doesn't set; add it with `mcmod add dep`",
+    ),
+    (
+        "Unsupported class file major version",
+        "Gradle is running on the wrong Java; Minecraft 1.20.5+ needs Java 21 (set JAVA_HOME or org.gradle.java.home)",
+    ),
+    (
+        "requires at least JVM runtime version",
+        "Gradle is running on the wrong Java; Minecraft 1.20.5+ needs Java 21 (set JAVA_HOME or org.gradle.java.home)",
+    ),
+    (
+        "cannot find symbol",
+        "The sources don't compile for this version: check that loader and version code sits inside Stonecutter `//? if` blocks",
+    ),
+    (
+        "e: Unresolved reference",
+        "The sources don't compile for this version: check that loader and version code sits inside Stonecutter `//? if` blocks",
+    ),
+    (
+        "fabric.mod.json",
+        "fabric.mod.json is out of step with mcmod.toml; `mcmod sync` rewrites it",
+    ),
+    (
+        "neoforge.mods.toml",
+        "neoforge.mods.toml is out of step with mcmod.toml; `mcmod sync` rewrites it",
+    ),
+];
+
+/// `mcmod verify`: confirm the project builds. Checks the mod's resource
+/// JSON, runs `./gradlew help` to configure every node, then (unless `quick`)
+/// the full build. Gradle is run with `--offline` in offline mode. A failing
+/// step is explained with hints for the errors it printed.
+pub fn run(dir: &Path, quick: bool) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    println!("{}", "\n  mcmod verify\n".bold().cyan());
+
+    if config.uses_stonecutter() {
+        let problems = content::validate(dir, &config);
+        if !problems.is_empty() {
+            for problem in &problems {
+                println!("  {} {}", format!("{}:", problem.path).yellow(), problem.message);
+            }
+            return Err(McmodError::Other(format!(
+                "{} resource problem(s); fix them before building",
+                problems.len()
+            )));
+        }
+        println!("  {} Resources", "✓".green());
+    }
+
+    let gradlew = build::gradle_wrapper(dir)?;
+    let (java, version) = build::check_java()?;
+    println!("  {} Java {version} ({})", "✓".green(), java.display());
+
+    let settings = crate::fs::read_to_string(&dir.join("settings.gradle.kts")).unwrap_or_default();
+    let mut steps = vec![("Gradle configuration", vec!["help".to_string()])];
+    if !quick {
+        steps.push(("Build", build::gradle_tasks(&config, &settings, None)?));
+    }
+    for (name, tasks) in steps {
+        println!("\n  {}\n", format!("./gradlew {}", tasks.join(" ")).dimmed());
+        let (status, output) = build::gradle_captured(dir, &gradlew, &tasks)?;
+        if status.success() {
+            println!("\n  {} {name}", "✓".green());
+            continue;
+        }
+        println!("\n  {} {name}", "✗".red());
+        for hint in hints(&output) {
+            println!("    {} {hint}", "hint:".yellow());
+        }
+        return Err(McmodError::Other(format!("{name} failed; `mcmod doctor` checks the project files")));
+    }
+
+    println!("\n  {}", "The project builds.".bold().green());
+    Ok(())
+}
+
+/// The hints for failures in `output`, each once, in the order of [`HINTS`].
+fn hints(output: &[String]) -> Vec<&'static str> {
+    let mut hints: Vec<&'static str> = Vec::new();
+    for (pattern, hint) in HINTS {
+        if output.iter().any(|line| line.contains(pattern)) && !hints.contains(hint) {
+            hints.push(hint);
+        }
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints() {
+        let output: Vec<String> = [
+            "> Task :1.21.1-fabric:compileJava FAILED",
+            "TestmodMod.java:12: error: cannot find symbol",
+            "e: Unresolved reference 'ModInitializer'.",
+            "Execution failed for task ':1.21.1-fabric:compileJava'.",
+        ]
+        .map(str::to_string)
+        .to_vec();
+        let hints = hints(&output);
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].contains("Stonecutter"));
+
+        let output = vec![
+            "> Could not resolve all files for configuration ':1.21.1-fabric:modCompileClasspath'.".to_string(),
+            "   > Could not get unknown property 'dep.jei.fabric' for project".to_string(),
+        ];
+        let hints = super::hints(&output);
+        assert_eq!(hints.len(), 2);
+        assert!(hints[1].contains("mcmod add dep"));
+        assert!(super::hints(&["BUILD SUCCESSFUL".to_string()]).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::version_meta;
+use crate::versions;
+use colored::Colorize;
+use serde::Serialize;
+
+/// One Minecraft release with the newest loader builds published for it.
+#[derive(Debug, PartialEq, Serialize)]
+struct Row {
+    minecraft: String,
+    fabric_api: Option<String>,
+    neoforge: Option<String>,
+    /// Whether mcmod has known-good pins for this release (`init --minecraft`).
+    supported: bool,
+}
+
+/// `mcmod versions`: list the newest `limit` Minecraft releases (all when 0)
+/// from Mojang's manifest with the latest Fabric API and NeoForge for each,
+/// plus the current Fabric Loader, as a table or JSON.
+pub fn run(limit: usize, json: bool) -> Result<()> {
+    let releases = versions::fetch_minecraft_releases()?;
+    let fabric_loader = versions::fetch_fabric_loader_version()?;
+    let rows = rows(
+        &releases,
+        &versions::fetch_fabric_api_versions()?,
+        &versions::fetch_neoforge_versions()?,
+        limit,
+    );
+
+    if json {
+        let report = serde_json::json!({ "fabric_loader": fabric_loader, "versions": rows });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{}", "\n  mcmod versions\n".bold().cyan());
+    println!("  {:<15} {fabric_loader}\n", "Fabric Loader:");
+    let width = |f: fn(&Row) -> usize, title: &str| rows.iter().map(f).max().unwrap_or(0).max(title.len());
+    let mc_width = width(|r| r.minecraft.len(), "Minecraft");
+    let api_width = width(|r| r.fabric_api.as_deref().unwrap_or("-").len(), "Fabric API");
+    println!(
+        "  {}",
+        format!("{:<mc_width$}  {:<api_width$}  NeoForge", "Minecraft", "Fabric API").bold()
+    );
+    for row in &rows {
+        let marker = if row.supported { " ✓".green() } else { "".normal() };
+        println!(
+            "  {:<mc_width$}  {:<api_width$}  {}{marker}",
+            row.minecraft,
+            row.fabric_api.as_deref().unwrap_or("-"),
+            row.neoforge.as_deref().unwrap_or("-"),
+        );
+    }
+    println!("\n  {}", "✓ = mcmod has known-good versions for this release".dimmed());
+    Ok(())
+}
+
+/// Pair each release (newest first) with its latest Fabric API and NeoForge.
+fn rows(releases: &[String], fabric_api: &[String], neoforge: &[String], limit: usize) -> Vec<Row> {
+    let limit = if limit == 0 { releases.len() } else { limit };
+    releases
+        .iter()
+        .take(limit)
+        .map(|mc| Row {
+            minecraft: mc.clone(),
+            fabric_api: versions::latest_fabric_api_for(fabric_api, mc),
+            neoforge: versions::latest_neoforge_for(neoforge, mc),
+            supported: version_meta::get_version_meta(mc).is_some(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rows() {
+        let releases = strings(&["1.21.4", "1.21.3", "1.20.1"]);
+        let fabric_api = strings(&["0.92.2+1.20.1", "0.110.0+1.21.4", "0.112.1+1.21.4"]);
+        let neoforge = strings(&["21.3.56", "21.4.10-beta"]);
+
+        let newest = rows(&releases, &fabric_api, &neoforge, 2);
+        assert_eq!(newest.len(), 2);
+        assert_eq!(
+            newest[0],
+            Row {
+                minecraft: "1.21.4".to_string(),
+                fabric_api: Some("0.112.1+1.21.4".to_string()),
+                neoforge: Some("21.4.10-beta".to_string()),
+                supported: true,
+            }
+        );
+        assert_eq!(newest[1].fabric_api, None);
+        assert_eq!(newest[1].neoforge.as_deref(), Some("21.3.56"));
+
+        let all = rows(&releases, &fabric_api, &neoforge, 0);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].neoforge, None);
+        assert!(!all[2].supported);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::config::McmodConfig;
+use crate::content::{self, ContentManifest, MANIFEST_FILE};
+use crate::error::Result;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Modification times of every watched file.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Watch content.toml and the mod's lang/model directories, regenerating
+/// resources when the manifest changes and re-validating on any change.
+/// `once` runs a single pass and exits with an error if problems were found.
+pub fn run(dir: &Path, once: bool, interval: Duration) -> Result<()> {
+    let config = McmodConfig::load(dir)?;
+    let problems = pass(dir, &config, true)?;
+    if once {
+        if problems > 0 {
+            return Err(crate::error::McmodError::Other(format!(
+                "{problems} content problem(s) in {}",
+                dir.display()
+            )));
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("\n  Watching {MANIFEST_FILE} and assets/{}/ (Ctrl+C to stop)\n", config.mod_info.mod_id).cyan()
+    );
+    let mut last = snapshot(dir, &config);
+    loop {
+        std::thread::sleep(interval);
+        let current = snapshot(dir, &config);
+        let changed = changed_paths(&last, &current);
+        if changed.is_empty() {
+            continue;
+        }
+        for path in &changed {
+            let rel = path.strip_prefix(dir).unwrap_or(path);
+            println!("  {}", format!("Changed: {}", rel.display()).dimmed());
+        }
+        let manifest_changed = changed.iter().any(|p| p.ends_with(MANIFEST_FILE));
+        if let Err(e) = pass(dir, &config, manifest_changed) {
+            eprintln!("  {}", format!("Error: {e}").red());
+        }
+        // Take in the files the pass itself wrote so they don't trigger another run
+        last = snapshot(dir, &config);
+    }
+}
+
+/// Regenerate from the manifest (when `generate` is set and one exists), then
+/// validate. Returns the number of problems found.
+fn pass(dir: &Path, config: &McmodConfig, generate: bool) -> Result<usize> {
+    if generate {
+        if let Some(manifest) = ContentManifest::load(dir)? {
+            for rel in content::generate(dir, config, &manifest)? {
+                crate::util::progress(format!("  Generated {rel}").green());
+            }
+        }
+    }
+
+    let problems = content::validate(dir, config);
+    if problems.is_empty() {
+        println!("{}", "  Content OK".green());
+    }
+    for problem in &problems {
+        println!("  {} {}", format!("{}:", problem.path).yellow(), problem.message);
+    }
+    Ok(problems.len())
+}
+
+/// Modification times for content.toml and every file in the watched asset directories.
+fn snapshot(dir: &Path, config: &McmodConfig) -> Snapshot {
+    let mut files = vec![dir.join(MANIFEST_FILE)];
+    let assets = content::assets_dir(dir, config);
+    for sub in content::ASSET_DIRS {
+        files.extend(content::json_files(&assets.join(sub)));
+    }
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Paths added, removed, or modified between two snapshots.
+fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|p| !after.contains_key(*p)).cloned());
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> (PathBuf, McmodConfig) {
+        let dir = std::env::temp_dir().join(format!("mcmod_watch_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        config.save(&dir).unwrap();
+        (dir, config)
+    }
+
+    #[test]
+    fn test_generate_and_validate_manifest() {
+        let (dir, config) = project("manifest");
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            "[[items]]\nid = \"ruby\"\n\n[[blocks]]\nid = \"ruby_block\"\nname = \"Block of Ruby\"\n",
+        )
+        .unwrap();
+        let assets = content::assets_dir(&dir, &config);
+        std::fs::create_dir_all(assets.join("lang")).unwrap();
+        std::fs::write(assets.join("lang/en_us.json"), "{\"block.testmod.ruby_block\": \"Ruby Block\"}").unwrap();
+
+        assert_eq!(pass(&dir, &config, true).unwrap(), 2, "both textures are missing");
+        let lang = std::fs::read_to_string(assets.join("lang/en_us.json")).unwrap();
+        assert_eq!(
+            lang,
+            "{\n  \"block.testmod.ruby_block\": \"Block of Ruby\",\n  \"item.testmod.ruby\": \"Ruby\"\n}\n"
+        );
+        for file in ["models/item/ruby.json", "items/ruby.json", "blockstates/ruby_block.json", "models/block/ruby_block.json"] {
+            assert!(assets.join(file).exists(), "missing {file}");
+        }
+
+        // A second run generates nothing new; textures and translations fix the rest
+        let manifest = ContentManifest::load(&dir).unwrap().unwrap();
+        assert!(content::generate(&dir, &config, &manifest).unwrap().is_empty());
+        for texture in ["item/ruby", "block/ruby_block"] {
+            std::fs::create_dir_all(assets.join("textures/item")).unwrap();
+            std::fs::create_dir_all(assets.join("textures/block")).unwrap();
+            std::fs::write(assets.join(format!("textures/{texture}.png")), "").unwrap();
+        }
+        std::fs::write(assets.join("lang/de_de.json"), "{\"item.testmod.ruby\": \"Rubin\",\n\"item.testmod.ruby\": \"x\"}").unwrap();
+        let problems = content::validate(&dir, &config);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, "lang/de_de.json");
+        assert!(problems[0].message.contains("duplicate key"));
+
+        std::fs::write(assets.join("lang/de_de.json"), "{\"item.testmod.ruby\": \"Rubin\"}").unwrap();
+        let problems = content::validate(&dir, &config);
+        assert_eq!(problems[0].message, "1 key(s) from en_us.json are not translated");
+
+        std::fs::write(dir.join(MANIFEST_FILE), "[[items]]\nid = \"Ruby\"\n").unwrap();
+        assert!(ContentManifest::load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_particles_and_sprites() {
+        let (dir, mut config) = project("sprites");
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            "[[particles]]\nid = \"spark\"\n\n[[particles]]\nid = \"glow\"\ntexture = \"effect/glow\"\n\n[[sprites]]\nid = \"progress\"\n",
+        )
+        .unwrap();
+        let manifest = ContentManifest::load(&dir).unwrap().unwrap();
+        let changed = content::generate(&dir, &config, &manifest).unwrap();
+        assert_eq!(changed, ["particles/spark.json", "particles/glow.json", "../minecraft/atlases/particles.json"]);
+
+        let assets = content::assets_dir(&dir, &config);
+        let glow = std::fs::read_to_string(assets.join("particles/glow.json")).unwrap();
+        assert!(glow.contains("\"testmod:effect/glow\""));
+        let atlas = std::fs::read_to_string(dir.join("src/main/resources/assets/minecraft/atlases/particles.json")).unwrap();
+        assert!(atlas.contains("\"resource\": \"testmod:effect/glow\""));
+        assert!(!atlas.contains("spark"), "textures in particle/ are already on the atlas");
+        assert!(content::generate(&dir, &config, &manifest).unwrap().is_empty());
+
+        let missing: Vec<_> = content::validate(&dir, &config).into_iter().map(|p| p.message).collect();
+        assert!(missing.contains(&"texture for testmod:progress is missing (textures/gui/sprites/progress.png)".to_string()));
+        assert_eq!(missing.len(), 3);
+
+        config.versions.targets[0].minecraft = "1.20.1".to_string();
+        let err = content::generate(&dir, &config, &manifest).unwrap_err();
+        assert_eq!(err.to_string(), "GUI sprites in content.toml need Minecraft 1.20.2+ (the oldest target is 1.20.1)");
+
+        std::fs::write(dir.join(MANIFEST_FILE), "[[sprites]]\nid = \"a\"\ntexture = \"gui/a.png\"\n").unwrap();
+        assert!(ContentManifest::load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_changed_paths() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let before: Snapshot = [(PathBuf::from("a"), t0), (PathBuf::from("b"), t0)].into();
+        let after: Snapshot = [(PathBuf::from("a"), t1), (PathBuf::from("c"), t0)].into();
+        assert_eq!(
+            changed_paths(&before, &after),
+            [PathBuf::from("a"), PathBuf::from("c"), PathBuf::from("b")]
+        );
+        assert!(changed_paths(&before, &before).is_empty());
+    }
+}
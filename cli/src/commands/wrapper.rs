@@ -0,0 +1,235 @@
+use crate::commands::update::Progress;
+use crate::error::{Context, McmodError, Result};
+use crate::jar::Jar;
+use crate::util::{http_download, http_get, sha256_file, write_binary, write_file};
+use colored::Colorize;
+use std::path::Path;
+
+const DISTRIBUTIONS: &str = "https://services.gradle.org/distributions";
+const CURRENT_VERSION: &str = "https://services.gradle.org/versions/current";
+/// Gradle's own repository, which checks in the launcher scripts at each release tag.
+const GRADLE_RAW: &str = "https://raw.githubusercontent.com/gradle/gradle";
+const PROPERTIES: &str = "gradle/wrapper/gradle-wrapper.properties";
+const WRAPPER_JAR: &str = "gradle/wrapper/gradle-wrapper.jar";
+
+/// The distribution gradle-wrapper.properties points at.
+#[derive(Debug, PartialEq)]
+struct Distribution {
+    version: String,
+    /// `bin` or `all`.
+    variant: String,
+}
+
+/// `mcmod wrapper [--version X]`: move the Gradle wrapper to `version` (the
+/// latest release by default). The wrapper jar is taken from the release's
+/// `-bin` distribution, which is checked against its published SHA-256, and
+/// the jar itself must match the published wrapper checksum before anything
+/// in the project is replaced.
+pub fn run(dir: &Path, version: Option<&str>, force: bool) -> Result<()> {
+    let props_path = dir.join(PROPERTIES);
+    let props = std::fs::read_to_string(&props_path)
+        .with_context(|| format!("Reading {PROPERTIES} (is this a Gradle project?)"))?;
+    let current = parse_distribution(&props)
+        .ok_or_else(|| McmodError::Other(format!("No Gradle distributionUrl in {PROPERTIES}")))?;
+
+    let version = match version {
+        Some(version) => version.to_string(),
+        None => latest_version()?,
+    };
+    if current.version == version && !force {
+        println!("{}", format!("  The wrapper is already on Gradle {version} (--force to reinstall it)").green());
+        return Ok(());
+    }
+    println!("  Updating the Gradle wrapper from {} to {}", current.version.dimmed(), version.cyan());
+
+    let dist_url = distribution_url(&version, &current.variant);
+    let dist_sha256 = published_checksum(&format!("{dist_url}.sha256"), &version)?;
+    let jar_sha256 = published_checksum(&format!("{DISTRIBUTIONS}/gradle-{version}-wrapper.jar.sha256"), &version)?;
+
+    let jar = wrapper_jar_from_distribution(&version)?;
+    let actual = crate::util::sha256_bytes(&jar);
+    if actual != jar_sha256 {
+        return Err(McmodError::Other(format!(
+            "The wrapper jar in Gradle {version} doesn't match its published checksum (expected {jar_sha256}, got {actual})"
+        )));
+    }
+    println!("{}", "  Wrapper jar verified".green());
+
+    let tag = release_tag(&version);
+    let gradlew = http_get(&format!("{GRADLE_RAW}/{tag}/gradlew"))
+        .with_context(|| format!("Fetching gradlew for Gradle {version}"))?;
+    let gradlew_bat = http_get(&format!("{GRADLE_RAW}/{tag}/gradlew.bat"))
+        .with_context(|| format!("Fetching gradlew.bat for Gradle {version}"))?;
+
+    write_file(&props_path, &set_distribution(&props, &dist_url, &dist_sha256))?;
+    write_binary(&dir.join(WRAPPER_JAR), &jar)?;
+    write_file(&dir.join("gradlew"), &gradlew)?;
+    write_file(&dir.join("gradlew.bat"), &gradlew_bat)?;
+
+    println!("{}", format!("  Gradle wrapper updated to {version}").green());
+    println!("  {}", "Run ./gradlew --version to download the new distribution".dimmed());
+    Ok(())
+}
+
+fn latest_version() -> Result<String> {
+    let current: serde_json::Value = serde_json::from_str(&http_get(CURRENT_VERSION)?)?;
+    current["version"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| McmodError::Other("services.gradle.org didn't name a current Gradle version".to_string()))
+}
+
+/// The checksum published at `url`; a missing file means the version doesn't exist.
+fn published_checksum(url: &str, version: &str) -> Result<String> {
+    let body = http_get(url).map_err(|e| match e.root() {
+        McmodError::Http(message) if message.contains("404") => {
+            McmodError::Other(format!("Gradle {version} isn't published at {DISTRIBUTIONS} (check the version)"))
+        }
+        _ => e,
+    })?;
+    body.split_whitespace()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| McmodError::Other(format!("Empty checksum at {url}")))
+}
+
+/// Download the `-bin` distribution (verified against its SHA-256) and pull
+/// the wrapper jar Gradle's `wrapper` task would write out of it.
+fn wrapper_jar_from_distribution(version: &str) -> Result<Vec<u8>> {
+    let url = distribution_url(version, "bin");
+    let expected = published_checksum(&format!("{url}.sha256"), version)?;
+    let zip = std::env::temp_dir().join(format!("mcmod-gradle-{version}-bin.zip"));
+    let partial = zip.with_extension("zip.part");
+
+    println!("  Downloading Gradle {version}");
+    let mut progress = Progress::new(None);
+    let result = http_download(&url, &partial, &mut |done, total| progress.update(done, total));
+    progress.finish();
+    result?;
+    let actual = sha256_file(&partial)?;
+    if actual != expected {
+        let _ = std::fs::remove_file(&partial);
+        return Err(McmodError::Other(format!(
+            "Checksum mismatch for gradle-{version}-bin.zip (expected {expected}, got {actual})"
+        )));
+    }
+    std::fs::rename(&partial, &zip)?;
+
+    let distribution = Jar::open(&zip);
+    let _ = std::fs::remove_file(&zip);
+    find_wrapper_jar(&distribution?)?
+        .ok_or_else(|| McmodError::Other(format!("Gradle {version}'s distribution has no gradle-wrapper.jar")))
+}
+
+/// The `gradle-wrapper.jar` resource bundled in one of the distribution's
+/// library jars. Jars named after the wrapper are searched first.
+fn find_wrapper_jar(distribution: &Jar) -> Result<Option<Vec<u8>>> {
+    let mut libs: Vec<&str> = distribution
+        .names()
+        .filter(|name| name.contains("/lib/") && name.ends_with(".jar"))
+        .collect();
+    libs.sort_by_key(|name| !name.rsplit('/').next().unwrap_or(name).contains("wrapper"));
+    for name in libs {
+        let Some(bytes) = distribution.read(name)? else { continue };
+        if let Some(wrapper) = Jar::from_bytes(bytes)?.read("gradle-wrapper.jar")? {
+            return Ok(Some(wrapper));
+        }
+    }
+    Ok(None)
+}
+
+fn distribution_url(version: &str, variant: &str) -> String {
+    format!("{DISTRIBUTIONS}/gradle-{version}-{variant}.zip")
+}
+
+/// The Gradle version the project's wrapper runs, if it has one.
+pub fn wrapper_version(dir: &Path) -> Option<String> {
+    let props = std::fs::read_to_string(dir.join(PROPERTIES)).ok()?;
+    parse_distribution(&props).map(|d| d.version)
+}
+
+/// The version and variant in `distributionUrl`.
+fn parse_distribution(props: &str) -> Option<Distribution> {
+    let url = props.lines().find_map(|line| line.trim().strip_prefix("distributionUrl="))?;
+    let file = url.rsplit('/').next()?;
+    let (version, variant) = file.strip_prefix("gradle-")?.strip_suffix(".zip")?.rsplit_once('-')?;
+    Some(Distribution { version: version.to_string(), variant: variant.to_string() })
+}
+
+/// Point `distributionUrl` at `url`, and refresh `distributionSha256Sum` when
+/// the project pins one. Every other line is kept as it is.
+fn set_distribution(props: &str, url: &str, sha256: &str) -> String {
+    let escaped = url.replacen("https://", "https\\://", 1);
+    let mut out = String::new();
+    for line in props.lines() {
+        let key = line.split('=').next().unwrap_or("").trim();
+        match key {
+            "distributionUrl" => out.push_str(&format!("distributionUrl={escaped}")),
+            "distributionSha256Sum" => out.push_str(&format!("distributionSha256Sum={sha256}")),
+            _ => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Gradle's git tag for a release: `8.12` -> `v8.12.0`, `8.12.1` -> `v8.12.1`,
+/// `8.12-rc-1` -> `v8.12.0-RC1`.
+fn release_tag(version: &str) -> String {
+    let (number, rc) = match version.split_once("-rc-") {
+        Some((number, rc)) => (number, Some(rc)),
+        None => (version, None),
+    };
+    let number = if number.matches('.').count() == 1 { format!("{number}.0") } else { number.to_string() };
+    match rc {
+        Some(rc) => format!("v{number}-RC{rc}"),
+        None => format!("v{number}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template;
+
+    #[test]
+    fn test_parse_and_set_distribution() {
+        let props = template::GRADLE_WRAPPER_PROPS;
+        let current = parse_distribution(props).unwrap();
+        assert_eq!(current.variant, "bin");
+        assert!(!current.version.is_empty());
+
+        let updated = set_distribution(props, &distribution_url("8.12", "bin"), "abc");
+        assert!(updated.contains("distributionUrl=https\\://services.gradle.org/distributions/gradle-8.12-bin.zip\n"));
+        assert!(!updated.contains("distributionSha256Sum"));
+        assert!(updated.contains("networkTimeout=10000\n"));
+        assert_eq!(
+            parse_distribution(&updated),
+            Some(Distribution { version: "8.12".to_string(), variant: "bin".to_string() })
+        );
+
+        let pinned = set_distribution(&format!("{props}distributionSha256Sum=old\n"), &distribution_url("8.12", "all"), "abc");
+        assert!(pinned.contains("distributionSha256Sum=abc\n"));
+        assert_eq!(parse_distribution(&pinned).unwrap().variant, "all");
+    }
+
+    #[test]
+    fn test_release_tag() {
+        assert_eq!(release_tag("8.12"), "v8.12.0");
+        assert_eq!(release_tag("8.12.1"), "v8.12.1");
+        assert_eq!(release_tag("9.0-rc-2"), "v9.0.0-RC2");
+    }
+
+    #[test]
+    fn test_find_wrapper_jar() {
+        let wrapper = crate::jar::stored_zip(&[("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\n")]);
+        let plugin = crate::jar::stored_zip(&[("gradle-wrapper.jar", &wrapper)]);
+        let other = crate::jar::stored_zip(&[("a.class", b"x")]);
+        let distribution = crate::jar::stored_zip(&[
+            ("gradle-8.12/lib/gradle-core-8.12.jar", &other),
+            ("gradle-8.12/lib/plugins/gradle-wrapper-main-8.12.jar", &plugin),
+        ]);
+        let distribution = Jar::from_bytes(distribution).unwrap();
+        assert_eq!(find_wrapper_jar(&distribution).unwrap(), Some(wrapper));
+    }
+}
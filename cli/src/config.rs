@@ -1,17 +1,252 @@
-use crate::error::{McmodError, Result};
+use crate::error::{Context, McmodError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-const CONFIG_FILE: &str = "mcmod.toml";
+pub const CONFIG_FILE: &str = "mcmod.toml";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct McmodConfig {
+    /// Oldest mcmod that understands this project, e.g. `">=0.3"`. Written by
+    /// init; an older CLI refuses to load the project rather than mis-edit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires_mcmod: Option<String>,
     pub mod_info: ModInfo,
     pub loaders: Loaders,
     pub features: Features,
     pub versions: Versions,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub publishing: Option<Publishing>,
+    /// Upload targets for `mcmod publish`.
+    #[serde(default, skip_serializing_if = "Publish::is_empty")]
+    pub publish: Publish,
+    /// Sibling projects wired together with `mcmod dep link`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<ProjectLink>,
+    /// Other mods this one has been tested against, keyed by their mod id.
+    /// `mcmod compat` renders them to COMPATIBILITY.md.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub compat: BTreeMap<String, CompatEntry>,
+    /// Optional neoforge.mods.toml fields, written by init and `mcmod sync`.
+    #[serde(default, skip_serializing_if = "NeoForgeMetadata::is_empty")]
+    pub neoforge: NeoForgeMetadata,
+    /// Optional fabric.mod.json fields, written by init and `mcmod sync`.
+    #[serde(default, skip_serializing_if = "FabricMetadata::is_empty")]
+    pub fabric: FabricMetadata,
+    /// Per-loader gradle.properties entries, written by init and `mcmod sync`.
+    #[serde(default, skip_serializing_if = "GradleOverrides::is_empty")]
+    pub gradle: GradleOverrides,
+    /// Mods this one depends on, added with `mcmod add dep` and keyed by the
+    /// name their versions go under in versions/dependencies (`dep.<name>.<loader>`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, ModDependency>,
+}
+
+/// A mod dependency added with `mcmod add dep`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModDependency {
+    pub source: DependencySource,
+    /// Modrinth project slug, or Maven `group:artifact`.
+    pub artifact: String,
+    /// Maven repository URL, when the artifact isn't on Maven Central.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// The dependency's mod id per loader, as its metadata declares it.
+    #[serde(default)]
+    pub mod_ids: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencySource {
+    Modrinth,
+    Maven,
+}
+
+/// Which side of a `mcmod dep link` relationship a project is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkKind {
+    /// This project depends on the linked one.
+    Dependency,
+    /// The linked project depends on this one.
+    Dependent,
+}
+
+/// A local project dependency between two sibling mcmod projects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLink {
+    pub mod_id: String,
+    /// Path to the other project, relative to this one.
+    pub path: String,
+    pub kind: LinkKind,
+}
+
+/// How this mod fares alongside another mod.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompatStatus {
+    /// Installed beside this mod but not checked yet.
+    #[default]
+    Untested,
+    Works,
+    /// Runs, with known problems described in the notes.
+    Issues,
+    Incompatible,
+}
+
+impl CompatStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompatStatus::Untested => "❔ Untested",
+            CompatStatus::Works => "✅ Works",
+            CompatStatus::Issues => "⚠️ Issues",
+            CompatStatus::Incompatible => "❌ Incompatible",
+        }
+    }
+}
+
+/// One `[compat.<mod_id>]` entry: the other mod's version this mod was tested
+/// against, and the result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatEntry {
+    /// Display name; the mod id is shown when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minecraft: Option<String>,
+    /// The loader it was tested on; all of this mod's loaders when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub status: CompatStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// What kind of project mcmod scaffolds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectType {
+    /// A regular mod with an entrypoint.
+    #[default]
+    Mod,
+    /// An API-style mod other mods depend on: no entrypoint, published to Maven
+    /// with sources and javadoc jars.
+    Library,
+    /// A pure data pack: pack.mcmeta and data/ zipped by Gradle, no loader.
+    Datapack,
+    /// An assets-only resource pack, zipped the same way as a data pack.
+    Resourcepack,
+    /// A Paper server plugin: paper-plugin.yml and a JavaPlugin main class.
+    PaperPlugin,
+}
+
+impl ProjectType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectType::Mod => "mod",
+            ProjectType::Library => "library",
+            ProjectType::Datapack => "datapack",
+            ProjectType::Resourcepack => "resourcepack",
+            ProjectType::PaperPlugin => "paper-plugin",
+        }
+    }
+
+    fn is_mod(&self) -> bool {
+        *self == ProjectType::Mod
+    }
+}
+
+/// Which physical side a mod runs on, declared in fabric.mod.json and
+/// quilt.mod.json and used to pick the Fabric entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Environment {
+    /// Client and dedicated server (`"*"`).
+    #[default]
+    Both,
+    /// Client only: a `client` entrypoint and no dedicated-server run config.
+    Client,
+    /// Dedicated server only: a `server` entrypoint.
+    Server,
+}
+
+impl Environment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Both => "both",
+            Environment::Client => "client",
+            Environment::Server => "server",
+        }
+    }
+
+    /// Value of the `environment` field in fabric.mod.json.
+    pub fn fabric_value(&self) -> &'static str {
+        match self {
+            Environment::Both => "*",
+            Environment::Client => "client",
+            Environment::Server => "server",
+        }
+    }
+
+    /// Entrypoint key, initializer interface, and its method for this side.
+    pub fn fabric_entrypoint(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Environment::Both => ("main", "ModInitializer", "onInitialize"),
+            Environment::Client => ("client", "ClientModInitializer", "onInitializeClient"),
+            Environment::Server => ("server", "DedicatedServerModInitializer", "onInitializeServer"),
+        }
+    }
+
+    fn is_both(&self) -> bool {
+        *self == Environment::Both
+    }
+}
+
+/// Extra Fabric entrypoints a mod can opt into, each generated as its own
+/// `<Class><Suffix>` class and listed in fabric.mod.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum FabricEntrypoint {
+    /// `ClientModInitializer`, run on the client after `main`.
+    Client,
+    /// `DataGeneratorEntrypoint`, run by `./gradlew runDatagen`.
+    Datagen,
+    /// `PreLaunchEntrypoint`, run before Minecraft classes load.
+    PreLaunch,
+}
+
+impl FabricEntrypoint {
+    pub const ALL: [FabricEntrypoint; 3] =
+        [FabricEntrypoint::Client, FabricEntrypoint::Datagen, FabricEntrypoint::PreLaunch];
+
+    /// Template variable holding the entrypoint's class name.
+    pub fn var(&self) -> &'static str {
+        match self {
+            FabricEntrypoint::Client => "client_entrypoint",
+            FabricEntrypoint::Datagen => "datagen_entrypoint",
+            FabricEntrypoint::PreLaunch => "prelaunch_entrypoint",
+        }
+    }
+
+    /// Suffix appended to the main class name.
+    pub fn class_suffix(&self) -> &'static str {
+        match self {
+            FabricEntrypoint::Client => "Client",
+            FabricEntrypoint::Datagen => "DataGenerator",
+            FabricEntrypoint::PreLaunch => "PreLaunch",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FabricEntrypoint::Client => "client (ClientModInitializer)",
+            FabricEntrypoint::Datagen => "datagen (DataGeneratorEntrypoint)",
+            FabricEntrypoint::PreLaunch => "pre-launch (PreLaunchEntrypoint)",
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -22,12 +257,31 @@ pub struct ModInfo {
     pub author: String,
     pub description: String,
     pub language: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// GitHub username or organization hosting the mod's repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "ProjectType::is_mod")]
+    pub project_type: ProjectType,
+    #[serde(default, skip_serializing_if = "Environment::is_both")]
+    pub environment: Environment,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Loaders {
     pub fabric: bool,
     pub neoforge: bool,
+    /// Forge (MinecraftForge), for modpacks that still ship it beside NeoForge.
+    #[serde(default)]
+    pub forge: bool,
+    /// Quilt, which runs the Fabric entrypoint through its Fabric compatibility.
+    #[serde(default)]
+    pub quilt: bool,
+    /// A `sponge/` subproject holding a SpongeAPI plugin. Not a Stonecutter
+    /// platform, so it is left out of `enabled_platforms`.
+    #[serde(default)]
+    pub sponge: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,6 +291,61 @@ pub struct Features {
     pub publishing: bool,
     #[serde(default)]
     pub testing: bool,
+    /// JUnit 5 (plus fabric-loader-junit on Fabric) in build.gradle.kts and an
+    /// example test for the mod class.
+    #[serde(default)]
+    pub unit_tests: bool,
+    /// Generate a `BuildConstants` class (version, git commit, build date) at compile time.
+    #[serde(default)]
+    pub build_info: bool,
+    /// A `paper/` subproject holding a Paper plugin built beside the mod.
+    #[serde(default)]
+    pub paper: bool,
+    /// A `velocity/` subproject holding a companion Velocity proxy plugin.
+    #[serde(default)]
+    pub velocity: bool,
+    /// A `spigot/` subproject holding a Spigot/Bukkit plugin (plugin.yml).
+    #[serde(default)]
+    pub spigot: bool,
+    /// Depend on Architectury API and generate a `platform.PlatformHelper` class.
+    #[serde(default)]
+    pub architectury: bool,
+    /// Client code in `src/client`, built by Fabric as Loom's split `client`
+    /// source set, with a `<Class>Client` entrypoint.
+    #[serde(default)]
+    pub split_environment: bool,
+    /// Extra Fabric entrypoint classes beyond the main one. A split client
+    /// source set implies `client`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fabric_entrypoints: Vec<FabricEntrypoint>,
+    /// Whether dev runs share `run/` or get `run/<loader>/` each.
+    #[serde(default, skip_serializing_if = "RunDirs::is_shared")]
+    pub run_dirs: RunDirs,
+}
+
+/// How the dev client and server lay out their game directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunDirs {
+    /// One `run/` shared by every loader
+    #[default]
+    Shared,
+    /// `run/<loader>/` for each loader, keeping worlds, options and mods apart
+    PerLoader,
+}
+
+impl RunDirs {
+    pub fn is_shared(&self) -> bool {
+        *self == RunDirs::Shared
+    }
+}
+
+impl Features {
+    /// Whether the project has the given extra Fabric entrypoint.
+    pub fn has_entrypoint(&self, entrypoint: FabricEntrypoint) -> bool {
+        self.fabric_entrypoints.contains(&entrypoint)
+            || (entrypoint == FabricEntrypoint::Client && self.split_environment)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,6 +355,171 @@ pub struct Publishing {
     pub curseforge_id: Option<String>,
 }
 
+/// `[neoforge]`: the neoforge.mods.toml fields mcmod manages beyond the
+/// generated defaults. `mcmod sync` rewrites them from here, so the file
+/// never drifts from mcmod.toml.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NeoForgeMetadata {
+    /// Which version mismatch the server list screen tolerates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_test: Option<DisplayTest>,
+    /// Top-level `clientSideOnly = true`, for mods that never load on servers.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub client_side_only: bool,
+    /// Logo inside the jar; defaults to `assets/<mod_id>/icon.png`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<String>,
+    /// `[features.<mod id>]` requirements, e.g. `openGLVersion = "[3.2,)"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, String>,
+}
+
+impl NeoForgeMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == NeoForgeMetadata::default()
+    }
+}
+
+/// `[fabric]`: the fabric.mod.json fields mcmod manages beyond the generated
+/// defaults, applied by init, `mcmod add fabric` and `mcmod sync`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FabricMetadata {
+    /// `accessWidener` path inside the jar, e.g. `mymod.accesswidener`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_widener: Option<String>,
+    /// Language adapter per entrypoint name, e.g. `main = "kotlin"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub adapters: BTreeMap<String, String>,
+    /// `[fabric.custom]`: merged into fabric.mod.json's `custom` object, for
+    /// Mod Menu, Cardinal Components and similar mod-specific keys.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, toml::Value>,
+}
+
+impl FabricMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == FabricMetadata::default()
+    }
+}
+
+/// `[gradle.fabric]` / `[gradle.neoforge]`: properties for one loader's
+/// builds, written into each of its Stonecutter version projects'
+/// gradle.properties (`versions/<mc>-<loader>/`), e.g. loom options.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GradleOverrides {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fabric: BTreeMap<String, toml::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub neoforge: BTreeMap<String, toml::Value>,
+}
+
+impl GradleOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == GradleOverrides::default()
+    }
+
+    /// `(loader, enabled, properties)` for each loader that can have overrides.
+    pub fn loaders(&self, enabled: &Loaders) -> [(&'static str, bool, &BTreeMap<String, toml::Value>); 2] {
+        [("fabric", enabled.fabric, &self.fabric), ("neoforge", enabled.neoforge, &self.neoforge)]
+    }
+}
+
+/// neoforge.mods.toml `displayTest` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisplayTest {
+    /// Red X unless client and server have the same mod version (NeoForge's default)
+    MatchVersion,
+    /// Ignored when the server doesn't have the mod
+    IgnoreServerVersion,
+    /// Never compared; for client-only mods
+    IgnoreAllVersion,
+    /// No check at all
+    None,
+}
+
+impl DisplayTest {
+    /// The value as written in neoforge.mods.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplayTest::MatchVersion => "MATCH_VERSION",
+            DisplayTest::IgnoreServerVersion => "IGNORE_SERVER_VERSION",
+            DisplayTest::IgnoreAllVersion => "IGNORE_ALL_VERSION",
+            DisplayTest::None => "NONE",
+        }
+    }
+}
+
+/// `[publish.*]` sections: where `mcmod publish` uploads built jars.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Publish {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modrinth: Option<ModrinthPublish>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curseforge: Option<CurseforgePublish>,
+}
+
+impl Publish {
+    pub fn is_empty(&self) -> bool {
+        self.modrinth.is_none() && self.curseforge.is_none()
+    }
+}
+
+/// `[publish.modrinth]`: the Modrinth project and where its API token comes from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModrinthPublish {
+    pub project_id: String,
+    /// Environment variable holding a personal access token with the
+    /// "Create versions" scope.
+    #[serde(default = "default_modrinth_token_env")]
+    pub token_env: String,
+}
+
+fn default_modrinth_token_env() -> String {
+    "MODRINTH_TOKEN".to_string()
+}
+
+impl ModrinthPublish {
+    /// `[publish.modrinth]`, or the project from `[publishing] modrinth_id`
+    /// with the default token variable.
+    pub fn from_config(config: &McmodConfig) -> Option<ModrinthPublish> {
+        config.publish.modrinth.clone().or_else(|| {
+            config.publishing.as_ref().map(|p| ModrinthPublish {
+                project_id: p.modrinth_id.clone(),
+                token_env: default_modrinth_token_env(),
+            })
+        })
+    }
+}
+
+/// `[publish.curseforge]`: the CurseForge project and where its API token comes from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CurseforgePublish {
+    /// Numeric project id, shown in the project's About box.
+    pub project_id: String,
+    /// Environment variable holding an upload API token
+    /// (curseforge.com/account/api-tokens).
+    #[serde(default = "default_curseforge_token_env")]
+    pub token_env: String,
+}
+
+fn default_curseforge_token_env() -> String {
+    "CURSEFORGE_TOKEN".to_string()
+}
+
+impl CurseforgePublish {
+    /// `[publish.curseforge]`, or the project from `[publishing] curseforge_id`
+    /// with the default token variable.
+    pub fn from_config(config: &McmodConfig) -> Option<CurseforgePublish> {
+        config.publish.curseforge.clone().or_else(|| {
+            let project_id = config.publishing.as_ref()?.curseforge_id.clone()?;
+            Some(CurseforgePublish { project_id, token_env: default_curseforge_token_env() })
+        })
+    }
+}
+
 /// Version configuration for a Stonecutter multi-version project.
 ///
 /// `targets` lists each MC version to build against. Each target holds
@@ -55,9 +529,11 @@ pub struct Publishing {
 pub struct Versions {
     pub targets: Vec<VersionTarget>,
     /// Deprecated: kept for backwards compatibility with old mcmod.toml files.
+    #[allow(dead_code)]
     #[serde(default, skip_serializing)]
     pub architectury_plugin: Option<String>,
     /// Deprecated: kept for backwards compatibility with old mcmod.toml files.
+    #[allow(dead_code)]
     #[serde(default, skip_serializing)]
     pub architectury_loom: Option<String>,
 }
@@ -72,9 +548,19 @@ pub struct VersionTarget {
     pub fabric_loader: String,
     pub fabric_api: String,
     pub neoforge: String,
+    /// Empty unless Forge is enabled.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub forge: String,
+    /// Empty unless Quilt is enabled.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub quilt_loader: String,
+    /// Empty unless Architectury API is enabled.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub architectury_api: String,
 }
 
 impl McmodConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         mod_id: String,
         mod_name: String,
@@ -90,6 +576,7 @@ impl McmodConfig {
         versions: Versions,
     ) -> Self {
         Self {
+            requires_mcmod: Some(current_requirement()),
             mod_info: ModInfo {
                 mod_id,
                 mod_name,
@@ -97,19 +584,90 @@ impl McmodConfig {
                 author,
                 description,
                 language,
+                email: None,
+                github: None,
+                project_type: ProjectType::Mod,
+                environment: Environment::Both,
+            },
+            loaders: Loaders {
+                fabric,
+                neoforge,
+                forge: false,
+                quilt: false,
+                sponge: false,
             },
-            loaders: Loaders { fabric, neoforge },
             features: Features {
                 ci,
                 publishing: publishing.is_some(),
                 testing,
+                unit_tests: false,
+                build_info: false,
+                paper: false,
+                velocity: false,
+                spigot: false,
+                architectury: false,
+                split_environment: false,
+                fabric_entrypoints: Vec::new(),
+                run_dirs: RunDirs::Shared,
             },
             versions,
             publishing,
+            publish: Publish::default(),
+            links: Vec::new(),
+            compat: BTreeMap::new(),
+            neoforge: NeoForgeMetadata::default(),
+            fabric: FabricMetadata::default(),
+            gradle: GradleOverrides::default(),
+            dependencies: BTreeMap::new(),
         }
     }
 
+    pub fn is_library(&self) -> bool {
+        self.mod_info.project_type == ProjectType::Library
+    }
+
+    pub fn is_datapack(&self) -> bool {
+        self.mod_info.project_type == ProjectType::Datapack
+    }
+
+    pub fn is_plugin(&self) -> bool {
+        self.mod_info.project_type == ProjectType::PaperPlugin
+    }
+
+    /// Mods and libraries are Stonecutter multi-loader builds; packs and
+    /// plugins are a single plain Gradle project without loaders.
+    pub fn uses_stonecutter(&self) -> bool {
+        matches!(
+            self.mod_info.project_type,
+            ProjectType::Mod | ProjectType::Library
+        )
+    }
+
+    /// Data packs and resource packs: no code, loaders or Stonecutter.
+    pub fn is_pack(&self) -> bool {
+        matches!(
+            self.mod_info.project_type,
+            ProjectType::Datapack | ProjectType::Resourcepack
+        )
+    }
+
     /// Returns the list of enabled platform names (e.g. ["fabric", "neoforge"])
+    /// A loader's dev run directory, relative to the project.
+    pub fn run_dir(&self, loader: &str) -> String {
+        match self.features.run_dirs {
+            RunDirs::Shared => "run".to_string(),
+            RunDirs::PerLoader => format!("run/{loader}"),
+        }
+    }
+
+    /// Every dev run directory: `run`, or `run/<loader>` per enabled loader.
+    pub fn run_dirs(&self) -> Vec<String> {
+        match self.features.run_dirs {
+            RunDirs::Shared => vec!["run".to_string()],
+            RunDirs::PerLoader => self.enabled_platforms().iter().map(|l| self.run_dir(l)).collect(),
+        }
+    }
+
     pub fn enabled_platforms(&self) -> Vec<&str> {
         let mut platforms = Vec::new();
         if self.loaders.fabric {
@@ -118,30 +676,52 @@ impl McmodConfig {
         if self.loaders.neoforge {
             platforms.push("neoforge");
         }
+        if self.loaders.forge {
+            platforms.push("forge");
+        }
+        if self.loaders.quilt {
+            platforms.push("quilt");
+        }
         platforms
     }
 
     /// Load config from mcmod.toml in the given directory.
     pub fn load(dir: &Path) -> Result<Self> {
         let path = dir.join(CONFIG_FILE);
-        if !path.exists() {
+        if !crate::fs::exists(&path) {
             return Err(McmodError::ConfigNotFound);
         }
-        let content = std::fs::read_to_string(&path)?;
-        let config: McmodConfig = toml::from_str(&content)?;
-        Ok(config)
+        let content = crate::fs::read_to_string(&path)
+            .with_context(|| format!("Reading {}", path.display()))?;
+        // Checked before the full parse: a newer project may use fields or
+        // values this version can't read.
+        #[derive(Deserialize)]
+        struct Requirement {
+            requires_mcmod: Option<String>,
+        }
+        let requirement: Requirement =
+            toml::from_str(&content).with_context(|| format!("Parsing {}", path.display()))?;
+        if let Some(required) = &requirement.requires_mcmod {
+            check_requirement(required, env!("CARGO_PKG_VERSION"))?;
+        }
+        toml::from_str(&content).with_context(|| format!("Parsing {}", path.display()))
     }
 
     /// Save config to mcmod.toml in the given directory.
     pub fn save(&self, dir: &Path) -> Result<()> {
         let path = dir.join(CONFIG_FILE);
         let content = toml::to_string_pretty(self)?;
-        std::fs::write(&path, content)?;
+        crate::util::write_file(&path, &content)?;
         Ok(())
     }
 
+    /// The project homepage: the GitHub repository when a GitHub owner is known.
+    pub fn homepage(&self) -> String {
+        let owner = self.mod_info.github.as_deref().unwrap_or("yourname");
+        format!("https://github.com/{owner}/{}", self.mod_info.mod_id)
+    }
+
     /// Returns the path to mcmod.toml for the given directory.
-    #[allow(dead_code)]
     pub fn config_path(dir: &Path) -> PathBuf {
         dir.join(CONFIG_FILE)
     }
@@ -154,11 +734,11 @@ impl McmodConfig {
             .first()
             .map(|t| t.minecraft.as_str())
             .unwrap_or("1.21.4");
-        let loader = if self.loaders.fabric {
-            "fabric"
-        } else {
-            "neoforge"
-        };
+        let loader = self
+            .enabled_platforms()
+            .first()
+            .copied()
+            .unwrap_or("fabric");
         format!("{mc}-{loader}")
     }
 
@@ -170,14 +750,12 @@ impl McmodConfig {
     ///         mc("1.21.7", "fabric", "neoforge")
     /// ```
     pub fn mc_versions_block(&self) -> String {
-        let mut loaders = Vec::new();
-        if self.loaders.fabric {
-            loaders.push("\"fabric\"");
-        }
-        if self.loaders.neoforge {
-            loaders.push("\"neoforge\"");
-        }
-        let loaders_str = loaders.join(", ");
+        let loaders_str = self
+            .enabled_platforms()
+            .iter()
+            .map(|l| format!("\"{l}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
         self.versions
             .targets
             .iter()
@@ -187,6 +765,71 @@ impl McmodConfig {
     }
 }
 
+/// The `requires_mcmod` written into new projects: at least this CLI's
+/// major.minor version.
+pub fn current_requirement() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let major_minor: Vec<&str> = version.split('.').take(2).collect();
+    format!(">={}", major_minor.join("."))
+}
+
+/// Check a `requires_mcmod` requirement (comma-separated `>=`, `>`, `<=`,
+/// `<` or `=` comparisons; a bare version means `>=`) against `current`.
+/// A requirement this version can't parse was written by a newer one, so it
+/// fails the same way.
+fn check_requirement(requirement: &str, current: &str) -> Result<()> {
+    use std::cmp::Ordering;
+    use crate::version_meta::compare_versions;
+    let unmet = || McmodError::NewerMcmodRequired {
+        required: requirement.to_string(),
+        current: current.to_string(),
+    };
+    for part in requirement.split(',').map(str::trim) {
+        let (op, version) = [">=", "<=", ">", "<", "="]
+            .iter()
+            .find_map(|op| part.strip_prefix(op).map(|v| (*op, v.trim())))
+            .unwrap_or((">=", part));
+        let valid = version.starts_with(|c: char| c.is_ascii_digit())
+            && version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'));
+        if !valid {
+            return Err(unmet());
+        }
+        let ord = compare_versions(current, version);
+        let ok = match op {
+            ">=" => ord != Ordering::Less,
+            "<=" => ord != Ordering::Greater,
+            ">" => ord == Ordering::Greater,
+            "<" => ord == Ordering::Less,
+            _ => ord == Ordering::Equal,
+        };
+        if !ok {
+            return Err(unmet());
+        }
+    }
+    Ok(())
+}
+
+/// Walk up from `start` to the nearest directory containing mcmod.toml,
+/// the way cargo locates Cargo.toml.
+pub fn find_project_root(start: &Path) -> Result<PathBuf> {
+    let start = std::path::absolute(start)?;
+    start
+        .ancestors()
+        .find(|dir| McmodConfig::config_path(dir).is_file())
+        .map(Path::to_path_buf)
+        .ok_or(McmodError::ConfigNotFound)
+}
+
+/// The project directory for commands that operate on an existing project:
+/// an explicit `--dir` is used as given, otherwise mcmod.toml is discovered
+/// from the current directory upward.
+pub fn resolve_project_dir(dir: Option<PathBuf>) -> Result<PathBuf> {
+    match dir {
+        Some(dir) => Ok(dir),
+        None => find_project_root(&std::env::current_dir()?),
+    }
+}
+
 impl Default for Versions {
     fn default() -> Self {
         Self {
@@ -196,6 +839,9 @@ impl Default for Versions {
                 fabric_loader: "0.18.5".to_string(),
                 fabric_api: "0.119.4+1.21.4".to_string(),
                 neoforge: "21.4.157".to_string(),
+                forge: String::new(),
+                quilt_loader: String::new(),
+                architectury_api: String::new(),
             }],
             architectury_plugin: None,
             architectury_loom: None,
@@ -207,6 +853,53 @@ impl Default for Versions {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_requirement() {
+        assert!(check_requirement(">=0.3", "0.3.0").is_ok());
+        assert!(check_requirement("0.2", "0.3.0").is_ok());
+        assert!(check_requirement(">=0.2, <0.4", "0.3.1").is_ok());
+        assert!(check_requirement("=0.3.0", "0.3.0").is_ok());
+        assert!(check_requirement(">=0.5", "0.3.0").is_err());
+        assert!(check_requirement(">=0.2, <0.3", "0.3.0").is_err());
+        let err = check_requirement("~0.5", "0.3.0").unwrap_err();
+        assert!(matches!(err, McmodError::NewerMcmodRequired { .. }));
+        assert!(err.hint().unwrap().contains("mcmod update"));
+        assert!(check_requirement(&current_requirement(), env!("CARGO_PKG_VERSION")).is_ok());
+    }
+
+    #[test]
+    fn test_load_checks_requirement() {
+        let config = McmodConfig::new(
+            "mymod".to_string(),
+            "My Mod".to_string(),
+            "com.example.mymod".to_string(),
+            "Author".to_string(),
+            "A mod".to_string(),
+            "java".to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            Default::default(),
+        );
+        let dir = Path::new("/project");
+        let (result, _) = crate::fs::in_memory(|| {
+            config.save(dir).unwrap();
+            let loaded = McmodConfig::load(dir).unwrap();
+            assert_eq!(loaded.requires_mcmod, Some(current_requirement()));
+
+            // A newer project is refused before its unknown values are parsed
+            let content = crate::fs::read_to_string(&dir.join(CONFIG_FILE)).unwrap();
+            let content = content
+                .replace(&current_requirement(), ">=999.0")
+                .replace("language = \"java\"", "language = 7");
+            crate::util::write_file(&dir.join(CONFIG_FILE), &content).unwrap();
+            McmodConfig::load(dir)
+        });
+        assert!(matches!(result, Err(McmodError::NewerMcmodRequired { .. })));
+    }
+
     #[test]
     fn test_config_roundtrip() {
         let config = McmodConfig::new(
@@ -229,13 +922,40 @@ mod tests {
 
         assert_eq!(deserialized.mod_info.mod_id, "mymod");
         assert_eq!(deserialized.mod_info.mod_name, "My Mod");
-        assert_eq!(deserialized.loaders.fabric, true);
-        assert_eq!(deserialized.loaders.neoforge, true);
-        assert_eq!(deserialized.features.ci, false);
+        assert!(deserialized.loaders.fabric);
+        assert!(deserialized.loaders.neoforge);
+        assert!(!deserialized.features.ci);
         assert_eq!(deserialized.versions.targets.len(), 1);
         assert_eq!(deserialized.versions.targets[0].minecraft, "1.21.4");
     }
 
+    #[test]
+    fn test_project_type_roundtrip() {
+        let mut config = McmodConfig::new(
+            "mylib".to_string(),
+            "My Lib".to_string(),
+            "com.example.mylib".to_string(),
+            "TestAuthor".to_string(),
+            "A test library".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Versions::default(),
+        );
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(!serialized.contains("type"), "the default mod type is omitted");
+        assert!(!toml::from_str::<McmodConfig>(&serialized).unwrap().is_library());
+
+        config.mod_info.project_type = ProjectType::Library;
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("type = \"library\""));
+        assert!(toml::from_str::<McmodConfig>(&serialized).unwrap().is_library());
+    }
+
     #[test]
     fn test_enabled_platforms() {
         let config = McmodConfig::new(
@@ -265,6 +985,9 @@ mod tests {
                     fabric_loader: "0.18.5".to_string(),
                     fabric_api: "0.116.9+1.21.1".to_string(),
                     neoforge: "21.1.221".to_string(),
+                    forge: String::new(),
+                    quilt_loader: String::new(),
+                    architectury_api: String::new(),
                 },
                 VersionTarget {
                     minecraft: "1.21.7".to_string(),
@@ -272,6 +995,9 @@ mod tests {
                     fabric_loader: "0.18.5".to_string(),
                     fabric_api: "0.128.2+1.21.7".to_string(),
                     neoforge: "21.7.25-beta".to_string(),
+                    forge: String::new(),
+                    quilt_loader: String::new(),
+                    architectury_api: String::new(),
                 },
             ],
             architectury_plugin: None,
@@ -296,4 +1022,91 @@ mod tests {
         assert!(block.contains("mc(\"1.21.7\", \"fabric\", \"neoforge\")"));
         assert_eq!(config.active_version(), "1.21.1-fabric");
     }
+
+    #[test]
+    fn test_forge_loader() {
+        let mut config = McmodConfig::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "com.test".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            false,
+            true,
+            false,
+            false,
+            None,
+            Versions::default(),
+        );
+        config.loaders.forge = true;
+        config.versions.targets[0].forge = "54.1.0".to_string();
+        assert_eq!(config.enabled_platforms(), vec!["neoforge", "forge"]);
+        assert_eq!(config.mc_versions_block(), "        mc(\"1.21.4\", \"neoforge\", \"forge\")");
+        assert_eq!(config.active_version(), "1.21.4-neoforge");
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_str.contains("forge = \"54.1.0\""));
+        let parsed: McmodConfig = toml::from_str(&toml_str).unwrap();
+        assert!(parsed.loaders.forge);
+
+        // Configs written before Forge support have no forge key
+        let old = toml_str
+            .replace("\nforge = true\n", "\n")
+            .replace("\nforge = \"54.1.0\"\n", "\n");
+        let parsed: McmodConfig = toml::from_str(&old).unwrap();
+        assert!(!parsed.loaders.forge);
+        assert!(parsed.versions.targets[0].forge.is_empty());
+    }
+
+    #[test]
+    fn test_quilt_loader() {
+        let mut config = McmodConfig::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "com.test".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            "java".to_string(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            Versions::default(),
+        );
+        config.loaders.quilt = true;
+        config.versions.targets[0].quilt_loader = "0.29.2".to_string();
+        assert_eq!(config.enabled_platforms(), vec!["fabric", "quilt"]);
+        assert_eq!(config.mc_versions_block(), "        mc(\"1.21.4\", \"fabric\", \"quilt\")");
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_str.contains("quilt_loader = \"0.29.2\""));
+        let parsed: McmodConfig = toml::from_str(&toml_str).unwrap();
+        assert!(parsed.loaders.quilt);
+        assert_eq!(parsed.versions.targets[0].quilt_loader, "0.29.2");
+    }
+
+    #[test]
+    fn test_find_project_root_walks_up() {
+        let root = std::env::temp_dir().join(format!("mcmod_root_{}", std::process::id()));
+        let nested = root.join("src/main/java/com/example");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(CONFIG_FILE), "").unwrap();
+
+        assert_eq!(find_project_root(&nested).unwrap(), root);
+        assert_eq!(find_project_root(&root).unwrap(), root);
+        assert_eq!(
+            resolve_project_dir(Some(nested.clone())).unwrap(),
+            nested,
+            "--dir is used as given"
+        );
+
+        std::fs::remove_file(root.join(CONFIG_FILE)).unwrap();
+        assert!(matches!(
+            find_project_root(&nested),
+            Err(McmodError::ConfigNotFound)
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,233 @@
+//! Generated files the user has changed.
+//!
+//! init, `add` and `gen` write whole files from templates through
+//! [`write_generated`], which records each file's SHA-256 in mcmod.lock. When
+//! a later run would replace a file whose content matches neither the new
+//! output nor the recorded hash, the user changed it, and the
+//! [`ConflictPolicy`] (`--on-conflict`) decides what happens instead of the
+//! file being overwritten. Edits to existing files go through
+//! `diff::apply_edit`, which shows and confirms the change itself.
+
+use crate::diff;
+use crate::error::{McmodError, Result};
+use crate::file_policy::FilePolicy;
+use crate::lockfile::{Generated, Lockfile};
+use crate::util::sha256_bytes;
+use clap::ValueEnum;
+use colored::Colorize;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// What to do with a generated file that would replace the user's changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Ask for each file (writes .new files when there's no terminal)
+    Ask,
+    /// Replace the file with the generated one
+    Overwrite,
+    /// Keep the file as it is
+    Skip,
+    /// Keep the file and write the generated one beside it as <file>.new
+    New,
+}
+
+/// The project being written to and the hashes recorded for it.
+struct Session {
+    root: PathBuf,
+    policy: ConflictPolicy,
+    /// SHA-256 of each generated file as last written, by project-relative path.
+    recorded: BTreeMap<String, String>,
+    changed: bool,
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Run `f` (a command writing into the project at `dir`) with conflicts
+/// resolved by `policy`, then save the hashes of the files it generated to
+/// mcmod.lock. They're saved when `f` fails too, so rerunning the command
+/// doesn't take the files it got to for the user's changes.
+pub fn session<R>(dir: &Path, policy: ConflictPolicy, f: impl FnOnce() -> Result<R>) -> Result<R> {
+    let recorded = Lockfile::load(dir)
+        .map(|lock| lock.generated.into_iter().map(|g| (g.path, g.sha256)).collect())
+        .unwrap_or_default();
+    SESSION.with(|s| {
+        *s.borrow_mut() = Some(Session { root: dir.to_path_buf(), policy, recorded, changed: false })
+    });
+    let result = f();
+    let session = SESSION.with(|s| s.borrow_mut().take());
+
+    if let Some(session) = session.filter(|s| s.changed) {
+        let saved = Lockfile::load(dir).and_then(|mut lock| {
+            lock.generated = session
+                .recorded
+                .into_iter()
+                .map(|(path, sha256)| Generated { path, sha256 })
+                .collect();
+            lock.save(dir)
+        });
+        // The command's own error matters more than this one
+        if result.is_ok() {
+            saved?;
+        }
+    }
+    result
+}
+
+/// Write a file generated from a template. Outside a [`session`] this is
+/// `util::write_file`. Inside one, an existing file that differs from both
+/// `content` and its recorded hash is a conflict for the session's policy;
+/// whatever is written has its hash recorded.
+pub fn write_generated(path: &Path, content: &str) -> Result<()> {
+    let Some((rel, policy, recorded)) = SESSION.with(|s| {
+        let session = s.borrow();
+        let session = session.as_ref()?;
+        let rel = relative(&session.root, path)?;
+        let recorded = session.recorded.get(&rel).cloned();
+        Some((rel, session.policy, recorded))
+    }) else {
+        return crate::util::write_file(path, content);
+    };
+
+    let file_policy = FilePolicy::for_path(path);
+    let generated = file_policy.normalize(content);
+    let resolution = match crate::fs::read(path) {
+        Ok(existing) if existing != generated.as_bytes() && recorded.as_deref() != Some(&sha256_bytes(&existing)) => {
+            resolve(&rel, &String::from_utf8_lossy(&existing), &generated, policy)?
+        }
+        _ => ConflictPolicy::Overwrite,
+    };
+
+    match resolution {
+        ConflictPolicy::Skip => {
+            crate::util::progress(format!("  Kept {rel}: it was changed since mcmod generated it").yellow());
+        }
+        ConflictPolicy::New => {
+            let mut new_path = path.as_os_str().to_owned();
+            new_path.push(".new");
+            crate::util::write_file_with(Path::new(&new_path), &generated, &file_policy)?;
+            crate::progress::warning(format!("{rel} was changed since mcmod generated it; wrote {rel}.new beside it"));
+        }
+        _ => {
+            crate::util::write_file_with(path, &generated, &file_policy)?;
+            record(&rel, sha256_bytes(generated.as_bytes()));
+        }
+    }
+    Ok(())
+}
+
+/// Note an edit mcmod made to a generated file, so the edited file still
+/// counts as unchanged by the user. Files whose recorded hash didn't match
+/// `old` stay as they were.
+pub fn record_edit(path: &Path, old: &str, new: &str) {
+    let Some(rel) = SESSION.with(|s| s.borrow().as_ref().and_then(|session| relative(&session.root, path))) else {
+        return;
+    };
+    let file_policy = FilePolicy::for_path(path);
+    let old_hash = sha256_bytes(file_policy.normalize(old).as_bytes());
+    let matches = SESSION.with(|s| {
+        s.borrow().as_ref().is_some_and(|session| session.recorded.get(&rel) == Some(&old_hash))
+    });
+    if matches {
+        record(&rel, sha256_bytes(file_policy.normalize(new).as_bytes()));
+    }
+}
+
+fn record(rel: &str, sha256: String) {
+    SESSION.with(|s| {
+        if let Some(session) = s.borrow_mut().as_mut() {
+            session.recorded.insert(rel.to_string(), sha256);
+            session.changed = true;
+        }
+    });
+}
+
+/// `path` relative to the project root, with `/` separators.
+fn relative(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+/// How to handle the conflict on `rel`. `Ask` prompts in a terminal; a
+/// preview or test (in memory) overwrites, so it shows the generated file,
+/// and a run without a terminal writes a .new file.
+fn resolve(rel: &str, existing: &str, generated: &str, policy: ConflictPolicy) -> Result<ConflictPolicy> {
+    if policy != ConflictPolicy::Ask {
+        return Ok(policy);
+    }
+    if crate::fs::is_in_memory() {
+        return Ok(ConflictPolicy::Overwrite);
+    }
+    if !std::io::stdin().is_terminal() {
+        return Ok(ConflictPolicy::New);
+    }
+    loop {
+        let choice = dialoguer::Select::new()
+            .with_prompt(format!("  {rel} was changed since mcmod generated it"))
+            .items(["overwrite", "skip", "show diff", "write .new alongside"])
+            .default(1)
+            .interact()
+            .map_err(|e| McmodError::Other(e.to_string()))?;
+        match choice {
+            0 => return Ok(ConflictPolicy::Overwrite),
+            1 => return Ok(ConflictPolicy::Skip),
+            2 => diff::print_colored(&diff::unified_diff(existing, generated, rel)),
+            _ => return Ok(ConflictPolicy::New),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_generated_conflicts() {
+        let dir = Path::new("/project");
+        let path = dir.join("src/Main.java");
+        let (result, fs) = crate::fs::in_memory(|| -> Result<()> {
+            session(dir, ConflictPolicy::Skip, || write_generated(&path, "class Main {}\n"))?;
+            let lock = Lockfile::load(dir)?;
+            assert_eq!(lock.generated.len(), 1);
+            assert_eq!(lock.generated[0].path, "src/Main.java");
+
+            // Unchanged since generated: replaced without asking
+            session(dir, ConflictPolicy::Skip, || write_generated(&path, "class Main { v2 }\n"))?;
+            assert_eq!(crate::fs::read_to_string(&path)?, "class Main { v2 }\n");
+
+            // Changed by the user: the policy decides
+            crate::util::write_file(&path, "class Main { mine }\n")?;
+            session(dir, ConflictPolicy::Skip, || write_generated(&path, "class Main { v3 }\n"))?;
+            assert_eq!(crate::fs::read_to_string(&path)?, "class Main { mine }\n");
+            session(dir, ConflictPolicy::New, || write_generated(&path, "class Main { v3 }\n"))?;
+            assert_eq!(crate::fs::read_to_string(&dir.join("src/Main.java.new"))?, "class Main { v3 }\n");
+            session(dir, ConflictPolicy::Ask, || write_generated(&path, "class Main { v3 }\n"))?;
+            assert_eq!(crate::fs::read_to_string(&path)?, "class Main { v3 }\n");
+            Ok(())
+        });
+        result.unwrap();
+        assert_eq!(fs.read_string(&dir.join("src/Main.java.new")).unwrap(), "class Main { v3 }\n");
+    }
+
+    #[test]
+    fn test_record_edit() {
+        let dir = Path::new("/project");
+        let path = dir.join("build.gradle.kts");
+        let (result, _) = crate::fs::in_memory(|| -> Result<()> {
+            session(dir, ConflictPolicy::Skip, || {
+                write_generated(&path, "plugins {}\n")?;
+                crate::util::write_file(&path, "plugins { java }\n")?;
+                record_edit(&path, "plugins {}\n", "plugins { java }\n");
+                Ok(())
+            })?;
+            // mcmod's own edit doesn't count as the user's
+            session(dir, ConflictPolicy::Skip, || write_generated(&path, "plugins { kotlin }\n"))?;
+            assert_eq!(crate::fs::read_to_string(&path)?, "plugins { kotlin }\n");
+            Ok(())
+        });
+        result.unwrap();
+    }
+}
@@ -0,0 +1,425 @@
+//! The content manifest (`content.toml`) and the resource generators and
+//! validators driven by it.
+//!
+//! The manifest lists a mod's items and blocks by registry id:
+//!
+//! ```toml
+//! [[items]]
+//! id = "ruby"
+//!
+//! [[blocks]]
+//! id = "ruby_block"
+//! name = "Block of Ruby"
+//! ```
+//!
+//! Generating adds each entry's translation to `en_us.json` and creates any
+//! missing model, blockstate, and item definition JSON. Existing model files
+//! are never overwritten, so they can be edited freely once created.
+//!
+//! `[[particles]]` and `[[sprites]]` (GUI sprites) take an optional `texture`
+//! path under `textures/`. Without one the texture lives in the directory the
+//! vanilla atlas already scans (`particle/`, `gui/sprites/`); with one, a
+//! `single` source is merged into `assets/minecraft/atlases/<atlas>.json` so
+//! the sprite is stitched anyway.
+
+use crate::config::McmodConfig;
+use crate::error::{Context, McmodError, Result};
+use crate::json_merge::{self, Json, OnConflict};
+use crate::naming;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "content.toml";
+
+/// Resource directories under `assets/<mod_id>/` that validators check.
+pub const ASSET_DIRS: &[&str] = &["lang", "models", "blockstates", "items", "particles"];
+
+/// First Minecraft version that reads item definitions from `assets/<ns>/items/`.
+const ITEM_DEFINITIONS_SINCE: &str = "1.21.4";
+
+/// A vanilla texture atlas that manifest sprites are stitched into.
+#[derive(Debug)]
+pub struct Atlas {
+    /// File name under `assets/minecraft/atlases/`.
+    pub name: &'static str,
+    /// What the manifest calls these entries, for messages.
+    pub label: &'static str,
+    /// Directory under `textures/` the vanilla atlas already scans.
+    pub dir: &'static str,
+    /// First Minecraft version with this atlas.
+    pub since: &'static str,
+}
+
+pub const PARTICLES_ATLAS: Atlas = Atlas { name: "particles", label: "particles", dir: "particle", since: "1.19.3" };
+pub const GUI_ATLAS: Atlas = Atlas { name: "gui", label: "GUI sprites", dir: "gui/sprites", since: "1.20.2" };
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContentManifest {
+    #[serde(default)]
+    pub items: Vec<ContentEntry>,
+    #[serde(default)]
+    pub blocks: Vec<ContentEntry>,
+    #[serde(default)]
+    pub particles: Vec<SpriteEntry>,
+    /// GUI sprites, e.g. a machine's progress arrow.
+    #[serde(default)]
+    pub sprites: Vec<SpriteEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContentEntry {
+    /// Registry path, e.g. `ruby` for `<mod_id>:ruby`.
+    pub id: String,
+    /// English display name; derived from the id when omitted.
+    pub name: Option<String>,
+}
+
+/// A particle type or GUI sprite.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpriteEntry {
+    pub id: String,
+    /// Texture path under `textures/` without `.png`, when it isn't in the
+    /// atlas's own directory.
+    pub texture: Option<String>,
+}
+
+impl SpriteEntry {
+    /// Texture path under `textures/`, without `.png`.
+    pub fn texture_path(&self, atlas: &Atlas) -> String {
+        self.texture.clone().unwrap_or_else(|| format!("{}/{}", atlas.dir, self.id))
+    }
+
+    /// Sprite name in the atlas: the path relative to the directory the atlas
+    /// scans, or the whole texture path for a `single` source.
+    pub fn sprite(&self, mod_id: &str) -> String {
+        format!("{mod_id}:{}", self.texture.as_deref().unwrap_or(&self.id))
+    }
+}
+
+impl ContentEntry {
+    /// The display name: `name`, or the id in title case (`ruby_block` -> `Ruby Block`).
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| naming::display_name(&self.id))
+    }
+}
+
+impl ContentManifest {
+    /// Load `content.toml` from the project directory; None when there isn't one.
+    pub fn load(dir: &Path) -> Result<Option<ContentManifest>> {
+        let path = dir.join(MANIFEST_FILE);
+        if !crate::fs::exists(&path) {
+            return Ok(None);
+        }
+        let content = crate::fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))?;
+        let manifest: ContentManifest =
+            toml::from_str(&content).with_context(|| format!("Parsing {}", path.display()))?;
+        manifest.validate()?;
+        Ok(Some(manifest))
+    }
+
+    fn validate(&self) -> Result<()> {
+        // Items and blocks share the item registry; particles and sprites
+        // each have their own namespace of ids
+        let groups: [Vec<&String>; 3] = [
+            self.items.iter().chain(&self.blocks).map(|e| &e.id).collect(),
+            self.particles.iter().map(|e| &e.id).collect(),
+            self.sprites.iter().map(|e| &e.id).collect(),
+        ];
+        for ids in groups {
+            let mut seen = Vec::new();
+            for id in ids {
+                if !naming::is_valid_id(id) {
+                    return Err(McmodError::Other(format!(
+                        "Invalid content id '{id}' in {MANIFEST_FILE}: use lowercase letters, digits and underscores"
+                    )));
+                }
+                if seen.contains(&id) {
+                    return Err(McmodError::Other(format!("'{id}' is listed twice in {MANIFEST_FILE}")));
+                }
+                seen.push(id);
+            }
+        }
+        for texture in self.particles.iter().chain(&self.sprites).filter_map(|e| e.texture.as_ref()) {
+            let valid = !texture.ends_with(".png")
+                && !texture.starts_with('/')
+                && texture.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-./".contains(c));
+            if !valid {
+                return Err(McmodError::Other(format!(
+                    "Invalid texture '{texture}' in {MANIFEST_FILE}: give the path under textures/ without .png, e.g. effect/spark"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// (atlas, entries) for the sprite lists that have entries.
+    pub fn sprite_groups(&self) -> Vec<(&'static Atlas, &[SpriteEntry])> {
+        [(&PARTICLES_ATLAS, &self.particles), (&GUI_ATLAS, &self.sprites)]
+            .into_iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(atlas, entries)| (atlas, entries.as_slice()))
+            .collect()
+    }
+}
+
+/// `src/main/resources/assets/<mod_id>`.
+pub fn assets_dir(dir: &Path, config: &McmodConfig) -> PathBuf {
+    dir.join("src/main/resources/assets").join(&config.mod_info.mod_id)
+}
+
+/// Append an `[[items]]` or `[[blocks]]` entry to content.toml, creating the
+/// file when there isn't one. Errors when the id is already listed, as an
+/// item or a block: both share the item registry.
+pub fn add_entry(dir: &Path, kind: naming::ContentKind, id: &str, name: Option<&str>) -> Result<()> {
+    if let Some(manifest) = ContentManifest::load(dir)? {
+        if manifest.items.iter().chain(&manifest.blocks).any(|e| e.id == id) {
+            return Err(McmodError::Other(format!("'{id}' is already listed in {MANIFEST_FILE}")));
+        }
+    }
+    let path = dir.join(MANIFEST_FILE);
+    let mut content = if crate::fs::exists(&path) { crate::fs::read_to_string(&path)? } else { String::new() };
+    let trimmed = content.trim_end().len();
+    content.truncate(trimmed);
+    if !content.is_empty() {
+        content.push_str("\n\n");
+    }
+    content.push_str(&format!("[[{}s]]\nid = \"{id}\"\n", kind.as_str()));
+    if let Some(name) = name {
+        content.push_str(&format!("name = {}\n", toml::Value::String(name.to_string())));
+    }
+    crate::util::write_file(&path, &content)
+}
+
+/// Generate the resources for every manifest entry. Returns the paths that
+/// were created or updated, relative to the assets directory.
+pub fn generate(dir: &Path, config: &McmodConfig, manifest: &ContentManifest) -> Result<Vec<String>> {
+    let mod_id = &config.mod_info.mod_id;
+    let assets = assets_dir(dir, config);
+    let mut changed = Vec::new();
+
+    // Translations: names written in the manifest win over hand edits to
+    // en_us.json; derived names are only added when the key is missing.
+    let mut named = Vec::new();
+    let mut derived = Vec::new();
+    for (kind, entries) in [("item", &manifest.items), ("block", &manifest.blocks)] {
+        for entry in entries {
+            let key = format!("{kind}.{mod_id}.{}", entry.id);
+            let value = Json::String(entry.display_name());
+            if entry.name.is_some() { &mut named } else { &mut derived }.push((key, value));
+        }
+    }
+    for (entries, on_conflict) in [(named, OnConflict::Replace), (derived, OnConflict::Keep)] {
+        if entries.is_empty() {
+            continue;
+        }
+        let generated = Json::Object(entries).to_pretty_string()?;
+        let report = json_merge::merge_into_file(&assets.join("lang/en_us.json"), &generated, on_conflict)?;
+        if report.changed() && changed.is_empty() {
+            changed.push("lang/en_us.json".to_string());
+        }
+    }
+
+    for (rel, content) in model_files(config, manifest) {
+        let path = assets.join(&rel);
+        if !crate::fs::exists(&path) {
+            crate::util::write_file(&path, &content)?;
+            changed.push(rel);
+        }
+    }
+
+    for (atlas, entries) in manifest.sprite_groups() {
+        check_atlas(config, atlas)?;
+        let sources: Vec<Json> = entries
+            .iter()
+            .filter_map(|e| e.texture.as_ref())
+            .map(|texture| {
+                Json::Object(vec![
+                    ("type".to_string(), Json::String("minecraft:single".to_string())),
+                    ("resource".to_string(), Json::String(format!("{mod_id}:{texture}"))),
+                ])
+            })
+            .collect();
+        if sources.is_empty() {
+            continue;
+        }
+        let generated = Json::Object(vec![("sources".to_string(), Json::Array(sources))]).to_pretty_string()?;
+        let rel = format!("../minecraft/atlases/{}.json", atlas.name);
+        let report = json_merge::merge_into_file(&assets.join(&rel), &generated, OnConflict::Keep)?;
+        if report.changed() {
+            changed.push(rel);
+        }
+    }
+    Ok(changed)
+}
+
+/// Error when the oldest target predates `atlas`.
+pub fn check_atlas(config: &McmodConfig, atlas: &Atlas) -> Result<()> {
+    let oldest = config.versions.targets.iter().map(|t| t.minecraft.as_str()).min_by(|a, b| {
+        crate::version_meta::compare_versions(a, b)
+    });
+    match oldest {
+        Some(oldest) if crate::version_meta::compare_versions(oldest, atlas.since).is_lt() => {
+            Err(McmodError::Other(format!(
+                "{} in {MANIFEST_FILE} need Minecraft {}+ (the oldest target is {oldest})",
+                atlas.label, atlas.since
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Default model, blockstate, item definition, and particle definition JSON
+/// for every manifest entry, as (path relative to the assets directory, content).
+pub fn model_files(config: &McmodConfig, manifest: &ContentManifest) -> Vec<(String, String)> {
+    let mod_id = &config.mod_info.mod_id;
+    let item_definitions = config.versions.targets.iter().any(|t| {
+        crate::version_meta::compare_versions(&t.max_minecraft, ITEM_DEFINITIONS_SINCE).is_ge()
+    });
+    let mut files = Vec::new();
+    for item in &manifest.items {
+        let id = &item.id;
+        files.push((
+            format!("models/item/{id}.json"),
+            format!("{{\n  \"parent\": \"minecraft:item/generated\",\n  \"textures\": {{\n    \"layer0\": \"{mod_id}:item/{id}\"\n  }}\n}}\n"),
+        ));
+        if item_definitions {
+            files.push((format!("items/{id}.json"), item_definition(mod_id, &format!("item/{id}"))));
+        }
+    }
+    for block in &manifest.blocks {
+        let id = &block.id;
+        files.push((
+            format!("blockstates/{id}.json"),
+            format!("{{\n  \"variants\": {{\n    \"\": {{\n      \"model\": \"{mod_id}:block/{id}\"\n    }}\n  }}\n}}\n"),
+        ));
+        files.push((
+            format!("models/block/{id}.json"),
+            format!("{{\n  \"parent\": \"minecraft:block/cube_all\",\n  \"textures\": {{\n    \"all\": \"{mod_id}:block/{id}\"\n  }}\n}}\n"),
+        ));
+        files.push((
+            format!("models/item/{id}.json"),
+            format!("{{\n  \"parent\": \"{mod_id}:block/{id}\"\n}}\n"),
+        ));
+        if item_definitions {
+            files.push((format!("items/{id}.json"), item_definition(mod_id, &format!("block/{id}"))));
+        }
+    }
+    for particle in &manifest.particles {
+        files.push((
+            format!("particles/{}.json", particle.id),
+            format!("{{\n  \"textures\": [\n    \"{}\"\n  ]\n}}\n", particle.sprite(mod_id)),
+        ));
+    }
+    files
+}
+
+/// 1.21.4+ item definition pointing at a model.
+fn item_definition(mod_id: &str, model: &str) -> String {
+    format!("{{\n  \"model\": {{\n    \"type\": \"minecraft:model\",\n    \"model\": \"{mod_id}:{model}\"\n  }}\n}}\n")
+}
+
+/// A problem found by [`validate`], with the file it's in.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// Path relative to the assets directory.
+    pub path: String,
+    pub message: String,
+}
+
+/// Check the mod's lang, model, blockstate and item definition JSON: every file
+/// parses without duplicate keys, other languages translate every `en_us` key,
+/// and textures the models reference from this mod's namespace exist.
+pub fn validate(dir: &Path, config: &McmodConfig) -> Vec<Problem> {
+    let mod_id = &config.mod_info.mod_id;
+    let assets = assets_dir(dir, config);
+    let mut problems = Vec::new();
+    let mut parsed = Vec::new();
+
+    for sub in ASSET_DIRS {
+        for path in json_files(&assets.join(sub)) {
+            let rel = path.strip_prefix(&assets).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            let json = std::fs::read_to_string(&path)
+                .map_err(McmodError::from)
+                .and_then(|content| Json::parse(&content));
+            match json {
+                Ok(json) => parsed.push((rel, json)),
+                Err(e) => problems.push(Problem { path: rel, message: e.to_string() }),
+            }
+        }
+    }
+
+    let keys = |json: &Json| -> Vec<String> {
+        match json {
+            Json::Object(entries) => entries.iter().map(|(k, _)| k.clone()).collect(),
+            _ => Vec::new(),
+        }
+    };
+    let english = parsed.iter().find(|(rel, _)| rel == "lang/en_us.json").map(|(_, j)| keys(j));
+    if let Some(english) = &english {
+        for (rel, json) in parsed.iter().filter(|(rel, _)| rel.starts_with("lang/") && rel != "lang/en_us.json") {
+            let translated = keys(json);
+            let missing = english.iter().filter(|k| !translated.contains(k)).count();
+            if missing > 0 {
+                problems.push(Problem {
+                    path: rel.clone(),
+                    message: format!("{missing} key(s) from en_us.json are not translated"),
+                });
+            }
+        }
+    }
+
+    for (rel, json) in parsed.iter().filter(|(rel, _)| rel.starts_with("models/")) {
+        let Json::Object(entries) = json else { continue };
+        let Some((_, Json::Object(textures))) = entries.iter().find(|(k, _)| k == "textures") else {
+            continue;
+        };
+        for (_, texture) in textures {
+            let Json::String(texture) = texture else { continue };
+            let Some(texture_path) = texture.strip_prefix(&format!("{mod_id}:")) else {
+                continue;
+            };
+            if !assets.join("textures").join(format!("{texture_path}.png")).exists() {
+                problems.push(Problem {
+                    path: rel.clone(),
+                    message: format!("texture {texture} is missing (textures/{texture_path}.png)"),
+                });
+            }
+        }
+    }
+
+    if let Ok(Some(manifest)) = ContentManifest::load(dir) {
+        for (atlas, entries) in manifest.sprite_groups() {
+            for entry in entries {
+                let texture = entry.texture_path(atlas);
+                if !assets.join(format!("textures/{texture}.png")).exists() {
+                    problems.push(Problem {
+                        path: MANIFEST_FILE.to_string(),
+                        message: format!("texture for {} is missing (textures/{texture}.png)", entry.sprite(mod_id)),
+                    });
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Every `.json` file under `dir`, recursively, in a stable order.
+pub fn json_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(json_files(&path));
+        } else if path.extension().is_some_and(|e| e == "json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
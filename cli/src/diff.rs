@@ -0,0 +1,291 @@
+use crate::error::{McmodError, Result};
+use colored::Colorize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Lines of unchanged context shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, PartialEq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Line-level diff via longest common subsequence. Inputs are small
+/// (build scripts, metadata files) so the quadratic table is fine.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| Op::Delete(l)));
+    ops.extend(new[j..].iter().map(|l| Op::Insert(l)));
+    ops
+}
+
+/// Produce a unified diff of `old` → `new` labelled with `label`.
+/// Returns an empty string when the contents are identical.
+pub fn unified_diff(old: &str, new: &str, label: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    // Group changed op indices into hunk ranges, merging nearby changes
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (idx, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Equal(_)) {
+            continue;
+        }
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + CONTEXT + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- a/{label}\n+++ b/{label}\n");
+    if ranges.is_empty() {
+        // Only a trailing-newline difference
+        out.push_str("@@ end of file @@\n\\ trailing newline changed\n");
+        return out;
+    }
+
+    for (start, end) in ranges {
+        // Line numbers (1-based) at the start of the hunk
+        let old_start = 1 + ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, Op::Insert(_)))
+            .count();
+        let new_start = 1 + ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, Op::Delete(_)))
+            .count();
+        let hunk = &ops[start..end];
+        let old_count = hunk.iter().filter(|op| !matches!(op, Op::Insert(_))).count();
+        let new_count = hunk.iter().filter(|op| !matches!(op, Op::Delete(_))).count();
+
+        out.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for op in hunk {
+            match op {
+                Op::Equal(l) => out.push_str(&format!(" {l}\n")),
+                Op::Delete(l) => out.push_str(&format!("-{l}\n")),
+                Op::Insert(l) => out.push_str(&format!("+{l}\n")),
+            }
+        }
+    }
+    out
+}
+
+/// Print a unified diff with colors (additions green, removals red, hunks cyan).
+pub fn print_colored(diff: &str) {
+    for line in diff.lines() {
+        let styled = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else {
+            line.dimmed()
+        };
+        println!("    {styled}");
+    }
+}
+
+/// Show a colored diff for an in-place edit of an existing file and write it.
+///
+/// In an interactive terminal the user must confirm the change; declining aborts
+/// the operation with an error so callers don't record a half-applied feature.
+/// Non-interactive runs print the diff and apply it. Returns false when the file
+/// was already up to date. In memory (tests, previews) the edit is just written;
+/// a preview prints all its changes at the end.
+pub fn apply_edit(path: &Path, old: &str, new: &str) -> Result<bool> {
+    if old == new {
+        return Ok(false);
+    }
+    crate::conflict::record_edit(path, old, new);
+    if crate::fs::is_in_memory() {
+        crate::util::write_file(path, new)?;
+        return Ok(true);
+    }
+    let label = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    println!("{}", format!("  Changes to {}:", path.display()).cyan());
+    print_colored(&unified_diff(old, new, &label));
+
+    if std::io::stdin().is_terminal() {
+        let accepted = dialoguer::Confirm::new()
+            .with_prompt("  Apply this change?")
+            .default(true)
+            .interact()
+            .map_err(|e| McmodError::Other(e.to_string()))?;
+        if !accepted {
+            return Err(McmodError::Other(format!(
+                "Aborted — change to {} was not applied",
+                path.display()
+            )));
+        }
+    }
+
+    crate::util::write_file(path, new)?;
+    Ok(true)
+}
+
+/// [`apply_edit`] without the diff or the confirmation, for commands whose
+/// stdout must stay machine-readable (`--json`). Returns false when the file
+/// was already up to date.
+pub fn apply_edit_quietly(path: &Path, old: &str, new: &str) -> Result<bool> {
+    if old == new {
+        return Ok(false);
+    }
+    crate::conflict::record_edit(path, old, new);
+    crate::util::write_file(path, new)?;
+    Ok(true)
+}
+
+/// Run `f` (a command on the project in `root`) as a preview: nothing is
+/// written, and the files it would create, modify, or delete are printed as
+/// colored diffs, or just listed with `stat`.
+pub fn preview(root: &Path, stat: bool, f: impl FnOnce() -> Result<()>) -> Result<()> {
+    let (result, changes) = crate::fs::preview(f);
+    result?;
+    print_changes(root, &changes, stat);
+    Ok(())
+}
+
+/// Print what a preview run would change, relative to `root`.
+pub fn print_changes(root: &Path, changes: &[crate::fs::FileChange], stat: bool) {
+    println!();
+    if changes.is_empty() {
+        println!("{}", "  No files would change.".green());
+        return;
+    }
+    // Created, modified, deleted
+    let mut counts = [0; 3];
+    for change in changes {
+        let rel = change.path.strip_prefix(root).unwrap_or(&change.path);
+        let label = rel.to_string_lossy().replace('\\', "/");
+        let (verb, slot) = match (&change.before, &change.after) {
+            (None, _) => ("create", 0),
+            (_, None) => ("delete", 2),
+            _ => ("modify", 1),
+        };
+        counts[slot] += 1;
+
+        fn text(bytes: Option<&[u8]>) -> Option<&str> {
+            std::str::from_utf8(bytes.unwrap_or_default()).ok()
+        }
+        let diff = match (text(change.before.as_deref()), text(change.after.as_deref())) {
+            (Some(old), Some(new)) => Some(unified_diff(old, new, &label)),
+            _ => None,
+        };
+        if stat {
+            let detail = match &diff {
+                Some(diff) => {
+                    let count = |prefix: char| {
+                        diff.lines()
+                            .filter(|l| l.starts_with(prefix) && !l.starts_with("+++") && !l.starts_with("---"))
+                            .count()
+                    };
+                    format!("+{} -{}", count('+'), count('-'))
+                }
+                None => "binary".to_string(),
+            };
+            println!("  {verb:<7} {label}  {}", detail.dimmed());
+        } else if let Some(diff) = diff {
+            println!("{}", format!("  Would {verb} {label}:").cyan());
+            print_colored(&diff);
+        } else {
+            println!("{}", format!("  Would {verb} {label} (binary file)").cyan());
+        }
+    }
+    let [created, modified, deleted] = counts;
+    println!(
+        "\n{}",
+        format!("  {created} to create, {modified} to modify, {deleted} to delete. Nothing was written.").bold()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_identical() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "f"), "");
+    }
+
+    #[test]
+    fn test_unified_diff_replace_line() {
+        let old = "mod_id=test\nmod_version=1.0.0\nfoo=bar\n";
+        let new = "mod_id=test\nmod_version=2.0.0\nfoo=bar\n";
+        let diff = unified_diff(old, new, "gradle.properties");
+        assert_eq!(
+            diff,
+            "--- a/gradle.properties\n+++ b/gradle.properties\n\
+             @@ -1,3 +1,3 @@\n mod_id=test\n-mod_version=1.0.0\n+mod_version=2.0.0\n foo=bar\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_append() {
+        let diff = unified_diff("a\n", "a\nb\n", "f");
+        assert!(diff.contains("@@ -1,1 +1,2 @@"));
+        assert!(diff.contains("\n+b\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_separate_hunks() {
+        let old: String = (1..=20).map(|i| format!("line{i}\n")).collect();
+        let new = old.replace("line2\n", "LINE2\n").replace("line19\n", "LINE19\n");
+        let diff = unified_diff(&old, &new, "f");
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,5 @@"));
+        assert!(diff.contains("@@ -16,5 +16,5 @@"));
+    }
+
+    #[test]
+    fn test_diff_lines_ops() {
+        let ops = diff_lines(&["a", "b", "c"], &["a", "c", "d"]);
+        assert_eq!(
+            ops,
+            vec![Op::Equal("a"), Op::Delete("b"), Op::Equal("c"), Op::Insert("d")]
+        );
+    }
+}
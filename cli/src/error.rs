@@ -14,6 +14,12 @@ pub enum McmodError {
     #[error("mcmod.toml not found — run `mcmod init` first")]
     ConfigNotFound,
 
+    #[error("This project requires mcmod {required}, but this is mcmod {current}")]
+    NewerMcmodRequired { required: String, current: String },
+
+    #[error("Refusing to write '{path}': {reason}")]
+    UnsafePath { path: String, reason: String },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -26,11 +32,151 @@ pub enum McmodError {
     #[error("HTTP error: {0}")]
     Http(String),
 
+    #[error("Offline mode is on; not contacting {0}")]
+    Offline(String),
+
     #[error("JSON parse error: {0}")]
     Json(#[from] serde_json::Error),
 
     #[error("{0}")]
     Other(String),
+
+    /// A lower-level error wrapped with what mcmod was doing at the time.
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<McmodError>,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, McmodError>;
+
+pub const ISSUE_URL: &str = "https://github.com/jhughes-dev/Minecraft-Mod-Starter/issues";
+
+impl McmodError {
+    /// Wrap the error with the operation that failed (which file, URL, or step).
+    pub fn context(self, context: impl Into<String>) -> McmodError {
+        McmodError::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The innermost error, past any context layers.
+    pub fn root(&self) -> &McmodError {
+        match self {
+            McmodError::Context { source, .. } => source.root(),
+            e => e,
+        }
+    }
+
+    /// The context layers from outermost to innermost, ending with the root
+    /// error's own message.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut current = self;
+        while let McmodError::Context { context, source } = current {
+            chain.push(context.clone());
+            current = source;
+        }
+        chain.push(current.to_string());
+        chain
+    }
+
+    /// A short "how to fix" for the root cause, when there's a likely one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self.root() {
+            McmodError::Io(e) => match e.kind() {
+                std::io::ErrorKind::NotFound => {
+                    Some("Check the path exists, or pass --dir to point at the project")
+                }
+                std::io::ErrorKind::PermissionDenied => Some(
+                    "Check the file permissions, and that no IDE, Gradle daemon, or running game holds the file open",
+                ),
+                std::io::ErrorKind::AlreadyExists => Some("Move or delete the existing file, then run the command again"),
+                _ => None,
+            },
+            McmodError::TomlDeserialize(_) => {
+                Some("Fix the TOML syntax at the position shown above")
+            }
+            McmodError::Http(_) => {
+                Some("Check your network connection and try again; the service may be briefly unavailable")
+            }
+            McmodError::NewerMcmodRequired { .. } => {
+                Some("Run `mcmod update` to install the latest mcmod, then run the command again")
+            }
+            McmodError::Offline(_) => Some(
+                "Run without --offline, and unset MCMOD_OFFLINE or `mcmod config set offline false`, to allow network access",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Errors that point at a bug in mcmod rather than at the input or the
+    /// environment; these get issue-report instructions.
+    pub fn is_unexpected(&self) -> bool {
+        match self.root() {
+            McmodError::TomlSerialize(_) | McmodError::Json(_) => true,
+            McmodError::Io(_) => self.hint().is_none(),
+            _ => false,
+        }
+    }
+
+    /// Instructions for reporting an unexpected error.
+    pub fn report_instructions() -> String {
+        format!("This looks like a bug in mcmod. Please report it at {ISSUE_URL} with the command you ran and this output, and attach the bundle `mcmod about --zip` writes.")
+    }
+}
+
+/// Attach context to fallible results, e.g.
+/// `fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))`.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T>;
+    fn with_context(self, context: impl FnOnce() -> String) -> Result<T>;
+}
+
+impl<T, E: Into<McmodError>> Context<T> for std::result::Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context(self, context: impl FnOnce() -> String) -> Result<T> {
+        self.map_err(|e| e.into().context(context()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permission_denied() -> Result<()> {
+        Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into())
+    }
+
+    #[test]
+    fn test_context_chain() {
+        let err = permission_denied()
+            .context("Writing src/main/resources/fabric.mod.json")
+            .context("Writing resource metadata")
+            .unwrap_err();
+        let chain = err.chain();
+        assert_eq!(chain[0], "Writing resource metadata");
+        assert_eq!(chain[1], "Writing src/main/resources/fabric.mod.json");
+        assert!(chain[2].starts_with("IO error: permission denied"));
+        assert!(err.to_string().starts_with("Writing resource metadata: Writing src/main/resources/fabric.mod.json: IO error"));
+        assert!(matches!(err.root(), McmodError::Io(_)));
+        assert!(err.hint().unwrap().contains("permissions"));
+        assert!(!err.is_unexpected());
+    }
+
+    #[test]
+    fn test_unexpected_errors() {
+        let err: McmodError = std::io::Error::other("boom").into();
+        assert!(err.hint().is_none());
+        assert!(err.context("Writing x").is_unexpected());
+        assert!(!McmodError::ConfigNotFound.is_unexpected());
+        assert!(!McmodError::Http("timeout".to_string()).is_unexpected());
+        assert!(McmodError::report_instructions().contains(ISSUE_URL));
+    }
+}
@@ -0,0 +1,148 @@
+//! Line-ending and permission policy applied to every generated file.
+//!
+//! `util::write_file` and `util::write_binary` look up a [`FilePolicy`] by path,
+//! so rules like "batch files use CRLF" and "shell scripts are executable" live
+//! in one place instead of at each call site. Callers with special needs pass an
+//! explicit policy to `write_file_with` / `write_binary_with`.
+
+use crate::error::{McmodError, Result};
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Parse a config value: `lf`, `crlf`, or `native` (CRLF on Windows).
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::Crlf),
+            "native" if cfg!(windows) => Ok(LineEnding::Crlf),
+            "native" => Ok(LineEnding::Lf),
+            _ => Err(McmodError::Other(format!(
+                "Invalid line ending '{value}': must be 'lf', 'crlf', or 'native'"
+            ))),
+        }
+    }
+}
+
+/// User-wide default for text files, set once at startup from the global config.
+static DEFAULT_LINE_ENDING: OnceLock<LineEnding> = OnceLock::new();
+
+/// Set the default line ending for text files. Only the first call has effect.
+pub fn set_default_line_ending(ending: LineEnding) {
+    let _ = DEFAULT_LINE_ENDING.set(ending);
+}
+
+fn default_line_ending() -> LineEnding {
+    DEFAULT_LINE_ENDING.get().copied().unwrap_or(LineEnding::Lf)
+}
+
+/// How a file is written to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePolicy {
+    /// Line ending for text content. Binary writes ignore this.
+    pub line_ending: LineEnding,
+    /// Mark the file executable (mode 0755 on Unix).
+    pub executable: bool,
+}
+
+impl FilePolicy {
+    /// The policy for a path, based on its file name:
+    /// - `.bat` / `.cmd` always use CRLF (cmd.exe misparses LF-only scripts)
+    /// - `gradlew` and `.sh` scripts always use LF and are executable
+    /// - everything else uses the configured default line ending
+    pub fn for_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match (name.as_str(), ext.as_str()) {
+            (_, "bat" | "cmd") => FilePolicy {
+                line_ending: LineEnding::Crlf,
+                executable: false,
+            },
+            ("gradlew", _) | (_, "sh") => FilePolicy {
+                line_ending: LineEnding::Lf,
+                executable: true,
+            },
+            _ => FilePolicy {
+                line_ending: default_line_ending(),
+                executable: false,
+            },
+        }
+    }
+
+    pub fn with_executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    /// Unix mode to apply, or None to keep the existing file's mode.
+    pub fn mode(&self) -> Option<u32> {
+        self.executable.then_some(0o755)
+    }
+
+    /// Convert text to this policy's line ending.
+    pub fn normalize<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        match self.line_ending {
+            LineEnding::Lf if content.contains('\r') => {
+                Cow::Owned(content.replace("\r\n", "\n"))
+            }
+            LineEnding::Crlf if content.contains('\n') => {
+                Cow::Owned(content.replace("\r\n", "\n").replace('\n', "\r\n"))
+            }
+            _ => Cow::Borrowed(content),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_for_path() {
+        let bat = FilePolicy::for_path(Path::new("project/gradlew.bat"));
+        assert_eq!(bat.line_ending, LineEnding::Crlf);
+        assert!(!bat.executable);
+
+        let gradlew = FilePolicy::for_path(Path::new("project/gradlew"));
+        assert_eq!(gradlew.line_ending, LineEnding::Lf);
+        assert!(gradlew.executable);
+
+        assert!(FilePolicy::for_path(Path::new("scripts/run.sh")).executable);
+        assert!(!FilePolicy::for_path(Path::new("build.gradle.kts")).executable);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let crlf = FilePolicy::for_path(Path::new("a.bat"));
+        assert_eq!(crlf.normalize("a\nb\r\nc"), "a\r\nb\r\nc");
+
+        let lf = FilePolicy {
+            line_ending: LineEnding::Lf,
+            ..crlf
+        };
+        assert_eq!(lf.normalize("a\r\nb\n"), "a\nb\n");
+        assert!(matches!(lf.normalize("a\nb\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_parse_line_ending() {
+        assert_eq!(LineEnding::parse("LF").unwrap(), LineEnding::Lf);
+        assert_eq!(LineEnding::parse("crlf").unwrap(), LineEnding::Crlf);
+        assert!(LineEnding::parse("native").is_ok());
+        assert!(LineEnding::parse("cr").is_err());
+    }
+}
@@ -0,0 +1,545 @@
+//! Filesystem access for project files.
+//!
+//! Writes (`util::write_file` and friends), `util::ensure_dir`, the Gradle
+//! editors, and project config loading go through the [`FileSystem`] trait.
+//! Normal runs use [`RealFs`]; tests and `debug render-all` can run a whole
+//! command against an in-memory tree with [`in_memory`] and inspect the files
+//! it produced. [`preview`] layers an in-memory tree over the disk instead, so
+//! a command sees the real project but its writes and deletions stay in
+//! memory; `mcmod diff` and `--diff` print what it would change.
+//! [`batched`] uses the same overlay to collect a large scaffold's writes,
+//! then puts them on disk in parallel.
+
+use crate::error::{Context, McmodError, Result};
+use crate::profile::{self, Phase};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub trait FileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Write `content`, creating parent directories as needed. `mode` sets
+    /// Unix permissions; when None an existing file keeps its mode.
+    fn write(&self, path: &Path, content: &[u8], mode: Option<u32>) -> Result<()>;
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Whether a file or directory exists at `path`.
+    fn exists(&self, path: &Path) -> bool;
+}
+
+/// The real disk.
+pub struct RealFs;
+
+impl FileSystem for RealFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    /// Crash-safe write: the content goes to a temporary file in the same
+    /// directory, is flushed to disk, then renamed over the destination, and
+    /// the directory entry is synced. An interrupted run leaves either the old
+    /// file or the new one, never a truncated mix.
+    fn write(&self, path: &Path, content: &[u8], mode: Option<u32>) -> Result<()> {
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| McmodError::Other(format!("Invalid file path: {}", path.display())))?;
+        let temp = parent.join(format!(
+            ".{}.mcmod-tmp-{}",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+
+        let result = (|| -> Result<()> {
+            let mut file = std::fs::File::create(&temp)?;
+            file.write_all(content)?;
+            set_mode(&file, path, mode)?;
+            file.sync_all()?;
+            drop(file);
+            std::fs::rename(&temp, path)?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp);
+            return result;
+        }
+
+        sync_dir(parent);
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_file(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(file: &std::fs::File, path: &Path, mode: Option<u32>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = match mode {
+        Some(m) => Some(m),
+        None => std::fs::metadata(path).ok().map(|m| m.permissions().mode()),
+    };
+    if let Some(mode) = mode {
+        file.set_permissions(std::fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_file: &std::fs::File, _path: &Path, _mode: Option<u32>) -> Result<()> {
+    Ok(())
+}
+
+/// Persist a rename by syncing the containing directory. Best effort: not all
+/// platforms and filesystems allow opening a directory for sync.
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    if let Ok(d) = std::fs::File::open(dir) {
+        let _ = d.sync_all();
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+pub use memory::{in_memory, preview, FileChange};
+
+fn with_fs<R>(f: impl FnOnce(&dyn FileSystem) -> R) -> R {
+    match memory::current() {
+        Some(fs) => f(&*fs),
+        None => f(&RealFs),
+    }
+}
+
+/// Whether writes go to an in-memory tree (or a preview overlay) rather than
+/// the disk, in which case there is nothing to confirm before writing.
+pub fn is_in_memory() -> bool {
+    memory::current().is_some()
+}
+
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    with_fs(|fs| fs.read(path))
+}
+
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write(path: &Path, content: &[u8], mode: Option<u32>) -> Result<()> {
+    with_fs(|fs| fs.write(path, content, mode))
+}
+
+pub fn create_dir_all(path: &Path) -> io::Result<()> {
+    with_fs(|fs| fs.create_dir_all(path))
+}
+
+pub fn remove_file(path: &Path) -> io::Result<()> {
+    with_fs(|fs| fs.remove_file(path))
+}
+
+pub fn exists(path: &Path) -> bool {
+    with_fs(|fs| fs.exists(path))
+}
+
+/// Most threads [`batched`] writes with; past this the disk is the limit.
+const MAX_WRITE_THREADS: usize = 8;
+
+/// Run `f` with its writes and deletions held in memory over the disk (reads
+/// see them, as in [`preview`]), then apply them: deletions and directories
+/// first, then the files on a bounded pool of threads. Files are reported in
+/// path order whichever thread wrote them, so logs and `--json` events are
+/// the same from run to run. Nothing is written if `f` fails. Inside
+/// [`in_memory`] or [`preview`], `f` just runs against that tree.
+pub fn batched<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
+    if is_in_memory() {
+        return f();
+    }
+    let (result, fs) = memory::overlay(f);
+    let result = result?;
+    let plan = fs.plan();
+
+    for path in &plan.removed {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(McmodError::from(e).context(format!("Removing {}", path.display())));
+            }
+            _ => {}
+        }
+    }
+    for dir in &plan.dirs {
+        std::fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+    }
+    let results = profile::time(
+        Phase::Write,
+        || format!("{} files in parallel", plan.files.len()),
+        || write_parallel(&plan.files),
+    );
+    let mut first_error = None;
+    for ((path, content, _), written) in plan.files.iter().zip(results) {
+        match written {
+            Ok(()) => crate::progress::file_written(path, content.len()),
+            Err(e) if first_error.is_none() => first_error = Some(e.context(format!("Writing {}", path.display()))),
+            Err(_) => {}
+        }
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Write each file to the disk with up to [`MAX_WRITE_THREADS`] threads
+/// taking the next one in turn; the results line up with `files`.
+fn write_parallel(files: &[memory::PlannedWrite]) -> Vec<Result<()>> {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .clamp(1, MAX_WRITE_THREADS)
+        .min(files.len());
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<Result<()>>> = files.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((path, content, mode)) = files.get(i) else {
+                            return done;
+                        };
+                        done.push((i, RealFs.write(path, content, *mode)));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (i, result) in worker.join().expect("file writer thread panicked") {
+                results[i] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err(McmodError::Other("File was not written".to_string()))))
+        .collect()
+}
+
+mod memory {
+    use super::FileSystem;
+    use crate::error::Result;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::rc::Rc;
+
+    /// File content and its Unix mode, if one was set.
+    type Entry = (Vec<u8>, Option<u32>);
+
+    /// A file [`super::batched`] will write: path, content, and mode.
+    pub type PlannedWrite = (PathBuf, Vec<u8>, Option<u32>);
+
+    /// What an overlay run leaves to apply to the disk, each list in path order.
+    pub struct Plan {
+        pub removed: Vec<PathBuf>,
+        pub dirs: Vec<PathBuf>,
+        pub files: Vec<PlannedWrite>,
+    }
+
+    /// A file tree held in memory. Directories are implied by the files in
+    /// them, plus any created explicitly.
+    #[derive(Default)]
+    pub struct MemoryFs {
+        files: RefCell<BTreeMap<PathBuf, Entry>>,
+        dirs: RefCell<BTreeSet<PathBuf>>,
+        /// Whether paths not written in memory are read from the disk.
+        overlay: bool,
+        /// Disk files removed in the overlay.
+        removed: RefCell<BTreeSet<PathBuf>>,
+    }
+
+    /// A file a [`preview`] run would create (`before` is None), modify, or
+    /// delete (`after` is None).
+    #[derive(Debug, PartialEq)]
+    pub struct FileChange {
+        pub path: PathBuf,
+        pub before: Option<Vec<u8>>,
+        pub after: Option<Vec<u8>>,
+    }
+
+    impl MemoryFs {
+        fn on_disk(&self, path: &Path) -> bool {
+            self.overlay && !self.removed.borrow().contains(path) && path.exists()
+        }
+
+        /// The overlay's writes and deletions that differ from the disk, by path.
+        pub fn changes(&self) -> Vec<FileChange> {
+            let mut changes: Vec<FileChange> = self
+                .files
+                .borrow()
+                .iter()
+                .filter_map(|(path, (content, _))| {
+                    let before = std::fs::read(path).ok();
+                    (before.as_ref() != Some(content)).then(|| FileChange {
+                        path: path.clone(),
+                        before,
+                        after: Some(content.clone()),
+                    })
+                })
+                .collect();
+            for path in self.removed.borrow().iter() {
+                if let Ok(before) = std::fs::read(path) {
+                    changes.push(FileChange { path: path.clone(), before: Some(before), after: None });
+                }
+            }
+            changes.sort_by(|a, b| a.path.cmp(&b.path));
+            changes
+        }
+
+        /// Everything the overlay holds, unchanged files included, so that
+        /// modes set in memory are applied too.
+        pub fn plan(&self) -> Plan {
+            Plan {
+                removed: self.removed.borrow().iter().cloned().collect(),
+                dirs: self.dirs.borrow().iter().cloned().collect(),
+                files: self
+                    .files
+                    .borrow()
+                    .iter()
+                    .map(|(path, (content, mode))| (path.clone(), content.clone(), *mode))
+                    .collect(),
+            }
+        }
+    }
+
+    impl MemoryFs {
+        /// Paths of all files under `root`, relative to it, in sorted order.
+        pub fn tree(&self, root: &Path) -> Vec<String> {
+            self.files
+                .borrow()
+                .keys()
+                .filter_map(|p| p.strip_prefix(root).ok())
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .collect()
+        }
+
+        #[cfg(test)]
+        pub fn read_string(&self, path: &Path) -> Option<String> {
+            let files = self.files.borrow();
+            files.get(path).map(|(c, _)| String::from_utf8_lossy(c).into_owned())
+        }
+
+        pub fn mode(&self, path: &Path) -> Option<u32> {
+            self.files.borrow().get(path).and_then(|(_, m)| *m)
+        }
+    }
+
+    impl FileSystem for MemoryFs {
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            if let Some((content, _)) = self.files.borrow().get(path) {
+                return Ok(content.clone());
+            }
+            if self.on_disk(path) {
+                return std::fs::read(path);
+            }
+            Err(io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+        }
+
+        fn write(&self, path: &Path, content: &[u8], mode: Option<u32>) -> Result<()> {
+            let mut files = self.files.borrow_mut();
+            let mode = mode.or_else(|| files.get(path).and_then(|(_, m)| *m));
+            files.insert(path.to_path_buf(), (content.to_vec(), mode));
+            self.removed.borrow_mut().remove(path);
+            Ok(())
+        }
+
+        fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+            self.dirs.borrow_mut().insert(path.to_path_buf());
+            Ok(())
+        }
+
+        fn remove_file(&self, path: &Path) -> io::Result<()> {
+            let in_memory = self.files.borrow_mut().remove(path).is_some();
+            let on_disk = self.on_disk(path);
+            if on_disk {
+                self.removed.borrow_mut().insert(path.to_path_buf());
+            }
+            if in_memory || on_disk {
+                Ok(())
+            } else {
+                Err(io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+            }
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.files.borrow().keys().any(|p| p.starts_with(path))
+                || self.dirs.borrow().iter().any(|d| d.starts_with(path))
+                || self.on_disk(path)
+        }
+    }
+
+    thread_local! {
+        static CURRENT: RefCell<Option<Rc<MemoryFs>>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn current() -> Option<Rc<MemoryFs>> {
+        CURRENT.with(|c| c.borrow().clone())
+    }
+
+    /// Run `f` with this thread's project file access redirected to a fresh
+    /// in-memory tree, and return its result along with the tree.
+    pub fn in_memory<R>(f: impl FnOnce() -> R) -> (R, Rc<MemoryFs>) {
+        run_with(MemoryFs::default(), f)
+    }
+
+    /// Run `f` against the disk with its writes and deletions kept in
+    /// memory, and return its result along with what it would change.
+    pub fn preview<R>(f: impl FnOnce() -> R) -> (R, Vec<FileChange>) {
+        let (result, fs) = overlay(f);
+        (result, fs.changes())
+    }
+
+    /// Run `f` against an overlay of the disk and return it for
+    /// [`super::batched`] to apply.
+    pub(super) fn overlay<R>(f: impl FnOnce() -> R) -> (R, Rc<MemoryFs>) {
+        run_with(MemoryFs { overlay: true, ..MemoryFs::default() }, f)
+    }
+
+    fn run_with<R>(fs: MemoryFs, f: impl FnOnce() -> R) -> (R, Rc<MemoryFs>) {
+        struct Reset;
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                CURRENT.with(|c| *c.borrow_mut() = None);
+            }
+        }
+
+        let fs = Rc::new(fs);
+        CURRENT.with(|c| *c.borrow_mut() = Some(fs.clone()));
+        let _reset = Reset;
+        (f(), fs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_fs_roundtrip() {
+        let root = Path::new("/memory/project");
+        let (result, fs) = in_memory(|| {
+            write(&root.join("a/b.txt"), b"hello", None)?;
+            write(&root.join("gradlew"), b"#!/bin/sh", Some(0o755))?;
+            write(&root.join("gradlew"), b"#!/bin/sh\n", None)?;
+            assert!(exists(&root.join("a")));
+            assert!(!exists(&root.join("c")));
+            read_to_string(&root.join("a/b.txt")).map_err(McmodError::from)
+        });
+        assert_eq!(result.unwrap(), "hello");
+        assert_eq!(fs.tree(root), vec!["a/b.txt", "gradlew"]);
+        assert_eq!(fs.mode(&root.join("gradlew")), Some(0o755));
+        assert!(!is_in_memory());
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_batched_writes_to_disk() {
+        let dir = std::env::temp_dir().join(format!("mcmod_fs_batched_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("gone.txt"), "bye").unwrap();
+
+        let result = batched(|| -> Result<String> {
+            for i in 0..40 {
+                write(&dir.join(format!("src/file{i}.txt")), i.to_string().as_bytes(), None)?;
+            }
+            write(&dir.join("gradlew"), b"#!/bin/sh\n", Some(0o755))?;
+            create_dir_all(&dir.join("run/mods"))?;
+            remove_file(&dir.join("gone.txt"))?;
+            // Nothing is on disk until the batch ends, but reads see the writes
+            assert!(!dir.join("src").exists());
+            Ok(read_to_string(&dir.join("src/file7.txt"))?)
+        });
+        assert_eq!(result.unwrap(), "7");
+        assert!(!is_in_memory());
+        assert_eq!(std::fs::read_to_string(dir.join("src/file39.txt")).unwrap(), "39");
+        assert!(dir.join("run/mods").is_dir());
+        assert!(!dir.join("gone.txt").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.join("gradlew")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+
+        // A failing batch writes nothing
+        let failed = batched(|| -> Result<()> {
+            write(&dir.join("partial.txt"), b"x", None)?;
+            Err(McmodError::Other("boom".to_string()))
+        });
+        assert!(failed.is_err());
+        assert!(!dir.join("partial.txt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_preview_overlays_disk() {
+        let dir = std::env::temp_dir().join(format!("mcmod_fs_preview_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("kept.txt"), "same").unwrap();
+        std::fs::write(dir.join("edited.txt"), "old").unwrap();
+        std::fs::write(dir.join("gone.txt"), "bye").unwrap();
+
+        let (result, changes) = preview(|| -> Result<String> {
+            write(&dir.join("kept.txt"), b"same", None)?;
+            let old = read_to_string(&dir.join("edited.txt"))?;
+            write(&dir.join("edited.txt"), format!("{old} new").as_bytes(), None)?;
+            write(&dir.join("new/file.txt"), b"hi", None)?;
+            remove_file(&dir.join("gone.txt"))?;
+            assert!(!exists(&dir.join("gone.txt")));
+            assert!(remove_file(&dir.join("missing.txt")).is_err());
+            Ok(read_to_string(&dir.join("edited.txt"))?)
+        });
+        assert_eq!(result.unwrap(), "old new");
+        let summary: Vec<(String, bool, bool)> = changes
+            .iter()
+            .map(|c| {
+                let name = c.path.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/");
+                (name, c.before.is_some(), c.after.is_some())
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("edited.txt".to_string(), true, true),
+                ("gone.txt".to_string(), true, false),
+                ("new/file.txt".to_string(), false, true),
+            ]
+        );
+        // Nothing reached the disk
+        assert_eq!(std::fs::read_to_string(dir.join("edited.txt")).unwrap(), "old");
+        assert!(dir.join("gone.txt").exists());
+        assert!(!dir.join("new").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,111 @@
+//! Git repositories around a directory `mcmod init` is about to fill.
+//!
+//! A project created inside another repository is easy to get wrong: the
+//! parent may ignore the path, so nothing gets committed, or have a Gradle
+//! build of its own above it. [`enclosing_repo`] finds the repository and
+//! those problems; init warns about them and asks how to nest the project.
+
+use crate::error::{McmodError, Result};
+use clap::ValueEnum;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// How `mcmod init` places a project inside an existing repository.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum NestedMode {
+    /// Files belong to the enclosing repository
+    Subdirectory,
+    /// The project gets its own repository (git init), to add as a submodule
+    Submodule,
+}
+
+/// The repository enclosing a directory, and what about it affects a new
+/// project there.
+#[derive(Debug, PartialEq)]
+pub struct EnclosingRepo {
+    pub root: PathBuf,
+    /// The directory relative to `root`, with `/` separators.
+    pub relative: String,
+    /// Whether the repository's ignore rules cover the directory.
+    pub ignored: bool,
+    /// A settings.gradle(.kts) between the directory and `root`: a Gradle
+    /// build the new project would sit inside.
+    pub gradle_settings: Option<PathBuf>,
+}
+
+/// The Git repository containing `dir`, if any. A repository at `dir`
+/// itself doesn't count; the project is then its own repository already.
+pub fn enclosing_repo(dir: &Path) -> Option<EnclosingRepo> {
+    let dir = std::path::absolute(dir).ok()?;
+    let root = dir.ancestors().skip(1).find(|a| a.join(".git").exists())?.to_path_buf();
+    let relative = dir.strip_prefix(&root).ok()?.to_string_lossy().replace('\\', "/");
+    let gradle_settings = dir
+        .ancestors()
+        .skip(1)
+        .take_while(|a| a.starts_with(&root))
+        .flat_map(|a| [a.join("settings.gradle.kts"), a.join("settings.gradle")])
+        .find(|p| p.is_file());
+    let ignored = is_ignored(&root, &relative);
+    Some(EnclosingRepo { root, relative, ignored, gradle_settings })
+}
+
+/// Whether `relative` (a directory, which need not exist yet) is ignored in
+/// the repository at `root`. False when git can't be run.
+fn is_ignored(root: &Path, relative: &str) -> bool {
+    Command::new("git")
+        .args(["check-ignore", "-q", &format!("{relative}/")])
+        .current_dir(root)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Make `dir` a repository of its own with `git init`.
+pub fn init(dir: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| McmodError::Other(format!("Could not run git: {e}")))?;
+    if !output.status.success() {
+        return Err(McmodError::Other(format!(
+            "git init failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        Command::new("git").args(args).current_dir(dir).output().is_ok_and(|o| o.status.success())
+    }
+
+    #[test]
+    fn test_enclosing_repo() {
+        let root = std::env::temp_dir().join(format!("mcmod_git_repo_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("tools")).unwrap();
+        if !git(&root, &["init", "-q"]) {
+            return; // git isn't installed
+        }
+        std::fs::write(root.join(".gitignore"), "scratch/\n").unwrap();
+        std::fs::write(root.join("tools/settings.gradle.kts"), "").unwrap();
+
+        let repo = enclosing_repo(&root.join("mods/mymod")).unwrap();
+        assert_eq!(repo.root, root);
+        assert_eq!(repo.relative, "mods/mymod");
+        assert!(!repo.ignored);
+        assert_eq!(repo.gradle_settings, None);
+
+        assert!(enclosing_repo(&root.join("scratch")).unwrap().ignored);
+        let nested = enclosing_repo(&root.join("tools/mymod")).unwrap();
+        assert_eq!(nested.gradle_settings, Some(root.join("tools/settings.gradle.kts")));
+
+        // A repository at the directory itself is the project's own
+        assert!(enclosing_repo(&root).is_none_or(|r| r.root != root));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
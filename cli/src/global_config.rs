@@ -1,11 +1,14 @@
-use crate::error::{McmodError, Result};
+use crate::error::{Context, McmodError, Result};
+use clap::ValueEnum;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 const CONFIG_FILENAME: &str = "config.toml";
 
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct GlobalConfig {
     #[serde(default)]
     pub defaults: GlobalDefaults,
@@ -13,12 +16,28 @@ pub struct GlobalConfig {
     pub options: ClientOptions,
     #[serde(default)]
     pub gamerules: GameRuleDefaults,
+    #[serde(default)]
+    pub server: ServerSettings,
 }
 
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct GlobalDefaults {
     pub author: Option<String>,
     pub language: Option<String>,
+    #[serde(default)]
+    pub github: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Line endings for generated text files: lf, crlf, or native.
+    #[serde(default)]
+    pub line_endings: Option<String>,
+    /// Template pack `mcmod init` and project updates use instead of the
+    /// built-in templates.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Never access the network, as if every command got `--offline`.
+    #[serde(default)]
+    pub offline: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -28,6 +47,23 @@ pub struct ClientOptions {
     pub auto_jump: Option<bool>,
     pub reduced_debug_info: Option<bool>,
     pub gamma: Option<f64>,
+    /// Accessibility: press sprint once instead of holding it.
+    #[serde(default)]
+    pub toggle_sprint: Option<bool>,
+    /// Accessibility: press sneak once instead of holding it.
+    #[serde(default)]
+    pub toggle_crouch: Option<bool>,
+    /// Packs to enable, e.g. `file/faithful.zip`; `vanilla` and the dev
+    /// resource pack are added around them.
+    #[serde(default)]
+    pub resource_packs: Option<Vec<String>>,
+    /// Packs kept enabled even though they target another pack format.
+    #[serde(default)]
+    pub incompatible_resource_packs: Option<Vec<String>>,
+    /// Any other options.txt key, written as is, e.g. key bindings like
+    /// `key_key.sprint = "key.keyboard.left.control"`. Overrides the settings above.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
 }
 
 impl Default for ClientOptions {
@@ -38,6 +74,11 @@ impl Default for ClientOptions {
             auto_jump: Some(false),
             reduced_debug_info: Some(false),
             gamma: None,
+            toggle_sprint: None,
+            toggle_crouch: None,
+            resource_packs: None,
+            incompatible_resource_packs: None,
+            extra: BTreeMap::new(),
         }
     }
 }
@@ -47,14 +88,68 @@ pub struct GameRuleDefaults {
     pub do_daylight_cycle: Option<bool>,
     pub do_weather_cycle: Option<bool>,
     pub time_of_day: Option<String>,
+    #[serde(default)]
+    pub random_tick_speed: Option<u32>,
+    #[serde(default)]
+    pub players_sleeping_percentage: Option<u32>,
+    #[serde(default)]
+    pub max_command_chain_length: Option<u32>,
+    /// `weather <value>` on first load: clear, rain, or thunder.
+    #[serde(default)]
+    pub weather: Option<String>,
+    /// `difficulty <value>` on first load.
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    /// `defaultgamemode <value>` on first load, so players joining get it.
+    #[serde(default)]
+    pub gamemode: Option<String>,
 }
 
+/// Accepted ranges for the integer game rules, by camelCase name.
+const RANDOM_TICK_SPEED: (&str, u32, u32) = ("randomTickSpeed", 0, 4096);
+const PLAYERS_SLEEPING_PERCENTAGE: (&str, u32, u32) = ("playersSleepingPercentage", 0, 100);
+const MAX_COMMAND_CHAIN_LENGTH: (&str, u32, u32) = ("maxCommandChainLength", 1, 1_000_000);
+
+/// Accepted values for the world settings applied alongside the game rules.
+const WEATHER: (&str, &[&str]) = ("weather", &["clear", "rain", "thunder"]);
+const DIFFICULTY: (&str, &[&str]) = ("difficulty", &["peaceful", "easy", "normal", "hard"]);
+const GAMEMODE: (&str, &[&str]) = ("gamemode", &["survival", "creative", "adventure", "spectator"]);
+
 impl Default for GameRuleDefaults {
     fn default() -> Self {
         Self {
             do_daylight_cycle: Some(false),
             do_weather_cycle: Some(false),
             time_of_day: Some("noon".to_string()),
+            random_tick_speed: None,
+            players_sleeping_percentage: None,
+            max_command_chain_length: None,
+            weather: Some("clear".to_string()),
+            difficulty: None,
+            gamemode: None,
+        }
+    }
+}
+
+/// Settings rendered into the dev server's server.properties.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerSettings {
+    /// Off by default so unauthenticated dev clients can join.
+    pub online_mode: Option<bool>,
+    pub difficulty: Option<String>,
+    /// Radius around spawn only operators can build in; 0 turns it off.
+    pub spawn_protection: Option<u32>,
+}
+
+/// server.properties caps spawn-protection at the world border radius.
+const SPAWN_PROTECTION: (&str, u32, u32) = ("spawnProtection", 0, 29_999_984);
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            online_mode: Some(false),
+            difficulty: Some("easy".to_string()),
+            spawn_protection: Some(16),
         }
     }
 }
@@ -84,15 +179,166 @@ pub fn global_config_dir() -> Result<PathBuf> {
     Ok(PathBuf::from(home).join(".config").join("mcmod"))
 }
 
+/// The active profile's preferences file: config.toml in
+/// [`global_config_dir`] for the default profile, `profiles/<name>.toml` for
+/// any other.
+pub fn global_config_file() -> Result<PathBuf> {
+    profile_file(&active_profile()?)
+}
+
+/// The profile whose preferences are config.toml itself.
+pub const DEFAULT_PROFILE: &str = "default";
+/// Directory under [`global_config_dir`] holding the other profiles.
+const PROFILES_DIR: &str = "profiles";
+/// Names the profile `config profile switch` chose; absent means the default.
+const ACTIVE_PROFILE_FILE: &str = "active-profile";
+/// Selects a profile for one run, like `--config-profile`.
+pub const PROFILE_ENV: &str = "MCMOD_PROFILE";
+
+/// The profile chosen for this run with `--config-profile` or `MCMOD_PROFILE`.
+static PROFILE_OVERRIDE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Use the profile `name` for the rest of the run instead of the one
+/// `config profile switch` chose. It must exist.
+pub fn select_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    if !profile_file(name)?.exists() && name != DEFAULT_PROFILE {
+        return Err(McmodError::Other(format!(
+            "No config profile '{name}'; create it with `mcmod config profile create {name}`"
+        )));
+    }
+    *PROFILE_OVERRIDE.lock().unwrap() = Some(name.to_string());
+    Ok(())
+}
+
+/// The profile in use: the one selected for this run, else the one
+/// `config profile switch` chose, else the default.
+pub fn active_profile() -> Result<String> {
+    if let Some(name) = PROFILE_OVERRIDE.lock().unwrap().clone() {
+        return Ok(name);
+    }
+    let switched = std::fs::read_to_string(global_config_dir()?.join(ACTIVE_PROFILE_FILE)).unwrap_or_default();
+    let switched = switched.trim();
+    Ok(if validate_profile_name(switched).is_ok() { switched } else { DEFAULT_PROFILE }.to_string())
+}
+
+/// The preferences file of the profile `name`.
+pub fn profile_file(name: &str) -> Result<PathBuf> {
+    let dir = global_config_dir()?;
+    Ok(match name {
+        DEFAULT_PROFILE => dir.join(CONFIG_FILENAME),
+        name => dir.join(PROFILES_DIR).join(format!("{name}.toml")),
+    })
+}
+
+/// Every profile, the default first and the rest by name.
+pub fn profiles() -> Result<Vec<String>> {
+    let mut names: Vec<String> = match std::fs::read_dir(global_config_dir()?.join(PROFILES_DIR)) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.strip_suffix(".toml").map(str::to_string))
+            .filter(|name| validate_profile_name(name).is_ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(names)
+}
+
+/// Create the profile `name`, starting from a copy of the profile `from`,
+/// or from the defaults.
+pub fn create_profile(name: &str, from: Option<&str>) -> Result<PathBuf> {
+    validate_profile_name(name)?;
+    let path = profile_file(name)?;
+    if name == DEFAULT_PROFILE || path.exists() {
+        return Err(McmodError::Other(format!("Config profile '{name}' already exists")));
+    }
+    let content = match from {
+        Some(from) => {
+            let source = profile_file(from)?;
+            if !source.exists() && from != DEFAULT_PROFILE {
+                return Err(McmodError::Other(format!("No config profile '{from}' to copy")));
+            }
+            std::fs::read_to_string(&source).unwrap_or_default()
+        }
+        None => GlobalConfig::default().export()?,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Creating {}", parent.display()))?;
+    }
+    crate::util::write_file(&path, &content)?;
+    Ok(path)
+}
+
+/// Make `name` the profile used when none is selected for the run.
+pub fn switch_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    let marker = global_config_dir()?.join(ACTIVE_PROFILE_FILE);
+    if name == DEFAULT_PROFILE {
+        if marker.exists() {
+            std::fs::remove_file(&marker).with_context(|| format!("Removing {}", marker.display()))?;
+        }
+        return Ok(());
+    }
+    if !profile_file(name)?.exists() {
+        return Err(McmodError::Other(format!(
+            "No config profile '{name}'; create it with `mcmod config profile create {name}`"
+        )));
+    }
+    crate::util::write_file(&marker, &format!("{name}\n"))
+}
+
+/// Profile names are file names: lowercase letters, digits, '-' and '_'.
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(McmodError::Other(format!(
+            "Invalid config profile name '{name}': use lowercase letters, digits, '-' and '_'"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the platform-specific cache directory for mcmod.
+/// - Linux/macOS: $XDG_CACHE_HOME/mcmod or ~/.cache/mcmod
+/// - Windows: %LOCALAPPDATA%/mcmod/cache
+pub fn cache_dir() -> Result<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        if let Ok(local) = std::env::var("LOCALAPPDATA") {
+            return Ok(PathBuf::from(local).join("mcmod").join("cache"));
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        if let Ok(xdg) = std::env::var("XDG_CACHE_HOME") {
+            return Ok(PathBuf::from(xdg).join("mcmod"));
+        }
+    }
+
+    // Fallback: ~/.cache/mcmod
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| McmodError::Other("Could not determine home directory".to_string()))?;
+    Ok(PathBuf::from(home).join(".cache").join("mcmod"))
+}
+
+/// Where user template packs live: templates/ in [`global_config_dir`].
+pub fn templates_dir() -> Result<PathBuf> {
+    Ok(global_config_dir()?.join("templates"))
+}
+
 impl GlobalConfig {
     /// Load global config from config.toml. Returns Default if file is missing or corrupt.
     pub fn load() -> Result<Self> {
-        let dir = global_config_dir()?;
-        let path = dir.join(CONFIG_FILENAME);
+        let path = global_config_file()?;
         if !path.exists() {
             return Ok(Self::default());
         }
-        let content = std::fs::read_to_string(&path)?;
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Reading {}", path.display()))?;
         let config: GlobalConfig = match toml::from_str(&content) {
             Ok(c) => c,
             Err(e) => {
@@ -108,36 +354,90 @@ impl GlobalConfig {
 
     /// Save global config to config.toml, creating the directory if needed.
     pub fn save(&self) -> Result<()> {
-        let dir = global_config_dir()?;
-        std::fs::create_dir_all(&dir)?;
-        let path = dir.join(CONFIG_FILENAME);
+        let path = global_config_file()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+        }
         let content = toml::to_string_pretty(self)
-            .map_err(|e| McmodError::TomlSerialize(e))?;
-        std::fs::write(&path, content)?;
+            .map_err(McmodError::TomlSerialize)?;
+        crate::util::write_file(&path, &content)?;
         Ok(())
     }
 
     /// Get a config value by key. Accepts short keys like "author" or dotted "defaults.author".
     pub fn get(&self, key: &str) -> Option<String> {
         let normalized = normalize_key(key);
+        if let Some(option) = normalized.strip_prefix(EXTRA_PREFIX) {
+            return self.options.extra.get(option).cloned();
+        }
         match normalized.as_str() {
             "defaults.author" => self.defaults.author.clone(),
             "defaults.language" => self.defaults.language.clone(),
+            "defaults.github" => self.defaults.github.clone(),
+            "defaults.email" => self.defaults.email.clone(),
+            "defaults.line_endings" => self.defaults.line_endings.clone(),
+            "defaults.template" => self.defaults.template.clone(),
+            "defaults.offline" => self.defaults.offline.map(|v| v.to_string()),
             "options.fullscreen" => self.options.fullscreen.map(|v| v.to_string()),
             "options.pause_on_lost_focus" => self.options.pause_on_lost_focus.map(|v| v.to_string()),
             "options.auto_jump" => self.options.auto_jump.map(|v| v.to_string()),
             "options.reduced_debug_info" => self.options.reduced_debug_info.map(|v| v.to_string()),
             "options.gamma" => self.options.gamma.map(|v| v.to_string()),
+            "options.toggle_sprint" => self.options.toggle_sprint.map(|v| v.to_string()),
+            "options.toggle_crouch" => self.options.toggle_crouch.map(|v| v.to_string()),
+            "options.resource_packs" => self.options.resource_packs.as_ref().map(|v| v.join(",")),
+            "options.incompatible_resource_packs" => {
+                self.options.incompatible_resource_packs.as_ref().map(|v| v.join(","))
+            }
             "gamerules.do_daylight_cycle" => self.gamerules.do_daylight_cycle.map(|v| v.to_string()),
             "gamerules.do_weather_cycle" => self.gamerules.do_weather_cycle.map(|v| v.to_string()),
             "gamerules.time_of_day" => self.gamerules.time_of_day.clone(),
+            "gamerules.random_tick_speed" => self.gamerules.random_tick_speed.map(|v| v.to_string()),
+            "gamerules.players_sleeping_percentage" => {
+                self.gamerules.players_sleeping_percentage.map(|v| v.to_string())
+            }
+            "gamerules.max_command_chain_length" => {
+                self.gamerules.max_command_chain_length.map(|v| v.to_string())
+            }
+            "gamerules.weather" => self.gamerules.weather.clone(),
+            "gamerules.difficulty" => self.gamerules.difficulty.clone(),
+            "gamerules.gamemode" => self.gamerules.gamemode.clone(),
+            "server.online_mode" => self.server.online_mode.map(|v| v.to_string()),
+            "server.difficulty" => self.server.difficulty.clone(),
+            "server.spawn_protection" => self.server.spawn_protection.map(|v| v.to_string()),
             _ => None,
         }
     }
 
-    /// Set a config value by key. Validates known keys and language values.
-    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+    /// Apply several assignments at once, all or nothing: returns the updated
+    /// config, or an error naming every rejected key.
+    pub fn apply_all(&self, assignments: &[(String, String)]) -> Result<GlobalConfig> {
+        let mut config = self.clone();
+        let errors: Vec<String> = assignments
+            .iter()
+            .filter_map(|(key, value)| config.apply(key, value).err().map(|e| format!("{key}: {e}")))
+            .collect();
+        if !errors.is_empty() {
+            return Err(McmodError::Other(format!(
+                "Preferences not set:\n    {}",
+                errors.join("\n    ")
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Validate and assign a config value without saving.
+    fn apply(&mut self, key: &str, value: &str) -> Result<()> {
         let normalized = normalize_key(key);
+        if let Some(option) = normalized.strip_prefix(EXTRA_PREFIX) {
+            validate_extra_option(option, value)?;
+            if value.is_empty() {
+                self.options.extra.remove(option);
+            } else {
+                self.options.extra.insert(option.to_string(), value.to_string());
+            }
+            return Ok(());
+        }
         match normalized.as_str() {
             "defaults.author" => {
                 self.defaults.author = Some(value.to_string());
@@ -151,6 +451,29 @@ impl GlobalConfig {
                 }
                 self.defaults.language = Some(lower);
             }
+            "defaults.github" => {
+                validate_github_username(value)?;
+                self.defaults.github = Some(value.to_string());
+            }
+            "defaults.email" => {
+                crate::util::validate_email(value)?;
+                self.defaults.email = Some(value.to_string());
+            }
+            "defaults.line_endings" => {
+                crate::file_policy::LineEnding::parse(value)?;
+                self.defaults.line_endings = Some(value.to_lowercase());
+            }
+            "defaults.template" => {
+                if value == crate::template_pack::BUILT_IN {
+                    self.defaults.template = None;
+                } else {
+                    crate::template_pack::validate_name(value)?;
+                    self.defaults.template = Some(value.to_string());
+                }
+            }
+            "defaults.offline" => {
+                self.defaults.offline = Some(parse_bool(value)?);
+            }
             "options.fullscreen" => {
                 self.options.fullscreen = Some(parse_bool(value)?);
             }
@@ -169,6 +492,18 @@ impl GlobalConfig {
                 })?;
                 self.options.gamma = Some(v);
             }
+            "options.toggle_sprint" => {
+                self.options.toggle_sprint = Some(parse_bool(value)?);
+            }
+            "options.toggle_crouch" => {
+                self.options.toggle_crouch = Some(parse_bool(value)?);
+            }
+            "options.resource_packs" => {
+                self.options.resource_packs = Some(parse_pack_list(value)?);
+            }
+            "options.incompatible_resource_packs" => {
+                self.options.incompatible_resource_packs = Some(parse_pack_list(value)?);
+            }
             "gamerules.do_daylight_cycle" => {
                 self.gamerules.do_daylight_cycle = Some(parse_bool(value)?);
             }
@@ -179,13 +514,190 @@ impl GlobalConfig {
                 crate::pack_format::validate_time_of_day(value)?;
                 self.gamerules.time_of_day = Some(value.to_lowercase());
             }
+            "gamerules.random_tick_speed" => {
+                self.gamerules.random_tick_speed = Some(parse_in_range(value, RANDOM_TICK_SPEED)?);
+            }
+            "gamerules.players_sleeping_percentage" => {
+                self.gamerules.players_sleeping_percentage =
+                    Some(parse_in_range(value, PLAYERS_SLEEPING_PERCENTAGE)?);
+            }
+            "gamerules.max_command_chain_length" => {
+                self.gamerules.max_command_chain_length =
+                    Some(parse_in_range(value, MAX_COMMAND_CHAIN_LENGTH)?);
+            }
+            "gamerules.weather" => {
+                self.gamerules.weather = Some(parse_choice(value, WEATHER)?);
+            }
+            "gamerules.difficulty" => {
+                self.gamerules.difficulty = Some(parse_choice(value, DIFFICULTY)?);
+            }
+            "gamerules.gamemode" => {
+                self.gamerules.gamemode = Some(parse_choice(value, GAMEMODE)?);
+            }
+            "server.online_mode" => {
+                self.server.online_mode = Some(parse_bool(value)?);
+            }
+            "server.difficulty" => {
+                self.server.difficulty = Some(parse_choice(value, DIFFICULTY)?);
+            }
+            "server.spawn_protection" => {
+                self.server.spawn_protection = Some(parse_in_range(value, SPAWN_PROTECTION)?);
+            }
             _ => {
                 return Err(McmodError::Other(format!(
                     "Unknown config key '{key}'. Run 'mcmod config list' to see valid keys."
                 )));
             }
         }
-        self.save()
+        Ok(())
+    }
+
+    /// Clear a preference back to not set, e.g. so init asks for the author
+    /// again or options.txt leaves the option to the game.
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        let normalized = normalize_key(key);
+        if let Some(option) = normalized.strip_prefix(EXTRA_PREFIX) {
+            return match self.options.extra.remove(option) {
+                Some(_) => Ok(()),
+                None => Err(McmodError::Other(format!("'{key}' is not set"))),
+            };
+        }
+        match normalized.as_str() {
+            "defaults.author" => self.defaults.author = None,
+            "defaults.language" => self.defaults.language = None,
+            "defaults.github" => self.defaults.github = None,
+            "defaults.email" => self.defaults.email = None,
+            "defaults.line_endings" => self.defaults.line_endings = None,
+            "defaults.template" => self.defaults.template = None,
+            "defaults.offline" => self.defaults.offline = None,
+            "options.fullscreen" => self.options.fullscreen = None,
+            "options.pause_on_lost_focus" => self.options.pause_on_lost_focus = None,
+            "options.auto_jump" => self.options.auto_jump = None,
+            "options.reduced_debug_info" => self.options.reduced_debug_info = None,
+            "options.gamma" => self.options.gamma = None,
+            "options.toggle_sprint" => self.options.toggle_sprint = None,
+            "options.toggle_crouch" => self.options.toggle_crouch = None,
+            "options.resource_packs" => self.options.resource_packs = None,
+            "options.incompatible_resource_packs" => self.options.incompatible_resource_packs = None,
+            "gamerules.do_daylight_cycle" => self.gamerules.do_daylight_cycle = None,
+            "gamerules.do_weather_cycle" => self.gamerules.do_weather_cycle = None,
+            "gamerules.time_of_day" => self.gamerules.time_of_day = None,
+            "gamerules.random_tick_speed" => self.gamerules.random_tick_speed = None,
+            "gamerules.players_sleeping_percentage" => self.gamerules.players_sleeping_percentage = None,
+            "gamerules.max_command_chain_length" => self.gamerules.max_command_chain_length = None,
+            "gamerules.weather" => self.gamerules.weather = None,
+            "gamerules.difficulty" => self.gamerules.difficulty = None,
+            "gamerules.gamemode" => self.gamerules.gamemode = None,
+            "server.online_mode" => self.server.online_mode = None,
+            "server.difficulty" => self.server.difficulty = None,
+            "server.spawn_protection" => self.server.spawn_protection = None,
+            _ => {
+                return Err(McmodError::Other(format!(
+                    "Unknown config key '{key}'. Run 'mcmod config list' to see valid keys."
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Restore one section's defaults, or every section's when `section` is None.
+    pub fn reset(&mut self, section: Option<ConfigSection>) {
+        match section {
+            None => *self = GlobalConfig::default(),
+            Some(ConfigSection::Defaults) => self.defaults = GlobalDefaults::default(),
+            Some(ConfigSection::Options) => self.options = ClientOptions::default(),
+            Some(ConfigSection::Gamerules) => self.gamerules = GameRuleDefaults::default(),
+            Some(ConfigSection::Server) => self.server = ServerSettings::default(),
+        }
+    }
+
+    /// Serialize the preferences for `mcmod config export`.
+    pub fn export(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(McmodError::TomlSerialize)
+    }
+
+    /// Preferences after importing an exported config.toml. Every key is
+    /// validated as `config set` would; any invalid or unknown key fails the
+    /// whole import so nothing is half-applied. `Merge` keeps preferences the
+    /// file doesn't mention, `Overwrite` resets them to the defaults.
+    pub fn import(&self, content: &str, mode: ImportMode) -> Result<GlobalConfig> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| McmodError::Other(format!("Invalid preferences file: {e}")))?;
+
+        let mut config = match mode {
+            ImportMode::Merge => self.clone(),
+            ImportMode::Overwrite => GlobalConfig::default(),
+        };
+        let errors = config.apply_table(&table);
+        if !errors.is_empty() {
+            return Err(McmodError::Other(format!(
+                "Preferences not imported:\n    {}",
+                errors.join("\n    ")
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Parse a hand-edited config.toml the way `load` reads it, but with every
+    /// key checked as `config set` would: unknown keys and bad values are
+    /// errors instead of a warning and the defaults.
+    pub fn parse_strict(content: &str) -> Result<GlobalConfig> {
+        let table: toml::Table =
+            toml::from_str(content).map_err(|e| McmodError::Other(format!("Invalid TOML: {e}")))?;
+        let errors = GlobalConfig::default().apply_table(&table);
+        if !errors.is_empty() {
+            return Err(McmodError::Other(format!(
+                "Invalid preferences:\n    {}",
+                errors.join("\n    ")
+            )));
+        }
+        toml::from_str(content).map_err(|e| McmodError::Other(format!("Invalid preferences: {e}")))
+    }
+
+    /// Apply every value in a config.toml table, returning a `key: error`
+    /// line for each one rejected.
+    fn apply_table(&mut self, table: &toml::Table) -> Vec<String> {
+        // Flatten to dotted keys; [options.extra] is the one nested table
+        let mut errors = Vec::new();
+        let mut values = Vec::new();
+        for (section, entries) in table {
+            let Some(entries) = entries.as_table() else {
+                errors.push(format!("'{section}' must be a [section]"));
+                continue;
+            };
+            for (key, value) in entries {
+                let key = format!("{section}.{key}");
+                match value.as_table() {
+                    Some(nested) if format!("{key}.") == EXTRA_PREFIX => {
+                        for (option, value) in nested {
+                            values.push((format!("{EXTRA_PREFIX}{option}"), value));
+                        }
+                    }
+                    _ => values.push((key, value)),
+                }
+            }
+        }
+        for (key, value) in values {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Boolean(b) => b.to_string(),
+                toml::Value::Integer(i) => i.to_string(),
+                toml::Value::Float(f) => f.to_string(),
+                toml::Value::Array(items) if items.iter().all(toml::Value::is_str) => items
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(","),
+                _ => {
+                    errors.push(format!("{key}: expected a string, number, boolean, or list of strings"));
+                    continue;
+                }
+            };
+            if let Err(e) = self.apply(&key, &value) {
+                errors.push(format!("{key}: {e}"));
+            }
+        }
+        errors
     }
 
     /// List all config key-value pairs, grouped by section.
@@ -204,10 +716,23 @@ impl GlobalConfig {
             Some(f) => f.to_string(),
             None => "(not set)".to_string(),
         };
+        let display_u32 = |v: &Option<u32>| match v {
+            Some(n) => n.to_string(),
+            None => "(not set)".to_string(),
+        };
+        let display_list = |v: &Option<Vec<String>>| match v {
+            Some(items) => items.join(","),
+            None => "(not set)".to_string(),
+        };
 
         // Defaults
         entries.push(("Defaults", "author".to_string(), display(&self.defaults.author)));
         entries.push(("Defaults", "language".to_string(), display(&self.defaults.language)));
+        entries.push(("Defaults", "github".to_string(), display(&self.defaults.github)));
+        entries.push(("Defaults", "email".to_string(), display(&self.defaults.email)));
+        entries.push(("Defaults", "lineEndings".to_string(), display(&self.defaults.line_endings)));
+        entries.push(("Defaults", "template".to_string(), display(&self.defaults.template)));
+        entries.push(("Defaults", "offline".to_string(), display_bool(&self.defaults.offline)));
 
         // Client Options
         entries.push(("Client Options", "fullscreen".to_string(), display_bool(&self.options.fullscreen)));
@@ -215,17 +740,37 @@ impl GlobalConfig {
         entries.push(("Client Options", "autoJump".to_string(), display_bool(&self.options.auto_jump)));
         entries.push(("Client Options", "reducedDebugInfo".to_string(), display_bool(&self.options.reduced_debug_info)));
         entries.push(("Client Options", "gamma".to_string(), display_f64(&self.options.gamma)));
+        entries.push(("Client Options", "toggleSprint".to_string(), display_bool(&self.options.toggle_sprint)));
+        entries.push(("Client Options", "toggleCrouch".to_string(), display_bool(&self.options.toggle_crouch)));
+        entries.push(("Client Options", "resourcePacks".to_string(), display_list(&self.options.resource_packs)));
+        entries.push(("Client Options", "incompatibleResourcePacks".to_string(), display_list(&self.options.incompatible_resource_packs)));
+        for (option, value) in &self.options.extra {
+            entries.push(("Client Options", format!("extra.{option}"), value.clone()));
+        }
 
         // Game Rules
         entries.push(("Game Rules", "doDaylightCycle".to_string(), display_bool(&self.gamerules.do_daylight_cycle)));
         entries.push(("Game Rules", "doWeatherCycle".to_string(), display_bool(&self.gamerules.do_weather_cycle)));
         entries.push(("Game Rules", "timeOfDay".to_string(), display(&self.gamerules.time_of_day)));
+        entries.push(("Game Rules", "randomTickSpeed".to_string(), display_u32(&self.gamerules.random_tick_speed)));
+        entries.push(("Game Rules", "playersSleepingPercentage".to_string(), display_u32(&self.gamerules.players_sleeping_percentage)));
+        entries.push(("Game Rules", "maxCommandChainLength".to_string(), display_u32(&self.gamerules.max_command_chain_length)));
+        entries.push(("Game Rules", "weather".to_string(), display(&self.gamerules.weather)));
+        entries.push(("Game Rules", "difficulty".to_string(), display(&self.gamerules.difficulty)));
+        entries.push(("Game Rules", "gamemode".to_string(), display(&self.gamerules.gamemode)));
+
+        // Dev Server
+        entries.push(("Dev Server", "onlineMode".to_string(), display_bool(&self.server.online_mode)));
+        entries.push(("Dev Server", "serverDifficulty".to_string(), display(&self.server.difficulty)));
+        entries.push(("Dev Server", "spawnProtection".to_string(), display_u32(&self.server.spawn_protection)));
 
         entries
     }
 
-    /// Render options.txt content from the current config.
-    pub fn render_options_txt(&self) -> String {
+    /// Render options.txt content from the current config. `dev_packs` (e.g.
+    /// `file/dev-resources`) are enabled after the configured resource packs,
+    /// so they take priority on first launch.
+    pub fn render_options_txt(&self, dev_packs: &[&str]) -> String {
         let mut lines = Vec::new();
         lines.push("lang:en_us".to_string());
 
@@ -244,10 +789,107 @@ impl GlobalConfig {
         if let Some(v) = self.options.gamma {
             lines.push(format!("gamma:{v}"));
         }
+        if let Some(v) = self.options.toggle_sprint {
+            lines.push(format!("toggleSprint:{v}"));
+        }
+        if let Some(v) = self.options.toggle_crouch {
+            lines.push(format!("toggleCrouch:{v}"));
+        }
+
+        // Bottom to top: vanilla first, dev packs last
+        let mut packs = vec!["vanilla"];
+        for pack in self.options.resource_packs.iter().flatten() {
+            packs.push(pack);
+        }
+        packs.extend_from_slice(dev_packs);
+        let mut seen = std::collections::HashSet::new();
+        packs.retain(|p| seen.insert(*p));
+        if packs.len() > 1 {
+            lines.push(format!("resourcePacks:{}", render_string_list(&packs)));
+        }
+        if let Some(ref packs) = self.options.incompatible_resource_packs {
+            let packs: Vec<&str> = packs.iter().map(String::as_str).collect();
+            lines.push(format!("incompatibleResourcePacks:{}", render_string_list(&packs)));
+        }
+
+        // Passthrough options replace a generated line with the same key
+        for (option, value) in &self.options.extra {
+            let line = format!("{option}:{value}");
+            match lines.iter().position(|l| l.split_once(':').is_some_and(|(k, _)| k == option)) {
+                Some(i) => lines[i] = line,
+                None => lines.push(line),
+            }
+        }
 
         lines.push(String::new()); // trailing newline
         lines.join("\n")
     }
+
+    /// Render the dev server's server.properties: the defaults mcmod init
+    /// writes, with the `server` section's settings applied.
+    pub fn render_server_properties(&self) -> String {
+        let mut settings = Vec::new();
+        if let Some(v) = self.server.online_mode {
+            settings.push(("online-mode", v.to_string()));
+        }
+        if let Some(ref v) = self.server.difficulty {
+            settings.push(("difficulty", v.clone()));
+        }
+        if let Some(v) = self.server.spawn_protection {
+            settings.push(("spawn-protection", v.to_string()));
+        }
+        let mut out = String::new();
+        for line in crate::commands::init::SERVER_PROPERTIES.lines() {
+            let key = line.split_once('=').map(|(k, _)| k);
+            match settings.iter().find(|(k, _)| Some(*k) == key) {
+                Some((k, v)) => out.push_str(&format!("{k}={v}")),
+                None => out.push_str(line),
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// How `mcmod config import` treats preferences the imported file leaves out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImportMode {
+    /// Keep current values for keys the file doesn't set
+    Merge,
+    /// Reset keys the file doesn't set to their defaults
+    Overwrite,
+}
+
+/// A config.toml section `mcmod config reset` can restore on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ConfigSection {
+    /// author, language, github, email, lineEndings, template, offline
+    Defaults,
+    /// Client options.txt settings, including extra options
+    Options,
+    /// Game rules and world settings for the dev-defaults data pack
+    Gamerules,
+    /// Dev server.properties settings
+    Server,
+}
+
+/// Parse `key=value` assignments for `config set`, one per item (argument or
+/// stdin line). Blank items and `#` comments are skipped.
+pub fn parse_assignments<'a>(items: impl IntoIterator<Item = &'a str>) -> Result<Vec<(String, String)>> {
+    let mut assignments = Vec::new();
+    for item in items {
+        let item = item.trim();
+        if item.is_empty() || item.starts_with('#') {
+            continue;
+        }
+        match item.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                assignments.push((key.trim().to_string(), value.trim().to_string()));
+            }
+            _ => return Err(McmodError::Other(format!("Expected key=value, got '{item}'"))),
+        }
+    }
+    Ok(assignments)
 }
 
 /// Normalize short key names to their dotted form.
@@ -257,6 +899,11 @@ fn normalize_key(key: &str) -> String {
         // Defaults
         "author" => "defaults.author".to_string(),
         "language" => "defaults.language".to_string(),
+        "github" => "defaults.github".to_string(),
+        "email" => "defaults.email".to_string(),
+        "lineEndings" | "line_endings" => "defaults.line_endings".to_string(),
+        "template" => "defaults.template".to_string(),
+        "offline" => "defaults.offline".to_string(),
 
         // Client Options — camelCase
         "fullscreen" => "options.fullscreen".to_string(),
@@ -264,16 +911,83 @@ fn normalize_key(key: &str) -> String {
         "autoJump" | "auto_jump" => "options.auto_jump".to_string(),
         "reducedDebugInfo" | "reduced_debug_info" => "options.reduced_debug_info".to_string(),
         "gamma" => "options.gamma".to_string(),
+        "toggleSprint" | "toggle_sprint" => "options.toggle_sprint".to_string(),
+        "toggleCrouch" | "toggle_crouch" => "options.toggle_crouch".to_string(),
+        "resourcePacks" | "resource_packs" => "options.resource_packs".to_string(),
+        "incompatibleResourcePacks" | "incompatible_resource_packs" => {
+            "options.incompatible_resource_packs".to_string()
+        }
 
         // Game Rules — camelCase and snake_case
         "doDaylightCycle" | "do_daylight_cycle" => "gamerules.do_daylight_cycle".to_string(),
         "doWeatherCycle" | "do_weather_cycle" => "gamerules.do_weather_cycle".to_string(),
         "timeOfDay" | "time_of_day" => "gamerules.time_of_day".to_string(),
+        "randomTickSpeed" | "random_tick_speed" => "gamerules.random_tick_speed".to_string(),
+        "playersSleepingPercentage" | "players_sleeping_percentage" => {
+            "gamerules.players_sleeping_percentage".to_string()
+        }
+        "maxCommandChainLength" | "max_command_chain_length" => {
+            "gamerules.max_command_chain_length".to_string()
+        }
+        "weather" => "gamerules.weather".to_string(),
+        "difficulty" => "gamerules.difficulty".to_string(),
+        "gamemode" | "defaultGamemode" | "default_gamemode" => "gamerules.gamemode".to_string(),
+
+        // Dev Server — difficulty alone means the game rule above
+        "onlineMode" | "online_mode" => "server.online_mode".to_string(),
+        "serverDifficulty" | "server_difficulty" => "server.difficulty".to_string(),
+        "spawnProtection" | "spawn_protection" => "server.spawn_protection".to_string(),
 
-        other => other.to_string(),
+        other => match other.strip_prefix("extra.") {
+            Some(option) => format!("{EXTRA_PREFIX}{option}"),
+            None => other.to_string(),
+        },
     }
 }
 
+/// Dotted-key prefix of the `[options.extra]` passthrough table.
+const EXTRA_PREFIX: &str = "options.extra.";
+
+/// Check a passthrough options.txt entry. Keys are plain option names such as
+/// `renderDistance` or `key_key.sprint`; key bindings must name an input like
+/// `key.keyboard.r` or `key.mouse.left`. An empty value removes the entry.
+fn validate_extra_option(option: &str, value: &str) -> Result<()> {
+    let valid_key = !option.is_empty()
+        && option.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if !valid_key {
+        return Err(McmodError::Other(format!(
+            "Invalid option name '{option}': use the options.txt key, e.g. renderDistance or key_key.sprint"
+        )));
+    }
+    if value.contains(['\n', '\r']) {
+        return Err(McmodError::Other(format!("Invalid value for {option}: must be a single line")));
+    }
+    let is_input = value.starts_with("key.keyboard.") || value.starts_with("key.mouse.");
+    if option.starts_with("key_") && !value.is_empty() && !is_input {
+        return Err(McmodError::Other(format!(
+            "Invalid key binding '{value}' for {option}: use an input like key.keyboard.r or key.mouse.left"
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a GitHub username: 1-39 alphanumerics or single hyphens,
+/// not starting or ending with a hyphen.
+pub fn validate_github_username(value: &str) -> Result<()> {
+    let valid = !value.is_empty()
+        && value.len() <= 39
+        && !value.starts_with('-')
+        && !value.ends_with('-')
+        && !value.contains("--")
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(McmodError::Other(format!(
+            "Invalid GitHub username '{value}': use letters, digits, and single hyphens"
+        )));
+    }
+    Ok(())
+}
+
 /// Parse a boolean value accepting true/false/yes/no/1/0.
 fn parse_bool(value: &str) -> Result<bool> {
     match value.to_lowercase().as_str() {
@@ -285,17 +999,59 @@ fn parse_bool(value: &str) -> Result<bool> {
     }
 }
 
-/// Copies options.txt generated from config into the given path.
-/// No-op if the destination already exists.
-pub fn copy_options_to(dest: &Path, config: &GlobalConfig) -> Result<()> {
+/// Parse an integer game rule value, rejecting values outside `min..=max`.
+fn parse_in_range(value: &str, (rule, min, max): (&str, u32, u32)) -> Result<u32> {
+    match value.trim().parse::<u32>() {
+        Ok(n) if (min..=max).contains(&n) => Ok(n),
+        _ => Err(McmodError::Other(format!(
+            "Invalid {rule} '{value}': must be a whole number from {min} to {max}"
+        ))),
+    }
+}
+
+/// Parse a world setting that takes one of a fixed set of names (case-insensitive).
+fn parse_choice(value: &str, (setting, choices): (&str, &[&str])) -> Result<String> {
+    let lower = value.trim().to_lowercase();
+    if choices.contains(&lower.as_str()) {
+        Ok(lower)
+    } else {
+        Err(McmodError::Other(format!(
+            "Invalid {setting} '{value}': must be {}",
+            choices.join("/")
+        )))
+    }
+}
+
+/// Parse a comma-separated list of resource pack ids. An empty value clears
+/// the list.
+fn parse_pack_list(value: &str) -> Result<Vec<String>> {
+    let packs: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    if let Some(bad) = packs.iter().find(|p| p.contains(['"', '[', ']'])) {
+        return Err(McmodError::Other(format!(
+            "Invalid resource pack '{bad}': use ids like vanilla or file/MyPack.zip, separated by commas"
+        )));
+    }
+    Ok(packs)
+}
+
+/// options.txt list syntax: a JSON array of strings.
+fn render_string_list(items: &[&str]) -> String {
+    serde_json::to_string(items).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Copies options.txt generated from config into the given path, enabling
+/// `dev_packs` too. No-op if the destination already exists.
+pub fn copy_options_to(dest: &Path, config: &GlobalConfig, dev_packs: &[&str]) -> Result<()> {
     if dest.exists() {
         return Ok(());
     }
-    let content = config.render_options_txt();
-    if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    std::fs::write(dest, content)?;
+    let content = config.render_options_txt(dev_packs);
+    crate::util::write_file(dest, &content)?;
     Ok(())
 }
 
@@ -307,6 +1063,19 @@ mod tests {
     fn test_normalize_key_defaults() {
         assert_eq!(normalize_key("author"), "defaults.author");
         assert_eq!(normalize_key("language"), "defaults.language");
+        assert_eq!(normalize_key("github"), "defaults.github");
+        assert_eq!(normalize_key("email"), "defaults.email");
+    }
+
+    #[test]
+    fn test_validate_github_username() {
+        assert!(validate_github_username("jane").is_ok());
+        assert!(validate_github_username("Jane-Doe42").is_ok());
+        assert!(validate_github_username("").is_err());
+        assert!(validate_github_username("-jane").is_err());
+        assert!(validate_github_username("jane-").is_err());
+        assert!(validate_github_username("ja--ne").is_err());
+        assert!(validate_github_username("jane doe").is_err());
     }
 
     #[test]
@@ -326,6 +1095,62 @@ mod tests {
         assert_eq!(normalize_key("doWeatherCycle"), "gamerules.do_weather_cycle");
         assert_eq!(normalize_key("timeOfDay"), "gamerules.time_of_day");
         assert_eq!(normalize_key("time_of_day"), "gamerules.time_of_day");
+        assert_eq!(normalize_key("randomTickSpeed"), "gamerules.random_tick_speed");
+        assert_eq!(normalize_key("players_sleeping_percentage"), "gamerules.players_sleeping_percentage");
+        assert_eq!(normalize_key("maxCommandChainLength"), "gamerules.max_command_chain_length");
+    }
+
+    #[test]
+    fn test_integer_gamerules() {
+        let mut config = GlobalConfig::default();
+        config.apply("randomTickSpeed", "20").unwrap();
+        config.apply("playersSleepingPercentage", "0").unwrap();
+        config.apply("max_command_chain_length", "1000").unwrap();
+        assert_eq!(config.gamerules.random_tick_speed, Some(20));
+        assert_eq!(config.get("playersSleepingPercentage"), Some("0".to_string()));
+        assert_eq!(config.gamerules.max_command_chain_length, Some(1000));
+
+        assert!(config.apply("randomTickSpeed", "-1").is_err());
+        assert!(config.apply("randomTickSpeed", "fast").is_err());
+        assert!(config.apply("playersSleepingPercentage", "101").is_err());
+        let err = config.apply("maxCommandChainLength", "0").err().unwrap().to_string();
+        assert!(err.contains("from 1 to 1000000"));
+        assert_eq!(config.gamerules.random_tick_speed, Some(20));
+    }
+
+    #[test]
+    fn test_world_settings() {
+        let mut config = GlobalConfig::default();
+        assert_eq!(config.get("weather"), Some("clear".to_string()));
+        config.apply("weather", "Thunder").unwrap();
+        config.apply("difficulty", "peaceful").unwrap();
+        config.apply("default_gamemode", "creative").unwrap();
+        assert_eq!(config.gamerules.weather, Some("thunder".to_string()));
+        assert_eq!(config.get("difficulty"), Some("peaceful".to_string()));
+        assert_eq!(config.gamerules.gamemode, Some("creative".to_string()));
+
+        assert!(config.apply("weather", "snow").is_err());
+        let err = config.apply("gamemode", "god").err().unwrap().to_string();
+        assert!(err.contains("survival/creative/adventure/spectator"));
+    }
+
+    #[test]
+    fn test_server_settings() {
+        let mut config = GlobalConfig::default();
+        assert_eq!(config.render_server_properties(), crate::commands::init::SERVER_PROPERTIES);
+
+        config.apply("onlineMode", "true").unwrap();
+        config.apply("server.difficulty", "Hard").unwrap();
+        config.apply("spawn_protection", "0").unwrap();
+        assert_eq!(config.get("difficulty"), None);
+        let props = config.render_server_properties();
+        assert!(props.contains("\nonline-mode=true\n"));
+        assert!(props.contains("\ndifficulty=hard\n"));
+        assert!(props.contains("\nspawn-protection=0\n"));
+        assert!(props.contains("\nenforce-secure-profile=false\n"));
+
+        assert!(config.apply("serverDifficulty", "nightmare").is_err());
+        assert!(config.apply("spawnProtection", "-1").is_err());
     }
 
     #[test]
@@ -342,7 +1167,7 @@ mod tests {
     #[test]
     fn test_render_options_txt_defaults() {
         let config = GlobalConfig::default();
-        let txt = config.render_options_txt();
+        let txt = config.render_options_txt(&[]);
         assert!(txt.contains("lang:en_us"));
         assert!(txt.contains("fullscreen:true"));
         assert!(txt.contains("pauseOnLostFocus:false"));
@@ -350,6 +1175,7 @@ mod tests {
         assert!(txt.contains("reducedDebugInfo:false"));
         // gamma not set by default, should not appear
         assert!(!txt.contains("gamma:"));
+        assert!(!txt.contains("resourcePacks:"));
     }
 
     #[test]
@@ -357,11 +1183,61 @@ mod tests {
         let mut config = GlobalConfig::default();
         config.options.fullscreen = Some(false);
         config.options.gamma = Some(1.5);
-        let txt = config.render_options_txt();
+        let txt = config.render_options_txt(&[]);
         assert!(txt.contains("fullscreen:false"));
         assert!(txt.contains("gamma:1.5"));
     }
 
+    #[test]
+    fn test_render_options_txt_resource_packs() {
+        let mut config = GlobalConfig::default();
+        let txt = config.render_options_txt(&["file/dev-resources"]);
+        assert!(txt.contains("resourcePacks:[\"vanilla\",\"file/dev-resources\"]\n"));
+        assert!(!txt.contains("incompatibleResourcePacks"));
+
+        config.apply("resourcePacks", "file/faithful.zip, vanilla").unwrap();
+        config.apply("incompatible_resource_packs", "file/faithful.zip").unwrap();
+        assert_eq!(config.get("resourcePacks"), Some("file/faithful.zip,vanilla".to_string()));
+        let txt = config.render_options_txt(&["file/dev-resources"]);
+        assert!(txt.contains("resourcePacks:[\"vanilla\",\"file/faithful.zip\",\"file/dev-resources\"]"));
+        assert!(txt.contains("incompatibleResourcePacks:[\"file/faithful.zip\"]"));
+
+        assert!(config.apply("resourcePacks", "file/\"x\".zip").is_err());
+        let exported = config.export().unwrap();
+        let imported = GlobalConfig::default().import(&exported, ImportMode::Overwrite).unwrap();
+        assert_eq!(imported.options.resource_packs, config.options.resource_packs);
+    }
+
+    #[test]
+    fn test_extra_options_passthrough() {
+        let mut config = GlobalConfig::default();
+        config.apply("toggleSprint", "true").unwrap();
+        config.apply("extra.key_key.sprint", "key.keyboard.left.control").unwrap();
+        config.apply("options.extra.fullscreen", "false").unwrap();
+        config.apply("extra.renderDistance", "8").unwrap();
+        assert_eq!(config.get("extra.renderDistance"), Some("8".to_string()));
+        assert!(config.list().iter().any(|(_, k, v)| k == "extra.key_key.sprint" && v == "key.keyboard.left.control"));
+
+        let txt = config.render_options_txt(&[]);
+        assert!(txt.contains("toggleSprint:true\n"));
+        assert!(txt.contains("key_key.sprint:key.keyboard.left.control\n"));
+        assert!(txt.contains("renderDistance:8\n"));
+        // The passthrough value replaces the generated line
+        assert!(txt.contains("fullscreen:false\n"));
+        assert!(!txt.contains("fullscreen:true"));
+
+        assert!(config.apply("extra.key_key.jump", "space").is_err());
+        assert!(config.apply("extra.bad key", "1").is_err());
+        config.apply("extra.renderDistance", "").unwrap();
+        assert_eq!(config.get("extra.renderDistance"), None);
+
+        let exported = config.export().unwrap();
+        assert!(exported.contains("[options.extra]"));
+        let imported = GlobalConfig::default().import(&exported, ImportMode::Overwrite).unwrap();
+        assert_eq!(imported.options.extra, config.options.extra);
+        assert_eq!(imported.options.toggle_sprint, Some(true));
+    }
+
     #[test]
     fn test_default_config_deserializes_from_empty() {
         let config: GlobalConfig = toml::from_str("").unwrap();
@@ -387,6 +1263,160 @@ language = "java"
         assert_eq!(config.gamerules.do_weather_cycle, Some(false));
     }
 
+    #[test]
+    fn test_export_import_roundtrip() {
+        let mut config = GlobalConfig::default();
+        config.apply("author", "Jane").unwrap();
+        config.apply("gamma", "1.5").unwrap();
+        let exported = config.export().unwrap();
+
+        let imported = GlobalConfig::default().import(&exported, ImportMode::Merge).unwrap();
+        assert_eq!(imported.defaults.author, Some("Jane".to_string()));
+        assert_eq!(imported.options.gamma, Some(1.5));
+        assert_eq!(imported.export().unwrap(), exported);
+    }
+
+    #[test]
+    fn test_import_merge_and_overwrite() {
+        let mut current = GlobalConfig::default();
+        current.apply("author", "Jane").unwrap();
+        current.apply("fullscreen", "false").unwrap();
+        let file = "[defaults]\nlanguage = \"kotlin\"\n\n[options]\ngamma = 2\n";
+
+        let merged = current.import(file, ImportMode::Merge).unwrap();
+        assert_eq!(merged.defaults.author, Some("Jane".to_string()));
+        assert_eq!(merged.defaults.language, Some("kotlin".to_string()));
+        assert_eq!(merged.options.fullscreen, Some(false));
+        assert_eq!(merged.options.gamma, Some(2.0));
+
+        let overwritten = current.import(file, ImportMode::Overwrite).unwrap();
+        assert_eq!(overwritten.defaults.author, None);
+        assert_eq!(overwritten.options.fullscreen, Some(true));
+        assert_eq!(overwritten.defaults.language, Some("kotlin".to_string()));
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_keys() {
+        let file = "[defaults]\nlanguage = \"scala\"\nnickname = \"j\"\n\n[options]\nfullscreen = \"maybe\"\n";
+        let err = GlobalConfig::default()
+            .import(file, ImportMode::Merge)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("defaults.language"));
+        assert!(err.contains("defaults.nickname"));
+        assert!(err.contains("options.fullscreen"));
+        assert!(GlobalConfig::default().import("author = \"x\"", ImportMode::Merge).is_err());
+        assert!(GlobalConfig::default().import("not toml [", ImportMode::Merge).is_err());
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let content = "[defaults]\nauthor = \"Jane\"\n\n[options]\nfullscreen = false\n";
+        let config = GlobalConfig::parse_strict(content).unwrap();
+        assert_eq!(config.defaults.author, Some("Jane".to_string()));
+        assert_eq!(config.options.fullscreen, Some(false));
+        // Keys the file leaves out read as load() reads them
+        assert_eq!(config.options.auto_jump, None);
+        assert_eq!(config.gamerules.weather, Some("clear".to_string()));
+
+        let err = GlobalConfig::parse_strict("[defaults]\nauthr = \"Jane\"\n\n[server]\nspawn_protection = \"big\"\n")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("defaults.authr: Unknown config key"));
+        assert!(err.contains("server.spawn_protection"));
+        assert!(GlobalConfig::parse_strict("[defaults\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_assignments() {
+        let input = "# provisioning\nauthor = Jane Doe\n\nlanguage=kotlin\nextra.lang=\n";
+        let assignments = parse_assignments(input.lines()).unwrap();
+        assert_eq!(
+            assignments,
+            [
+                ("author".to_string(), "Jane Doe".to_string()),
+                ("language".to_string(), "kotlin".to_string()),
+                ("extra.lang".to_string(), String::new()),
+            ]
+        );
+        assert!(parse_assignments(["author"]).is_err());
+        assert!(parse_assignments(["=kotlin"]).is_err());
+    }
+
+    #[test]
+    fn test_apply_all_is_all_or_nothing() {
+        let config = GlobalConfig::default();
+        let ok = [
+            ("author".to_string(), "Jane".to_string()),
+            ("language".to_string(), "kotlin".to_string()),
+        ];
+        let updated = config.apply_all(&ok).unwrap();
+        assert_eq!(updated.defaults.author, Some("Jane".to_string()));
+        assert_eq!(updated.defaults.language, Some("kotlin".to_string()));
+
+        let bad = [
+            ("author".to_string(), "Jane".to_string()),
+            ("language".to_string(), "scala".to_string()),
+            ("nickname".to_string(), "j".to_string()),
+        ];
+        let err = config.apply_all(&bad).err().unwrap().to_string();
+        assert!(err.contains("language: ") && err.contains("nickname: "));
+        assert_eq!(config.defaults.author, None);
+    }
+
+    #[test]
+    fn test_unset() {
+        let mut config = GlobalConfig::default();
+        config.apply("author", "Jane").unwrap();
+        config.apply("extra.renderDistance", "8").unwrap();
+        config.unset("author").unwrap();
+        config.unset("fullscreen").unwrap();
+        config.unset("serverDifficulty").unwrap();
+        config.unset("extra.renderDistance").unwrap();
+        assert_eq!(config.defaults.author, None);
+        assert_eq!(config.options.fullscreen, None);
+        assert_eq!(config.server.difficulty, None);
+        assert!(config.options.extra.is_empty());
+        assert_eq!(config.gamerules.weather, Some("clear".to_string()));
+        assert!(config.unset("extra.renderDistance").is_err());
+        assert!(config.unset("nickname").is_err());
+
+        // An unset default stays unset once saved and loaded again
+        let reloaded: GlobalConfig = toml::from_str(&config.export().unwrap()).unwrap();
+        assert_eq!(reloaded.options.fullscreen, None);
+        assert_eq!(reloaded.options.auto_jump, Some(false));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut config = GlobalConfig::default();
+        config.apply("author", "Jane").unwrap();
+        config.apply("fullscreen", "false").unwrap();
+        config.apply("spawnProtection", "0").unwrap();
+
+        config.reset(Some(ConfigSection::Options));
+        assert_eq!(config.options.fullscreen, Some(true));
+        assert_eq!(config.defaults.author, Some("Jane".to_string()));
+        assert_eq!(config.server.spawn_protection, Some(0));
+
+        config.reset(None);
+        assert_eq!(config.defaults.author, None);
+        assert_eq!(config.server.spawn_protection, Some(16));
+    }
+
+    #[test]
+    fn test_profile_files() {
+        let dir = global_config_dir().unwrap();
+        assert_eq!(profile_file(DEFAULT_PROFILE).unwrap(), dir.join("config.toml"));
+        assert_eq!(profile_file("work").unwrap(), dir.join("profiles").join("work.toml"));
+        assert!(validate_profile_name("work-2").is_ok());
+        for invalid in ["", "Work", "../work", "my profile"] {
+            assert!(validate_profile_name(invalid).is_err(), "{invalid}");
+        }
+    }
+
     #[test]
     fn test_list_returns_all_sections() {
         let config = GlobalConfig::default();
@@ -395,6 +1425,7 @@ language = "java"
         assert!(sections.contains(&"Defaults"));
         assert!(sections.contains(&"Client Options"));
         assert!(sections.contains(&"Game Rules"));
-        assert_eq!(entries.len(), 10);
+        assert!(sections.contains(&"Dev Server"));
+        assert_eq!(entries.len(), 28);
     }
 }
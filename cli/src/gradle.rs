@@ -1,3 +1,4 @@
+use crate::diff;
 use crate::error::Result;
 use std::path::Path;
 
@@ -6,9 +7,17 @@ use std::path::Path;
 /// Looks for lines matching `mc("X.Y.Z", ...)` and adds the loader argument
 /// if not already present. For example, adding "neoforge" to
 /// `mc("1.21.1", "fabric")` produces `mc("1.21.1", "fabric", "neoforge")`.
+/// The change is previewed as a diff before it is written.
 pub fn add_loader_to_settings_kts(dir: &Path, loader: &str) -> Result<()> {
     let path = dir.join("settings.gradle.kts");
-    let content = std::fs::read_to_string(&path)?;
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = add_loader_to_settings_content(&content, loader);
+    diff::apply_edit(&path, &content, &updated)?;
+    Ok(())
+}
+
+/// Pure transform behind `add_loader_to_settings_kts`.
+pub fn add_loader_to_settings_content(content: &str, loader: &str) -> String {
     let loader_arg = format!("\"{}\"", loader);
 
     let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
@@ -27,21 +36,83 @@ pub fn add_loader_to_settings_kts(dir: &Path, loader: &str) -> Result<()> {
         }
     }
 
-    let result = lines.join("\n");
-    let result = if content.ends_with('\n') && !result.ends_with('\n') {
-        result + "\n"
-    } else {
-        result
+    join_preserving_newline(lines, content)
+}
+
+/// Maven repository hosting paperweight-userdev and the Paper API.
+pub const PAPER_MAVEN: &str = "https://repo.papermc.io/repository/maven-public/";
+
+/// Maven repository hosting Quilt Loom and Quilt Loader.
+pub const QUILT_MAVEN: &str = "https://maven.quiltmc.org/repository/release/";
+
+/// Add a Maven repository to the plugin repositories in settings.gradle.kts,
+/// for loaders whose Gradle plugins aren't on the default repositories.
+/// The change is previewed as a diff before it is written.
+pub fn add_plugin_repository_to_settings_kts(dir: &Path, url: &str) -> Result<()> {
+    let path = dir.join("settings.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = add_plugin_repository_content(&content, url);
+    diff::apply_edit(&path, &content, &updated)?;
+    Ok(())
+}
+
+/// Pure transform behind `add_plugin_repository_to_settings_kts`: appends
+/// `maven("<url>")` to the first `repositories { }` block (the one inside
+/// `pluginManagement`) unless it is already listed.
+pub fn add_plugin_repository_content(content: &str, url: &str) -> String {
+    let entry = format!("maven(\"{url}\")");
+    if content.contains(&entry) {
+        return content.to_string();
+    }
+
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let Some(start) = lines.iter().position(|l| l.trim() == "repositories {") else {
+        return content.to_string();
+    };
+    let Some(end) = lines[start..].iter().position(|l| l.trim() == "}").map(|i| start + i) else {
+        return content.to_string();
     };
-    std::fs::write(&path, result)?;
+    let indent: String = lines[end - 1]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    lines.insert(end, format!("{indent}{entry}"));
+
+    join_preserving_newline(lines, content)
+}
+
+/// Include a subproject in settings.gradle.kts.
+/// The change is previewed as a diff before it is written.
+pub fn add_include_to_settings(dir: &Path, project: &str) -> Result<()> {
+    let path = dir.join("settings.gradle.kts");
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = add_include_content(&content, project);
+    diff::apply_edit(&path, &content, &updated)?;
     Ok(())
 }
 
+/// Include a subproject (`include("paper")`) in settings.gradle.kts unless it
+/// is already included.
+pub fn add_include_content(content: &str, project: &str) -> String {
+    let include = format!("include(\"{project}\")");
+    if content.lines().any(|l| l.trim() == include) {
+        return content.to_string();
+    }
+    append_block(content, &format!("{include}\n"))
+}
+
 /// Set or add a property in gradle.properties.
+/// The change is previewed as a diff before it is written.
 pub fn set_gradle_property(dir: &Path, key: &str, value: &str) -> Result<()> {
     let path = dir.join("gradle.properties");
-    let content = std::fs::read_to_string(&path)?;
+    let content = crate::fs::read_to_string(&path)?;
+    let updated = set_property_content(&content, key, value);
+    diff::apply_edit(&path, &content, &updated)?;
+    Ok(())
+}
 
+/// Pure transform behind `set_gradle_property`.
+pub fn set_property_content(content: &str, key: &str, value: &str) -> String {
     let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
     let prefix = format!("{key}=");
     let commented_prefix = format!("# {key}=");
@@ -61,14 +132,255 @@ pub fn set_gradle_property(dir: &Path, key: &str, value: &str) -> Result<()> {
         lines.push(new_line);
     }
 
+    join_preserving_newline(lines, content)
+}
+
+/// The value of `key` in gradle.properties content, if it is set.
+pub fn get_property_content(content: &str, key: &str) -> Option<String> {
+    let prefix = format!("{key}=");
+    content
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix(&prefix))
+        .map(|value| value.trim().to_string())
+}
+
+/// gradle.properties content with the block mcmod manages (headed by
+/// `marker`) replaced by `properties`, or removed when there are none.
+/// Lines outside the block are left alone; the block goes last so its values
+/// win over earlier ones.
+pub fn set_managed_properties(content: &str, marker: &str, properties: &[(String, String)]) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        if line == marker {
+            in_block = true;
+            // The blank line that separated the block goes with it
+            if lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+        } else if in_block && (line.trim().is_empty() || line.starts_with('#')) {
+            in_block = false;
+        }
+        if !in_block {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let mut out: String = lines.iter().map(|l| format!("{l}\n")).collect();
+    if !properties.is_empty() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(marker);
+        out.push('\n');
+        for (key, value) in properties {
+            out.push_str(&format!("{key}={value}\n"));
+        }
+    }
+    out
+}
+
+/// Retarget a Stonecutter version in settings.gradle.kts: the `mc("old", ...)`
+/// line and, if it is the active one, `vcsVersion = "old-<loader>"`.
+pub fn rename_mc_version_content(content: &str, old: &str, new: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mc_call = format!("mc(\"{old}\"");
+    let vcs = format!("\"{old}-");
+
+    for line in &mut lines {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(&mc_call) {
+            *line = line.replacen(&mc_call, &format!("mc(\"{new}\""), 1);
+        } else if trimmed.starts_with("vcsVersion") && line.contains(&vcs) {
+            *line = line.replacen(&vcs, &format!("\"{new}-"), 1);
+        }
+    }
+
+    join_preserving_newline(lines, content)
+}
+
+/// Drop a Stonecutter version from settings.gradle.kts: its `mc("version", ...)`
+/// line, and `vcsVersion` moves to `fallback` (e.g. `1.21.4-fabric`) if it
+/// named that version.
+pub fn remove_mc_version_content(content: &str, version: &str, fallback: &str) -> String {
+    let mc_call = format!("mc(\"{version}\"");
+    let vcs = format!("\"{version}-");
+    let lines: Vec<String> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(&mc_call))
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("vcsVersion") && line.contains(&vcs) {
+                let indent = &line[..line.len() - trimmed.len()];
+                format!("{indent}vcsVersion = \"{fallback}\"")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    join_preserving_newline(lines, content)
+}
+
+/// Marker comment above the blocks `mcmod dep link` adds, so a link is
+/// recognised and not added twice.
+pub fn link_marker(mod_id: &str) -> String {
+    format!("// mcmod:link {mod_id}")
+}
+
+/// Append an `includeBuild` for a sibling Stonecutter project to settings.gradle.kts.
+///
+/// Each Stonecutter version of the sibling (`1.21.1-fabric`, ...) is substituted
+/// for the `<group>:<mod_id>-<version>` module, matching the coordinates
+/// library projects publish under. Does nothing if the link is already present.
+pub fn add_include_build_content(
+    content: &str,
+    rel_path: &str,
+    group: &str,
+    mod_id: &str,
+    projects: &[String],
+) -> String {
+    let marker = link_marker(mod_id);
+    if content.contains(&marker) {
+        return content.to_string();
+    }
+    let list = projects
+        .iter()
+        .map(|p| format!("\"{p}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let block = format!(
+        "{marker}\n\
+         includeBuild(\"{rel_path}\") {{\n\
+         \x20   dependencySubstitution {{\n\
+         \x20       for (version in listOf({list})) {{\n\
+         \x20           substitute(module(\"{group}:{mod_id}-$version\")).using(project(\":$version\"))\n\
+         \x20       }}\n\
+         \x20   }}\n\
+         }}\n"
+    );
+    append_block(content, &block)
+}
+
+/// Append a dependency on a linked sibling to build.gradle.kts, resolved per
+/// Stonecutter version. Does nothing if the link is already present.
+pub fn add_linked_dependency_content(content: &str, group: &str, mod_id: &str) -> String {
+    let marker = link_marker(mod_id);
+    if content.contains(&marker) {
+        return content.to_string();
+    }
+    let block = format!(
+        "{marker}\n\
+         dependencies {{\n\
+         \x20   modImplementation(\"{group}:{mod_id}-${{stonecutter.current.project}}\")\n\
+         }}\n"
+    );
+    append_block(content, &block)
+}
+
+/// Give `plugin` (e.g. `kotlin("jvm")`) a default version in settings.gradle.kts's
+/// `pluginManagement { plugins { } }`, so build scripts can apply it without
+/// one. Unchanged if the plugin already has a version there; None when there
+/// is no `pluginManagement` block to put it in.
+pub fn add_plugin_version_content(content: &str, plugin: &str, version: &str) -> Option<String> {
+    if content.lines().any(|l| l.trim_start().starts_with(&format!("{plugin} version"))) {
+        return Some(content.to_string());
+    }
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let start = lines.iter().position(|l| l.trim() == "pluginManagement {")?;
+    let end = lines[start..].iter().position(|l| l == "}").map(|i| start + i)?;
+    let entry = format!("{plugin} version \"{version}\"");
+    match lines[start..end].iter().position(|l| l.trim() == "plugins {") {
+        Some(i) => lines.insert(start + i + 1, format!("        {entry}")),
+        None => {
+            for (i, line) in ["    plugins {".to_string(), format!("        {entry}"), "    }".to_string()].into_iter().enumerate() {
+                lines.insert(end + i, line);
+            }
+        }
+    }
+    Some(join_preserving_newline(lines, content))
+}
+
+/// Apply `plugin` in build.gradle.kts's top-level `plugins { }` block, after
+/// the plugins already there, with `version` when settings.gradle.kts doesn't
+/// give it one. Unchanged if it is already applied; None when there is no
+/// `plugins` block.
+pub fn add_build_plugin_content(content: &str, plugin: &str, version: Option<&str>) -> Option<String> {
+    if content.lines().any(|l| l.trim() == plugin || l.trim().starts_with(&format!("{plugin} version"))) {
+        return Some(content.to_string());
+    }
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let start = lines.iter().position(|l| l == "plugins {")?;
+    let end = lines[start..].iter().position(|l| l == "}").map(|i| start + i)?;
+    match version {
+        Some(version) => lines.insert(end, format!("    {plugin} version \"{version}\"")),
+        None => lines.insert(end, format!("    {plugin}")),
+    }
+    Some(join_preserving_newline(lines, content))
+}
+
+/// Add `kotlin.srcDir("src/client/kotlin")` next to the client Java source
+/// directory that split-environment projects fold into main on non-Fabric
+/// builds. Unchanged for projects without one.
+pub fn add_client_kotlin_src_content(content: &str) -> String {
+    let java = "java.srcDir(\"src/client/java\")";
+    let kotlin = "kotlin.srcDir(\"src/client/kotlin\")";
+    if content.contains(kotlin) {
+        return content.to_string();
+    }
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let Some(i) = lines.iter().position(|l| l.trim() == java) else {
+        return content.to_string();
+    };
+    let indent: String = lines[i].chars().take_while(|c| c.is_whitespace()).collect();
+    lines.insert(i + 1, format!("{indent}{kotlin}"));
+    join_preserving_newline(lines, content)
+}
+
+/// Append a dependency added with `mcmod add dep` to build.gradle.kts. Each
+/// Stonecutter version reads its artifact version from the
+/// `dep.<name>.<loader>` property, so builds for loaders without one skip it.
+/// Does nothing if the dependency is already present.
+pub fn add_mod_dependency_content(content: &str, name: &str, coordinate: &str, repository: Option<&str>) -> String {
+    let marker = format!("// mcmod dependency: {name}");
+    if content.contains(&marker) {
+        return content.to_string();
+    }
+    let repositories = repository
+        .map(|url| format!("repositories {{\n    maven(\"{url}\")\n}}\n"))
+        .unwrap_or_default();
+    let block = format!(
+        "{marker}\n\
+         {repositories}\
+         dependencies {{\n\
+         \x20   findProperty(\"dep.{name}.${{stonecutter.current.project.substringAfterLast('-')}}\")?.let {{\n\
+         \x20       modImplementation(\"{coordinate}:$it\")\n\
+         \x20   }}\n\
+         }}\n"
+    );
+    append_block(content, &block)
+}
+
+/// Append a block after the existing content, separated by a blank line.
+fn append_block(content: &str, block: &str) -> String {
+    let trimmed = content.trim_end_matches('\n');
+    if trimmed.is_empty() {
+        block.to_string()
+    } else {
+        format!("{trimmed}\n\n{block}")
+    }
+}
+
+/// Join edited lines, keeping the original file's trailing newline.
+fn join_preserving_newline(lines: Vec<String>, original: &str) -> String {
     let result = lines.join("\n");
-    let result = if content.ends_with('\n') && !result.ends_with('\n') {
+    if original.ends_with('\n') && !result.ends_with('\n') {
         result + "\n"
     } else {
         result
-    };
-    std::fs::write(&path, result)?;
-    Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +472,122 @@ mod tests {
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn test_add_include_build_content() {
+        let settings = "rootProject.name = \"content\"\n";
+        let projects = vec!["1.21.1-fabric".to_string(), "1.21.1-neoforge".to_string()];
+        let result = add_include_build_content(settings, "../lib", "com.example.lib", "lib", &projects);
+        assert!(result.starts_with("rootProject.name = \"content\"\n\n// mcmod:link lib\n"));
+        assert!(result.contains("includeBuild(\"../lib\") {"));
+        assert!(result.contains("listOf(\"1.21.1-fabric\", \"1.21.1-neoforge\")"));
+        assert!(result.contains("module(\"com.example.lib:lib-$version\")).using(project(\":$version\"))"));
+
+        let again = add_include_build_content(&result, "../lib", "com.example.lib", "lib", &projects);
+        assert_eq!(again, result);
+    }
+
+    #[test]
+    fn test_add_linked_dependency_content() {
+        let build = "plugins {\n    id(\"gg.meza.stonecraft\")\n}\n";
+        let result = add_linked_dependency_content(build, "com.example.lib", "lib");
+        assert!(result.contains(
+            "    modImplementation(\"com.example.lib:lib-${stonecutter.current.project}\")\n"
+        ));
+        assert_eq!(add_linked_dependency_content(&result, "com.example.lib", "lib"), result);
+    }
+
+    #[test]
+    fn test_rename_mc_version_content() {
+        let settings = "        mc(\"1.21.1\", \"fabric\")\n        mc(\"1.21.4\", \"fabric\")\n        vcsVersion = \"1.21.4-fabric\"\n";
+        assert_eq!(
+            remove_mc_version_content(settings, "1.21.4", "1.21.1-fabric"),
+            "        mc(\"1.21.1\", \"fabric\")\n        vcsVersion = \"1.21.1-fabric\"\n"
+        );
+        assert_eq!(
+            remove_mc_version_content(settings, "1.21.1", "1.21.4-fabric"),
+            "        mc(\"1.21.4\", \"fabric\")\n        vcsVersion = \"1.21.4-fabric\"\n"
+        );
+        let result = rename_mc_version_content(settings, "1.21.4", "1.21.11");
+        assert_eq!(
+            result,
+            "        mc(\"1.21.1\", \"fabric\")\n        mc(\"1.21.11\", \"fabric\")\n        vcsVersion = \"1.21.11-fabric\"\n"
+        );
+        // A prefix of another version must not match
+        assert_eq!(rename_mc_version_content(&result, "1.21.1", "1.21.2").matches("1.21.11").count(), 2);
+    }
+
+    #[test]
+    fn test_add_plugin_repository_content() {
+        let settings = "pluginManagement {\n    repositories {\n        mavenCentral()\n        maven(\"https://maven.fabricmc.net/\")\n    }\n}\n\nplugins {\n}\n";
+        let url = "https://maven.quiltmc.org/repository/release/";
+        let updated = add_plugin_repository_content(settings, url);
+        assert!(updated.contains(
+            "        maven(\"https://maven.fabricmc.net/\")\n        maven(\"https://maven.quiltmc.org/repository/release/\")\n    }\n}"
+        ));
+        assert_eq!(add_plugin_repository_content(&updated, url), updated);
+    }
+
+    #[test]
+    fn test_add_include_content() {
+        let settings = "rootProject.name = \"mymod\"\n";
+        let updated = add_include_content(settings, "paper");
+        assert_eq!(updated, "rootProject.name = \"mymod\"\n\ninclude(\"paper\")\n");
+        assert_eq!(add_include_content(&updated, "paper"), updated);
+    }
+
+    #[test]
+    fn test_set_managed_properties() {
+        let marker = "# From [gradle.fabric] in mcmod.toml";
+        let props = vec![("fabric.loom.multiProjectOptimisation".to_string(), "true".to_string())];
+        let written = set_managed_properties("a=1\n", marker, &props);
+        assert_eq!(written, format!("a=1\n\n{marker}\nfabric.loom.multiProjectOptimisation=true\n"));
+        assert_eq!(set_managed_properties(&written, marker, &props), written);
+
+        let edited = format!("{written}\n# mine\nb=2\n");
+        let changed = set_managed_properties(&edited, marker, &[("x".to_string(), "y".to_string())]);
+        assert_eq!(changed, format!("a=1\n\n# mine\nb=2\n\n{marker}\nx=y\n"));
+        assert_eq!(set_managed_properties(&changed, marker, &[]), "a=1\n\n# mine\nb=2\n");
+        assert_eq!(set_managed_properties(&format!("{marker}\nx=y\n"), marker, &[]), "");
+    }
+
+    #[test]
+    fn test_add_mod_dependency_content() {
+        let updated = add_mod_dependency_content("plugins {}\n", "cloth-config", "maven.modrinth:cloth-config", Some("https://api.modrinth.com/maven"));
+        assert_eq!(
+            updated,
+            "plugins {}\n\n// mcmod dependency: cloth-config\nrepositories {\n    maven(\"https://api.modrinth.com/maven\")\n}\n\
+             dependencies {\n    findProperty(\"dep.cloth-config.${stonecutter.current.project.substringAfterLast('-')}\")?.let {\n\
+             \x20       modImplementation(\"maven.modrinth:cloth-config:$it\")\n    }\n}\n"
+        );
+        assert_eq!(add_mod_dependency_content(&updated, "cloth-config", "x:y", None), updated);
+        assert!(!add_mod_dependency_content("", "lib", "a:lib", None).contains("repositories"));
+    }
+
+    #[test]
+    fn test_kotlin_plugin_edits() {
+        let settings = "pluginManagement {\n    repositories {\n        mavenCentral()\n    }\n}\n\nplugins {\n    id(\"x\") version \"1\"\n}\n";
+        let updated = add_plugin_version_content(settings, "kotlin(\"jvm\")", "2.1.0").unwrap();
+        assert_eq!(
+            updated,
+            "pluginManagement {\n    repositories {\n        mavenCentral()\n    }\n    plugins {\n        kotlin(\"jvm\") version \"2.1.0\"\n    }\n}\n\nplugins {\n    id(\"x\") version \"1\"\n}\n"
+        );
+        assert_eq!(add_plugin_version_content(&updated, "kotlin(\"jvm\")", "2.1.0").unwrap(), updated);
+        let second = add_plugin_version_content(&updated, "kotlin(\"plugin.serialization\")", "2.1.0").unwrap();
+        assert!(second.contains("    plugins {\n        kotlin(\"plugin.serialization\") version \"2.1.0\"\n        kotlin(\"jvm\")"));
+        assert_eq!(add_plugin_version_content("plugins {\n}\n", "kotlin(\"jvm\")", "2.1.0"), None);
+
+        let build = "plugins {\n    id(\"gg.meza.stonecraft\")\n}\n\nsourceSets.main {\n        java.srcDir(\"src/client/java\")\n}\n";
+        let updated = add_client_kotlin_src_content(&add_build_plugin_content(build, "kotlin(\"jvm\")", None).unwrap());
+        assert_eq!(
+            updated,
+            "plugins {\n    id(\"gg.meza.stonecraft\")\n    kotlin(\"jvm\")\n}\n\nsourceSets.main {\n        java.srcDir(\"src/client/java\")\n        kotlin.srcDir(\"src/client/kotlin\")\n}\n"
+        );
+        assert_eq!(add_client_kotlin_src_content(&add_build_plugin_content(&updated, "kotlin(\"jvm\")", None).unwrap()), updated);
+        // Without a version in settings.gradle.kts the build script pins it
+        let versioned = add_build_plugin_content(build, "kotlin(\"jvm\")", Some("2.1.0")).unwrap();
+        assert!(versioned.contains("    kotlin(\"jvm\") version \"2.1.0\"\n}\n"));
+        assert_eq!(add_build_plugin_content(&versioned, "kotlin(\"jvm\")", Some("2.1.0")).unwrap(), versioned);
+        assert_eq!(add_build_plugin_content("dependencies {\n}\n", "kotlin(\"jvm\")", None), None);
+    }
 }
@@ -43,6 +43,45 @@ pub fn install_path() -> Result<PathBuf> {
     }
 }
 
+/// Path where an update is staged when the installed binary can't be replaced
+/// in place (e.g. locked by antivirus or another shell on Windows).
+pub fn staged_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".staged");
+    target.with_file_name(name)
+}
+
+/// Completes a previously staged update by swapping the staged binary into place.
+///
+/// Returns Ok(true) if a staged binary was applied, Ok(false) if none was pending.
+/// The running executable is renamed aside first, which Windows permits even
+/// while the file is in use.
+pub fn apply_staged_update(target: &Path) -> Result<bool> {
+    let staged = staged_path(target);
+    if !staged.exists() {
+        return Ok(false);
+    }
+
+    let mut old_name = target.file_name().unwrap_or_default().to_os_string();
+    old_name.push(".old");
+    let old_path = target.with_file_name(old_name);
+    if old_path.exists() {
+        let _ = std::fs::remove_file(&old_path);
+    }
+
+    if target.exists() {
+        std::fs::rename(target, &old_path)?;
+    }
+    if let Err(e) = std::fs::rename(&staged, target) {
+        // Put the previous binary back so mcmod keeps working
+        let _ = std::fs::rename(&old_path, target);
+        return Err(e.into());
+    }
+    // Best effort: the old binary may still be running
+    let _ = std::fs::remove_file(&old_path);
+    Ok(true)
+}
+
 /// Returns whether the given directory is present on the system PATH.
 pub fn is_on_path(dir: &Path) -> bool {
     if let Ok(path_var) = std::env::var("PATH") {
@@ -94,6 +133,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_staged_path() {
+        assert_eq!(
+            staged_path(Path::new("/bin/mcmod.exe")),
+            PathBuf::from("/bin/mcmod.exe.staged")
+        );
+        assert_eq!(staged_path(Path::new("/bin/mcmod")), PathBuf::from("/bin/mcmod.staged"));
+    }
+
+    #[test]
+    fn test_apply_staged_update() {
+        let dir = std::env::temp_dir().join(format!("mcmod_install_staged_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("mcmod.exe");
+        std::fs::write(&target, b"old").unwrap();
+
+        // Nothing staged: no-op
+        assert!(!apply_staged_update(&target).unwrap());
+        assert_eq!(std::fs::read(&target).unwrap(), b"old");
+
+        std::fs::write(staged_path(&target), b"new").unwrap();
+        assert!(apply_staged_update(&target).unwrap());
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        assert!(!staged_path(&target).exists());
+        assert!(!dir.join("mcmod.exe.old").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_is_on_path_with_nonexistent_dir() {
         assert!(!is_on_path(Path::new("/this/path/definitely/does/not/exist/anywhere")));
@@ -0,0 +1,374 @@
+//! Read-only access to built jars (zip archives) for post-build checks, and
+//! [`stored_zip`] for writing simple archives.
+//!
+//! Only what Gradle writes is supported for reading: stored and deflated entries in a
+//! single-disk archive without zip64. Entries are listed from the central
+//! directory and inflated on demand.
+
+use crate::error::{Context, McmodError, Result};
+use std::path::Path;
+
+const END_OF_CENTRAL_DIR: u32 = 0x0605_4b50;
+const CENTRAL_FILE_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+/// One entry from the central directory.
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    local_offset: usize,
+}
+
+/// A jar loaded into memory.
+pub struct Jar {
+    data: Vec<u8>,
+    entries: Vec<Entry>,
+}
+
+impl Jar {
+    pub fn open(path: &Path) -> Result<Jar> {
+        let data = crate::fs::read(path).with_context(|| format!("Reading {}", path.display()))?;
+        Jar::from_bytes(data).with_context(|| format!("Opening {}", path.display()))
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Jar> {
+        // The end record is at least 22 bytes and may be followed by a comment
+        let eocd = (0..data.len().saturating_sub(21))
+            .rev()
+            .find(|&i| u32_at(&data, i) == Some(END_OF_CENTRAL_DIR))
+            .ok_or_else(|| invalid("no end of central directory record"))?;
+        let count = u16_at(&data, eocd + 10).ok_or_else(|| invalid("truncated end record"))? as usize;
+        let mut pos = u32_at(&data, eocd + 16).ok_or_else(|| invalid("truncated end record"))? as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if u32_at(&data, pos) != Some(CENTRAL_FILE_HEADER) {
+                return Err(invalid("bad central directory entry"));
+            }
+            let field = |offset: usize| u16_at(&data, pos + offset).ok_or_else(|| invalid("truncated entry"));
+            let method = field(10)?;
+            let compressed_size = u32_at(&data, pos + 20).ok_or_else(|| invalid("truncated entry"))? as usize;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let local_offset = u32_at(&data, pos + 42).ok_or_else(|| invalid("truncated entry"))? as usize;
+            let name = data
+                .get(pos + 46..pos + 46 + name_len)
+                .ok_or_else(|| invalid("truncated entry name"))?;
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                local_offset,
+            });
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Jar { data, entries })
+    }
+
+    /// Entry names in archive order, directories included (they end in `/`).
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|e| e.name == name)
+    }
+
+    /// The uncompressed content of `name`, or None when the jar doesn't have it.
+    pub fn read(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let Some(entry) = self.entries.iter().find(|e| e.name == name) else {
+            return Ok(None);
+        };
+        let pos = entry.local_offset;
+        if u32_at(&self.data, pos) != Some(LOCAL_FILE_HEADER) {
+            return Err(invalid(&format!("bad local header for {name}")));
+        }
+        let name_len = u16_at(&self.data, pos + 26).ok_or_else(|| invalid("truncated local header"))? as usize;
+        let extra_len = u16_at(&self.data, pos + 28).ok_or_else(|| invalid("truncated local header"))? as usize;
+        let start = pos + 30 + name_len + extra_len;
+        let raw = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(|| invalid(&format!("{name} runs past the end of the archive")))?;
+        match entry.method {
+            0 => Ok(Some(raw.to_vec())),
+            8 => inflate(raw).map(Some),
+            other => Err(invalid(&format!("{name} uses unsupported compression method {other}"))),
+        }
+    }
+
+    pub fn read_to_string(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.read(name)?.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+fn invalid(message: &str) -> McmodError {
+    McmodError::Other(format!("Not a readable jar: {message}"))
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+/// LSB-first bit reader over a deflate stream.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn take(&mut self, n: u32) -> Result<u32> {
+        while self.count < n {
+            let byte = *self.data.get(self.pos).ok_or_else(|| invalid("deflate stream ends early"))?;
+            self.pos += 1;
+            self.buffer |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1u64 << n) - 1) as u32;
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Drop the bits left in the current byte.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code as symbol counts per length plus symbols in code order.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= bits.take(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("bad Huffman code"))
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order code length code lengths are stored in a dynamic block header.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decompress a raw deflate stream (RFC 1951).
+fn inflate(data: &[u8]) -> Result<Vec<u8>> {
+    let mut bits = Bits { data, pos: 0, buffer: 0, count: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => {
+                bits.align();
+                let header = data.get(bits.pos..bits.pos + 4).ok_or_else(|| invalid("truncated stored block"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                let start = bits.pos + 4;
+                let block = data.get(start..start + len).ok_or_else(|| invalid("truncated stored block"))?;
+                out.extend_from_slice(block);
+                bits.pos = start + len;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let literals = Huffman::new(&lengths);
+                let distances = Huffman::new(&[5; 30]);
+                inflate_block(&mut bits, &literals, &distances, &mut out)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_tables(&mut bits)?;
+                inflate_block(&mut bits, &literals, &distances, &mut out)?;
+            }
+            _ => return Err(invalid("reserved deflate block type")),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn dynamic_tables(bits: &mut Bits) -> Result<(Huffman, Huffman)> {
+    let literal_count = bits.take(5)? as usize + 257;
+    let distance_count = bits.take(5)? as usize + 1;
+    let code_count = bits.take(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for &i in &CODE_LENGTH_ORDER[..code_count] {
+        code_lengths[i] = bits.take(3)? as u8;
+    }
+    let code = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.last().ok_or_else(|| invalid("length repeat with no previous length"))?;
+                (previous, 3 + bits.take(2)? as usize)
+            }
+            17 => (0, 3 + bits.take(3)? as usize),
+            _ => (0, 11 + bits.take(7)? as usize),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err(invalid("code lengths overrun"));
+    }
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+fn inflate_block(bits: &mut Bits, literals: &Huffman, distances: &Huffman, out: &mut Vec<u8>) -> Result<()> {
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let i = symbol - 257;
+                if i >= LENGTH_BASE.len() {
+                    return Err(invalid("bad length code"));
+                }
+                let length = LENGTH_BASE[i] as usize + bits.take(LENGTH_EXTRA[i] as u32)? as usize;
+                let d = distances.decode(bits)? as usize;
+                if d >= DIST_BASE.len() {
+                    return Err(invalid("bad distance code"));
+                }
+                let distance = DIST_BASE[d] as usize + bits.take(DIST_EXTRA[d] as u32)? as usize;
+                if distance > out.len() {
+                    return Err(invalid("distance before start of output"));
+                }
+                let start = out.len() - distance;
+                for k in 0..length {
+                    out.push(out[start + k]);
+                }
+            }
+        }
+    }
+}
+
+/// A zip archive of uncompressed (stored) entries, e.g. a bug report bundle
+/// or a jar for tests of code that reads jars.
+pub fn stored_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut central = Vec::new();
+    for (name, content) in files {
+        let offset = data.len() as u32;
+        let mut header = Vec::new();
+        header.extend(LOCAL_FILE_HEADER.to_le_bytes());
+        header.extend([20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]); // version, flags, method, time, date (1980-01-01)
+        header.extend(crate::util::crc32(content).to_le_bytes());
+        header.extend((content.len() as u32).to_le_bytes());
+        header.extend((content.len() as u32).to_le_bytes());
+        header.extend((name.len() as u16).to_le_bytes());
+        header.extend(0u16.to_le_bytes());
+        data.extend(&header);
+        data.extend(name.as_bytes());
+        data.extend(*content);
+
+        central.extend(CENTRAL_FILE_HEADER.to_le_bytes());
+        central.extend([20, 0]); // version made by
+        central.extend(&header[4..]);
+        central.extend([0; 10]); // comment length, disk, internal and external attributes
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+    let central_offset = data.len() as u32;
+    data.extend(&central);
+    data.extend(END_OF_CENTRAL_DIR.to_le_bytes());
+    data.extend([0; 4]);
+    data.extend((files.len() as u16).to_le_bytes());
+    data.extend((files.len() as u16).to_le_bytes());
+    data.extend((central.len() as u32).to_le_bytes());
+    data.extend(central_offset.to_le_bytes());
+    data.extend([0; 2]);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_inflate() {
+        // zlib with Z_FIXED: one fixed-Huffman block
+        let fixed = hex("ab56ca4c51b252502a492d2ec9cd4f51aa0500");
+        assert_eq!(inflate(&fixed).unwrap(), b"{\"id\": \"testmod\"}");
+
+        // zlib level 9 on repetitive text: one dynamic-Huffman block with back-references
+        let dynamic = hex(concat!(
+            "75d43b4ec3601444e19e5558aea3283393271b60193458510a8b02ef5fd0fce386d3dde2549f46779ae6d7b6acc76df9d9",
+            "d6efafe373593f4ff3fb347f2ceb749a0f6fd33f814620083c024390110482f308ce105c467081e03a822b04b711dc20b8",
+            "8fe00ec163040f822aa5d072c7244d9553e4a9828a44555291a98a2a52555945ae2aac4856a515d9aab8225d57d7a4ebea",
+            "1ab7ba8f95745d5d93aeab6bd275754dbaaeae49d7d535e9baba265d57d7a49bea867453dd906eaa1bfc05fb3320dd5437",
+            "a49bea867453dd906eaa1bd24d7543baa9eedf75f805",
+        ));
+        let expected: Vec<String> = (0..40).map(|i| format!("  \"item.testmod.gem_{i}\": \"Gem {i}\",")).collect();
+        assert_eq!(String::from_utf8(inflate(&dynamic).unwrap()).unwrap(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_read_stored_zip() {
+        let jar = Jar::from_bytes(stored_zip(&[("META-INF/", b""), ("a.txt", b"hello")])).unwrap();
+        assert_eq!(jar.names().collect::<Vec<_>>(), ["META-INF/", "a.txt"]);
+        assert_eq!(jar.read_to_string("a.txt").unwrap().as_deref(), Some("hello"));
+        assert!(jar.read("b.txt").unwrap().is_none());
+        assert!(Jar::from_bytes(b"not a zip".to_vec()).is_err());
+    }
+}
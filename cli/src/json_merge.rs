@@ -0,0 +1,350 @@
+//! Merging generated JSON into files that may already exist and hold user
+//! edits: lang files, sounds.json, tags, and mixins.json.
+//!
+//! Objects merge key by key and arrays merge as an ordered union, so running a
+//! generator twice adds nothing the second time. Existing keys keep their
+//! position and new keys are appended in the order the generator lists them,
+//! so reruns produce the same file and small diffs. Files with duplicate keys
+//! are rejected rather than silently collapsed, and a key whose existing value
+//! differs from the generated one is resolved by an [`OnConflict`] policy.
+
+use crate::error::{Context, McmodError, Result};
+use colored::Colorize;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::fmt;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// A JSON value that keeps object keys in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Parse JSON text, rejecting objects that repeat a key.
+    pub fn parse(content: &str) -> Result<Json> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Pretty-printed with two-space indents and a trailing newline.
+    pub fn to_pretty_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    fn position(entries: &[(String, Json)], key: &str) -> Option<usize> {
+        entries.iter().position(|(k, _)| k == key)
+    }
+}
+
+impl<'de> Deserialize<'de> for Json {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Json, D::Error> {
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Json;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<Json, E> {
+        Ok(Json::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> std::result::Result<Json, E> {
+        Ok(Json::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> std::result::Result<Json, E> {
+        Ok(Json::Number(v.into()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> std::result::Result<Json, E> {
+        Ok(Json::Number(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Json, E> {
+        serde_json::Number::from_f64(v)
+            .map(Json::Number)
+            .ok_or_else(|| E::custom("number out of range"))
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Json, E> {
+        Ok(Json::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> std::result::Result<Json, E> {
+        Ok(Json::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Json, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Json::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Json, A::Error> {
+        let mut entries: Vec<(String, Json)> = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if entries.iter().any(|(k, _)| *k == key) {
+                return Err(de::Error::custom(format!("duplicate key \"{key}\"")));
+            }
+            let value = map.next_value()?;
+            entries.push((key, value));
+        }
+        Ok(Json::Object(entries))
+    }
+}
+
+impl Serialize for Json {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Json::Null => serializer.serialize_unit(),
+            Json::Bool(b) => serializer.serialize_bool(*b),
+            Json::Number(n) => n.serialize(serializer),
+            Json::String(s) => serializer.serialize_str(s),
+            Json::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Json::Object(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// What to do when a key already holds a different value than the generated one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keep the file's value (user edits win).
+    Keep,
+    /// Overwrite it with the generated value (the generator's input is the source of truth).
+    Replace,
+    /// Ask in an interactive terminal; keep the file's value otherwise.
+    Ask,
+}
+
+/// What a merge changed.
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeReport {
+    /// The file didn't exist and was written from the generated JSON.
+    pub created: bool,
+    /// Dotted paths of keys and array items that were added.
+    pub added: Vec<String>,
+    /// Paths where the file's value differed and was replaced.
+    pub replaced: Vec<String>,
+    /// Paths where the file's value differed and was kept.
+    pub kept: Vec<String>,
+}
+
+impl MergeReport {
+    pub fn changed(&self) -> bool {
+        self.created || !self.added.is_empty() || !self.replaced.is_empty()
+    }
+}
+
+/// Merge `incoming` into `existing`. Objects merge recursively, arrays gain the
+/// incoming items they don't already contain, and differing scalars (or
+/// mismatched types) are conflicts resolved by `on_conflict`.
+pub fn merge(existing: &mut Json, incoming: Json, on_conflict: OnConflict) -> Result<MergeReport> {
+    let mut report = MergeReport::default();
+    merge_at(existing, incoming, "", on_conflict, &mut report)?;
+    Ok(report)
+}
+
+fn merge_at(
+    existing: &mut Json,
+    incoming: Json,
+    path: &str,
+    on_conflict: OnConflict,
+    report: &mut MergeReport,
+) -> Result<()> {
+    match (existing, incoming) {
+        (Json::Object(entries), Json::Object(new_entries)) => {
+            for (key, value) in new_entries {
+                let child = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                match Json::position(entries, &key) {
+                    Some(i) => merge_at(&mut entries[i].1, value, &child, on_conflict, report)?,
+                    None => {
+                        entries.push((key, value));
+                        report.added.push(child);
+                    }
+                }
+            }
+        }
+        (Json::Array(items), Json::Array(new_items)) => {
+            for item in new_items {
+                if !items.contains(&item) {
+                    report.added.push(format!("{path}[{}]", items.len()));
+                    items.push(item);
+                }
+            }
+        }
+        (existing, incoming) => {
+            if *existing != incoming {
+                if resolve_conflict(path, existing, &incoming, on_conflict)? {
+                    *existing = incoming;
+                    report.replaced.push(path.to_string());
+                } else {
+                    report.kept.push(path.to_string());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true to take the incoming value.
+fn resolve_conflict(path: &str, existing: &Json, incoming: &Json, on_conflict: OnConflict) -> Result<bool> {
+    match on_conflict {
+        OnConflict::Keep => Ok(false),
+        OnConflict::Replace => Ok(true),
+        OnConflict::Ask => {
+            if !std::io::stdin().is_terminal() || crate::fs::is_in_memory() {
+                return Ok(false);
+            }
+            let existing = serde_json::to_string(existing)?;
+            let incoming = serde_json::to_string(incoming)?;
+            println!("{}", format!("  '{path}' differs from the generated value:").yellow());
+            println!("    current:   {existing}");
+            println!("    generated: {incoming}");
+            let choice = dialoguer::Select::new()
+                .with_prompt("  Which value should be kept?")
+                .items(["current", "generated"])
+                .default(0)
+                .interact()
+                .map_err(|e| McmodError::Other(e.to_string()))?;
+            Ok(choice == 1)
+        }
+    }
+}
+
+/// Merge generated JSON text into the file at `path`, creating it when missing.
+/// The file is only rewritten when the merge added or replaced something, so
+/// an up-to-date file keeps its formatting. Kept conflicts are reported as a
+/// warning.
+pub fn merge_into_file(path: &Path, generated: &str, on_conflict: OnConflict) -> Result<MergeReport> {
+    let incoming = Json::parse(generated).context("Parsing generated JSON")?;
+    if !crate::fs::exists(path) {
+        crate::util::write_file(path, generated)?;
+        return Ok(MergeReport {
+            created: true,
+            ..MergeReport::default()
+        });
+    }
+
+    let content = crate::fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+    let mut existing = Json::parse(&content).with_context(|| format!("Parsing {}", path.display()))?;
+    let report = merge(&mut existing, incoming, on_conflict)?;
+    if report.changed() {
+        crate::util::write_file(path, &existing.to_pretty_string()?)?;
+    }
+    if !report.kept.is_empty() {
+        eprintln!(
+            "  {}",
+            format!(
+                "Kept existing values in {} for: {}",
+                path.display(),
+                report.kept.join(", ")
+            )
+            .yellow()
+        );
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_preserves_order_and_rejects_duplicates() {
+        let json = Json::parse(r#"{"b": 1, "a": [true, null, "x"], "c": {"z": 1.5, "y": -2}}"#).unwrap();
+        assert_eq!(
+            json.to_pretty_string().unwrap(),
+            "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    null,\n    \"x\"\n  ],\n  \"c\": {\n    \"z\": 1.5,\n    \"y\": -2\n  }\n}\n"
+        );
+
+        let err = Json::parse("{\"item.x\": \"A\",\n \"item.x\": \"B\"}").unwrap_err().to_string();
+        assert!(err.contains("duplicate key \"item.x\""), "{err}");
+        assert!(err.contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn test_merge_lang_appends_new_keys() {
+        let mut lang = Json::parse(r#"{"item.mymod.ruby": "Ruby", "block.mymod.ore": "Ore"}"#).unwrap();
+        let incoming = Json::parse(r#"{"block.mymod.ore": "Ore", "item.mymod.gem": "Gem"}"#).unwrap();
+        let report = merge(&mut lang, incoming.clone(), OnConflict::Keep).unwrap();
+        assert_eq!(report.added, ["item.mymod.gem"]);
+        let keys: Vec<&str> = match &lang {
+            Json::Object(entries) => entries.iter().map(|(k, _)| k.as_str()).collect(),
+            _ => unreachable!(),
+        };
+        assert_eq!(keys, ["item.mymod.ruby", "block.mymod.ore", "item.mymod.gem"]);
+
+        // Merging the same content again is a no-op
+        assert!(!merge(&mut lang, incoming, OnConflict::Keep).unwrap().changed());
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let existing = Json::parse(r#"{"item.mymod.gem": "Shiny Gem", "injectors": {"defaultRequire": 1}}"#).unwrap();
+        let incoming = Json::parse(r#"{"item.mymod.gem": "Gem", "injectors": {"defaultRequire": 1}}"#).unwrap();
+
+        let mut kept = existing.clone();
+        let report = merge(&mut kept, incoming.clone(), OnConflict::Keep).unwrap();
+        assert_eq!(report.kept, ["item.mymod.gem"]);
+        assert!(!report.changed());
+        assert_eq!(kept, existing);
+
+        let mut replaced = existing.clone();
+        let report = merge(&mut replaced, incoming.clone(), OnConflict::Replace).unwrap();
+        assert_eq!(report.replaced, ["item.mymod.gem"]);
+        assert_eq!(replaced, incoming);
+
+        // Non-interactive Ask keeps the file's value
+        let mut asked = existing.clone();
+        let (report, _) = crate::fs::in_memory(|| merge(&mut asked, incoming, OnConflict::Ask).unwrap());
+        assert_eq!(report.kept, ["item.mymod.gem"]);
+    }
+
+    #[test]
+    fn test_merge_into_file_unions_tag_values() {
+        let path = Path::new("/memory/pack/data/minecraft/tags/function/load.json");
+        let (result, fs) = crate::fs::in_memory(|| {
+            merge_into_file(path, "{\n  \"values\": [\"dev:load\"]\n}\n", OnConflict::Keep)?;
+            crate::util::write_file(path, "{\"replace\": false, \"values\": [\"mypack:setup\"]}")?;
+            let first = merge_into_file(path, "{\"values\": [\"dev:load\"]}", OnConflict::Keep)?;
+            let second = merge_into_file(path, "{\"values\": [\"dev:load\"]}", OnConflict::Keep)?;
+            Ok::<_, McmodError>((first, second))
+        });
+        let (first, second) = result.unwrap();
+        assert_eq!(first.added, ["values[1]"]);
+        assert!(!second.changed());
+        assert_eq!(
+            fs.read_string(path).unwrap(),
+            "{\n  \"replace\": false,\n  \"values\": [\n    \"mypack:setup\",\n    \"dev:load\"\n  ]\n}\n"
+        );
+    }
+}
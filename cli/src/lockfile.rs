@@ -0,0 +1,300 @@
+use crate::config::VersionTarget;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const LOCK_FILE: &str = "mcmod.lock";
+
+const HEADER: &str = "# Written by mcmod. Commit it: the exact dependency versions each target\n\
+                      # resolved to and where they came from (reused by `mcmod add` and\n\
+                      # `mcmod upgrade --locked`), updates held back by `mcmod upgrade -i`\n\
+                      # (skipped by `mcmod upgrade` until accepted again), placeholder\n\
+                      # assets created by `mcmod assets stub`, the hashes of generated\n\
+                      # files, so `mcmod add` asks before replacing ones you changed, and\n\
+                      # the steps of an `mcmod add` that failed partway, which rerunning\n\
+                      # it skips.\n\n";
+
+/// Source of versions taken from mcmod's built-in version table.
+pub const BUILT_IN_SOURCE: &str = "built-in";
+
+/// Source of pins that were already in mcmod.toml when they were first locked.
+pub const CONFIG_SOURCE: &str = "mcmod.toml";
+
+/// mcmod.lock: state mcmod records about the project that isn't configuration.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Lockfile {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved: Vec<Resolved>,
+    #[serde(default, rename = "hold", skip_serializing_if = "Vec::is_empty")]
+    pub holds: Vec<Hold>,
+    #[serde(default, rename = "stub", skip_serializing_if = "Vec::is_empty")]
+    pub stubs: Vec<Stub>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generated: Vec<Generated>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<Pending>,
+}
+
+/// The version `dependency` of the `target` Minecraft version resolved to.
+/// `source` is the metadata URL it was looked up from (or [`BUILT_IN_SOURCE`]
+/// / [`CONFIG_SOURCE`]), and `resolved_at` an RFC 3339 UTC timestamp.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Resolved {
+    pub target: String,
+    pub dependency: String,
+    pub version: String,
+    pub source: String,
+    pub resolved_at: String,
+}
+
+/// An update the user declined: `dependency` of the `target` Minecraft
+/// version stays at `version` instead of moving to `skipped`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Hold {
+    pub target: String,
+    pub dependency: String,
+    pub version: String,
+    pub skipped: String,
+}
+
+/// A placeholder asset written by `mcmod assets stub`. `sha256` is the hash
+/// it was written with, so `--clean-stubs` leaves files that were edited since.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Stub {
+    /// Path relative to the project directory.
+    pub path: String,
+    pub sha256: String,
+}
+
+/// A file written from a template by init or `mcmod add`. `sha256` is its
+/// hash as mcmod last wrote it, so later writes can tell whether the user
+/// changed it since (see `conflict`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Generated {
+    /// Path relative to the project directory.
+    pub path: String,
+    pub sha256: String,
+}
+
+/// An operation that started but hasn't finished, e.g. `add neoforge`, and
+/// the steps of it that are done.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pending {
+    pub operation: String,
+    #[serde(default)]
+    pub done: Vec<String>,
+}
+
+impl Lockfile {
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(LOCK_FILE)
+    }
+
+    /// Load mcmod.lock, or an empty lockfile if the project has none yet.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = Self::path(dir);
+        if !crate::fs::exists(&path) {
+            return Ok(Self::default());
+        }
+        let content = crate::fs::read_to_string(&path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let content = format!("{HEADER}{}", toml::to_string_pretty(self)?);
+        crate::util::write_file(&Self::path(dir), &content)
+    }
+
+    pub fn resolved(&self, target: &str, dependency: &str) -> Option<&Resolved> {
+        self.resolved
+            .iter()
+            .find(|r| r.target == target && r.dependency == dependency)
+    }
+
+    /// The locked version of `dependency` for `target`, if there is one.
+    pub fn locked(&self, target: &str, dependency: &str) -> Option<String> {
+        self.resolved(target, dependency).map(|r| r.version.clone())
+    }
+
+    /// Bring the resolved entries in line with `targets`' pins. Pins already
+    /// locked at the same version keep their entry and timestamp; new ones are
+    /// recorded now with the source `source(target, dependency)` names, or
+    /// [`CONFIG_SOURCE`] when it names none. Entries for pins the project no
+    /// longer has are dropped.
+    pub fn record_targets(&mut self, targets: &[VersionTarget], source: impl Fn(&str, &str) -> Option<String>) {
+        let now = timestamp(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        );
+        let mut resolved = Vec::new();
+        for target in targets {
+            for (dependency, version) in pins(target) {
+                if version.is_empty() {
+                    continue;
+                }
+                match self.resolved(&target.minecraft, dependency) {
+                    Some(entry) if entry.version == version => resolved.push(entry.clone()),
+                    _ => resolved.push(Resolved {
+                        target: target.minecraft.clone(),
+                        dependency: dependency.to_string(),
+                        version: version.to_string(),
+                        source: source(&target.minecraft, dependency).unwrap_or_else(|| CONFIG_SOURCE.to_string()),
+                        resolved_at: now.clone(),
+                    }),
+                }
+            }
+        }
+        self.resolved = resolved;
+    }
+
+    pub fn hold(&self, target: &str, dependency: &str) -> Option<&Hold> {
+        self.holds
+            .iter()
+            .find(|h| h.target == target && h.dependency == dependency)
+    }
+
+    /// Record a hold, replacing any earlier one for the same dependency.
+    pub fn set_hold(&mut self, hold: Hold) {
+        self.release(&hold.target, &hold.dependency);
+        self.holds.push(hold);
+    }
+
+    pub fn release(&mut self, target: &str, dependency: &str) {
+        self.holds
+            .retain(|h| !(h.target == target && h.dependency == dependency));
+    }
+
+    pub fn pending(&self, operation: &str) -> Option<&Pending> {
+        self.pending.iter().find(|p| p.operation == operation)
+    }
+
+    /// Record the steps of `operation` done so far, replacing any earlier
+    /// record of it.
+    pub fn set_pending(&mut self, operation: &str, done: Vec<String>) {
+        self.finish_pending(operation);
+        self.pending.push(Pending { operation: operation.to_string(), done });
+    }
+
+    pub fn finish_pending(&mut self, operation: &str) {
+        self.pending.retain(|p| p.operation != operation);
+    }
+
+    /// Move holds and resolved versions to a target's new Minecraft version
+    /// after it was bumped.
+    pub fn rename_target(&mut self, old: &str, new: &str) {
+        for hold in self.holds.iter_mut().filter(|h| h.target == old) {
+            hold.target = new.to_string();
+        }
+        for entry in self.resolved.iter_mut().filter(|r| r.target == old) {
+            entry.target = new.to_string();
+        }
+    }
+}
+
+/// A target's pins by their mcmod.lock name (see `upgrade::Dep::key`).
+fn pins(target: &VersionTarget) -> [(&'static str, &str); 7] {
+    [
+        ("minecraft", &target.minecraft),
+        ("fabric_loader", &target.fabric_loader),
+        ("fabric_api", &target.fabric_api),
+        ("neoforge", &target.neoforge),
+        ("forge", &target.forge),
+        ("quilt_loader", &target.quilt_loader),
+        ("architectury_api", &target.architectury_api),
+    ]
+}
+
+/// `secs` since the Unix epoch as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn timestamp(secs: u64) -> String {
+    // civil_from_days, as in template::chrono_year
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe as i64 + era * 400 + i64::from(month <= 2);
+    let time = secs % 86400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("mcmod_lockfile_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(Lockfile::load(&dir).unwrap(), Lockfile::default());
+
+        let mut lock = Lockfile::default();
+        lock.set_hold(Hold {
+            target: "1.21.4".to_string(),
+            dependency: "neoforge".to_string(),
+            version: "21.4.157".to_string(),
+            skipped: "21.4.160".to_string(),
+        });
+        lock.save(&dir).unwrap();
+
+        let content = std::fs::read_to_string(Lockfile::path(&dir)).unwrap();
+        assert!(content.starts_with("# Written by mcmod."));
+        assert!(content.contains("[[hold]]"));
+        let mut loaded = Lockfile::load(&dir).unwrap();
+        assert_eq!(loaded, lock);
+
+        loaded.rename_target("1.21.4", "1.21.11");
+        assert!(loaded.hold("1.21.11", "neoforge").is_some());
+        loaded.release("1.21.11", "neoforge");
+        assert!(loaded.holds.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_targets() {
+        let mut target = VersionTarget {
+            minecraft: "1.21.4".to_string(),
+            max_minecraft: "1.21.4".to_string(),
+            fabric_loader: "0.18.5".to_string(),
+            fabric_api: "0.119.4+1.21.4".to_string(),
+            neoforge: "21.4.157".to_string(),
+            forge: String::new(),
+            quilt_loader: String::new(),
+            architectury_api: String::new(),
+        };
+        let mut lock = Lockfile::default();
+        lock.record_targets(std::slice::from_ref(&target), |_, _| Some(BUILT_IN_SOURCE.to_string()));
+        assert_eq!(lock.resolved.len(), 4);
+        assert_eq!(lock.locked("1.21.4", "neoforge").as_deref(), Some("21.4.157"));
+        assert_eq!(lock.resolved("1.21.4", "fabric_api").unwrap().source, BUILT_IN_SOURCE);
+
+        // Unchanged pins keep their entry; changed ones take the new source
+        lock.resolved[0].resolved_at = "2025-01-01T00:00:00Z".to_string();
+        target.neoforge = "21.4.160".to_string();
+        let maven = "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
+        lock.record_targets(std::slice::from_ref(&target), |_, dep| {
+            (dep == "neoforge").then(|| maven.to_string())
+        });
+        assert_eq!(lock.resolved[0].resolved_at, "2025-01-01T00:00:00Z");
+        let neoforge = lock.resolved("1.21.4", "neoforge").unwrap();
+        assert_eq!((neoforge.version.as_str(), neoforge.source.as_str()), ("21.4.160", maven));
+
+        lock.rename_target("1.21.4", "1.21.11");
+        assert!(lock.resolved("1.21.4", "minecraft").is_none());
+        assert!(lock.resolved("1.21.11", "minecraft").is_some());
+        assert!(lock.resolved.iter().all(|r| r.dependency != "forge"));
+
+        assert_eq!(timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(1_709_210_096), "2024-02-29T12:34:56Z");
+    }
+}
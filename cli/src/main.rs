@@ -1,17 +1,35 @@
 mod commands;
 mod config;
+mod conflict;
+mod content;
+mod pack_project;
+mod preset;
+mod plugin_project;
+mod profile;
+mod progress;
+mod diff;
 mod error;
+mod file_policy;
+mod fs;
+mod git_repo;
 mod global_config;
 mod gradle;
 mod install;
+mod jar;
+mod json_merge;
+mod lockfile;
+mod naming;
 mod pack_format;
+mod sandbox;
 mod template;
+mod template_pack;
 mod util;
 mod version_meta;
 mod versions;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use colored::Colorize;
+use error::McmodError;
 use std::path::PathBuf;
 use std::process;
 
@@ -20,9 +38,26 @@ use std::process;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Report how long version fetches, template rendering, and file writes took
+    #[arg(long, global = true)]
+    profile: bool,
+
+    /// Never access the network; version lookups use the built-in table (also MCMOD_OFFLINE=1 or the `offline` preference)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Global preferences profile to use for this run (also MCMOD_PROFILE; see `mcmod config profile`)
+    #[arg(long, global = true, value_name = "NAME")]
+    config_profile: Option<String>,
+
+    /// Progress output: colored text, or line-delimited JSON events for tools wrapping mcmod
+    #[arg(long, global = true, value_enum, default_value_t = progress::ProgressFormat::Text)]
+    progress: progress::ProgressFormat,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Initialize a new Minecraft mod project
     Init {
@@ -46,18 +81,34 @@ enum Commands {
         #[arg(long)]
         author: Option<String>,
 
+        /// Author contact email
+        #[arg(long)]
+        email: Option<String>,
+
+        /// GitHub username or organization hosting the repository
+        #[arg(long)]
+        github: Option<String>,
+
         /// Mod description
         #[arg(long)]
         description: Option<String>,
 
+        /// Project type: a regular mod, or a library other mods depend on
+        #[arg(long = "type", value_enum)]
+        project_type: Option<config::ProjectType>,
+
         /// Language: java or kotlin
         #[arg(long)]
         language: Option<String>,
 
-        /// Loaders to enable (can be specified multiple times)
+        /// Loaders to enable: fabric, neoforge, forge, quilt, sponge (can be specified multiple times)
         #[arg(long = "loader")]
         loaders: Vec<String>,
 
+        /// Side the mod runs on: both, client, or server (fabric.mod.json/quilt.mod.json `environment` and entrypoint)
+        #[arg(long, value_enum)]
+        environment: Option<config::Environment>,
+
         /// Enable GitHub Actions CI
         #[arg(long)]
         ci: Option<bool>,
@@ -82,29 +133,230 @@ enum Commands {
         #[arg(long)]
         testing: Option<bool>,
 
+        /// Generate a BuildConstants class with version, git commit, and build date
+        #[arg(long)]
+        build_info: Option<bool>,
+
+        /// Depend on Architectury API and generate a PlatformHelper class (Fabric/NeoForge/Quilt)
+        #[arg(long)]
+        architectury: Option<bool>,
+
+        /// Fabric: put client code in src/client as Loom's split client source set, with a client entrypoint
+        #[arg(long)]
+        split_environment: Option<bool>,
+
+        /// Fabric: extra entrypoint classes (client, datagen, pre-launch; can be specified multiple times)
+        #[arg(long = "entrypoint", value_enum)]
+        fabric_entrypoints: Vec<config::FabricEntrypoint>,
+
+        /// Resource packs: add the OptiFine/CTM folder layout
+        #[arg(long)]
+        optifine: Option<bool>,
+
         /// Minecraft versions to target (can be specified multiple times)
         #[arg(long = "minecraft")]
         minecraft_versions: Vec<String>,
 
-        /// Skip online version fetching, use defaults
-        #[arg(long)]
-        offline: bool,
-
         /// Overwrite files in an existing non-empty directory without prompting
         #[arg(long)]
         force: bool,
+
+        /// Inside another Git repository: keep the project in it as a subdirectory, or give it its own repository to add as a submodule
+        #[arg(long, value_enum)]
+        nested: Option<git_repo::NestedMode>,
+
+        /// Template pack to generate from instead of the default (see `mcmod template list`)
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Take unset answers from a preset file instead of prompting
+        #[arg(long, value_name = "FILE")]
+        preset: Option<PathBuf>,
+
+        /// Save the answers to a preset file for later `--preset` runs
+        #[arg(long, value_name = "FILE")]
+        save_preset: Option<PathBuf>,
     },
 
     /// Add a feature to an existing project
     Add {
-        /// Feature to add: fabric, neoforge, ci, kotlin, publishing
+        /// Feature to add: fabric, neoforge, forge, quilt, sponge, ci, kotlin, publishing, testing, build-info, paper, velocity, spigot, dep
+        /// (kotlin wires the Kotlin runtime for Fabric, Quilt, NeoForge and Forge)
         feature: commands::add::Feature,
 
+        /// For `dep`: modrinth:<project>[@version] or a Maven group:artifact:version
+        spec: Option<String>,
+
+        /// For `dep`: the dependency's mod id in the loader metadata (default: read from its jar)
+        #[arg(long)]
+        mod_id: Option<String>,
+
+        /// For `dep` with a Maven coordinate: the repository it's published to
+        #[arg(long)]
+        repository: Option<String>,
+
+        /// Show the files that would be created or changed, without writing anything
+        #[arg(long)]
+        diff: bool,
+
+        /// What to do with generated files you changed since mcmod wrote them
+        #[arg(long, value_enum, default_value = "ask")]
+        on_conflict: conflict::ConflictPolicy,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Show how the project differs from freshly generated templates, without writing anything
+    Diff {
+        /// Only list the files and their added/removed line counts
+        #[arg(long)]
+        stat: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Gather versions, settings and logs into a report to attach to bug reports
+    #[command(visible_alias = "debug-info")]
+    About {
+        /// Bundle the report and the full config and log files as a zip
+        #[arg(long)]
+        zip: bool,
+
+        /// Write the report here (default: print it; mcmod-debug-info.zip with --zip)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml, if any)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Check the project for missing files and config drift
+    Doctor {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Apply safe automatic fixes
+        #[arg(long)]
+        fix: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List Minecraft releases with the latest Fabric API and NeoForge for each
+    Versions {
+        /// How many of the newest releases to show (0 for all)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+
+        /// Print the list as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List pinned dependencies that have newer releases, and the upgrade commands that apply them
+    Outdated {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Upgrade Minecraft and loader versions to the latest releases
+    Upgrade {
+        /// Choose which updates to apply; unchecked ones are held back in mcmod.lock
+        #[arg(long, short)]
+        interactive: bool,
+
+        /// Show the available updates without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Move the newest target to this Minecraft version instead of the latest release
+        #[arg(long, value_name = "VERSION", conflicts_with = "locked")]
+        mc_version: Option<String>,
+
+        /// Apply the versions recorded in mcmod.lock instead of looking for newer ones
+        #[arg(long)]
+        locked: bool,
+
+        /// Show the file changes the updates would make, without writing anything
+        #[arg(long)]
+        diff: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Move the project to a newer Minecraft version, apply known file migrations, and list manual ones
+    Migrate {
+        /// Minecraft version to migrate to, e.g. 1.21.4
+        #[arg(long, value_name = "VERSION")]
+        to: String,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Remove parts of the project it no longer needs
+    Prune {
+        #[command(subcommand)]
+        action: PruneCommands,
+    },
+
+    /// Older spelling of `migrate --to <version>`
+    #[command(hide = true)]
+    MigrateMc {
+        /// Minecraft version to migrate to, e.g. 1.21.4
+        version: String,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Write mcmod.toml for an existing mod project from its gradle.properties and loader metadata
+    Adopt {
+        /// Replace an existing mcmod.toml
+        #[arg(long)]
+        force: bool,
+
+        /// Show what was inferred without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
         /// Project directory (default: current directory)
         #[arg(long, default_value = ".")]
         dir: PathBuf,
     },
 
+    /// Change the mod id and/or Java package across sources, resources, and build files
+    Rename {
+        /// New mod id
+        #[arg(long)]
+        mod_id: Option<String>,
+
+        /// New Java package
+        #[arg(long)]
+        package: Option<String>,
+
+        /// List the files that would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
     /// Update mcmod to the latest version
     Update,
 
@@ -113,20 +365,833 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigCommands,
     },
+
+    /// Check that mcmod runs without network access
+    Offline {
+        #[command(subcommand)]
+        action: OfflineCommands,
+    },
+
+    /// Manage template packs that replace the built-in project templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+
+    /// Write the built-in templates to a directory as a template pack, to customize and install
+    EjectTemplates {
+        /// Directory to write the pack into
+        dir: PathBuf,
+
+        /// Overwrite templates in a directory that isn't empty
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Move the Gradle wrapper (properties, jar and gradlew scripts) to another Gradle version
+    Wrapper {
+        /// Gradle version, e.g. 8.12 (default: the latest release)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Reinstall even if the wrapper is already on that version
+        #[arg(long)]
+        force: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Manage dependencies between local projects
+    Dep {
+        #[command(subcommand)]
+        action: DepCommands,
+    },
+
+    /// Tools for auditing what mcmod generates
+    Debug {
+        #[command(subcommand)]
+        action: DebugCommands,
+    },
+
+    /// Summarize the project: loaders, language, CI, pinned versions, and drift from mcmod.toml
+    Status {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Rewrite loader metadata fields managed by mcmod.toml ([neoforge]) to match it
+    Sync {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Watch content.toml and lang/model files, regenerating and validating resources on change
+    Watch {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Run one generate-and-validate pass and exit (non-zero if problems were found)
+        #[arg(long)]
+        once: bool,
+
+        /// Polling interval in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+
+    /// Work with the mod's resource assets
+    Assets {
+        #[command(subcommand)]
+        action: AssetsCommands,
+    },
+
+    /// Build the project with the Gradle wrapper and check the jars it produces
+    Build {
+        /// Only build this loader's targets (fabric, quilt, neoforge, forge)
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Check that the project configures and builds, with hints for common failures
+    Verify {
+        /// Stop after resource validation and `./gradlew help`, without building
+        #[arg(long)]
+        quick: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Run unit tests and GameTests for every loader and summarize the results
+    Test {
+        /// Only test this loader's targets (fabric, quilt, neoforge, forge)
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Run a single unit test class (e.g. com.example.mymod.MyModTest); skips GameTests
+        #[arg(long)]
+        class: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Keep CHANGELOG.md: note changes under [Unreleased] and turn them into a release section
+    Changelog {
+        #[command(subcommand)]
+        action: ChangelogCommands,
+    },
+
+    /// Track the mods this one is tested against and generate COMPATIBILITY.md
+    Compat {
+        #[command(subcommand)]
+        action: CompatCommands,
+    },
+
+    /// Manage the dev environment under run/
+    Dev {
+        #[command(subcommand)]
+        action: DevCommands,
+    },
+
+    /// Generate code into the mod and register it
+    Gen {
+        #[command(subcommand)]
+        action: GenCommands,
+    },
+
+    /// Work out consistent names for content
+    Name {
+        #[command(subcommand)]
+        action: NameCommands,
+    },
+
+    /// Bump the version, update the changelog, build, validate, tag, and publish a release
+    Release {
+        /// Version part to increment
+        #[arg(long, value_enum, default_value = "patch")]
+        bump: commands::release::Bump,
+
+        /// Release this exact version instead of bumping
+        #[arg(long, conflicts_with = "bump")]
+        version: Option<String>,
+
+        /// Release channel for Modrinth and CurseForge (default: from gradle.properties or the version)
+        #[arg(long, value_enum)]
+        version_type: Option<commands::publish::VersionType>,
+
+        /// Keep mod.version as it is
+        #[arg(long)]
+        no_bump: bool,
+
+        /// Leave CHANGELOG.md alone
+        #[arg(long)]
+        no_changelog: bool,
+
+        /// Don't run the Gradle build (use the jars already built)
+        #[arg(long)]
+        no_build: bool,
+
+        /// Don't check the built jars
+        #[arg(long)]
+        no_validate: bool,
+
+        /// Don't commit or tag
+        #[arg(long)]
+        no_tag: bool,
+
+        /// Don't push or create a GitHub release
+        #[arg(long)]
+        no_github: bool,
+
+        /// Don't publish to Modrinth
+        #[arg(long)]
+        no_modrinth: bool,
+
+        /// Don't publish to CurseForge
+        #[arg(long)]
+        no_curseforge: bool,
+
+        /// Print the plan and the file changes without doing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Build, then collect the mod jars and their checksums into dist/<version>/ for manual upload
+    Package {
+        /// Only package this loader's jars (fabric, quilt, neoforge, forge)
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Package the jars already built instead of running the build first
+        #[arg(long)]
+        skip_build: bool,
+
+        /// Replace an existing dist/<version>/
+        #[arg(long)]
+        force: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Upload built jars to mod hosting sites
+    Publish {
+        #[command(subcommand)]
+        action: PublishCommands,
+    },
+
+    /// Inspect build output
+    Report {
+        #[command(subcommand)]
+        action: ReportCommands,
+    },
+
+    /// Print a Mermaid or DOT graph of the project's targets, dependencies, and content
+    Graph {
+        /// Output format
+        #[arg(long, value_enum, default_value = "mermaid")]
+        format: commands::graph::GraphFormat,
+
+        /// Write the graph to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Run a command in every mcmod project under a directory
+    Foreach {
+        /// Directory to search for projects (default: current directory)
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+
+        /// Stop at the first project where the command fails
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Command to run in each project, e.g. `add ci`
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Print a shell completion script (bash, zsh, fish, powershell)
+    Completions {
+        shell: commands::completions::Shell,
+    },
+
+    /// Completion protocol used by the shell scripts: prints candidates for the last word
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum OfflineCommands {
+    /// Confirm offline mode is on and no command will reach the network, and check the Gradle cache
+    Verify,
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// List the built-in templates and installed packs (* marks the default)
+    List,
+
+    /// Install a pack: a directory laid out like the built-in templates/, with an optional pack.toml
+    Install {
+        /// Pack directory
+        path: PathBuf,
+
+        /// Name to install it under (default: pack.toml's name, or the directory name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Replace an installed pack with the same name
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Remove an installed pack
+    Remove {
+        /// Pack name
+        name: String,
+    },
+
+    /// Use a pack by default (`default` for the built-in templates)
+    Use {
+        /// Pack name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
 enum ConfigCommands {
-    /// Set a global preference (e.g., mcmod config set author "Jane")
-    Set { key: String, value: String },
+    /// Set global preferences: `author "Jane"`, `author=Jane language=kotlin`, or `-` for key=value lines on stdin
+    Set {
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true, value_name = "KEY=VALUE")]
+        assignments: Vec<String>,
+    },
     /// Get a global preference value
     Get { key: String },
+    /// Clear a global preference so it is no longer set
+    Unset { key: String },
+    /// Restore the defaults for one section of the preferences, or all of them
+    Reset {
+        #[arg(value_enum)]
+        section: Option<global_config::ConfigSection>,
+    },
     /// List all global preferences
     List,
+    /// Open config.toml in $VISUAL or $EDITOR, then check it before saving
+    Edit,
+    /// Print all global preferences as TOML (e.g. mcmod config export > prefs.toml)
+    Export,
+    /// Import preferences from a file written by `config export` ("-" reads stdin)
+    Import {
+        file: PathBuf,
+
+        /// merge keeps preferences the file leaves out; overwrite resets them to defaults
+        #[arg(long, value_enum, default_value = "merge")]
+        mode: global_config::ImportMode,
+    },
+    /// Manage named preference profiles (e.g. work, personal), each its own TOML file
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommands,
+    },
+    /// Print where mcmod keeps its files: one location as a bare path, or all as `name<TAB>path` lines
+    Path {
+        /// Only this location
+        #[arg(value_enum)]
+        kind: Option<commands::config::PathKind>,
+
+        /// Print a JSON object (null for a location that doesn't resolve)
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommands {
+    /// List the profiles, marking the active one
+    List,
+    /// Create a profile with the default preferences
+    Create {
+        name: String,
+
+        /// Start from a copy of this profile's preferences instead
+        #[arg(long, value_name = "PROFILE")]
+        from: Option<String>,
+    },
+    /// Use a profile from now on when --config-profile and MCMOD_PROFILE are unset
+    Switch { name: String },
+}
+
+#[derive(Subcommand)]
+enum AssetsCommands {
+    /// Create placeholder models and magenta-checker textures for content.toml entries missing them
+    Stub {
+        /// Remove the placeholders recorded by earlier runs instead (edited ones are kept)
+        #[arg(long)]
+        clean_stubs: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GenCommands {
+    /// A class with an init() hook called from the mod's init()
+    Class {
+        /// Class name, optionally in a subpackage of the mod's package (e.g. event.ModEvents)
+        name: String,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// An item registered in registry.ModItems, with its model, texture and translation
+    Item {
+        /// Registry id (ruby) or display name ("Ruby Sword")
+        name: String,
+
+        /// English display name (default: derived from the id)
+        #[arg(long = "name", value_name = "NAME")]
+        display_name: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// A block class registered with its block item in registry.ModBlocks, plus its assets and loot table
+    Block {
+        /// Registry id (ruby_block) or display name ("Block of Ruby")
+        name: String,
+
+        /// English display name (default: derived from the id)
+        #[arg(long = "name", value_name = "NAME")]
+        display_name: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// A block with a block entity registered in registry.ModBlockEntities, and a renderer stub for it
+    BlockEntity {
+        /// Registry id (ruby_box) or display name ("Gem Safe")
+        name: String,
+
+        /// English display name (default: derived from the id)
+        #[arg(long = "name", value_name = "NAME")]
+        display_name: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum NameCommands {
+    /// Print the registry id, translation key, class name and texture path for a display name
+    Suggest {
+        /// Display name, e.g. "Ruby Sword"
+        name: String,
+
+        /// What the content is registered as
+        #[arg(long, value_enum, default_value = "item")]
+        kind: naming::ContentKind,
+
+        /// Mod id to use instead of the project's
+        #[arg(long)]
+        mod_id: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PublishCommands {
+    /// Upload each built jar as a Modrinth version of [publish.modrinth].project_id
+    Modrinth {
+        /// Print the version payloads instead of uploading
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Release channel (default: version_type in gradle.properties, else from mod.version)
+        #[arg(long, value_enum)]
+        version_type: Option<commands::publish::VersionType>,
+
+        /// Markdown file with the release notes (default: changelogs/v<version>.md or CHANGELOG.md)
+        #[arg(long)]
+        changelog: Option<PathBuf>,
+
+        /// Only upload this loader's jars
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Upload each built jar as a file of the [publish.curseforge].project_id project
+    Curseforge {
+        /// Print the upload metadata instead of uploading
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Release channel (default: version_type in gradle.properties, else from mod.version)
+        #[arg(long, value_enum)]
+        version_type: Option<commands::publish::VersionType>,
+
+        /// Markdown file with the release notes (default: changelogs/v<version>.md or CHANGELOG.md)
+        #[arg(long)]
+        changelog: Option<PathBuf>,
+
+        /// Only upload this loader's jars
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ChangelogCommands {
+    /// Add an entry under [Unreleased] (mcmod publish uses these notes until a release section exists)
+    Add {
+        /// The change, e.g. "Ruby ore generates in the Nether"
+        entry: String,
+
+        /// Keep a Changelog heading to file it under
+        #[arg(long = "type", value_enum)]
+        kind: Option<commands::changelog::ChangeType>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Move the [Unreleased] notes into a dated section for a version
+    Release {
+        /// The version being released, e.g. 1.2.0
+        version: String,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CompatCommands {
+    /// Record a mod in [compat] or update its entry, then regenerate COMPATIBILITY.md
+    Add {
+        /// The other mod's id
+        mod_id: String,
+
+        /// Version tested against (required for a new entry)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Minecraft version it was tested on
+        #[arg(long)]
+        minecraft: Option<String>,
+
+        /// Loader it was tested on (default: all of this mod's loaders)
+        #[arg(long)]
+        loader: Option<String>,
+
+        /// Test result (default for a new entry: works)
+        #[arg(long, value_enum)]
+        status: Option<config::CompatStatus>,
+
+        /// Known problems or workarounds
+        #[arg(long)]
+        notes: Option<String>,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Remove a mod from [compat] and COMPATIBILITY.md
+    Remove {
+        /// The other mod's id
+        mod_id: String,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Write COMPATIBILITY.md from [compat] in mcmod.toml
+    Generate {
+        /// Fail if COMPATIBILITY.md is out of date instead of writing it
+        #[arg(long)]
+        check: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PruneCommands {
+    /// Drop Minecraft versions: their mc(...) lines, versions/ files, lockfile and CI matrix entries
+    Versions {
+        /// Minecraft versions to drop, e.g. 1.20.1
+        #[arg(required = true)]
+        versions: Vec<String>,
+
+        /// Prune even when version checks in the sources become always true or false
+        #[arg(long)]
+        force: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevCommands {
+    /// Manage the extra mods loaded by dev runs (the run directory's mods/)
+    Mods {
+        #[command(subcommand)]
+        action: DevModsCommands,
+    },
+
+    /// Install and start a standalone server in run/server, without Gradle
+    Server {
+        #[command(subcommand)]
+        action: DevServerCommands,
+    },
+
+    /// Share one run/ between loaders or give each loader its own run/<loader>
+    RunDirs {
+        /// Run directory layout
+        #[arg(value_enum)]
+        layout: config::RunDirs,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevServerCommands {
+    /// Download the vanilla, Fabric, or NeoForge server for a project version into run/server
+    Install {
+        /// Server to install (default: fabric, then neoforge, whichever the project has)
+        #[arg(long, value_enum)]
+        loader: Option<commands::dev_server::ServerLoader>,
+
+        /// Minecraft version, one of the project's targets (default: the newest)
+        #[arg(long)]
+        mc_version: Option<String>,
+
+        /// Accept the Minecraft EULA in run/server/eula.txt and write dev server.properties
+        #[arg(long)]
+        accept_eula: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Write run/server's server.properties and dev data pack from your preferences
+    Init {
+        /// Accept the Minecraft EULA in run/server/eula.txt
+        #[arg(long)]
+        accept_eula: bool,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Copy the built mod jars into run/server/mods and start the installed server
+    Run {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevModsCommands {
+    /// Copy a mod jar into its loader's run mods folder and record it in [compat]
+    Add {
+        /// Path to the mod jar
+        jar: PathBuf,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Check the run mods folders for duplicate mod ids, Minecraft version mismatches, and missing dependencies
+    Check {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Open the built jars and check metadata, mixin configs, refmaps, and bundled classes
+    Jars {
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DebugCommands {
+    /// Render every language/loader/feature combination in memory and print
+    /// each project's file manifest (path + content hash)
+    RenderAll {
+        /// Only combinations matching all of these comma-separated terms (e.g. kotlin,forge,ci)
+        #[arg(long)]
+        only: Option<String>,
+
+        /// Print one line per combination instead of every file
+        #[arg(long)]
+        summary: bool,
+
+        /// Also write each rendered project to <out>/<language>-<loaders>-<features>
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DepCommands {
+    /// Depend on a sibling project (e.g. a library) via a Gradle composite build
+    Link {
+        /// Path to the sibling project, or its directory name next to this project
+        sibling: PathBuf,
+
+        /// Project directory (default: nearest directory above the current one with mcmod.toml)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+/// Activate the template pack for this run: `requested` (from `init
+/// --template`) or else the user's default pack. A requested pack that isn't
+/// installed is an error; a missing default only warns.
+fn apply_template_pack(requested: Option<&str>) -> error::Result<()> {
+    let name = match requested {
+        Some(name) => name.to_string(),
+        None => match global_config::GlobalConfig::load().ok().and_then(|c| c.defaults.template) {
+            Some(name) => name,
+            None => return Ok(()),
+        },
+    };
+    if name == template_pack::BUILT_IN {
+        return Ok(());
+    }
+    match template_pack::find(&name) {
+        Ok(pack) => template_pack::activate(pack),
+        Err(e) if requested.is_some() => return Err(e),
+        Err(e) => eprintln!("{}", format!("  Warning: {e}; using the built-in templates").yellow()),
+    }
+    Ok(())
+}
+
+/// The subcommand names in `matches`, e.g. `dev mods check`.
+fn command_path(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    names.join(" ")
+}
+
+/// Apply the user's `line_endings` preference to generated text files.
+fn apply_line_ending_default() {
+    let Ok(config) = global_config::GlobalConfig::load() else {
+        return;
+    };
+    if let Some(ending) = config
+        .defaults
+        .line_endings
+        .as_deref()
+        .and_then(|v| file_policy::LineEnding::parse(v).ok())
+    {
+        file_policy::set_default_line_ending(ending);
+    }
 }
 
 fn main() {
-    let cli = Cli::parse();
+    finish_staged_update();
+
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let config_profile = cli
+        .config_profile
+        .clone()
+        .or_else(|| std::env::var(global_config::PROFILE_ENV).ok().filter(|p| !p.is_empty()));
+    if let Some(name) = config_profile {
+        if let Err(e) = global_config::select_profile(&name) {
+            exit_with(e);
+        }
+    }
+    apply_line_ending_default();
+    if cli.profile {
+        profile::enable();
+    }
+    if cli.progress == progress::ProgressFormat::Json {
+        progress::enable_json();
+    }
+    let step = command_path(&matches);
+    progress::step_started(&step);
+    let offline_source = commands::offline::offline_source(cli.offline);
+    util::set_offline(offline_source.is_some());
+
+    // init picks its pack itself, since --template overrides the default
+    if !matches!(cli.command, Commands::Init { .. }) {
+        let _ = apply_template_pack(None);
+    }
 
     let result = match cli.command {
         Commands::Init {
@@ -135,9 +1200,13 @@ fn main() {
             mod_name,
             package,
             author,
+            email,
+            github,
             description,
+            project_type,
             language,
             loaders,
+            environment,
             minecraft_versions,
             ci,
             server,
@@ -145,17 +1214,29 @@ fn main() {
             modrinth_id,
             curseforge_id,
             testing,
-            offline,
+            build_info,
+            architectury,
+            split_environment,
+            fabric_entrypoints,
+            optifine,
             force,
-        } => commands::init::run(commands::init::InitOptions {
+            nested,
+            template,
+            preset,
+            save_preset,
+        } => apply_template_pack(template.as_deref()).and_then(|()| commands::init::run(commands::init::InitOptions {
             dir,
             mod_id,
             mod_name,
             package,
             author,
+            email,
+            github,
             description,
+            project_type,
             language,
             loaders,
+            environment,
             minecraft_versions,
             ci,
             server,
@@ -163,20 +1244,284 @@ fn main() {
             modrinth_id,
             curseforge_id,
             testing,
-            offline,
+            build_info,
+            architectury,
+            split_environment,
+            fabric_entrypoints: (!fabric_entrypoints.is_empty()).then_some(fabric_entrypoints),
+            optifine,
+            offline: util::is_offline(),
             force,
+            nested,
+            preset,
+            save_preset,
+        })),
+        Commands::Add { feature, spec, mod_id, repository, diff, on_conflict, dir } => config::resolve_project_dir(dir).and_then(|dir| {
+            let add = || conflict::session(&dir, on_conflict, || match (&feature, spec.as_deref()) {
+                (commands::add::Feature::Dep, Some(spec)) => {
+                    commands::add_dep::run(&dir, spec, mod_id.as_deref(), repository.as_deref())
+                }
+                (_, Some(spec)) => Err(McmodError::Other(format!("Unexpected argument '{spec}': only `mcmod add dep` takes one"))),
+                _ => commands::add::run(&feature, &dir),
+            });
+            if diff {
+                diff::preview(&dir, false, add)
+            } else {
+                add()
+            }
+        }),
+        Commands::Diff { stat, dir } => config::resolve_project_dir(dir).and_then(|dir| commands::diff::run(&dir, stat)),
+        Commands::About { zip, output, dir } => {
+            commands::about::run(config::resolve_project_dir(dir).ok().as_deref(), zip, output.as_deref())
+        }
+        Commands::Doctor { dir, fix, json } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::doctor::run(&dir, fix, json)),
+        Commands::Versions { limit, json } => commands::versions::run(limit, json),
+        Commands::Outdated { dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::outdated::run(&dir, util::is_offline())),
+        Commands::Upgrade {
+            interactive,
+            dry_run,
+            mc_version,
+            locked,
+            diff,
+            dir,
+        } => config::resolve_project_dir(dir).and_then(|dir| {
+            let upgrade = || {
+                commands::upgrade::run(&dir, interactive, dry_run, util::is_offline(), locked, mc_version.as_deref())
+            };
+            if diff {
+                diff::preview(&dir, false, upgrade)
+            } else {
+                upgrade()
+            }
+        }),
+        Commands::Migrate { to: version, dir } | Commands::MigrateMc { version, dir } => {
+            config::resolve_project_dir(dir).and_then(|dir| commands::migrate_mc::run(&dir, &version))
+        }
+        Commands::Prune { action } => match action {
+            PruneCommands::Versions { versions, force, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::prune::run_versions(&dir, &versions, force)),
+        },
+        Commands::Adopt { force, dry_run, dir } => commands::adopt::run(&dir, force, dry_run),
+        Commands::Rename {
+            mod_id,
+            package,
+            dry_run,
+            dir,
+        } => config::resolve_project_dir(dir).and_then(|dir| {
+            commands::rename::run(&dir, mod_id.as_deref(), package.as_deref(), dry_run)
         }),
-        Commands::Add { feature, dir } => commands::add::run(&feature, &dir),
         Commands::Update => commands::update::run(),
+        Commands::Offline { action } => match action {
+            OfflineCommands::Verify => commands::offline::run_verify(offline_source),
+        },
+        Commands::Template { action } => match action {
+            TemplateCommands::List => commands::template::run_list(),
+            TemplateCommands::Install { path, name, force } => {
+                commands::template::run_install(&path, name.as_deref(), force)
+            }
+            TemplateCommands::Remove { name } => commands::template::run_remove(&name),
+            TemplateCommands::Use { name } => commands::template::run_use(&name),
+        },
+        Commands::EjectTemplates { dir, force } => commands::template::run_eject(&dir, force),
         Commands::Config { action } => match action {
-            ConfigCommands::Set { key, value } => commands::config::run_set(&key, &value),
+            ConfigCommands::Set { assignments } => commands::config::run_set(&assignments),
             ConfigCommands::Get { key } => commands::config::run_get(&key),
+            ConfigCommands::Unset { key } => commands::config::run_unset(&key),
+            ConfigCommands::Reset { section } => commands::config::run_reset(section),
             ConfigCommands::List => commands::config::run_list(),
+            ConfigCommands::Edit => commands::config::run_edit(),
+            ConfigCommands::Export => commands::config::run_export(),
+            ConfigCommands::Import { file, mode } => commands::config::run_import(&file, mode),
+            ConfigCommands::Path { kind, json } => commands::config::run_path(kind, json),
+            ConfigCommands::Profile { action } => match action {
+                ProfileCommands::List => commands::config::run_profile_list(),
+                ProfileCommands::Create { name, from } => commands::config::run_profile_create(&name, from.as_deref()),
+                ProfileCommands::Switch { name } => commands::config::run_profile_switch(&name),
+            },
+        },
+        Commands::Status { dir } => config::resolve_project_dir(dir).and_then(|dir| commands::status::run(&dir)),
+        Commands::Sync { dir } => config::resolve_project_dir(dir).and_then(|dir| commands::sync::run(&dir)),
+        Commands::Watch { dir, once, interval } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::watch::run(&dir, once, std::time::Duration::from_millis(interval))),
+        Commands::Graph { format, output, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::graph::run(&dir, format, output.as_deref())),
+        Commands::Assets { action } => match action {
+            AssetsCommands::Stub { clean_stubs, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::assets::run(&dir, clean_stubs)),
         },
+        Commands::Build { loader, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::build::run(&dir, loader.as_deref())),
+        Commands::Verify { quick, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::verify::run(&dir, quick)),
+        Commands::Test { loader, class, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::test::run(&dir, loader.as_deref(), class.as_deref())),
+        Commands::Changelog { action } => match action {
+            ChangelogCommands::Add { entry, kind, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::changelog::run_add(&dir, &entry, kind)),
+            ChangelogCommands::Release { version, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::changelog::run_release(&dir, &version)),
+        },
+        Commands::Compat { action } => match action {
+            CompatCommands::Add {
+                mod_id,
+                version,
+                name,
+                minecraft,
+                loader,
+                status,
+                notes,
+                dir,
+            } => config::resolve_project_dir(dir).and_then(|dir| {
+                let update = commands::compat::CompatUpdate { version, name, minecraft, loader, status, notes };
+                commands::compat::run_add(&dir, &mod_id, update)
+            }),
+            CompatCommands::Remove { mod_id, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::compat::run_remove(&dir, &mod_id)),
+            CompatCommands::Generate { check, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::compat::run_generate(&dir, check)),
+        },
+        Commands::Dev { action } => match action {
+            DevCommands::Mods { action } => match action {
+                DevModsCommands::Add { jar, dir } => config::resolve_project_dir(dir)
+                    .and_then(|dir| commands::dev::run_mods_add(&dir, &jar)),
+                DevModsCommands::Check { dir } => config::resolve_project_dir(dir)
+                    .and_then(|dir| commands::dev::run_mods_check(&dir)),
+            },
+            DevCommands::Server { action } => match action {
+                DevServerCommands::Install { loader, mc_version, accept_eula, dir } => config::resolve_project_dir(dir)
+                    .and_then(|dir| commands::dev_server::run_install(&dir, loader, mc_version.as_deref(), accept_eula)),
+                DevServerCommands::Init { accept_eula, dir } => config::resolve_project_dir(dir)
+                    .and_then(|dir| commands::dev_server::run_init(&dir, accept_eula)),
+                DevServerCommands::Run { dir } => config::resolve_project_dir(dir)
+                    .and_then(|dir| commands::dev_server::run_server(&dir)),
+            },
+            DevCommands::RunDirs { layout, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::dev::run_run_dirs(&dir, layout)),
+        },
+        Commands::Gen { action } => match action {
+            GenCommands::Class { name, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::gen::init_class::run(&dir, &name)),
+            GenCommands::Item { name, display_name, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::gen::item::run(&dir, &name, display_name.as_deref())),
+            GenCommands::Block { name, display_name, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::gen::block::run(&dir, &name, display_name.as_deref())),
+            GenCommands::BlockEntity { name, display_name, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::gen::block_entity::run(&dir, &name, display_name.as_deref())),
+        },
+        Commands::Wrapper { version, force, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::wrapper::run(&dir, version.as_deref(), force)),
+        Commands::Name { action } => match action {
+            NameCommands::Suggest { name, kind, mod_id, dir } => {
+                commands::name::run_suggest(&name, kind, mod_id, dir)
+            }
+        },
+        Commands::Release {
+            bump,
+            version,
+            version_type,
+            no_bump,
+            no_changelog,
+            no_build,
+            no_validate,
+            no_tag,
+            no_github,
+            no_modrinth,
+            no_curseforge,
+            dry_run,
+            dir,
+        } => config::resolve_project_dir(dir).and_then(|dir| {
+            let opts = commands::release::ReleaseOptions {
+                bump,
+                version,
+                version_type,
+                no_bump,
+                no_changelog,
+                no_build,
+                no_validate,
+                no_tag,
+                no_github,
+                no_modrinth,
+                no_curseforge,
+                dry_run,
+            };
+            commands::release::run(&dir, &opts)
+        }),
+        Commands::Package { loader, skip_build, force, dir } => config::resolve_project_dir(dir)
+            .and_then(|dir| commands::package::run(&dir, loader.as_deref(), skip_build, force)),
+        Commands::Publish { action } => match action {
+            PublishCommands::Modrinth { dry_run, version_type, changelog, loader, dir } => {
+                config::resolve_project_dir(dir).and_then(|dir| {
+                    commands::publish::run_modrinth(&dir, dry_run, version_type, changelog.as_deref(), loader.as_deref())
+                })
+            }
+            PublishCommands::Curseforge { dry_run, version_type, changelog, loader, dir } => {
+                config::resolve_project_dir(dir).and_then(|dir| {
+                    commands::publish::run_curseforge(&dir, dry_run, version_type, changelog.as_deref(), loader.as_deref())
+                })
+            }
+        },
+        Commands::Report { action } => match action {
+            ReportCommands::Jars { dir } => {
+                config::resolve_project_dir(dir).and_then(|dir| commands::report::run_jars(&dir))
+            }
+        },
+        Commands::Debug { action } => match action {
+            DebugCommands::RenderAll { only, summary, out } => {
+                commands::debug::run_render_all(only.as_deref(), summary, out.as_deref())
+            }
+        },
+        Commands::Dep { action } => match action {
+            DepCommands::Link { sibling, dir } => config::resolve_project_dir(dir)
+                .and_then(|dir| commands::dep::run_link(&dir, &sibling)),
+        },
+        Commands::Foreach {
+            dir,
+            fail_fast,
+            command,
+        } => commands::foreach::run(&Cli::command(), &dir, &command, fail_fast),
+        Commands::Completions { shell } => commands::completions::run(&shell),
+        Commands::Complete { words } => {
+            commands::completions::run_complete(Cli::command(), &words)
+        }
     };
+    progress::step_finished(&step, result.as_ref().err().map(|e| e.chain().join(": ")));
+    profile::report();
 
     if let Err(e) = result {
-        eprintln!("{}", format!("\n  Error: {e}\n").red().bold());
-        process::exit(1);
+        exit_with(e);
+    }
+}
+
+/// Print an error with its causes and any hint, then exit with status 1.
+fn exit_with(e: McmodError) -> ! {
+    let chain = e.chain();
+    eprintln!("{}", format!("\n  Error: {}", chain[0]).red().bold());
+    for cause in &chain[1..] {
+        eprintln!("  {}", format!("  caused by: {cause}").red());
+    }
+    if let Some(hint) = e.hint() {
+        eprintln!("\n  {} {hint}", "hint:".yellow().bold());
+    }
+    if e.is_unexpected() {
+        eprintln!("\n  {}", McmodError::report_instructions().dimmed());
+    }
+    eprintln!();
+    process::exit(1);
+}
+
+/// Swap in an update that `mcmod update` staged because the binary was locked.
+fn finish_staged_update() {
+    let Ok(target) = install::install_path() else {
+        return;
+    };
+    match install::apply_staged_update(&target) {
+        Ok(true) => eprintln!("{}", "  Finished applying staged mcmod update".green()),
+        Ok(false) => {}
+        Err(e) => eprintln!(
+            "{}",
+            format!("  Warning: staged update is still pending ({e}); it will be retried next run")
+                .yellow()
+        ),
     }
 }
@@ -0,0 +1,149 @@
+//! Names derived from a piece of content's display name.
+//!
+//! "Ruby Sword" becomes the registry id `ruby_sword`, the translation key
+//! `item.<mod_id>.ruby_sword`, the class `RubySwordItem` and the texture
+//! `assets/<mod_id>/textures/item/ruby_sword.png`. `mcmod name suggest` prints
+//! these, and content.toml and the generators use the same rules so ids,
+//! keys and classes stay consistent across a mod.
+
+use crate::error::{McmodError, Result};
+use crate::util;
+
+/// What a piece of content is registered as.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum ContentKind {
+    Item,
+    Block,
+}
+
+impl ContentKind {
+    /// Translation key prefix and texture directory, e.g. `item`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentKind::Item => "item",
+            ContentKind::Block => "block",
+        }
+    }
+
+    /// Suffix for generated class names, e.g. `Item`.
+    fn class_suffix(self) -> &'static str {
+        match self {
+            ContentKind::Item => "Item",
+            ContentKind::Block => "Block",
+        }
+    }
+}
+
+/// The names one piece of content goes by.
+#[derive(Debug, PartialEq)]
+pub struct ContentName {
+    /// Registry path, e.g. `ruby_sword` for `<mod_id>:ruby_sword`.
+    pub id: String,
+    pub translation_key: String,
+    pub class_name: String,
+    /// Texture path relative to the resources root.
+    pub texture: String,
+}
+
+impl ContentName {
+    /// Derive every name from `display` (e.g. "Ruby Sword").
+    pub fn suggest(display: &str, mod_id: &str, kind: ContentKind) -> Result<ContentName> {
+        let id = registry_id(display).ok_or_else(|| {
+            McmodError::Other(format!(
+                "Can't derive an id from '{display}': the name needs a Latin, Cyrillic or Greek letter"
+            ))
+        })?;
+        Ok(ContentName::from_id(&id, mod_id, kind))
+    }
+
+    /// The names for an existing registry id.
+    pub fn from_id(id: &str, mod_id: &str, kind: ContentKind) -> ContentName {
+        let kind_name = kind.as_str();
+        let mut class_name = util::to_pascal_case(id);
+        if !class_name.ends_with(kind.class_suffix()) {
+            class_name.push_str(kind.class_suffix());
+        }
+        ContentName {
+            id: id.to_string(),
+            translation_key: format!("{kind_name}.{mod_id}.{id}"),
+            class_name,
+            texture: format!("assets/{mod_id}/textures/{kind_name}/{id}.png"),
+        }
+    }
+}
+
+/// Whether `id` is a usable registry path: `[a-z0-9_]`, starting with a
+/// letter (the same rule as mod ids).
+pub fn is_valid_id(id: &str) -> bool {
+    util::validate_mod_id(id).is_ok()
+}
+
+/// Lowercase, `[a-z0-9_]` and starting with a letter, or None if nothing
+/// usable remains. Non-ASCII letters are transliterated ("Épée" ->
+/// `epee`). Unlike package segments, Java keywords are fine here.
+/// e.g. "Ruby Sword" -> "ruby_sword", "TNT-Cart 2" -> "tnt_cart_2"
+pub fn registry_id(display: &str) -> Option<String> {
+    let mapped: String = util::transliterate(display)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let id = mapped.split('_').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("_");
+    let id = id.trim_start_matches(|c: char| c.is_ascii_digit() || c == '_');
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+/// The id in title case, the default display name: `ruby_block` -> `Ruby Block`.
+pub fn display_name(id: &str) -> String {
+    id.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_id() {
+        assert_eq!(registry_id("Ruby Sword").as_deref(), Some("ruby_sword"));
+        assert_eq!(registry_id("  TNT-Cart 2 ").as_deref(), Some("tnt_cart_2"));
+        assert_eq!(registry_id("Class").as_deref(), Some("class"));
+        assert_eq!(registry_id("Épée de Rubis").as_deref(), Some("epee_de_rubis"));
+        assert_eq!(registry_id("Рубиновый меч").as_deref(), Some("rubinovyy_mech"));
+        assert_eq!(registry_id("2x Ingot").as_deref(), Some("x_ingot"));
+        assert_eq!(registry_id("123"), None);
+        assert_eq!(registry_id("!!"), None);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let name = ContentName::suggest("Ruby Sword", "gems", ContentKind::Item).unwrap();
+        assert_eq!(
+            name,
+            ContentName {
+                id: "ruby_sword".to_string(),
+                translation_key: "item.gems.ruby_sword".to_string(),
+                class_name: "RubySwordItem".to_string(),
+                texture: "assets/gems/textures/item/ruby_sword.png".to_string(),
+            }
+        );
+        let block = ContentName::suggest("Ruby Block", "gems", ContentKind::Block).unwrap();
+        assert_eq!(block.class_name, "RubyBlock");
+        assert_eq!(block.translation_key, "block.gems.ruby_block");
+        assert!(ContentName::suggest("???", "gems", ContentKind::Item).is_err());
+    }
+
+    #[test]
+    fn test_display_name_round_trips() {
+        assert_eq!(display_name("ruby_block"), "Ruby Block");
+        assert_eq!(registry_id(&display_name("ruby_block")).as_deref(), Some("ruby_block"));
+        assert!(is_valid_id("ruby_2"));
+        assert!(!is_valid_id("2ruby"));
+        assert!(!is_valid_id("Ruby"));
+    }
+}
@@ -2,34 +2,74 @@ use crate::error::{McmodError, Result};
 use crate::global_config::GlobalConfig;
 use std::path::Path;
 
+/// Which kind of pack a pack.mcmeta describes. Data packs and resource packs
+/// are numbered independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackKind {
+    Data,
+    Resource,
+}
+
+/// Maps an unknown Minecraft version to the closest known one by guessing from
+/// the patch number; known versions are returned unchanged.
+fn known_version(mc_version: &str) -> &str {
+    const KNOWN: &[&str] = &[
+        "1.21", "1.21.1", "1.21.2", "1.21.3", "1.21.4", "1.21.5", "1.21.6", "1.21.7", "1.21.8",
+        "1.21.9", "1.21.10", "1.21.11",
+    ];
+    if KNOWN.contains(&mc_version) {
+        return mc_version;
+    }
+    // Parse the third component if present.
+    let parts: Vec<&str> = mc_version.splitn(3, '.').collect();
+    if parts.len() == 3 {
+        if let Ok(minor) = parts[2].parse::<u32>() {
+            if minor >= 11 {
+                return "1.21.11"; // latest known
+            } else if minor >= 9 {
+                return "1.21.9";
+            }
+        }
+    }
+    // Default fallback to 1.21.4's format
+    "1.21.4"
+}
+
 /// Maps a Minecraft version string to the correct data pack pack_format number.
 /// Returns (major, minor) where minor is 0 for pre-1.21.9 versions.
 fn mc_version_to_pack_format(mc_version: &str) -> (u32, u32) {
-    match mc_version {
+    match known_version(mc_version) {
         "1.21" | "1.21.1" => (48, 0),
         "1.21.2" | "1.21.3" => (57, 0),
-        "1.21.4" => (61, 0),
         "1.21.5" => (71, 0),
         "1.21.6" => (80, 0),
         "1.21.7" | "1.21.8" => (81, 0),
         "1.21.9" | "1.21.10" => (88, 0),
         "1.21.11" => (94, 1),
-        _ => {
-            // For unknown versions, try to guess based on the minor version number.
-            // Parse the third component if present.
-            let parts: Vec<&str> = mc_version.splitn(3, '.').collect();
-            if parts.len() == 3 {
-                if let Ok(minor) = parts[2].parse::<u32>() {
-                    if minor >= 11 {
-                        return (94, 1); // latest known
-                    } else if minor >= 9 {
-                        return (88, 0);
-                    }
-                }
-            }
-            // Default fallback to 1.21.4's format
-            (61, 0)
-        }
+        // 1.21.4, also the fallback for unknown versions
+        _ => (61, 0),
+    }
+}
+
+/// Maps a Minecraft version string to the resource pack pack_format number.
+fn mc_version_to_resource_pack_format(mc_version: &str) -> (u32, u32) {
+    match known_version(mc_version) {
+        "1.21" | "1.21.1" => (34, 0),
+        "1.21.2" | "1.21.3" => (42, 0),
+        "1.21.5" => (55, 0),
+        "1.21.6" => (63, 0),
+        "1.21.7" | "1.21.8" => (64, 0),
+        "1.21.9" | "1.21.10" => (69, 0),
+        "1.21.11" => (75, 0),
+        // 1.21.4, also the fallback for unknown versions
+        _ => (46, 0),
+    }
+}
+
+fn pack_format_for(kind: PackKind, mc_version: &str) -> (u32, u32) {
+    match kind {
+        PackKind::Data => mc_version_to_pack_format(mc_version),
+        PackKind::Resource => mc_version_to_resource_pack_format(mc_version),
     }
 }
 
@@ -42,31 +82,78 @@ fn uses_new_pack_format(mc_version: &str) -> bool {
 
 /// Renders the pack.mcmeta JSON for the given Minecraft version.
 fn render_pack_mcmeta(mc_version: &str) -> String {
-    let (major, minor) = mc_version_to_pack_format(mc_version);
-    if uses_new_pack_format(mc_version) {
-        // 1.21.9+ uses min_format/max_format alongside pack_format
-        if minor > 0 {
-            format!(
-                "{{\n  \"pack\": {{\n    \"pack_format\": [{major}, {minor}],\n    \"min_format\": [{major}, 0],\n    \"max_format\": [{major}, {minor}],\n    \"description\": \"Dev defaults (generated by mcmod)\"\n  }}\n}}\n"
-            )
+    render_pack_mcmeta_range(
+        PackKind::Data,
+        mc_version,
+        mc_version,
+        "Dev defaults (generated by mcmod)",
+    )
+}
+
+/// Renders pack.mcmeta for a pack that supports `min_mc` through `max_mc`.
+/// A range spanning several formats declares `supported_formats` for pre-1.21.9
+/// clients and `min_format`/`max_format` for newer ones.
+pub fn render_pack_mcmeta_range(
+    kind: PackKind,
+    min_mc: &str,
+    max_mc: &str,
+    description: &str,
+) -> String {
+    let (min_major, _) = pack_format_for(kind, min_mc);
+    let (major, minor) = pack_format_for(kind, max_mc);
+    let description = serde_json::to_string(description).unwrap_or_default();
+
+    let fields = if min_major == major {
+        if !uses_new_pack_format(max_mc) {
+            // Pre-1.21.9 uses only pack_format
+            vec![format!("\"pack_format\": {major}")]
+        } else if minor > 0 {
+            // 1.21.9+ uses min_format/max_format alongside pack_format
+            vec![
+                format!("\"pack_format\": [{major}, {minor}]"),
+                format!("\"min_format\": [{major}, 0]"),
+                format!("\"max_format\": [{major}, {minor}]"),
+            ]
         } else {
-            format!(
-                "{{\n  \"pack\": {{\n    \"pack_format\": {major},\n    \"min_format\": {major},\n    \"max_format\": {major},\n    \"description\": \"Dev defaults (generated by mcmod)\"\n  }}\n}}\n"
-            )
+            vec![
+                format!("\"pack_format\": {major}"),
+                format!("\"min_format\": {major}"),
+                format!("\"max_format\": {major}"),
+            ]
         }
     } else {
-        // Pre-1.21.9 uses only pack_format
-        format!(
-            "{{\n  \"pack\": {{\n    \"pack_format\": {major},\n    \"description\": \"Dev defaults (generated by mcmod)\"\n  }}\n}}\n"
-        )
+        // The oldest supported version reads pack_format
+        let mut fields = vec![format!("\"pack_format\": {min_major}")];
+        if !uses_new_pack_format(min_mc) {
+            fields.push(format!("\"supported_formats\": [{min_major}, {major}]"));
+        }
+        if uses_new_pack_format(max_mc) {
+            let max_format = if minor > 0 {
+                format!("[{major}, {minor}]")
+            } else {
+                major.to_string()
+            };
+            fields.push(format!("\"min_format\": {min_major}"));
+            fields.push(format!("\"max_format\": {max_format}"));
+        }
+        fields
+    };
+
+    let mut out = String::from("{\n  \"pack\": {\n");
+    for field in fields {
+        out.push_str(&format!("    {field},\n"));
     }
+    out.push_str(&format!("    \"description\": {description}\n  }}\n}}\n"));
+    out
 }
 
-/// Writes a dev-defaults data pack into the project's run/world directory.
-/// The data pack sets game rules on world load via a mcfunction.
+/// Writes a dev-defaults data pack into the world directory of a dev run directory.
+/// The data pack sets game rules and world settings via a mcfunction that a
+/// scoreboard guard runs only on the world's first load, so later changes made
+/// in game (or by reload-triggered functions) aren't reset.
 /// `mc_version` determines the correct pack_format for pack.mcmeta.
-pub fn write_dev_datapack(project_dir: &Path, config: &GlobalConfig, mc_version: &str) -> Result<()> {
-    let pack_dir = project_dir.join("run/world/datapacks/dev-defaults");
+pub fn write_dev_datapack(run_dir: &Path, config: &GlobalConfig, mc_version: &str) -> Result<()> {
+    let pack_dir = run_dir.join("world/datapacks/dev-defaults");
 
     // pack.mcmeta — version-aware format
     crate::util::write_file(
@@ -74,10 +161,20 @@ pub fn write_dev_datapack(project_dir: &Path, config: &GlobalConfig, mc_version:
         &render_pack_mcmeta(mc_version),
     )?;
 
-    // load function tag — runs dev:init on world load
-    crate::util::write_file(
+    // load function tag — runs dev:load on every load and /reload, merged so
+    // functions added to the tag by hand survive regeneration
+    crate::json_merge::merge_into_file(
         &pack_dir.join("data/minecraft/tags/function/load.json"),
-        "{\n  \"values\": [\n    \"dev:init\"\n  ]\n}\n",
+        "{\n  \"values\": [\n    \"dev:load\"\n  ]\n}\n",
+        crate::json_merge::OnConflict::Keep,
+    )?;
+
+    // load.mcfunction — runs dev:init once per world, remembered in a scoreboard
+    crate::util::write_file(
+        &pack_dir.join("data/dev/function/load.mcfunction"),
+        "scoreboard objectives add dev.init dummy\n\
+         execute unless score #done dev.init matches 1 run function dev:init\n\
+         scoreboard players set #done dev.init 1\n",
     )?;
 
     // init.mcfunction — generated from gamerule config
@@ -89,9 +186,28 @@ pub fn write_dev_datapack(project_dir: &Path, config: &GlobalConfig, mc_version:
     if let Some(v) = config.gamerules.do_weather_cycle {
         commands.push(format!("gamerule doWeatherCycle {v}"));
     }
+    if let Some(v) = config.gamerules.random_tick_speed {
+        commands.push(format!("gamerule randomTickSpeed {v}"));
+    }
+    if let Some(v) = config.gamerules.players_sleeping_percentage {
+        commands.push(format!("gamerule playersSleepingPercentage {v}"));
+    }
+    if let Some(v) = config.gamerules.max_command_chain_length {
+        commands.push(format!("gamerule maxCommandChainLength {v}"));
+    }
     if let Some(ref time) = config.gamerules.time_of_day {
         commands.push(format!("time set {}", time_to_tick(time)));
     }
+    if let Some(ref weather) = config.gamerules.weather {
+        commands.push(format!("weather {weather}"));
+    }
+    if let Some(ref difficulty) = config.gamerules.difficulty {
+        commands.push(format!("difficulty {difficulty}"));
+    }
+    // Load runs before anyone joins, so set the default players get on join
+    if let Some(ref gamemode) = config.gamerules.gamemode {
+        commands.push(format!("defaultgamemode {gamemode}"));
+    }
 
     if !commands.is_empty() {
         commands.push(String::new()); // trailing newline
@@ -105,6 +221,55 @@ pub fn write_dev_datapack(project_dir: &Path, config: &GlobalConfig, mc_version:
     Ok(())
 }
 
+/// Folder of the dev resource pack under `run/resourcepacks/`.
+pub const DEV_RESOURCE_PACK: &str = "dev-resources";
+
+/// Writes an empty dev resource pack into a dev run directory's resourcepacks
+/// for dev-only texture, model and language overrides. options.txt enables it
+/// as `file/dev-resources`.
+pub fn write_dev_resourcepack(run_dir: &Path, mc_version: &str) -> Result<()> {
+    let pack_dir = run_dir.join(format!("resourcepacks/{DEV_RESOURCE_PACK}"));
+    crate::util::write_file(
+        &pack_dir.join("pack.mcmeta"),
+        &render_pack_mcmeta_range(
+            PackKind::Resource,
+            mc_version,
+            mc_version,
+            "Dev resources (generated by mcmod)",
+        ),
+    )?;
+    crate::util::write_file(
+        &pack_dir.join("README.md"),
+        "# Dev resources\n\n\
+         Enabled in the dev client through run/options.txt. Files under `assets/`\n\
+         here override the mod's and vanilla resources, so it's a place for debug\n\
+         textures or language strings that shouldn't ship in the jar.\n",
+    )?;
+    Ok(())
+}
+
+/// Rewrite the pack.mcmeta of a run directory's dev data and resource packs
+/// for `mc_version`, leaving the rest of each pack alone. Returns the files
+/// that changed, relative to `run_dir`.
+pub fn update_dev_pack_formats(run_dir: &Path, mc_version: &str) -> Result<Vec<String>> {
+    let packs = [
+        ("world/datapacks/dev-defaults/pack.mcmeta".to_string(), render_pack_mcmeta(mc_version)),
+        (
+            format!("resourcepacks/{DEV_RESOURCE_PACK}/pack.mcmeta"),
+            render_pack_mcmeta_range(PackKind::Resource, mc_version, mc_version, "Dev resources (generated by mcmod)"),
+        ),
+    ];
+    let mut changed = Vec::new();
+    for (rel, content) in packs {
+        let path = run_dir.join(&rel);
+        let Ok(old) = crate::fs::read_to_string(&path) else { continue };
+        if crate::diff::apply_edit(&path, &old, &content)? {
+            changed.push(rel);
+        }
+    }
+    Ok(changed)
+}
+
 /// Convert a time-of-day name to its Minecraft tick value for mcfunction commands.
 pub fn time_to_tick(time: &str) -> &str {
     match time.to_lowercase().as_str() {
@@ -185,6 +350,35 @@ mod tests {
         assert!(mcmeta.contains("\"max_format\": [94, 1]"));
     }
 
+    #[test]
+    fn test_render_pack_mcmeta_range() {
+        let mcmeta = render_pack_mcmeta_range(PackKind::Data, "1.21.1", "1.21.11", "My \"pack\"");
+        let parsed: serde_json::Value = serde_json::from_str(&mcmeta).unwrap();
+        assert_eq!(parsed["pack"]["pack_format"], 48);
+        assert_eq!(parsed["pack"]["supported_formats"], serde_json::json!([48, 94]));
+        assert_eq!(parsed["pack"]["min_format"], 48);
+        assert_eq!(parsed["pack"]["max_format"], serde_json::json!([94, 1]));
+        assert_eq!(parsed["pack"]["description"], "My \"pack\"");
+
+        let mcmeta = render_pack_mcmeta_range(PackKind::Data, "1.21.2", "1.21.4", "d");
+        assert!(mcmeta.contains("\"supported_formats\": [57, 61]"));
+        assert!(!mcmeta.contains("min_format"));
+
+        let mcmeta = render_pack_mcmeta_range(PackKind::Resource, "1.21.4", "1.21.10", "d");
+        assert!(mcmeta.contains("\"pack_format\": 46"));
+        assert!(mcmeta.contains("\"supported_formats\": [46, 69]"));
+        assert!(mcmeta.contains("\"max_format\": 69"));
+    }
+
+    #[test]
+    fn test_mc_version_to_resource_pack_format() {
+        assert_eq!(mc_version_to_resource_pack_format("1.21.1"), (34, 0));
+        assert_eq!(mc_version_to_resource_pack_format("1.21.4"), (46, 0));
+        assert_eq!(mc_version_to_resource_pack_format("1.21.8"), (64, 0));
+        assert_eq!(mc_version_to_resource_pack_format("1.21.11"), (75, 0));
+        assert_eq!(mc_version_to_resource_pack_format("1.21.15"), (75, 0));
+    }
+
     #[test]
     fn test_unknown_version_fallback() {
         // Unknown future version with high minor should use latest known
@@ -205,6 +399,70 @@ mod tests {
         assert!(validate_time_of_day("banana").is_err());
     }
 
+    #[test]
+    fn test_dev_datapack_integer_gamerules() {
+        let dir = Path::new("/memory/devpack");
+        let mut config = GlobalConfig::default();
+        config.gamerules.random_tick_speed = Some(10);
+        config.gamerules.players_sleeping_percentage = Some(0);
+        let (result, fs) = crate::fs::in_memory(|| write_dev_datapack(dir, &config, "1.21.4"));
+        result.unwrap();
+
+        let init = fs
+            .read_string(&dir.join("world/datapacks/dev-defaults/data/dev/function/init.mcfunction"))
+            .unwrap();
+        assert_eq!(
+            init,
+            "gamerule doDaylightCycle false\n\
+             gamerule doWeatherCycle false\n\
+             gamerule randomTickSpeed 10\n\
+             gamerule playersSleepingPercentage 0\n\
+             time set day\n\
+             weather clear\n"
+        );
+    }
+
+    #[test]
+    fn test_update_dev_pack_formats() {
+        let dir = Path::new("/memory/run");
+        let (result, fs) = crate::fs::in_memory(|| {
+            write_dev_datapack(dir, &GlobalConfig::default(), "1.21.1")?;
+            write_dev_resourcepack(dir, "1.21.1")?;
+            let moved = update_dev_pack_formats(dir, "1.21.4")?;
+            let again = update_dev_pack_formats(dir, "1.21.4")?;
+            Ok::<_, crate::error::McmodError>((moved, again))
+        });
+        let (moved, again) = result.unwrap();
+        assert_eq!(moved.len(), 2);
+        assert!(again.is_empty());
+        let data = fs.read_string(&dir.join("world/datapacks/dev-defaults/pack.mcmeta")).unwrap();
+        assert!(data.contains("\"pack_format\": 61"));
+        let resources = fs.read_string(&dir.join("resourcepacks/dev-resources/pack.mcmeta")).unwrap();
+        assert!(resources.contains("\"pack_format\": 46"));
+    }
+
+    #[test]
+    fn test_dev_datapack_runs_init_once() {
+        let dir = Path::new("/memory/devpack");
+        let mut config = GlobalConfig::default();
+        config.gamerules.weather = None;
+        config.gamerules.difficulty = Some("peaceful".to_string());
+        config.gamerules.gamemode = Some("creative".to_string());
+        let (result, fs) = crate::fs::in_memory(|| write_dev_datapack(dir, &config, "1.21.4"));
+        result.unwrap();
+
+        let pack = dir.join("world/datapacks/dev-defaults");
+        let tag = fs.read_string(&pack.join("data/minecraft/tags/function/load.json")).unwrap();
+        assert!(tag.contains("\"dev:load\""));
+        let load = fs.read_string(&pack.join("data/dev/function/load.mcfunction")).unwrap();
+        assert!(load.contains("execute unless score #done dev.init matches 1 run function dev:init"));
+        assert!(load.ends_with("scoreboard players set #done dev.init 1\n"));
+
+        let init = fs.read_string(&pack.join("data/dev/function/init.mcfunction")).unwrap();
+        assert!(init.ends_with("time set day\ndifficulty peaceful\ndefaultgamemode creative\n"));
+        assert!(!init.contains("weather"));
+    }
+
     #[test]
     fn test_time_to_tick() {
         assert_eq!(time_to_tick("noon"), "day");
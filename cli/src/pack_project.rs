@@ -0,0 +1,180 @@
+use crate::config::McmodConfig;
+use crate::error::Result;
+use crate::pack_format::PackKind;
+use crate::template::{self, render, strip_conditional_blocks};
+use crate::util::{write_binary, write_file};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Data pack or resource pack, from the project type in mcmod.toml.
+fn pack_kind(config: &McmodConfig) -> PackKind {
+    if config.is_datapack() {
+        PackKind::Data
+    } else {
+        PackKind::Resource
+    }
+}
+
+/// Files mcmod generates for a pack project and can recreate from mcmod.toml,
+/// as (relative path, content). Starter content, README and LICENSE are
+/// user-owned and not included.
+pub fn managed_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let mut files = vec![
+        ("pack.mcmeta".to_string(), pack_mcmeta(config)),
+        ("build.gradle.kts".to_string(), render(template::PACK_BUILD_GRADLE, vars)?),
+        ("settings.gradle.kts".to_string(), render(template::PACK_SETTINGS_GRADLE, vars)?),
+        ("gradle.properties".to_string(), render(template::PACK_GRADLE_PROPERTIES, vars)?),
+        (".gitignore".to_string(), crate::template_pack::resolve(template::TMPL_GITIGNORE).to_string()),
+        ("gradlew".to_string(), template::GRADLEW.to_string()),
+        ("gradlew.bat".to_string(), template::GRADLEW_BAT.to_string()),
+        (
+            "gradle/wrapper/gradle-wrapper.properties".to_string(),
+            template::GRADLE_WRAPPER_PROPS.to_string(),
+        ),
+    ];
+    if pack_kind(config) == PackKind::Data {
+        files.push((
+            "data/minecraft/tags/function/load.json".to_string(),
+            render(template::DP_LOAD_TAG, vars)?,
+        ));
+    }
+    if config.features.ci {
+        files.push((
+            ".github/workflows/build.yml".to_string(),
+            template::render_standalone_ci(config, vars)?,
+        ));
+    }
+    Ok(files)
+}
+
+/// pack.mcmeta covering every Minecraft version targeted in mcmod.toml.
+pub fn pack_mcmeta(config: &McmodConfig) -> String {
+    let targets = &config.versions.targets;
+    let min = targets.first().map(|t| t.minecraft.as_str()).unwrap_or("1.21.4");
+    let max = targets.last().map(|t| t.max_minecraft.as_str()).unwrap_or(min);
+    crate::pack_format::render_pack_mcmeta_range(
+        pack_kind(config),
+        min,
+        max,
+        &config.mod_info.description,
+    )
+}
+
+/// Write a new pack project into `dir`. `optifine` adds the OptiFine/CTM
+/// folder layout to resource packs.
+pub fn write_project(
+    dir: &Path,
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+    optifine: bool,
+) -> Result<()> {
+    for (rel, content) in managed_files(config, vars)? {
+        write_file(&dir.join(rel), &content)?;
+    }
+    write_binary(
+        &dir.join("gradle/wrapper/gradle-wrapper.jar"),
+        template::GRADLE_WRAPPER_JAR,
+    )?;
+    write_file(&dir.join("LICENSE"), &render(template::TMPL_LICENSE, vars)?)?;
+
+    match pack_kind(config) {
+        PackKind::Data => {
+            let mod_id = &config.mod_info.mod_id;
+            write_file(
+                &dir.join(format!("data/{mod_id}/function/load.mcfunction")),
+                &render(template::DP_LOAD_FUNCTION, vars)?,
+            )?;
+            write_file(&dir.join("README.md"), &render(template::DP_README, vars)?)?;
+        }
+        PackKind::Resource => {
+            write_file(
+                &dir.join("assets/minecraft/textures/README.md"),
+                template::RP_TEXTURES_NOTE,
+            )?;
+            if optifine {
+                write_file(
+                    &dir.join("assets/minecraft/optifine/README.md"),
+                    template::RP_OPTIFINE_NOTE,
+                )?;
+                crate::util::ensure_dir(&dir.join("assets/minecraft/optifine/ctm"))?;
+                crate::util::ensure_dir(&dir.join("assets/minecraft/optifine/cit"))?;
+            }
+            let readme = strip_conditional_blocks(template::RP_README, &[("optifine", optifine)]);
+            write_file(&dir.join("README.md"), &render(&readme, vars)?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProjectType, Versions};
+
+    fn pack_config(project_type: ProjectType, ci: bool) -> McmodConfig {
+        let mut config = McmodConfig::new(
+            "mypack".to_string(),
+            "My Pack".to_string(),
+            "com.example.mypack".to_string(),
+            "Author".to_string(),
+            "A pack".to_string(),
+            "java".to_string(),
+            false,
+            false,
+            ci,
+            false,
+            None,
+            Versions::default(),
+        );
+        config.mod_info.project_type = project_type;
+        config
+    }
+
+    #[test]
+    fn test_write_datapack_project() {
+        let dir = std::env::temp_dir().join(format!("mcmod_datapack_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = pack_config(ProjectType::Datapack, true);
+        let vars = template::build_common_vars(&config);
+        write_project(&dir, &config, &vars, false).unwrap();
+
+        let mcmeta = std::fs::read_to_string(dir.join("pack.mcmeta")).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&mcmeta).unwrap();
+        assert_eq!(parsed["pack"]["pack_format"], 61);
+        assert_eq!(parsed["pack"]["description"], "A pack");
+
+        let tag = std::fs::read_to_string(dir.join("data/minecraft/tags/function/load.json")).unwrap();
+        assert!(tag.contains("\"mypack:load\""));
+        assert!(dir.join("data/mypack/function/load.mcfunction").is_file());
+        assert!(std::fs::read_to_string(dir.join("build.gradle.kts")).unwrap().contains("Zip"));
+        assert!(dir.join(".github/workflows/build.yml").is_file());
+        assert!(dir.join("gradle/wrapper/gradle-wrapper.jar").is_file());
+        assert!(!dir.join("src").exists());
+        assert!(!dir.join("assets").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_write_resourcepack_project() {
+        let dir = std::env::temp_dir().join(format!("mcmod_resourcepack_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = pack_config(ProjectType::Resourcepack, false);
+        let vars = template::build_common_vars(&config);
+        write_project(&dir, &config, &vars, true).unwrap();
+
+        let mcmeta = std::fs::read_to_string(dir.join("pack.mcmeta")).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&mcmeta).unwrap();
+        assert_eq!(parsed["pack"]["pack_format"], 46, "resource pack formats differ from data packs");
+
+        assert!(dir.join("assets/minecraft/optifine/ctm").is_dir());
+        assert!(dir.join("assets/minecraft/optifine/cit").is_dir());
+        let readme = std::fs::read_to_string(dir.join("README.md")).unwrap();
+        assert!(readme.contains("OptiFine"));
+        assert!(!dir.join("data").exists());
+        assert!(!dir.join(".github").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
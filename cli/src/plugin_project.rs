@@ -0,0 +1,484 @@
+use crate::config::McmodConfig;
+use crate::error::Result;
+use crate::template::{self, render, strip_conditional_blocks};
+use crate::util::{package_to_path, to_pascal_case, write_binary, write_file};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Template variables for a Paper plugin: the common set plus the
+/// paper-plugin.yml fields and the Paper version to build against.
+fn plugin_vars(config: &McmodConfig, vars: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut vars = vars.clone();
+
+    // Plugin names may only contain letters, digits, `_`, `-` and `.`
+    let name: String = config
+        .mod_info
+        .mod_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .collect();
+    let name = if name.is_empty() { config.mod_info.mod_id.clone() } else { name };
+    vars.insert("plugin_name".to_string(), name);
+
+    // JSON strings are valid YAML scalars, which keeps colons and quotes safe
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    vars.insert("plugin_display_name".to_string(), quote(&config.mod_info.mod_name));
+    vars.insert("plugin_description".to_string(), quote(&config.mod_info.description));
+    vars.insert("plugin_author".to_string(), quote(&config.mod_info.author));
+
+    // Compile against the oldest targeted version so the plugin loads on all of them
+    let minecraft = config
+        .versions
+        .targets
+        .first()
+        .map(|t| t.minecraft.clone())
+        .unwrap_or_else(|| "1.21.4".to_string());
+    vars.insert("paper_minecraft".to_string(), minecraft);
+    vars
+}
+
+/// The plugin's main class and its path for the project's language.
+fn render_main_class(config: &McmodConfig, vars: &HashMap<String, String>) -> Result<(String, String)> {
+    render_class(config, vars, template::PLUGIN_MAIN_JAVA, template::PLUGIN_MAIN_KT)
+}
+
+/// Render `java` or `kotlin` as the `class_name` class in `package_path`,
+/// returning the source and its path.
+fn render_class(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+    java: &str,
+    kotlin: &str,
+) -> Result<(String, String)> {
+    let (tmpl, ext, source_dir) = if config.mod_info.language == "kotlin" {
+        (kotlin, "kt", "kotlin")
+    } else {
+        (java, "java", "java")
+    };
+    let package_path = vars.get("package_path").cloned().unwrap_or_default();
+    let class_name = vars.get("class_name").cloned().unwrap_or_default();
+    let path = format!("src/main/{source_dir}/{package_path}/{class_name}.{ext}");
+    Ok((render(tmpl, vars)?, path))
+}
+
+/// Files mcmod generates for a plugin project and can recreate from
+/// mcmod.toml, as (relative path, content). The main class, README and
+/// LICENSE are user-owned and not included.
+pub fn managed_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let vars = plugin_vars(config, vars);
+    let build = strip_conditional_blocks(
+        template::PLUGIN_BUILD_GRADLE,
+        &[("kotlin", config.mod_info.language == "kotlin")],
+    );
+    let mut files = vec![
+        ("build.gradle.kts".to_string(), render(&build, &vars)?),
+        ("settings.gradle.kts".to_string(), render(template::PACK_SETTINGS_GRADLE, &vars)?),
+        ("gradle.properties".to_string(), render(template::PLUGIN_GRADLE_PROPERTIES, &vars)?),
+        (
+            "src/main/resources/paper-plugin.yml".to_string(),
+            render(template::PLUGIN_YML, &vars)?,
+        ),
+        (".gitignore".to_string(), crate::template_pack::resolve(template::TMPL_GITIGNORE).to_string()),
+        ("gradlew".to_string(), template::GRADLEW.to_string()),
+        ("gradlew.bat".to_string(), template::GRADLEW_BAT.to_string()),
+        (
+            "gradle/wrapper/gradle-wrapper.properties".to_string(),
+            template::GRADLE_WRAPPER_PROPS.to_string(),
+        ),
+    ];
+    if config.features.ci {
+        files.push((
+            ".github/workflows/build.yml".to_string(),
+            template::render_standalone_ci(config, &vars)?,
+        ));
+    }
+    Ok(files)
+}
+
+/// Write a new Paper plugin project into `dir`.
+pub fn write_project(dir: &Path, config: &McmodConfig, vars: &HashMap<String, String>) -> Result<()> {
+    for (rel, content) in managed_files(config, vars)? {
+        write_file(&dir.join(rel), &content)?;
+    }
+    write_binary(
+        &dir.join("gradle/wrapper/gradle-wrapper.jar"),
+        template::GRADLE_WRAPPER_JAR,
+    )?;
+
+    let vars = plugin_vars(config, vars);
+    let (source, source_path) = render_main_class(config, &vars)?;
+    write_file(&crate::sandbox::resolve_output(dir, &source_path)?, &source)?;
+    write_file(&dir.join("README.md"), &render(template::PLUGIN_README, &vars)?)?;
+    write_file(&dir.join("LICENSE"), &render(template::TMPL_LICENSE, &vars)?)?;
+    Ok(())
+}
+
+/// Subproject `mcmod add paper` creates in a mod project.
+pub const PAPER_MODULE_DIR: &str = "paper";
+
+/// Template variables for a plugin subproject of a mod project: the plugin
+/// lives in a `<module>` subpackage with a `<Name>Plugin` main class.
+fn module_vars(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+    module: &str,
+) -> HashMap<String, String> {
+    let mut vars = plugin_vars(config, vars);
+    let package = format!("{}.{module}", config.mod_info.package);
+    vars.insert("package_path".to_string(), package_to_path(&package));
+    vars.insert("package".to_string(), package);
+    vars.insert(
+        "class_name".to_string(),
+        format!("{}Plugin", to_pascal_case(&config.mod_info.mod_id)),
+    );
+    vars
+}
+
+fn paper_module_vars(config: &McmodConfig, vars: &HashMap<String, String>) -> HashMap<String, String> {
+    module_vars(config, vars, PAPER_MODULE_DIR)
+}
+
+/// Files of a mod project's paper/ module that mcmod can recreate from
+/// mcmod.toml, as (path relative to the module, content).
+pub fn paper_module_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let vars = paper_module_vars(config, vars);
+    let build = strip_conditional_blocks(
+        template::PAPER_MODULE_BUILD_GRADLE,
+        &[("kotlin", config.mod_info.language == "kotlin")],
+    );
+    Ok(vec![
+        ("build.gradle.kts".to_string(), render(&build, &vars)?),
+        ("gradle.properties".to_string(), render(template::PAPER_MODULE_PROPERTIES, &vars)?),
+        (
+            "src/main/resources/paper-plugin.yml".to_string(),
+            render(template::PLUGIN_YML, &vars)?,
+        ),
+    ])
+}
+
+/// Write the paper/ module of a mod project: its build script, plugin
+/// metadata, and main class.
+pub fn write_paper_module(dir: &Path, config: &McmodConfig, vars: &HashMap<String, String>) -> Result<()> {
+    let module = dir.join(PAPER_MODULE_DIR);
+    for (rel, content) in paper_module_files(config, vars)? {
+        write_file(&module.join(rel), &content)?;
+    }
+    let vars = paper_module_vars(config, vars);
+    let (source, source_path) = render_main_class(config, &vars)?;
+    write_file(&crate::sandbox::resolve_output(&module, &source_path)?, &source)?;
+    Ok(())
+}
+
+/// Subproject a mod project's `sponge` loader lives in.
+pub const SPONGE_MODULE_DIR: &str = "sponge";
+
+/// SpongeAPI releases by the first Minecraft version they target. A newer
+/// Minecraft version gets the latest API listed here.
+const SPONGE_API: &[(&str, &str)] = &[
+    ("1.21.1", "12.0.0"),
+    ("1.21.3", "13.0.0"),
+    ("1.21.4", "14.0.0"),
+];
+
+/// SpongeAPI version for a Minecraft version.
+fn sponge_api_for(minecraft: &str) -> &'static str {
+    SPONGE_API
+        .iter()
+        .rev()
+        .find(|(mc, _)| crate::version_meta::compare_versions(minecraft, mc).is_ge())
+        .unwrap_or(&SPONGE_API[0])
+        .1
+}
+
+/// Template variables for the sponge/ module, which builds against the
+/// SpongeAPI for the oldest target.
+fn sponge_module_vars(config: &McmodConfig, vars: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut vars = module_vars(config, vars, SPONGE_MODULE_DIR);
+    let minecraft = config.versions.targets.first().map(|t| t.minecraft.as_str()).unwrap_or_default();
+    vars.insert("sponge_api".to_string(), sponge_api_for(minecraft).to_string());
+    vars
+}
+
+/// Files of a mod project's sponge/ module that mcmod can recreate from
+/// mcmod.toml, as (path relative to the module, content).
+pub fn sponge_module_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let vars = sponge_module_vars(config, vars);
+    let build = strip_conditional_blocks(
+        template::SPONGE_MODULE_BUILD_GRADLE,
+        &[("kotlin", config.mod_info.language == "kotlin")],
+    );
+    Ok(vec![
+        ("build.gradle.kts".to_string(), render(&build, &vars)?),
+        ("gradle.properties".to_string(), render(template::SPONGE_MODULE_PROPERTIES, &vars)?),
+        (
+            "src/main/resources/META-INF/sponge_plugins.json".to_string(),
+            render(template::SPONGE_PLUGINS_JSON, &vars)?,
+        ),
+    ])
+}
+
+/// Write the sponge/ module of a mod project: its SpongeGradle build script,
+/// plugin metadata, and main class.
+pub fn write_sponge_module(dir: &Path, config: &McmodConfig, vars: &HashMap<String, String>) -> Result<()> {
+    let module = dir.join(SPONGE_MODULE_DIR);
+    for (rel, content) in sponge_module_files(config, vars)? {
+        write_file(&module.join(rel), &content)?;
+    }
+    let vars = sponge_module_vars(config, vars);
+    let (source, source_path) =
+        render_class(config, &vars, template::SPONGE_MAIN_JAVA, template::SPONGE_MAIN_KT)?;
+    write_file(&crate::sandbox::resolve_output(&module, &source_path)?, &source)?;
+    Ok(())
+}
+
+/// Subproject `mcmod add velocity` creates in a mod project.
+pub const VELOCITY_MODULE_DIR: &str = "velocity";
+
+/// Velocity API the velocity/ module builds against. Proxy plugins don't
+/// depend on the Minecraft version.
+const VELOCITY_API: &str = "3.4.0-SNAPSHOT";
+
+fn velocity_module_vars(config: &McmodConfig, vars: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut vars = module_vars(config, vars, VELOCITY_MODULE_DIR);
+    vars.insert("velocity_api".to_string(), VELOCITY_API.to_string());
+    vars
+}
+
+/// Files of a mod project's velocity/ module that mcmod can recreate from
+/// mcmod.toml, as (path relative to the module, content).
+pub fn velocity_module_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let vars = velocity_module_vars(config, vars);
+    let build = strip_conditional_blocks(
+        template::VELOCITY_MODULE_BUILD_GRADLE,
+        &[("kotlin", config.mod_info.language == "kotlin")],
+    );
+    Ok(vec![
+        ("build.gradle.kts".to_string(), render(&build, &vars)?),
+        ("gradle.properties".to_string(), render(template::VELOCITY_MODULE_PROPERTIES, &vars)?),
+        (
+            "src/main/resources/velocity-plugin.json".to_string(),
+            render(template::VELOCITY_PLUGIN_JSON, &vars)?,
+        ),
+    ])
+}
+
+/// Write the velocity/ module of a mod project: its build script, plugin
+/// metadata, and `@Plugin` main class.
+pub fn write_velocity_module(dir: &Path, config: &McmodConfig, vars: &HashMap<String, String>) -> Result<()> {
+    let module = dir.join(VELOCITY_MODULE_DIR);
+    for (rel, content) in velocity_module_files(config, vars)? {
+        write_file(&module.join(rel), &content)?;
+    }
+    let vars = velocity_module_vars(config, vars);
+    let (source, source_path) =
+        render_class(config, &vars, template::VELOCITY_MAIN_JAVA, template::VELOCITY_MAIN_KT)?;
+    write_file(&crate::sandbox::resolve_output(&module, &source_path)?, &source)?;
+    Ok(())
+}
+
+/// Subproject `mcmod add spigot` creates in a mod project.
+pub const SPIGOT_MODULE_DIR: &str = "spigot";
+
+/// Template variables for the spigot/ module. Spigot's `api-version` is the
+/// major.minor release (`1.21`), which it accepts across patch versions.
+fn spigot_module_vars(config: &McmodConfig, vars: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut vars = module_vars(config, vars, SPIGOT_MODULE_DIR);
+    let minecraft = vars.get("paper_minecraft").cloned().unwrap_or_default();
+    let api_version = minecraft.splitn(3, '.').take(2).collect::<Vec<_>>().join(".");
+    vars.insert("spigot_api_version".to_string(), api_version);
+    vars
+}
+
+/// Files of a mod project's spigot/ module that mcmod can recreate from
+/// mcmod.toml, as (path relative to the module, content).
+pub fn spigot_module_files(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let vars = spigot_module_vars(config, vars);
+    let build = strip_conditional_blocks(
+        template::SPIGOT_MODULE_BUILD_GRADLE,
+        &[("kotlin", config.mod_info.language == "kotlin")],
+    );
+    Ok(vec![
+        ("build.gradle.kts".to_string(), render(&build, &vars)?),
+        ("gradle.properties".to_string(), render(template::SPIGOT_MODULE_PROPERTIES, &vars)?),
+        (
+            "src/main/resources/plugin.yml".to_string(),
+            render(template::SPIGOT_PLUGIN_YML, &vars)?,
+        ),
+    ])
+}
+
+/// Write the spigot/ module of a mod project: its build script, plugin.yml,
+/// and `JavaPlugin` main class.
+pub fn write_spigot_module(dir: &Path, config: &McmodConfig, vars: &HashMap<String, String>) -> Result<()> {
+    let module = dir.join(SPIGOT_MODULE_DIR);
+    for (rel, content) in spigot_module_files(config, vars)? {
+        write_file(&module.join(rel), &content)?;
+    }
+    let vars = spigot_module_vars(config, vars);
+    let (source, source_path) = render_main_class(config, &vars)?;
+    write_file(&crate::sandbox::resolve_output(&module, &source_path)?, &source)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProjectType, Versions};
+
+    fn plugin_config(language: &str) -> McmodConfig {
+        let mut config = McmodConfig::new(
+            "my_plugin".to_string(),
+            "My Plugin".to_string(),
+            "com.example.myplugin".to_string(),
+            "Author".to_string(),
+            "Does things: well".to_string(),
+            language.to_string(),
+            false,
+            false,
+            true,
+            false,
+            None,
+            Versions::default(),
+        );
+        config.mod_info.project_type = ProjectType::PaperPlugin;
+        config
+    }
+
+    #[test]
+    fn test_managed_files() {
+        let config = plugin_config("java");
+        let vars = template::build_common_vars(&config);
+        let files: HashMap<String, String> = managed_files(&config, &vars).unwrap().into_iter().collect();
+
+        let yml = &files["src/main/resources/paper-plugin.yml"];
+        assert!(yml.contains("name: MyPlugin\n"));
+        assert!(yml.contains("main: com.example.myplugin.MyPlugin\n"));
+        assert!(yml.contains("description: \"Does things: well\"\n"));
+        assert!(yml.contains("api-version: '1.21.4'"));
+
+        let props = &files["gradle.properties"];
+        assert!(props.contains("paper.version=1.21.4-R0.1-SNAPSHOT"));
+        assert!(files["build.gradle.kts"].contains("paper-api"));
+        assert!(!files["build.gradle.kts"].contains("kotlin(\"jvm\")"));
+        assert!(files[".github/workflows/build.yml"].contains("build/libs/*.jar"));
+    }
+
+    #[test]
+    fn test_render_main_class() {
+        let config = plugin_config("kotlin");
+        let vars = plugin_vars(&config, &template::build_common_vars(&config));
+        let (source, path) = render_main_class(&config, &vars).unwrap();
+        assert_eq!(path, "src/main/kotlin/com/example/myplugin/MyPlugin.kt");
+        assert!(source.contains("class MyPlugin : JavaPlugin()"));
+    }
+
+    #[test]
+    fn test_paper_module_files() {
+        let mut config = plugin_config("java");
+        config.mod_info.project_type = ProjectType::Mod;
+        let vars = template::build_common_vars(&config);
+        let files: HashMap<String, String> = paper_module_files(&config, &vars).unwrap().into_iter().collect();
+
+        let yml = &files["src/main/resources/paper-plugin.yml"];
+        assert!(yml.contains("main: com.example.myplugin.paper.MyPluginPlugin\n"));
+        assert!(files["build.gradle.kts"].contains("paperweight.paperDevBundle"));
+        assert!(!files["build.gradle.kts"].contains("kotlin(\"jvm\")"));
+        assert_eq!(files["gradle.properties"].lines().last(), Some("paper.minecraft=1.21.4"));
+
+        let (source, path) = render_main_class(&config, &paper_module_vars(&config, &vars)).unwrap();
+        assert_eq!(path, "src/main/java/com/example/myplugin/paper/MyPluginPlugin.java");
+        assert!(source.starts_with("package com.example.myplugin.paper;"));
+    }
+
+    #[test]
+    fn test_sponge_module_files() {
+        let mut config = plugin_config("kotlin");
+        config.mod_info.project_type = ProjectType::Mod;
+        let vars = template::build_common_vars(&config);
+        let files: HashMap<String, String> = sponge_module_files(&config, &vars).unwrap().into_iter().collect();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&files["src/main/resources/META-INF/sponge_plugins.json"]).unwrap();
+        assert_eq!(json["plugins"][0]["id"], "my_plugin");
+        assert_eq!(json["plugins"][0]["entrypoint"], "com.example.myplugin.sponge.MyPluginPlugin");
+        assert_eq!(json["plugins"][0]["description"], "Does things: well");
+        assert_eq!(json["global"]["dependencies"][0]["version"], "14.0.0");
+        assert!(files["build.gradle.kts"].contains("org.spongepowered.gradle.plugin"));
+        assert!(files["build.gradle.kts"].contains("kotlin(\"jvm\")"));
+        assert_eq!(files["gradle.properties"].lines().last(), Some("sponge.api=14.0.0"));
+
+        let vars = sponge_module_vars(&config, &vars);
+        let (source, path) =
+            render_class(&config, &vars, template::SPONGE_MAIN_JAVA, template::SPONGE_MAIN_KT).unwrap();
+        assert_eq!(path, "src/main/kotlin/com/example/myplugin/sponge/MyPluginPlugin.kt");
+        assert!(source.contains("@Plugin(\"my_plugin\")"));
+    }
+
+    #[test]
+    fn test_velocity_module_files() {
+        let mut config = plugin_config("java");
+        config.mod_info.project_type = ProjectType::Mod;
+        config.mod_info.mod_name = "My \"Quoted\" Plugin".to_string();
+        let vars = template::build_common_vars(&config);
+        let files: HashMap<String, String> = velocity_module_files(&config, &vars).unwrap().into_iter().collect();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&files["src/main/resources/velocity-plugin.json"]).unwrap();
+        assert_eq!(json["id"], "my_plugin");
+        assert_eq!(json["name"], "My \"Quoted\" Plugin");
+        assert_eq!(json["authors"][0], "Author");
+        assert_eq!(json["main"], "com.example.myplugin.velocity.MyPluginPlugin");
+        assert!(files["build.gradle.kts"].contains("com.velocitypowered:velocity-api"));
+
+        let vars = velocity_module_vars(&config, &vars);
+        let (source, path) =
+            render_class(&config, &vars, template::VELOCITY_MAIN_JAVA, template::VELOCITY_MAIN_KT).unwrap();
+        assert_eq!(path, "src/main/java/com/example/myplugin/velocity/MyPluginPlugin.java");
+        assert!(source.contains("@Plugin(id = \"my_plugin\")"));
+    }
+
+    #[test]
+    fn test_spigot_module_files() {
+        let mut config = plugin_config("java");
+        config.mod_info.project_type = ProjectType::Mod;
+        config.versions = Versions {
+            targets: crate::version_meta::targets_to_ranges(&["1.21.1"]),
+            ..Versions::default()
+        };
+        let vars = template::build_common_vars(&config);
+        let files: HashMap<String, String> = spigot_module_files(&config, &vars).unwrap().into_iter().collect();
+
+        let yml = &files["src/main/resources/plugin.yml"];
+        assert!(yml.contains("main: com.example.myplugin.spigot.MyPluginPlugin\n"));
+        assert!(yml.contains("api-version: '1.21'\n"));
+        assert!(files["build.gradle.kts"].contains("org.spigotmc:spigot-api"));
+        assert_eq!(files["gradle.properties"].lines().last(), Some("spigot.minecraft=1.21.1"));
+
+        let (source, _) = render_main_class(&config, &spigot_module_vars(&config, &vars)).unwrap();
+        assert!(source.contains("extends JavaPlugin"));
+    }
+
+    #[test]
+    fn test_sponge_api_for() {
+        assert_eq!(sponge_api_for("1.21.1"), "12.0.0");
+        assert_eq!(sponge_api_for("1.21.2"), "12.0.0");
+        assert_eq!(sponge_api_for("1.21.4"), "14.0.0");
+        assert_eq!(sponge_api_for("1.21.10"), "14.0.0");
+        assert_eq!(sponge_api_for("1.20.1"), "12.0.0");
+    }
+}
@@ -0,0 +1,223 @@
+//! Init presets: `mcmod init` answers saved to a TOML file, so a team can
+//! scaffold projects the same way from scripts without prompts.
+//!
+//! Every field is optional and command-line flags win over the preset. Two
+//! placeholders keep a preset reusable across projects: `{dir}` in `mod_id`
+//! is the project directory's name as a mod id, and `{mod_id}` in `mod_id`'s
+//! dependents (`mod_name`, `package`, `modrinth_id`) is the final mod id.
+
+use crate::commands::init::{default_mod_name, slugify_dir_name, InitOptions};
+use crate::config::{Environment, FabricEntrypoint, McmodConfig, ProjectType};
+use crate::error::{Context, McmodError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mod_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mod_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<ProjectType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loaders: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub minecraft: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publishing: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modrinth_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curseforge_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub testing: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_info: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architectury: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_environment: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entrypoints: Option<Vec<FabricEntrypoint>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optifine: Option<bool>,
+}
+
+const HEADER: &str = "# mcmod init preset: `mcmod init --preset <this file>`\n\
+# Command-line flags override these answers. {dir} in mod_id is the project\n\
+# directory's name; {mod_id} in mod_name, package, and modrinth_id is the mod id.\n\n";
+
+impl Preset {
+    pub fn load(path: &Path) -> Result<Preset> {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Reading preset {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Parsing preset {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string_pretty(self).map_err(McmodError::TomlSerialize)?;
+        crate::util::write_file(path, &format!("{HEADER}{body}"))
+    }
+
+    /// Fill the options the command line left unset. The result never
+    /// prompts for the mod id, name, or description, so a preset always runs
+    /// non-interactively.
+    pub fn apply(self, mut opts: InitOptions) -> InitOptions {
+        let dir_id = slugify_dir_name(&opts.dir);
+        let mod_id = opts
+            .mod_id
+            .take()
+            .or_else(|| self.mod_id.map(|id| id.replace("{dir}", &dir_id)))
+            .unwrap_or(dir_id);
+        let expand = |value: Option<String>| value.map(|v| v.replace("{mod_id}", &mod_id));
+
+        opts.mod_name = opts.mod_name.or(expand(self.mod_name)).or_else(|| Some(default_mod_name(&mod_id)));
+        opts.package = opts.package.or(expand(self.package));
+        opts.modrinth_id = opts.modrinth_id.or(expand(self.modrinth_id));
+        opts.author = opts.author.or(self.author);
+        opts.email = opts.email.or(self.email);
+        opts.github = opts.github.or(self.github);
+        opts.description = opts.description.or(self.description).or_else(|| Some("A Minecraft mod".to_string()));
+        opts.project_type = opts.project_type.or(self.project_type);
+        opts.language = opts.language.or(self.language);
+        if opts.loaders.is_empty() {
+            opts.loaders = self.loaders;
+        }
+        opts.environment = opts.environment.or(self.environment);
+        if opts.minecraft_versions.is_empty() {
+            opts.minecraft_versions = self.minecraft;
+        }
+        opts.ci = opts.ci.or(self.ci);
+        opts.server = opts.server.or(self.server);
+        opts.publishing = opts.publishing.or(self.publishing);
+        opts.curseforge_id = opts.curseforge_id.or(self.curseforge_id);
+        opts.testing = opts.testing.or(self.testing);
+        opts.build_info = opts.build_info.or(self.build_info);
+        opts.architectury = opts.architectury.or(self.architectury);
+        opts.split_environment = opts.split_environment.or(self.split_environment);
+        opts.fabric_entrypoints = opts.fabric_entrypoints.or(self.entrypoints);
+        opts.optifine = opts.optifine.or(self.optifine);
+        opts.mod_id = Some(mod_id);
+        opts
+    }
+
+    /// The answers that produced `config`, with the mod id, name, package,
+    /// and Modrinth slug turned back into placeholders where they follow the
+    /// defaults, so the preset fits the next project too.
+    pub fn from_answers(config: &McmodConfig, dir: &Path, server: bool, optifine: bool) -> Preset {
+        let info = &config.mod_info;
+        let mod_id = &info.mod_id;
+        let standalone = matches!(
+            info.project_type,
+            ProjectType::Datapack | ProjectType::Resourcepack | ProjectType::PaperPlugin
+        );
+        let loaders = [
+            ("fabric", config.loaders.fabric),
+            ("neoforge", config.loaders.neoforge),
+            ("forge", config.loaders.forge),
+            ("quilt", config.loaders.quilt),
+            ("sponge", config.loaders.sponge),
+        ];
+        let publishing = config.publishing.as_ref();
+        Preset {
+            mod_id: Some(if *mod_id == slugify_dir_name(dir) { "{dir}".to_string() } else { mod_id.clone() }),
+            mod_name: (info.mod_name != default_mod_name(mod_id)).then(|| info.mod_name.clone()),
+            package: Some(match info.package.strip_suffix(&format!(".{mod_id}")) {
+                Some(prefix) => format!("{prefix}.{{mod_id}}"),
+                None => info.package.clone(),
+            }),
+            author: Some(info.author.clone()),
+            email: info.email.clone(),
+            github: info.github.clone(),
+            description: Some(info.description.clone()),
+            project_type: Some(info.project_type),
+            language: Some(info.language.clone()),
+            loaders: loaders.iter().filter(|(_, on)| *on).map(|(l, _)| l.to_string()).collect(),
+            environment: (!standalone).then_some(info.environment),
+            minecraft: config.versions.targets.iter().map(|t| t.minecraft.clone()).collect(),
+            ci: Some(config.features.ci),
+            server: (!standalone).then_some(server),
+            publishing: (!standalone).then_some(publishing.is_some()),
+            modrinth_id: publishing.map(|p| p.modrinth_id.replace(mod_id.as_str(), "{mod_id}")),
+            curseforge_id: publishing.and_then(|p| p.curseforge_id.clone()),
+            testing: (!standalone).then_some(config.features.testing),
+            build_info: (!standalone).then_some(config.features.build_info),
+            architectury: (!standalone).then_some(config.features.architectury),
+            split_environment: (!standalone).then_some(config.features.split_environment),
+            entrypoints: (!standalone).then_some(config.features.fabric_entrypoints.clone()),
+            optifine: (info.project_type == ProjectType::Resourcepack).then_some(optifine),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_apply_and_round_trip() {
+        let preset: Preset = toml::from_str(
+            "mod_id = \"{dir}\"\npackage = \"io.github.team.{mod_id}\"\nauthor = \"Team\"\n\
+             loaders = [\"fabric\", \"quilt\"]\nci = false\nentrypoints = [\"client\"]\n",
+        )
+        .unwrap();
+        let mut opts = init::tests::options(Path::new("/work/Cool Mod"), &[]);
+        opts.mod_id = None;
+        opts.package = None;
+        opts.author = None;
+        opts.description = None;
+        opts.ci = None;
+        opts.fabric_entrypoints = None;
+        opts.language = Some("kotlin".to_string());
+        let opts = preset.clone().apply(opts);
+        assert_eq!(opts.mod_id.as_deref(), Some("cool_mod"));
+        assert_eq!(opts.package.as_deref(), Some("io.github.team.cool_mod"));
+        assert_eq!(opts.author.as_deref(), Some("Team"));
+        assert_eq!(opts.description.as_deref(), Some("A Minecraft mod"));
+        assert_eq!(opts.loaders, ["fabric", "quilt"]);
+        assert_eq!(opts.ci, Some(false));
+        assert_eq!(opts.fabric_entrypoints, Some(vec![FabricEntrypoint::Client]));
+        // The command line wins
+        assert_eq!(opts.language.as_deref(), Some("kotlin"));
+
+        assert!(toml::from_str::<Preset>("mod_idd = \"x\"").is_err());
+
+        let dir = PathBuf::from("/memory/cool_mod");
+        let (result, _) = crate::fs::in_memory(|| {
+            let mut opts = init::tests::options(&dir, &["fabric", "neoforge"]);
+            opts.mod_id = Some("cool_mod".to_string());
+            opts.mod_name = Some("Cool Mod".to_string());
+            opts.package = Some("io.github.team.cool_mod".to_string());
+            init::run(opts)?;
+            McmodConfig::load(&dir)
+        });
+        let saved = Preset::from_answers(&result.unwrap(), &dir, false, false);
+        assert_eq!(saved.mod_id.as_deref(), Some("{dir}"));
+        assert_eq!(saved.package.as_deref(), Some("io.github.team.{mod_id}"));
+        assert_eq!(saved.mod_name, None);
+        assert_eq!(saved.loaders, ["fabric", "neoforge"]);
+        let reparsed: Preset = toml::from_str(&toml::to_string_pretty(&saved).unwrap()).unwrap();
+        assert_eq!(reparsed, saved);
+    }
+}
@@ -0,0 +1,158 @@
+//! Timing for `--profile`: version fetches, template rendering, and file
+//! writes record how long they took, and a summary is printed when the
+//! command finishes. Recording is a no-op unless profiling was enabled.
+
+use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Fetch,
+    Render,
+    Write,
+}
+
+impl Phase {
+    const ALL: [Phase; 3] = [Phase::Fetch, Phase::Render, Phase::Write];
+
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Fetch => "version fetches",
+            Phase::Render => "template rendering",
+            Phase::Write => "file writes",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Span {
+    phase: Phase,
+    label: String,
+    elapsed: Duration,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Vec<Span>> = Mutex::new(Vec::new());
+static STARTED: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Slowest fetches and writes listed individually in the report.
+const SLOWEST: usize = 5;
+
+/// Start recording timings for the rest of the run.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+    *STARTED.lock().unwrap() = Some(Instant::now());
+}
+
+/// Run `f`, recording its duration under `phase` when profiling is enabled.
+/// `label` (a URL or path) is only built when it will be recorded.
+pub fn time<T>(phase: Phase, label: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let span = Span {
+        phase,
+        label: label(),
+        elapsed: start.elapsed(),
+    };
+    SPANS.lock().unwrap().push(span);
+    result
+}
+
+/// Per-phase totals: (phase, total time, number of operations).
+fn totals(spans: &[Span]) -> Vec<(Phase, Duration, usize)> {
+    Phase::ALL
+        .iter()
+        .map(|&phase| {
+            let matching = spans.iter().filter(|s| s.phase == phase);
+            let count = matching.clone().count();
+            (phase, matching.map(|s| s.elapsed).sum(), count)
+        })
+        .collect()
+}
+
+/// The `n` slowest spans of a phase, slowest first.
+fn slowest(spans: &[Span], phase: Phase, n: usize) -> Vec<&Span> {
+    let mut matching: Vec<&Span> = spans.iter().filter(|s| s.phase == phase).collect();
+    matching.sort_by_key(|s| std::cmp::Reverse(s.elapsed));
+    matching.truncate(n);
+    matching
+}
+
+fn format_duration(d: Duration) -> String {
+    if d.as_secs_f64() >= 1.0 {
+        format!("{:.2}s", d.as_secs_f64())
+    } else {
+        format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Print the timing summary to stderr. Does nothing unless profiling is enabled.
+pub fn report() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let total = STARTED.lock().unwrap().map(|s| s.elapsed()).unwrap_or_default();
+    let spans = SPANS.lock().unwrap();
+
+    eprintln!();
+    eprintln!("{}", format!("  Profile ({} total)", format_duration(total)).bold());
+    for (phase, elapsed, count) in totals(&spans) {
+        eprintln!(
+            "    {:<20} {:>9}  ({count})",
+            phase.label(),
+            format_duration(elapsed)
+        );
+    }
+    for phase in [Phase::Fetch, Phase::Write] {
+        let slow = slowest(&spans, phase, SLOWEST);
+        if slow.is_empty() {
+            continue;
+        }
+        eprintln!("{}", format!("  Slowest {}:", phase.label()).dimmed());
+        for span in slow {
+            eprintln!("    {:>9}  {}", format_duration(span.elapsed), span.label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(phase: Phase, label: &str, ms: u64) -> Span {
+        Span {
+            phase,
+            label: label.to_string(),
+            elapsed: Duration::from_millis(ms),
+        }
+    }
+
+    #[test]
+    fn test_totals_and_slowest() {
+        let spans = vec![
+            span(Phase::Fetch, "https://meta.fabricmc.net", 300),
+            span(Phase::Fetch, "https://maven.neoforged.net", 900),
+            span(Phase::Write, "mcmod.toml", 2),
+            span(Phase::Render, "", 1),
+        ];
+        let totals = totals(&spans);
+        assert_eq!(totals[0], (Phase::Fetch, Duration::from_millis(1200), 2));
+        assert_eq!(totals[1], (Phase::Render, Duration::from_millis(1), 1));
+        assert_eq!(totals[2], (Phase::Write, Duration::from_millis(2), 1));
+
+        let slow = slowest(&spans, Phase::Fetch, 1);
+        assert_eq!(slow.len(), 1);
+        assert_eq!(slow[0].label, "https://maven.neoforged.net");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50s");
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2.5ms");
+    }
+}
@@ -0,0 +1,143 @@
+//! Machine-readable progress for `--progress json`: one JSON object per line
+//! on stdout, for GUIs and scripts that wrap mcmod.
+//!
+//! Events are the command starting and finishing, each file written, the
+//! versions a project was pinned to, and warnings. Lines that aren't JSON
+//! objects are the usual human output and can be skipped; the "Created ..."
+//! lines are left out in JSON mode since `file_written` covers them.
+
+use crate::config::VersionTarget;
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ProgressFormat {
+    /// Colored text for people
+    #[default]
+    Text,
+    /// Line-delimited JSON events
+    Json,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    StepStarted { step: &'a str },
+    StepCompleted { step: &'a str, elapsed_ms: u128 },
+    StepFailed { step: &'a str, elapsed_ms: u128, error: String },
+    FileWritten { path: String, bytes: usize },
+    VersionsResolved {
+        minecraft: &'a str,
+        max_minecraft: &'a str,
+        #[serde(skip_serializing_if = "str::is_empty")]
+        fabric_loader: &'a str,
+        #[serde(skip_serializing_if = "str::is_empty")]
+        fabric_api: &'a str,
+        #[serde(skip_serializing_if = "str::is_empty")]
+        neoforge: &'a str,
+        #[serde(skip_serializing_if = "str::is_empty")]
+        forge: &'a str,
+        #[serde(skip_serializing_if = "str::is_empty")]
+        quilt_loader: &'a str,
+    },
+    Warning { message: String },
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+static STARTED: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Switch to JSON events for the rest of the run.
+pub fn enable_json() {
+    JSON.store(true, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Print `event` as a JSON line. Does nothing in text mode.
+pub fn emit(event: Event) {
+    if !is_json() {
+        return;
+    }
+    if let Ok(line) = serde_json::to_string(&event) {
+        println!("{line}");
+    }
+}
+
+/// The command (`init`, `dev mods check`, ...) is starting.
+pub fn step_started(step: &str) {
+    *STARTED.lock().unwrap() = Some(Instant::now());
+    emit(Event::StepStarted { step });
+}
+
+/// The command finished, with the error message when it failed.
+pub fn step_finished(step: &str, error: Option<String>) {
+    let elapsed_ms = STARTED.lock().unwrap().map(|s| s.elapsed().as_millis()).unwrap_or_default();
+    match error {
+        None => emit(Event::StepCompleted { step, elapsed_ms }),
+        Some(error) => emit(Event::StepFailed { step, elapsed_ms, error }),
+    }
+}
+
+pub fn file_written(path: &std::path::Path, bytes: usize) {
+    if is_json() {
+        emit(Event::FileWritten { path: path.display().to_string(), bytes });
+    }
+}
+
+/// The versions each target is pinned to, after init or an upgrade.
+pub fn versions_resolved(targets: &[VersionTarget]) {
+    for t in targets {
+        emit(Event::VersionsResolved {
+            minecraft: &t.minecraft,
+            max_minecraft: &t.max_minecraft,
+            fabric_loader: &t.fabric_loader,
+            fabric_api: &t.fabric_api,
+            neoforge: &t.neoforge,
+            forge: &t.forge,
+            quilt_loader: &t.quilt_loader,
+        });
+    }
+}
+
+/// A yellow "Warning: ..." line, or a `warning` event in JSON mode.
+pub fn warning(message: impl Display) {
+    if is_json() {
+        emit(Event::Warning { message: message.to_string() });
+    } else {
+        println!("{}", format!("  Warning: {message}").yellow());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let json = |event: Event| serde_json::to_string(&event).unwrap();
+        assert_eq!(json(Event::StepStarted { step: "init" }), r#"{"event":"step_started","step":"init"}"#);
+        assert_eq!(
+            json(Event::FileWritten { path: "mcmod.toml".to_string(), bytes: 12 }),
+            r#"{"event":"file_written","path":"mcmod.toml","bytes":12}"#
+        );
+        assert_eq!(
+            json(Event::VersionsResolved {
+                minecraft: "1.21.1",
+                max_minecraft: "1.21.1",
+                fabric_loader: "0.16.9",
+                fabric_api: "",
+                neoforge: "21.1.77",
+                forge: "",
+                quilt_loader: "",
+            }),
+            r#"{"event":"versions_resolved","minecraft":"1.21.1","max_minecraft":"1.21.1","fabric_loader":"0.16.9","neoforge":"21.1.77"}"#
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Guards for template-derived output paths.
+//!
+//! Any path that comes from data rather than a literal in the code (template
+//! mappings, values from a hand-edited mcmod.toml) must go through
+//! [`resolve_output`] before it is written, so a bad mapping can't write
+//! outside the project directory.
+
+use crate::error::{McmodError, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Device names Windows refuses as file names, with or without an extension.
+const WINDOWS_RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Characters that are invalid in Windows file names. Rejected everywhere so
+/// generated projects stay portable.
+const WINDOWS_INVALID_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+fn unsafe_path(path: &str, reason: &str) -> McmodError {
+    McmodError::UnsafePath {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Resolve `relative` against the project `root` and verify the result stays
+/// inside it. Rejects absolute paths, `..` segments, names that are invalid on
+/// Windows, and symlinks (anywhere along the path) that point outside the root.
+pub fn resolve_output(root: &Path, relative: impl AsRef<Path>) -> Result<PathBuf> {
+    let relative = relative.as_ref();
+    let display = relative.to_string_lossy();
+    check_relative(relative, &display)?;
+
+    let target = root.join(relative);
+    let canonical_root = canonicalize_existing(root, &display)?;
+    let canonical_target = canonicalize_existing(&target, &display)?;
+    if !canonical_target.starts_with(&canonical_root) {
+        return Err(unsafe_path(
+            &display,
+            "resolves outside the project directory (symlink escape)",
+        ));
+    }
+    Ok(target)
+}
+
+/// Lexical checks that don't touch the filesystem.
+fn check_relative(relative: &Path, display: &str) -> Result<()> {
+    if relative.as_os_str().is_empty() {
+        return Err(unsafe_path(display, "path is empty"));
+    }
+    if display.contains('\0') {
+        return Err(unsafe_path(display, "path contains a NUL byte"));
+    }
+    for component in relative.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => {
+                return Err(unsafe_path(display, "absolute paths are not allowed"));
+            }
+            Component::ParentDir => {
+                return Err(unsafe_path(display, "'..' segments are not allowed"));
+            }
+            Component::CurDir | Component::Normal(_) => {}
+        }
+    }
+
+    // Backslashes are separators on Windows, so check segments split on both
+    for segment in display.split(['/', '\\']).filter(|s| !s.is_empty()) {
+        if segment == ".." {
+            return Err(unsafe_path(display, "'..' segments are not allowed"));
+        }
+        if segment.contains(WINDOWS_INVALID_CHARS) || segment.chars().any(|c| c.is_control()) {
+            return Err(unsafe_path(
+                display,
+                &format!("'{segment}' contains characters that are invalid on Windows"),
+            ));
+        }
+        if segment != "." && (segment.ends_with('.') || segment.ends_with(' ')) {
+            return Err(unsafe_path(
+                display,
+                &format!("'{segment}' ends with a dot or space"),
+            ));
+        }
+        let stem = segment.split('.').next().unwrap_or("").to_lowercase();
+        if WINDOWS_RESERVED.contains(&stem.as_str()) {
+            return Err(unsafe_path(
+                display,
+                &format!("'{segment}' is a reserved device name on Windows"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Canonicalize the longest existing prefix of `path` (following symlinks) and
+/// append the not-yet-created remainder.
+fn canonicalize_existing(path: &Path, display: &str) -> Result<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    // symlink_metadata so a dangling symlink counts as existing and fails below
+    while std::fs::symlink_metadata(existing).is_err() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+    let base = if existing.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        existing
+    };
+
+    let mut resolved = base
+        .canonicalize()
+        .map_err(|_| unsafe_path(display, "path goes through a dangling symlink"))?;
+    resolved.extend(missing.iter().rev());
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mcmod_sandbox_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_output_accepts_nested_paths() {
+        let root = temp_root("ok");
+        let path = resolve_output(&root, "src/main/java/com/example/Mod.java").unwrap();
+        assert_eq!(path, root.join("src/main/java/com/example/Mod.java"));
+        assert!(resolve_output(&root, "./README.md").is_ok());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_resolve_output_rejects_traversal() {
+        let root = temp_root("traversal");
+        assert!(resolve_output(&root, "../outside.txt").is_err());
+        assert!(resolve_output(&root, "src/../../outside.txt").is_err());
+        assert!(resolve_output(&root, "src\\..\\..\\outside.txt").is_err());
+        assert!(resolve_output(&root, "/etc/passwd").is_err());
+        assert!(resolve_output(&root, "").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_resolve_output_rejects_invalid_names() {
+        let root = temp_root("names");
+        assert!(resolve_output(&root, "assets/con.json").is_err());
+        assert!(resolve_output(&root, "assets/what?.json").is_err());
+        assert!(resolve_output(&root, "assets/trailing./a.json").is_err());
+        assert!(resolve_output(&root, "assets/console.json").is_ok());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_output_rejects_symlink_escape() {
+        let root = temp_root("symlink");
+        let outside = temp_root("symlink_target");
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("missing"), root.join("dangling")).unwrap();
+
+        assert!(resolve_output(&root, "link/file.txt").is_err());
+        assert!(resolve_output(&root, "dangling").is_err());
+
+        // A symlink that stays inside the project is fine
+        std::fs::create_dir_all(root.join("real")).unwrap();
+        std::os::unix::fs::symlink(root.join("real"), root.join("alias")).unwrap();
+        assert!(resolve_output(&root, "alias/file.txt").is_ok());
+
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+}
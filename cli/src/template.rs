@@ -1,10 +1,13 @@
-use crate::config::{McmodConfig, VersionTarget};
+use crate::config::{Environment, FabricEntrypoint, McmodConfig, RunDirs, VersionTarget};
 use crate::error::{McmodError, Result};
+use crate::profile::{self, Phase};
 use std::collections::HashMap;
 
 // --- Shared templates ---
 pub const TMPL_GITIGNORE: &str = include_str!("../templates/gitignore");
 pub const TMPL_LICENSE: &str = include_str!("../templates/LICENSE");
+pub const TMPL_README: &str = include_str!("../templates/README.md");
+pub const TMPL_DEVELOPMENT: &str = include_str!("../templates/DEVELOPMENT.md");
 
 pub const TMPL_FABRIC_MIXINS_JSON: &str = include_str!("../templates/fabric/mixins.json");
 pub const TMPL_FABRIC_MIXIN_PACKAGE_INFO: &str =
@@ -12,9 +15,12 @@ pub const TMPL_FABRIC_MIXIN_PACKAGE_INFO: &str =
 
 pub const TMPL_COMMON_TEST_JAVA: &str = include_str!("../templates/common/ExampleModTest.java");
 pub const TMPL_COMMON_TEST_KT: &str = include_str!("../templates/common/ExampleModTest.kt");
+pub const TMPL_COMMON_UNIT_TEST_JAVA: &str = include_str!("../templates/common/UnitTest.java");
+pub const TMPL_COMMON_UNIT_TEST_KT: &str = include_str!("../templates/common/UnitTest.kt");
 
 pub const TMPL_CI_BUILD_YML: &str = include_str!("../templates/ci/build.yml");
 pub const TMPL_CI_RELEASE_YML: &str = include_str!("../templates/ci/release.yml");
+pub const TMPL_CI_STANDALONE_YML: &str = include_str!("../templates/ci/standalone.yml");
 
 // --- Stonecutter templates ---
 pub const SC_SETTINGS_GRADLE: &str =
@@ -31,51 +37,291 @@ pub const SC_FABRIC_MOD_JSON: &str =
     include_str!("../templates/stonecutter/fabric/fabric.mod.json");
 pub const SC_NEOFORGE_MODS_TOML: &str =
     include_str!("../templates/stonecutter/neoforge/neoforge.mods.toml");
+pub const SC_FORGE_MODS_TOML: &str =
+    include_str!("../templates/stonecutter/forge/mods.toml");
+pub const SC_QUILT_MOD_JSON: &str =
+    include_str!("../templates/stonecutter/quilt/quilt.mod.json");
 pub const SC_UNIFIED_MOD_JAVA: &str =
     include_str!("../templates/stonecutter/UnifiedMod.java");
 pub const SC_UNIFIED_MOD_KT: &str =
     include_str!("../templates/stonecutter/UnifiedMod.kt");
+pub const SC_LIBRARY_API_JAVA: &str =
+    include_str!("../templates/stonecutter/LibraryApi.java");
+pub const SC_LIBRARY_API_KT: &str =
+    include_str!("../templates/stonecutter/LibraryApi.kt");
+pub const SC_BUILD_INFO_GRADLE: &str =
+    include_str!("../templates/stonecutter/build_info.gradle.kts");
+pub const SC_ARCHITECTURY_GRADLE: &str =
+    include_str!("../templates/stonecutter/architectury.gradle.kts");
+pub const SC_UNIT_TESTS_GRADLE: &str =
+    include_str!("../templates/stonecutter/unit_tests.gradle.kts");
+pub const SC_RUN_DIRS_GRADLE: &str =
+    include_str!("../templates/stonecutter/run_dirs.gradle.kts");
+pub const GEN_INIT_CLASS_JAVA: &str = include_str!("../templates/gen/InitClass.java");
+pub const GEN_INIT_CLASS_KT: &str = include_str!("../templates/gen/InitClass.kt");
+pub const GEN_MOD_ITEMS_JAVA: &str = include_str!("../templates/gen/ModItems.java");
+pub const GEN_MOD_ITEMS_KT: &str = include_str!("../templates/gen/ModItems.kt");
+pub const GEN_MOD_BLOCKS_JAVA: &str = include_str!("../templates/gen/ModBlocks.java");
+pub const GEN_MOD_BLOCKS_KT: &str = include_str!("../templates/gen/ModBlocks.kt");
+pub const GEN_BLOCK_JAVA: &str = include_str!("../templates/gen/Block.java");
+pub const GEN_BLOCK_KT: &str = include_str!("../templates/gen/Block.kt");
+pub const GEN_ENTITY_BLOCK_JAVA: &str = include_str!("../templates/gen/EntityBlock.java");
+pub const GEN_ENTITY_BLOCK_KT: &str = include_str!("../templates/gen/EntityBlock.kt");
+pub const GEN_BLOCK_ENTITY_JAVA: &str = include_str!("../templates/gen/BlockEntity.java");
+pub const GEN_BLOCK_ENTITY_KT: &str = include_str!("../templates/gen/BlockEntity.kt");
+pub const GEN_MOD_BLOCK_ENTITIES_JAVA: &str = include_str!("../templates/gen/ModBlockEntities.java");
+pub const GEN_MOD_BLOCK_ENTITIES_KT: &str = include_str!("../templates/gen/ModBlockEntities.kt");
+pub const GEN_BLOCK_ENTITY_RENDERER_JAVA: &str = include_str!("../templates/gen/BlockEntityRenderer.java");
+pub const GEN_BLOCK_ENTITY_RENDERER_KT: &str = include_str!("../templates/gen/BlockEntityRenderer.kt");
+pub const GEN_MOD_BLOCK_ENTITY_RENDERERS_JAVA: &str = include_str!("../templates/gen/ModBlockEntityRenderers.java");
+pub const GEN_MOD_BLOCK_ENTITY_RENDERERS_KT: &str = include_str!("../templates/gen/ModBlockEntityRenderers.kt");
+pub const SC_PLATFORM_HELPER_JAVA: &str =
+    include_str!("../templates/stonecutter/PlatformHelper.java");
+pub const SC_CLIENT_MOD_JAVA: &str =
+    include_str!("../templates/stonecutter/ClientMod.java");
+pub const SC_CLIENT_MOD_KT: &str =
+    include_str!("../templates/stonecutter/ClientMod.kt");
+pub const SC_DATA_GENERATOR_JAVA: &str =
+    include_str!("../templates/stonecutter/DataGenerator.java");
+pub const SC_DATA_GENERATOR_KT: &str =
+    include_str!("../templates/stonecutter/DataGenerator.kt");
+pub const SC_PRE_LAUNCH_JAVA: &str =
+    include_str!("../templates/stonecutter/PreLaunch.java");
+pub const SC_PRE_LAUNCH_KT: &str =
+    include_str!("../templates/stonecutter/PreLaunch.kt");
+
+// --- Pack project templates (data packs and resource packs) ---
+pub const PACK_BUILD_GRADLE: &str = include_str!("../templates/pack/build.gradle.kts");
+pub const PACK_SETTINGS_GRADLE: &str = include_str!("../templates/pack/settings.gradle.kts");
+pub const PACK_GRADLE_PROPERTIES: &str = include_str!("../templates/pack/gradle.properties");
+pub const DP_README: &str = include_str!("../templates/pack/datapack/README.md");
+pub const DP_LOAD_TAG: &str = include_str!("../templates/pack/datapack/load.json");
+pub const DP_LOAD_FUNCTION: &str = include_str!("../templates/pack/datapack/load.mcfunction");
+pub const RP_README: &str = include_str!("../templates/pack/resourcepack/README.md");
+pub const RP_TEXTURES_NOTE: &str = include_str!("../templates/pack/resourcepack/textures.md");
+pub const RP_OPTIFINE_NOTE: &str = include_str!("../templates/pack/resourcepack/optifine.md");
+
+// --- Paper plugin project templates ---
+pub const PLUGIN_BUILD_GRADLE: &str = include_str!("../templates/plugin/build.gradle.kts");
+pub const PLUGIN_GRADLE_PROPERTIES: &str = include_str!("../templates/plugin/gradle.properties");
+pub const PLUGIN_YML: &str = include_str!("../templates/plugin/paper-plugin.yml");
+pub const PLUGIN_MAIN_JAVA: &str = include_str!("../templates/plugin/Plugin.java");
+pub const PLUGIN_MAIN_KT: &str = include_str!("../templates/plugin/Plugin.kt");
+pub const PLUGIN_README: &str = include_str!("../templates/plugin/README.md");
+pub const PAPER_MODULE_BUILD_GRADLE: &str =
+    include_str!("../templates/plugin/paper-module.gradle.kts");
+pub const PAPER_MODULE_PROPERTIES: &str =
+    include_str!("../templates/plugin/paper-module.properties");
+
+// --- Sponge plugin module templates ---
+pub const SPONGE_MODULE_BUILD_GRADLE: &str =
+    include_str!("../templates/plugin/sponge-module.gradle.kts");
+pub const SPONGE_MODULE_PROPERTIES: &str =
+    include_str!("../templates/plugin/sponge-module.properties");
+pub const SPONGE_PLUGINS_JSON: &str = include_str!("../templates/plugin/sponge_plugins.json");
+pub const SPONGE_MAIN_JAVA: &str = include_str!("../templates/plugin/SpongePlugin.java");
+pub const SPONGE_MAIN_KT: &str = include_str!("../templates/plugin/SpongePlugin.kt");
+
+// --- Spigot plugin module templates ---
+pub const SPIGOT_MODULE_BUILD_GRADLE: &str =
+    include_str!("../templates/plugin/spigot-module.gradle.kts");
+pub const SPIGOT_MODULE_PROPERTIES: &str =
+    include_str!("../templates/plugin/spigot-module.properties");
+pub const SPIGOT_PLUGIN_YML: &str = include_str!("../templates/plugin/spigot-plugin.yml");
+
+// --- Velocity plugin module templates ---
+pub const VELOCITY_MODULE_BUILD_GRADLE: &str =
+    include_str!("../templates/plugin/velocity-module.gradle.kts");
+pub const VELOCITY_MODULE_PROPERTIES: &str =
+    include_str!("../templates/plugin/velocity-module.properties");
+pub const VELOCITY_PLUGIN_JSON: &str = include_str!("../templates/plugin/velocity-plugin.json");
+pub const VELOCITY_MAIN_JAVA: &str = include_str!("../templates/plugin/VelocityPlugin.java");
+pub const VELOCITY_MAIN_KT: &str = include_str!("../templates/plugin/VelocityPlugin.kt");
+
+// --- Shell completion scripts ---
+pub const COMPLETION_BASH: &str = include_str!("../templates/completions/mcmod.bash");
+pub const COMPLETION_ZSH: &str = include_str!("../templates/completions/mcmod.zsh");
+pub const COMPLETION_FISH: &str = include_str!("../templates/completions/mcmod.fish");
+pub const COMPLETION_POWERSHELL: &str = include_str!("../templates/completions/mcmod.ps1");
 
 // --- Binary templates (include_bytes!) ---
 pub const GRADLE_WRAPPER_JAR: &[u8] =
     include_bytes!("../templates/gradle-wrapper/gradle-wrapper.jar");
 pub const GRADLE_WRAPPER_PROPS: &str =
     include_str!("../templates/gradle-wrapper/gradle-wrapper.properties");
-pub const GRADLEW: &[u8] = include_bytes!("../templates/gradle-wrapper/gradlew");
-pub const GRADLEW_BAT: &[u8] = include_bytes!("../templates/gradle-wrapper/gradlew.bat");
+pub const GRADLEW: &str = include_str!("../templates/gradle-wrapper/gradlew");
+pub const GRADLEW_BAT: &str = include_str!("../templates/gradle-wrapper/gradlew.bat");
+
+/// Project templates by their path under templates/, the layout template packs
+/// use to override them (see `template_pack`). Shell completions and the Gradle
+/// wrapper aren't overridable.
+pub const PROJECT_TEMPLATES: &[(&str, &str)] = &[
+    ("gitignore", TMPL_GITIGNORE),
+    ("LICENSE", TMPL_LICENSE),
+    ("README.md", TMPL_README),
+    ("DEVELOPMENT.md", TMPL_DEVELOPMENT),
+    ("fabric/mixins.json", TMPL_FABRIC_MIXINS_JSON),
+    ("fabric/mixin_package_info.java", TMPL_FABRIC_MIXIN_PACKAGE_INFO),
+    ("common/ExampleModTest.java", TMPL_COMMON_TEST_JAVA),
+    ("common/ExampleModTest.kt", TMPL_COMMON_TEST_KT),
+    ("common/UnitTest.java", TMPL_COMMON_UNIT_TEST_JAVA),
+    ("common/UnitTest.kt", TMPL_COMMON_UNIT_TEST_KT),
+    ("ci/build.yml", TMPL_CI_BUILD_YML),
+    ("ci/release.yml", TMPL_CI_RELEASE_YML),
+    ("ci/standalone.yml", TMPL_CI_STANDALONE_YML),
+    ("stonecutter/settings.gradle.kts", SC_SETTINGS_GRADLE),
+    ("stonecutter/stonecutter.gradle.kts", SC_STONECUTTER_GRADLE),
+    ("stonecutter/build.gradle.kts", SC_BUILD_GRADLE),
+    ("stonecutter/gradle.properties", SC_GRADLE_PROPERTIES),
+    ("stonecutter/version.gradle.properties", SC_VERSION_GRADLE_PROPERTIES),
+    ("stonecutter/fabric/fabric.mod.json", SC_FABRIC_MOD_JSON),
+    ("stonecutter/neoforge/neoforge.mods.toml", SC_NEOFORGE_MODS_TOML),
+    ("stonecutter/forge/mods.toml", SC_FORGE_MODS_TOML),
+    ("stonecutter/quilt/quilt.mod.json", SC_QUILT_MOD_JSON),
+    ("stonecutter/UnifiedMod.java", SC_UNIFIED_MOD_JAVA),
+    ("stonecutter/UnifiedMod.kt", SC_UNIFIED_MOD_KT),
+    ("stonecutter/LibraryApi.java", SC_LIBRARY_API_JAVA),
+    ("stonecutter/LibraryApi.kt", SC_LIBRARY_API_KT),
+    ("stonecutter/build_info.gradle.kts", SC_BUILD_INFO_GRADLE),
+    ("stonecutter/architectury.gradle.kts", SC_ARCHITECTURY_GRADLE),
+    ("stonecutter/unit_tests.gradle.kts", SC_UNIT_TESTS_GRADLE),
+    ("stonecutter/run_dirs.gradle.kts", SC_RUN_DIRS_GRADLE),
+    ("gen/InitClass.java", GEN_INIT_CLASS_JAVA),
+    ("gen/InitClass.kt", GEN_INIT_CLASS_KT),
+    ("gen/ModItems.java", GEN_MOD_ITEMS_JAVA),
+    ("gen/ModItems.kt", GEN_MOD_ITEMS_KT),
+    ("gen/ModBlocks.java", GEN_MOD_BLOCKS_JAVA),
+    ("gen/ModBlocks.kt", GEN_MOD_BLOCKS_KT),
+    ("gen/Block.java", GEN_BLOCK_JAVA),
+    ("gen/Block.kt", GEN_BLOCK_KT),
+    ("gen/EntityBlock.java", GEN_ENTITY_BLOCK_JAVA),
+    ("gen/EntityBlock.kt", GEN_ENTITY_BLOCK_KT),
+    ("gen/BlockEntity.java", GEN_BLOCK_ENTITY_JAVA),
+    ("gen/BlockEntity.kt", GEN_BLOCK_ENTITY_KT),
+    ("gen/ModBlockEntities.java", GEN_MOD_BLOCK_ENTITIES_JAVA),
+    ("gen/ModBlockEntities.kt", GEN_MOD_BLOCK_ENTITIES_KT),
+    ("gen/BlockEntityRenderer.java", GEN_BLOCK_ENTITY_RENDERER_JAVA),
+    ("gen/BlockEntityRenderer.kt", GEN_BLOCK_ENTITY_RENDERER_KT),
+    ("gen/ModBlockEntityRenderers.java", GEN_MOD_BLOCK_ENTITY_RENDERERS_JAVA),
+    ("gen/ModBlockEntityRenderers.kt", GEN_MOD_BLOCK_ENTITY_RENDERERS_KT),
+    ("stonecutter/PlatformHelper.java", SC_PLATFORM_HELPER_JAVA),
+    ("stonecutter/ClientMod.java", SC_CLIENT_MOD_JAVA),
+    ("stonecutter/ClientMod.kt", SC_CLIENT_MOD_KT),
+    ("stonecutter/DataGenerator.java", SC_DATA_GENERATOR_JAVA),
+    ("stonecutter/DataGenerator.kt", SC_DATA_GENERATOR_KT),
+    ("stonecutter/PreLaunch.java", SC_PRE_LAUNCH_JAVA),
+    ("stonecutter/PreLaunch.kt", SC_PRE_LAUNCH_KT),
+    ("pack/build.gradle.kts", PACK_BUILD_GRADLE),
+    ("pack/settings.gradle.kts", PACK_SETTINGS_GRADLE),
+    ("pack/gradle.properties", PACK_GRADLE_PROPERTIES),
+    ("pack/datapack/README.md", DP_README),
+    ("pack/datapack/load.json", DP_LOAD_TAG),
+    ("pack/datapack/load.mcfunction", DP_LOAD_FUNCTION),
+    ("pack/resourcepack/README.md", RP_README),
+    ("pack/resourcepack/textures.md", RP_TEXTURES_NOTE),
+    ("pack/resourcepack/optifine.md", RP_OPTIFINE_NOTE),
+    ("plugin/build.gradle.kts", PLUGIN_BUILD_GRADLE),
+    ("plugin/gradle.properties", PLUGIN_GRADLE_PROPERTIES),
+    ("plugin/paper-plugin.yml", PLUGIN_YML),
+    ("plugin/Plugin.java", PLUGIN_MAIN_JAVA),
+    ("plugin/Plugin.kt", PLUGIN_MAIN_KT),
+    ("plugin/README.md", PLUGIN_README),
+    ("plugin/paper-module.gradle.kts", PAPER_MODULE_BUILD_GRADLE),
+    ("plugin/paper-module.properties", PAPER_MODULE_PROPERTIES),
+    ("plugin/sponge-module.gradle.kts", SPONGE_MODULE_BUILD_GRADLE),
+    ("plugin/sponge-module.properties", SPONGE_MODULE_PROPERTIES),
+    ("plugin/sponge_plugins.json", SPONGE_PLUGINS_JSON),
+    ("plugin/SpongePlugin.java", SPONGE_MAIN_JAVA),
+    ("plugin/SpongePlugin.kt", SPONGE_MAIN_KT),
+    ("plugin/spigot-module.gradle.kts", SPIGOT_MODULE_BUILD_GRADLE),
+    ("plugin/spigot-module.properties", SPIGOT_MODULE_PROPERTIES),
+    ("plugin/spigot-plugin.yml", SPIGOT_PLUGIN_YML),
+    ("plugin/velocity-module.gradle.kts", VELOCITY_MODULE_BUILD_GRADLE),
+    ("plugin/velocity-module.properties", VELOCITY_MODULE_PROPERTIES),
+    ("plugin/velocity-plugin.json", VELOCITY_PLUGIN_JSON),
+    ("plugin/VelocityPlugin.java", VELOCITY_MAIN_JAVA),
+    ("plugin/VelocityPlugin.kt", VELOCITY_MAIN_KT),
+];
 
 /// Render a template by replacing all `{{placeholder}}` occurrences with values from the map.
 /// Returns an error if any `{{placeholder}}` patterns remain after substitution.
+/// A built-in template is swapped for the active template pack's version first.
 pub fn render(template: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let template = crate::template_pack::resolve(template);
+    profile::time(Phase::Render, String::new, || substitute(template, vars))
+}
+
+fn substitute(template: &str, vars: &HashMap<String, String>) -> Result<String> {
     let mut result = template.to_string();
     for (key, value) in vars {
         let placeholder = format!("{{{{{}}}}}", key);
         result = result.replace(&placeholder, value);
     }
 
-    // Check for unreplaced placeholders (but not conditional block markers like {{#name}}/{{/name}}
-    // and not GitHub Actions expressions like ${{ ... }})
+    if let Some(name) = unresolved_placeholders(&result).first() {
+        return Err(McmodError::Other(format!(
+            "Unreplaced template placeholder: {{{{{name}}}}}"
+        )));
+    }
+
+    Ok(result)
+}
+
+/// Names of `{{placeholder}}`s left in `text`, in order. Conditional block
+/// markers (`{{#name}}`/`{{/name}}`) and GitHub Actions expressions
+/// (`${{ ... }}`) are not placeholders.
+pub fn unresolved_placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
     let mut pos = 0;
-    while let Some(start) = result[pos..].find("{{") {
+    while let Some(start) = text[pos..].find("{{") {
         let abs_start = pos + start;
-        if let Some(end) = result[abs_start..].find("}}") {
-            let inner = &result[abs_start + 2..abs_start + end];
-            // Skip conditional block markers ({{#...}} and {{/...}})
-            // Skip GitHub Actions expressions (${{...}})
-            let is_gha = abs_start > 0 && result.as_bytes()[abs_start - 1] == b'$';
-            if !inner.starts_with('#') && !inner.starts_with('/') && !is_gha {
-                return Err(McmodError::Other(format!(
-                    "Unreplaced template placeholder: {{{{{}}}}}",
-                    inner
-                )));
-            }
-            pos = abs_start + end + 2;
-        } else {
+        let Some(end) = text[abs_start..].find("}}") else {
             break;
+        };
+        let inner = &text[abs_start + 2..abs_start + end];
+        let is_gha = abs_start > 0 && text.as_bytes()[abs_start - 1] == b'$';
+        if !inner.starts_with('#') && !inner.starts_with('/') && !is_gha {
+            names.push(inner.to_string());
         }
+        pos = abs_start + end + 2;
     }
+    names
+}
 
-    Ok(result)
+/// Variables that may be absent from the map. Templates wrap their usages in
+/// `{{#name}}...{{/name}}` blocks, which `render_optional` strips when unset.
+/// `entrypoint` is set for regular mods and `library` for library projects;
+/// `architectury` when the project depends on Architectury API, and
+/// `client_entrypoint`, `datagen_entrypoint` and `prelaunch_entrypoint` for
+/// each extra Fabric entrypoint the mod has.
+const OPTIONAL_VARS: &[&str] = &[
+    "email",
+    "github",
+    "entrypoint",
+    "library",
+    "architectury",
+    "client_entrypoint",
+    "datagen_entrypoint",
+    "prelaunch_entrypoint",
+];
+
+/// Render a template that references optional variables (see `OPTIONAL_VARS`).
+/// Blocks for optional variables present in the map are kept; others are removed.
+pub fn render_optional(template: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let conditions: Vec<(&str, bool)> = OPTIONAL_VARS
+        .iter()
+        .map(|&name| (name, vars.contains_key(name)))
+        .collect();
+    render(&strip_conditional_blocks(template, &conditions), vars)
+}
+
+/// Render the CI workflow for projects built by one plain Gradle project (packs
+/// and plugins): build, upload the zip or jar, and release it on `v*` tags.
+pub fn render_standalone_ci(config: &McmodConfig, vars: &HashMap<String, String>) -> Result<String> {
+    let mut vars = vars.clone();
+    let artifact = if config.is_pack() { "zip" } else { "jar" };
+    vars.insert("artifact".to_string(), artifact.to_string());
+    render(TMPL_CI_STANDALONE_YML, &vars)
 }
 
 /// Build the common template variables from an McmodConfig.
@@ -89,11 +335,59 @@ pub fn build_common_vars(config: &McmodConfig) -> HashMap<String, String> {
         "package_path".to_string(),
         crate::util::package_to_path(&config.mod_info.package),
     );
+    let class_name = if config.is_plugin() {
+        let base = crate::util::to_pascal_case(&config.mod_info.mod_id);
+        if base.ends_with("Plugin") {
+            base
+        } else {
+            format!("{base}Plugin")
+        }
+    } else {
+        crate::util::derive_class_name(&config.mod_info.mod_id)
+    };
+    vars.insert("class_name".to_string(), class_name.clone());
+    vars.insert("author".to_string(), config.mod_info.author.clone());
+    if config.is_library() {
+        vars.insert("library".to_string(), "true".to_string());
+    } else {
+        vars.insert("entrypoint".to_string(), class_name);
+    }
+    if config.features.architectury {
+        vars.insert("architectury".to_string(), "true".to_string());
+    }
+    // Quilt runs the Fabric entrypoints, so it shares the Fabric branch
+    let fabric_condition = if config.loaders.quilt { "fabric || quilt" } else { "fabric" };
+    vars.insert("fabric_condition".to_string(), fabric_condition.to_string());
+    if !config.is_library() {
+        for entrypoint in FabricEntrypoint::ALL {
+            if config.features.has_entrypoint(entrypoint) {
+                let class = format!("{}{}", vars["class_name"], entrypoint.class_suffix());
+                vars.insert(entrypoint.var().to_string(), class);
+            }
+        }
+    }
+    let client_source_set = if config.features.split_environment { "client" } else { "main" };
+    vars.insert("client_source_set".to_string(), client_source_set.to_string());
+    let (entrypoint, initializer, init_method) = config.mod_info.environment.fabric_entrypoint();
     vars.insert(
-        "class_name".to_string(),
-        crate::util::derive_class_name(&config.mod_info.mod_id),
+        "fabric_environment".to_string(),
+        config.mod_info.environment.fabric_value().to_string(),
     );
-    vars.insert("author".to_string(), config.mod_info.author.clone());
+    vars.insert("fabric_entrypoint".to_string(), entrypoint.to_string());
+    vars.insert("fabric_initializer".to_string(), initializer.to_string());
+    vars.insert("fabric_init_method".to_string(), init_method.to_string());
+    vars.insert("homepage".to_string(), config.homepage());
+    let copyright_holder = match config.mod_info.email {
+        Some(ref email) => format!("{} <{email}>", config.mod_info.author),
+        None => config.mod_info.author.clone(),
+    };
+    vars.insert("copyright_holder".to_string(), copyright_holder);
+    if let Some(ref email) = config.mod_info.email {
+        vars.insert("email".to_string(), email.clone());
+    }
+    if let Some(ref github) = config.mod_info.github {
+        vars.insert("github".to_string(), github.clone());
+    }
     vars.insert(
         "description".to_string(),
         config.mod_info.description.clone(),
@@ -103,7 +397,12 @@ pub fn build_common_vars(config: &McmodConfig) -> HashMap<String, String> {
 
     // Kotlin version (used inside {{#kotlin}} blocks)
     if config.mod_info.language == "kotlin" {
-        vars.insert("kotlin_version".to_string(), "2.1.0".to_string());
+        vars.insert("kotlin_version".to_string(), crate::version_meta::KOTLIN.to_string());
+    }
+
+    // JUnit BOM version (used by the unit-tests Gradle block)
+    if config.features.unit_tests {
+        vars.insert("junit_version".to_string(), "5.11.4".to_string());
     }
 
     // Stonecutter-specific
@@ -125,6 +424,156 @@ pub fn build_common_vars(config: &McmodConfig) -> HashMap<String, String> {
     vars
 }
 
+/// Render the unified entrypoint class for the project's language, or the
+/// public API class for library projects.
+/// Returns the rendered source and its path relative to the project root.
+pub fn render_unified_mod(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<(String, String)> {
+    let package_path = crate::util::package_to_path(&config.mod_info.package);
+    let class_name = crate::util::derive_class_name(&config.mod_info.mod_id);
+
+    let kotlin = config.mod_info.language == "kotlin";
+    let (tmpl, ext, source_dir) = match (kotlin, config.is_library()) {
+        (true, false) => (SC_UNIFIED_MOD_KT, "kt", "kotlin"),
+        (true, true) => (SC_LIBRARY_API_KT, "kt", "kotlin"),
+        (false, false) => (SC_UNIFIED_MOD_JAVA, "java", "java"),
+        (false, true) => (SC_LIBRARY_API_JAVA, "java", "java"),
+    };
+
+    let stripped = strip_conditional_blocks(
+        tmpl,
+        &[
+            ("build_info", config.features.build_info),
+            ("forge", config.loaders.forge),
+        ],
+    );
+    // Quilt runs the Fabric entrypoint, so it shares the Fabric branch
+    let mut vars = vars.clone();
+    let fabric_condition = if config.loaders.quilt { "fabric || quilt" } else { "fabric" };
+    vars.insert("fabric_condition".to_string(), fabric_condition.to_string());
+    let path = format!("src/main/{source_dir}/{package_path}/{class_name}.{ext}");
+    Ok((render(&stripped, &vars)?, path))
+}
+
+/// Render the Architectury `PlatformHelper` class. Stonecutter picks the
+/// loader-specific bodies, standing in for `@ExpectPlatform`, which needs a
+/// separate module per loader. Returns the source and its path.
+pub fn render_platform_helper(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<(String, String)> {
+    let package_path = crate::util::package_to_path(&config.mod_info.package);
+    let stripped = strip_conditional_blocks(SC_PLATFORM_HELPER_JAVA, &[("quilt", config.loaders.quilt)]);
+    let path = format!("src/main/java/{package_path}/platform/PlatformHelper.java");
+    Ok((render(&stripped, vars)?, path))
+}
+
+/// Render docs/DEVELOPMENT.md for a Stonecutter project: where loader-specific
+/// code goes and how to run each loader, with sections for the project's
+/// loaders and features.
+pub fn render_development_doc(config: &McmodConfig, vars: &HashMap<String, String>) -> Result<String> {
+    let per_loader = config.features.run_dirs == crate::config::RunDirs::PerLoader;
+    let stripped = strip_conditional_blocks(
+        TMPL_DEVELOPMENT,
+        &[
+            ("fabric", config.loaders.fabric),
+            ("quilt", config.loaders.quilt),
+            ("neoforge", config.loaders.neoforge),
+            ("forge", config.loaders.forge),
+            ("architectury", config.features.architectury),
+            ("entrypoint_hooks", !config.features.architectury && !config.is_library()),
+            ("library_hooks", !config.features.architectury && config.is_library()),
+            ("split_environment", config.features.split_environment),
+            ("unit_tests", config.features.unit_tests),
+            ("run_per_loader", per_loader),
+            ("shared_run", !per_loader),
+        ],
+    );
+    render(&stripped, vars)
+}
+
+/// Render the classes for the extra Fabric entrypoints present in `vars`, as
+/// (source, path) pairs. The client class goes under `src/<client_source_set>`
+/// so a split client source set gets it; the others go under `src/main`.
+pub fn render_fabric_entrypoints(vars: &HashMap<String, String>) -> Result<Vec<(String, String)>> {
+    let package_path = vars.get("package_path").map(String::as_str).unwrap_or_default();
+    let kotlin = vars.get("language").is_some_and(|l| l == "kotlin");
+    let (ext, source_dir) = if kotlin { ("kt", "kotlin") } else { ("java", "java") };
+    let mut files = Vec::new();
+    for entrypoint in FabricEntrypoint::ALL {
+        let Some(class) = vars.get(entrypoint.var()) else {
+            continue;
+        };
+        let (java, kt) = match entrypoint {
+            FabricEntrypoint::Client => (SC_CLIENT_MOD_JAVA, SC_CLIENT_MOD_KT),
+            FabricEntrypoint::Datagen => (SC_DATA_GENERATOR_JAVA, SC_DATA_GENERATOR_KT),
+            FabricEntrypoint::PreLaunch => (SC_PRE_LAUNCH_JAVA, SC_PRE_LAUNCH_KT),
+        };
+        let source_set = match entrypoint {
+            FabricEntrypoint::Client => vars.get("client_source_set").map(String::as_str).unwrap_or("main"),
+            _ => "main",
+        };
+        let path = format!("src/{source_set}/{source_dir}/{package_path}/{class}.{ext}");
+        files.push((render(if kotlin { kt } else { java }, vars)?, path));
+    }
+    Ok(files)
+}
+
+/// Loader/language/project-type conditions used by the root Gradle build
+/// templates and `mcmod gen` templates.
+pub fn build_conditions(config: &McmodConfig) -> [(&'static str, bool); 13] {
+    [
+        ("fabric", config.loaders.fabric),
+        ("neoforge", config.loaders.neoforge),
+        ("forge", config.loaders.forge),
+        ("quilt", config.loaders.quilt),
+        ("kotlin", config.mod_info.language == "kotlin"),
+        ("library", config.is_library()),
+        ("paper", config.features.paper),
+        ("sponge", config.loaders.sponge),
+        ("velocity", config.features.velocity),
+        ("spigot", config.features.spigot),
+        ("client_only", config.mod_info.environment == Environment::Client),
+        ("split_environment", config.features.split_environment),
+        ("datagen", config.features.has_entrypoint(FabricEntrypoint::Datagen)),
+    ]
+}
+
+/// Render settings.gradle.kts for the project's loaders.
+pub fn render_settings_gradle(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<String> {
+    let settings = strip_conditional_blocks(SC_SETTINGS_GRADLE, &build_conditions(config));
+    render(&settings, vars)
+}
+
+/// Render build.gradle.kts, including the build-info task, Architectury API
+/// dependency, and JUnit setup when enabled.
+pub fn render_build_gradle(
+    config: &McmodConfig,
+    vars: &HashMap<String, String>,
+) -> Result<String> {
+    // Strip conditionals first (removes {{kotlin_version}} if not kotlin), then render
+    let build = strip_conditional_blocks(SC_BUILD_GRADLE, &build_conditions(config));
+    let mut build = render(&build, vars)?;
+    if config.features.build_info {
+        build.push_str(&render(SC_BUILD_INFO_GRADLE, vars)?);
+    }
+    if config.features.architectury {
+        build.push_str(&render(SC_ARCHITECTURY_GRADLE, vars)?);
+    }
+    if config.features.unit_tests {
+        build.push_str(&render(SC_UNIT_TESTS_GRADLE, vars)?);
+    }
+    if config.features.run_dirs == RunDirs::PerLoader {
+        build.push_str(&render(SC_RUN_DIRS_GRADLE, vars)?);
+    }
+    Ok(build)
+}
+
 /// Build per-version template variables for a specific VersionTarget.
 /// Used to render the per-version properties file.
 pub fn build_version_vars(target: &VersionTarget) -> HashMap<String, String> {
@@ -142,16 +591,35 @@ pub fn build_version_vars(target: &VersionTarget) -> HashMap<String, String> {
         target.fabric_api.clone(),
     );
     vars.insert("neoforge_version".to_string(), target.neoforge.clone());
+    vars.insert("forge_version".to_string(), target.forge.clone());
+    vars.insert("quilt_loader_version".to_string(), target.quilt_loader.clone());
+    vars.insert("architectury_api_version".to_string(), target.architectury_api.clone());
     vars
 }
 
+/// Render `versions/dependencies/<minecraft>.properties` for a target.
+/// `forge_version`, `quilt_loader_version` and `architectury_api_version` are
+/// only written for targets that pin them.
+pub fn render_version_properties(target: &VersionTarget) -> Result<String> {
+    let properties = strip_conditional_blocks(
+        SC_VERSION_GRADLE_PROPERTIES,
+        &[
+            ("forge", !target.forge.is_empty()),
+            ("quilt", !target.quilt_loader.is_empty()),
+            ("architectury", !target.architectury_api.is_empty()),
+        ],
+    );
+    render(&properties, &build_version_vars(target))
+}
+
 /// Strip conditional blocks from rendered template content.
 ///
 /// Blocks are delimited by `{{#name}}...{{/name}}` markers (each on its own line).
 /// If a condition is true the markers are removed but the content is kept.
-/// If false the entire block (markers + content) is removed.
+/// If false the entire block (markers + content) is removed. Like [`render`],
+/// takes the active template pack's version of a built-in template.
 pub fn strip_conditional_blocks(content: &str, conditions: &[(&str, bool)]) -> String {
-    let mut result = content.to_string();
+    let mut result = crate::template_pack::resolve(content).to_string();
     for &(name, enabled) in conditions {
         let open = format!("{{{{#{}}}}}", name);
         let close = format!("{{{{/{}}}}}", name);
@@ -294,4 +762,318 @@ mod tests {
         assert!(!result.contains("inner"));
         assert!(result.contains("rest"));
     }
+
+    fn test_config(language: &str) -> McmodConfig {
+        McmodConfig::new(
+            "testmod".to_string(),
+            "Test Mod".to_string(),
+            "com.example.testmod".to_string(),
+            "Author".to_string(),
+            "Desc".to_string(),
+            language.to_string(),
+            true,
+            true,
+            false,
+            false,
+            None,
+            crate::config::Versions::default(),
+        )
+    }
+
+    #[test]
+    fn test_render_development_doc() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        let doc = render_development_doc(&config, &vars).unwrap();
+        assert!(doc.contains("`src/main/java/com/example/testmod/`"));
+        assert!(doc.contains("./gradlew :<minecraft>-fabric:runClient"));
+        assert!(doc.contains("./gradlew :<minecraft>-neoforge:runClient"));
+        assert!(!doc.contains("-forge:runClient"));
+        assert!(doc.contains("calls the shared `TestmodMod.init()`"));
+        assert!(!doc.contains("PlatformHelper` plays"));
+        assert!(doc.contains("All loaders share the `run/` game directory"));
+        assert!(!doc.contains("{{"));
+
+        config.loaders.neoforge = false;
+        config.features.architectury = true;
+        config.features.run_dirs = crate::config::RunDirs::PerLoader;
+        let doc = render_development_doc(&config, &vars).unwrap();
+        assert!(!doc.contains("-neoforge:runClient"));
+        assert!(doc.contains("`com.example.testmod.platform.PlatformHelper` plays that role"));
+        assert!(doc.contains("under `run/<loader>/`"));
+    }
+
+    #[test]
+    fn test_render_optional_metadata() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        let json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        assert!(json.contains("\"homepage\": \"https://github.com/yourname/testmod\""));
+        assert!(!json.contains("\"email\""));
+        assert!(!json.contains("\"issues\""));
+        serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+        config.mod_info.email = Some("jane@example.com".to_string());
+        config.mod_info.github = Some("jane".to_string());
+        let vars = build_common_vars(&config);
+        let json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["contact"]["email"], "jane@example.com");
+        assert_eq!(parsed["contact"]["issues"], "https://github.com/jane/testmod/issues");
+
+        let toml = render_optional(SC_NEOFORGE_MODS_TOML, &vars).unwrap();
+        assert!(toml.contains("displayURL = \"https://github.com/jane/testmod\""));
+        assert!(toml.contains("issueTrackerURL = \"https://github.com/jane/testmod/issues\""));
+
+        let license = render(TMPL_LICENSE, &vars).unwrap();
+        assert!(license.contains("Author <jane@example.com>"));
+    }
+
+    #[test]
+    fn test_render_library_project() {
+        let mut config = test_config("java");
+        config.mod_info.project_type = crate::config::ProjectType::Library;
+        let vars = build_common_vars(&config);
+
+        let json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(parsed.get("entrypoints").is_none());
+        assert_eq!(parsed["custom"]["modmenu"]["badges"][0], "library");
+
+        let toml = render_optional(SC_NEOFORGE_MODS_TOML, &vars).unwrap();
+        assert!(toml.contains("library = true"));
+
+        let build = render_build_gradle(&config, &vars).unwrap();
+        assert!(build.contains("`maven-publish`"));
+        assert!(build.contains("withJavadocJar()"));
+
+        let (source, path) = render_unified_mod(&config, &vars).unwrap();
+        assert_eq!(path, "src/main/java/com/example/testmod/TestmodMod.java");
+        assert!(!source.contains("ModInitializer"));
+
+        // Regular mods keep their entrypoint and skip publishing
+        let config = test_config("java");
+        let vars = build_common_vars(&config);
+        let json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        assert!(json.contains("\"entrypoints\""));
+        assert!(!json.contains("modmenu"));
+        assert!(!render_build_gradle(&config, &vars).unwrap().contains("maven-publish"));
+    }
+
+    #[test]
+    fn test_render_unified_mod_paths() {
+        let config = test_config("java");
+        let vars = build_common_vars(&config);
+        let (_, path) = render_unified_mod(&config, &vars).unwrap();
+        assert_eq!(path, "src/main/java/com/example/testmod/TestmodMod.java");
+
+        let config = test_config("kotlin");
+        let vars = build_common_vars(&config);
+        let (_, path) = render_unified_mod(&config, &vars).unwrap();
+        assert_eq!(path, "src/main/kotlin/com/example/testmod/TestmodMod.kt");
+    }
+
+    #[test]
+    fn test_render_unified_mod_build_info() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(!source.contains("BuildConstants"));
+        assert!(!source.contains("{{#build_info}}"));
+
+        config.features.build_info = true;
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("BuildConstants.GIT_COMMIT"));
+        assert!(!source.contains("{{/build_info}}"));
+    }
+
+    #[test]
+    fn test_render_build_info_gradle() {
+        let config = test_config("java");
+        let vars = build_common_vars(&config);
+        let rendered = render(SC_BUILD_INFO_GRADLE, &vars).unwrap();
+        assert!(rendered.contains("package com.example.testmod;"));
+        assert!(rendered.contains("com/example/testmod/BuildConstants.java"));
+    }
+
+    #[test]
+    fn test_render_forge_loader() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(!source.contains("minecraftforge"));
+        assert!(!source.contains("{{#forge}}"));
+
+        config.loaders.forge = true;
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("import net.minecraftforge.fml.common.Mod;"));
+        assert!(source.contains("*//*?} elif forge {*/\n/*@Mod(TestmodMod.MOD_ID)"));
+        assert!(source.contains("public TestmodMod(FMLJavaModLoadingContext context) {"));
+
+        let mods_toml = render_optional(SC_FORGE_MODS_TOML, &vars).unwrap();
+        assert!(mods_toml.contains("modId = \"forge\"\nmandatory = true"));
+    }
+
+    #[test]
+    fn test_render_version_properties() {
+        let mut target = crate::config::Versions::default().targets.remove(0);
+        let rendered = render_version_properties(&target).unwrap();
+        assert!(rendered.ends_with("neoforge_version=21.4.157\n"));
+
+        target.forge = "54.1.0".to_string();
+        let rendered = render_version_properties(&target).unwrap();
+        assert!(rendered.ends_with("neoforge_version=21.4.157\nforge_version=54.1.0\n"));
+    }
+
+    #[test]
+    fn test_render_quilt_loader() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("/*? if fabric {*/"));
+
+        config.loaders.quilt = true;
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("/*? if fabric || quilt {*/\nimport net.fabricmc.api.ModInitializer;"));
+        assert!(source.contains("*//*?} elif fabric || quilt {*/"));
+
+        let quilt_json = render_optional(SC_QUILT_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&quilt_json).unwrap();
+        assert_eq!(parsed["quilt_loader"]["id"], "${id}");
+        assert_eq!(parsed["quilt_loader"]["entrypoints"]["main"], "${group}.TestmodMod");
+        assert_eq!(parsed["mixin"], "testmod.mixins.json");
+
+        let mut target = crate::config::Versions::default().targets.remove(0);
+        target.quilt_loader = "0.29.2".to_string();
+        let rendered = render_version_properties(&target).unwrap();
+        assert!(rendered.ends_with("neoforge_version=21.4.157\nquilt_loader_version=0.29.2\n"));
+    }
+
+    #[test]
+    fn test_render_architectury() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        assert!(!render_build_gradle(&config, &vars).unwrap().contains("architectury"));
+        assert!(!render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap().contains("architectury"));
+
+        config.features.architectury = true;
+        config.loaders.quilt = true;
+        let vars = build_common_vars(&config);
+        let build = render_build_gradle(&config, &vars).unwrap();
+        assert!(build.contains("maven(\"https://maven.architectury.dev/\")"));
+        assert!(build.contains("dev.architectury:architectury-$platform:${property(\"architectury_api_version\")}"));
+
+        let fabric_json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&fabric_json).unwrap();
+        assert_eq!(parsed["depends"]["architectury"], "*");
+        let quilt_json = render_optional(SC_QUILT_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&quilt_json).unwrap();
+        assert_eq!(parsed["quilt_loader"]["depends"][2]["id"], "architectury");
+        let mods_toml = render_optional(SC_NEOFORGE_MODS_TOML, &vars).unwrap();
+        assert!(mods_toml.contains("modId = \"architectury\"\ntype = \"required\""));
+
+        let (helper, path) = render_platform_helper(&config, &vars).unwrap();
+        assert_eq!(path, "src/main/java/com/example/testmod/platform/PlatformHelper.java");
+        assert!(helper.starts_with("package com.example.testmod.platform;"));
+        assert!(helper.contains("*//*?} elif quilt {*/\n        /*return \"Quilt\";"));
+        assert!(!helper.contains("{{"));
+
+        let mut target = crate::config::Versions::default().targets.remove(0);
+        target.architectury_api = "15.0.3".to_string();
+        let rendered = render_version_properties(&target).unwrap();
+        assert!(rendered.ends_with("neoforge_version=21.4.157\narchitectury_api_version=15.0.3\n"));
+    }
+
+    #[test]
+    fn test_render_client_environment() {
+        let mut config = test_config("kotlin");
+        let vars = build_common_vars(&config);
+        let fabric_json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&fabric_json).unwrap();
+        assert_eq!(parsed["environment"], "*");
+        assert_eq!(parsed["entrypoints"]["main"][0], "${group}.TestmodMod");
+        assert!(!render_build_gradle(&config, &vars).unwrap().contains("runConfigs"));
+
+        config.mod_info.environment = Environment::Client;
+        let vars = build_common_vars(&config);
+        let fabric_json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&fabric_json).unwrap();
+        assert_eq!(parsed["environment"], "client");
+        assert_eq!(parsed["entrypoints"]["client"][0], "${group}.TestmodMod");
+        let quilt_json = render_optional(SC_QUILT_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&quilt_json).unwrap();
+        assert_eq!(parsed["minecraft"]["environment"], "client");
+        assert_eq!(parsed["quilt_loader"]["entrypoints"]["client"], "${group}.TestmodMod");
+
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("import net.fabricmc.api.ClientModInitializer"));
+        assert!(source.contains("class TestmodMod : ClientModInitializer {\n    override fun onInitializeClient() {"));
+        let build = render_build_gradle(&config, &vars).unwrap();
+        assert!(build.contains("runConfigs.named(\"server\") {\n        ideConfigGenerated(false)"));
+
+        config.mod_info.environment = Environment::Server;
+        let vars = build_common_vars(&config);
+        let (source, _) = render_unified_mod(&config, &vars).unwrap();
+        assert!(source.contains("override fun onInitializeServer()"));
+        assert!(!render_build_gradle(&config, &vars).unwrap().contains("runConfigs"));
+    }
+
+    #[test]
+    fn test_render_split_environment() {
+        let mut config = test_config("java");
+        let vars = build_common_vars(&config);
+        assert!(render_fabric_entrypoints(&vars).unwrap().is_empty());
+        assert!(!render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap().contains("\"client\""));
+
+        config.features.split_environment = true;
+        config.loaders.quilt = true;
+        let vars = build_common_vars(&config);
+        let fabric_json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&fabric_json).unwrap();
+        assert_eq!(parsed["entrypoints"]["client"][0], "${group}.TestmodModClient");
+        assert_eq!(parsed["entrypoints"]["main"][0], "${group}.TestmodMod");
+        let quilt_json = render_optional(SC_QUILT_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&quilt_json).unwrap();
+        assert_eq!(parsed["quilt_loader"]["entrypoints"]["client"], "${group}.TestmodModClient");
+
+        let (source, path) = render_fabric_entrypoints(&vars).unwrap().remove(0);
+        assert_eq!(path, "src/client/java/com/example/testmod/TestmodModClient.java");
+        assert!(source.contains("/*? if fabric || quilt {*/\npublic class TestmodModClient implements ClientModInitializer {"));
+        assert!(source.contains("TestmodMod.LOGGER.info"));
+
+        let build = render_build_gradle(&config, &vars).unwrap();
+        assert!(build.contains("        splitEnvironmentSourceSets()\n"));
+        assert!(build.contains("java.srcDir(\"src/client/java\")"));
+        assert!(!build.contains("kotlin.srcDir"));
+    }
+
+    #[test]
+    fn test_render_fabric_entrypoints() {
+        let mut config = test_config("kotlin");
+        config.features.fabric_entrypoints = FabricEntrypoint::ALL.to_vec();
+        let vars = build_common_vars(&config);
+        let fabric_json = render_optional(SC_FABRIC_MOD_JSON, &vars).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&fabric_json).unwrap();
+        assert_eq!(parsed["entrypoints"]["client"][0], "${group}.TestmodModClient");
+        assert_eq!(parsed["entrypoints"]["fabric-datagen"][0], "${group}.TestmodModDataGenerator");
+        assert_eq!(parsed["entrypoints"]["preLaunch"][0], "${group}.TestmodModPreLaunch");
+
+        let files = render_fabric_entrypoints(&vars).unwrap();
+        let paths: Vec<&str> = files.iter().map(|(_, p)| p.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src/main/kotlin/com/example/testmod/TestmodModClient.kt",
+                "src/main/kotlin/com/example/testmod/TestmodModDataGenerator.kt",
+                "src/main/kotlin/com/example/testmod/TestmodModPreLaunch.kt",
+            ]
+        );
+        assert!(files[1].0.contains("class TestmodModDataGenerator : DataGeneratorEntrypoint {"));
+        assert!(files[2].0.contains("override fun onPreLaunch()"));
+        assert!(render_build_gradle(&config, &vars).unwrap().contains("configureDataGeneration()"));
+
+        config.mod_info.project_type = crate::config::ProjectType::Library;
+        let vars = build_common_vars(&config);
+        assert!(render_fabric_entrypoints(&vars).unwrap().is_empty());
+    }
 }
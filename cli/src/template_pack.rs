@@ -0,0 +1,293 @@
+//! Template packs: user-installed replacements for the built-in project
+//! templates.
+//!
+//! A pack is a directory with a `pack.toml` and files laid out like the
+//! built-in `templates/` tree (`stonecutter/build.gradle.kts`,
+//! `stonecutter/UnifiedMod.java`, ...); each file replaces the built-in
+//! template at that path. Installed packs live in the global config dir's
+//! `templates/<name>`. One pack is active per run, chosen at startup (`init
+//! --template`, or the `template` preference set by `mcmod template use`), and
+//! `template::render` and `template::strip_conditional_blocks` pick up its
+//! versions of the templates they're given.
+
+use crate::error::{Context, McmodError, Result};
+use crate::global_config;
+use crate::template::PROJECT_TEMPLATES;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Metadata file at the root of a pack.
+pub const PACK_FILE: &str = "pack.toml";
+
+/// Name that selects the built-in templates.
+pub const BUILT_IN: &str = "default";
+
+/// Contents of `pack.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct TemplatePack {
+    pub info: PackInfo,
+    pub dir: PathBuf,
+    /// Overridden templates by path under templates/.
+    files: BTreeMap<String, String>,
+    /// Files that don't match any built-in template, and so are never used.
+    pub unused: Vec<String>,
+}
+
+impl TemplatePack {
+    /// Read the pack in `dir`. Without a `pack.toml` the pack is named after
+    /// the directory.
+    pub fn load(dir: &Path) -> Result<TemplatePack> {
+        let info_path = dir.join(PACK_FILE);
+        let info = if info_path.exists() {
+            let content = std::fs::read_to_string(&info_path)
+                .with_context(|| format!("Reading {}", info_path.display()))?;
+            toml::from_str(&content).with_context(|| format!("Parsing {}", info_path.display()))?
+        } else {
+            let name = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            PackInfo { name, description: None, version: None }
+        };
+
+        let mut files = BTreeMap::new();
+        let mut unused = Vec::new();
+        for relative in list_files(dir)? {
+            if relative == PACK_FILE {
+                continue;
+            }
+            if PROJECT_TEMPLATES.iter().any(|(path, _)| *path == relative) {
+                let content = std::fs::read_to_string(dir.join(&relative))
+                    .with_context(|| format!("Reading template {relative} from pack {}", info.name))?;
+                files.insert(relative, content);
+            } else {
+                unused.push(relative);
+            }
+        }
+        Ok(TemplatePack { info, dir: dir.to_path_buf(), files, unused })
+    }
+
+    /// Templates this pack replaces, by path under templates/.
+    pub fn overrides(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// The pack's version of a built-in template, looked up by its content.
+    fn lookup(&self, builtin: &str) -> Option<&str> {
+        let (path, _) = PROJECT_TEMPLATES.iter().find(|(_, content)| *content == builtin)?;
+        self.files.get(*path).map(String::as_str)
+    }
+}
+
+/// Relative paths (with `/` separators) of every file below `dir`, sorted.
+fn list_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current).with_context(|| format!("Reading {}", current.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+                files.push(parts.join("/"));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Pack names are directory names in the global config dir: lowercase
+/// letters, digits, `-` and `_`.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(McmodError::Other(format!(
+            "Invalid template pack name '{name}': use lowercase letters, digits, '-' and '_'"
+        )));
+    }
+    if name == BUILT_IN {
+        return Err(McmodError::Other(format!("'{BUILT_IN}' is the name of the built-in templates")));
+    }
+    Ok(())
+}
+
+/// Installed packs, by name.
+pub fn installed() -> Result<Vec<TemplatePack>> {
+    installed_in(&global_config::templates_dir()?)
+}
+
+fn installed_in(root: &Path) -> Result<Vec<TemplatePack>> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Ok(Vec::new());
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    dirs.sort();
+    dirs.iter().map(|dir| TemplatePack::load(dir)).collect()
+}
+
+/// The installed pack called `name`.
+pub fn find(name: &str) -> Result<TemplatePack> {
+    find_in(&global_config::templates_dir()?, name)
+}
+
+fn find_in(root: &Path, name: &str) -> Result<TemplatePack> {
+    validate_name(name)?;
+    let dir = root.join(name);
+    if !dir.is_dir() {
+        return Err(McmodError::Other(format!(
+            "No template pack named '{name}' (see `mcmod template list`)"
+        )));
+    }
+    TemplatePack::load(&dir)
+}
+
+/// Copy the pack in `source` into `root/<name>`, writing its `pack.toml`
+/// with the final name. `name` defaults to the pack's own.
+pub fn install_into(root: &Path, source: &Path, name: Option<&str>, force: bool) -> Result<TemplatePack> {
+    let mut pack = TemplatePack::load(source)?;
+    if let Some(name) = name {
+        pack.info.name = name.to_string();
+    }
+    validate_name(&pack.info.name)?;
+    if pack.files.is_empty() {
+        return Err(McmodError::Other(format!(
+            "{} doesn't replace any built-in template; lay files out like templates/, e.g. stonecutter/build.gradle.kts",
+            source.display()
+        )));
+    }
+
+    let dest = root.join(&pack.info.name);
+    if dest.exists() {
+        if !force {
+            return Err(McmodError::Other(format!(
+                "A template pack named '{}' is already installed; pass --force to replace it",
+                pack.info.name
+            )));
+        }
+        std::fs::remove_dir_all(&dest).with_context(|| format!("Removing {}", dest.display()))?;
+    }
+    for relative in pack.files.keys().chain(&pack.unused) {
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Creating {}", parent.display()))?;
+        }
+        std::fs::copy(source.join(relative), &target).with_context(|| format!("Copying {relative}"))?;
+    }
+    let info = toml::to_string_pretty(&pack.info).map_err(McmodError::TomlSerialize)?;
+    crate::util::write_file(&dest.join(PACK_FILE), &info)?;
+    pack.dir = dest;
+    Ok(pack)
+}
+
+/// Write every built-in project template into `dest` in the pack layout,
+/// with a `pack.toml` named after the directory, so the copy can be edited
+/// and installed. Refuses a non-empty `dest` unless `force` is set.
+pub fn eject_into(dest: &Path, force: bool) -> Result<TemplatePack> {
+    if !force && std::fs::read_dir(dest).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(McmodError::Other(format!(
+            "{} is not empty; pass --force to overwrite the templates in it",
+            dest.display()
+        )));
+    }
+    for (relative, content) in PROJECT_TEMPLATES {
+        crate::util::write_file(&dest.join(relative), content)?;
+    }
+    let name: String = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' { c } else { '-' })
+        .collect();
+    let info = PackInfo {
+        name: if validate_name(&name).is_ok() { name } else { "ejected".to_string() },
+        description: Some("The built-in templates, exported by mcmod eject-templates".to_string()),
+        version: Some(env!("CARGO_PKG_VERSION").to_string()),
+    };
+    let info = toml::to_string_pretty(&info).map_err(McmodError::TomlSerialize)?;
+    crate::util::write_file(&dest.join(PACK_FILE), &info)?;
+    TemplatePack::load(dest)
+}
+
+/// The pack used for this run, if any.
+static ACTIVE: OnceLock<TemplatePack> = OnceLock::new();
+
+/// Use `pack`'s templates for the rest of the run. Only the first call has effect.
+pub fn activate(pack: TemplatePack) {
+    let _ = ACTIVE.set(pack);
+}
+
+pub fn active() -> Option<&'static TemplatePack> {
+    ACTIVE.get()
+}
+
+/// The active pack's version of `template` when it's a built-in template the
+/// pack replaces, otherwise `template` itself.
+pub fn resolve(template: &str) -> &str {
+    match ACTIVE.get() {
+        Some(pack) => pack.lookup(template).unwrap_or(template),
+        None => template,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template;
+
+    #[test]
+    fn test_install_and_lookup() {
+        let base = std::env::temp_dir().join(format!("mcmod_template_pack_{}", std::process::id()));
+        let source = base.join("arch-layout");
+        let root = base.join("templates");
+        std::fs::create_dir_all(source.join("stonecutter")).unwrap();
+        std::fs::write(source.join("stonecutter/build.gradle.kts"), "// custom {{mod_id}}\n").unwrap();
+        std::fs::write(source.join("notes.txt"), "not a template").unwrap();
+
+        let pack = install_into(&root, &source, None, false).unwrap();
+        assert_eq!(pack.info.name, "arch-layout");
+        assert_eq!(pack.overrides().collect::<Vec<_>>(), ["stonecutter/build.gradle.kts"]);
+        assert_eq!(pack.unused, ["notes.txt"]);
+        assert_eq!(pack.lookup(template::SC_BUILD_GRADLE), Some("// custom {{mod_id}}\n"));
+        assert_eq!(pack.lookup(template::SC_SETTINGS_GRADLE), None);
+        assert_eq!(pack.lookup("not a template"), None);
+
+        assert!(install_into(&root, &source, None, false).is_err());
+        install_into(&root, &source, Some("renamed"), false).unwrap();
+        let names: Vec<String> = installed_in(&root).unwrap().into_iter().map(|p| p.info.name).collect();
+        assert_eq!(names, ["arch-layout", "renamed"]);
+        assert_eq!(find_in(&root, "renamed").unwrap().overrides().count(), 1);
+        assert!(find_in(&root, "missing").is_err());
+        assert!(install_into(&root, &source, Some("Bad Name"), false).is_err());
+        assert!(install_into(&root, &source, Some(BUILT_IN), false).is_err());
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_eject_round_trip() {
+        let base = std::env::temp_dir().join(format!("mcmod_eject_{}", std::process::id()));
+        let dest = base.join("My Templates");
+        let pack = eject_into(&dest, false).unwrap();
+        assert_eq!(pack.info.name, "my-templates");
+        assert_eq!(pack.overrides().count(), PROJECT_TEMPLATES.len());
+        assert!(pack.unused.is_empty());
+        assert_eq!(pack.lookup(template::SC_BUILD_GRADLE), Some(template::SC_BUILD_GRADLE));
+
+        assert!(eject_into(&dest, false).is_err());
+        eject_into(&dest, true).unwrap();
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::error::{McmodError, Result};
-use std::io::Read;
+use crate::error::{Context, McmodError, Result};
+use crate::file_policy::FilePolicy;
+use crate::fs;
+use crate::profile::{self, Phase};
+use std::io::{Read, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 /// Validates a mod ID: must match ^[a-z][a-z0-9_]*$
@@ -29,6 +33,194 @@ pub fn validate_package(pkg: &str) -> Result<()> {
     Ok(())
 }
 
+/// Loosely validates an email address: a single `@` with text on both sides
+/// and a dot in the domain. Metadata only, so no deliverability checks.
+pub fn validate_email(email: &str) -> Result<()> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(McmodError::Other(format!("Invalid email address '{email}'")));
+    }
+    Ok(())
+}
+
+/// Java reserved words that cannot be used as package segments.
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short",
+    "static", "strictfp", "super", "switch", "synchronized", "this", "throw", "throws",
+    "transient", "true", "try", "void", "volatile", "while",
+];
+
+/// Spells accented Latin, Cyrillic and Greek letters in lowercase ASCII, so
+/// identifiers derived from display names keep their words:
+/// e.g. "Crème Brûlée" -> "Creme Brulee", "Мой мод" -> "moy mod".
+/// Other characters (CJK, symbols) are left as they are for the caller to
+/// replace; the result is not necessarily ASCII.
+pub fn transliterate(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() {
+            out.push(c);
+            continue;
+        }
+        for lower in c.to_lowercase() {
+            match transliterate_char(lower) {
+                Some(ascii) => out.push_str(ascii),
+                None => out.push(lower),
+            }
+        }
+    }
+    out
+}
+
+/// ASCII spelling of a lowercase non-ASCII letter, if it has one.
+fn transliterate_char(c: char) -> Option<&'static str> {
+    Some(match c {
+        // Combining marks, as in decomposed "e\u{301}"
+        '\u{300}'..='\u{36f}' => "",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        // Cyrillic (Russian, Ukrainian, Belarusian)
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' | 'ў' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        // Greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' | 'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'θ' => "th",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' | 'ω' | 'ώ' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        _ => return None,
+    })
+}
+
+/// Converts arbitrary text (an author name, a GitHub handle) into a valid
+/// Java package segment: lowercase, `[a-z0-9_]`, starting with a letter.
+/// Non-ASCII letters are transliterated first. Returns None if nothing
+/// usable remains.
+/// e.g. "Jane-Doe" -> "jane_doe", "Zoë" -> "zoe", "Class" -> "class_", "123" -> None
+pub fn sanitize_package_segment(s: &str) -> Option<String> {
+    let mapped: String = transliterate(s)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let segment = mapped
+        .split('_')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    let segment = segment.trim_start_matches(|c: char| c.is_ascii_digit() || c == '_');
+
+    if segment.is_empty() {
+        return None;
+    }
+    if JAVA_KEYWORDS.contains(&segment) {
+        return Some(format!("{segment}_"));
+    }
+    Some(segment.to_string())
+}
+
+/// Derives the default package for a new mod.
+/// Prefers the reverse-domain `io.github.<user>.<mod_id>` when a GitHub
+/// username is known, then `com.<author>.<mod_id>`, then `com.example.<mod_id>`.
+pub fn default_package(author: &str, github: Option<&str>, mod_id: &str) -> String {
+    if let Some(user) = github.and_then(sanitize_package_segment) {
+        return format!("io.github.{user}.{mod_id}");
+    }
+    let author = sanitize_package_segment(author).unwrap_or_else(|| "example".to_string());
+    format!("com.{author}.{mod_id}")
+}
+
+/// Returns true if the package uses the `com.example` placeholder domain.
+pub fn is_placeholder_package(pkg: &str) -> bool {
+    pkg == "com.example" || pkg.starts_with("com.example.")
+}
+
 /// Converts a snake_case string to PascalCase.
 /// e.g. "my_cool_mod" -> "MyCoolMod"
 pub fn to_pascal_case(s: &str) -> String {
@@ -60,32 +252,109 @@ pub fn derive_class_name(mod_id: &str) -> String {
     format!("{}Mod", to_pascal_case(mod_id))
 }
 
+/// Path from directory `from` to `to` using forward slashes, e.g. `../mylib`.
+/// Both paths should be absolute and normalized (canonicalized).
+pub fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Silence [`progress`] lines, for commands that generate many projects at once.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Print a "Created ..." style progress line unless output is quieted (or
+/// reported as `--progress json` events instead).
+pub fn progress(line: impl std::fmt::Display) {
+    if !QUIET.load(Ordering::Relaxed) && !crate::progress::is_json() {
+        println!("{line}");
+    }
+}
+
 /// Ensures a directory exists, creating it if necessary.
 pub fn ensure_dir(path: &Path) -> Result<()> {
-    if !path.exists() {
-        std::fs::create_dir_all(path)?;
+    if !fs::exists(path) {
+        fs::create_dir_all(path)?;
     }
     Ok(())
 }
 
 /// Writes content to a file, creating parent directories as needed.
+/// Line endings and permissions follow `FilePolicy::for_path`.
 pub fn write_file(path: &Path, content: &str) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        ensure_dir(parent)?;
-    }
-    std::fs::write(path, content)?;
-    Ok(())
+    write_file_with(path, content, &FilePolicy::for_path(path))
+}
+
+/// Writes text content using an explicit file policy.
+pub fn write_file_with(path: &Path, content: &str, policy: &FilePolicy) -> Result<()> {
+    atomic_write(path, policy.normalize(content).as_bytes(), policy.mode())
 }
 
 /// Writes binary content to a file, creating parent directories as needed.
+/// Content is written verbatim; only the permissions from the policy apply.
 pub fn write_binary(path: &Path, content: &[u8]) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        ensure_dir(parent)?;
+    write_binary_with(path, content, &FilePolicy::for_path(path))
+}
+
+/// Writes binary content using an explicit file policy.
+pub fn write_binary_with(path: &Path, content: &[u8], policy: &FilePolicy) -> Result<()> {
+    atomic_write(path, content, policy.mode())
+}
+
+/// Crash-safe write through the active filesystem (see `fs::RealFs::write`).
+///
+/// `mode` sets Unix permissions; when None an existing file keeps its mode.
+fn atomic_write(path: &Path, content: &[u8], mode: Option<u32>) -> Result<()> {
+    profile::time(
+        Phase::Write,
+        || path.display().to_string(),
+        || fs::write(path, content, mode),
+    )
+    .with_context(|| format!("Writing {}", path.display()))?;
+    if !fs::is_in_memory() {
+        crate::progress::file_written(path, content.len());
     }
-    std::fs::write(path, content)?;
     Ok(())
 }
 
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Refuse network access for the rest of the run (`--offline`,
+/// `MCMOD_OFFLINE`, or the `offline` preference).
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// The one check every request below makes before connecting, so offline
+/// mode covers all of them.
+fn ensure_online(url: &str) -> Result<()> {
+    if !is_offline() {
+        return Ok(());
+    }
+    let host = url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or(url);
+    Err(McmodError::Offline(host.to_string()))
+}
+
 fn http_agent() -> ureq::Agent {
     ureq::Agent::config_builder()
         .timeout_global(Some(Duration::from_secs(10)))
@@ -95,9 +364,22 @@ fn http_agent() -> ureq::Agent {
 
 /// Perform an HTTP GET request and return the response body as a string.
 pub fn http_get(url: &str) -> Result<String> {
-    let body = http_agent()
-        .get(url)
-        .header("User-Agent", "mcmod-cli")
+    http_get_with_headers(url, &[])
+}
+
+/// [`http_get`] with extra request headers, e.g. an API token.
+pub fn http_get_with_headers(url: &str, headers: &[(&str, &str)]) -> Result<String> {
+    profile::time(Phase::Fetch, || url.to_string(), || fetch_text(url, headers))
+        .with_context(|| format!("Fetching {url}"))
+}
+
+fn fetch_text(url: &str, headers: &[(&str, &str)]) -> Result<String> {
+    ensure_online(url)?;
+    let mut request = http_agent().get(url).header("User-Agent", "mcmod-cli");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let body = request
         .call()
         .map_err(|e| McmodError::Http(format!("{e}")))?
         .into_body()
@@ -106,19 +388,156 @@ pub fn http_get(url: &str) -> Result<String> {
     Ok(body)
 }
 
-/// Perform an HTTP GET request and return the response body as bytes.
-pub fn http_get_bytes(url: &str) -> Result<Vec<u8>> {
-    let mut bytes = Vec::new();
-    http_agent()
-        .get(url)
-        .header("User-Agent", "mcmod-cli")
+/// POST `body` to `url` with the given headers. Returns the status and the
+/// response text; error statuses are returned rather than raised so callers
+/// can show the API's message.
+pub fn http_post(url: &str, headers: &[(&str, &str)], body: &[u8]) -> Result<(u16, String)> {
+    profile::time(Phase::Fetch, || url.to_string(), || post(url, headers, body))
+        .with_context(|| format!("Posting to {url}"))
+}
+
+fn post(url: &str, headers: &[(&str, &str)], body: &[u8]) -> Result<(u16, String)> {
+    ensure_online(url)?;
+    let mut request = download_agent().post(url).header("User-Agent", "mcmod-cli");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let mut response = request.send(body).map_err(|e| McmodError::Http(format!("{e}")))?;
+    let status = response.status().as_u16();
+    let text = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| McmodError::Http(format!("{e}")))?;
+    Ok((status, text))
+}
+
+/// Agent for large downloads and uploads: bounded connect and header waits but
+/// no overall deadline, so slow connections can still finish. Status codes are returned
+/// rather than raised so callers can handle range responses.
+fn download_agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_connect(Some(Duration::from_secs(10)))
+        .timeout_recv_response(Some(Duration::from_secs(30)))
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+/// Download `url` into `dest`, resuming with an HTTP range request if `dest`
+/// already holds a partial download. A 206 appends to the partial file, a 200
+/// (the server ignored the range) starts it over, and a 416 means the partial
+/// file is already complete. Nothing here checks the contents; callers verify
+/// the size or checksum. `on_progress(downloaded, total)` is called as bytes
+/// arrive. Returns the number of bytes on disk when finished.
+pub fn http_download(
+    url: &str,
+    dest: &Path,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<u64> {
+    download_to(url, dest, on_progress)
+        .with_context(|| format!("Downloading {url} to {}", dest.display()))
+}
+
+fn download_to(
+    url: &str,
+    dest: &Path,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<u64> {
+    ensure_online(url)?;
+    let existing = std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = download_agent().get(url).header("User-Agent", "mcmod-cli");
+    if existing > 0 {
+        request = request.header("Range", &format!("bytes={existing}-"));
+    }
+    let mut response = request
         .call()
-        .map_err(|e| McmodError::Http(format!("{e}")))?
-        .into_body()
-        .as_reader()
-        .read_to_end(&mut bytes)
         .map_err(|e| McmodError::Http(format!("{e}")))?;
-    Ok(bytes)
+
+    let status = response.status().as_u16();
+    let length = response.body().content_length();
+    let (mut file, mut downloaded, total) = match status {
+        206 => {
+            let file = std::fs::OpenOptions::new().append(true).open(dest)?;
+            (file, existing, length.map(|l| l + existing))
+        }
+        200 => (std::fs::File::create(dest)?, 0, length),
+        // The partial file already covers the whole resource
+        416 if existing > 0 => return Ok(existing),
+        _ => {
+            return Err(McmodError::Http(format!("HTTP {status}")));
+        }
+    };
+
+    let mut reader = response.body_mut().as_reader();
+    let mut buf = [0u8; 64 * 1024];
+    on_progress(downloaded, total);
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| McmodError::Http(format!("{e}")))?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buf[..n])?;
+        downloaded += n as u64;
+        on_progress(downloaded, total);
+    }
+    file.sync_all()?;
+    Ok(downloaded)
+}
+
+/// CRC-32 (the zip and PNG checksum).
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Hex-encoded SHA-256 digest of a file's contents.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Hex-encoded SHA-256 digest of in-memory bytes.
+pub fn sha256_bytes(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(data).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hex-encoded SHA-1 digest of a file's contents, for checksums that are
+/// only published as SHA-1 (Mojang's downloads, Maven `.sha1` files).
+pub fn sha1_file(path: &Path) -> Result<String> {
+    use ring::digest;
+    let mut file = std::fs::File::open(path)?;
+    let mut context = digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY);
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.update(&buf[..n]);
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
 }
 
 #[cfg(test)]
@@ -153,6 +572,129 @@ mod tests {
         assert!(validate_package("com.1example").is_err());
     }
 
+    #[test]
+    fn test_write_file_replaces_atomically() {
+        let dir = std::env::temp_dir().join(format!("mcmod_atomic_{}", std::process::id()));
+        let path = dir.join("nested/file.txt");
+        write_file(&path, "first").unwrap();
+        write_file(&path, "second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+
+        // No temp files left behind
+        let entries: Vec<_> = std::fs::read_dir(dir.join("nested")).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_executable_mode_preserved() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("mcmod_exec_{}", std::process::id()));
+        let path = dir.join("gradlew");
+        let policy = FilePolicy::for_path(&path).with_executable(true);
+        write_binary_with(&path, b"#!/bin/sh\n", &policy).unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&path), 0o755);
+
+        // Rewriting without an explicit mode keeps the executable bit
+        let plain = policy.with_executable(false);
+        write_binary_with(&path, b"#!/bin/sh\necho hi\n", &plain).unwrap();
+        assert_eq!(mode(&path), 0o755);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_write_file_applies_line_endings() {
+        let dir = std::env::temp_dir().join(format!("mcmod_eol_{}", std::process::id()));
+        write_file(&dir.join("run.bat"), "@echo off\necho hi\n").unwrap();
+        assert_eq!(
+            std::fs::read(dir.join("run.bat")).unwrap(),
+            b"@echo off\r\necho hi\r\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let path = std::env::temp_dir().join(format!("mcmod_sha256_{}", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_sha1_file() {
+        let path = std::env::temp_dir().join(format!("mcmod_sha1_{}", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        assert_eq!(sha1_file(&path).unwrap(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(validate_email("jane@example.com").is_ok());
+        assert!(validate_email("jane.doe+mods@mail.example.org").is_ok());
+        assert!(validate_email("").is_err());
+        assert!(validate_email("jane").is_err());
+        assert!(validate_email("jane@localhost").is_err());
+        assert!(validate_email("@example.com").is_err());
+        assert!(validate_email("jane@@example.com").is_err());
+        assert!(validate_email("jane doe@example.com").is_err());
+    }
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate("Crème Brûlée"), "Creme Brulee");
+        assert_eq!(transliterate("Straße"), "Strasse");
+        assert_eq!(transliterate("Ærø"), "aero");
+        assert_eq!(transliterate("Мой мод"), "moy mod");
+        assert_eq!(transliterate("Ψυχή"), "psychi");
+        assert_eq!(transliterate("Cafe\u{301}"), "Cafe");
+        assert_eq!(transliterate("Plain ASCII"), "Plain ASCII");
+        assert_eq!(transliterate("魔法"), "魔法");
+    }
+
+    #[test]
+    fn test_sanitize_package_segment() {
+        assert_eq!(sanitize_package_segment("Jane-Doe"), Some("jane_doe".to_string()));
+        assert_eq!(sanitize_package_segment("Your Name"), Some("your_name".to_string()));
+        assert_eq!(sanitize_package_segment("dev123"), Some("dev123".to_string()));
+        assert_eq!(sanitize_package_segment("42cats"), Some("cats".to_string()));
+        assert_eq!(sanitize_package_segment("Class"), Some("class_".to_string()));
+        assert_eq!(sanitize_package_segment("123"), None);
+        assert_eq!(sanitize_package_segment("--"), None);
+        assert_eq!(sanitize_package_segment("Zoë Müller"), Some("zoe_muller".to_string()));
+        assert_eq!(sanitize_package_segment("Дмитрий"), Some("dmitriy".to_string()));
+        assert_eq!(sanitize_package_segment("山田"), None);
+        for input in ["Jane-Doe", "42cats", "Class", "a.b.c"] {
+            let seg = sanitize_package_segment(input).unwrap();
+            assert!(validate_package(&seg).is_ok(), "{seg}");
+        }
+    }
+
+    #[test]
+    fn test_default_package() {
+        assert_eq!(
+            default_package("Jane", Some("Jane-Dev"), "mymod"),
+            "io.github.jane_dev.mymod"
+        );
+        assert_eq!(default_package("Jane Doe", None, "mymod"), "com.jane_doe.mymod");
+        assert_eq!(default_package("", None, "mymod"), "com.example.mymod");
+        assert_eq!(default_package("Jane", Some("---"), "mymod"), "com.jane.mymod");
+    }
+
+    #[test]
+    fn test_is_placeholder_package() {
+        assert!(is_placeholder_package("com.example.mymod"));
+        assert!(is_placeholder_package("com.example"));
+        assert!(!is_placeholder_package("com.examples.mymod"));
+        assert!(!is_placeholder_package("io.github.jane.mymod"));
+    }
+
     #[test]
     fn test_to_pascal_case() {
         assert_eq!(to_pascal_case("my_cool_mod"), "MyCoolMod");
@@ -167,6 +709,15 @@ mod tests {
         assert_eq!(package_to_path("mymod"), "mymod");
     }
 
+    #[test]
+    fn test_relative_path() {
+        let root = Path::new("/work");
+        assert_eq!(relative_path(&root.join("content"), &root.join("lib")), "../lib");
+        assert_eq!(relative_path(root, &root.join("a/b")), "a/b");
+        assert_eq!(relative_path(&root.join("a/b"), root), "../..");
+        assert_eq!(relative_path(root, root), ".");
+    }
+
     #[test]
     fn test_derive_class_name() {
         assert_eq!(derive_class_name("my_mod"), "MyModMod");
@@ -1,9 +1,9 @@
-/// Per-Minecraft-version metadata for all upstream dependency versions.
-///
-/// Each entry pins known-good versions of Fabric Loader, Fabric API, and NeoForge
-/// for a specific Minecraft release. When the CLI targets a particular MC version
-/// (via `--minecraft` or interactive prompt), this table provides the offline
-/// defaults and guides online fetching.
+//! Per-Minecraft-version metadata for all upstream dependency versions.
+//!
+//! Each entry pins known-good versions of Fabric Loader, Fabric API, and NeoForge
+//! for a specific Minecraft release. When the CLI targets a particular MC version
+//! (via `--minecraft` or interactive prompt), this table provides the offline
+//! defaults and guides online fetching.
 
 /// Dependency versions for a specific Minecraft release.
 #[derive(Debug, Clone)]
@@ -12,8 +12,23 @@ pub struct VersionMeta {
     pub fabric_loader: &'static str,
     pub fabric_api: &'static str,
     pub neoforge: &'static str,
+    /// Forge version without the Minecraft prefix (`54.1.0`, published as
+    /// `1.21.4-54.1.0`); empty when Forge has no build for that release.
+    pub forge: &'static str,
+    /// Architectury API for Fabric and NeoForge (it has no Forge builds for 1.21+).
+    pub architectury_api: &'static str,
 }
 
+/// Known-good Quilt Loader. Like Fabric Loader it isn't tied to a Minecraft
+/// release, so one version serves every target.
+pub const QUILT_LOADER: &str = "0.29.2";
+
+/// Kotlin Gradle plugin for Kotlin projects, with the Fabric Language Kotlin
+/// and KotlinForForge (NeoForge) releases built against it.
+pub const KOTLIN: &str = "2.1.0";
+pub const FABRIC_LANGUAGE_KOTLIN: &str = "1.13.0+kotlin.2.1.0";
+pub const KOTLIN_FOR_FORGE: &str = "5.7.0";
+
 /// All supported Minecraft versions and their known-good dependency versions.
 ///
 /// Versions are ordered oldest → newest. When multiple MC versions share the
@@ -22,6 +37,8 @@ pub struct VersionMeta {
 ///
 /// NeoForge versions marked with `-beta` indicate that no stable release exists
 /// for that MC version yet. The CLI will still use them as defaults.
+///
+/// Forge skipped 1.21.2, so that entry has no Forge version.
 pub const VERSION_TABLE: &[VersionMeta] = &[
     // --- 1.21.1 ---
     VersionMeta {
@@ -29,7 +46,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.116.9+1.21.1",
         neoforge: "21.1.221",
-
+        forge: "52.1.0",
+        architectury_api: "13.0.8",
     },
     // --- 1.21.2 ---
     // NeoForge only has beta releases for this MC version.
@@ -38,7 +56,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.106.1+1.21.2",
         neoforge: "21.2.1-beta",
-
+        forge: "",
+        architectury_api: "14.0.4",
     },
     // --- 1.21.3 ---
     VersionMeta {
@@ -46,7 +65,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.114.1+1.21.3",
         neoforge: "21.3.96",
-
+        forge: "53.1.0",
+        architectury_api: "14.0.4",
     },
     // --- 1.21.4 ---
     VersionMeta {
@@ -54,7 +74,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.119.4+1.21.4",
         neoforge: "21.4.157",
-
+        forge: "54.1.0",
+        architectury_api: "15.0.3",
     },
     // --- 1.21.5 ---
     VersionMeta {
@@ -62,7 +83,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.128.2+1.21.5",
         neoforge: "21.5.97",
-
+        forge: "55.0.24",
+        architectury_api: "16.1.4",
     },
     // --- 1.21.6 ---
     // NeoForge only has beta releases for this MC version.
@@ -71,7 +93,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.128.2+1.21.6",
         neoforge: "21.6.20-beta",
-
+        forge: "56.0.9",
+        architectury_api: "17.0.6",
     },
     // --- 1.21.7 ---
     // NeoForge only has beta releases for this MC version.
@@ -80,7 +103,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.128.2+1.21.7",
         neoforge: "21.7.25-beta",
-
+        forge: "57.0.3",
+        architectury_api: "17.0.8",
     },
     // --- 1.21.8 ---
     VersionMeta {
@@ -88,7 +112,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.136.1+1.21.8",
         neoforge: "21.8.53",
-
+        forge: "58.1.0",
+        architectury_api: "17.0.8",
     },
     // --- 1.21.9 ---
     // NeoForge only has beta releases for this MC version.
@@ -97,7 +122,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.134.1+1.21.9",
         neoforge: "21.9.16-beta",
-
+        forge: "59.0.5",
+        architectury_api: "18.0.3",
     },
     // --- 1.21.10 ---
     VersionMeta {
@@ -105,7 +131,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.138.4+1.21.10",
         neoforge: "21.10.64",
-
+        forge: "60.1.0",
+        architectury_api: "18.0.5",
     },
     // --- 1.21.11 ---
     // NeoForge only has beta releases for this MC version.
@@ -114,7 +141,8 @@ pub const VERSION_TABLE: &[VersionMeta] = &[
         fabric_loader: "0.18.5",
         fabric_api: "0.141.3+1.21.11",
         neoforge: "21.11.40-beta",
-
+        forge: "61.0.1",
+        architectury_api: "19.0.1",
     },
 ];
 
@@ -166,12 +194,46 @@ pub fn targets_to_ranges(targets: &[&str]) -> Vec<crate::config::VersionTarget>
             fabric_loader: meta.fabric_loader.to_string(),
             fabric_api: meta.fabric_api.to_string(),
             neoforge: meta.neoforge.to_string(),
+            // Only recorded for projects that enable Forge, Quilt, or Architectury
+            forge: String::new(),
+            quilt_loader: String::new(),
+            architectury_api: String::new(),
         });
     }
 
     result
 }
 
+/// Order two dependency version strings, e.g. `0.119.4+1.21.4` or `21.6.20-beta`.
+///
+/// Build metadata after `+` is ignored, numeric components compare as numbers,
+/// and a pre-release suffix (`-beta`, `-pre1`) sorts before the release.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn split(v: &str) -> (Vec<u64>, bool) {
+        let v = v.split('+').next().unwrap_or(v);
+        let (core, pre) = match v.split_once('-') {
+            Some((core, _)) => (core, true),
+            None => (v, false),
+        };
+        let parts = core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+        (parts, pre)
+    }
+    let (a_parts, a_pre) = split(a);
+    let (b_parts, b_pre) = split(b);
+    let len = a_parts.len().max(b_parts.len());
+    for i in 0..len {
+        let ord = a_parts
+            .get(i)
+            .unwrap_or(&0)
+            .cmp(b_parts.get(i).unwrap_or(&0));
+        if ord.is_ne() {
+            return ord;
+        }
+    }
+    // A release is newer than its own pre-releases
+    b_pre.cmp(&a_pre)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,4 +333,27 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering::*;
+        assert_eq!(compare_versions("1.21.10", "1.21.9"), Greater);
+        assert_eq!(compare_versions("1.21", "1.21.0"), Equal);
+        assert_eq!(compare_versions("0.141.3+1.21.11", "0.119.4+1.21.4"), Greater);
+        assert_eq!(compare_versions("21.6.20-beta", "21.6.20"), Less);
+        assert_eq!(compare_versions("21.11.40-beta", "21.10.64"), Greater);
+    }
+
+    #[test]
+    fn test_forge_versions() {
+        assert_eq!(get_version_meta("1.21.4").unwrap().forge, "54.1.0");
+        assert!(get_version_meta("1.21.2").unwrap().forge.is_empty());
+        // Forge bumps its major version with every Minecraft release
+        let majors: Vec<u32> = VERSION_TABLE
+            .iter()
+            .filter(|m| !m.forge.is_empty())
+            .map(|m| m.forge.split('.').next().unwrap().parse().unwrap())
+            .collect();
+        assert!(majors.windows(2).all(|w| w[1] == w[0] + 1));
+    }
 }
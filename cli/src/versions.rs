@@ -3,6 +3,16 @@
 use crate::error::McmodError;
 use crate::util::http_get;
 
+// Metadata endpoints; mcmod.lock records which one a version came from.
+pub const FABRIC_GAME_META: &str = "https://meta.fabricmc.net/v2/versions/game";
+pub const FABRIC_LOADER_META: &str = "https://meta.fabricmc.net/v2/versions/loader";
+pub const FABRIC_API_MAVEN: &str = "https://maven.fabricmc.net/net/fabricmc/fabric-api/fabric-api/maven-metadata.xml";
+pub const NEOFORGE_MAVEN: &str = "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
+pub const FORGE_MAVEN: &str = "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
+pub const QUILT_LOADER_META: &str = "https://meta.quiltmc.org/v3/versions/loader";
+pub const FABRIC_INSTALLER_META: &str = "https://meta.fabricmc.net/v2/versions/installer";
+pub const MOJANG_VERSION_MANIFEST: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
 /// Parse `<version>` tags from Maven metadata XML, returning all version strings.
 fn parse_maven_versions(xml: &str) -> Vec<String> {
     xml.lines()
@@ -34,66 +44,141 @@ fn fetch_stable_from_fabric_meta(endpoint: &str, error_msg: &str) -> Result<Stri
 
 /// Fetch latest stable Minecraft version from Fabric Meta API.
 pub fn fetch_minecraft_version() -> Result<String, McmodError> {
-    fetch_stable_from_fabric_meta(
-        "https://meta.fabricmc.net/v2/versions/game",
-        "No stable Minecraft version found",
-    )
+    fetch_stable_from_fabric_meta(FABRIC_GAME_META, "No stable Minecraft version found")
 }
 
 /// Fetch latest stable Fabric Loader version from Fabric Meta API.
 pub fn fetch_fabric_loader_version() -> Result<String, McmodError> {
-    fetch_stable_from_fabric_meta(
-        "https://meta.fabricmc.net/v2/versions/loader",
-        "No stable Fabric Loader version found",
-    )
+    fetch_stable_from_fabric_meta(FABRIC_LOADER_META, "No stable Fabric Loader version found")
+}
+
+/// Fetch latest stable Fabric installer version (the server launcher's) from Fabric Meta API.
+pub fn fetch_fabric_installer_version() -> Result<String, McmodError> {
+    fetch_stable_from_fabric_meta(FABRIC_INSTALLER_META, "No stable Fabric installer version found")
+}
+
+/// File in the cache directory holding the last fetched release list, one per line.
+const RELEASES_CACHE: &str = "minecraft-releases.txt";
+
+/// Fetch every Minecraft release (no snapshots) from Mojang's version manifest, newest first.
+/// The list is cached for shell completion.
+pub fn fetch_minecraft_releases() -> Result<Vec<String>, McmodError> {
+    let body = http_get(MOJANG_VERSION_MANIFEST)?;
+    let releases = parse_mojang_releases(&body)?;
+    if let Ok(dir) = crate::global_config::cache_dir() {
+        // Completion falls back to the built-in list, so a failed write is harmless
+        let _ = write_release_cache(&dir, &releases);
+    }
+    Ok(releases)
+}
+
+/// The release list cached by the last [`fetch_minecraft_releases`], newest
+/// first, or `None` if nothing has been fetched yet.
+pub fn cached_minecraft_releases() -> Option<Vec<String>> {
+    read_release_cache(&crate::global_config::cache_dir().ok()?)
+}
+
+fn write_release_cache(dir: &std::path::Path, releases: &[String]) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join(RELEASES_CACHE), releases.join("\n") + "\n")
+}
+
+fn read_release_cache(dir: &std::path::Path) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(dir.join(RELEASES_CACHE)).ok()?;
+    let releases: Vec<String> = content.lines().filter(|l| !l.trim().is_empty()).map(str::to_string).collect();
+    (!releases.is_empty()).then_some(releases)
+}
+
+/// Release ids from a Mojang version manifest, in manifest (newest first) order.
+fn parse_mojang_releases(json: &str) -> Result<Vec<String>, McmodError> {
+    let manifest: serde_json::Value = serde_json::from_str(json)?;
+    let versions = manifest["versions"]
+        .as_array()
+        .ok_or_else(|| McmodError::Other("Mojang version manifest has no versions list".to_string()))?;
+    Ok(versions
+        .iter()
+        .filter(|v| v["type"] == "release")
+        .filter_map(|v| v["id"].as_str().map(str::to_string))
+        .collect())
+}
+
+/// Fetch every published Fabric API version from Maven metadata, oldest first.
+pub fn fetch_fabric_api_versions() -> Result<Vec<String>, McmodError> {
+    Ok(parse_maven_versions(&http_get(FABRIC_API_MAVEN)?))
 }
 
 /// Fetch latest Fabric API version for the given Minecraft version from Maven metadata.
 pub fn fetch_fabric_api_version(mc_version: &str) -> Result<String, McmodError> {
-    let url = "https://maven.fabricmc.net/net/fabricmc/fabric-api/fabric-api/maven-metadata.xml";
-    let body = http_get(url)?;
-    let suffix = format!("+{mc_version}");
+    latest_fabric_api_for(&fetch_fabric_api_versions()?, mc_version)
+        .ok_or_else(|| McmodError::Other(format!("No Fabric API version found for {mc_version}")))
+}
 
-    let matching: Vec<String> = parse_maven_versions(&body)
-        .into_iter()
-        .filter(|v| v.ends_with(&suffix))
-        .collect();
+/// The newest Fabric API built for `mc_version` (published as `<api>+<minecraft>`).
+pub fn latest_fabric_api_for(versions: &[String], mc_version: &str) -> Option<String> {
+    let suffix = format!("+{mc_version}");
+    versions.iter().rev().find(|v| v.ends_with(&suffix)).cloned()
+}
 
-    matching
-        .last()
-        .cloned()
-        .ok_or_else(|| McmodError::Other(format!("No Fabric API version found for {mc_version}")))
+/// Fetch every published NeoForge version from Maven metadata, oldest first.
+pub fn fetch_neoforge_versions() -> Result<Vec<String>, McmodError> {
+    Ok(parse_maven_versions(&http_get(NEOFORGE_MAVEN)?))
 }
 
 /// Fetch latest NeoForge version for the given Minecraft version from Maven metadata.
 pub fn fetch_neoforge_version(mc_version: &str) -> Result<String, McmodError> {
-    let url = "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
-    let body = http_get(url)?;
-
-    // NeoForge versions follow the pattern {mc_major}.{mc_minor}.xxx
-    // For MC 1.21.4, NeoForge versions are 21.4.xxx
-    let parts: Vec<&str> = mc_version.splitn(3, '.').collect();
-    let prefix = if parts.len() >= 3 {
-        format!("{}.{}.", parts[1], parts[2])
-    } else if parts.len() == 2 {
-        format!("{}.", parts[1])
-    } else {
+    if mc_version.split('.').count() < 2 {
         return Err(McmodError::Other(format!(
             "Cannot parse Minecraft version: {mc_version}"
         )));
-    };
-
-    let matching: Vec<String> = parse_maven_versions(&body)
-        .into_iter()
-        .filter(|v| v.starts_with(&prefix))
-        .collect();
-
-    matching
-        .last()
-        .cloned()
-        .ok_or_else(|| {
-            McmodError::Other(format!("No NeoForge version found for {mc_version}"))
-        })
+    }
+    latest_neoforge_for(&fetch_neoforge_versions()?, mc_version).ok_or_else(|| {
+        McmodError::Other(format!("No NeoForge version found for {mc_version}"))
+    })
+}
+
+/// The newest NeoForge built for `mc_version`. NeoForge versions follow the
+/// pattern {mc_minor}.{mc_patch}.xxx: for MC 1.21.4 they are 21.4.xxx, and
+/// for MC 1.21 they are 21.0.xxx.
+pub fn latest_neoforge_for(versions: &[String], mc_version: &str) -> Option<String> {
+    let mut parts = mc_version.split('.').skip(1);
+    let prefix = format!("{}.{}.", parts.next()?, parts.next().unwrap_or("0"));
+    versions.iter().rev().find(|v| v.starts_with(&prefix)).cloned()
+}
+
+/// Fetch latest Forge version for the given Minecraft version from Maven metadata.
+/// Forge publishes `<minecraft>-<forge>` (e.g. `1.21.4-54.1.0`); the Minecraft
+/// prefix is stripped from the result.
+pub fn fetch_forge_version(mc_version: &str) -> Result<String, McmodError> {
+    let body = http_get(FORGE_MAVEN)?;
+    latest_forge_for(&parse_maven_versions(&body), mc_version)
+        .ok_or_else(|| McmodError::Other(format!("No Forge version found for {mc_version}")))
+}
+
+fn latest_forge_for(versions: &[String], mc_version: &str) -> Option<String> {
+    let prefix = format!("{mc_version}-");
+    versions
+        .iter()
+        .filter_map(|v| v.strip_prefix(&prefix))
+        .max_by(|a, b| crate::version_meta::compare_versions(a, b))
+        .map(str::to_string)
+}
+
+/// Fetch the latest Quilt Loader release from the Quilt Meta API.
+pub fn fetch_quilt_loader_version() -> Result<String, McmodError> {
+    let body = http_get(QUILT_LOADER_META)?;
+    let versions: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    latest_quilt_release(&versions)
+        .ok_or_else(|| McmodError::Other("No Quilt Loader release found".to_string()))
+}
+
+/// Quilt Meta has no `stable` flag; betas carry a `-beta.N` suffix. Entries
+/// are listed newest first.
+fn latest_quilt_release(versions: &[serde_json::Value]) -> Option<String> {
+    versions
+        .iter()
+        .filter_map(|v| v.get("version").and_then(|v| v.as_str()))
+        .find(|v| !v.contains('-'))
+        .map(str::to_string)
 }
 
 #[cfg(test)]
@@ -139,4 +224,70 @@ mod tests {
         let versions = parse_maven_versions(xml);
         assert_eq!(versions, vec!["1.0.0"]);
     }
+
+    #[test]
+    fn test_parse_mojang_releases() {
+        let json = r#"{"latest": {"release": "1.21.4"}, "versions": [
+            {"id": "25w02a", "type": "snapshot"},
+            {"id": "1.21.4", "type": "release"},
+            {"id": "1.21.4-rc3", "type": "snapshot"},
+            {"id": "1.21.3", "type": "release"}
+        ]}"#;
+        assert_eq!(parse_mojang_releases(json).unwrap(), ["1.21.4", "1.21.3"]);
+        assert!(parse_mojang_releases("{}").is_err());
+    }
+
+    #[test]
+    fn test_release_cache() {
+        let dir = std::env::temp_dir().join(format!("mcmod_release_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(read_release_cache(&dir), None);
+
+        let releases = vec!["1.21.5".to_string(), "1.21.4".to_string()];
+        write_release_cache(&dir, &releases).unwrap();
+        assert_eq!(read_release_cache(&dir), Some(releases));
+
+        write_release_cache(&dir, &[]).unwrap();
+        assert_eq!(read_release_cache(&dir), None, "an empty list isn't a usable cache");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_latest_for_minecraft() {
+        let fabric: Vec<String> = ["0.110.0+1.21.4", "0.110.5+1.21.4", "0.114.0+1.21.5"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(latest_fabric_api_for(&fabric, "1.21.4").as_deref(), Some("0.110.5+1.21.4"));
+        assert_eq!(latest_fabric_api_for(&fabric, "1.21.3"), None);
+
+        let neoforge: Vec<String> = ["21.0.167", "21.1.90", "21.4.3-beta", "21.4.10-beta"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(latest_neoforge_for(&neoforge, "1.21.4").as_deref(), Some("21.4.10-beta"));
+        assert_eq!(latest_neoforge_for(&neoforge, "1.21").as_deref(), Some("21.0.167"));
+        assert_eq!(latest_neoforge_for(&neoforge, "1.21.2"), None);
+    }
+
+    #[test]
+    fn test_latest_forge_for() {
+        let versions: Vec<String> = ["1.21.1-52.0.9", "1.21.1-52.1.0", "1.21.10-60.0.1", "1.21.4-54.1.0"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(latest_forge_for(&versions, "1.21.1").as_deref(), Some("52.1.0"));
+        assert_eq!(latest_forge_for(&versions, "1.21.10").as_deref(), Some("60.0.1"));
+        assert_eq!(latest_forge_for(&versions, "1.21.2"), None);
+    }
+
+    #[test]
+    fn test_latest_quilt_release() {
+        let versions: Vec<serde_json::Value> = serde_json::from_str(
+            r#"[{"version": "0.30.0-beta.1"}, {"version": "0.29.2"}, {"version": "0.29.1"}]"#,
+        )
+        .unwrap();
+        assert_eq!(latest_quilt_release(&versions).as_deref(), Some("0.29.2"));
+        assert_eq!(latest_quilt_release(&[]), None);
+    }
 }
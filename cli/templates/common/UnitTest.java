@@ -0,0 +1,12 @@
+package {{package}};
+
+import org.junit.jupiter.api.Test;
+import static org.junit.jupiter.api.Assertions.*;
+
+public class {{class_name}}Test {
+    @Test
+    void modIdIsValid() {
+        assertEquals("{{mod_id}}", {{class_name}}.MOD_ID);
+        assertTrue({{class_name}}.MOD_ID.matches("[a-z][a-z0-9_]*"));
+    }
+}
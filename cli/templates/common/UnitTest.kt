@@ -0,0 +1,12 @@
+package {{package}}
+
+import org.junit.jupiter.api.Assertions.*
+import org.junit.jupiter.api.Test
+
+class {{class_name}}Test {
+    @Test
+    fun modIdIsValid() {
+        assertEquals("{{mod_id}}", {{class_name}}.MOD_ID)
+        assertTrue({{class_name}}.MOD_ID.matches(Regex("[a-z][a-z0-9_]*")))
+    }
+}
@@ -0,0 +1,7 @@
+# mcmod bash completion
+# Install: mcmod completions bash > ~/.local/share/bash-completion/completions/mcmod
+_mcmod() {
+    local IFS=$'\n'
+    COMPREPLY=($(mcmod __complete "${COMP_WORDS[@]:0:$((COMP_CWORD + 1))}" 2>/dev/null))
+}
+complete -o default -F _mcmod mcmod
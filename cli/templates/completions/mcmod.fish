@@ -0,0 +1,6 @@
+# mcmod fish completion
+# Install: mcmod completions fish > ~/.config/fish/completions/mcmod.fish
+function __mcmod_complete
+    mcmod __complete (commandline -opc) (commandline -ct) 2>/dev/null
+end
+complete -c mcmod -f -a '(__mcmod_complete)'
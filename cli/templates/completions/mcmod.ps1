@@ -0,0 +1,10 @@
+# mcmod PowerShell completion
+# Install: mcmod completions powershell >> $PROFILE
+Register-ArgumentCompleter -Native -CommandName mcmod -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements | ForEach-Object { $_.ToString() })
+    if ($wordToComplete -eq '') { $words += '""' }
+    mcmod __complete @words 2>$null | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+}
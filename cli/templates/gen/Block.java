@@ -0,0 +1,13 @@
+package {{gen_package}};
+
+import net.minecraft.world.level.block.Block;
+import net.minecraft.world.level.block.state.BlockBehaviour;
+
+/**
+ * The {@code {{mod_id}}:{{gen_id}}} block, registered with its block item in {@code ModBlocks}.
+ */
+public class {{gen_class}} extends Block {
+    public {{gen_class}}(BlockBehaviour.Properties properties) {
+        super(properties);
+    }
+}
@@ -0,0 +1,7 @@
+package {{gen_package}}
+
+import net.minecraft.world.level.block.Block
+import net.minecraft.world.level.block.state.BlockBehaviour
+
+/** The `{{mod_id}}:{{gen_id}}` block, registered with its block item in `ModBlocks`. */
+class {{gen_class}}(properties: BlockBehaviour.Properties) : Block(properties)
@@ -0,0 +1,19 @@
+package {{gen_package}};
+
+import {{package}}.registry.ModBlockEntities;
+import net.minecraft.core.BlockPos;
+import net.minecraft.world.level.block.entity.BlockEntity;
+import net.minecraft.world.level.block.state.BlockState;
+{{gen_storage_imports}}
+
+/**
+ * The state of a placed {@code {{mod_id}}:{{gen_id}}} block, registered in {@code ModBlockEntities}. Save and load
+ * its fields in {@code saveAdditional} and {@code loadAdditional}.
+ */
+public class {{gen_class}} extends BlockEntity {
+    public {{gen_class}}(BlockPos pos, BlockState state) {
+        super(ModBlockEntities.{{gen_field_name}}.get(), pos, state);
+    }
+
+{{gen_storage_methods}}
+}
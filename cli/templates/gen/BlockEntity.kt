@@ -0,0 +1,15 @@
+package {{gen_package}}
+
+import {{package}}.registry.ModBlockEntities
+import net.minecraft.core.BlockPos
+import net.minecraft.world.level.block.entity.BlockEntity
+import net.minecraft.world.level.block.state.BlockState
+{{gen_storage_imports}}
+
+/**
+ * The state of a placed `{{mod_id}}:{{gen_id}}` block, registered in `ModBlockEntities`. Save and load its fields in
+ * [saveAdditional] and [loadAdditional].
+ */
+class {{gen_class}}(pos: BlockPos, state: BlockState) : BlockEntity(ModBlockEntities.{{gen_field_name}}.get(), pos, state) {
+{{gen_storage_methods}}
+}
@@ -0,0 +1,12 @@
+package {{gen_package}};
+
+{{gen_entity_import}}
+{{gen_renderer_imports}}
+import net.minecraft.client.renderer.blockentity.BlockEntityRenderer;
+import net.minecraft.client.renderer.blockentity.BlockEntityRendererProvider;
+
+/**
+ * Draws each placed {@code {{mod_id}}:{{gen_id}}} block's {@link {{gen_entity_class}}}, registered in
+ * {@code ModBlockEntityRenderers}.
+ */
+{{gen_renderer}}
@@ -0,0 +1,9 @@
+package {{gen_package}}
+
+{{gen_entity_import}}
+{{gen_renderer_imports}}
+import net.minecraft.client.renderer.blockentity.BlockEntityRenderer
+import net.minecraft.client.renderer.blockentity.BlockEntityRendererProvider
+
+/** Draws each placed `{{mod_id}}:{{gen_id}}` block's [{{gen_entity_class}}], registered in `ModBlockEntityRenderers`. */
+{{gen_renderer}}
@@ -0,0 +1,24 @@
+package {{gen_package}};
+
+{{gen_entity_import}}
+import net.minecraft.core.BlockPos;
+import net.minecraft.world.level.block.Block;
+import net.minecraft.world.level.block.EntityBlock;
+import net.minecraft.world.level.block.entity.BlockEntity;
+import net.minecraft.world.level.block.state.BlockBehaviour;
+import net.minecraft.world.level.block.state.BlockState;
+
+/**
+ * The {@code {{mod_id}}:{{gen_id}}} block, registered with its block item in {@code ModBlocks}. Each placed one
+ * holds a {@link {{gen_entity_class}}}.
+ */
+public class {{gen_class}} extends Block implements EntityBlock {
+    public {{gen_class}}(BlockBehaviour.Properties properties) {
+        super(properties);
+    }
+
+    @Override
+    public BlockEntity newBlockEntity(BlockPos pos, BlockState state) {
+        return new {{gen_entity_class}}(pos, state);
+    }
+}
@@ -0,0 +1,14 @@
+package {{gen_package}}
+
+{{gen_entity_import}}
+import net.minecraft.core.BlockPos
+import net.minecraft.world.level.block.Block
+import net.minecraft.world.level.block.EntityBlock
+import net.minecraft.world.level.block.entity.BlockEntity
+import net.minecraft.world.level.block.state.BlockBehaviour
+import net.minecraft.world.level.block.state.BlockState
+
+/** The `{{mod_id}}:{{gen_id}}` block, registered with its block item in `ModBlocks`. Each placed one holds a [{{gen_entity_class}}]. */
+class {{gen_class}}(properties: BlockBehaviour.Properties) : Block(properties), EntityBlock {
+    override fun newBlockEntity(pos: BlockPos, state: BlockState): BlockEntity = {{gen_entity_class}}(pos, state)
+}
@@ -0,0 +1,17 @@
+package {{gen_package}};
+{{#subpackage}}
+
+import {{package}}.{{class_name}};
+{{/subpackage}}
+
+/**
+ * Set up from {@link {{class_name}}#init()}, which runs on every loader.
+ */
+public final class {{gen_class}} {
+    private {{gen_class}}() {
+    }
+
+    public static void init() {
+        {{class_name}}.LOGGER.debug("Initializing {{gen_class}}");
+    }
+}
@@ -0,0 +1,12 @@
+package {{gen_package}}
+{{#subpackage}}
+
+import {{package}}.{{class_name}}
+{{/subpackage}}
+
+/** Set up from [{{class_name}}.init], which runs on every loader. */
+object {{gen_class}} {
+    fun init() {
+        {{class_name}}.LOGGER.debug("Initializing {{gen_class}}")
+    }
+}
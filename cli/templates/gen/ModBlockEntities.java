@@ -0,0 +1,83 @@
+package {{gen_package}};
+
+import {{package}}.{{class_name}};
+{{gen_entity_import}}
+import java.util.Set;
+import java.util.function.Supplier;
+import net.minecraft.core.registries.Registries;
+import net.minecraft.resources.ResourceKey;
+{{gen_identifier_import}}
+import net.minecraft.world.level.block.Block;
+import net.minecraft.world.level.block.entity.BlockEntity;
+import net.minecraft.world.level.block.entity.BlockEntityType;
+
+/*? if neoforge {*/
+/*import net.neoforged.bus.api.IEventBus;
+import net.neoforged.fml.ModLoadingContext;
+import net.neoforged.neoforge.registries.DeferredRegister;
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.eventbus.api.IEventBus;
+import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext;
+import net.minecraftforge.registries.DeferredRegister;
+import net.minecraftforge.registries.ForgeRegistries;
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry;
+import net.minecraft.core.registries.BuiltInRegistries;
+/*?}*/
+
+/**
+ * The mod's block entity types, registered from {@link {{class_name}}#init()} after {@code ModBlocks}.
+ * {@code mcmod gen block-entity} adds new ones.
+ */
+public final class {{gen_class}} {
+    /*? if neoforge {*/
+    /*private static final DeferredRegister<BlockEntityType<?>> BLOCK_ENTITY_TYPES = DeferredRegister.create(Registries.BLOCK_ENTITY_TYPE, {{class_name}}.MOD_ID);
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private static final DeferredRegister<BlockEntityType<?>> BLOCK_ENTITY_TYPES = DeferredRegister.create(ForgeRegistries.BLOCK_ENTITY_TYPES, {{class_name}}.MOD_ID);
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    private {{gen_class}}() {
+    }
+
+    public static void init() {
+        /*? if neoforge {*/
+        /*IEventBus bus = ModLoadingContext.get().getActiveContainer().getEventBus();
+        BLOCK_ENTITY_TYPES.register(bus);
+{{#forge}}
+        *//*?} elif forge {*/
+        /*IEventBus bus = FMLJavaModLoadingContext.get().getModEventBus();
+        BLOCK_ENTITY_TYPES.register(bus);
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered block entity types");
+    }
+
+    /** Register the type of the block entities {@code factory} makes for {@code block} as {@code <mod_id>:<id>}. */
+    private static <T extends BlockEntity> Supplier<BlockEntityType<T>> register(
+            String id, BlockEntityType.BlockEntitySupplier<T> factory, Supplier<? extends Block> block) {
+        /*? if neoforge {*/
+        /*return BLOCK_ENTITY_TYPES.register(id, () -> type(factory, block.get()));
+{{#forge}}
+        *//*?} elif forge {*/
+        /*return BLOCK_ENTITY_TYPES.register(id, () -> type(factory, block.get()));
+{{/forge}}
+        *//*?} else {*/
+        BlockEntityType<T> type = Registry.register(BuiltInRegistries.BLOCK_ENTITY_TYPE, key(id), type(factory, block.get()));
+        return () -> type;
+        /*?}*/
+    }
+
+    private static ResourceKey<BlockEntityType<?>> key(String id) {
+{{gen_key}}
+    }
+
+    private static <T extends BlockEntity> BlockEntityType<T> type(BlockEntityType.BlockEntitySupplier<T> factory, Block block) {
+{{gen_type}}
+    }
+}
@@ -0,0 +1,77 @@
+package {{gen_package}}
+
+import {{package}}.{{class_name}}
+{{gen_entity_import}}
+import java.util.function.Supplier
+import net.minecraft.core.registries.Registries
+import net.minecraft.resources.ResourceKey
+{{gen_identifier_import}}
+import net.minecraft.world.level.block.Block
+import net.minecraft.world.level.block.entity.BlockEntity
+import net.minecraft.world.level.block.entity.BlockEntityType
+
+/*? if neoforge {*/
+/*import net.neoforged.fml.ModLoadingContext
+import net.neoforged.neoforge.registries.DeferredRegister
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext
+import net.minecraftforge.registries.DeferredRegister
+import net.minecraftforge.registries.ForgeRegistries
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry
+import net.minecraft.core.registries.BuiltInRegistries
+/*?}*/
+
+/** The mod's block entity types, registered from [{{class_name}}.init] after `ModBlocks`. `mcmod gen block-entity` adds new ones. */
+object {{gen_class}} {
+    /*? if neoforge {*/
+    /*private val BLOCK_ENTITY_TYPES: DeferredRegister<BlockEntityType<*>> = DeferredRegister.create(Registries.BLOCK_ENTITY_TYPE, {{class_name}}.MOD_ID)
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private val BLOCK_ENTITY_TYPES: DeferredRegister<BlockEntityType<*>> = DeferredRegister.create(ForgeRegistries.BLOCK_ENTITY_TYPES, {{class_name}}.MOD_ID)
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    fun init() {
+        /*? if neoforge {*/
+        /*val bus = ModLoadingContext.get().activeContainer.eventBus!!
+        BLOCK_ENTITY_TYPES.register(bus)
+{{#forge}}
+        *//*?} elif forge {*/
+        /*val bus = FMLJavaModLoadingContext.get().modEventBus
+        BLOCK_ENTITY_TYPES.register(bus)
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered block entity types")
+    }
+
+    /** Register the type of the block entities [factory] makes for [block] as `<mod_id>:<id>`. */
+    private fun <T : BlockEntity> register(
+        id: String,
+        factory: BlockEntityType.BlockEntitySupplier<T>,
+        block: Supplier<out Block>,
+    ): Supplier<BlockEntityType<T>> {
+        /*? if neoforge {*/
+        /*return BLOCK_ENTITY_TYPES.register(id, Supplier { type(factory, block.get()) })
+{{#forge}}
+        *//*?} elif forge {*/
+        /*return BLOCK_ENTITY_TYPES.register(id, Supplier { type(factory, block.get()) })
+{{/forge}}
+        *//*?} else {*/
+        val type = Registry.register(BuiltInRegistries.BLOCK_ENTITY_TYPE, key(id), type(factory, block.get()))
+        return Supplier { type }
+        /*?}*/
+    }
+
+    private fun key(id: String): ResourceKey<BlockEntityType<*>> {
+{{gen_key}}
+    }
+
+    private fun <T : BlockEntity> type(factory: BlockEntityType.BlockEntitySupplier<T>, block: Block): BlockEntityType<T> {
+{{gen_type}}
+    }
+}
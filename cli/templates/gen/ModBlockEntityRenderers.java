@@ -0,0 +1,60 @@
+package {{gen_package}};
+
+import {{package}}.{{class_name}};
+import {{package}}.registry.ModBlockEntities;
+
+/*? if neoforge {*/
+/*import net.neoforged.api.distmarker.Dist;
+import net.neoforged.bus.api.IEventBus;
+import net.neoforged.fml.common.Mod;
+import net.neoforged.neoforge.client.event.EntityRenderersEvent;
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.api.distmarker.Dist;
+import net.minecraftforge.client.event.EntityRenderersEvent;
+import net.minecraftforge.eventbus.api.SubscribeEvent;
+import net.minecraftforge.fml.common.Mod;
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.client.renderer.blockentity.BlockEntityRenderers;
+/*?}*/
+
+/**
+ * Renderers for the mod's block entities. Only the client loads this class: NeoForge and Forge find it by its
+ * annotation, Fabric calls {@code init()} from the client entrypoint. {@code mcmod gen block-entity} adds new ones.
+ */
+/*? if neoforge {*/
+/*@Mod(value = {{class_name}}.MOD_ID, dist = Dist.CLIENT)
+public final class {{gen_class}} {
+    public {{gen_class}}(IEventBus modEventBus) {
+        modEventBus.addListener({{gen_class}}::registerRenderers);
+    }
+
+    private static void registerRenderers(EntityRenderersEvent.RegisterRenderers event) {
+{{gen_event_registration}}
+    }
+}
+{{#forge}}
+*//*?} elif forge {*/
+/*@Mod.EventBusSubscriber(modid = {{class_name}}.MOD_ID, bus = Mod.EventBusSubscriber.Bus.MOD, value = Dist.CLIENT)
+public final class {{gen_class}} {
+    private {{gen_class}}() {
+    }
+
+    @SubscribeEvent
+    public static void registerRenderers(EntityRenderersEvent.RegisterRenderers event) {
+{{gen_event_registration}}
+    }
+}
+{{/forge}}
+*//*?} else {*/
+public final class {{gen_class}} {
+    private {{gen_class}}() {
+    }
+
+    public static void init() {
+{{gen_registration}}
+        {{class_name}}.LOGGER.debug("Registered block entity renderers");
+    }
+}
+/*?}*/
@@ -0,0 +1,99 @@
+package {{gen_package}};
+
+import {{package}}.{{class_name}};
+{{gen_block_import}}
+import java.util.function.Function;
+import java.util.function.Supplier;
+import net.minecraft.core.registries.Registries;
+import net.minecraft.resources.ResourceKey;
+{{gen_identifier_import}}
+import net.minecraft.world.item.BlockItem;
+import net.minecraft.world.item.Item;
+import net.minecraft.world.level.block.Block;
+import net.minecraft.world.level.block.state.BlockBehaviour;
+
+/*? if neoforge {*/
+/*import net.neoforged.bus.api.IEventBus;
+import net.neoforged.fml.ModLoadingContext;
+import net.neoforged.neoforge.registries.DeferredRegister;
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.eventbus.api.IEventBus;
+import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext;
+import net.minecraftforge.registries.DeferredRegister;
+import net.minecraftforge.registries.ForgeRegistries;
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry;
+import net.minecraft.core.registries.BuiltInRegistries;
+/*?}*/
+
+/**
+ * The mod's blocks and their block items, registered from {@link {{class_name}}#init()}. {@code mcmod gen block} adds new ones.
+ */
+public final class {{gen_class}} {
+    /*? if neoforge {*/
+    /*private static final DeferredRegister.Blocks BLOCKS = DeferredRegister.createBlocks({{class_name}}.MOD_ID);
+    private static final DeferredRegister.Items ITEMS = DeferredRegister.createItems({{class_name}}.MOD_ID);
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private static final DeferredRegister<Block> BLOCKS = DeferredRegister.create(ForgeRegistries.BLOCKS, {{class_name}}.MOD_ID);
+    private static final DeferredRegister<Item> ITEMS = DeferredRegister.create(ForgeRegistries.ITEMS, {{class_name}}.MOD_ID);
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    private {{gen_class}}() {
+    }
+
+    public static void init() {
+        /*? if neoforge {*/
+        /*IEventBus bus = ModLoadingContext.get().getActiveContainer().getEventBus();
+        BLOCKS.register(bus);
+        ITEMS.register(bus);
+{{#forge}}
+        *//*?} elif forge {*/
+        /*IEventBus bus = FMLJavaModLoadingContext.get().getModEventBus();
+        BLOCKS.register(bus);
+        ITEMS.register(bus);
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered blocks");
+    }
+
+    /** Register a block made by {@code factory} and its block item, both as {@code <mod_id>:<id>}. */
+    private static <B extends Block> Supplier<B> register(String id, Function<BlockBehaviour.Properties, B> factory) {
+        /*? if neoforge {*/
+        /*Supplier<B> block = BLOCKS.register(id, () -> factory.apply(blockProperties(blockKey(id))));
+        ITEMS.register(id, () -> new BlockItem(block.get(), itemProperties(itemKey(id))));
+        return block;
+{{#forge}}
+        *//*?} elif forge {*/
+        /*Supplier<B> block = BLOCKS.register(id, () -> factory.apply(blockProperties(blockKey(id))));
+        ITEMS.register(id, () -> new BlockItem(block.get(), itemProperties(itemKey(id))));
+        return block;
+{{/forge}}
+        *//*?} else {*/
+        B block = Registry.register(BuiltInRegistries.BLOCK, blockKey(id), factory.apply(blockProperties(blockKey(id))));
+        Registry.register(BuiltInRegistries.ITEM, itemKey(id), new BlockItem(block, itemProperties(itemKey(id))));
+        return () -> block;
+        /*?}*/
+    }
+
+    private static ResourceKey<Block> blockKey(String id) {
+{{gen_block_key}}
+    }
+
+    private static ResourceKey<Item> itemKey(String id) {
+{{gen_item_key}}
+    }
+
+    private static BlockBehaviour.Properties blockProperties(ResourceKey<Block> key) {
+{{gen_block_properties}}
+    }
+
+    private static Item.Properties itemProperties(ResourceKey<Item> key) {
+{{gen_item_properties}}
+    }
+}
@@ -0,0 +1,91 @@
+package {{gen_package}}
+
+import {{package}}.{{class_name}}
+{{gen_block_import}}
+import java.util.function.Supplier
+import net.minecraft.core.registries.Registries
+import net.minecraft.resources.ResourceKey
+{{gen_identifier_import}}
+import net.minecraft.world.item.BlockItem
+import net.minecraft.world.item.Item
+import net.minecraft.world.level.block.Block
+import net.minecraft.world.level.block.state.BlockBehaviour
+
+/*? if neoforge {*/
+/*import net.neoforged.fml.ModLoadingContext
+import net.neoforged.neoforge.registries.DeferredRegister
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext
+import net.minecraftforge.registries.DeferredRegister
+import net.minecraftforge.registries.ForgeRegistries
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry
+import net.minecraft.core.registries.BuiltInRegistries
+/*?}*/
+
+/** The mod's blocks and their block items, registered from [{{class_name}}.init]. `mcmod gen block` adds new ones. */
+object {{gen_class}} {
+    /*? if neoforge {*/
+    /*private val BLOCKS: DeferredRegister.Blocks = DeferredRegister.createBlocks({{class_name}}.MOD_ID)
+    private val ITEMS: DeferredRegister.Items = DeferredRegister.createItems({{class_name}}.MOD_ID)
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private val BLOCKS: DeferredRegister<Block> = DeferredRegister.create(ForgeRegistries.BLOCKS, {{class_name}}.MOD_ID)
+    private val ITEMS: DeferredRegister<Item> = DeferredRegister.create(ForgeRegistries.ITEMS, {{class_name}}.MOD_ID)
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    fun init() {
+        /*? if neoforge {*/
+        /*val bus = ModLoadingContext.get().activeContainer.eventBus!!
+        BLOCKS.register(bus)
+        ITEMS.register(bus)
+{{#forge}}
+        *//*?} elif forge {*/
+        /*val bus = FMLJavaModLoadingContext.get().modEventBus
+        BLOCKS.register(bus)
+        ITEMS.register(bus)
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered blocks")
+    }
+
+    /** Register a block made by [factory] and its block item, both as `<mod_id>:<id>`. */
+    private fun <B : Block> register(id: String, factory: (BlockBehaviour.Properties) -> B): Supplier<B> {
+        /*? if neoforge {*/
+        /*val block: Supplier<B> = BLOCKS.register(id, Supplier { factory(blockProperties(blockKey(id))) })
+        ITEMS.register(id, Supplier { BlockItem(block.get(), itemProperties(itemKey(id))) })
+        return block
+{{#forge}}
+        *//*?} elif forge {*/
+        /*val block: Supplier<B> = BLOCKS.register(id, Supplier { factory(blockProperties(blockKey(id))) })
+        ITEMS.register(id, Supplier { BlockItem(block.get(), itemProperties(itemKey(id))) })
+        return block
+{{/forge}}
+        *//*?} else {*/
+        val block = Registry.register(BuiltInRegistries.BLOCK, blockKey(id), factory(blockProperties(blockKey(id))))
+        Registry.register(BuiltInRegistries.ITEM, itemKey(id), BlockItem(block, itemProperties(itemKey(id))))
+        return Supplier { block }
+        /*?}*/
+    }
+
+    private fun blockKey(id: String): ResourceKey<Block> {
+{{gen_block_key}}
+    }
+
+    private fun itemKey(id: String): ResourceKey<Item> {
+{{gen_item_key}}
+    }
+
+    private fun blockProperties(key: ResourceKey<Block>): BlockBehaviour.Properties {
+{{gen_block_properties}}
+    }
+
+    private fun itemProperties(key: ResourceKey<Item>): Item.Properties {
+{{gen_item_properties}}
+    }
+}
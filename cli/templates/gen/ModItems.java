@@ -0,0 +1,75 @@
+package {{gen_package}};
+
+import {{package}}.{{class_name}};
+import java.util.function.Function;
+import java.util.function.Supplier;
+import net.minecraft.core.registries.Registries;
+import net.minecraft.resources.ResourceKey;
+{{gen_identifier_import}}
+import net.minecraft.world.item.Item;
+
+/*? if neoforge {*/
+/*import net.neoforged.fml.ModLoadingContext;
+import net.neoforged.neoforge.registries.DeferredRegister;
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext;
+import net.minecraftforge.registries.DeferredRegister;
+import net.minecraftforge.registries.ForgeRegistries;
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry;
+import net.minecraft.core.registries.BuiltInRegistries;
+/*?}*/
+
+/**
+ * The mod's items, registered from {@link {{class_name}}#init()}. {@code mcmod gen item} adds new ones.
+ */
+public final class {{gen_class}} {
+    /*? if neoforge {*/
+    /*private static final DeferredRegister.Items ITEMS = DeferredRegister.createItems({{class_name}}.MOD_ID);
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private static final DeferredRegister<Item> ITEMS = DeferredRegister.create(ForgeRegistries.ITEMS, {{class_name}}.MOD_ID);
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    private {{gen_class}}() {
+    }
+
+    public static void init() {
+        /*? if neoforge {*/
+        /*ITEMS.register(ModLoadingContext.get().getActiveContainer().getEventBus());
+{{#forge}}
+        *//*?} elif forge {*/
+        /*ITEMS.register(FMLJavaModLoadingContext.get().getModEventBus());
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered items");
+    }
+
+    /** Register an item made by {@code factory} (usually {@code Item::new}) as {@code <mod_id>:<id>}. */
+    private static Supplier<Item> register(String id, Function<Item.Properties, Item> factory) {
+        /*? if neoforge {*/
+        /*return ITEMS.registerItem(id, factory);
+{{#forge}}
+        *//*?} elif forge {*/
+        /*return ITEMS.register(id, () -> factory.apply(properties(key(id))));
+{{/forge}}
+        *//*?} else {*/
+        ResourceKey<Item> key = key(id);
+        Item item = Registry.register(BuiltInRegistries.ITEM, key, factory.apply(properties(key)));
+        return () -> item;
+        /*?}*/
+    }
+
+    private static ResourceKey<Item> key(String id) {
+{{gen_item_key}}
+    }
+
+    private static Item.Properties properties(ResourceKey<Item> key) {
+{{gen_item_properties}}
+    }
+}
@@ -0,0 +1,69 @@
+package {{gen_package}}
+
+import {{package}}.{{class_name}}
+import java.util.function.Supplier
+import net.minecraft.core.registries.Registries
+import net.minecraft.resources.ResourceKey
+{{gen_identifier_import}}
+import net.minecraft.world.item.Item
+
+/*? if neoforge {*/
+/*import net.neoforged.fml.ModLoadingContext
+import net.neoforged.neoforge.registries.DeferredRegister
+{{#forge}}
+*//*?} elif forge {*/
+/*import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext
+import net.minecraftforge.registries.DeferredRegister
+import net.minecraftforge.registries.ForgeRegistries
+{{/forge}}
+*//*?} else {*/
+import net.minecraft.core.Registry
+import net.minecraft.core.registries.BuiltInRegistries
+/*?}*/
+
+/** The mod's items, registered from [{{class_name}}.init]. `mcmod gen item` adds new ones. */
+object {{gen_class}} {
+    /*? if neoforge {*/
+    /*private val ITEMS: DeferredRegister.Items = DeferredRegister.createItems({{class_name}}.MOD_ID)
+{{#forge}}
+    *//*?} elif forge {*/
+    /*private val ITEMS: DeferredRegister<Item> = DeferredRegister.create(ForgeRegistries.ITEMS, {{class_name}}.MOD_ID)
+{{/forge}}
+    *//*?}*/
+
+{{gen_field}}
+
+    fun init() {
+        /*? if neoforge {*/
+        /*ITEMS.register(ModLoadingContext.get().activeContainer.eventBus!!)
+{{#forge}}
+        *//*?} elif forge {*/
+        /*ITEMS.register(FMLJavaModLoadingContext.get().modEventBus)
+{{/forge}}
+        *//*?}*/
+        {{class_name}}.LOGGER.debug("Registered items")
+    }
+
+    /** Register an item made by [factory] (usually `::Item`) as `<mod_id>:<id>`. */
+    private fun register(id: String, factory: (Item.Properties) -> Item): Supplier<Item> {
+        /*? if neoforge {*/
+        /*return ITEMS.registerItem(id) { factory(it) }
+{{#forge}}
+        *//*?} elif forge {*/
+        /*return ITEMS.register(id) { factory(properties(key(id))) }
+{{/forge}}
+        *//*?} else {*/
+        val key = key(id)
+        val item = Registry.register(BuiltInRegistries.ITEM, key, factory(properties(key)))
+        return Supplier { item }
+        /*?}*/
+    }
+
+    private fun key(id: String): ResourceKey<Item> {
+{{gen_item_key}}
+    }
+
+    private fun properties(key: ResourceKey<Item>): Item.Properties {
+{{gen_item_properties}}
+    }
+}
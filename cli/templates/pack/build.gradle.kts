@@ -0,0 +1,22 @@
+plugins {
+    base
+}
+
+version = property("mod.version").toString()
+
+// Zips pack.mcmeta, pack.png, data/ and assets/ into build/libs/<id>-<version>.zip
+val packZip = tasks.register<Zip>("packZip") {
+    group = "build"
+    description = "Assembles the pack zip."
+    archiveFileName = "${property("mod.id")}-${project.version}.zip"
+    destinationDirectory = layout.buildDirectory.dir("libs")
+    from(layout.projectDirectory) {
+        include("pack.mcmeta", "pack.png", "data/**", "assets/**")
+        // Notes left in the tree by mcmod aren't part of the pack
+        exclude("**/*.md")
+    }
+}
+
+tasks.assemble {
+    dependsOn(packZip)
+}
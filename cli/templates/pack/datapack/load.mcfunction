@@ -0,0 +1,2 @@
+# Runs once when the world loads or /reload is used
+tellraw @a {"text":"{{mod_name}} loaded","color":"green"}
@@ -0,0 +1 @@
+rootProject.name = "{{mod_id}}"
@@ -0,0 +1,15 @@
+package {{package}};
+
+import org.bukkit.plugin.java.JavaPlugin;
+
+public final class {{class_name}} extends JavaPlugin {
+    @Override
+    public void onEnable() {
+        getLogger().info("{{mod_name}} enabled");
+    }
+
+    @Override
+    public void onDisable() {
+        getLogger().info("{{mod_name}} disabled");
+    }
+}
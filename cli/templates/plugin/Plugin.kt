@@ -0,0 +1,13 @@
+package {{package}}
+
+import org.bukkit.plugin.java.JavaPlugin
+
+class {{class_name}} : JavaPlugin() {
+    override fun onEnable() {
+        logger.info("{{mod_name}} enabled")
+    }
+
+    override fun onDisable() {
+        logger.info("{{mod_name}} disabled")
+    }
+}
@@ -0,0 +1,29 @@
+package {{package}};
+
+import com.google.inject.Inject;
+import org.apache.logging.log4j.Logger;
+import org.spongepowered.api.Server;
+import org.spongepowered.api.event.Listener;
+import org.spongepowered.api.event.lifecycle.StartedEngineEvent;
+import org.spongepowered.api.event.lifecycle.StoppingEngineEvent;
+import org.spongepowered.plugin.builtin.jvm.Plugin;
+
+@Plugin("{{mod_id}}")
+public final class {{class_name}} {
+    private final Logger logger;
+
+    @Inject
+    {{class_name}}(final Logger logger) {
+        this.logger = logger;
+    }
+
+    @Listener
+    public void onServerStarted(final StartedEngineEvent<Server> event) {
+        logger.info("{{mod_name}} enabled");
+    }
+
+    @Listener
+    public void onServerStopping(final StoppingEngineEvent<Server> event) {
+        logger.info("{{mod_name}} disabled");
+    }
+}
@@ -0,0 +1,22 @@
+package {{package}}
+
+import com.google.inject.Inject
+import org.apache.logging.log4j.Logger
+import org.spongepowered.api.Server
+import org.spongepowered.api.event.Listener
+import org.spongepowered.api.event.lifecycle.StartedEngineEvent
+import org.spongepowered.api.event.lifecycle.StoppingEngineEvent
+import org.spongepowered.plugin.builtin.jvm.Plugin
+
+@Plugin("{{mod_id}}")
+class {{class_name}} @Inject constructor(private val logger: Logger) {
+    @Listener
+    fun onServerStarted(event: StartedEngineEvent<Server>) {
+        logger.info("{{mod_name}} enabled")
+    }
+
+    @Listener
+    fun onServerStopping(event: StoppingEngineEvent<Server>) {
+        logger.info("{{mod_name}} disabled")
+    }
+}
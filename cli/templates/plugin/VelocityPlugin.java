@@ -0,0 +1,28 @@
+package {{package}};
+
+import com.google.inject.Inject;
+import com.velocitypowered.api.event.Subscribe;
+import com.velocitypowered.api.event.proxy.ProxyInitializeEvent;
+import com.velocitypowered.api.event.proxy.ProxyShutdownEvent;
+import com.velocitypowered.api.plugin.Plugin;
+import org.slf4j.Logger;
+
+@Plugin(id = "{{mod_id}}")
+public final class {{class_name}} {
+    private final Logger logger;
+
+    @Inject
+    public {{class_name}}(final Logger logger) {
+        this.logger = logger;
+    }
+
+    @Subscribe
+    public void onProxyInitialize(final ProxyInitializeEvent event) {
+        logger.info("{{mod_name}} enabled");
+    }
+
+    @Subscribe
+    public void onProxyShutdown(final ProxyShutdownEvent event) {
+        logger.info("{{mod_name}} disabled");
+    }
+}
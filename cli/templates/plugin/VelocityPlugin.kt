@@ -0,0 +1,21 @@
+package {{package}}
+
+import com.google.inject.Inject
+import com.velocitypowered.api.event.Subscribe
+import com.velocitypowered.api.event.proxy.ProxyInitializeEvent
+import com.velocitypowered.api.event.proxy.ProxyShutdownEvent
+import com.velocitypowered.api.plugin.Plugin
+import org.slf4j.Logger
+
+@Plugin(id = "{{mod_id}}")
+class {{class_name}} @Inject constructor(private val logger: Logger) {
+    @Subscribe
+    fun onProxyInitialize(event: ProxyInitializeEvent) {
+        logger.info("{{mod_name}} enabled")
+    }
+
+    @Subscribe
+    fun onProxyShutdown(event: ProxyShutdownEvent) {
+        logger.info("{{mod_name}} disabled")
+    }
+}
@@ -0,0 +1,36 @@
+plugins {
+    java
+{{#kotlin}}
+    kotlin("jvm") version "{{kotlin_version}}"
+{{/kotlin}}
+    id("xyz.jpenilla.run-paper") version "2.3.1"
+}
+
+group = property("mod.group").toString()
+version = property("mod.version").toString()
+
+repositories {
+    mavenCentral()
+    maven("https://repo.papermc.io/repository/maven-public/")
+}
+
+dependencies {
+    compileOnly("io.papermc.paper:paper-api:${property("paper.version")}")
+}
+
+java {
+    toolchain.languageVersion = JavaLanguageVersion.of(21)
+}
+
+tasks.processResources {
+    val props = mapOf("version" to project.version)
+    inputs.properties(props)
+    filesMatching("paper-plugin.yml") {
+        expand(props)
+    }
+}
+
+// ./gradlew runServer starts a Paper server with the plugin installed
+tasks.runServer {
+    minecraftVersion(property("paper.minecraft").toString())
+}
@@ -0,0 +1,39 @@
+// Paper plugin built alongside the mod. paperweight-userdev provides the
+// Paper server internals as well as the API.
+plugins {
+    java
+{{#kotlin}}
+    kotlin("jvm")
+{{/kotlin}}
+    id("io.papermc.paperweight.userdev") version "2.0.0-beta.19"
+    id("xyz.jpenilla.run-paper") version "2.3.1"
+}
+
+group = rootProject.property("mod.group").toString()
+version = rootProject.property("mod.version").toString()
+
+repositories {
+    mavenCentral()
+    maven("https://repo.papermc.io/repository/maven-public/")
+}
+
+dependencies {
+    paperweight.paperDevBundle("${property("paper.minecraft")}-R0.1-SNAPSHOT")
+}
+
+java {
+    toolchain.languageVersion = JavaLanguageVersion.of(21)
+}
+
+tasks.processResources {
+    val props = mapOf("version" to project.version)
+    inputs.properties(props)
+    filesMatching("paper-plugin.yml") {
+        expand(props)
+    }
+}
+
+// ./gradlew :paper:runServer starts a Paper server with the plugin installed
+tasks.runServer {
+    minecraftVersion(property("paper.minecraft").toString())
+}
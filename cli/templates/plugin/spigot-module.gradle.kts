@@ -0,0 +1,33 @@
+// Spigot plugin built alongside the mod, for servers running Spigot/Bukkit.
+// It compiles against the Spigot API only, so it also loads on Paper.
+plugins {
+    java
+{{#kotlin}}
+    kotlin("jvm")
+{{/kotlin}}
+}
+
+group = rootProject.property("mod.group").toString()
+version = rootProject.property("mod.version").toString()
+
+repositories {
+    mavenCentral()
+    maven("https://hub.spigotmc.org/nexus/content/repositories/snapshots/")
+    maven("https://oss.sonatype.org/content/repositories/snapshots/")
+}
+
+dependencies {
+    compileOnly("org.spigotmc:spigot-api:${property("spigot.minecraft")}-R0.1-SNAPSHOT")
+}
+
+java {
+    toolchain.languageVersion = JavaLanguageVersion.of(21)
+}
+
+tasks.processResources {
+    val props = mapOf("version" to project.version)
+    inputs.properties(props)
+    filesMatching("plugin.yml") {
+        expand(props)
+    }
+}
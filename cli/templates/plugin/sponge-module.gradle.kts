@@ -0,0 +1,40 @@
+// Sponge plugin built alongside the mod. SpongeGradle provides the SpongeAPI
+// dependency and a runServer task; the plugin metadata lives in
+// src/main/resources/META-INF/sponge_plugins.json.
+import org.spongepowered.gradle.plugin.config.PluginLoaders
+
+plugins {
+    `java-library`
+{{#kotlin}}
+    kotlin("jvm")
+{{/kotlin}}
+    id("org.spongepowered.gradle.plugin") version "2.3.0"
+}
+
+group = rootProject.property("mod.group").toString()
+version = rootProject.property("mod.version").toString()
+
+repositories {
+    mavenCentral()
+}
+
+sponge {
+    apiVersion(property("sponge.api").toString())
+    license("MIT")
+    loader {
+        name(PluginLoaders.JAVA_PLAIN)
+        version("1.0")
+    }
+}
+
+java {
+    toolchain.languageVersion = JavaLanguageVersion.of(21)
+}
+
+tasks.processResources {
+    val props = mapOf("version" to project.version)
+    inputs.properties(props)
+    filesMatching("META-INF/sponge_plugins.json") {
+        expand(props)
+    }
+}
@@ -0,0 +1,38 @@
+// Velocity proxy plugin built alongside the mod. The plugin metadata lives in
+// src/main/resources/velocity-plugin.json.
+plugins {
+    java
+{{#kotlin}}
+    kotlin("jvm")
+{{/kotlin}}
+    id("xyz.jpenilla.run-velocity") version "2.3.1"
+}
+
+group = rootProject.property("mod.group").toString()
+version = rootProject.property("mod.version").toString()
+
+repositories {
+    mavenCentral()
+    maven("https://repo.papermc.io/repository/maven-public/")
+}
+
+dependencies {
+    compileOnly("com.velocitypowered:velocity-api:${property("velocity.api")}")
+}
+
+java {
+    toolchain.languageVersion = JavaLanguageVersion.of(21)
+}
+
+tasks.processResources {
+    val props = mapOf("version" to project.version)
+    inputs.properties(props)
+    filesMatching("velocity-plugin.json") {
+        expand(props)
+    }
+}
+
+// ./gradlew :velocity:runVelocity starts a Velocity proxy with the plugin installed
+tasks.runVelocity {
+    velocityVersion(property("velocity.api").toString())
+}
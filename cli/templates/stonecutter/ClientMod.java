@@ -0,0 +1,25 @@
+package {{package}};
+
+/*? if {{fabric_condition}} {*/
+import net.fabricmc.api.ClientModInitializer;
+/*?}*/
+
+/**
+ * Client-only setup: key bindings, renderers, screens. Only the client loads
+ * this class, so it can use classes that don't exist on a dedicated server.
+ */
+/*? if {{fabric_condition}} {*/
+public class {{client_entrypoint}} implements ClientModInitializer {
+    @Override
+    public void onInitializeClient() {
+        init();
+    }
+/*?} else {*/
+/*public class {{client_entrypoint}} {
+*//*?}*/
+
+    /** Call from the loader's client setup event on loaders without a client entrypoint. */
+    public static void init() {
+        {{class_name}}.LOGGER.info("Initializing {{mod_name}} client");
+    }
+}
@@ -0,0 +1,26 @@
+package {{package}}
+
+/*? if {{fabric_condition}} {*/
+import net.fabricmc.api.ClientModInitializer
+/*?}*/
+
+/**
+ * Client-only setup: key bindings, renderers, screens. Only the client loads
+ * this class, so it can use classes that don't exist on a dedicated server.
+ */
+/*? if {{fabric_condition}} {*/
+class {{client_entrypoint}} : ClientModInitializer {
+    override fun onInitializeClient() {
+        init()
+    }
+/*?} else {*/
+/*class {{client_entrypoint}} {
+*//*?}*/
+
+    companion object {
+        /** Call from the loader's client setup event on loaders without a client entrypoint. */
+        fun init() {
+            {{class_name}}.LOGGER.info("Initializing {{mod_name}} client")
+        }
+    }
+}
@@ -0,0 +1,18 @@
+package {{package}};
+
+/*? if fabric {*/
+import net.fabricmc.fabric.api.datagen.v1.DataGeneratorEntrypoint;
+import net.fabricmc.fabric.api.datagen.v1.FabricDataGenerator;
+/*?}*/
+
+// Data generation entrypoint, run by ./gradlew runDatagen on Fabric builds.
+// Register providers (recipes, loot tables, tags, models) on the pack; the
+// output goes to src/main/generated.
+/*? if fabric {*/
+public class {{datagen_entrypoint}} implements DataGeneratorEntrypoint {
+    @Override
+    public void onInitializeDataGenerator(FabricDataGenerator generator) {
+        FabricDataGenerator.Pack pack = generator.createPack();
+    }
+}
+/*?}*/
@@ -0,0 +1,17 @@
+package {{package}}
+
+/*? if fabric {*/
+import net.fabricmc.fabric.api.datagen.v1.DataGeneratorEntrypoint
+import net.fabricmc.fabric.api.datagen.v1.FabricDataGenerator
+/*?}*/
+
+// Data generation entrypoint, run by ./gradlew runDatagen on Fabric builds.
+// Register providers (recipes, loot tables, tags, models) on the pack; the
+// output goes to src/main/generated.
+/*? if fabric {*/
+class {{datagen_entrypoint}} : DataGeneratorEntrypoint {
+    override fun onInitializeDataGenerator(generator: FabricDataGenerator) {
+        val pack = generator.createPack()
+    }
+}
+/*?}*/
@@ -0,0 +1,16 @@
+package {{package}};
+
+/**
+ * Public API for {{mod_name}}.
+ *
+ * <p>This is a library mod: it has no initializer and adds no gameplay on its own.
+ * Other mods depend on it and call into the classes in this package.
+ */
+public final class {{class_name}} {
+    public static final String MOD_ID = "{{mod_id}}";
+{{#build_info}}
+    public static final String VERSION = BuildConstants.VERSION;
+{{/build_info}}
+
+    private {{class_name}}() {}
+}
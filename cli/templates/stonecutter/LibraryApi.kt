@@ -0,0 +1,14 @@
+package {{package}}
+
+/**
+ * Public API for {{mod_name}}.
+ *
+ * This is a library mod: it has no initializer and adds no gameplay on its own.
+ * Other mods depend on it and call into the declarations in this package.
+ */
+object {{class_name}} {
+    const val MOD_ID = "{{mod_id}}"
+{{#build_info}}
+    const val VERSION = BuildConstants.VERSION
+{{/build_info}}
+}
@@ -0,0 +1,44 @@
+package {{package}}.platform;
+
+import dev.architectury.platform.Platform;
+
+import java.nio.file.Path;
+
+/**
+ * Loader-specific helpers for {{mod_name}}.
+ *
+ * <p>Architectury projects split into common/fabric/neoforge modules declare these with
+ * {@code @ExpectPlatform}. This project builds every loader from one source set, so
+ * Stonecutter swaps the loader-specific bodies instead, and Architectury's {@link Platform}
+ * answers everything that is the same on every loader.
+ */
+public final class PlatformHelper {
+    private PlatformHelper() {
+    }
+
+    /** Name of the loader this jar was built for. */
+    public static String loaderName() {
+        /*? if neoforge {*/
+        /*return "NeoForge";
+{{#quilt}}
+        *//*?} elif quilt {*/
+        /*return "Quilt";
+{{/quilt}}
+        *//*?} else {*/
+        return "Fabric";
+        /*?}*/
+    }
+
+    /** The loader's config directory, e.g. for {@code {{mod_id}}.json}. */
+    public static Path configDir() {
+        return Platform.getConfigFolder();
+    }
+
+    public static boolean isModLoaded(String modId) {
+        return Platform.isModLoaded(modId);
+    }
+
+    public static boolean isDevelopmentEnvironment() {
+        return Platform.isDevelopmentEnvironment();
+    }
+}
@@ -0,0 +1,16 @@
+package {{package}};
+
+/*? if fabric {*/
+import net.fabricmc.loader.api.entrypoint.PreLaunchEntrypoint;
+/*?}*/
+
+// Runs before Minecraft classes are loaded, on Fabric builds. Referencing game
+// classes here loads them too early; keep it to setup like config or logging.
+/*? if fabric {*/
+public class {{prelaunch_entrypoint}} implements PreLaunchEntrypoint {
+    @Override
+    public void onPreLaunch() {
+        {{class_name}}.LOGGER.info("Pre-launch {{mod_name}}");
+    }
+}
+/*?}*/
@@ -0,0 +1,15 @@
+package {{package}}
+
+/*? if fabric {*/
+import net.fabricmc.loader.api.entrypoint.PreLaunchEntrypoint
+/*?}*/
+
+// Runs before Minecraft classes are loaded, on Fabric builds. Referencing game
+// classes here loads them too early; keep it to setup like config or logging.
+/*? if fabric {*/
+class {{prelaunch_entrypoint}} : PreLaunchEntrypoint {
+    override fun onPreLaunch() {
+        {{class_name}}.LOGGER.info("Pre-launch {{mod_name}}")
+    }
+}
+/*?}*/
@@ -3,14 +3,21 @@ package {{package}};
 import org.slf4j.Logger;
 import org.slf4j.LoggerFactory;
 
-/*? if fabric {*/
-import net.fabricmc.api.ModInitializer;
+/*? if {{fabric_condition}} {*/
+import net.fabricmc.api.{{fabric_initializer}};
 /*?}*/
 
 /*? if neoforge {*/
 /*import net.neoforged.bus.api.IEventBus;
 import net.neoforged.fml.common.Mod;
 *//*?}*/
+{{#forge}}
+
+/*? if forge {*/
+/*import net.minecraftforge.fml.common.Mod;
+import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext;
+*//*?}*/
+{{/forge}}
 
 /*? if neoforge {*/
 /*@Mod({{class_name}}.MOD_ID)
@@ -18,10 +25,18 @@ public class {{class_name}} {
     public {{class_name}}(IEventBus modEventBus) {
         init();
     }
-*//*?} elif fabric {*/
-public class {{class_name}} implements ModInitializer {
+{{#forge}}
+*//*?} elif forge {*/
+/*@Mod({{class_name}}.MOD_ID)
+public class {{class_name}} {
+    public {{class_name}}(FMLJavaModLoadingContext context) {
+        init();
+    }
+{{/forge}}
+*//*?} elif {{fabric_condition}} {*/
+public class {{class_name}} implements {{fabric_initializer}} {
     @Override
-    public void onInitialize() {
+    public void {{fabric_init_method}}() {
         init();
     }
 /*?}*/
@@ -31,5 +46,8 @@ public class {{class_name}} implements ModInitializer {
 
     public static void init() {
         LOGGER.info("Initializing {{mod_name}}");
+{{#build_info}}
+        LOGGER.info("Build {} ({}, {})", BuildConstants.VERSION, BuildConstants.GIT_COMMIT, BuildConstants.BUILD_DATE);
+{{/build_info}}
     }
 }
@@ -2,14 +2,21 @@ package {{package}}
 
 import org.slf4j.LoggerFactory
 
-/*? if fabric {*/
-import net.fabricmc.api.ModInitializer
+/*? if {{fabric_condition}} {*/
+import net.fabricmc.api.{{fabric_initializer}}
 /*?}*/
 
 /*? if neoforge {*/
 /*import net.neoforged.bus.api.IEventBus
 import net.neoforged.fml.common.Mod
 *//*?}*/
+{{#forge}}
+
+/*? if forge {*/
+/*import net.minecraftforge.fml.common.Mod
+import net.minecraftforge.fml.javafmlmod.FMLJavaModLoadingContext
+*//*?}*/
+{{/forge}}
 
 /*? if neoforge {*/
 /*@Mod({{class_name}}.MOD_ID)
@@ -17,9 +24,17 @@ class {{class_name}}(modEventBus: IEventBus) {
     init {
         Companion.init()
     }
-*//*?} elif fabric {*/
-class {{class_name}} : ModInitializer {
-    override fun onInitialize() {
+{{#forge}}
+*//*?} elif forge {*/
+/*@Mod({{class_name}}.MOD_ID)
+class {{class_name}}(context: FMLJavaModLoadingContext) {
+    init {
+        Companion.init()
+    }
+{{/forge}}
+*//*?} elif {{fabric_condition}} {*/
+class {{class_name}} : {{fabric_initializer}} {
+    override fun {{fabric_init_method}}() {
         init()
     }
 /*?}*/
@@ -30,6 +45,9 @@ class {{class_name}} : ModInitializer {
 
         fun init() {
             LOGGER.info("Initializing {{mod_name}}")
+{{#build_info}}
+            LOGGER.info("Build {} ({}, {})", BuildConstants.VERSION, BuildConstants.GIT_COMMIT, BuildConstants.BUILD_DATE)
+{{/build_info}}
         }
     }
 }
@@ -0,0 +1,11 @@
+
+// Architectury API: cross-loader events, networking, registries and Platform helpers
+repositories {
+    maven("https://maven.architectury.dev/")
+}
+
+dependencies {
+    // Quilt runs the Fabric build of Architectury
+    val platform = if (stonecutter.current.project.endsWith("neoforge")) "neoforge" else "fabric"
+    modImplementation("dev.architectury:architectury-$platform:${property("architectury_api_version")}")
+}
@@ -5,6 +5,9 @@ plugins {
 {{#kotlin}}
     kotlin("jvm")
 {{/kotlin}}
+{{#library}}
+    `maven-publish`
+{{/library}}
 }
 
 modSettings {
@@ -12,3 +15,72 @@ modSettings {
         narrator = false
     }
 }
+{{#client_only}}
+
+// Client-only mod: no dedicated-server run configuration
+loom {
+    runConfigs.named("server") {
+        ideConfigGenerated(false)
+    }
+}
+{{/client_only}}
+{{#split_environment}}
+
+// Client-only code lives in src/client. Fabric builds compile it as Loom's
+// separate client source set; the other loaders fold it into main.
+if (stonecutter.current.project.endsWith("fabric")) {
+    loom {
+        splitEnvironmentSourceSets()
+        mods {
+            register(property("mod.id").toString()) {
+                sourceSet(sourceSets.main.get())
+                sourceSet(sourceSets["client"])
+            }
+        }
+    }
+} else {
+    sourceSets.main {
+        java.srcDir("src/client/java")
+{{#kotlin}}
+        kotlin.srcDir("src/client/kotlin")
+{{/kotlin}}
+        resources.srcDir("src/client/resources")
+    }
+}
+{{/split_environment}}
+{{#datagen}}
+
+// Data generation: ./gradlew runDatagen (Fabric builds) runs the fabric-datagen
+// entrypoint and writes its output to src/main/generated, packaged as resources
+if (stonecutter.current.project.endsWith("fabric")) {
+    fabricApi {
+        configureDataGeneration()
+    }
+}
+{{/datagen}}
+{{#library}}
+
+// Library: publish the API jar with sources and javadoc so other mods can depend on it
+java {
+    withSourcesJar()
+    withJavadocJar()
+}
+
+publishing {
+    publications {
+        create<MavenPublication>("mavenJava") {
+            groupId = property("mod.group").toString()
+            artifactId = "${property("mod.id")}-${stonecutter.current.project}"
+            version = property("mod.version").toString()
+            from(components["java"])
+        }
+    }
+    repositories {
+        // ./gradlew publish writes here; add your own Maven repository alongside it
+        maven {
+            name = "projectRepo"
+            url = uri(rootProject.layout.buildDirectory.dir("repo"))
+        }
+    }
+}
+{{/library}}
@@ -0,0 +1,38 @@
+
+// Build info: generates {{package}}.BuildConstants at compile time
+val generateBuildConstants by tasks.registering {
+    val outputDir = layout.buildDirectory.dir("generated/sources/buildConstants/java")
+    val modVersion = providers.gradleProperty("mod.version").orElse("unknown")
+    val minecraftVersion = stonecutter.current.version
+    val gitCommit = providers.exec {
+        commandLine("git", "rev-parse", "--short", "HEAD")
+        isIgnoreExitValue = true
+    }.standardOutput.asText.map { it.trim().ifEmpty { "unknown" } }.orElse("unknown")
+
+    outputs.dir(outputDir)
+    outputs.upToDateWhen { false }
+
+    doLast {
+        val file = outputDir.get().file("{{package_path}}/BuildConstants.java").asFile
+        file.parentFile.mkdirs()
+        file.writeText(
+            """
+            |package {{package}};
+            |
+            |/** Build metadata generated by Gradle. Do not edit. */
+            |public final class BuildConstants {
+            |    public static final String VERSION = "${modVersion.get()}";
+            |    public static final String MINECRAFT_VERSION = "$minecraftVersion";
+            |    public static final String GIT_COMMIT = "${gitCommit.get()}";
+            |    public static final String BUILD_DATE = "${java.time.Instant.now()}";
+            |
+            |    private BuildConstants() {}
+            |}
+            |""".trimMargin()
+        )
+    }
+}
+
+sourceSets.main {
+    java.srcDir(generateBuildConstants)
+}
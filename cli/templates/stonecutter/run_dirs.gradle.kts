@@ -0,0 +1,6 @@
+
+// Per-loader run directories: each loader's dev client and server run in
+// run/<loader> so worlds, options.txt and extra mods stay apart
+modSettings {
+    runDirectory = rootProject.layout.projectDirectory.dir("run/" + stonecutter.current.project.substringAfterLast('-'))
+}
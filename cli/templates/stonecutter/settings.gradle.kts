@@ -5,6 +5,12 @@ pluginManagement {
         maven("https://maven.fabricmc.net/")
         maven("https://maven.architectury.dev/")
         maven("https://maven.neoforged.net/releases/")
+{{#quilt}}
+        maven("https://maven.quiltmc.org/repository/release/")
+{{/quilt}}
+{{#paper}}
+        maven("https://repo.papermc.io/repository/maven-public/")
+{{/paper}}
         maven("https://maven.kikugie.dev/snapshots")
     }
 }
@@ -28,3 +34,19 @@ stonecutter {
 }
 
 rootProject.name = "{{mod_id}}"
+{{#paper}}
+
+include("paper")
+{{/paper}}
+{{#sponge}}
+
+include("sponge")
+{{/sponge}}
+{{#velocity}}
+
+include("velocity")
+{{/velocity}}
+{{#spigot}}
+
+include("spigot")
+{{/spigot}}
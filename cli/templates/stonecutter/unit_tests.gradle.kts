@@ -0,0 +1,15 @@
+
+// Unit tests: JUnit 5 on every target. Fabric builds also get fabric-loader-junit,
+// which runs tests under Fabric Loader so Minecraft classes can be loaded
+dependencies {
+    testImplementation(platform("org.junit:junit-bom:{{junit_version}}"))
+    testImplementation("org.junit.jupiter:junit-jupiter")
+    testRuntimeOnly("org.junit.platform:junit-platform-launcher")
+    if (stonecutter.current.project.endsWith("fabric")) {
+        testImplementation("net.fabricmc:fabric-loader-junit:${property("loader_version")}")
+    }
+}
+
+tasks.test {
+    useJUnitPlatform()
+}
@@ -29,6 +29,11 @@ main() {
 
     asset="${BINARY_NAME}-${platform}-${arch}"
 
+    # musl-based distros (Alpine, Void musl) need the statically linked build
+    if [ "$platform" = "linux" ] && ldd --version 2>&1 | grep -qi musl; then
+        asset="${asset}-musl"
+    fi
+
     echo "Detecting platform... ${platform}/${arch}"
 
     # Fetch latest release tag